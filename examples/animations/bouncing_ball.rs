@@ -24,6 +24,7 @@
 #![allow(clippy::cast_possible_wrap)]
 
 use dotmax::animation::AnimationLoop;
+use dotmax::motion::{Body, Bounds, Vec2};
 use dotmax::primitives::draw_circle_filled;
 
 /// Terminal dimensions in cells
@@ -31,34 +32,24 @@ const WIDTH: usize = 80;
 const HEIGHT: usize = 24;
 
 /// Physics constants
-const GRAVITY: f64 = 0.3; // Gravity acceleration (dots per frame^2)
-const BOUNCE_DAMPING: f64 = 0.85; // Energy retained after bounce (0.0-1.0)
-const INITIAL_VX: f64 = 2.5; // Initial horizontal velocity
-const INITIAL_VY: f64 = 0.0; // Initial vertical velocity (starts at rest)
+const GRAVITY: Vec2 = Vec2::new(0.0, 0.3); // Gravity acceleration (dots per frame^2)
+const BOUNCE_DAMPING: f32 = 0.85; // Energy retained after bounce (0.0-1.0)
+const INITIAL_VELOCITY: Vec2 = Vec2::new(2.5, 0.0); // Starts at rest vertically
 
 /// Ball state for physics simulation
 struct Ball {
-    /// X position in dot coordinates
-    x: f64,
-    /// Y position in dot coordinates
-    y: f64,
-    /// Horizontal velocity (dots per frame)
-    vx: f64,
-    /// Vertical velocity (dots per frame)
-    vy: f64,
+    /// Position and velocity in dot coordinates (dots per frame)
+    body: Body,
     /// Ball radius in dots
     radius: u32,
 }
 
 impl Ball {
     /// Create a new ball at the center-top of the screen
-    const fn new(width: usize, _height: usize) -> Self {
+    fn new(width: usize, _height: usize) -> Self {
         Self {
-            // Start near top-center
-            x: (width * 2 / 2) as f64, // Center horizontally (dot coordinates)
-            y: 20.0,                   // Near top
-            vx: INITIAL_VX,
-            vy: INITIAL_VY,
+            // Start near top-center (dot coordinates)
+            body: Body::new(Vec2::new(width as f32, 20.0), INITIAL_VELOCITY),
             radius: 6,
         }
     }
@@ -66,62 +57,34 @@ impl Ball {
     /// Update ball physics for one frame
     ///
     /// # Physics Calculations
-    /// 1. Apply gravity to vertical velocity: vy += GRAVITY
-    /// 2. Update position: x += vx, y += vy
-    /// 3. Bounce off walls with damping
-    fn update(&mut self, max_x: f64, max_y: f64) {
-        // Apply gravity to vertical velocity
-        // Gravity accelerates the ball downward each frame
-        self.vy += GRAVITY;
-
-        // Update position based on velocity
-        self.x += self.vx;
-        self.y += self.vy;
-
-        // Bounce off horizontal walls (left/right)
-        let radius_f64 = f64::from(self.radius);
-        let min_x = radius_f64;
-        let max_bound_x = max_x - radius_f64;
-
-        if self.x <= min_x {
-            self.x = min_x;
-            self.vx = -self.vx * BOUNCE_DAMPING;
-        } else if self.x >= max_bound_x {
-            self.x = max_bound_x;
-            self.vx = -self.vx * BOUNCE_DAMPING;
-        }
-
-        // Bounce off vertical walls (top/bottom)
-        let min_y = radius_f64;
-        let max_bound_y = max_y - radius_f64;
-
-        if self.y <= min_y {
-            self.y = min_y;
-            self.vy = -self.vy * BOUNCE_DAMPING;
-        } else if self.y >= max_bound_y {
-            self.y = max_bound_y;
-            // Bounce with damping - energy is lost on each bounce
-            self.vy = -self.vy * BOUNCE_DAMPING;
+    /// 1. Apply gravity to the velocity, then move by it
+    /// 2. Bounce off walls with damping, keeping the whole ball on screen
+    fn update(&mut self, bounds: Bounds) {
+        self.body.integrate(GRAVITY, 1.0);
+
+        // Energy is lost on each bounce
+        let contact = self
+            .body
+            .bounce(bounds.shrink(self.radius as f32), BOUNCE_DAMPING);
+        if contact.bottom {
             // Also apply horizontal damping on floor bounce (friction)
-            self.vx *= 0.98;
+            self.body.velocity.x *= 0.98;
         }
     }
 
     /// Draw the ball on the grid as a filled circle
     fn draw(&self, grid: &mut dotmax::BrailleGrid) {
-        let _ = draw_circle_filled(
-            grid,
-            self.x as i32,
-            self.y as i32,
-            self.radius,
-        );
+        let (x, y) = self.body.dot();
+        let _ = draw_circle_filled(grid, x, y, self.radius);
     }
 }
 
 fn main() -> Result<(), dotmax::DotmaxError> {
-    // Calculate dot dimensions (each cell is 2×4 dots)
-    let dot_width = (WIDTH * 2) as f64;
-    let dot_height = (HEIGHT * 4) as f64;
+    // Screen bounds in dots (each cell is 2×4 dots)
+    let bounds = Bounds::new(
+        Vec2::ZERO,
+        Vec2::new((WIDTH * 2) as f32, (HEIGHT * 4) as f32),
+    );
 
     // Initialize ball with screen dimensions
     let mut ball = Ball::new(WIDTH, HEIGHT);
@@ -136,7 +99,7 @@ fn main() -> Result<(), dotmax::DotmaxError> {
         .fps(60) // Target 60 FPS for smooth motion
         .on_frame(move |frame, buffer| {
             // Update physics
-            ball.update(dot_width, dot_height);
+            ball.update(bounds);
 
             // Draw ball
             ball.draw(buffer);
@@ -0,0 +1,101 @@
+//! Generator for animated WebP test fixtures
+//!
+//! Run this to generate test WebP files:
+//! ```bash
+//! cargo run --features image --example generate_webp_fixtures
+//! ```
+//!
+//! This creates:
+//! - animated.webp - 3-frame animation (10x10), infinite loop, 100ms delay
+//! - loop_twice.webp - 2-frame animation with a loop count of 2
+//!
+//! The encoder only writes still images, so each frame is encoded as a
+//! lossless still and its `VP8L` chunk wrapped in an `ANMF` chunk.
+
+use std::path::Path;
+
+use image_webp::{ColorType, WebPEncoder};
+
+fn main() {
+    let fixtures_dir = Path::new("tests/fixtures/media");
+    std::fs::create_dir_all(fixtures_dir).unwrap();
+
+    // Generate animated.webp - 3 frames, infinite loop
+    generate_animated_webp(&fixtures_dir.join("animated.webp"), 10, 10, 3, 0, 100);
+    println!("Generated: animated.webp");
+
+    // Generate loop_twice.webp - 2 frames, loop 2 times
+    generate_animated_webp(&fixtures_dir.join("loop_twice.webp"), 10, 10, 2, 2, 100);
+    println!("Generated: loop_twice.webp");
+}
+
+fn generate_animated_webp(
+    path: &Path,
+    width: u32,
+    height: u32,
+    frames: u32,
+    loop_count: u16,
+    delay_ms: u32,
+) {
+    // VP8X: animation + alpha flags, then the canvas size minus one
+    let mut vp8x = vec![0x12, 0, 0, 0];
+    vp8x.extend_from_slice(&u24(width - 1));
+    vp8x.extend_from_slice(&u24(height - 1));
+
+    // ANIM: transparent background, then the loop count (0 = forever)
+    let mut anim = vec![0, 0, 0, 0];
+    anim.extend_from_slice(&loop_count.to_le_bytes());
+
+    let mut body = b"WEBP".to_vec();
+    push_chunk(&mut body, b"VP8X", &vp8x);
+    push_chunk(&mut body, b"ANIM", &anim);
+
+    for i in 0..frames {
+        // Each frame lights a different vertical band
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                if x * frames / width == i {
+                    let offset = ((y * width + x) * 4) as usize;
+                    pixels[offset..offset + 4].copy_from_slice(&[255, 255, 255, 255]);
+                }
+            }
+        }
+
+        let mut still = Vec::new();
+        WebPEncoder::new(&mut still)
+            .encode(&pixels, width, height, ColorType::Rgba8)
+            .unwrap();
+
+        // Frame at the origin covering the canvas, no blending
+        let mut anmf = Vec::new();
+        anmf.extend_from_slice(&u24(0));
+        anmf.extend_from_slice(&u24(0));
+        anmf.extend_from_slice(&u24(width - 1));
+        anmf.extend_from_slice(&u24(height - 1));
+        anmf.extend_from_slice(&u24(delay_ms));
+        anmf.push(0x02);
+        // Skip the still's 12-byte RIFF header, keeping its VP8L chunk
+        anmf.extend_from_slice(&still[12..]);
+        push_chunk(&mut body, b"ANMF", &anmf);
+    }
+
+    let mut file = b"RIFF".to_vec();
+    file.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    file.extend_from_slice(&body);
+    std::fs::write(path, file).unwrap();
+}
+
+fn push_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+fn u24(value: u32) -> [u8; 3] {
+    let [a, b, c, _] = value.to_le_bytes();
+    [a, b, c]
+}
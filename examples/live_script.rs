@@ -0,0 +1,29 @@
+//! Live-code visuals with a Rhai script.
+//!
+//! The script must define `fn draw(t)`; it is called every frame with the
+//! elapsed time in seconds. Save the file while this runs to reload it.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --features script --example live_script -- examples/scripts/orbit.rhai
+//! ```
+//!
+//! Press 'q' or Ctrl+C to exit.
+
+use dotmax::script::LiveScript;
+use dotmax::Result;
+use std::env;
+
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/scripts/orbit.rhai".to_string());
+
+    let (width, height) = crossterm::terminal::size().map_or((80, 24), |(w, h)| {
+        (w as usize, h.saturating_sub(1) as usize)
+    });
+
+    let mut script = LiveScript::load(&path)?;
+    script.run(width, height, 30)
+}
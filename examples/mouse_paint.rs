@@ -0,0 +1,111 @@
+//! Paint with the mouse, or steer a brush with the keyboard.
+//!
+//! Drag with the left button to draw and with the right button to erase;
+//! the arrow keys (or WASD) move a keyboard brush that paints with space.
+//! `c` clears the canvas and `esc` quits.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --example mouse_paint
+//! ```
+
+use crossterm::event::MouseButton;
+use dotmax::animation::AnimationLoop;
+use dotmax::input::actions::ActionMap;
+use dotmax::input::mouse::{Gesture, Mouse};
+use dotmax::primitives::{draw_circle, draw_line};
+use dotmax::{BrailleGrid, Result};
+use std::cell::{Cell, RefCell};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Paint,
+    Clear,
+    Quit,
+}
+
+fn main() -> Result<()> {
+    let (width, height) = crossterm::terminal::size()?;
+    let (width, height) = (usize::from(width), usize::from(height));
+    let canvas = RefCell::new(BrailleGrid::new(width, height)?);
+    let brush = Cell::new((width, height * 2));
+    let mut mouse = Mouse::new();
+    let actions = ActionMap::new()
+        .with("up", Action::Up)?
+        .with("w", Action::Up)?
+        .with("down", Action::Down)?
+        .with("s", Action::Down)?
+        .with("left", Action::Left)?
+        .with("a", Action::Left)?
+        .with("right", Action::Right)?
+        .with("d", Action::Right)?
+        .with("space", Action::Paint)?
+        .with("c", Action::Clear)?
+        .with("esc", Action::Quit)?;
+
+    AnimationLoop::new(width, height)
+        .fps(30)
+        .on_frame(|_, buffer| {
+            buffer.union(&canvas.borrow());
+            let (x, y) = brush.get();
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            draw_circle(buffer, x as i32, y as i32, 2)?;
+            Ok(true)
+        })
+        .on_event(|event| {
+            let mut canvas = canvas.borrow_mut();
+            let (x, y) = brush.get();
+            match actions.action_for(event) {
+                Some(Action::Up) => brush.set((x, y.saturating_sub(1))),
+                Some(Action::Down) => brush.set((x, (y + 1).min(canvas.dot_height() - 1))),
+                Some(Action::Left) => brush.set((x.saturating_sub(1), y)),
+                Some(Action::Right) => brush.set(((x + 1).min(canvas.dot_width() - 1), y)),
+                Some(Action::Paint) => canvas.set_dot(x, y)?,
+                Some(Action::Clear) => canvas.clear(),
+                Some(Action::Quit) => return Ok(false),
+                None => {}
+            }
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            match mouse.update(event) {
+                Some(Gesture::Press { button, x, y } | Gesture::Click { button, x, y }) => {
+                    paint(&mut canvas, button, x, y)?;
+                }
+                Some(Gesture::Drag {
+                    button, from, to, ..
+                }) => {
+                    if button == MouseButton::Left {
+                        draw_line(
+                            &mut canvas,
+                            from.0 as i32,
+                            from.1 as i32,
+                            to.0 as i32,
+                            to.1 as i32,
+                        )?;
+                    } else {
+                        paint(&mut canvas, button, to.0, to.1)?;
+                    }
+                }
+                _ => {}
+            }
+            Ok(true)
+        })
+        .run()
+}
+
+/// Sets the dot at (`x`, `y`) with the left button and clears the cell
+/// under it with any other. Points off the canvas are ignored.
+fn paint(canvas: &mut BrailleGrid, button: MouseButton, x: usize, y: usize) -> Result<()> {
+    if x >= canvas.dot_width() || y >= canvas.dot_height() {
+        Ok(())
+    } else if button == MouseButton::Left {
+        canvas.set_dot(x, y)
+    } else {
+        canvas.clear_region(x / 2, y / 4, 1, 1)
+    }
+}
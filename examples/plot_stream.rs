@@ -0,0 +1,24 @@
+//! Chart numbers piped in on stdin, live, as they arrive.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # One series
+//! for i in $(seq 200); do echo "s($i / 10)" | bc -l; sleep 0.05; done \
+//!     | cargo run --example plot_stream
+//!
+//! # Two series, one per column
+//! vmstat 1 | awk '{ print $13, $14; fflush() }' | cargo run --example plot_stream
+//!
+//! # A CSV file, all at once
+//! cargo run --example plot_stream < data.csv
+//! ```
+//!
+//! Press 'q', Esc, or Ctrl+C to stop early. Once the input ends, any key
+//! exits and the final chart is left on screen.
+
+use dotmax::{quick, Result};
+
+fn main() -> Result<()> {
+    quick::plot_stdin()
+}
@@ -0,0 +1,40 @@
+//! Record an animation to an asciinema cast without a terminal.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --example record_cast -- orbit.cast
+//! asciinema play orbit.cast
+//!
+//! # Any other extension writes raw ANSI, which `cat` replays at once
+//! cargo run --example record_cast -- orbit.ans && cat orbit.ans
+//! ```
+
+use dotmax::primitives::draw_circle;
+use dotmax::render::recording::Recorder;
+use dotmax::{BrailleGrid, Color, Result};
+use std::time::Duration;
+
+const FRAMES: u32 = 120;
+const FRAME_TIME: Duration = Duration::from_millis(33);
+
+fn main() -> Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "orbit.cast".to_string());
+    let mut recorder = Recorder::create(&path)?.title("dotmax orbit");
+
+    for frame in 0..FRAMES {
+        let mut grid = BrailleGrid::new(40, 12)?;
+        draw_circle(&mut grid, 40, 24, 20)?;
+        let angle = f64::from(frame) / f64::from(FRAMES) * std::f64::consts::TAU;
+        let x = 20.0f64.mul_add(angle.cos(), 40.0).round() as i32;
+        let y = 20.0f64.mul_add(angle.sin(), 24.0).round() as i32;
+        draw_circle(&mut grid, x, y, 3)?;
+        grid.set_cell_color((x / 2) as usize, (y / 4) as usize, Color::rgb(255, 200, 0))?;
+        recorder.record_at(&grid, FRAME_TIME * frame)?;
+    }
+    recorder.finish()?;
+    println!("Wrote {FRAMES} frames to {path}");
+    Ok(())
+}
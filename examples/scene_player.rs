@@ -0,0 +1,22 @@
+//! Play a declarative scene file (TOML or JSON).
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --features scene --example scene_player -- examples/scenes/pulse.toml
+//! ```
+//!
+//! Press 'q' or Ctrl+C to exit.
+
+use dotmax::scene::Scene;
+use dotmax::Result;
+use std::env;
+
+fn main() -> Result<()> {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/scenes/pulse.toml".to_string());
+
+    let scene = Scene::load(&path)?;
+    scene.play()
+}
@@ -0,0 +1,94 @@
+//! Mirror a monitor or window into the terminal.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Primary monitor
+//! cargo run --example screen_mirror --features screen-capture
+//!
+//! # List monitors and windows, then pick one
+//! cargo run --example screen_mirror --features screen-capture -- --list
+//! cargo run --example screen_mirror --features screen-capture -- --window 41943046
+//! cargo run --example screen_mirror --features screen-capture -- --monitor 1 --color
+//! ```
+//!
+//! Press any key to exit.
+
+use std::env;
+use std::io::stdout;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute};
+use dotmax::image::ColorMode;
+use dotmax::media::{list_capture_sources, CaptureSource, MediaPlayer, ScreenCapturePlayer};
+use dotmax::TerminalRenderer;
+
+fn main() -> dotmax::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let id_after = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .and_then(|i| args.get(i + 1))
+            .and_then(|id| id.parse().ok())
+    };
+
+    if args.iter().any(|arg| arg == "--list") {
+        for info in list_capture_sources()? {
+            println!(
+                "{:<22} {}x{}  {}",
+                info.source.to_string(),
+                info.width,
+                info.height,
+                info.name
+            );
+        }
+        return Ok(());
+    }
+
+    let source = match (id_after("--monitor"), id_after("--window")) {
+        (Some(id), _) => CaptureSource::Monitor(id),
+        (None, Some(id)) => CaptureSource::Window(id),
+        (None, None) => CaptureSource::PrimaryMonitor,
+    };
+    let mut builder = ScreenCapturePlayer::builder().source(source).fps(15);
+    if args.iter().any(|arg| arg == "--color") {
+        builder = builder.color_mode(ColorMode::TrueColor);
+    }
+    let mut player = builder.build()?;
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+    let result = mirror(&mut player);
+    execute!(stdout(), cursor::Show, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn mirror(player: &mut ScreenCapturePlayer) -> dotmax::Result<()> {
+    let mut renderer = TerminalRenderer::new()?;
+    while let Some(frame) = player.next_frame() {
+        let (grid, delay) = frame?;
+        renderer.render(&grid)?;
+
+        let deadline = Instant::now() + delay;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if !event::poll(timeout.max(Duration::from_millis(1)))? {
+                break;
+            }
+            match event::read()? {
+                Event::Key(key) if !matches!(key.code, KeyCode::Modifier(_)) => return Ok(()),
+                Event::Resize(width, height) => {
+                    player.handle_resize(width as usize, height as usize);
+                }
+                _ => {}
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! A `top`-style dashboard of CPU, memory, and network use.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --example system_monitor --features sysinfo
+//! ```
+//!
+//! Press any key to exit.
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode};
+use dotmax::animation::FrameSource;
+use dotmax::system::SystemMonitor;
+use dotmax::TerminalRenderer;
+
+fn main() -> dotmax::Result<()> {
+    let mut renderer = TerminalRenderer::new()?;
+    let result = run(&mut renderer, &mut SystemMonitor::new());
+    renderer.cleanup()?;
+    result
+}
+
+fn run(renderer: &mut TerminalRenderer, monitor: &mut SystemMonitor) -> dotmax::Result<()> {
+    loop {
+        let (width, height) = renderer.get_terminal_size()?;
+        let Some(frame) = monitor.next_frame(width as usize, height as usize) else {
+            return Ok(());
+        };
+        let (grid, delay) = frame?;
+        renderer.render(&grid)?;
+
+        // Wait out the interval, redrawing early on resize
+        let deadline = Instant::now() + delay;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if !event::poll(timeout.max(Duration::from_millis(1)))? {
+                break;
+            }
+            match event::read()? {
+                Event::Key(key) if !matches!(key.code, KeyCode::Modifier(_)) => return Ok(()),
+                Event::Resize(..) => break,
+                _ => {}
+            }
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+    }
+}
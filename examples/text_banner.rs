@@ -0,0 +1,30 @@
+//! Render a banner from a TTF/OTF font.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --example text_banner --features text -- path/to/font.ttf "Hello"
+//! cargo run --example text_banner --features text -- path/to/font.ttf "Big" 48
+//! ```
+//!
+//! The optional third argument is the font size in dots (default 32).
+
+use std::env;
+
+use dotmax::render_once_to_stdout;
+use dotmax::text::{Banner, Font};
+
+fn main() -> dotmax::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(path), Some(text)) = (args.first(), args.get(1)) else {
+        eprintln!("Usage: text_banner <font.ttf> <text> [size]");
+        std::process::exit(2);
+    };
+    let size = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(32.0);
+
+    let font = Font::from_path(path)?;
+    let grid = Banner::new(&font, text.replace("\\n", "\n"))
+        .size(size)
+        .render()?;
+    render_once_to_stdout(&grid)
+}
@@ -71,6 +71,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Category: Animated Image");
             println!("Renderer: ApngPlayer (coming in Story 9.3)");
         }
+        MediaFormat::AnimatedWebp => {
+            println!("Category: Animated Image");
+            println!("Renderer: AnimatedWebpPlayer");
+        }
         MediaFormat::Video(codec) => {
             println!("Category: Video");
             println!("Codec: {}", codec);
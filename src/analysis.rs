@@ -0,0 +1,691 @@
+//! Analysis of the dot field and of source images.
+//!
+//! Most functions here treat a [`BrailleGrid`] as a binary image at dot
+//! resolution (`width*2 × height*4`). They are the building blocks for
+//! cleaning up dithered output ([`despeckle`], closing pinholes) and for
+//! picking out drawn shapes, e.g. to select the blob under a mouse click.
+//!
+//! [`luma_histogram`] and [`ExposureStats`] look at the image before it
+//! becomes dots, to help pick brightness, contrast, and threshold settings;
+//! [`HistogramOverlay`](crate::widgets::HistogramOverlay) draws them.
+//!
+//! Morphological operations return a new grid with the same size, colors, and
+//! text characters as the input; only the dots change.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::analysis::{connected_components, opening, Connectivity};
+//! use dotmax::primitives::draw_rectangle_filled;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(20, 5)?;
+//! draw_rectangle_filled(&mut grid, 2, 2, 8, 8)?;
+//! grid.set_dot(30, 10)?; // a stray speckle
+//!
+//! let regions = connected_components(&grid, Connectivity::Eight);
+//! assert_eq!(regions.len(), 2);
+//! assert_eq!(regions[0].bounds.width, 8);
+//!
+//! // Opening removes features smaller than the structuring element
+//! let cleaned = opening(&grid, Connectivity::Eight);
+//! assert_eq!(connected_components(&cleaned, Connectivity::Eight).len(), 1);
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::grid::BrailleGrid;
+
+/// Which neighbors count as touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Up, down, left, and right
+    Four,
+    /// The four edge neighbors plus the four diagonals
+    #[default]
+    Eight,
+}
+
+impl Connectivity {
+    const fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// Axis-aligned rectangle in dot coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    /// Left edge in dots
+    pub x: usize,
+    /// Top edge in dots
+    pub y: usize,
+    /// Width in dots (at least 1)
+    pub width: usize,
+    /// Height in dots (at least 1)
+    pub height: usize,
+}
+
+impl BoundingBox {
+    /// Whether the dot `(x, y)` lies inside the box.
+    #[must_use]
+    pub const fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && y >= self.y && x < self.x + self.width && y < self.y + self.height
+    }
+
+    /// Grow the box to include a dot.
+    fn include(&mut self, x: usize, y: usize) {
+        let right = (self.x + self.width).max(x + 1);
+        let bottom = (self.y + self.height).max(y + 1);
+        self.x = self.x.min(x);
+        self.y = self.y.min(y);
+        self.width = right - self.x;
+        self.height = bottom - self.y;
+    }
+}
+
+/// A set of connected dots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Dot coordinates in the region, in scan order (top to bottom, left to right)
+    pub dots: Vec<(usize, usize)>,
+    /// Smallest box containing every dot
+    pub bounds: BoundingBox,
+}
+
+impl Region {
+    /// Number of dots in the region.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.dots.len()
+    }
+
+    /// Always `false`; regions contain at least one dot.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dots.is_empty()
+    }
+
+    /// Mean dot position.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn centroid(&self) -> (f32, f32) {
+        let n = self.dots.len().max(1) as f32;
+        let (sx, sy) = self.dots.iter().fold((0.0, 0.0), |(sx, sy), &(x, y)| {
+            (sx + x as f32, sy + y as f32)
+        });
+        (sx / n, sy / n)
+    }
+}
+
+/// The dot field as a flat row-major bitmap.
+struct DotField {
+    width: usize,
+    height: usize,
+    bits: Vec<bool>,
+}
+
+impl DotField {
+    fn from_grid(grid: &BrailleGrid) -> Self {
+        Self {
+            width: grid.dot_width(),
+            height: grid.dot_height(),
+            bits: grid.dot_field(),
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.bits[y * self.width + x]
+    }
+
+    /// In-bounds neighbors of `(x, y)`.
+    fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            (nx < self.width && ny < self.height).then_some((nx, ny))
+        })
+    }
+
+    /// A copy of `template` with its dots replaced by this field.
+    fn into_grid(self, template: &BrailleGrid) -> BrailleGrid {
+        let mut out = template.clone();
+        out.set_dot_field(&self.bits);
+        out
+    }
+}
+
+/// Label every group of touching set dots.
+///
+/// Regions are returned in the order their first dot appears scanning top to
+/// bottom, left to right.
+#[must_use]
+pub fn connected_components(grid: &BrailleGrid, connectivity: Connectivity) -> Vec<Region> {
+    let field = DotField::from_grid(grid);
+    let mut visited = vec![false; field.bits.len()];
+    let mut regions = Vec::new();
+    for start in 0..field.bits.len() {
+        if field.bits[start] && !visited[start] {
+            regions.push(flood(&field, &mut visited, start, connectivity));
+        }
+    }
+    regions
+}
+
+/// The region containing dot `(x, y)`, or `None` if that dot is unset.
+///
+/// Cheaper than [`connected_components`] when only one region is needed,
+/// such as selecting the shape under a cursor.
+#[must_use]
+pub fn region_at(
+    grid: &BrailleGrid,
+    x: usize,
+    y: usize,
+    connectivity: Connectivity,
+) -> Option<Region> {
+    if !grid.is_dot_set(x, y) {
+        return None;
+    }
+    let field = DotField::from_grid(grid);
+    let mut visited = vec![false; field.bits.len()];
+    Some(flood(
+        &field,
+        &mut visited,
+        y * field.width + x,
+        connectivity,
+    ))
+}
+
+/// Collect the region around `start` with an explicit stack.
+fn flood(
+    field: &DotField,
+    visited: &mut [bool],
+    start: usize,
+    connectivity: Connectivity,
+) -> Region {
+    let (sx, sy) = (start % field.width, start / field.width);
+    let mut bounds = BoundingBox {
+        x: sx,
+        y: sy,
+        width: 1,
+        height: 1,
+    };
+    let mut dots = Vec::new();
+    let mut stack = vec![(sx, sy)];
+    visited[start] = true;
+    while let Some((x, y)) = stack.pop() {
+        dots.push((x, y));
+        bounds.include(x, y);
+        for (nx, ny) in field.neighbors(x, y, connectivity) {
+            let index = ny * field.width + nx;
+            if field.bits[index] && !visited[index] {
+                visited[index] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+    dots.sort_unstable_by_key(|&(x, y)| (y, x));
+    Region { dots, bounds }
+}
+
+/// Bounding box of every set dot, or `None` for a grid with no dots.
+#[must_use]
+pub fn dot_bounds(grid: &BrailleGrid) -> Option<BoundingBox> {
+    let mut bounds: Option<BoundingBox> = None;
+    for y in 0..grid.dot_height() {
+        for x in 0..grid.dot_width() {
+            if grid.is_dot_set(x, y) {
+                match bounds.as_mut() {
+                    Some(b) => b.include(x, y),
+                    None => {
+                        bounds = Some(BoundingBox {
+                            x,
+                            y,
+                            width: 1,
+                            height: 1,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    bounds
+}
+
+/// Keep only dots whose every neighbor is also set.
+///
+/// Shrinks shapes by one dot and deletes features thinner than three dots.
+/// Neighbors beyond the grid edge don't count against a dot, so shapes
+/// touching the edge aren't eaten away from it.
+#[must_use]
+pub fn erode(grid: &BrailleGrid, connectivity: Connectivity) -> BrailleGrid {
+    let field = DotField::from_grid(grid);
+    let bits = (0..field.bits.len())
+        .map(|i| {
+            let (x, y) = (i % field.width, i / field.width);
+            field.get(x, y)
+                && field
+                    .neighbors(x, y, connectivity)
+                    .all(|(nx, ny)| field.get(nx, ny))
+        })
+        .collect();
+    DotField { bits, ..field }.into_grid(grid)
+}
+
+/// Set every dot that is set or has a set neighbor.
+///
+/// Grows shapes by one dot and bridges one-dot gaps.
+#[must_use]
+pub fn dilate(grid: &BrailleGrid, connectivity: Connectivity) -> BrailleGrid {
+    let field = DotField::from_grid(grid);
+    let bits = (0..field.bits.len())
+        .map(|i| {
+            let (x, y) = (i % field.width, i / field.width);
+            field.get(x, y)
+                || field
+                    .neighbors(x, y, connectivity)
+                    .any(|(nx, ny)| field.get(nx, ny))
+        })
+        .collect();
+    DotField { bits, ..field }.into_grid(grid)
+}
+
+/// Erode then dilate: removes specks and thin spurs, keeps larger shapes'
+/// size.
+#[must_use]
+pub fn opening(grid: &BrailleGrid, connectivity: Connectivity) -> BrailleGrid {
+    dilate(&erode(grid, connectivity), connectivity)
+}
+
+/// Dilate then erode: fills pinholes and narrow gaps, keeps larger shapes'
+/// size.
+#[must_use]
+pub fn closing(grid: &BrailleGrid, connectivity: Connectivity) -> BrailleGrid {
+    erode(&dilate(grid, connectivity), connectivity)
+}
+
+/// Remove set dots that have fewer than `min_neighbors` set dots among their
+/// eight neighbors.
+///
+/// A rank filter: `0` changes nothing and `8` behaves like [`erode`] away
+/// from the grid edge. With `min_neighbors = 1` only fully isolated dots go,
+/// which is what dithering noise mostly looks like; `2` also removes isolated
+/// pairs and the loose ends of one-dot-wide lines. Dots along the edge of a
+/// solid shape have at least three set neighbors, so edges survive at both
+/// settings. Neighbors beyond the grid edge count as unset.
+#[must_use]
+pub fn despeckle(grid: &BrailleGrid, min_neighbors: u8) -> BrailleGrid {
+    let field = DotField::from_grid(grid);
+    let bits = (0..field.bits.len())
+        .map(|i| {
+            let (x, y) = (i % field.width, i / field.width);
+            field.get(x, y)
+                && field
+                    .neighbors(x, y, Connectivity::Eight)
+                    .filter(|&(nx, ny)| field.get(nx, ny))
+                    .count()
+                    >= usize::from(min_neighbors)
+        })
+        .collect();
+    DotField { bits, ..field }.into_grid(grid)
+}
+
+// ============================================================================
+// Luminance histograms and exposure
+// ============================================================================
+
+/// Count the pixels at each luminance level, `0` (black) to `255` (white).
+///
+/// Uses the same grayscale conversion as
+/// [`ImageRenderer`](crate::image::ImageRenderer), so the counts line up with
+/// what its brightness, contrast, and threshold settings act on.
+#[cfg(feature = "image")]
+#[must_use]
+pub fn luma_histogram(image: &image::DynamicImage) -> [u32; 256] {
+    match image {
+        image::DynamicImage::ImageLuma8(gray) => gray_histogram(gray),
+        other => gray_histogram(&crate::image::to_grayscale(other)),
+    }
+}
+
+/// [`luma_histogram`] for an image that is already grayscale.
+#[cfg(feature = "image")]
+pub(crate) fn gray_histogram(gray: &image::GrayImage) -> [u32; 256] {
+    let mut histogram = [0u32; 256];
+    for pixel in gray.pixels() {
+        histogram[usize::from(pixel[0])] += 1;
+    }
+    histogram
+}
+
+/// Levels this close to black or white count as clipped.
+const CLIP_MARGIN: usize = 4;
+
+/// Summary of a luminance histogram, for tuning camera and image settings.
+///
+/// The `suggested_*` values are starting points for the matching
+/// [`ImageRenderer`](crate::image::ImageRenderer) settings, already clamped
+/// to their valid ranges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExposureStats {
+    /// Average luminance.
+    pub mean: f32,
+    /// Median luminance.
+    pub median: u8,
+    /// Darkest level once the darkest 1% of pixels is ignored.
+    pub low: u8,
+    /// Brightest level once the brightest 1% of pixels is ignored.
+    pub high: u8,
+    /// Fraction of pixels within a few levels of black.
+    pub shadows_clipped: f32,
+    /// Fraction of pixels within a few levels of white.
+    pub highlights_clipped: f32,
+    /// The threshold automatic (Otsu) thresholding would pick.
+    pub threshold: u8,
+}
+
+impl ExposureStats {
+    /// Summarizes a histogram such as one from [`luma_histogram`].
+    ///
+    /// An empty histogram gives all-zero statistics.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn from_histogram(histogram: &[u32; 256]) -> Self {
+        let total: u64 = histogram.iter().map(|&c| u64::from(c)).sum();
+        if total == 0 {
+            return Self {
+                mean: 0.0,
+                median: 0,
+                low: 0,
+                high: 0,
+                shadows_clipped: 0.0,
+                highlights_clipped: 0.0,
+                threshold: 0,
+            };
+        }
+
+        // First level at which the running count passes `fraction` of pixels
+        let percentile = |fraction: f64| -> u8 {
+            let target = (total as f64 * fraction).ceil().max(1.0) as u64;
+            let mut running = 0;
+            for (level, &count) in histogram.iter().enumerate() {
+                running += u64::from(count);
+                if running >= target {
+                    return level as u8;
+                }
+            }
+            255
+        };
+        let share = |counts: &[u32]| {
+            counts.iter().map(|&c| u64::from(c)).sum::<u64>() as f32 / total as f32
+        };
+        let weighted: u64 = (0u64..)
+            .zip(histogram)
+            .map(|(level, &count)| level * u64::from(count))
+            .sum();
+
+        Self {
+            mean: (weighted as f64 / total as f64) as f32,
+            median: percentile(0.5),
+            low: percentile(0.01),
+            high: percentile(0.99),
+            shadows_clipped: share(&histogram[..=CLIP_MARGIN]),
+            highlights_clipped: share(&histogram[255 - CLIP_MARGIN..]),
+            threshold: otsu_from_histogram(histogram),
+        }
+    }
+
+    /// Brightness factor that would bring the mean to mid-gray.
+    #[must_use]
+    pub fn suggested_brightness(&self) -> f32 {
+        if self.mean <= 0.0 {
+            2.0
+        } else {
+            (128.0 / self.mean).clamp(0.0, 2.0)
+        }
+    }
+
+    /// Contrast factor that would spread the central 98% of levels across
+    /// the full range around mid-gray.
+    #[must_use]
+    pub fn suggested_contrast(&self) -> f32 {
+        let reach = (f32::from(self.low) - 128.0)
+            .abs()
+            .max((f32::from(self.high) - 128.0).abs());
+        if reach <= 0.0 {
+            2.0
+        } else {
+            (127.5 / reach).clamp(0.0, 2.0)
+        }
+    }
+}
+
+/// Otsu's threshold for a 256-bin luminance histogram: the level that best
+/// separates it into dark and bright classes.
+///
+/// Shared with [`otsu_threshold`](crate::image::otsu_threshold), which builds
+/// the histogram from an image.
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn otsu_from_histogram(histogram: &[u32; 256]) -> u8 {
+    let total: f64 = histogram.iter().map(|&count| f64::from(count)).sum();
+    let sum_total: f64 = (0u32..)
+        .zip(histogram)
+        .map(|(level, &count)| f64::from(level) * f64::from(count))
+        .sum();
+
+    let mut max_variance = 0.0;
+    let mut best_threshold = 0u8;
+    let mut weight_background = 0.0;
+    let mut sum_background = 0.0;
+
+    for (level, &count) in (0u32..).zip(histogram) {
+        weight_background += f64::from(count);
+        if weight_background == 0.0 {
+            continue;
+        }
+        let weight_foreground = total - weight_background;
+        if weight_foreground == 0.0 {
+            break;
+        }
+        sum_background += f64::from(level) * f64::from(count);
+
+        let mean_background = sum_background / weight_background;
+        let mean_foreground = (sum_total - sum_background) / weight_foreground;
+
+        // Between-class variance
+        let variance =
+            weight_background * weight_foreground * (mean_background - mean_foreground).powi(2);
+        if variance > max_variance {
+            max_variance = variance;
+            best_threshold = level as u8;
+        }
+    }
+
+    best_threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+    use crate::primitives::draw_rectangle_filled;
+
+    fn dot_count(grid: &BrailleGrid) -> u32 {
+        grid.get_raw_patterns().iter().map(|p| p.count_ones()).sum()
+    }
+
+    #[test]
+    fn test_connectivity_changes_diagonal_grouping() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        grid.set_dot(1, 1).unwrap();
+        grid.set_dot(2, 2).unwrap();
+        assert_eq!(connected_components(&grid, Connectivity::Eight).len(), 1);
+        assert_eq!(connected_components(&grid, Connectivity::Four).len(), 2);
+    }
+
+    #[test]
+    fn test_regions_in_scan_order_with_bounds() {
+        let mut grid = BrailleGrid::new(10, 4).unwrap();
+        draw_rectangle_filled(&mut grid, 10, 8, 3, 2).unwrap();
+        grid.set_dot(15, 0).unwrap();
+        let regions = connected_components(&grid, Connectivity::Eight);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].dots, vec![(15, 0)]);
+        assert_eq!(
+            regions[1].bounds,
+            BoundingBox {
+                x: 10,
+                y: 8,
+                width: 3,
+                height: 2
+            }
+        );
+        assert_eq!(regions[1].len(), 6);
+        assert_eq!(regions[1].centroid(), (11.0, 8.5));
+    }
+
+    #[test]
+    fn test_region_at() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        draw_rectangle_filled(&mut grid, 0, 0, 2, 2).unwrap();
+        assert!(region_at(&grid, 5, 5, Connectivity::Eight).is_none());
+        let region = region_at(&grid, 1, 1, Connectivity::Four).unwrap();
+        assert_eq!(region.dots, vec![(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(region.bounds.contains(1, 1) && !region.bounds.contains(2, 0));
+    }
+
+    #[test]
+    fn test_dot_bounds() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        assert!(dot_bounds(&grid).is_none());
+        grid.set_dot(6, 1).unwrap();
+        grid.set_dot(2, 5).unwrap();
+        assert_eq!(
+            dot_bounds(&grid),
+            Some(BoundingBox {
+                x: 2,
+                y: 1,
+                width: 5,
+                height: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_erode_and_dilate() {
+        let mut grid = BrailleGrid::new(5, 3).unwrap();
+        draw_rectangle_filled(&mut grid, 2, 2, 5, 5).unwrap();
+        assert_eq!(dot_count(&erode(&grid, Connectivity::Eight)), 9);
+        assert_eq!(dot_count(&dilate(&grid, Connectivity::Eight)), 49);
+        assert_eq!(dot_count(&dilate(&grid, Connectivity::Four)), 45);
+    }
+
+    #[test]
+    fn test_erode_ignores_grid_edge() {
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        grid.set_raw_patterns(&[0xFF, 0xFF]);
+        assert_eq!(dot_count(&erode(&grid, Connectivity::Eight)), 16);
+    }
+
+    #[test]
+    fn test_opening_removes_speck_and_closing_fills_hole() {
+        let mut grid = BrailleGrid::new(10, 4).unwrap();
+        draw_rectangle_filled(&mut grid, 2, 2, 6, 6).unwrap();
+        grid.set_dot(15, 12).unwrap();
+        let opened = opening(&grid, Connectivity::Eight);
+        assert!(!opened.is_dot_set(15, 12));
+        assert_eq!(dot_count(&opened), 36);
+
+        let mut holed = BrailleGrid::new(10, 4).unwrap();
+        draw_rectangle_filled(&mut holed, 2, 2, 6, 6).unwrap();
+        let mut with_hole = BrailleGrid::new(10, 4).unwrap();
+        for (x, y) in (2..8).flat_map(|y| (2..8).map(move |x| (x, y))) {
+            if (x, y) != (4, 4) {
+                with_hole.set_dot(x, y).unwrap();
+            }
+        }
+        let closed = closing(&with_hole, Connectivity::Eight);
+        assert_eq!(closed.get_raw_patterns(), holed.get_raw_patterns());
+    }
+
+    #[test]
+    fn test_despeckle_removes_isolated_dots_and_keeps_edges() {
+        let mut grid = BrailleGrid::new(10, 4).unwrap();
+        draw_rectangle_filled(&mut grid, 2, 2, 6, 6).unwrap();
+        grid.set_dot(15, 12).unwrap();
+        // Two-dot spur off the right edge
+        grid.set_dot(8, 4).unwrap();
+        grid.set_dot(9, 4).unwrap();
+
+        assert_eq!(
+            despeckle(&grid, 0).get_raw_patterns(),
+            grid.get_raw_patterns()
+        );
+        let once = despeckle(&grid, 1);
+        assert!(!once.is_dot_set(15, 12) && once.is_dot_set(9, 4));
+        assert_eq!(dot_count(&once), 38);
+        let twice = despeckle(&grid, 2);
+        assert!(twice.is_dot_set(8, 4) && !twice.is_dot_set(9, 4));
+        assert_eq!(dot_count(&twice), 37);
+    }
+
+    #[test]
+    fn test_exposure_stats_of_two_level_histogram() {
+        let mut histogram = [0u32; 256];
+        histogram[0] = 25;
+        histogram[200] = 75;
+        let stats = ExposureStats::from_histogram(&histogram);
+        assert!((stats.mean - 150.0).abs() < 1e-3);
+        assert_eq!(stats.median, 200);
+        assert_eq!((stats.low, stats.high), (0, 200));
+        assert!((stats.shadows_clipped - 0.25).abs() < 1e-6);
+        assert!(stats.highlights_clipped.abs() < 1e-6);
+        assert!(stats.threshold < 200);
+        assert!((stats.suggested_contrast() - 127.5 / 128.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_exposure_stats_of_empty_histogram() {
+        let stats = ExposureStats::from_histogram(&[0; 256]);
+        assert_eq!(stats.median, 0);
+        assert!((stats.suggested_brightness() - 2.0).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_luma_histogram_counts_every_pixel() {
+        let image = image::DynamicImage::ImageLuma8(image::GrayImage::from_fn(4, 2, |x, _| {
+            image::Luma([if x < 1 { 0 } else { 255 }])
+        }));
+        let histogram = luma_histogram(&image);
+        assert_eq!((histogram[0], histogram[255]), (2, 6));
+
+        let rgb = image::DynamicImage::ImageRgb8(image::RgbImage::new(3, 3));
+        assert_eq!(luma_histogram(&rgb)[0], 9);
+    }
+
+    #[test]
+    fn test_morphology_keeps_colors_and_characters() {
+        let mut grid = BrailleGrid::new(3, 1).unwrap();
+        grid.set_cell_color(1, 0, Color::rgb(1, 2, 3)).unwrap();
+        grid.set_char(2, 0, 'A').unwrap();
+        let out = dilate(&grid, Connectivity::Eight);
+        assert_eq!(out.get_color(1, 0), Some(Color::rgb(1, 2, 3)));
+        assert_eq!(out.get_char(2, 0), 'A');
+    }
+}
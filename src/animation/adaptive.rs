@@ -0,0 +1,296 @@
+//! Adaptive quality for animations that can't keep up.
+//!
+//! [`AdaptiveQuality`] watches how long frames take against the frame
+//! budget. When recent frames run over, it steps the [`Quality`] down one
+//! level; when they finish well inside the budget again, it steps back up.
+//! Each change waits for a fresh window of frames, so it doesn't flap
+//! between levels.
+//!
+//! The levels, from best to cheapest:
+//!
+//! | Level | Effect |
+//! |-------|--------|
+//! | [`Full`](Quality::Full) | Nothing changed |
+//! | [`NoDithering`](Quality::NoDithering) | Images are thresholded instead of dithered |
+//! | [`Monochrome`](Quality::Monochrome) | Colors are dropped, which also drops their escape codes |
+//! | [`LowResolution`](Quality::LowResolution) | Dots are merged into 2×2 blocks, so fewer cells change per frame |
+//!
+//! [`AnimationLoop`](super::AnimationLoop) applies the levels to each
+//! frame when built with
+//! [`adaptive_quality(true)`](super::AnimationLoopBuilder::adaptive_quality).
+//! It drops colors and merges dots after the frame callback has drawn, so
+//! those levels cut the bytes written to the terminal, not the drawing
+//! work. Dithering only matters where pixels are turned into dots, which
+//! happens in the frame callback, so the loop publishes the level through
+//! [`frame_quality`] and callbacks that render images pass their options
+//! through [`Quality::render_options`]. A callback that ignores the level
+//! draws the same frame at `NoDithering` as at `Full`.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use dotmax::animation::{AdaptiveQuality, Quality};
+//!
+//! let mut adaptive = AdaptiveQuality::new(60);
+//! let mut changes = Vec::new();
+//! for _ in 0..60 {
+//!     // 25ms frames against a 16.7ms budget
+//!     changes.extend(adaptive.record(Duration::from_millis(25)));
+//! }
+//! assert_eq!(changes, [Quality::NoDithering, Quality::Monochrome]);
+//! ```
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::grid::BrailleGrid;
+#[cfg(feature = "image")]
+use crate::image::{ColorMode, DitheringMethod, RenderOptions};
+
+/// Frames averaged before deciding to change level.
+const WINDOW: usize = 30;
+
+/// Step down when the average frame takes more than this share of the
+/// budget.
+const DEGRADE_AT: f64 = 0.9;
+
+/// Step up when the average frame takes less than this share of the
+/// budget.
+const RESTORE_AT: f64 = 0.5;
+
+thread_local! {
+    /// Level of the frame being drawn on this thread
+    static FRAME_QUALITY: Cell<Quality> = const { Cell::new(Quality::Full) };
+}
+
+/// The [`Quality`] of the frame an [`AnimationLoop`](super::AnimationLoop)
+/// on this thread is drawing, for frame callbacks to read; `Full` outside
+/// a frame callback or without adaptive quality.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::animation::{frame_quality, AnimationLoop, Quality};
+///
+/// AnimationLoop::new(80, 24)
+///     .adaptive_quality(true)
+///     .on_frame(|_, buffer| {
+///         if frame_quality() == Quality::Full {
+///             // Draw the expensive dithered background
+///         }
+///         buffer.set_dot(80, 48)?;
+///         Ok(true)
+///     })
+///     .run()?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[must_use]
+pub fn frame_quality() -> Quality {
+    FRAME_QUALITY.with(Cell::get)
+}
+
+/// Sets what [`frame_quality`] returns on this thread.
+pub fn set_frame_quality(quality: Quality) {
+    FRAME_QUALITY.with(|current| current.set(quality));
+}
+
+/// How much work a frame gets, from best to cheapest.
+///
+/// [`apply`](Self::apply) degrades a frame that is already drawn, which
+/// saves output rather than drawing time. Only callbacks that check
+/// [`frame_quality`] draw less.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Quality {
+    /// Nothing changed
+    #[default]
+    Full,
+    /// Images thresholded instead of dithered
+    NoDithering,
+    /// No colors
+    Monochrome,
+    /// Dots merged into 2×2 blocks
+    LowResolution,
+}
+
+impl Quality {
+    /// The next cheaper level, or `None` at the cheapest.
+    #[must_use]
+    pub const fn degraded(self) -> Option<Self> {
+        match self {
+            Self::Full => Some(Self::NoDithering),
+            Self::NoDithering => Some(Self::Monochrome),
+            Self::Monochrome => Some(Self::LowResolution),
+            Self::LowResolution => None,
+        }
+    }
+
+    /// The next better level, or `None` at full quality.
+    #[must_use]
+    pub const fn restored(self) -> Option<Self> {
+        match self {
+            Self::Full => None,
+            Self::NoDithering => Some(Self::Full),
+            Self::Monochrome => Some(Self::NoDithering),
+            Self::LowResolution => Some(Self::Monochrome),
+        }
+    }
+
+    /// Applies this level to a drawn frame: drops colors from
+    /// [`Monochrome`](Self::Monochrome) down and merges dots into 2×2
+    /// blocks at [`LowResolution`](Self::LowResolution).
+    pub fn apply(self, grid: &mut BrailleGrid) {
+        if self >= Self::Monochrome {
+            grid.clear_colors();
+        }
+        if self >= Self::LowResolution {
+            // Dots 1, 2, 4, 5 and dots 3, 6, 7, 8: each cell's two 2×2 blocks
+            const TOP: u8 = 0x1B;
+            const BOTTOM: u8 = 0xE4;
+            let coarse: Vec<u8> = grid
+                .get_raw_patterns()
+                .iter()
+                .map(|&p| {
+                    (if p & TOP == 0 { 0 } else { TOP })
+                        | (if p & BOTTOM == 0 { 0 } else { BOTTOM })
+                })
+                .collect();
+            grid.set_raw_patterns(&coarse);
+        }
+    }
+
+    /// `options` as this level renders them: without dithering from
+    /// [`NoDithering`](Self::NoDithering) down, and monochrome from
+    /// [`Monochrome`](Self::Monochrome) down.
+    #[cfg(feature = "image")]
+    #[must_use]
+    pub const fn render_options(self, options: RenderOptions) -> RenderOptions {
+        let mut options = options;
+        if !matches!(self, Self::Full) {
+            options.dithering = DitheringMethod::None;
+        }
+        if matches!(self, Self::Monochrome | Self::LowResolution) {
+            options.color_mode = ColorMode::Monochrome;
+        }
+        options
+    }
+}
+
+/// Steps [`Quality`] down when frames overrun their budget and back up
+/// when they have room to spare.
+#[derive(Debug, Clone)]
+pub struct AdaptiveQuality {
+    budget: Duration,
+    quality: Quality,
+    frame_times: VecDeque<Duration>,
+}
+
+impl AdaptiveQuality {
+    /// Full quality, with a budget of `1 / target_fps` per frame (at least
+    /// 1 FPS).
+    #[must_use]
+    pub fn new(target_fps: u32) -> Self {
+        Self {
+            budget: Duration::from_secs_f64(1.0 / f64::from(target_fps.max(1))),
+            quality: Quality::Full,
+            frame_times: VecDeque::with_capacity(WINDOW),
+        }
+    }
+
+    /// The current level.
+    #[must_use]
+    pub const fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Records the time one frame took to draw and render, and returns the
+    /// new level if this frame changed it.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn record(&mut self, frame_time: Duration) -> Option<Quality> {
+        if self.frame_times.len() == WINDOW {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+        if self.frame_times.len() < WINDOW {
+            return None;
+        }
+
+        let total: Duration = self.frame_times.iter().sum();
+        let load = total.as_secs_f64() / WINDOW as f64 / self.budget.as_secs_f64();
+        let next = if load > DEGRADE_AT {
+            self.quality.degraded()
+        } else if load < RESTORE_AT {
+            self.quality.restored()
+        } else {
+            None
+        }?;
+        self.quality = next;
+        // Judge the new level on its own frames
+        self.frame_times.clear();
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(adaptive: &mut AdaptiveQuality, frame_time: Duration, frames: usize) -> Vec<Quality> {
+        (0..frames)
+            .filter_map(|_| adaptive.record(frame_time))
+            .collect()
+    }
+
+    #[test]
+    fn test_degrades_one_level_per_window_then_restores() {
+        let mut adaptive = AdaptiveQuality::new(100); // 10ms budget
+        assert_eq!(feed(&mut adaptive, Duration::from_millis(8), 100), []);
+
+        let slow = feed(&mut adaptive, Duration::from_millis(20), WINDOW * 5);
+        assert_eq!(
+            slow,
+            [
+                Quality::NoDithering,
+                Quality::Monochrome,
+                Quality::LowResolution
+            ]
+        );
+
+        // Between the thresholds: stays put
+        assert_eq!(
+            feed(&mut adaptive, Duration::from_millis(7), WINDOW * 2),
+            []
+        );
+
+        let fast = feed(&mut adaptive, Duration::from_millis(2), WINDOW * 5);
+        assert_eq!(
+            fast,
+            [Quality::Monochrome, Quality::NoDithering, Quality::Full]
+        );
+        assert_eq!(adaptive.quality(), Quality::Full);
+    }
+
+    #[test]
+    fn test_apply_drops_colors_and_resolution() {
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        grid.enable_color_support();
+        grid.set_cell_color(0, 0, crate::Color::rgb(255, 0, 0))
+            .unwrap();
+        grid.set_dot(0, 0).unwrap();
+        grid.set_dot(3, 3).unwrap();
+
+        let mut full = grid.clone();
+        Quality::NoDithering.apply(&mut full);
+        assert_eq!(full.get_raw_patterns(), grid.get_raw_patterns());
+        assert!(full.get_color(0, 0).is_some());
+
+        let mut mono = grid.clone();
+        Quality::Monochrome.apply(&mut mono);
+        assert_eq!(mono.get_color(0, 0), None);
+        assert_eq!(mono.get_raw_patterns(), grid.get_raw_patterns());
+
+        Quality::LowResolution.apply(&mut grid);
+        assert_eq!(grid.get_raw_patterns(), [0x1B, 0xE4]);
+    }
+}
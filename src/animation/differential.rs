@@ -18,6 +18,16 @@
 //! - **Differential**: ~96 cells → ~96 escape codes
 //! - **I/O reduction**: ~95% (exceeds the 60-80% target)
 //!
+//! # Color-only Changes
+//!
+//! A cell whose glyph is unchanged but whose color changed is a
+//! [`CellChange::Color`]: the glyph is re-emitted behind the new color escape,
+//! and nothing is emitted at all for blank cells, whose color doesn't show.
+//! Changed cells are written in runs, so a row of them costs one cursor move,
+//! and a color escape is only written when the color differs from the one
+//! before it. An animation pulsing hue over static geometry sends little more
+//! than one escape per run.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -44,6 +54,14 @@
 //! # }
 //! ```
 //!
+//! # Dirty Tracking
+//!
+//! Comparing frames still means visiting every cell, which dominates frame
+//! time on large grids. Keep drawing into one grid with
+//! [`BrailleGrid::set_dirty_tracking`] on and render it with
+//! [`DifferentialRenderer::render_dirty`]: only the cells inside the grid's
+//! [dirty rects](BrailleGrid::take_dirty_rects) are compared and copied.
+//!
 //! # When to Use Differential Rendering
 //!
 //! Differential rendering is most effective when:
@@ -56,12 +74,23 @@
 //! - Frame rate is already low (< 10 fps)
 
 use crate::error::DotmaxError;
-use crate::grid::BrailleGrid;
-use crate::render::TerminalRenderer;
+use crate::grid::{BrailleGrid, Color, DirtyRect};
+use crate::render::{display_char, RenderMode, TerminalRenderer};
 use crossterm::{cursor::MoveTo, QueueableCommand};
-use std::io::Write;
+use ratatui::layout::Rect;
+use std::io::{self, Write};
 use tracing::debug;
 
+/// How a cell differs from the same cell in the previous frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellChange {
+    /// The glyph changed (dots or text character), so the cell is redrawn.
+    Content,
+    /// Only the color changed. The same glyph is written again behind the
+    /// new color escape.
+    Color,
+}
+
 /// Optimized renderer that only outputs changed cells.
 ///
 /// `DifferentialRenderer` compares the current frame to the previous frame
@@ -131,7 +160,11 @@ impl DifferentialRenderer {
     ///
     /// - **First call**: Renders entire grid (no previous frame)
     /// - **Dimension mismatch**: Renders entire grid (auto-invalidates)
-    /// - **Normal operation**: Renders only changed cells
+    /// - **Renderer reset** (new safe area, status line, or render mode):
+    ///   Renders entire grid
+    /// - **Normal operation**: Renders only changed cells, inside the
+    ///   renderer's [safe area](TerminalRenderer::set_safe_area) and, for an
+    ///   [inline](TerminalRenderer::inline) renderer, inside its block
     ///
     /// # Arguments
     ///
@@ -168,11 +201,70 @@ impl DifferentialRenderer {
         current: &BrailleGrid,
         renderer: &mut TerminalRenderer,
     ) -> Result<(), DotmaxError> {
-        // Check for dimension mismatch or no previous frame
-        let should_full_render = self
-            .last_frame
-            .as_ref()
-            .map_or(true, |last| {
+        self.render_areas(current, None, renderer)
+    }
+
+    /// Like [`render_diff()`](Self::render_diff), but compares only the cells
+    /// in `current`'s [dirty rects](BrailleGrid::take_dirty_rects), which
+    /// this takes. Without dirty tracking on `current`, this is
+    /// `render_diff()`.
+    ///
+    /// The rects must cover every change since the last frame this renderer
+    /// drew, so draw each frame into the same grid and let nothing else take
+    /// its rects. If the screen was disturbed, [`invalidate()`](Self::invalidate)
+    /// as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DotmaxError::Terminal` if terminal I/O operations fail.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::DifferentialRenderer;
+    /// use dotmax::{BrailleGrid, TerminalRenderer};
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let mut diff = DifferentialRenderer::new();
+    /// let mut terminal = TerminalRenderer::new()?;
+    /// let mut frame = BrailleGrid::new(300, 100)?;
+    /// frame.set_dirty_tracking(true);
+    ///
+    /// for x in 0..600 {
+    ///     frame.set_dot(x, 200)?;
+    ///     // Compares one cell per frame, not 30,000
+    ///     diff.render_dirty(&mut frame, &mut terminal)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render_dirty(
+        &mut self,
+        current: &mut BrailleGrid,
+        renderer: &mut TerminalRenderer,
+    ) -> Result<(), DotmaxError> {
+        let areas = current.take_dirty_rects();
+        self.render_areas(current, areas.as_deref(), renderer)
+    }
+
+    /// Renders the changes inside `areas`, or anywhere with `None`.
+    fn render_areas(
+        &mut self,
+        current: &BrailleGrid,
+        areas: Option<&[DirtyRect]>,
+        renderer: &mut TerminalRenderer,
+    ) -> Result<(), DotmaxError> {
+        // Sixel output is an image; it can't be patched cell by cell
+        if renderer.render_mode() == RenderMode::Sixel {
+            renderer.render(current)?;
+            self.last_frame = Some(untracked(current));
+            return Ok(());
+        }
+
+        // Check for dimension mismatch, no previous frame, or a screen the
+        // renderer is about to clear
+        let should_full_render = renderer.needs_full_render()
+            || self.last_frame.as_ref().map_or(true, |last| {
                 if last.width() != current.width() || last.height() != current.height() {
                     debug!(
                         old_width = last.width(),
@@ -193,45 +285,48 @@ impl DifferentialRenderer {
                 debug!("First render or dimension change - performing full frame render");
                 // Full render using the terminal renderer
                 renderer.render(current)?;
-                self.last_frame = Some(current.clone());
+                self.last_frame = Some(untracked(current));
                 return Ok(());
             }
             (false, Some(last)) => last,
         };
 
-        // Differential render
-        let mut stdout = std::io::stdout();
-        let mut changed_count = 0;
-
-        for y in 0..current.height() {
-            for x in 0..current.width() {
-                if Self::cells_differ(current, last, x, y) {
-                    // Move cursor to position
-                    // Safe to truncate: terminal dimensions fit in u16
-                    #[allow(clippy::cast_possible_truncation)]
-                    stdout.queue(MoveTo(x as u16, y as u16))?;
-
-                    // Get the character to render
-                    let ch = current.get_char(x, y);
-
-                    // Apply color if present
-                    if let Some(color) = current.get_color(x, y) {
-                        write!(
-                            stdout,
-                            "\x1b[38;2;{};{};{}m{}\x1b[0m",
-                            color.r, color.g, color.b, ch
-                        )?;
-                    } else {
-                        write!(stdout, "{ch}")?;
+        // Differential render, buffered so the frame goes out in one write
+        let whole = [DirtyRect {
+            x: 0,
+            y: 0,
+            width: current.width(),
+            height: current.height(),
+        }];
+        let area = renderer.grid_area()?;
+        let mut out = Vec::new();
+        let mode = renderer.render_mode();
+        let (content, color) =
+            write_changes(&mut out, current, last, mode, areas.unwrap_or(&whole), area)?;
+
+        let writer = renderer.writer();
+        writer.write_all(&out)?;
+        writer.flush()?;
+        // The status row is outside the grid area, so no diff touches it
+        renderer.draw_status_if_dirty()?;
+        debug!(
+            changed_cells = content,
+            color_only_cells = color,
+            bytes = out.len(),
+            "Differential render complete"
+        );
+        match (areas, self.last_frame.as_mut()) {
+            (Some(areas), Some(last)) => {
+                for area in areas {
+                    for y in area.y..area.y + area.height {
+                        for x in area.x..area.x + area.width {
+                            last.copy_cell_from(current, x, y);
+                        }
                     }
-                    changed_count += 1;
                 }
             }
+            _ => self.last_frame = Some(untracked(current)),
         }
-
-        stdout.flush()?;
-        debug!(changed_cells = changed_count, "Differential render complete");
-        self.last_frame = Some(current.clone());
         Ok(())
     }
 
@@ -258,21 +353,49 @@ impl DifferentialRenderer {
     ///
     /// Returns `true` if the cells differ (dots or colors).
     fn cells_differ(current: &BrailleGrid, last: &BrailleGrid, x: usize, y: usize) -> bool {
+        Self::cell_change(current, last, x, y).is_some()
+    }
+
+    /// How cell `(x, y)` of `current` differs from the same cell of
+    /// `previous`, or `None` if it would look the same.
+    ///
+    /// A color change on a blank cell is not a change: with no ink, the
+    /// color doesn't show. Both frames must be the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::animation::{CellChange, DifferentialRenderer};
+    /// use dotmax::{BrailleGrid, Color};
+    ///
+    /// let mut previous = BrailleGrid::new(4, 1)?;
+    /// previous.set_dot(0, 0)?;
+    /// let mut current = previous.clone();
+    /// current.set_cell_color(0, 0, Color::rgb(255, 0, 0))?;
+    /// current.set_cell_color(1, 0, Color::rgb(255, 0, 0))?;
+    ///
+    /// let change = |x| DifferentialRenderer::cell_change(&current, &previous, x, 0);
+    /// assert_eq!(change(0), Some(CellChange::Color));
+    /// assert_eq!(change(1), None); // blank
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn cell_change(
+        current: &BrailleGrid,
+        previous: &BrailleGrid,
+        x: usize,
+        y: usize,
+    ) -> Option<CellChange> {
         // Compare dot patterns (using raw access for efficiency)
-        let current_patterns = current.get_raw_patterns();
-        let last_patterns = last.get_raw_patterns();
         let index = y * current.width() + x;
-
-        if current_patterns[index] != last_patterns[index] {
-            return true;
+        let pattern = current.get_raw_patterns()[index];
+        let glyph = current.get_char(x, y);
+        if pattern != previous.get_raw_patterns()[index] || glyph != previous.get_char(x, y) {
+            return Some(CellChange::Content);
         }
 
-        // Compare colors
-        if current.get_color(x, y) != last.get_color(x, y) {
-            return true;
-        }
-
-        false
+        let blank = glyph == ' ' || glyph == '\u{2800}';
+        (!blank && current.get_color(x, y) != previous.get_color(x, y)).then_some(CellChange::Color)
     }
 
     /// Returns the number of cells that would change between two frames.
@@ -322,6 +445,22 @@ impl DifferentialRenderer {
         count
     }
 
+    /// Returns the number of cells whose only change between two frames is
+    /// their color (see [`CellChange::Color`]).
+    ///
+    /// Frames of different sizes have no cells in common, so this is 0 for
+    /// them.
+    #[must_use]
+    pub fn count_color_changes(&self, current: &BrailleGrid, previous: &BrailleGrid) -> usize {
+        if current.width() != previous.width() || current.height() != previous.height() {
+            return 0;
+        }
+        (0..current.height())
+            .flat_map(|y| (0..current.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| Self::cell_change(current, previous, x, y) == Some(CellChange::Color))
+            .count()
+    }
+
     /// Returns whether the renderer has a cached previous frame.
     ///
     /// # Returns
@@ -343,6 +482,73 @@ impl DifferentialRenderer {
     }
 }
 
+/// A copy of `grid` for comparing against, without the cost of dirty
+/// tracking.
+fn untracked(grid: &BrailleGrid) -> BrailleGrid {
+    let mut copy = grid.clone();
+    copy.set_dirty_tracking(false);
+    copy
+}
+
+/// Writes the cells of `current` inside `areas` that differ from `last`,
+/// which must be the same size, and returns how many changed in content and
+/// in color only.
+///
+/// Cell `(0, 0)` is drawn at the top-left of `area`, and cells that fall
+/// outside it are skipped, as [`TerminalRenderer::render`] crops them.
+///
+/// Adjacent changed cells share one cursor move, and the foreground color is
+/// only set when it differs from the last one written.
+fn write_changes(
+    out: &mut impl Write,
+    current: &BrailleGrid,
+    last: &BrailleGrid,
+    mode: RenderMode,
+    areas: &[DirtyRect],
+    area: Rect,
+) -> io::Result<(usize, usize)> {
+    let (mut content, mut color_only) = (0, 0);
+    // Foreground color the terminal is currently set to
+    let mut active: Option<Color> = None;
+
+    let (width, height) = (usize::from(area.width), usize::from(area.height));
+    let rows = areas.iter().flat_map(|dirty| {
+        let columns = dirty.x..(dirty.x + dirty.width).min(width);
+        (dirty.y..(dirty.y + dirty.height).min(height)).map(move |y| (y, columns.clone()))
+    });
+    for (y, columns) in rows {
+        // Where the cursor is after the last write, if on this row
+        let mut cursor = None;
+        for x in columns {
+            match DifferentialRenderer::cell_change(current, last, x, y) {
+                None => continue,
+                Some(CellChange::Content) => content += 1,
+                Some(CellChange::Color) => color_only += 1,
+            }
+            if cursor != Some(x) {
+                // Safe to truncate: terminal dimensions fit in u16
+                #[allow(clippy::cast_possible_truncation)]
+                out.queue(MoveTo(area.x + x as u16, area.y + y as u16))?;
+            }
+
+            let color = current.get_color(x, y);
+            if color != active {
+                match color {
+                    Some(c) => write!(out, "\x1b[38;2;{};{};{}m", c.r, c.g, c.b)?,
+                    None => write!(out, "\x1b[0m")?,
+                }
+                active = color;
+            }
+            write!(out, "{}", display_char(current, x, y, mode))?;
+            cursor = Some(x + 1);
+        }
+    }
+    if active.is_some() {
+        write!(out, "\x1b[0m")?;
+    }
+    Ok((content, color_only))
+}
+
 impl Default for DifferentialRenderer {
     fn default() -> Self {
         Self::new()
@@ -443,6 +649,152 @@ mod tests {
         assert!(DifferentialRenderer::cells_differ(&frame2, &frame1, 0, 0));
     }
 
+    #[test]
+    fn test_color_only_changes() {
+        let red = Color::rgb(255, 0, 0);
+        let mut last = BrailleGrid::new(6, 2).unwrap();
+        for x in 0..6 {
+            last.set_dot(x * 2, 0).unwrap();
+        }
+        let mut current = last.clone();
+        // Three adjacent lit cells turn red, plus one blank cell
+        for x in 1..4 {
+            current.set_cell_color(x, 0, red).unwrap();
+        }
+        current.set_cell_color(0, 1, red).unwrap();
+
+        let renderer = DifferentialRenderer::new();
+        assert_eq!(renderer.count_changed_cells(&current, &last), 3);
+        assert_eq!(renderer.count_color_changes(&current, &last), 3);
+
+        let mut out = Vec::new();
+        let areas = whole(&current);
+        let counts = write_changes(
+            &mut out,
+            &current,
+            &last,
+            RenderMode::Braille,
+            &areas,
+            screen(&current),
+        )
+        .unwrap();
+        assert_eq!(counts, (0, 3));
+        // One cursor move, one color escape, the three glyphs, one reset
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "\x1b[1;2H\x1b[38;2;255;0;0m⠁⠁⠁\x1b[0m");
+    }
+
+    #[test]
+    fn test_write_changes_mixes_content_and_color() {
+        let red = Color::rgb(255, 0, 0);
+        let mut last = BrailleGrid::new(4, 1).unwrap();
+        last.set_dot(0, 0).unwrap();
+        last.set_dot(6, 0).unwrap();
+        let mut current = last.clone();
+        current.set_cell_color(0, 0, red).unwrap();
+        current.set_dot(2, 0).unwrap();
+        current.set_char(3, 0, '#').unwrap();
+
+        let mut out = Vec::new();
+        let areas = whole(&current);
+        let counts = write_changes(
+            &mut out,
+            &current,
+            &last,
+            RenderMode::Ascii,
+            &areas,
+            screen(&current),
+        )
+        .unwrap();
+        assert_eq!(counts, (2, 1));
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "\x1b[1;1H\x1b[38;2;255;0;0m.\x1b[0m.\x1b[1;4H#");
+    }
+
+    /// A grid area that fits all of `grid`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn screen(grid: &BrailleGrid) -> Rect {
+        Rect::new(0, 0, grid.width() as u16, grid.height() as u16)
+    }
+
+    fn whole(grid: &BrailleGrid) -> [DirtyRect; 1] {
+        [DirtyRect {
+            x: 0,
+            y: 0,
+            width: grid.width(),
+            height: grid.height(),
+        }]
+    }
+
+    #[test]
+    fn test_write_changes_within_dirty_rects() {
+        let mut current = BrailleGrid::new(300, 100).unwrap();
+        current.set_dirty_tracking(true);
+        let mut last = untracked(&current);
+        current.take_dirty_rects();
+
+        current.set_dot(20, 8).unwrap();
+        current.set_dot(22, 8).unwrap();
+        current.set_char(200, 90, '#').unwrap();
+        // Changed behind the tracker's back, so it's missed
+        last.set_dot(0, 0).unwrap();
+
+        let areas = current.take_dirty_rects().unwrap();
+        assert_eq!(areas.len(), 2);
+        let mut out = Vec::new();
+        let counts = write_changes(
+            &mut out,
+            &current,
+            &last,
+            RenderMode::Braille,
+            &areas,
+            screen(&current),
+        );
+        assert_eq!(counts.unwrap(), (3, 0));
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "\x1b[3;11H⠁⠁\x1b[91;201H#");
+    }
+
+    #[test]
+    fn test_write_changes_offsets_by_origin() {
+        let last = BrailleGrid::new(4, 2).unwrap();
+        let mut current = last.clone();
+        current.set_dot(2, 4).unwrap();
+
+        let mut out = Vec::new();
+        let areas = whole(&current);
+        let area = Rect::new(3, 2, 77, 22);
+        let counts = write_changes(&mut out, &current, &last, RenderMode::Braille, &areas, area);
+        assert_eq!(counts.unwrap(), (1, 0));
+        // Cell (1, 1) inside a safe area with 3 columns left and 2 rows top
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "\x1b[4;5H⠁");
+    }
+
+    #[test]
+    fn test_write_changes_clips_to_area() {
+        let last = BrailleGrid::new(10, 4).unwrap();
+        let mut current = last.clone();
+        current.set_dot(2, 0).unwrap();
+        // Right of and below a 4×2 grid area, e.g. the status row
+        current.set_dot(10, 0).unwrap();
+        current.set_dot(2, 8).unwrap();
+
+        let mut out = Vec::new();
+        let areas = whole(&current);
+        let counts = write_changes(
+            &mut out,
+            &current,
+            &last,
+            RenderMode::Braille,
+            &areas,
+            Rect::new(0, 0, 4, 2),
+        );
+        assert_eq!(counts.unwrap(), (1, 0));
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "\x1b[1;2H⠁");
+    }
+
     #[test]
     fn test_clone() {
         let mut renderer = DifferentialRenderer::new();
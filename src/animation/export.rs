@@ -0,0 +1,419 @@
+//! Exporting animations to files that play outside the terminal.
+//!
+//! [`GifExporter`] records [`BrailleGrid`] frames into an animated GIF.
+//! Each braille dot becomes a square of pixels, with a gap around it so the
+//! result still reads as braille, drawn in the cell's color if it has one
+//! and the exporter's foreground color if not.
+//!
+//! Requires the `image` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::animation::export::GifExporter;
+//! use dotmax::primitives::draw_circle;
+//! use dotmax::{BrailleGrid, Color};
+//! use std::time::Duration;
+//!
+//! let mut gif = GifExporter::create("pulse.gif")?
+//!     .dot_size(4)
+//!     .foreground(Color::rgb(80, 220, 120));
+//! for radius in (2..20).chain((2..20).rev()) {
+//!     let mut grid = BrailleGrid::new(30, 12)?;
+//!     draw_circle(&mut grid, 30, 24, radius)?;
+//!     gif.add_frame(&grid, Duration::from_millis(40))?;
+//! }
+//! gif.finish()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use gif::{Encoder, Frame, Repeat};
+use tracing::debug;
+
+use crate::{BrailleGrid, Color, DotmaxError};
+
+/// Most colors a GIF frame's palette can hold.
+const MAX_PALETTE: usize = 256;
+
+/// Writes [`BrailleGrid`] frames as an animated GIF.
+///
+/// Style settings apply to frames added after they are set. Every frame
+/// must be the size of the first, which fixes the GIF's dimensions. Call
+/// [`finish`](Self::finish) once the last frame is in; dropping the
+/// exporter instead still ends the file, but hides any write error.
+pub struct GifExporter<W: Write> {
+    /// The writer until the first frame, when it moves into the encoder
+    writer: Option<W>,
+    encoder: Option<Encoder<W>>,
+    /// Where the GIF goes, for errors
+    path: PathBuf,
+    /// Grid size in cells, fixed by the first frame
+    size: Option<(usize, usize)>,
+    dot_size: u16,
+    gap: u16,
+    foreground: Color,
+    background: Color,
+    looping: bool,
+    frames: usize,
+}
+
+impl GifExporter<BufWriter<File>> {
+    /// An exporter writing to a new file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        let file = File::create(path)?;
+        let mut exporter = Self::new(BufWriter::new(file));
+        exporter.path = path.to_path_buf();
+        Ok(exporter)
+    }
+}
+
+impl<W: Write> GifExporter<W> {
+    /// An exporter writing to `writer`, with 3 pixel dots, 1 pixel gaps,
+    /// white on black, and looping forever.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Some(writer),
+            encoder: None,
+            path: PathBuf::from("<writer>"),
+            size: None,
+            dot_size: 3,
+            gap: 1,
+            foreground: Color::white(),
+            background: Color::black(),
+            looping: true,
+            frames: 0,
+        }
+    }
+
+    /// Draws each dot as a `pixels × pixels` square. Values below 1 are
+    /// raised to 1.
+    #[must_use]
+    pub fn dot_size(mut self, pixels: u16) -> Self {
+        self.dot_size = pixels.max(1);
+        self
+    }
+
+    /// Leaves `pixels` of background between neighboring dots and around
+    /// the edge.
+    #[must_use]
+    pub const fn gap(mut self, pixels: u16) -> Self {
+        self.gap = pixels;
+        self
+    }
+
+    /// The color of dots in cells without a color of their own.
+    #[must_use]
+    pub const fn foreground(mut self, color: Color) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    /// The color behind the dots.
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Whether the GIF loops forever (the default) or plays once. Only
+    /// takes effect before the first frame.
+    #[must_use]
+    pub const fn looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Frames added so far.
+    #[must_use]
+    pub const fn frame_count(&self) -> usize {
+        self.frames
+    }
+
+    /// Appends `grid` as a frame shown for `delay`.
+    ///
+    /// GIF delays count hundredths of a second, so `delay` is rounded to
+    /// the nearest 10 ms. Delays under 20 ms become 20 ms, as most viewers
+    /// slow anything shorter right down.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if `grid` isn't the size
+    /// of the first frame or is too large for a GIF at the dot size, or
+    /// [`DotmaxError::GifError`] if writing fails.
+    pub fn add_frame(&mut self, grid: &BrailleGrid, delay: Duration) -> Result<(), DotmaxError> {
+        let (width, height) = grid.dimensions();
+        if self.size.is_some_and(|size| size != (width, height)) {
+            return Err(DotmaxError::InvalidDimensions { width, height });
+        }
+        let (pixel_width, pixel_height) = self.pixel_size(grid)?;
+
+        let mut frame = self.rasterize(grid, pixel_width, pixel_height);
+        frame.delay = delay_centiseconds(delay);
+
+        if self.encoder.is_none() {
+            let writer = self
+                .writer
+                .take()
+                .unwrap_or_else(|| unreachable!("the writer moves into the encoder only here"));
+            let mut encoder =
+                Encoder::new(writer, pixel_width, pixel_height, &[]).map_err(|e| self.error(&e))?;
+            if self.looping {
+                encoder
+                    .set_repeat(Repeat::Infinite)
+                    .map_err(|e| self.error(&e))?;
+            }
+            debug!(pixel_width, pixel_height, "Started GIF export");
+            self.encoder = Some(encoder);
+            self.size = Some((width, height));
+        }
+        let encoder = self.encoder.as_mut().unwrap_or_else(|| unreachable!());
+        let written = encoder.write_frame(&frame);
+        written.map_err(|e| self.error(&e))?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Ends the GIF and returns the writer, flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::GifError`] if no frames were added or the
+    /// end of the file can't be written.
+    pub fn finish(mut self) -> Result<W, DotmaxError> {
+        let Some(encoder) = self.encoder.take() else {
+            return Err(self.error(&"no frames to write"));
+        };
+        let mut writer = encoder.into_inner().map_err(|e| self.error(&e))?;
+        writer.flush()?;
+        debug!(frames = self.frames, "Finished GIF export");
+        Ok(writer)
+    }
+
+    /// The GIF's size in pixels for a grid like `grid`.
+    fn pixel_size(&self, grid: &BrailleGrid) -> Result<(u16, u16), DotmaxError> {
+        let pitch = usize::from(self.dot_size) + usize::from(self.gap);
+        let side = |dots: usize| u16::try_from(dots * pitch + usize::from(self.gap)).ok();
+        side(grid.dot_width())
+            .zip(side(grid.dot_height()))
+            .ok_or(DotmaxError::InvalidDimensions {
+                width: grid.width(),
+                height: grid.height(),
+            })
+    }
+
+    /// Draws `grid` as a frame, with an exact palette when its colors fit in
+    /// one and a quantized one when they don't.
+    fn rasterize(&self, grid: &BrailleGrid, pixel_width: u16, pixel_height: u16) -> Frame<'static> {
+        let (width, height) = grid.dimensions();
+        let cell_colors = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| grid.get_color(x, y).unwrap_or(self.foreground));
+
+        let mut palette = vec![self.background];
+        let mut lookup = HashMap::from([(self.background, 0_u8)]);
+        let mut cells = Vec::with_capacity(width * height);
+        for color in cell_colors {
+            let index = match lookup.get(&color) {
+                Some(&index) => index,
+                None if palette.len() < MAX_PALETTE => {
+                    let index = u8::try_from(palette.len()).unwrap_or(u8::MAX);
+                    palette.push(color);
+                    lookup.insert(color, index);
+                    index
+                }
+                None => return self.rasterize_rgb(grid, pixel_width, pixel_height),
+            };
+            cells.push(index);
+        }
+
+        let mut pixels = vec![0_u8; usize::from(pixel_width) * usize::from(pixel_height)];
+        self.paint(
+            grid,
+            usize::from(pixel_width),
+            |cell| cells[cell],
+            |pixel, index| {
+                pixels[pixel] = index;
+            },
+        );
+        let palette: Vec<u8> = palette.iter().flat_map(|c| [c.r, c.g, c.b]).collect();
+        Frame::from_palette_pixels(pixel_width, pixel_height, pixels, palette, None)
+    }
+
+    /// [`rasterize`](Self::rasterize) for frames with more than 256 colors.
+    fn rasterize_rgb(
+        &self,
+        grid: &BrailleGrid,
+        pixel_width: u16,
+        pixel_height: u16,
+    ) -> Frame<'static> {
+        let background = [self.background.r, self.background.g, self.background.b];
+        let mut pixels = background.repeat(usize::from(pixel_width) * usize::from(pixel_height));
+        let width = grid.width();
+        self.paint(
+            grid,
+            usize::from(pixel_width),
+            |cell| {
+                grid.get_color(cell % width, cell / width)
+                    .unwrap_or(self.foreground)
+            },
+            |pixel, color| {
+                pixels[pixel * 3..pixel * 3 + 3].copy_from_slice(&[color.r, color.g, color.b]);
+            },
+        );
+        // Speed 10 is the gif crate's suggested balance of speed and quality
+        Frame::from_rgb_speed(pixel_width, pixel_height, &pixels, 10)
+    }
+
+    /// Calls `put` for every pixel of every set dot, with the pixel's index
+    /// in a row-major image `pixel_width` wide and the value `cell` gives
+    /// for the dot's cell.
+    fn paint<T: Copy>(
+        &self,
+        grid: &BrailleGrid,
+        pixel_width: usize,
+        cell: impl Fn(usize) -> T,
+        mut put: impl FnMut(usize, T),
+    ) {
+        let (dot_size, gap) = (usize::from(self.dot_size), usize::from(self.gap));
+        let pitch = dot_size + gap;
+        for dot_y in 0..grid.dot_height() {
+            for dot_x in 0..grid.dot_width() {
+                if !grid.is_dot_set(dot_x, dot_y) {
+                    continue;
+                }
+                let value = cell(dot_y / 4 * grid.width() + dot_x / 2);
+                let (left, top) = (gap + dot_x * pitch, gap + dot_y * pitch);
+                for y in top..top + dot_size {
+                    for x in left..left + dot_size {
+                        put(y * pixel_width + x, value);
+                    }
+                }
+            }
+        }
+    }
+
+    fn error(&self, message: &impl std::fmt::Display) -> DotmaxError {
+        DotmaxError::GifError {
+            path: self.path.clone(),
+            message: format!("Failed to encode GIF: {message}"),
+        }
+    }
+}
+
+/// `delay` in whole hundredths of a second, at least 2.
+#[allow(clippy::cast_possible_truncation)] // Clamped to u16 first
+fn delay_centiseconds(delay: Duration) -> u16 {
+    let centiseconds = (delay.as_millis() + 5) / 10;
+    centiseconds.clamp(2, u128::from(u16::MAX)) as u16
+}
+
+impl<W: Write> std::fmt::Debug for GifExporter<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GifExporter")
+            .field("path", &self.path)
+            .field("size", &self.size)
+            .field("dot_size", &self.dot_size)
+            .field("gap", &self.gap)
+            .field("foreground", &self.foreground)
+            .field("background", &self.background)
+            .field("looping", &self.looping)
+            .field("frames", &self.frames)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> Vec<gif::Frame<'static>> {
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(bytes).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push(frame.clone());
+        }
+        frames
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        let mut exporter = GifExporter::new(Vec::new())
+            .dot_size(2)
+            .gap(1)
+            .foreground(Color::rgb(255, 0, 0));
+        exporter
+            .add_frame(&grid, Duration::from_millis(100))
+            .unwrap();
+        grid.set_cell_color(1, 0, Color::rgb(0, 0, 255)).unwrap();
+        grid.set_dot(3, 3).unwrap();
+        exporter.add_frame(&grid, Duration::from_millis(1)).unwrap();
+        assert_eq!(exporter.frame_count(), 2);
+        let bytes = exporter.finish().unwrap();
+
+        let frames = decode(&bytes);
+        assert_eq!(frames.len(), 2);
+        // 4×4 dots at a pitch of 3, plus the outer gap
+        assert_eq!((frames[0].width, frames[0].height), (13, 13));
+        assert_eq!((frames[0].delay, frames[1].delay), (10, 2));
+        let pixel = |frame: &gif::Frame<'_>, x: usize, y: usize| {
+            let offset = (y * 13 + x) * 4;
+            frame.buffer[offset..offset + 3].to_vec()
+        };
+        assert_eq!(pixel(&frames[0], 1, 1), [255, 0, 0]);
+        assert_eq!(pixel(&frames[0], 3, 1), [0, 0, 0]); // the gap
+        assert_eq!(pixel(&frames[1], 10, 10), [0, 0, 255]);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_and_empty() {
+        let mut exporter = GifExporter::new(Vec::new());
+        exporter
+            .add_frame(&BrailleGrid::new(4, 2).unwrap(), Duration::ZERO)
+            .unwrap();
+        assert!(matches!(
+            exporter.add_frame(&BrailleGrid::new(5, 2).unwrap(), Duration::ZERO),
+            Err(DotmaxError::InvalidDimensions {
+                width: 5,
+                height: 2
+            })
+        ));
+        assert!(GifExporter::new(Vec::new()).finish().is_err());
+        let huge = BrailleGrid::new(10_000, 1).unwrap();
+        assert!(GifExporter::new(Vec::new())
+            .add_frame(&huge, Duration::ZERO)
+            .is_err());
+    }
+
+    #[test]
+    fn test_many_colors_are_quantized() {
+        let mut grid = BrailleGrid::new(20, 20).unwrap();
+        for y in 0..20 {
+            for x in 0..20 {
+                grid.set_dot(x * 2, y * 4).unwrap();
+                grid.set_cell_color(x, y, Color::rgb((x * 12) as u8, (y * 12) as u8, 200))
+                    .unwrap();
+            }
+        }
+        let mut exporter = GifExporter::new(Vec::new());
+        exporter
+            .add_frame(&grid, Duration::from_millis(50))
+            .unwrap();
+        assert_eq!(decode(&exporter.finish().unwrap()).len(), 1);
+    }
+}
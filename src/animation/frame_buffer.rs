@@ -1,8 +1,11 @@
 //! Double-buffered frame management for flicker-free animation.
 //!
 //! This module provides [`FrameBuffer`], a double-buffering implementation that
-//! enables smooth terminal animations without tearing or flickering.
+//! enables smooth terminal animations without tearing or flickering, and
+//! [`FrameDiff`], which lists the cells that differ between the two buffers
+//! for loops that write only what changed.
 
+use crate::animation::{CellChange, DifferentialRenderer};
 use crate::error::DotmaxError;
 use crate::grid::BrailleGrid;
 use crate::render::TerminalRenderer;
@@ -196,6 +199,50 @@ impl FrameBuffer {
         std::mem::swap(&mut self.front, &mut self.back);
     }
 
+    /// Returns the cells that differ between the back buffer and the front
+    /// buffer, in row-major order.
+    ///
+    /// Call it after drawing the next frame and before
+    /// [`swap_buffers()`](Self::swap_buffers): each item is a cell whose
+    /// dots, character, or color must be written to turn the displayed
+    /// frame into the new one, with the [`CellChange`] saying which. It
+    /// uses the same rules as [`DifferentialRenderer::cell_change`], so
+    /// color changes on blank cells are skipped.
+    ///
+    /// The iterator reads both buffers in place and allocates nothing, so
+    /// render loops that don't use [`AnimationLoop`](crate::animation::AnimationLoop)
+    /// can write only the changed cells every frame at no extra cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::animation::{CellChange, FrameBuffer};
+    /// use dotmax::Color;
+    ///
+    /// let mut buffer = FrameBuffer::new(10, 5);
+    /// buffer.get_back_buffer().set_dot(4, 4)?; // cell (2, 1)
+    ///
+    /// let changed: Vec<_> = buffer.diff().collect();
+    /// assert_eq!(changed, [(2, 1, CellChange::Content)]);
+    ///
+    /// // Next frame: same dot, new color
+    /// buffer.swap_buffers();
+    /// let back = buffer.get_back_buffer();
+    /// back.enable_color_support();
+    /// back.set_dot(4, 4)?;
+    /// back.set_cell_color(2, 1, Color::rgb(255, 0, 0))?;
+    /// assert_eq!(buffer.diff().next(), Some((2, 1, CellChange::Color)));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn diff(&self) -> FrameDiff<'_> {
+        FrameDiff {
+            current: &self.back,
+            previous: &self.front,
+            index: 0,
+        }
+    }
+
     /// Renders the front buffer to the terminal.
     ///
     /// Delegates to [`TerminalRenderer::render()`] to display the current
@@ -263,6 +310,43 @@ impl FrameBuffer {
     }
 }
 
+/// Iterator over the changed cells of a [`FrameBuffer`], as
+/// `(x, y, change)` in cell coordinates.
+///
+/// Created by [`FrameBuffer::diff`].
+#[derive(Debug, Clone)]
+pub struct FrameDiff<'a> {
+    current: &'a BrailleGrid,
+    previous: &'a BrailleGrid,
+    index: usize,
+}
+
+impl Iterator for FrameDiff<'_> {
+    type Item = (usize, usize, CellChange);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.current.width();
+        let len = width * self.current.height();
+        while self.index < len {
+            let (x, y) = (self.index % width, self.index / width);
+            self.index += 1;
+            if let Some(change) =
+                DifferentialRenderer::cell_change(self.current, self.previous, x, y)
+            {
+                return Some((x, y, change));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.current.width() * self.current.height();
+        (0, Some(len - self.index))
+    }
+}
+
+impl std::iter::FusedIterator for FrameDiff<'_> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -396,6 +480,52 @@ mod tests {
         assert_ne!(buffer.get_front_buffer().cell_to_braille_char(0, 0).unwrap(), '⠀');
     }
 
+    // ========================================================================
+    // diff() Yields Changed Cells Between Back and Front
+    // ========================================================================
+
+    #[test]
+    fn test_diff_identical_buffers_is_empty() {
+        let mut buffer = FrameBuffer::new(10, 5);
+        assert_eq!(buffer.diff().count(), 0);
+
+        buffer.get_back_buffer().set_dot(0, 0).unwrap();
+        buffer.swap_buffers();
+        buffer.get_back_buffer().set_dot(0, 0).unwrap();
+        assert_eq!(buffer.diff().count(), 0);
+    }
+
+    #[test]
+    fn test_diff_reports_content_and_color_in_row_major_order() {
+        let mut buffer = FrameBuffer::new(4, 3);
+        {
+            let first = buffer.get_back_buffer();
+            first.enable_color_support();
+            first.set_dot(6, 8).unwrap(); // cell (3, 2)
+            first.set_dot(0, 4).unwrap(); // cell (0, 1)
+        }
+        buffer.swap_buffers();
+        {
+            let back = buffer.get_back_buffer();
+            back.enable_color_support();
+            back.set_dot(0, 4).unwrap();
+            back.set_cell_color(0, 1, crate::Color::rgb(0, 255, 0)).unwrap();
+            back.set_dot(2, 0).unwrap(); // cell (1, 0)
+            // Color on a blank cell doesn't show
+            back.set_cell_color(2, 2, crate::Color::rgb(0, 0, 255)).unwrap();
+        }
+
+        let changes: Vec<_> = buffer.diff().collect();
+        assert_eq!(
+            changes,
+            [
+                (1, 0, CellChange::Content),
+                (0, 1, CellChange::Color),
+                (3, 2, CellChange::Content),
+            ]
+        );
+    }
+
     // ========================================================================
     // AC #6: Unit Tests Verify Buffer Swap Correctness
     // ========================================================================
@@ -11,6 +11,11 @@
 //! - **Frame timing**: Uses [`FrameTimer`](super::FrameTimer) to maintain consistent FPS
 //! - **Terminal management**: Sets up raw mode, alternate screen, and cleanup
 //! - **Graceful exit**: Handles Ctrl+C signal for clean shutdown
+//! - **Input**: Hands keyboard and mouse events to an optional handler
+//! - **Statistics**: Optionally overlays frame rate and timings with
+//!   [`show_stats`](AnimationLoopBuilder::show_stats)
+//! - **Adaptive quality**: Optionally trades detail for speed when frames
+//!   overrun, with [`adaptive_quality`](AnimationLoopBuilder::adaptive_quality)
 //!
 //! # Example
 //!
@@ -31,6 +36,12 @@
 //! # Ok::<(), dotmax::DotmaxError>(())
 //! ```
 //!
+//! For motion that should keep its speed when frames are dropped,
+//! [`on_update`](AnimationLoopBuilder::on_update) passes a
+//! [`FrameContext`] with the time since the last frame, and
+//! [`on_fixed_update`](AnimationLoopBuilder::on_fixed_update) runs a
+//! simulation in fixed steps with interpolation for drawing.
+//!
 //! # Callback Return Values
 //!
 //! The frame callback returns `Result<bool, DotmaxError>`:
@@ -38,25 +49,70 @@
 //! - `Ok(false)`: Stop animation gracefully
 //! - `Err(...)`: Stop animation with an error
 //!
+//! A callback that panics stops the animation too: the panic is caught,
+//! the terminal is restored, and [`run`](AnimationLoop::run) returns
+//! [`DotmaxError::CallbackPanic`] with the panic message.
+//!
+//! # Input
+//!
+//! Without an event handler, `q` and Ctrl+C stop the loop. With one set
+//! through [`on_event`](AnimationLoop::on_event), every key, mouse, and
+//! resize event goes to the handler before the next frame is drawn, and
+//! only Ctrl+C stops the loop on its own. Both callbacks can share state
+//! through a [`Cell`](std::cell::Cell) or [`RefCell`](std::cell::RefCell):
+//!
+//! ```no_run
+//! use crossterm::event::KeyCode;
+//! use dotmax::animation::AnimationLoop;
+//! use std::cell::Cell;
+//!
+//! let x = Cell::new(80_usize);
+//! AnimationLoop::new(80, 24)
+//!     .on_frame(|_, buffer| {
+//!         buffer.set_dot(x.get(), 48)?;
+//!         Ok(true)
+//!     })
+//!     .on_event(|event| {
+//!         match event.key().map(|key| key.code) {
+//!             Some(KeyCode::Left) => x.set(x.get().saturating_sub(1)),
+//!             Some(KeyCode::Right) => x.set((x.get() + 1).min(159)),
+//!             Some(KeyCode::Esc) => return Ok(false),
+//!             _ => {}
+//!         }
+//!         Ok(true)
+//!     })
+//!     .run()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
 //! # Performance
 //!
 //! - Target 60fps with <10% single-core CPU usage
 //! - Buffer swap is O(1) pointer exchange (~2.4ns)
 //! - Frame timing uses efficient sleep-based rate limiting
 
-use crate::animation::{FrameBuffer, FrameTimer};
+use super::adaptive::set_frame_quality;
+use crate::animation::{
+    AdaptiveQuality, DifferentialRenderer, FrameBuffer, FrameContext, FrameTimer, Quality,
+    Timestep,
+};
 use crate::error::DotmaxError;
 use crate::grid::BrailleGrid;
-use crate::render::TerminalRenderer;
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crate::input::{EventPump, InputEvent};
+use crate::render::{render_once_to_stdout, SafeArea, TerminalRenderer};
+use crate::widgets::StatsHud;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyModifiers};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use crossterm::{
     cursor::{Hide, Show},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
+use std::cell::Cell;
 use std::io::{stdout, Write};
-use std::time::Duration;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
 /// Minimum FPS value (cannot be below 1).
@@ -68,16 +124,68 @@ const MAX_FPS: u32 = 240;
 /// Default FPS when not specified.
 const DEFAULT_FPS: u32 = 60;
 
+/// The event handler type of a loop without one.
+type NoEventHandler = fn(&InputEvent) -> Result<bool, DotmaxError>;
+
+thread_local! {
+    /// Whether this thread is inside [`catch_callback_panic`]
+    static IN_CALLBACK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Puts a hook in front of the current panic hook, once per process, that
+/// passes on every panic except those inside [`catch_callback_panic`].
+///
+/// The default hook would print those into the alternate screen in raw
+/// mode, where the message is garbled and then lost with the screen. They
+/// reach the caller in the error instead.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IN_CALLBACK.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs a user callback, turning a panic into
+/// [`DotmaxError::CallbackPanic`] so the loop can restore the terminal
+/// instead of unwinding past its cleanup.
+///
+/// The panic hook installed by [`AnimationLoop::run`] stays quiet
+/// meanwhile; elsewhere the panic is printed as usual.
+fn catch_callback_panic(
+    callback: &'static str,
+    f: impl FnOnce() -> Result<bool, DotmaxError>,
+) -> Result<bool, DotmaxError> {
+    let outer = IN_CALLBACK.with(|flag| flag.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IN_CALLBACK.with(|flag| flag.set(outer));
+
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        Err(DotmaxError::CallbackPanic { callback, message })
+    })
+}
+
 /// High-level animation loop abstraction.
 ///
 /// `AnimationLoop` provides a simple builder-pattern API for creating
 /// terminal animations. It handles all the complexity of double-buffering,
 /// frame timing, and terminal management automatically.
 ///
-/// # Type Parameter
+/// # Type Parameters
 ///
 /// - `F`: The frame callback type, which must implement
 ///   `FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>`
+/// - `H`: The event handler type, which must implement
+///   `FnMut(&InputEvent) -> Result<bool, DotmaxError>`
 ///
 /// # Examples
 ///
@@ -110,9 +218,11 @@ const DEFAULT_FPS: u32 = 60;
 ///     .run()?;
 /// # Ok::<(), dotmax::DotmaxError>(())
 /// ```
-pub struct AnimationLoop<F>
+#[allow(clippy::struct_excessive_bools)] // Independent options
+pub struct AnimationLoop<F, H = NoEventHandler>
 where
     F: FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>,
+    H: FnMut(&InputEvent) -> Result<bool, DotmaxError>,
 {
     /// Width in terminal cells (characters).
     width: usize,
@@ -120,8 +230,20 @@ where
     height: usize,
     /// Target frames per second (1-240).
     target_fps: u32,
+    /// Margins to keep clear; `None` uses the process-wide safe area.
+    safe_area: Option<SafeArea>,
+    /// Draw at the cursor instead of on the alternate screen.
+    inline: bool,
+    /// Print the last frame to the normal screen on exit.
+    keep_final_frame: bool,
+    /// Overlay frame statistics in the top-right corner.
+    show_stats: bool,
+    /// Lower frame quality while frames overrun their budget.
+    adaptive_quality: bool,
     /// Frame callback function.
     on_frame: F,
+    /// Input event handler, if any.
+    on_event: Option<H>,
 }
 
 /// Builder for constructing [`AnimationLoop`] instances.
@@ -153,6 +275,7 @@ where
 /// let builder = AnimationLoop::new(80, 24);
 /// // Default FPS is 60
 /// ```
+#[allow(clippy::struct_excessive_bools)] // Independent options
 pub struct AnimationLoopBuilder {
     /// Width in terminal cells.
     width: usize,
@@ -160,6 +283,16 @@ pub struct AnimationLoopBuilder {
     height: usize,
     /// Target FPS (default 60).
     target_fps: u32,
+    /// Margins to keep clear (default: the process-wide safe area).
+    safe_area: Option<SafeArea>,
+    /// Draw at the cursor instead of on the alternate screen (default false).
+    inline: bool,
+    /// Print the last frame to the normal screen on exit (default false).
+    keep_final_frame: bool,
+    /// Overlay frame statistics in the top-right corner (default false).
+    show_stats: bool,
+    /// Lower frame quality while frames overrun their budget (default false).
+    adaptive_quality: bool,
 }
 
 // Convenience alias for AnimationLoop::new
@@ -197,6 +330,11 @@ impl AnimationLoop<fn(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>> {
             width,
             height,
             target_fps: DEFAULT_FPS,
+            safe_area: None,
+            inline: false,
+            keep_final_frame: false,
+            show_stats: false,
+            adaptive_quality: false,
         }
     }
 }
@@ -239,6 +377,143 @@ impl AnimationLoopBuilder {
         self
     }
 
+    /// Keeps `area`'s margins free of output, for example to leave the
+    /// bottom rows to a shell prompt. Frames larger than the space inside
+    /// are clipped on the right and bottom.
+    ///
+    /// Defaults to the process-wide [`safe_area`](crate::render::safe_area).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::animation::AnimationLoop;
+    /// use dotmax::SafeArea;
+    ///
+    /// let builder = AnimationLoop::new(80, 22).safe_area(SafeArea {
+    ///     bottom: 2,
+    ///     ..SafeArea::NONE
+    /// });
+    /// ```
+    #[must_use]
+    pub const fn safe_area(mut self, area: SafeArea) -> Self {
+        self.safe_area = Some(area);
+        self
+    }
+
+    /// Draws the animation in a block `height` rows tall at the cursor,
+    /// rather than on the alternate screen. Earlier output stays in
+    /// scrollback above it, and the final frame stays on screen when the
+    /// loop ends. See [`TerminalRenderer::inline`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    ///
+    /// println!("Crunching numbers...");
+    /// AnimationLoop::new(40, 2)
+    ///     .inline()
+    ///     .on_frame(|frame, buffer| {
+    ///         for y in 0..8 {
+    ///             buffer.set_dot(frame as usize, y)?;
+    ///         }
+    ///         Ok(frame < 79)
+    ///     })
+    ///     .run()?;
+    /// println!("Done");
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn inline(mut self) -> Self {
+        self.inline = true;
+        self
+    }
+
+    /// Leaves the last frame drawn, colors included, on the normal screen
+    /// after the loop exits, instead of restoring the screen as it was.
+    /// Inline loops always keep their last frame.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    ///
+    /// AnimationLoop::new(80, 24)
+    ///     .keep_final_frame()
+    ///     .on_frame(|frame, buffer| {
+    ///         buffer.set_dot(frame as usize, 48)?;
+    ///         Ok(frame < 159)
+    ///     })
+    ///     .run()?;
+    /// // The finished line is still on screen here
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn keep_final_frame(mut self) -> Self {
+        self.keep_final_frame = true;
+        self
+    }
+
+    /// Overlays a [`StatsHud`] in the top-right corner of every frame:
+    /// frame rate, 50th/95th/99th percentile frame times, frames that
+    /// overran the budget, and bytes written to the terminal per frame.
+    /// It is drawn after the frame callback, over whatever it drew.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    ///
+    /// AnimationLoop::new(80, 24)
+    ///     .show_stats(true)
+    ///     .on_frame(|frame, buffer| {
+    ///         buffer.set_dot(frame as usize % 160, 48)?;
+    ///         Ok(true)
+    ///     })
+    ///     .run()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn show_stats(mut self, show: bool) -> Self {
+        self.show_stats = show;
+        self
+    }
+
+    /// Lowers the [`Quality`] of frames while they take longer than the
+    /// frame budget to draw and render, and raises it again once they fit
+    /// comfortably. See [`AdaptiveQuality`] for the levels.
+    ///
+    /// After the frame callback has drawn, the loop drops colors, then
+    /// merges dots into 2×2 blocks. Degraded frames are sent with a
+    /// [`DifferentialRenderer`], so only the cells that changed are written.
+    /// This cuts the bytes sent to the terminal, which is what slows frames
+    /// over SSH, but the callback still draws every frame in full. To save
+    /// drawing work too, the callback reads the level with
+    /// [`frame_quality`](super::frame_quality) and skips what it can, such
+    /// as dithering from [`NoDithering`](Quality::NoDithering) down; a
+    /// callback that doesn't gets no change at that level. Changes are
+    /// logged at `info` level.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    ///
+    /// AnimationLoop::new(200, 60)
+    ///     .adaptive_quality(true)
+    ///     .on_frame(|frame, buffer| {
+    ///         buffer.set_dot(frame as usize % 400, 120)?;
+    ///         Ok(true)
+    ///     })
+    ///     .run()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn adaptive_quality(mut self, adaptive: bool) -> Self {
+        self.adaptive_quality = adaptive;
+        self
+    }
+
     /// Sets the frame callback and builds the [`AnimationLoop`].
     ///
     /// The callback is called once per frame with:
@@ -289,21 +564,190 @@ impl AnimationLoopBuilder {
             width: self.width,
             height: self.height,
             target_fps: self.target_fps,
+            safe_area: self.safe_area,
+            inline: self.inline,
+            keep_final_frame: self.keep_final_frame,
+            show_stats: self.show_stats,
+            adaptive_quality: self.adaptive_quality,
             on_frame: callback,
+            on_event: None,
         }
     }
+
+    /// Sets a frame callback that gets a [`FrameContext`] with the time
+    /// since the last frame, and builds the [`AnimationLoop`].
+    ///
+    /// Scaling motion by [`dt`](FrameContext::dt) keeps its speed the same
+    /// whatever the frame rate, and when frames are dropped. Otherwise the
+    /// callback works like [`on_frame`](Self::on_frame)'s.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    ///
+    /// // 40 dots per second, at any frame rate
+    /// let mut x = 0.0_f32;
+    /// AnimationLoop::new(80, 24)
+    ///     .on_update(|ctx, buffer| {
+    ///         x = (x + 40.0 * ctx.dt_secs()) % 160.0;
+    ///         buffer.set_dot(x as usize, 48)?;
+    ///         Ok(true)
+    ///     })
+    ///     .run()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn on_update<U>(
+        self,
+        mut callback: U,
+    ) -> AnimationLoop<impl FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>>
+    where
+        U: FnMut(&FrameContext, &mut BrailleGrid) -> Result<bool, DotmaxError>,
+    {
+        let mut timestep = Timestep::variable();
+        self.on_frame(move |_, buffer| {
+            let (_, ctx) = timestep.advance(Instant::now());
+            callback(&ctx, buffer)
+        })
+    }
+
+    /// Runs `simulate` in fixed steps of `step`, then draws with `draw`,
+    /// and builds the [`AnimationLoop`].
+    ///
+    /// Before each frame, `simulate` is called with `step` once for every
+    /// whole step of time that has passed, up to [`Timestep::MAX_STEPS`].
+    /// `draw` then gets a [`FrameContext`] whose
+    /// [`alpha`](FrameContext::alpha) says how far the clock is into the
+    /// next step, for interpolating between the last two simulated states.
+    /// The simulation stays deterministic and stable however the frames
+    /// fall. An error from either callback stops the loop.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    /// use std::cell::Cell;
+    /// use std::time::Duration;
+    ///
+    /// // Falling dot, simulated at 120 Hz and drawn at 30 FPS
+    /// let state = Cell::new((0.0_f32, 0.0_f32)); // (previous y, y)
+    /// let velocity = Cell::new(0.0_f32);
+    /// AnimationLoop::new(80, 24)
+    ///     .fps(30)
+    ///     .on_fixed_update(
+    ///         Duration::from_secs_f32(1.0 / 120.0),
+    ///         |step| {
+    ///             let (_, y) = state.get();
+    ///             velocity.set(velocity.get() + 200.0 * step.as_secs_f32());
+    ///             state.set((y, (y + velocity.get() * step.as_secs_f32()).min(95.0)));
+    ///             Ok(())
+    ///         },
+    ///         |ctx, buffer| {
+    ///             let (previous, y) = state.get();
+    ///             let shown = previous + (y - previous) * ctx.alpha;
+    ///             buffer.set_dot(80, shown as usize)?;
+    ///             Ok(y < 95.0)
+    ///         },
+    ///     )
+    ///     .run()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn on_fixed_update<S, D>(
+        self,
+        step: Duration,
+        mut simulate: S,
+        mut draw: D,
+    ) -> AnimationLoop<impl FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>>
+    where
+        S: FnMut(Duration) -> Result<(), DotmaxError>,
+        D: FnMut(&FrameContext, &mut BrailleGrid) -> Result<bool, DotmaxError>,
+    {
+        let mut timestep = Timestep::fixed(step);
+        let step = timestep.step().unwrap_or(step);
+        self.on_frame(move |_, buffer| {
+            let (steps, ctx) = timestep.advance(Instant::now());
+            for _ in 0..steps {
+                simulate(step)?;
+            }
+            draw(&ctx, buffer)
+        })
+    }
 }
 
-impl<F> AnimationLoop<F>
+impl<F, H> AnimationLoop<F, H>
 where
     F: FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>,
+    H: FnMut(&InputEvent) -> Result<bool, DotmaxError>,
 {
+    /// Sets a handler for keyboard, mouse, and resize events, and turns on
+    /// mouse capture while the loop runs.
+    ///
+    /// Events that arrived since the last frame are handed over, in order,
+    /// just before the next frame is drawn. `q` no longer stops the loop,
+    /// so games can use it; Ctrl+C still does.
+    ///
+    /// # Handler Return Values
+    ///
+    /// - `Ok(true)`: Keep running
+    /// - `Ok(false)`: Stop the animation gracefully
+    /// - `Err(...)`: Stop with an error
+    ///
+    /// # Examples
+    ///
+    /// Paint with the mouse:
+    ///
+    /// ```no_run
+    /// use dotmax::animation::AnimationLoop;
+    /// use dotmax::input::mouse::{Gesture, Mouse};
+    /// use std::cell::RefCell;
+    ///
+    /// let strokes = RefCell::new(Vec::new());
+    /// let mut mouse = Mouse::new();
+    /// AnimationLoop::new(80, 24)
+    ///     .on_frame(|_, buffer| {
+    ///         for &(x, y) in strokes.borrow().iter() {
+    ///             buffer.set_dot(x, y)?;
+    ///         }
+    ///         Ok(true)
+    ///     })
+    ///     .on_event(|event| {
+    ///         match mouse.update(event) {
+    ///             Some(Gesture::Press { x, y, .. }) => strokes.borrow_mut().push((x, y)),
+    ///             Some(Gesture::Drag { to, .. }) => strokes.borrow_mut().push(to),
+    ///             _ => {}
+    ///         }
+    ///         Ok(event.key().is_none())  // Any key stops
+    ///     })
+    ///     .run()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn on_event<E>(self, handler: E) -> AnimationLoop<F, E>
+    where
+        E: FnMut(&InputEvent) -> Result<bool, DotmaxError>,
+    {
+        AnimationLoop {
+            width: self.width,
+            height: self.height,
+            target_fps: self.target_fps,
+            safe_area: self.safe_area,
+            inline: self.inline,
+            keep_final_frame: self.keep_final_frame,
+            show_stats: self.show_stats,
+            adaptive_quality: self.adaptive_quality,
+            on_frame: self.on_frame,
+            on_event: Some(handler),
+        }
+    }
+
     /// Runs the animation loop until stopped.
     ///
     /// This method blocks until:
-    /// - The callback returns `Ok(false)`
-    /// - The callback returns `Err(...)`
-    /// - Ctrl+C is pressed
+    /// - The callback or event handler returns `Ok(false)`
+    /// - The callback or event handler returns `Err(...)`
+    /// - Ctrl+C is pressed, or `q` without an event handler
     ///
     /// The method handles all terminal setup (raw mode, alternate screen,
     /// cursor hiding) and cleanup automatically, even on error.
@@ -320,19 +764,26 @@ where
     /// - Rendering to terminal fails during animation
     /// - Terminal cleanup fails on exit
     ///
-    /// Returns the error from the callback if it returns `Err(...)`.
+    /// Returns the error from the callback if it returns `Err(...)`, and
+    /// [`DotmaxError::CallbackPanic`] if the callback or event handler
+    /// panics.
     ///
     /// # Terminal State
     ///
     /// On entry:
     /// - Enables raw mode for unbuffered input
-    /// - Enters alternate screen to preserve original content
+    /// - Enters alternate screen to preserve original content (unless
+    ///   [`inline`](AnimationLoopBuilder::inline))
     /// - Hides cursor for clean animation
+    /// - Captures the mouse, if there's an event handler
     ///
     /// On exit (any path):
+    /// - Releases the mouse
     /// - Shows cursor
-    /// - Leaves alternate screen
+    /// - Leaves alternate screen, or moves below the inline block
     /// - Disables raw mode
+    /// - Prints the last frame, if
+    ///   [`keep_final_frame`](AnimationLoopBuilder::keep_final_frame) is set
     ///
     /// # Examples
     ///
@@ -356,28 +807,63 @@ where
             "Starting animation loop"
         );
 
+        install_panic_hook();
+
         // Setup terminal
         let mut stdout = stdout();
         enable_raw_mode()?;
-        execute!(stdout, EnterAlternateScreen, Hide)?;
+        if self.inline {
+            execute!(stdout, Hide)?;
+        } else {
+            execute!(stdout, EnterAlternateScreen, Hide)?;
+        }
+        let mouse = self.on_event.is_some();
+        if mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
 
         // Use a guard pattern to ensure cleanup on any exit path
         let result = self.run_inner();
 
         // Cleanup terminal (always runs)
-        let cleanup_result = Self::cleanup_terminal(&mut stdout);
+        let cleanup_result = Self::cleanup_terminal(&mut stdout, self.inline, mouse);
 
         // Return first error if any
-        result.and(cleanup_result)
+        let final_frame = result.and_then(|frame| cleanup_result.map(|()| frame))?;
+        if let Some(grid) = final_frame {
+            // Back on the normal screen, so it stays there
+            render_once_to_stdout(&grid)?;
+        }
+        Ok(())
     }
 
-    /// Inner animation loop, separated for cleanup guard pattern.
-    fn run_inner(&mut self) -> Result<(), DotmaxError> {
+    /// Inner animation loop, separated for cleanup guard pattern. Returns
+    /// the last frame shown if it should be kept.
+    fn run_inner(&mut self) -> Result<Option<BrailleGrid>, DotmaxError> {
         // Create animation infrastructure
         let mut frame_buffer = FrameBuffer::new(self.width, self.height);
         let mut frame_timer = FrameTimer::new(self.target_fps);
-        let mut renderer = TerminalRenderer::new()?;
+        let mut renderer = if self.inline {
+            let height =
+                u16::try_from(self.height).map_err(|_| DotmaxError::InvalidDimensions {
+                    width: self.width,
+                    height: self.height,
+                })?;
+            TerminalRenderer::inline(height)?
+        } else {
+            TerminalRenderer::new()?
+        };
+        if let Some(area) = self.safe_area {
+            renderer.set_safe_area(area);
+        }
+        let mut events = EventPump::new();
         let mut frame_num: u64 = 0;
+        let mut hud = self.show_stats.then(|| StatsHud::new(self.target_fps));
+        let mut adaptive = self
+            .adaptive_quality
+            .then(|| AdaptiveQuality::new(self.target_fps));
+        let mut differential = DifferentialRenderer::new();
+        let mut last_start = Instant::now();
 
         debug!(
             width = self.width,
@@ -387,39 +873,64 @@ where
         );
 
         loop {
-            // Check for Ctrl+C with non-blocking poll
-            if event::poll(Duration::ZERO)? {
-                if let Event::Key(key) = event::read()? {
-                    if key.code == KeyCode::Char('c')
-                        && key.modifiers.contains(KeyModifiers::CONTROL)
-                    {
-                        info!("Ctrl+C detected, stopping animation gracefully");
-                        break;
-                    }
-                    // Also allow 'q' to quit for convenience
-                    if key.code == KeyCode::Char('q') {
-                        debug!("'q' pressed, stopping animation");
-                        break;
-                    }
-                }
+            let started = Instant::now();
+            let bytes_before = renderer.bytes_written();
+
+            // Check for Ctrl+C and pass input on without blocking
+            if !self.handle_events(&mut events)? {
+                break;
             }
 
             // Clear back buffer before each frame
             frame_buffer.get_back_buffer().clear();
 
             // Call user's frame callback
-            let should_continue = (self.on_frame)(frame_num, frame_buffer.get_back_buffer())?;
+            let quality = adaptive
+                .as_ref()
+                .map_or(Quality::Full, AdaptiveQuality::quality);
+            let should_continue =
+                self.draw_at_quality(frame_num, frame_buffer.get_back_buffer(), quality)?;
 
             if !should_continue {
                 debug!(frame = frame_num, "Callback returned false, stopping");
                 break;
             }
 
+            // Statistics so far go over the callback's drawing
+            if let Some(hud) = &hud {
+                hud.render(frame_buffer.get_back_buffer());
+            }
+
             // Swap buffers (O(1) pointer swap)
             frame_buffer.swap_buffers();
 
-            // Render front buffer to terminal
-            frame_buffer.render(&mut renderer)?;
+            // Render front buffer to terminal; degraded frames send only
+            // the cells that changed
+            if quality == Quality::Full {
+                frame_buffer.render(&mut renderer)?;
+            } else {
+                differential.render_diff(frame_buffer.get_front_buffer(), &mut renderer)?;
+            }
+
+            if let Some(hud) = &mut hud {
+                hud.record(
+                    started.elapsed(),
+                    started.duration_since(last_start),
+                    renderer.bytes_written() - bytes_before,
+                );
+            }
+            last_start = started;
+            if let Some(adaptive) = &mut adaptive {
+                if let Some(quality) = adaptive.record(started.elapsed()) {
+                    info!(?quality, frame = frame_num, "Frame quality changed");
+                    if quality == Quality::Full {
+                        // Differential output went around ratatui, so its
+                        // record of the screen is stale
+                        renderer.clear()?;
+                        differential.invalidate();
+                    }
+                }
+            }
 
             // Wait for next frame timing
             frame_timer.wait_for_next_frame();
@@ -433,13 +944,82 @@ where
             "Animation completed"
         );
 
-        Ok(())
+        let keep = self.keep_final_frame && !self.inline && frame_num > 0;
+        Ok(keep.then(|| frame_buffer.get_front_buffer().clone()))
+    }
+
+    /// Takes every pending event, stopping at Ctrl+C or when the handler
+    /// (or `q`, without one) asks to. Returns whether to keep running.
+    fn handle_events(&mut self, events: &mut EventPump) -> Result<bool, DotmaxError> {
+        for event in events.pending()? {
+            if let Some(key) = event.key() {
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    info!("Ctrl+C detected, stopping animation gracefully");
+                    return Ok(false);
+                }
+            }
+            if let Some(handler) = self.on_event.as_mut() {
+                if !catch_callback_panic("event", || handler(&event))? {
+                    debug!("Event handler returned false, stopping");
+                    return Ok(false);
+                }
+            } else if event
+                .key()
+                .is_some_and(|key| key.code == KeyCode::Char('q'))
+            {
+                // Also allow 'q' to quit for convenience
+                debug!("'q' pressed, stopping animation");
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Draws frame number `frame` into `buffer` with the frame callback,
+    /// catching a panic. Shared by [`run`](Self::run) and other drivers,
+    /// such as [`testing::soak`](crate::testing::soak).
+    pub(crate) fn draw_frame(
+        &mut self,
+        frame: u64,
+        buffer: &mut BrailleGrid,
+    ) -> Result<bool, DotmaxError> {
+        catch_callback_panic("frame", || (self.on_frame)(frame, buffer))
+    }
+
+    /// Draws frame number `frame` into `buffer` at `quality`: the callback
+    /// sees the level through [`frame_quality`](super::frame_quality), and
+    /// what it drew is then [degraded](Quality::apply) to match.
+    fn draw_at_quality(
+        &mut self,
+        frame: u64,
+        buffer: &mut BrailleGrid,
+        quality: Quality,
+    ) -> Result<bool, DotmaxError> {
+        set_frame_quality(quality);
+        let result = self.draw_frame(frame, buffer);
+        set_frame_quality(Quality::Full);
+        if matches!(result, Ok(true)) {
+            quality.apply(buffer);
+        }
+        result
     }
 
     /// Cleanup terminal state.
-    fn cleanup_terminal(stdout: &mut std::io::Stdout) -> Result<(), DotmaxError> {
+    fn cleanup_terminal(
+        stdout: &mut std::io::Stdout,
+        inline: bool,
+        mouse: bool,
+    ) -> Result<(), DotmaxError> {
+        if mouse {
+            execute!(stdout, DisableMouseCapture)?;
+        }
         // Show cursor, leave alternate screen, disable raw mode
-        execute!(stdout, Show, LeaveAlternateScreen)?;
+        if inline {
+            // The renderer already moved below its block
+            execute!(stdout, Show)?;
+        } else {
+            execute!(stdout, Show, LeaveAlternateScreen)?;
+        }
         disable_raw_mode()?;
         stdout.flush()?;
         debug!("Terminal state restored");
@@ -655,6 +1235,203 @@ mod tests {
         assert_eq!(anim.height(), 100);
     }
 
+    #[test]
+    fn test_frame_callback_panic_becomes_error() {
+        let mut anim = AnimationLoop::new(10, 10).on_frame(|frame, _| {
+            assert!(frame < 2, "frame {frame} is too late");
+            Ok(true)
+        });
+        let mut buffer = BrailleGrid::new(10, 10).unwrap();
+
+        assert!(anim.draw_frame(1, &mut buffer).unwrap());
+        match anim.draw_frame(2, &mut buffer) {
+            Err(DotmaxError::CallbackPanic { callback, message }) => {
+                assert_eq!(callback, "frame");
+                assert_eq!(message, "frame 2 is too late");
+            }
+            other => panic!("expected CallbackPanic, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_panic_payloads() {
+        let message = |f: fn() -> Result<bool, DotmaxError>| match catch_callback_panic("event", f)
+        {
+            Err(DotmaxError::CallbackPanic { message, .. }) => message,
+            other => panic!("expected CallbackPanic, got {other:?}"),
+        };
+        assert_eq!(message(|| panic!("static")), "static");
+        assert_eq!(
+            message(|| std::panic::panic_any(42_u8)),
+            "non-string panic payload"
+        );
+        assert!(catch_callback_panic("event", || Ok(false)).is_ok());
+    }
+
+    #[test]
+    fn test_callback_panic_resets_flag() {
+        let result = catch_callback_panic("frame", || {
+            assert!(IN_CALLBACK.with(Cell::get));
+            panic!("quiet")
+        });
+        assert!(matches!(result, Err(DotmaxError::CallbackPanic { .. })));
+        assert!(!IN_CALLBACK.with(Cell::get));
+    }
+
+    #[test]
+    fn test_on_update_passes_frame_context() {
+        use std::cell::RefCell;
+
+        let seen = RefCell::new(Vec::new());
+        let mut anim = AnimationLoop::new(4, 4).on_update(|ctx, _| {
+            seen.borrow_mut().push(*ctx);
+            Ok(true)
+        });
+        let mut buffer = BrailleGrid::new(4, 4).unwrap();
+        for frame in 0..3 {
+            anim.draw_frame(frame, &mut buffer).unwrap();
+        }
+        drop(anim);
+
+        let seen = seen.into_inner();
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[0].dt, Duration::ZERO);
+        assert_eq!(seen[2].frame_index, 2);
+        assert_eq!(seen[2].elapsed, seen[1].dt + seen[2].dt);
+    }
+
+    #[test]
+    fn test_on_fixed_update_simulates_before_drawing() {
+        use std::cell::Cell;
+
+        // A step no test run will reach: nothing is simulated
+        let steps = Cell::new(0);
+        let mut anim = AnimationLoop::new(4, 4).on_fixed_update(
+            Duration::from_secs(3600),
+            |_| {
+                steps.set(steps.get() + 1);
+                Ok(())
+            },
+            |ctx, _| Ok(ctx.alpha < 1.0),
+        );
+        let mut buffer = BrailleGrid::new(4, 4).unwrap();
+        assert!(anim.draw_frame(0, &mut buffer).unwrap());
+        assert!(anim.draw_frame(1, &mut buffer).unwrap());
+        drop(anim);
+        assert_eq!(steps.get(), 0);
+    }
+
+    #[test]
+    fn test_show_stats_carries_through_on_event() {
+        let anim = AnimationLoop::new(80, 24).on_frame(|_, _| Ok(false));
+        assert!(!anim.show_stats);
+
+        let anim = AnimationLoop::new(80, 24)
+            .show_stats(true)
+            .on_frame(|_, _| Ok(false))
+            .on_event(|_| Ok(true));
+        assert!(anim.show_stats);
+    }
+
+    #[test]
+    fn test_adaptive_quality_carries_through_on_event() {
+        let anim = AnimationLoop::new(80, 24).on_frame(|_, _| Ok(false));
+        assert!(!anim.adaptive_quality);
+
+        let anim = AnimationLoop::new(80, 24)
+            .adaptive_quality(true)
+            .on_frame(|_, _| Ok(false))
+            .on_event(|_| Ok(true));
+        assert!(anim.adaptive_quality);
+    }
+
+    #[test]
+    fn test_adaptive_quality_degrades_callbacks_that_ignore_it() {
+        use crate::Color;
+
+        let mut anim = AnimationLoop::new(2, 1).on_frame(|_, buffer| {
+            buffer.enable_color_support();
+            buffer.set_cell_color(1, 0, Color::rgb(0, 0, 255))?;
+            buffer.set_dot(0, 0)?;
+            buffer.set_dot(3, 3)?;
+            Ok(true)
+        });
+        let mut full = BrailleGrid::new(2, 1).unwrap();
+        assert!(anim.draw_at_quality(0, &mut full, Quality::Full).unwrap());
+
+        for quality in [
+            Quality::NoDithering,
+            Quality::Monochrome,
+            Quality::LowResolution,
+        ] {
+            let mut buffer = BrailleGrid::new(2, 1).unwrap();
+            assert!(anim.draw_at_quality(0, &mut buffer, quality).unwrap());
+            let mut expected = full.clone();
+            quality.apply(&mut expected);
+            assert_eq!(buffer.get_raw_patterns(), expected.get_raw_patterns());
+            assert_eq!(buffer.get_color(1, 0), expected.get_color(1, 0));
+        }
+
+        // Without frame_quality() there is nothing to skip at NoDithering
+        let mut buffer = BrailleGrid::new(2, 1).unwrap();
+        assert!(anim
+            .draw_at_quality(0, &mut buffer, Quality::NoDithering)
+            .unwrap());
+        assert_eq!(buffer.get_raw_patterns(), full.get_raw_patterns());
+        assert_eq!(buffer.get_color(1, 0), full.get_color(1, 0));
+    }
+
+    #[test]
+    fn test_adaptive_quality_steps_through_every_level() {
+        use crate::animation::frame_quality;
+        use crate::Color;
+        use std::cell::RefCell;
+
+        let seen = RefCell::new(Vec::new());
+        let mut anim = AnimationLoop::new(1, 1)
+            .adaptive_quality(true)
+            .on_frame(|_, buffer| {
+                seen.borrow_mut().push(frame_quality());
+                buffer.enable_color_support();
+                buffer.set_cell_color(0, 0, Color::rgb(255, 0, 0))?;
+                buffer.set_dot(0, 0)?;
+                Ok(true)
+            });
+        let mut adaptive = AdaptiveQuality::new(anim.target_fps());
+        let mut changes = Vec::new();
+
+        // 25ms frames overrun a 16.7ms budget, 2ms frames leave room
+        let slow = std::iter::repeat(Duration::from_millis(25)).take(120);
+        let fast = std::iter::repeat(Duration::from_millis(2)).take(120);
+        for (frame, frame_time) in (0..).zip(slow.chain(fast)) {
+            let quality = adaptive.quality();
+            let mut buffer = BrailleGrid::new(1, 1).unwrap();
+            assert!(anim.draw_at_quality(frame, &mut buffer, quality).unwrap());
+            assert_eq!(frame_quality(), Quality::Full);
+
+            let colored = buffer.get_color(0, 0).is_some();
+            assert_eq!(colored, quality < Quality::Monochrome, "{quality:?}");
+            let coarse = buffer.get_raw_patterns()[0] == 0x1B;
+            assert_eq!(coarse, quality == Quality::LowResolution, "{quality:?}");
+
+            changes.extend(adaptive.record(frame_time));
+        }
+
+        let levels = [
+            Quality::Full,
+            Quality::NoDithering,
+            Quality::Monochrome,
+            Quality::LowResolution,
+            Quality::Monochrome,
+            Quality::NoDithering,
+            Quality::Full,
+        ];
+        assert_eq!(changes, levels[1..]);
+        let mut seen = seen.into_inner();
+        seen.dedup();
+        assert_eq!(seen, levels);
+    }
+
     #[test]
     fn test_builder_method_chaining() {
         // Verify builder pattern works correctly
@@ -48,14 +48,24 @@
 //! - Designed for 60+ fps animations
 //! - Memory efficient: buffers are reused, not reallocated
 
+mod adaptive;
 mod differential;
+#[cfg(feature = "image")]
+pub mod export;
 mod frame_buffer;
 mod loop_helper;
 mod prerender;
+mod source;
+mod timestep;
 mod timing;
+mod transition;
 
-pub use differential::DifferentialRenderer;
-pub use frame_buffer::FrameBuffer;
+pub use adaptive::{frame_quality, AdaptiveQuality, Quality};
+pub use differential::{CellChange, DifferentialRenderer};
+pub use frame_buffer::{FrameBuffer, FrameDiff};
 pub use loop_helper::{AnimationLoop, AnimationLoopBuilder};
 pub use prerender::PrerenderedAnimation;
+pub use source::FrameSource;
+pub use timestep::{FrameContext, Timestep};
 pub use timing::FrameTimer;
+pub use transition::{cut, dissolve, wipe, Transition};
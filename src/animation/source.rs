@@ -0,0 +1,74 @@
+//! Pluggable frame producers.
+//!
+//! A [`FrameSource`] yields a sequence of [`BrailleGrid`] frames, each with the
+//! time it should stay on screen. It is the common shape for anything a player
+//! can show — generative visuals, dashboards, decoded media — and what the
+//! [`registry`](crate::registry) hands out by name.
+
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use std::time::Duration;
+
+/// A producer of timed braille frames.
+///
+/// Unlike the media module's `MediaPlayer`, which decodes at a size
+/// fixed when it was opened, a frame source is asked for each frame at the
+/// current output size, so generative sources can follow terminal resizes.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::animation::FrameSource;
+/// use dotmax::{BrailleGrid, DotmaxError};
+/// use std::time::Duration;
+///
+/// /// Sweeps a vertical line across the screen, then ends.
+/// struct Sweep {
+///     x: usize,
+/// }
+///
+/// impl FrameSource for Sweep {
+///     fn next_frame(
+///         &mut self,
+///         width: usize,
+///         height: usize,
+///     ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+///         let mut grid = match BrailleGrid::new(width, height) {
+///             Ok(grid) => grid,
+///             Err(e) => return Some(Err(e)),
+///         };
+///         if self.x >= grid.dot_width() {
+///             return None;
+///         }
+///         for y in 0..grid.dot_height() {
+///             grid.set_dot(self.x, y).ok()?;
+///         }
+///         self.x += 1;
+///         Some(Ok((grid, Duration::from_millis(16))))
+///     }
+///
+///     fn reset(&mut self) {
+///         self.x = 0;
+///     }
+/// }
+///
+/// let mut sweep = Sweep { x: 0 };
+/// let (frame, delay) = sweep.next_frame(4, 1).unwrap()?;
+/// assert!(!frame.is_empty(0, 0));
+/// assert_eq!(delay, Duration::from_millis(16));
+/// # Ok::<(), DotmaxError>(())
+/// ```
+pub trait FrameSource: Send {
+    /// Produce the next frame at `width`×`height` cells and how long to show it.
+    ///
+    /// Returns `None` once the source is exhausted; endless sources never do.
+    fn next_frame(
+        &mut self,
+        width: usize,
+        height: usize,
+    ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>>;
+
+    /// Rewind to the first frame. The default does nothing, which suits
+    /// live or generative sources.
+    fn reset(&mut self) {}
+}
@@ -0,0 +1,213 @@
+//! Frame-rate independent timing for animation callbacks.
+//!
+//! A callback that moves things a fixed distance per frame slows down and
+//! stutters when frames are dropped. [`AnimationLoop::on_update`] passes a
+//! [`FrameContext`] instead, with the real time since the last frame, so
+//! motion can be scaled by `dt`. For physics that needs a constant step,
+//! [`AnimationLoop::on_fixed_update`] runs the simulation in fixed steps,
+//! as many as the elapsed time calls for, and draws with
+//! [`alpha`](FrameContext::alpha) set to how far the clock is into the next
+//! step, so positions can be interpolated between the last two states.
+//!
+//! [`Timestep`] does the bookkeeping for both and can drive a hand-written
+//! loop too.
+//!
+//! [`AnimationLoop::on_update`]: super::AnimationLoopBuilder::on_update
+//! [`AnimationLoop::on_fixed_update`]: super::AnimationLoopBuilder::on_fixed_update
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use dotmax::animation::Timestep;
+//!
+//! let start = Instant::now();
+//! let mut timestep = Timestep::fixed(Duration::from_millis(10));
+//! timestep.advance(start);
+//!
+//! // 25ms later: two whole steps to simulate, half of the next one elapsed
+//! let (steps, ctx) = timestep.advance(start + Duration::from_millis(25));
+//! assert_eq!(steps, 2);
+//! assert!((ctx.alpha - 0.5).abs() < 1e-6);
+//! ```
+
+use std::time::{Duration, Instant};
+
+/// Timing of the frame being drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameContext {
+    /// Frame number, starting at 0
+    pub frame_index: u64,
+    /// Time since the previous frame, at most [`Timestep::MAX_DELTA`]; zero
+    /// for the first frame
+    pub dt: Duration,
+    /// Sum of every `dt` so far
+    pub elapsed: Duration,
+    /// With a fixed timestep, the fraction (`0.0..1.0`) of a step elapsed
+    /// since the last one ran; `0.0` otherwise
+    pub alpha: f32,
+}
+
+impl FrameContext {
+    /// [`dt`](Self::dt) in seconds, for multiplying velocities.
+    #[must_use]
+    pub fn dt_secs(&self) -> f32 {
+        self.dt.as_secs_f32()
+    }
+}
+
+/// Turns clock readings into [`FrameContext`]s, with variable or fixed
+/// steps.
+#[derive(Debug, Clone)]
+pub struct Timestep {
+    step: Option<Duration>,
+    last: Option<Instant>,
+    frame_index: u64,
+    elapsed: Duration,
+    accumulator: Duration,
+}
+
+impl Timestep {
+    /// Longest `dt` handed out. A frame after a pause or a debugger stop
+    /// would otherwise move everything by the length of the stall.
+    pub const MAX_DELTA: Duration = Duration::from_millis(250);
+
+    /// Most fixed steps run in one frame. When simulation can't keep up,
+    /// the rest of the backlog is dropped instead of growing every frame.
+    pub const MAX_STEPS: u32 = 8;
+
+    /// One `dt` per frame, however long the frame took.
+    #[must_use]
+    pub const fn variable() -> Self {
+        Self::with_step(None)
+    }
+
+    /// Simulation in steps of `step` (at least 1µs), decoupled from the
+    /// frame rate.
+    #[must_use]
+    pub fn fixed(step: Duration) -> Self {
+        Self::with_step(Some(step.max(Duration::from_micros(1))))
+    }
+
+    const fn with_step(step: Option<Duration>) -> Self {
+        Self {
+            step,
+            last: None,
+            frame_index: 0,
+            elapsed: Duration::ZERO,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// The fixed step, if any.
+    #[must_use]
+    pub const fn step(&self) -> Option<Duration> {
+        self.step
+    }
+
+    /// Starts a frame at `now`. Returns how many fixed steps to simulate
+    /// before drawing (always 0 with a variable timestep) and the
+    /// context to draw with.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn advance(&mut self, now: Instant) -> (u32, FrameContext) {
+        let dt = self
+            .last
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last))
+            .min(Self::MAX_DELTA);
+        self.last = Some(now);
+        self.elapsed += dt;
+
+        let (steps, alpha) = match self.step {
+            Some(step) => {
+                self.accumulator += dt;
+                let due = self.accumulator.as_nanos() / step.as_nanos();
+                let steps = due.min(u128::from(Self::MAX_STEPS)) as u32;
+                if due > u128::from(steps) {
+                    // Drop the backlog rather than fall further behind
+                    self.accumulator = Duration::ZERO;
+                } else {
+                    self.accumulator -= step * steps;
+                }
+                let alpha = self.accumulator.as_secs_f64() / step.as_secs_f64();
+                (steps, alpha as f32)
+            }
+            None => (0, 0.0),
+        };
+
+        let ctx = FrameContext {
+            frame_index: self.frame_index,
+            dt,
+            elapsed: self.elapsed,
+            alpha,
+        };
+        self.frame_index += 1;
+        (steps, ctx)
+    }
+}
+
+impl Default for Timestep {
+    fn default() -> Self {
+        Self::variable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_variable_dt_and_elapsed() {
+        let start = Instant::now();
+        let mut timestep = Timestep::variable();
+
+        let (steps, first) = timestep.advance(start);
+        assert_eq!((steps, first.frame_index, first.dt), (0, 0, Duration::ZERO));
+
+        timestep.advance(start + ms(16));
+        let (_, ctx) = timestep.advance(start + ms(50));
+        assert_eq!(ctx.frame_index, 2);
+        assert_eq!(ctx.dt, ms(34));
+        assert_eq!(ctx.elapsed, ms(50));
+        assert_eq!(ctx.alpha, 0.0);
+    }
+
+    #[test]
+    fn test_long_stalls_are_clamped() {
+        let start = Instant::now();
+        let mut timestep = Timestep::variable();
+        timestep.advance(start);
+        let (_, ctx) = timestep.advance(start + Duration::from_secs(10));
+        assert_eq!(ctx.dt, Timestep::MAX_DELTA);
+    }
+
+    #[test]
+    fn test_fixed_steps_carry_the_remainder() {
+        let start = Instant::now();
+        let mut timestep = Timestep::fixed(ms(10));
+        assert_eq!(timestep.step(), Some(ms(10)));
+        timestep.advance(start);
+
+        let (steps, ctx) = timestep.advance(start + ms(7));
+        assert_eq!(steps, 0);
+        assert!((ctx.alpha - 0.7).abs() < 1e-6);
+
+        // 7 + 16 = 23ms: two steps, 3ms left over
+        let (steps, ctx) = timestep.advance(start + ms(23));
+        assert_eq!(steps, 2);
+        assert!((ctx.alpha - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fixed_backlog_is_dropped() {
+        let start = Instant::now();
+        let mut timestep = Timestep::fixed(ms(1));
+        timestep.advance(start);
+        let (steps, ctx) = timestep.advance(start + ms(100));
+        assert_eq!(steps, Timestep::MAX_STEPS);
+        assert_eq!(ctx.alpha, 0.0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Transitions that blend one frame into the next.
+//!
+//! A transition takes the outgoing frame, the incoming frame, and a progress
+//! value from `0.0` (all outgoing) to `1.0` (all incoming), and returns the
+//! frame to display. Transitions work on whole cells, so dots, colors, and
+//! text characters move together.
+
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use std::sync::Arc;
+
+/// A shareable transition function.
+///
+/// Built-in transitions are [`cut`], [`wipe`], and [`dissolve`]; register your
+/// own with [`registry::register_transition`](crate::registry::register_transition).
+pub type Transition =
+    Arc<dyn Fn(&BrailleGrid, &BrailleGrid, f32) -> Result<BrailleGrid, DotmaxError> + Send + Sync>;
+
+fn check_sizes(from: &BrailleGrid, to: &BrailleGrid) -> Result<(), DotmaxError> {
+    let (expected, actual) = (from.width() * from.height(), to.width() * to.height());
+    if from.dimensions() != to.dimensions() {
+        return Err(DotmaxError::BufferSizeMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+/// Build a frame taking each cell from `to` where `take_new` says so, else `from`.
+fn blend(
+    from: &BrailleGrid,
+    to: &BrailleGrid,
+    take_new: impl Fn(usize, usize) -> bool,
+) -> Result<BrailleGrid, DotmaxError> {
+    check_sizes(from, to)?;
+    let mut out = from.clone();
+    for y in 0..to.height() {
+        for x in 0..to.width() {
+            if take_new(x, y) {
+                out.copy_cell_from(to, x, y);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Switch to the incoming frame as soon as progress passes zero.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::BufferSizeMismatch`] if the frames differ in size.
+pub fn cut(
+    from: &BrailleGrid,
+    to: &BrailleGrid,
+    progress: f32,
+) -> Result<BrailleGrid, DotmaxError> {
+    check_sizes(from, to)?;
+    Ok(if progress > 0.0 {
+        to.clone()
+    } else {
+        from.clone()
+    })
+}
+
+/// Reveal the incoming frame left to right.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::BufferSizeMismatch`] if the frames differ in size.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{animation::wipe, BrailleGrid};
+///
+/// let from = BrailleGrid::new(10, 1)?;
+/// let mut to = BrailleGrid::new(10, 1)?;
+/// for x in 0..10 {
+///     to.set_char(x, 0, '#')?;
+/// }
+/// let half = wipe(&from, &to, 0.5)?;
+/// assert_eq!(half.get_char(4, 0), '#');
+/// assert_eq!(half.get_char(5, 0), '⠀');
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn wipe(
+    from: &BrailleGrid,
+    to: &BrailleGrid,
+    progress: f32,
+) -> Result<BrailleGrid, DotmaxError> {
+    let edge = (progress.clamp(0.0, 1.0) * to.width() as f32).round() as usize;
+    blend(from, to, |x, _| x < edge)
+}
+
+/// Replace cells in a fixed pseudo-random order.
+///
+/// The order depends only on cell position, so the same progress always
+/// produces the same frame.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::BufferSizeMismatch`] if the frames differ in size.
+pub fn dissolve(
+    from: &BrailleGrid,
+    to: &BrailleGrid,
+    progress: f32,
+) -> Result<BrailleGrid, DotmaxError> {
+    let progress = progress.clamp(0.0, 1.0);
+    blend(from, to, |x, y| {
+        // Integer hash of the cell position, mapped to [0, 1)
+        let mut h = (x as u32).wrapping_mul(0x9E37_79B1) ^ (y as u32).wrapping_mul(0x85EB_CA77);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2C1B_3C6D);
+        h ^= h >> 12;
+        let rank = h as f32 / u32::MAX as f32;
+        progress >= 1.0 || rank < progress
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+
+    fn filled(width: usize, height: usize) -> BrailleGrid {
+        let mut grid = BrailleGrid::new(width, height).unwrap();
+        grid.set_raw_patterns(&vec![0xFF; width * height]);
+        for y in 0..height {
+            for x in 0..width {
+                grid.set_cell_color(x, y, Color::rgb(1, 2, 3)).unwrap();
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_endpoints_match_inputs() {
+        let from = BrailleGrid::new(8, 4).unwrap();
+        let to = filled(8, 4);
+        for transition in [cut, wipe, dissolve] {
+            let start = transition(&from, &to, 0.0).unwrap();
+            let end = transition(&from, &to, 1.0).unwrap();
+            assert_eq!(start.get_raw_patterns(), from.get_raw_patterns());
+            assert_eq!(end.get_raw_patterns(), to.get_raw_patterns());
+            assert_eq!(end.get_color(7, 3), Some(Color::rgb(1, 2, 3)));
+        }
+    }
+
+    #[test]
+    fn test_dissolve_is_monotonic_and_deterministic() {
+        let from = BrailleGrid::new(20, 10).unwrap();
+        let to = filled(20, 10);
+        let count = |p| {
+            dissolve(&from, &to, p)
+                .unwrap()
+                .get_raw_patterns()
+                .iter()
+                .filter(|&&b| b != 0)
+                .count()
+        };
+        assert!(count(0.25) <= count(0.5));
+        assert!(count(0.5) <= count(0.75));
+        assert_eq!(count(0.5), count(0.5));
+        assert!(count(0.5) > 0 && count(0.5) < 200);
+    }
+
+    #[test]
+    fn test_size_mismatch_is_rejected() {
+        let from = BrailleGrid::new(4, 4).unwrap();
+        let to = BrailleGrid::new(5, 4).unwrap();
+        assert!(matches!(
+            wipe(&from, &to, 0.5),
+            Err(DotmaxError::BufferSizeMismatch { .. })
+        ));
+    }
+}
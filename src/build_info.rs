@@ -0,0 +1,324 @@
+//! What this build of dotmax can do.
+//!
+//! Most of dotmax sits behind Cargo features, so whether a program can open
+//! a video or parse a scene depends on how it was built. [`capabilities`]
+//! reports that at runtime: the crate version, which [`Feature`]s were
+//! compiled in, and the versions of native libraries linked in for them.
+//! Applications can hide menu entries for formats they can't open and say
+//! which feature is missing instead of failing with a generic error.
+//!
+//! Terminal features are a separate question; see
+//! [`TerminalCapabilities`](crate::TerminalCapabilities).
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::build_info::Feature;
+//!
+//! let build = dotmax::capabilities();
+//! println!("{build}"); // e.g. "dotmax 0.1.7 (features: image, svg)"
+//!
+//! if let Err(e) = build.require(Feature::Video) {
+//!     eprintln!("video playback unavailable: {e}");
+//! }
+//! assert_eq!(build.has(Feature::Image), cfg!(feature = "image"));
+//! ```
+
+use std::fmt;
+
+use crate::error::DotmaxError;
+
+/// An optional part of dotmax, named after the Cargo feature enabling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feature {
+    /// Image loading and rendering (`image`)
+    Image,
+    /// SVG rasterization (`svg`)
+    Svg,
+    /// Video playback through FFmpeg (`video`)
+    Video,
+    /// Live display capture (`screen-capture`)
+    ScreenCapture,
+    /// Serialization of grids, colors, and key maps (`serde`)
+    Serde,
+    /// Declarative TOML/JSON scenes (`scene`)
+    Scene,
+    /// Rhai scripts (`script`)
+    Script,
+    /// TTF/OTF text banners (`text`)
+    Text,
+    /// Chart series from JSON (`json`)
+    Json,
+    /// `ndarray` conversions (`ndarray`)
+    Ndarray,
+    /// `nalgebra` conversions (`nalgebra`)
+    Nalgebra,
+    /// System metrics (`sysinfo`)
+    Sysinfo,
+    /// Game controller input (`gamepad`)
+    Gamepad,
+    /// OBJ and STL model loading (`models`)
+    Models,
+    /// Music visualizer rendering (`viz`)
+    Viz,
+    /// QR code rendering (`qr`)
+    Qr,
+}
+
+impl Feature {
+    /// Every feature, in the order `Cargo.toml` lists them.
+    pub const ALL: [Self; 16] = [
+        Self::Image,
+        Self::Svg,
+        Self::Video,
+        Self::ScreenCapture,
+        Self::Serde,
+        Self::Scene,
+        Self::Script,
+        Self::Text,
+        Self::Json,
+        Self::Ndarray,
+        Self::Nalgebra,
+        Self::Sysinfo,
+        Self::Gamepad,
+        Self::Models,
+        Self::Viz,
+        Self::Qr,
+    ];
+
+    /// The Cargo feature name, as passed to `--features`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Image => "image",
+            Self::Svg => "svg",
+            Self::Video => "video",
+            Self::ScreenCapture => "screen-capture",
+            Self::Serde => "serde",
+            Self::Scene => "scene",
+            Self::Script => "script",
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Ndarray => "ndarray",
+            Self::Nalgebra => "nalgebra",
+            Self::Sysinfo => "sysinfo",
+            Self::Gamepad => "gamepad",
+            Self::Models => "models",
+            Self::Viz => "viz",
+            Self::Qr => "qr",
+        }
+    }
+
+    /// The feature with Cargo name `name`, if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+
+    /// Whether this feature was compiled in.
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        match self {
+            Self::Image => cfg!(feature = "image"),
+            Self::Svg => cfg!(feature = "svg"),
+            Self::Video => cfg!(feature = "video"),
+            Self::ScreenCapture => cfg!(feature = "screen-capture"),
+            Self::Serde => cfg!(feature = "serde"),
+            Self::Scene => cfg!(feature = "scene"),
+            Self::Script => cfg!(feature = "script"),
+            Self::Text => cfg!(feature = "text"),
+            Self::Json => cfg!(feature = "json"),
+            Self::Ndarray => cfg!(feature = "ndarray"),
+            Self::Nalgebra => cfg!(feature = "nalgebra"),
+            Self::Sysinfo => cfg!(feature = "sysinfo"),
+            Self::Gamepad => cfg!(feature = "gamepad"),
+            Self::Models => cfg!(feature = "models"),
+            Self::Viz => cfg!(feature = "viz"),
+            Self::Qr => cfg!(feature = "qr"),
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A native library version, as `major.minor.micro`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LibraryVersion {
+    /// Major version
+    pub major: u32,
+    /// Minor version
+    pub minor: u32,
+    /// Micro (patch) version
+    pub micro: u32,
+}
+
+impl LibraryVersion {
+    /// Unpacks FFmpeg's `AV_VERSION_INT` encoding.
+    #[must_use]
+    pub const fn from_av_int(version: u32) -> Self {
+        Self {
+            major: version >> 16,
+            minor: (version >> 8) & 0xFF,
+            micro: version & 0xFF,
+        }
+    }
+}
+
+impl fmt::Display for LibraryVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.micro)
+    }
+}
+
+/// The version and compiled-in features of this build; see [`capabilities`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The dotmax crate version
+    pub version: &'static str,
+    /// Compiled-in features, in [`Feature::ALL`] order
+    pub features: Vec<Feature>,
+    /// Version of the FFmpeg `libavcodec` linked in, with the `video`
+    /// feature
+    pub ffmpeg: Option<LibraryVersion>,
+}
+
+impl BuildInfo {
+    /// Whether `feature` was compiled in.
+    #[must_use]
+    pub fn has(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// The features that were left out.
+    #[must_use]
+    pub fn missing(&self) -> Vec<Feature> {
+        Feature::ALL
+            .into_iter()
+            .filter(|feature| !self.has(*feature))
+            .collect()
+    }
+
+    /// `Ok` if `feature` was compiled in.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::FeatureDisabled`], naming the feature to
+    /// build with, if it wasn't.
+    pub fn require(&self, feature: Feature) -> Result<(), DotmaxError> {
+        if self.has(feature) {
+            Ok(())
+        } else {
+            Err(DotmaxError::FeatureDisabled {
+                feature: feature.name(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    /// One line, e.g. `dotmax 0.1.7 (features: image, video; FFmpeg
+    /// libavcodec 61.3.100)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dotmax {} (features: ", self.version)?;
+        if self.features.is_empty() {
+            f.write_str("none")?;
+        }
+        for (i, feature) in self.features.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{feature}")?;
+        }
+        if let Some(ffmpeg) = self.ffmpeg {
+            write!(f, "; FFmpeg libavcodec {ffmpeg}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Reports what this build of dotmax can do.
+#[must_use]
+pub fn capabilities() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        features: Feature::ALL
+            .into_iter()
+            .filter(|feature| feature.is_enabled())
+            .collect(),
+        ffmpeg: ffmpeg_version(),
+    }
+}
+
+/// The linked `libavcodec` version.
+#[cfg(feature = "video")]
+fn ffmpeg_version() -> Option<LibraryVersion> {
+    Some(LibraryVersion::from_av_int(ffmpeg_next::codec::version()))
+}
+
+/// Without the `video` feature no FFmpeg is linked.
+#[cfg(not(feature = "video"))]
+const fn ffmpeg_version() -> Option<LibraryVersion> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for feature in Feature::ALL {
+            assert_eq!(Feature::from_name(feature.name()), Some(feature));
+        }
+        assert_eq!(
+            Feature::from_name(" screen-capture "),
+            Some(Feature::ScreenCapture)
+        );
+        assert_eq!(Feature::from_name("gpu"), None);
+    }
+
+    #[test]
+    fn test_capabilities_match_cfg() {
+        let build = capabilities();
+        assert_eq!(build.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(build.has(Feature::Image), cfg!(feature = "image"));
+        assert_eq!(build.ffmpeg.is_some(), cfg!(feature = "video"));
+        assert_eq!(
+            build.features.len() + build.missing().len(),
+            Feature::ALL.len()
+        );
+    }
+
+    #[test]
+    fn test_require_names_the_feature() {
+        let build = BuildInfo {
+            version: "1.2.3",
+            features: vec![Feature::Image],
+            ffmpeg: None,
+        };
+        assert!(build.require(Feature::Image).is_ok());
+        let err = build.require(Feature::Video).unwrap_err();
+        assert!(err.to_string().contains("--features video"));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut build = BuildInfo {
+            version: "1.2.3",
+            features: Vec::new(),
+            ffmpeg: None,
+        };
+        assert_eq!(build.to_string(), "dotmax 1.2.3 (features: none)");
+        build.features = vec![Feature::Image, Feature::Video];
+        build.ffmpeg = Some(LibraryVersion::from_av_int((61 << 16) | (3 << 8) | 100));
+        assert_eq!(
+            build.to_string(),
+            "dotmax 1.2.3 (features: image, video; FFmpeg libavcodec 61.3.100)"
+        );
+    }
+}
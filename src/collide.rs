@@ -0,0 +1,301 @@
+//! Collision tests at dot resolution, for games.
+//!
+//! Sprites are [`BrailleGrid`]s placed in a shared world by the dot
+//! position of their top-left corner, the same offsets
+//! [`BrailleGrid::blit`] takes. Two sprites collide when a set dot of one
+//! lands on a set dot of the other; transparent corners of a sprite's
+//! bounding box never count.
+//!
+//! Every test works a cell at a time on the grids' packed patterns: the
+//! dot rectangles must meet (broad phase), cells with no dots are skipped,
+//! and what's left is one bitwise AND of two `u8` patterns per cell
+//! (narrow phase). Sprites placed a whole number of cells apart compare
+//! patterns directly; other offsets gather the other sprite's dots under
+//! each cell first.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::collide::{circle_overlap, grid_overlap, point_overlap};
+//! use dotmax::primitives::draw_rectangle_filled;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut ship = BrailleGrid::new(3, 2)?; // 6×8 dots
+//! draw_rectangle_filled(&mut ship, 0, 0, 6, 8)?;
+//! let mut rock = BrailleGrid::new(2, 1)?; // 4×4 dots
+//! rock.set_dot(0, 0)?;
+//!
+//! // The rock's only dot lands one dot inside the ship's right edge
+//! assert!(grid_overlap(&ship, (10, 10), &rock, (15, 17)));
+//! assert!(!grid_overlap(&ship, (10, 10), &rock, (16, 17)));
+//!
+//! assert!(point_overlap(&ship, (10, 10), 12, 12));
+//! assert!(circle_overlap(&ship, (10, 10), 20, 14, 5));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::grid::{dot_mask, BrailleGrid};
+
+/// Whether any set dot of `a` at `offset_a` lands on a set dot of `b` at
+/// `offset_b`. Offsets are the world dot positions of each grid's top-left
+/// dot and may be negative.
+#[must_use]
+pub fn grid_overlap(
+    a: &BrailleGrid,
+    offset_a: (i32, i32),
+    b: &BrailleGrid,
+    offset_b: (i32, i32),
+) -> bool {
+    // b's top-left dot in a's dot coordinates
+    let bx = i64::from(offset_b.0) - i64::from(offset_a.0);
+    let by = i64::from(offset_b.1) - i64::from(offset_a.1);
+    let Some(area) = Area::of(a).intersect(Area::of(b).shifted(bx, by)) else {
+        return false;
+    };
+    any_cell(a, area, |cell_x, cell_y, pattern| {
+        pattern & window(b, cell_x * 2 - bx, cell_y * 4 - by) != 0
+    })
+}
+
+/// Whether the dot at world position (`x`, `y`) is set in `grid` placed at
+/// `offset`.
+#[must_use]
+pub fn point_overlap(grid: &BrailleGrid, offset: (i32, i32), x: i32, y: i32) -> bool {
+    let local_x = i64::from(x) - i64::from(offset.0);
+    let local_y = i64::from(y) - i64::from(offset.1);
+    is_set(grid, local_x, local_y)
+}
+
+/// Whether any set dot of `grid` placed at `offset` lies inside the
+/// `width × height` dot rectangle with its top-left corner at world
+/// position (`x`, `y`).
+#[must_use]
+pub fn rect_overlap(
+    grid: &BrailleGrid,
+    offset: (i32, i32),
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> bool {
+    let rect = Area {
+        left: i64::from(x) - i64::from(offset.0),
+        top: i64::from(y) - i64::from(offset.1),
+        right: i64::from(x) - i64::from(offset.0) + i64::from(width),
+        bottom: i64::from(y) - i64::from(offset.1) + i64::from(height),
+    };
+    let Some(area) = Area::of(grid).intersect(rect) else {
+        return false;
+    };
+    any_cell(grid, area, |cell_x, cell_y, pattern| {
+        pattern & area.mask(cell_x, cell_y, |_, _| true) != 0
+    })
+}
+
+/// Whether any set dot of `grid` placed at `offset` lies within `radius`
+/// dots of world position (`center_x`, `center_y`).
+///
+/// The tested region is the filled disc
+/// [`draw_circle_filled`](crate::primitives::draw_circle_filled) would draw.
+#[must_use]
+pub fn circle_overlap(
+    grid: &BrailleGrid,
+    offset: (i32, i32),
+    center_x: i32,
+    center_y: i32,
+    radius: u32,
+) -> bool {
+    let cx = i64::from(center_x) - i64::from(offset.0);
+    let cy = i64::from(center_y) - i64::from(offset.1);
+    let r = i64::from(radius);
+    let bounds = Area {
+        left: cx - r,
+        top: cy - r,
+        right: cx + r + 1,
+        bottom: cy + r + 1,
+    };
+    let Some(area) = Area::of(grid).intersect(bounds) else {
+        return false;
+    };
+    any_cell(grid, area, |cell_x, cell_y, pattern| {
+        let disc = area.mask(cell_x, cell_y, |x, y| {
+            let (dx, dy) = (x - cx, y - cy);
+            dx * dx + dy * dy <= r * r
+        });
+        pattern & disc != 0
+    })
+}
+
+/// A half-open rectangle of dots in one grid's dot coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Area {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Area {
+    /// Every dot of `grid`.
+    const fn of(grid: &BrailleGrid) -> Self {
+        #[allow(clippy::cast_possible_wrap)]
+        Self {
+            left: 0,
+            top: 0,
+            right: grid.dot_width() as i64,
+            bottom: grid.dot_height() as i64,
+        }
+    }
+
+    const fn shifted(self, dx: i64, dy: i64) -> Self {
+        Self {
+            left: self.left + dx,
+            top: self.top + dy,
+            right: self.right + dx,
+            bottom: self.bottom + dy,
+        }
+    }
+
+    /// The dots in both, or `None` if they don't meet.
+    fn intersect(self, other: Self) -> Option<Self> {
+        let area = Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        (area.left < area.right && area.top < area.bottom).then_some(area)
+    }
+
+    /// Bits of the cell at (`cell_x`, `cell_y`) for its dots inside the
+    /// area that also pass `keep`, given their dot coordinates.
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn mask(self, cell_x: i64, cell_y: i64, keep: impl Fn(i64, i64) -> bool) -> u8 {
+        let mut mask = 0;
+        for y in (cell_y * 4).max(self.top)..(cell_y * 4 + 4).min(self.bottom) {
+            for x in (cell_x * 2).max(self.left)..(cell_x * 2 + 2).min(self.right) {
+                if keep(x, y) {
+                    mask |= dot_mask(x as usize, y as usize);
+                }
+            }
+        }
+        mask
+    }
+}
+
+/// Calls `hit` with the position and pattern of each non-empty cell of
+/// `grid` touching `area`, stopping at the first that returns `true`.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn any_cell(grid: &BrailleGrid, area: Area, hit: impl Fn(i64, i64, u8) -> bool) -> bool {
+    let width = grid.dimensions().0;
+    let patterns = grid.get_raw_patterns();
+    // The area lies inside the grid, so none of these are negative
+    for cell_y in area.top / 4..(area.bottom + 3) / 4 {
+        for cell_x in area.left / 2..(area.right + 1) / 2 {
+            let pattern = patterns[cell_y as usize * width + cell_x as usize];
+            if pattern != 0 && hit(cell_x, cell_y, pattern) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Whether the dot at (`x`, `y`) is set; anything outside the grid is not.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn is_set(grid: &BrailleGrid, x: i64, y: i64) -> bool {
+    x >= 0 && y >= 0 && grid.is_dot_set(x as usize, y as usize)
+}
+
+/// The dots of `grid` in the cell-sized 2×4 window with its top-left dot
+/// at (`x`, `y`), packed as a cell pattern.
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+fn window(grid: &BrailleGrid, x: i64, y: i64) -> u8 {
+    let (width, height) = grid.dimensions();
+    if x % 2 == 0 && y % 4 == 0 {
+        // Aligned with a cell: its pattern is the window
+        let (cell_x, cell_y) = (x / 2, y / 4);
+        #[allow(clippy::cast_possible_wrap)]
+        let inside = (0..width as i64).contains(&cell_x) && (0..height as i64).contains(&cell_y);
+        return if inside {
+            grid.get_raw_patterns()[cell_y as usize * width + cell_x as usize]
+        } else {
+            0
+        };
+    }
+    let mut pattern = 0;
+    for dy in 0..4 {
+        for dx in 0..2 {
+            if is_set(grid, x + dx, y + dy) {
+                pattern |= dot_mask(dx as usize, dy as usize);
+            }
+        }
+    }
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{draw_circle_filled, draw_rectangle_filled};
+
+    /// Brute-force overlap, one world dot at a time.
+    fn overlap_by_dots(a: &BrailleGrid, oa: (i32, i32), b: &BrailleGrid, ob: (i32, i32)) -> bool {
+        let (width, height) = (a.dot_width(), a.dot_height());
+        (0..width * height).any(|i| {
+            let (x, y) = ((i % width) as i32, (i / width) as i32);
+            a.is_dot_set(x as usize, y as usize) && point_overlap(b, ob, x + oa.0, y + oa.1)
+        })
+    }
+
+    #[test]
+    fn test_grid_overlap_matches_dots_at_every_offset() {
+        let mut ring = BrailleGrid::new(4, 3).unwrap();
+        draw_circle_filled(&mut ring, 4, 6, 4).unwrap();
+        let mut bar = BrailleGrid::new(3, 1).unwrap();
+        draw_rectangle_filled(&mut bar, 0, 1, 5, 2).unwrap();
+
+        for dy in -8..16 {
+            for dx in -8..12 {
+                assert_eq!(
+                    grid_overlap(&ring, (3, 5), &bar, (3 + dx, 5 + dy)),
+                    overlap_by_dots(&ring, (3, 5), &bar, (3 + dx, 5 + dy)),
+                    "offset ({dx}, {dy})"
+                );
+                // The test is symmetric
+                assert_eq!(
+                    grid_overlap(&ring, (3, 5), &bar, (3 + dx, 5 + dy)),
+                    grid_overlap(&bar, (3 + dx, 5 + dy), &ring, (3, 5)),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_shapes_against_grid() {
+        let mut grid = BrailleGrid::new(5, 3).unwrap(); // 10×12 dots
+        grid.set_dot(7, 9).unwrap();
+        let offset = (-20, 30);
+
+        assert!(point_overlap(&grid, offset, -13, 39));
+        assert!(!point_overlap(&grid, offset, -12, 39));
+        assert!(!point_overlap(&grid, offset, -100, 39));
+
+        assert!(rect_overlap(&grid, offset, -13, 39, 1, 1));
+        assert!(rect_overlap(&grid, offset, -30, 20, 100, 100));
+        assert!(!rect_overlap(&grid, offset, -12, 30, 10, 20));
+        assert!(!rect_overlap(&grid, offset, -13, 39, 0, 5));
+
+        assert!(circle_overlap(&grid, offset, -10, 35, 5)); // 3² + 4² = 5²
+        assert!(!circle_overlap(&grid, offset, -10, 35, 4));
+        assert!(circle_overlap(&grid, offset, -13, 39, 0));
+
+        assert!(!circle_overlap(
+            &BrailleGrid::new(5, 3).unwrap(),
+            offset,
+            -15,
+            36,
+            50
+        ));
+    }
+}
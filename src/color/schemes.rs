@@ -107,6 +107,11 @@ use crate::grid::Color;
 /// // Result is approximately purple (127, 0, 128)
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SchemeFields")
+)]
 pub struct ColorScheme {
     /// Human-readable name of the scheme
     name: String,
@@ -474,6 +479,23 @@ impl ColorScheme {
     }
 }
 
+/// The fields of a serialized scheme, checked before becoming one.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SchemeFields {
+    name: String,
+    colors: Vec<Color>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SchemeFields> for ColorScheme {
+    type Error = DotmaxError;
+
+    fn try_from(fields: SchemeFields) -> Result<Self, Self::Error> {
+        Self::new(fields.name, fields.colors)
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
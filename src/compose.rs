@@ -0,0 +1,206 @@
+//! Stamping one grid onto another: watermarks and logos.
+//!
+//! [`watermark`] places a pre-rendered logo grid in a corner of a frame.
+//! How the logo's dots combine with the frame's is up to the
+//! [`WatermarkMode`]: a plain OR reads well over dark content, while
+//! [`Invert`](WatermarkMode::Invert) and [`Knockout`](WatermarkMode::Knockout)
+//! stay visible over bright content too.
+//!
+//! Placement is cell-aligned and clips at the frame edges. Logo cell colors
+//! carry over to the cells the logo touches.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::compose::{watermark, Corner, WatermarkMode};
+//! use dotmax::BrailleGrid;
+//!
+//! let mut logo = BrailleGrid::new(4, 1)?;
+//! for x in 0..8 {
+//!     logo.set_dot(x, 0)?; // a bar along the top of the logo
+//! }
+//!
+//! let mut frame = BrailleGrid::new(40, 10)?;
+//! watermark(&mut frame, &logo, Corner::BottomRight, WatermarkMode::Invert);
+//! assert!(frame.is_dot_set(79, 36));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! Stamping every frame of a video is one frame hook (`image` feature):
+//!
+//! ```no_run
+//! # #[cfg(feature = "image")]
+//! # {
+//! use dotmax::compose::{watermark, Corner, WatermarkMode};
+//! use dotmax::media::{GifPlayer, MediaPlayer};
+//!
+//! let logo = dotmax::quick::load_image_sized("logo.png", 8, 2)?;
+//! let mut player = GifPlayer::new("clip.gif")?.on_frame(move |grid, _| {
+//!     watermark(grid, &logo, Corner::TopRight, WatermarkMode::Knockout);
+//!     Ok(())
+//! });
+//! # let _ = player.next_frame();
+//! # }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::grid::BrailleGrid;
+
+/// Which corner of the frame a watermark sits in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Corner {
+    /// Top-left corner
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+    /// Bottom-left corner
+    BottomLeft,
+    /// Bottom-right corner
+    BottomRight,
+}
+
+/// How a watermark's dots combine with the frame underneath.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WatermarkMode {
+    /// Logo dots are added to the frame's. Disappears into bright content.
+    Or,
+    /// Logo dots flip the frame's: set where the frame is empty, cleared
+    /// where it is lit. Visible over any content, though busy frames make
+    /// it harder to read.
+    Invert,
+    /// Every cell the logo touches shows only the logo, cutting it out of
+    /// the frame. The most legible, at the cost of hiding what's beneath.
+    Knockout,
+}
+
+/// Stamps `logo` into a corner of `grid`.
+///
+/// A logo larger than the frame is clipped on the side away from the
+/// corner.
+pub fn watermark(grid: &mut BrailleGrid, logo: &BrailleGrid, corner: Corner, mode: WatermarkMode) {
+    let right = grid.width() as i64 - logo.width() as i64;
+    let bottom = grid.height() as i64 - logo.height() as i64;
+    let (cell_x, cell_y) = match corner {
+        Corner::TopLeft => (0, 0),
+        Corner::TopRight => (right, 0),
+        Corner::BottomLeft => (0, bottom),
+        Corner::BottomRight => (right, bottom),
+    };
+    watermark_at(grid, logo, cell_x, cell_y, mode);
+}
+
+/// Stamps `logo` with its top-left cell at `(cell_x, cell_y)` in `grid`.
+///
+/// Offsets may be negative or run past the edges; the logo is clipped.
+pub fn watermark_at(
+    grid: &mut BrailleGrid,
+    logo: &BrailleGrid,
+    cell_x: i64,
+    cell_y: i64,
+    mode: WatermarkMode,
+) {
+    let (logo_w, grid_w, grid_h) = (logo.width(), grid.width(), grid.height());
+    let mut patterns = grid.get_raw_patterns().to_vec();
+    let mut colored = Vec::new();
+
+    for (index, &mark) in logo.get_raw_patterns().iter().enumerate() {
+        if mark == 0 {
+            continue;
+        }
+        let (lx, ly) = (index % logo_w, index / logo_w);
+        let (Ok(x), Ok(y)) = (
+            usize::try_from(lx as i64 + cell_x),
+            usize::try_from(ly as i64 + cell_y),
+        ) else {
+            continue;
+        };
+        if x >= grid_w || y >= grid_h {
+            continue;
+        }
+
+        let cell = &mut patterns[y * grid_w + x];
+        *cell = match mode {
+            WatermarkMode::Or => *cell | mark,
+            WatermarkMode::Invert => *cell ^ mark,
+            WatermarkMode::Knockout => mark,
+        };
+        if let Some(color) = logo.get_color(lx, ly) {
+            colored.push((x, y, color));
+        }
+    }
+
+    grid.set_raw_patterns(&patterns);
+    for (x, y, color) in colored {
+        // In bounds by construction
+        let _ = grid.set_cell_color(x, y, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    /// A 2×1-cell logo with its top-left and bottom-right dots set.
+    fn logo() -> BrailleGrid {
+        let mut logo = BrailleGrid::new(2, 1).unwrap();
+        logo.set_dot(0, 0).unwrap();
+        logo.set_dot(3, 3).unwrap();
+        logo
+    }
+
+    #[test]
+    fn test_corners() {
+        for (corner, dot) in [
+            (Corner::TopLeft, (0, 0)),
+            (Corner::TopRight, (16, 0)),
+            (Corner::BottomLeft, (0, 16)),
+            (Corner::BottomRight, (16, 16)),
+        ] {
+            let mut grid = BrailleGrid::new(10, 5).unwrap();
+            watermark(&mut grid, &logo(), corner, WatermarkMode::Or);
+            assert!(grid.is_dot_set(dot.0, dot.1), "{corner:?}");
+            assert!(grid.is_dot_set(dot.0 + 3, dot.1 + 3), "{corner:?}");
+        }
+    }
+
+    #[test]
+    fn test_modes_over_lit_frame() {
+        let lit = || {
+            let mut grid = BrailleGrid::new(4, 2).unwrap();
+            grid.set_raw_patterns(&[0xFF; 8]);
+            grid
+        };
+
+        let mut grid = lit();
+        watermark(&mut grid, &logo(), Corner::TopLeft, WatermarkMode::Or);
+        assert!(grid.get_raw_patterns().iter().all(|&p| p == 0xFF));
+
+        let mut grid = lit();
+        watermark(&mut grid, &logo(), Corner::TopLeft, WatermarkMode::Invert);
+        assert!(!grid.is_dot_set(0, 0));
+        assert!(grid.is_dot_set(1, 0));
+
+        let mut grid = lit();
+        watermark(&mut grid, &logo(), Corner::TopLeft, WatermarkMode::Knockout);
+        assert!(grid.is_dot_set(0, 0));
+        assert!(!grid.is_dot_set(1, 0));
+        // Untouched cells keep the frame
+        assert!(grid.is_dot_set(4, 0));
+    }
+
+    #[test]
+    fn test_clips_and_carries_color() {
+        let mut logo = logo();
+        logo.set_cell_color(1, 0, Color::rgb(255, 0, 0)).unwrap();
+
+        let mut grid = BrailleGrid::new(1, 1).unwrap();
+        watermark(&mut grid, &logo, Corner::BottomRight, WatermarkMode::Or);
+        // Only the logo's right cell fits
+        assert!(grid.is_dot_set(1, 3));
+        assert!(!grid.is_dot_set(0, 0));
+        assert_eq!(grid.get_color(0, 0), Some(Color::rgb(255, 0, 0)));
+
+        watermark_at(&mut grid, &logo, -10, 100, WatermarkMode::Or);
+    }
+}
@@ -0,0 +1,396 @@
+//! Two-dimensional intensity data, sized independently of the grid.
+//!
+//! [`BrailleGrid::render_density`] takes a flat slice that must already be
+//! exactly `width × height` cells. An [`IntensityBuffer`] carries its own
+//! shape instead, so data of any size can be rescaled to 0.0-1.0 with
+//! [`normalized`](IntensityBuffer::normalized) and drawn into any grid with
+//! [`render`](IntensityBuffer::render), which resamples it to fit.
+//!
+//! With the `ndarray` feature, `Array2<f32>` and its views convert into a
+//! buffer and back; with `nalgebra`, so do its matrices. Rows become grid
+//! rows in both cases: the first axis of an array, the row index of a
+//! matrix.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::density::{DensitySet, IntensityBuffer};
+//! use dotmax::BrailleGrid;
+//!
+//! // A 200×100 field of raw readings between -3 and 3
+//! let field = IntensityBuffer::from_fn(200, 100, |x, y| {
+//!     3.0 * ((x as f32 / 20.0).sin() * (y as f32 / 15.0).cos())
+//! });
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! field.normalized().render(&mut grid, &DensitySet::simple(), None)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::color::schemes::ColorScheme;
+use crate::{BrailleGrid, DotmaxError};
+
+use super::DensitySet;
+
+/// A row-major grid of intensities, usually 0.0-1.0.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IntensityBuffer {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl IntensityBuffer {
+    /// A `width × height` buffer holding `values` row by row.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::BufferSizeMismatch`] if `values.len()` isn't
+    /// `width * height`.
+    pub fn new(width: usize, height: usize, values: Vec<f32>) -> Result<Self, DotmaxError> {
+        let expected = width.saturating_mul(height);
+        if values.len() != expected {
+            return Err(DotmaxError::BufferSizeMismatch {
+                expected,
+                actual: values.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            values,
+        })
+    }
+
+    /// A `width × height` buffer with `f(x, y)` at each position.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> f32) -> Self {
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// A buffer from nested rows, as taken by
+    /// [`apply_color_scheme`](crate::color::apply::apply_color_scheme).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::BufferSizeMismatch`] if the rows differ in
+    /// length.
+    pub fn from_rows(rows: &[Vec<f32>]) -> Result<Self, DotmaxError> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().find(|row| row.len() != width) {
+            return Err(DotmaxError::BufferSizeMismatch {
+                expected: width,
+                actual: row.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height: rows.len(),
+            values: rows.concat(),
+        })
+    }
+
+    /// Width in values.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in values.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the buffer holds no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value at `(x, y)`, if in bounds.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<f32> {
+        (x < self.width && y < self.height).then(|| self.values[y * self.width + x])
+    }
+
+    /// All values, row by row.
+    #[must_use]
+    pub fn as_slice(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// The values, row by row.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<f32> {
+        self.values
+    }
+
+    /// A copy with values stretched linearly so the smallest finite one is
+    /// 0.0 and the largest 1.0. A constant buffer becomes all 0.0, and
+    /// NaN and infinite values become 0.0.
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        let range = max - min;
+        let values = self
+            .values
+            .iter()
+            .map(|&value| {
+                if value.is_finite() && range > 0.0 {
+                    (value - min) / range
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Self { values, ..*self }
+    }
+
+    /// A copy resampled to `width × height`. Each new value is the mean of
+    /// the values it covers when shrinking, or the nearest value when
+    /// growing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if this buffer is empty
+    /// and the new size isn't.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn resample(&self, width: usize, height: usize) -> Result<Self, DotmaxError> {
+        if (width, height) == (self.width, self.height) {
+            return Ok(self.clone());
+        }
+        if self.is_empty() && width * height > 0 {
+            return Err(DotmaxError::InvalidDimensions {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        // Source indices covered by output index `i` of `len` along an
+        // axis of `source` values, never empty
+        let span = |i: usize, len: usize, source: usize| {
+            let start = i * source / len;
+            let end = ((i + 1) * source / len).max(start + 1);
+            start..end
+        };
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let rows = span(y, height, self.height);
+                let columns = span(x, width, self.width);
+                let count = rows.len() * columns.len();
+                let sum: f32 = rows
+                    .flat_map(|row| &self.values[row * self.width..][columns.clone()])
+                    .sum();
+                sum / count as f32
+            })
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            values,
+        })
+    }
+
+    /// Draws the buffer into every cell of `grid` with
+    /// [`BrailleGrid::render_density`], resampled to the grid's size first
+    /// if needed. With a `scheme`, cells are also colored, as by
+    /// [`BrailleGrid::render_density_colored`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if the buffer is empty.
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        density_set: &DensitySet,
+        scheme: Option<&ColorScheme>,
+    ) -> Result<(), DotmaxError> {
+        let (width, height) = grid.dimensions();
+        let fitted = self.resample(width, height)?;
+        match scheme {
+            Some(scheme) => grid.render_density_colored(&fitted.values, density_set, scheme),
+            None => grid.render_density(&fitted.values, density_set),
+        }
+    }
+
+    /// Sets every dot of `grid` whose value is above `threshold`, after
+    /// resampling the buffer to the grid's size in dots. Dots at or below
+    /// it are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::density::IntensityBuffer;
+    /// use dotmax::BrailleGrid;
+    ///
+    /// let ramp = IntensityBuffer::from_fn(8, 4, |x, _| x as f32 / 7.0);
+    /// let mut grid = BrailleGrid::new(4, 1)?; // 8×4 dots
+    /// ramp.render_dots(&mut grid, 0.5)?;
+    /// assert_eq!(grid.get_char(0, 0), '⠀');
+    /// assert_eq!(grid.get_char(3, 0), '⣿');
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn render_dots(&self, grid: &mut BrailleGrid, threshold: f32) -> Result<(), DotmaxError> {
+        let width = grid.dot_width();
+        let fitted = self.resample(width, grid.dot_height())?;
+        for (index, &value) in fitted.values.iter().enumerate() {
+            if value > threshold {
+                grid.set_dot(index % width, index / width)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "ndarray")]
+mod ndarray_interop {
+    use ndarray::{Array2, ArrayBase, Data, Ix2};
+
+    use super::IntensityBuffer;
+
+    /// Rows along the first axis, columns along the second.
+    impl<S: Data<Elem = f32>> From<&ArrayBase<S, Ix2>> for IntensityBuffer {
+        fn from(array: &ArrayBase<S, Ix2>) -> Self {
+            let (height, width) = array.dim();
+            Self {
+                width,
+                height,
+                values: array.iter().copied().collect(),
+            }
+        }
+    }
+
+    impl From<Array2<f32>> for IntensityBuffer {
+        fn from(array: Array2<f32>) -> Self {
+            Self::from(&array)
+        }
+    }
+
+    impl From<IntensityBuffer> for Array2<f32> {
+        fn from(buffer: IntensityBuffer) -> Self {
+            Self::from_shape_vec((buffer.height, buffer.width), buffer.values)
+                .unwrap_or_else(|_| unreachable!("buffer length is width × height"))
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+mod nalgebra_interop {
+    use nalgebra::{DMatrix, Dim, Matrix, RawStorage};
+
+    use super::IntensityBuffer;
+
+    /// Matrix rows become buffer rows.
+    impl<R: Dim, C: Dim, S: RawStorage<f32, R, C>> From<&Matrix<f32, R, C, S>> for IntensityBuffer {
+        fn from(matrix: &Matrix<f32, R, C, S>) -> Self {
+            let (height, width) = matrix.shape();
+            Self::from_fn(width, height, |x, y| matrix[(y, x)])
+        }
+    }
+
+    impl From<IntensityBuffer> for DMatrix<f32> {
+        fn from(buffer: IntensityBuffer) -> Self {
+            Self::from_row_slice(buffer.height, buffer.width, &buffer.values)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construct_and_normalize() {
+        assert!(matches!(
+            IntensityBuffer::new(3, 2, vec![0.0; 5]),
+            Err(DotmaxError::BufferSizeMismatch {
+                expected: 6,
+                actual: 5
+            })
+        ));
+        let rows = IntensityBuffer::from_rows(&[vec![-2.0, 0.0], vec![2.0, f32::NAN]]).unwrap();
+        assert_eq!((rows.width(), rows.height()), (2, 2));
+        assert_eq!(rows.get(0, 1), Some(2.0));
+        assert_eq!(rows.get(2, 0), None);
+        assert_eq!(rows.normalized().as_slice(), [0.0, 0.5, 1.0, 0.0]);
+        assert!(IntensityBuffer::from_rows(&[vec![1.0], vec![]]).is_err());
+
+        let flat = IntensityBuffer::from_fn(2, 2, |_, _| 7.0).normalized();
+        assert_eq!(flat.into_vec(), [0.0; 4]);
+    }
+
+    #[test]
+    fn test_resample_and_render() {
+        // Shrinking averages 2×2 blocks
+        let buffer = IntensityBuffer::from_fn(4, 2, |x, y| (x + 4 * y) as f32);
+        let half = buffer.resample(2, 1).unwrap();
+        assert_eq!(half.as_slice(), [2.5, 4.5]);
+        // Growing repeats the nearest value
+        let double = half.resample(4, 2).unwrap();
+        assert_eq!(double.as_slice(), [2.5, 2.5, 4.5, 4.5, 2.5, 2.5, 4.5, 4.5]);
+        assert!(IntensityBuffer::default().resample(1, 1).is_err());
+
+        let ramp = IntensityBuffer::from_fn(100, 50, |x, _| x as f32 / 99.0);
+        let mut grid = BrailleGrid::new(10, 3).unwrap();
+        let scheme = ColorScheme::grayscale();
+        ramp.render(&mut grid, &DensitySet::simple(), Some(&scheme))
+            .unwrap();
+        assert_eq!(grid.get_char(0, 2), ' ');
+        assert_eq!(grid.get_char(9, 0), '@');
+        assert!(grid.get_color(9, 1).is_some_and(|color| color.r > 200));
+
+        let mut dots = BrailleGrid::new(2, 1).unwrap();
+        IntensityBuffer::from_fn(2, 2, |x, y| if x == y { 1.0 } else { 0.0 })
+            .render_dots(&mut dots, 0.5)
+            .unwrap();
+        // Each value covers 2×2 dots of the 4×4
+        assert_eq!(dots.get_raw_patterns(), [0x1B, 0xE4]);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_ndarray_round_trip() {
+        use ndarray::{array, Array2};
+
+        let array = array![[0.0_f32, 0.25, 0.5], [0.75, 1.0, 0.0]];
+        let buffer = IntensityBuffer::from(&array);
+        assert_eq!((buffer.width(), buffer.height()), (3, 2));
+        assert_eq!(buffer.get(0, 1), Some(0.75));
+        // A transposed view is read in its logical order
+        let transposed = IntensityBuffer::from(&array.t());
+        assert_eq!(transposed.get(1, 0), Some(0.75));
+        assert_eq!(IntensityBuffer::from(array.t().to_owned()), transposed);
+        assert_eq!(Array2::from(buffer), array);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra_round_trip() {
+        use nalgebra::{DMatrix, Matrix2x3};
+
+        let matrix = Matrix2x3::new(0.0_f32, 0.25, 0.5, 0.75, 1.0, 0.0);
+        let buffer = IntensityBuffer::from(&matrix);
+        assert_eq!((buffer.width(), buffer.height()), (3, 2));
+        assert_eq!(buffer.get(0, 1), Some(0.75));
+        let expected = DMatrix::from_row_slice(2, 3, &[0.0, 0.25, 0.5, 0.75, 1.0, 0.0]);
+        assert_eq!(DMatrix::from(buffer), expected);
+    }
+}
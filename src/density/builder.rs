@@ -0,0 +1,251 @@
+//! Builder for density sets with custom response curves.
+//!
+//! [`DensitySet::new`] covers the common case of a plain character ramp.
+//! [`DensitySetBuilder`] additionally attaches a coverage table and gamma for
+//! [`DensitySet::map_perceptual`], which is how you tune a set to a specific
+//! font or to input that isn't linear light.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::density::DensitySetBuilder;
+//!
+//! // Coverage measured for a custom font; values needn't be normalized
+//! let density = DensitySetBuilder::new("dots")
+//!     .characters(" .oO@")
+//!     .coverage(vec![0.0, 6.0, 30.0, 45.0, 60.0])
+//!     .build()?;
+//!
+//! assert_eq!(density.coverage(), Some(&[0.0, 0.1, 0.5, 0.75, 1.0][..]));
+//! assert_eq!(density.map_perceptual(0.45), 'o');
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use super::DensitySet;
+use crate::error::DotmaxError;
+
+/// A builder for [`DensitySet`]s with coverage tables and gamma.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::density::{DensitySet, DensitySetBuilder};
+///
+/// // sRGB-encoded input: decode to linear light before matching coverage
+/// let density = DensitySetBuilder::from_set(DensitySet::ascii())
+///     .gamma(2.2)
+///     .build()?;
+/// assert!(density.map_perceptual(0.5) != DensitySet::ascii().map_perceptual(0.5));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DensitySetBuilder {
+    name: String,
+    characters: Vec<char>,
+    coverage: Option<Vec<f32>>,
+    gamma: f32,
+}
+
+impl DensitySetBuilder {
+    /// Start a builder with no characters, no coverage table, and gamma 1.0.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            characters: Vec::new(),
+            coverage: None,
+            gamma: 1.0,
+        }
+    }
+
+    /// Start from an existing set, keeping its characters, coverage, and gamma.
+    #[must_use]
+    pub fn from_set(set: DensitySet) -> Self {
+        Self {
+            name: set.name,
+            characters: set.characters,
+            coverage: set.coverage,
+            gamma: set.gamma,
+        }
+    }
+
+    /// Set the characters, ordered from sparse to dense.
+    #[must_use]
+    pub fn characters(mut self, characters: &str) -> Self {
+        self.characters = characters.chars().collect();
+        self
+    }
+
+    /// Attach a coverage table: one ink-coverage value per character.
+    ///
+    /// Values can be in any unit (pixel counts, percentages); they are
+    /// rescaled so the smallest becomes 0.0 and the largest 1.0.
+    #[must_use]
+    pub fn coverage(mut self, coverage: Vec<f32>) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    /// Remove any coverage table so characters are treated as evenly spaced.
+    #[must_use]
+    pub fn linear(mut self) -> Self {
+        self.coverage = None;
+        self
+    }
+
+    /// Exponent applied to intensity before the coverage lookup.
+    ///
+    /// Use 1.0 (the default) for linear-light input and about 2.2 for
+    /// sRGB-encoded input. Values below 1.0 brighten midtones.
+    #[must_use]
+    pub const fn gamma(mut self, gamma: f32) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Validate and build the density set.
+    ///
+    /// # Errors
+    ///
+    /// - [`DotmaxError::EmptyDensitySet`] or [`DotmaxError::TooManyCharacters`]
+    ///   under the same rules as [`DensitySet::new`]
+    /// - [`DotmaxError::BufferSizeMismatch`] if the coverage table length
+    ///   differs from the character count
+    /// - [`DotmaxError::InvalidParameter`] if a coverage value is negative or
+    ///   not finite, or gamma is not in 0.1..=10.0
+    pub fn build(self) -> Result<DensitySet, DotmaxError> {
+        let mut set = DensitySet::new(self.name, self.characters)?;
+
+        if !(0.1..=10.0).contains(&self.gamma) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "density gamma".to_string(),
+                value: self.gamma.to_string(),
+                min: "0.1".to_string(),
+                max: "10.0".to_string(),
+            });
+        }
+        set.gamma = self.gamma;
+
+        if let Some(coverage) = self.coverage {
+            if coverage.len() != set.characters.len() {
+                return Err(DotmaxError::BufferSizeMismatch {
+                    expected: set.characters.len(),
+                    actual: coverage.len(),
+                });
+            }
+            if let Some(bad) = coverage.iter().find(|c| !c.is_finite() || **c < 0.0) {
+                return Err(DotmaxError::InvalidParameter {
+                    parameter_name: "coverage".to_string(),
+                    value: bad.to_string(),
+                    min: "0".to_string(),
+                    max: "finite".to_string(),
+                });
+            }
+            set.coverage = Some(normalize(&coverage));
+        }
+
+        Ok(set)
+    }
+}
+
+/// Rescale to 0.0..=1.0; a flat table becomes evenly spaced instead.
+#[allow(clippy::cast_precision_loss)]
+fn normalize(coverage: &[f32]) -> Vec<f32> {
+    let min = coverage.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = coverage.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+    if range <= f32::EPSILON {
+        let steps = (coverage.len() - 1).max(1) as f32;
+        return (0..coverage.len()).map(|i| i as f32 / steps).collect();
+    }
+    coverage.iter().map(|c| (c - min) / range).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_requires_characters() {
+        assert!(matches!(
+            DensitySetBuilder::new("empty").build(),
+            Err(DotmaxError::EmptyDensitySet)
+        ));
+    }
+
+    #[test]
+    fn test_coverage_length_must_match() {
+        let result = DensitySetBuilder::new("short")
+            .characters(" .:")
+            .coverage(vec![0.0, 1.0])
+            .build();
+        assert!(matches!(
+            result,
+            Err(DotmaxError::BufferSizeMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_bad_values() {
+        let negative = DensitySetBuilder::new("neg")
+            .characters(" #")
+            .coverage(vec![-1.0, 1.0])
+            .build();
+        assert!(matches!(
+            negative,
+            Err(DotmaxError::InvalidParameter { .. })
+        ));
+
+        let gamma = DensitySetBuilder::new("gamma")
+            .characters(" #")
+            .gamma(0.0)
+            .build();
+        assert!(matches!(gamma, Err(DotmaxError::InvalidParameter { .. })));
+    }
+
+    #[test]
+    fn test_unordered_coverage_picks_nearest() {
+        // Characters deliberately out of coverage order
+        let set = DensitySetBuilder::new("shuffled")
+            .characters(" #.")
+            .coverage(vec![0.0, 1.0, 0.2])
+            .build()
+            .unwrap();
+        assert_eq!(set.map_perceptual(0.25), '.');
+        assert_eq!(set.map_perceptual(0.9), '#');
+    }
+
+    #[test]
+    fn test_flat_coverage_falls_back_to_even_spacing() {
+        let set = DensitySetBuilder::new("flat")
+            .characters(" .#")
+            .coverage(vec![3.0, 3.0, 3.0])
+            .build()
+            .unwrap();
+        assert_eq!(set.coverage(), Some(&[0.0, 0.5, 1.0][..]));
+    }
+
+    #[test]
+    fn test_gamma_darkens_midtones() {
+        let set = DensitySetBuilder::new("ramp")
+            .characters(" .:-=+*#%@")
+            .gamma(2.0)
+            .build()
+            .unwrap();
+        // 0.5² = 0.25 → index round(2.25) = 2
+        assert_eq!(set.map_perceptual(0.5), ':');
+        assert_eq!(set.map_perceptual(1.0), '@');
+    }
+
+    #[test]
+    fn test_from_set_keeps_table() {
+        let set = DensitySetBuilder::from_set(DensitySet::blocks())
+            .build()
+            .unwrap();
+        assert_eq!(set.coverage(), DensitySet::blocks().coverage());
+        assert_eq!(set.name, "Blocks");
+    }
+}
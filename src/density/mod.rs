@@ -18,6 +18,10 @@
 //! - `SIMPLE_DENSITY`: Simple 10-character gradient for quick prototypes
 //! - `BLOCKS_DENSITY`: Unicode block characters for modern terminals
 //! - `BRAILLE_DENSITY`: Braille dot progression (unique to dotmax)
+//! - `DOTS_DENSITY`, `SHADE_BLOCKS_DENSITY`, `KATAKANA_MATRIX_DENSITY`: stylized sets
+//!
+//! Sets can also be looked up by name with [`list_sets`] and [`get_set`], which
+//! is what `--density <name>` style flags should use.
 //!
 //! # Examples
 //!
@@ -72,6 +76,24 @@
 //! grid.render_density(&intensities, &density).unwrap();
 //! ```
 //!
+//! ## Perceptual Mapping
+//!
+//! [`DensitySet::map`] spaces characters evenly, but glyphs don't darken a cell
+//! evenly: in the ASCII set `l` is lighter than `!` and `i` comes before it.
+//! [`DensitySet::map_perceptual`] instead picks the character whose measured
+//! ink coverage is closest to the requested intensity. The builtin ASCII,
+//! simple, and blocks sets ship with coverage tables; attach your own with
+//! [`DensitySetBuilder`].
+//!
+//! ```
+//! use dotmax::density::DensitySet;
+//!
+//! let density = DensitySet::blocks();
+//! // '░' covers ~18% of the cell, so 0.2 picks it where map() rounds to ' '
+//! assert_eq!(density.map(0.1), ' ');
+//! assert_eq!(density.map_perceptual(0.2), '░');
+//! ```
+//!
 //! # Performance
 //!
 //! - Intensity mapping: O(1) per cell (array index lookup)
@@ -87,7 +109,18 @@
 //! | `SIMPLE_DENSITY` | 10 chars | Quick prototypes, minimal variation |
 //! | `BLOCKS_DENSITY` | 5 chars | Block-based shading (Unicode) |
 //! | `BRAILLE_DENSITY` | 9 chars | Braille-themed density progression |
+//! | `DOTS_DENSITY` | 6 chars | Dot-only punctuation, soft stippled look |
+//! | `SHADE_BLOCKS_DENSITY` | 9 chars | Eighth blocks, bar-chart-like fill |
+//! | `KATAKANA_MATRIX_DENSITY` | 16 chars | Half-width katakana "digital rain" |
+
+pub mod buffer;
+pub mod builder;
+
+pub use buffer::IntensityBuffer;
+pub use builder::DensitySetBuilder;
 
+use crate::color::schemes::ColorScheme;
+use crate::render::glyphs::glyph_policy;
 use crate::{BrailleGrid, DotmaxError};
 
 /// Predefined ASCII density character set (69 characters)
@@ -182,6 +215,55 @@ pub const BLOCKS_DENSITY: &str = " ░▒▓█";
 /// ```
 pub const BRAILLE_DENSITY: &str = "⠀⠁⠃⠇⠏⠟⠿⡿⣿";
 
+/// Predefined dots-only density character set (6 characters)
+///
+/// Each step adds one dot: nothing, `.`, `:`, `∴`, `∷`, `⁙`. Gives a soft,
+/// stippled look without the letterforms of the ASCII sets.
+///
+/// **Compatibility:** `∴`, `∷`, and `⁙` need Unicode symbol coverage in the font.
+pub const DOTS_DENSITY: &str = " .:∴∷⁙";
+
+/// Predefined eighth-block density character set (9 characters)
+///
+/// Lower eighth blocks from empty to full, so each step fills exactly one more
+/// eighth of the cell. Looks like a column chart when rendered.
+///
+/// **Character progression:** ` ▁▂▃▄▅▆▇█`
+pub const SHADE_BLOCKS_DENSITY: &str = " ▁▂▃▄▅▆▇█";
+
+/// Predefined katakana "digital rain" density character set (16 characters)
+///
+/// Half-width katakana (single terminal column each) ordered roughly by stroke
+/// weight, for the classic falling-code look.
+///
+/// **Compatibility:** Requires a font with half-width katakana (U+FF61-U+FF9F).
+pub const KATAKANA_MATRIX_DENSITY: &str = " ･ｰｨｲｼﾂﾘｸﾀﾓｹｦｱｻﾎ";
+
+/// Relative ink coverage of each character in [`ASCII_DENSITY`]
+///
+/// Measured from DejaVu Sans Mono and normalized so the emptiest glyph is 0.0
+/// and the densest 1.0. Other monospace fonts differ in detail but agree on
+/// the broad shape, which is far from the linear ramp [`DensitySet::map`]
+/// assumes.
+pub const ASCII_COVERAGE: [f32; 69] = [
+    0.00, 0.09, 0.12, 0.08, 0.26, 0.24, 0.16, 0.19, 0.25, 0.64, 0.46, 0.31, 0.49, 0.43, 0.43, 0.22,
+    0.41, 0.12, 0.11, 0.45, 0.50, 0.50, 0.55, 0.55, 0.58, 0.41, 0.41, 0.43, 0.40, 0.51, 0.53, 0.52,
+    0.39, 0.53, 0.59, 0.59, 0.50, 0.47, 0.52, 0.73, 0.56, 0.80, 0.57, 0.60, 0.53, 0.92, 0.89, 0.86,
+    0.72, 0.78, 0.68, 0.79, 0.79, 0.79, 0.79, 0.69, 0.69, 0.70, 0.64, 0.35, 0.88, 1.00, 0.98, 0.83,
+    0.91, 0.68, 1.00, 1.00, 0.76,
+];
+
+/// Relative ink coverage of each character in [`SIMPLE_DENSITY`]
+///
+/// Measured the same way as [`ASCII_COVERAGE`].
+pub const SIMPLE_COVERAGE: [f32; 10] = [0.00, 0.09, 0.19, 0.11, 0.45, 0.41, 0.35, 0.88, 0.68, 1.00];
+
+/// Relative ink coverage of each character in [`BLOCKS_DENSITY`]
+///
+/// The shade characters are nominally 25/50/75% but render lighter in most
+/// fonts.
+pub const BLOCKS_COVERAGE: [f32; 5] = [0.00, 0.18, 0.48, 0.78, 1.00];
+
 /// Character density set for intensity-based rendering
 ///
 /// Maps intensity values [0.0, 1.0] to characters ordered from sparse (low intensity)
@@ -227,6 +309,10 @@ pub struct DensitySet {
     pub characters: Vec<char>,
     /// Descriptive name for this density set
     pub name: String,
+    /// Normalized ink coverage per character, used by `map_perceptual`
+    coverage: Option<Vec<f32>>,
+    /// Exponent applied to intensity before the coverage lookup
+    gamma: f32,
 }
 
 impl DensitySet {
@@ -286,7 +372,12 @@ impl DensitySet {
             });
         }
 
-        Ok(Self { characters, name })
+        Ok(Self {
+            characters,
+            name,
+            coverage: None,
+            gamma: 1.0,
+        })
     }
 
     /// Map intensity value [0.0, 1.0] to character
@@ -348,6 +439,57 @@ impl DensitySet {
         self.characters[index]
     }
 
+    /// Map intensity value [0.0, 1.0] to the character that looks closest to it
+    ///
+    /// Raises the clamped intensity to this set's gamma (1.0 unless set with
+    /// [`DensitySetBuilder::gamma`]), then returns the character whose
+    /// coverage is nearest. Sets without a coverage table, or whose
+    /// `characters` were changed so the table no longer lines up, treat the
+    /// characters as evenly spaced, so with gamma 1.0 this matches [`map`](Self::map).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::density::DensitySet;
+    ///
+    /// let density = DensitySet::simple();
+    /// assert_eq!(density.map_perceptual(0.0), ' ');
+    /// assert_eq!(density.map_perceptual(1.0), '@');
+    /// // '#' is inkier than '%' in practice, so it is picked for bright values
+    /// assert_eq!(density.map_perceptual(0.9), '#');
+    /// ```
+    #[must_use]
+    pub fn map_perceptual(&self, intensity: f32) -> char {
+        let target = intensity.clamp(0.0, 1.0).powf(self.gamma);
+        let Some(coverage) = self
+            .coverage
+            .as_ref()
+            .filter(|c| c.len() == self.characters.len())
+        else {
+            return self.map(target);
+        };
+
+        let mut best = 0;
+        for (i, &c) in coverage.iter().enumerate() {
+            if (c - target).abs() < (coverage[best] - target).abs() {
+                best = i;
+            }
+        }
+        self.characters[best]
+    }
+
+    /// Normalized coverage table used by [`map_perceptual`](Self::map_perceptual), if any
+    #[must_use]
+    pub fn coverage(&self) -> Option<&[f32]> {
+        self.coverage.as_deref()
+    }
+
+    /// Gamma applied to intensity by [`map_perceptual`](Self::map_perceptual)
+    #[must_use]
+    pub const fn gamma(&self) -> f32 {
+        self.gamma
+    }
+
     /// Create predefined ASCII density set (69 characters)
     ///
     /// Returns a density set with the full ASCII gradient, providing maximum
@@ -368,6 +510,8 @@ impl DensitySet {
         Self {
             characters: ASCII_DENSITY.chars().collect(),
             name: "ASCII".to_string(),
+            coverage: Some(ASCII_COVERAGE.to_vec()),
+            gamma: 1.0,
         }
     }
 
@@ -390,6 +534,8 @@ impl DensitySet {
         Self {
             characters: SIMPLE_DENSITY.chars().collect(),
             name: "Simple".to_string(),
+            coverage: Some(SIMPLE_COVERAGE.to_vec()),
+            gamma: 1.0,
         }
     }
 
@@ -412,6 +558,8 @@ impl DensitySet {
         Self {
             characters: BLOCKS_DENSITY.chars().collect(),
             name: "Blocks".to_string(),
+            coverage: Some(BLOCKS_COVERAGE.to_vec()),
+            gamma: 1.0,
         }
     }
 
@@ -434,8 +582,136 @@ impl DensitySet {
         Self {
             characters: BRAILLE_DENSITY.chars().collect(),
             name: "Braille".to_string(),
+            // One more dot per step, so coverage is already linear
+            coverage: None,
+            gamma: 1.0,
+        }
+    }
+
+    /// Create predefined dots-only density set (6 characters)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::density::DensitySet;
+    ///
+    /// let density = DensitySet::dots();
+    /// assert_eq!(density.name, "Dots");
+    /// assert_eq!(density.map(1.0), '⁙');
+    /// ```
+    #[must_use]
+    pub fn dots() -> Self {
+        Self {
+            characters: DOTS_DENSITY.chars().collect(),
+            name: "Dots".to_string(),
+            coverage: None,
+            gamma: 1.0,
         }
     }
+
+    /// Create predefined eighth-block density set (9 characters)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::density::DensitySet;
+    ///
+    /// let density = DensitySet::shade_blocks();
+    /// assert_eq!(density.name, "Shade Blocks");
+    /// assert_eq!(density.map(0.5), '▄');
+    /// ```
+    #[must_use]
+    pub fn shade_blocks() -> Self {
+        Self {
+            characters: SHADE_BLOCKS_DENSITY.chars().collect(),
+            name: "Shade Blocks".to_string(),
+            // Each step is exactly one eighth of the cell
+            coverage: None,
+            gamma: 1.0,
+        }
+    }
+
+    /// Create predefined katakana "digital rain" density set (16 characters)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::density::DensitySet;
+    ///
+    /// let density = DensitySet::katakana_matrix();
+    /// assert_eq!(density.name, "Katakana Matrix");
+    /// assert_eq!(density.characters.len(), 16);
+    /// ```
+    #[must_use]
+    pub fn katakana_matrix() -> Self {
+        Self {
+            characters: KATAKANA_MATRIX_DENSITY.chars().collect(),
+            name: "Katakana Matrix".to_string(),
+            coverage: None,
+            gamma: 1.0,
+        }
+    }
+}
+
+// ============================================================================
+// Discovery API
+// ============================================================================
+
+/// List all predefined density set names.
+///
+/// Returns names in a consistent order, suitable for `--help` output. Use
+/// [`get_set`] to retrieve a set by name.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::density::list_sets;
+///
+/// let names = list_sets();
+/// assert_eq!(names.len(), 7);
+/// assert_eq!(names[0], "ascii");
+/// assert!(names.contains(&"katakana_matrix".to_string()));
+/// ```
+#[must_use]
+pub fn list_sets() -> Vec<String> {
+    vec![
+        "ascii".to_string(),
+        "simple".to_string(),
+        "blocks".to_string(),
+        "braille".to_string(),
+        "dots".to_string(),
+        "shade_blocks".to_string(),
+        "katakana_matrix".to_string(),
+    ]
+}
+
+/// Get a predefined density set by name.
+///
+/// Matching is case-insensitive, and `-` or a space may be used in place of
+/// `_`, so `"Shade Blocks"`, `"shade-blocks"`, and `"shade_blocks"` all match.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::density::get_set;
+///
+/// assert_eq!(get_set("ASCII").unwrap().characters.len(), 69);
+/// assert!(get_set("katakana-matrix").is_some());
+/// assert!(get_set("matrix").is_some()); // alias
+/// assert!(get_set("nonexistent").is_none());
+/// ```
+#[must_use]
+pub fn get_set(name: &str) -> Option<DensitySet> {
+    match name.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+        "ascii" => Some(DensitySet::ascii()),
+        "simple" => Some(DensitySet::simple()),
+        "blocks" => Some(DensitySet::blocks()),
+        "braille" => Some(DensitySet::braille()),
+        "dots" => Some(DensitySet::dots()),
+        "shade_blocks" | "eighths" => Some(DensitySet::shade_blocks()),
+        "katakana_matrix" | "katakana" | "matrix" => Some(DensitySet::katakana_matrix()),
+        _ => None,
+    }
 }
 
 impl BrailleGrid {
@@ -453,6 +729,10 @@ impl BrailleGrid {
     ///   - 0.0 = darkest (sparse character), 1.0 = brightest (dense character)
     /// - `density_set`: Character mapping for intensity → character conversion
     ///
+    /// If the terminal can't show every character of `density_set`, the
+    /// process-wide [`glyph_policy`] picks a set it can instead (see
+    /// [`GlyphPolicy::density_set`](crate::render::glyphs::GlyphPolicy::density_set)).
+    ///
     /// # Errors
     ///
     /// Returns [`DotmaxError::BufferSizeMismatch`] if `intensity_buffer.len() !=
@@ -509,6 +789,8 @@ impl BrailleGrid {
             });
         }
 
+        let density_set = glyph_policy().density_set(density_set);
+
         // Render each intensity value as a character
         // Story 4.4: We store characters in the BrailleGrid using set_char()
         // which overrides braille dot rendering when set
@@ -527,6 +809,43 @@ impl BrailleGrid {
 
         Ok(())
     }
+
+    /// Render intensity buffer as colored characters
+    ///
+    /// Combines [`render_density`](Self::render_density) and
+    /// [`apply_color_scheme`](Self::apply_color_scheme): each cell gets the
+    /// density character for its intensity and the scheme color sampled at the
+    /// same intensity, so bright areas are both denser and hotter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::BufferSizeMismatch`] if `intensity_buffer.len() !=
+    /// grid.width() * grid.height()`. The grid is left untouched in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BrailleGrid, ColorScheme, density::DensitySet};
+    ///
+    /// let mut grid = BrailleGrid::new(10, 1).unwrap();
+    /// let intensities: Vec<f32> = (0..10).map(|i| i as f32 / 9.0).collect();
+    ///
+    /// grid.render_density_colored(&intensities, &DensitySet::simple(), &ColorScheme::heat_map())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(grid.get_char(9, 0), '@');
+    /// assert_eq!(grid.get_color(9, 0).unwrap().r, 255); // white-hot
+    /// ```
+    pub fn render_density_colored(
+        &mut self,
+        intensity_buffer: &[f32],
+        density_set: &DensitySet,
+        scheme: &ColorScheme,
+    ) -> Result<(), DotmaxError> {
+        // Both calls validate the size; the first failing leaves the grid unchanged
+        self.render_density(intensity_buffer, density_set)?;
+        self.apply_color_scheme(intensity_buffer, scheme)
+    }
 }
 
 #[cfg(test)]
@@ -705,4 +1024,110 @@ mod tests {
         assert_eq!(grid.get_char(0, 1), ' ');
         assert_eq!(grid.get_char(2, 1), '@');
     }
+
+    // map_perceptual() tests
+    #[test]
+    fn test_builtin_coverage_tables_match_character_counts() {
+        for set in [
+            DensitySet::ascii(),
+            DensitySet::simple(),
+            DensitySet::blocks(),
+        ] {
+            assert_eq!(set.coverage().unwrap().len(), set.characters.len());
+        }
+        assert!(DensitySet::braille().coverage().is_none());
+    }
+
+    #[test]
+    fn test_map_perceptual_endpoints() {
+        for set in [
+            DensitySet::ascii(),
+            DensitySet::simple(),
+            DensitySet::blocks(),
+        ] {
+            assert_eq!(set.map_perceptual(0.0), ' ');
+            assert_eq!(set.map_perceptual(f32::NAN), ' ');
+        }
+        assert_eq!(DensitySet::blocks().map_perceptual(1.0), '█');
+    }
+
+    #[test]
+    fn test_map_perceptual_without_table_matches_map() {
+        let set = DensitySet::braille();
+        for i in 0..=20 {
+            let v = i as f32 / 20.0;
+            assert_eq!(set.map_perceptual(v), set.map(v));
+        }
+    }
+
+    #[test]
+    fn test_map_perceptual_ignores_stale_table() {
+        let mut set = DensitySet::blocks();
+        set.characters.push('#');
+        assert_eq!(set.map_perceptual(1.0), '#');
+    }
+
+    // Discovery API tests
+    #[test]
+    fn test_every_listed_set_resolves() {
+        for name in list_sets() {
+            let set = get_set(&name).unwrap();
+            assert!(!set.characters.is_empty(), "{name}");
+            // Sparsest character is always blank
+            assert!(
+                set.characters[0] == ' ' || set.characters[0] == '⠀',
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_set_normalizes_names() {
+        assert_eq!(get_set("Shade Blocks").unwrap().name, "Shade Blocks");
+        assert_eq!(get_set("SHADE-BLOCKS").unwrap().name, "Shade Blocks");
+        assert_eq!(get_set(" braille ").unwrap().name, "Braille");
+        assert!(get_set("shadeblocks2").is_none());
+    }
+
+    #[test]
+    fn test_new_sets_are_single_width() {
+        for set in [
+            DensitySet::dots(),
+            DensitySet::shade_blocks(),
+            DensitySet::katakana_matrix(),
+        ] {
+            // Half-width katakana and block elements occupy one column each
+            assert!(set
+                .characters
+                .iter()
+                .all(|c| !('\u{3000}'..='\u{FF60}').contains(c)));
+        }
+    }
+
+    // render_density_colored() tests
+    #[test]
+    fn test_render_density_colored_sets_chars_and_colors() {
+        let mut grid = BrailleGrid::new(3, 1).unwrap();
+        let scheme = ColorScheme::grayscale();
+        grid.render_density_colored(&[0.0, 0.5, 1.0], &DensitySet::simple(), &scheme)
+            .unwrap();
+
+        assert_eq!(grid.get_char(2, 0), '@');
+        assert_eq!(grid.get_color(0, 0), Some(scheme.sample(0.0)));
+        assert_eq!(grid.get_color(1, 0), Some(scheme.sample(0.5)));
+        assert_eq!(grid.get_color(2, 0), Some(scheme.sample(1.0)));
+    }
+
+    #[test]
+    fn test_render_density_colored_size_mismatch_leaves_grid_untouched() {
+        let mut grid = BrailleGrid::new(3, 1).unwrap();
+        let result =
+            grid.render_density_colored(&[1.0; 2], &DensitySet::simple(), &ColorScheme::rainbow());
+        assert!(matches!(
+            result,
+            Err(DotmaxError::BufferSizeMismatch { .. })
+        ));
+        assert_eq!(grid.get_color(0, 0), None);
+        assert_eq!(grid.get_char(0, 0), '⠀');
+    }
 }
@@ -156,16 +156,28 @@ pub enum DotmaxError {
         height: u32,
     },
 
-    /// Invalid parameter value provided to image processing function
+    /// A raw pixel buffer is too short for the frame it should hold
+    ///
+    /// Returned by [`RawFrame`](crate::image::RawFrame) when the bytes end
+    /// before the last pixel of the last row.
+    #[cfg(feature = "image")]
+    #[error("Raw frame buffer too small: need {expected} bytes, got {actual}")]
+    FrameBufferTooSmall {
+        /// Bytes needed to reach the end of the last row
+        expected: usize,
+        /// Bytes provided
+        actual: usize,
+    },
+
+    /// Invalid parameter value outside its valid range
     ///
     /// This error is returned when a function parameter (brightness, contrast,
-    /// gamma, etc.) is outside its valid range.
+    /// gamma, dot aspect ratio, etc.) is outside its valid range.
     ///
     /// The error message includes:
     /// - Parameter name (e.g., "brightness factor")
     /// - Provided value
     /// - Valid range (min-max)
-    #[cfg(feature = "image")]
     #[error("Invalid {parameter_name}: {value} (valid range: {min}-{max})")]
     InvalidParameter {
         /// Name of the invalid parameter
@@ -284,9 +296,9 @@ pub enum DotmaxError {
     /// Supported formats include:
     /// - Static images: PNG, JPEG, GIF (single frame), BMP, WebP, TIFF
     /// - Vector graphics: SVG (requires `svg` feature)
-    /// - Animated: GIF (multi-frame), APNG
+    /// - Animated: GIF (multi-frame), APNG, WebP
     /// - Video: MP4, MKV, AVI, WebM (requires `video` feature)
-    #[error("Unsupported media format: {format}. Supported: static (PNG, JPEG, GIF, BMP, WebP, TIFF), vector (SVG), animated (GIF, APNG), video (MP4, MKV, AVI, WebM)")]
+    #[error("Unsupported media format: {format}. Supported: static (PNG, JPEG, GIF, BMP, WebP, TIFF), vector (SVG), animated (GIF, APNG, WebP), video (MP4, MKV, AVI, WebM)")]
     FormatError {
         /// Description of the detected or unknown format
         format: String,
@@ -327,6 +339,38 @@ pub enum DotmaxError {
         message: String,
     },
 
+    /// Animated WebP decoding or playback error
+    ///
+    /// This error is returned when an animated WebP file cannot be decoded or
+    /// played back. Common causes include:
+    /// - Corrupted WebP file or invalid RIFF chunk structure
+    /// - Missing animation (ANIM/ANMF) chunks
+    /// - Frame decode errors
+    #[cfg(feature = "image")]
+    #[error("WebP error for {path:?}: {message}")]
+    WebpError {
+        /// Path to the WebP file
+        path: std::path::PathBuf,
+        /// Error message
+        message: String,
+    },
+
+    /// A custom pipeline stage returned the wrong kind of data
+    ///
+    /// Each [`InsertionPoint`](crate::image::InsertionPoint) passes one kind
+    /// of [`FrameData`](crate::image::FrameData) through its stages, and a
+    /// stage must hand back the same kind it was given.
+    #[cfg(feature = "image")]
+    #[error("Pipeline stage {stage} returned {found} data where {expected} was expected")]
+    StageOutputMismatch {
+        /// Name of the offending stage
+        stage: String,
+        /// The kind of data the insertion point carries
+        expected: &'static str,
+        /// The kind of data the stage returned
+        found: &'static str,
+    },
+
     /// Video decoding or playback error
     ///
     /// This error is returned when a video file cannot be decoded or played back.
@@ -422,6 +466,176 @@ pub enum DotmaxError {
         /// The device that is in use
         device: String,
     },
+
+    /// Camera does not support the requested capture mode
+    ///
+    /// This error is returned when a webcam builder asks for a pixel format,
+    /// resolution, and frame rate combination the device doesn't offer. The
+    /// error lists the modes the device reported.
+    ///
+    /// # Remediation
+    ///
+    /// - Pick one of the listed modes, or use `WebcamDevice::supported_modes()`
+    /// - Leave the mode unset to let the camera choose
+    #[cfg(feature = "video")]
+    #[error("Camera mode {requested} not supported by {device}. Supported modes: {}", if available.is_empty() { "none reported".to_string() } else { available.join(", ") })]
+    UnsupportedCameraMode {
+        /// The device the mode was requested from
+        device: String,
+        /// The requested mode
+        requested: String,
+        /// Modes the device supports
+        available: Vec<String>,
+    },
+
+    /// Screen capture failed
+    ///
+    /// Common causes include:
+    /// - The monitor or window no longer exists (unplugged or closed)
+    /// - The capture region lies outside the monitor
+    /// - No permission to record the screen (macOS Screen Recording,
+    ///   or a Wayland compositor without the screenshot portal)
+    ///
+    /// Requires the `screen-capture` feature.
+    #[cfg(feature = "screen-capture")]
+    #[error("Screen capture error for {source_name}: {message}")]
+    ScreenCaptureError {
+        /// The monitor or window being captured
+        source_name: String,
+        /// Error message
+        message: String,
+    },
+
+    /// The platform's game controller API could not be opened
+    ///
+    /// Returned by [`Gamepads::new`](crate::input::gamepad::Gamepads::new),
+    /// e.g. when udev is unavailable on Linux.
+    ///
+    /// Requires the `gamepad` feature.
+    #[cfg(feature = "gamepad")]
+    #[error("Gamepad error: {0}")]
+    GamepadError(String),
+
+    /// A key binding could not be parsed
+    ///
+    /// Returned by [`KeyBinding`](crate::keymap::KeyBinding) parsing when a
+    /// key name or modifier isn't recognized, e.g. `"ctrl+enterr"`.
+    #[error("Invalid key binding {binding:?}: {reason}")]
+    InvalidKeyBinding {
+        /// The binding as written
+        binding: String,
+        /// What is wrong with it
+        reason: String,
+    },
+
+    /// A coordinate range is empty or not finite
+    ///
+    /// Returned when mapping world coordinates onto dots, e.g. by
+    /// [`Canvas::new`](crate::primitives::Canvas::new), if a range's ends
+    /// are equal or either is NaN or infinite.
+    #[error("Invalid {axis} range: {start}..{end} (ends must be finite and distinct)")]
+    InvalidRange {
+        /// Which axis the range is for
+        axis: String,
+        /// Start of the range
+        start: f64,
+        /// End of the range
+        end: f64,
+    },
+
+    /// Tabular data could not be turned into chart series
+    ///
+    /// Returned by the [`plot::data`](crate::plot::data) loaders when a CSV
+    /// line has an unterminated quote, a JSON value isn't an array of rows,
+    /// a requested column doesn't exist, or no column holds numbers.
+    #[error("Data error: {0}")]
+    DataError(String),
+
+    /// Bytes could not be decoded as a grid
+    ///
+    /// Returned by [`BrailleGrid::from_bytes`](crate::BrailleGrid::from_bytes)
+    /// when the bytes lack the grid header, were written by an unsupported
+    /// format version, or are truncated or corrupt.
+    #[error("Invalid grid data: {reason}")]
+    InvalidGridData {
+        /// What is wrong with the bytes
+        reason: String,
+    },
+
+    /// Scene description could not be parsed or is invalid
+    ///
+    /// This error is returned when loading a declarative scene file fails:
+    /// - Malformed TOML or JSON syntax
+    /// - Unknown element type, easing name, or animated property
+    /// - Scene dimensions or frame rate outside the supported range
+    ///
+    /// Requires the `scene` feature.
+    #[cfg(feature = "scene")]
+    #[error("Scene error: {0}")]
+    SceneError(String),
+
+    /// Script compilation or runtime error
+    ///
+    /// This error is returned when a live-coding script fails:
+    /// - Syntax errors when the script is (re)compiled
+    /// - The script does not define a `draw(t)` function
+    /// - Runtime errors raised inside `draw(t)`, including bad drawing arguments
+    ///
+    /// The message includes the script path and Rhai's position information.
+    /// Requires the `script` feature.
+    #[cfg(feature = "script")]
+    #[error("Script error: {0}")]
+    ScriptError(String),
+
+    /// Font file could not be read or parsed
+    ///
+    /// Returned when loading a TTF/OTF font fails, either because the file
+    /// can't be read or because its data isn't a font ab_glyph understands.
+    /// Requires the `text` feature.
+    #[cfg(feature = "text")]
+    #[error("Font error: {0}")]
+    FontError(String),
+
+    /// 3D model file could not be parsed
+    ///
+    /// Returned when loading an OBJ or STL model fails: a malformed vertex
+    /// or face (the message names the line), a face index that refers to
+    /// no vertex, data that is neither binary nor ASCII STL, or an
+    /// unrecognized file extension. Requires the `models` feature.
+    #[cfg(feature = "models")]
+    #[error("Model error: {0}")]
+    ModelError(String),
+
+    /// Data could not be encoded as a QR code
+    ///
+    /// Returned by [`quick::render_qr`](crate::quick::render_qr) when the
+    /// data is too long for the largest QR code. Requires the `qr` feature.
+    #[cfg(feature = "qr")]
+    #[error("QR code error: {0}")]
+    QrError(String),
+
+    /// A user callback panicked
+    ///
+    /// Returned by [`AnimationLoop::run`](crate::animation::AnimationLoop::run)
+    /// when the frame callback or event handler panics. The panic is caught
+    /// so the terminal is restored before the error is returned.
+    #[error("Animation {callback} callback panicked: {message}")]
+    CallbackPanic {
+        /// Which callback panicked (`frame` or `event`)
+        callback: &'static str,
+        /// The panic message, if the payload was a string
+        message: String,
+    },
+
+    /// An operation needs a Cargo feature this build was compiled without
+    ///
+    /// Returned by [`BuildInfo::require`](crate::build_info::BuildInfo::require);
+    /// the message names the feature to enable.
+    #[error("Feature '{feature}' is not compiled in (rebuild dotmax with `--features {feature}`)")]
+    FeatureDisabled {
+        /// Cargo name of the missing feature
+        feature: &'static str,
+    },
 }
 
 #[cfg(test)]
@@ -579,7 +793,6 @@ mod tests {
         assert!(msg.contains("10,000"));
     }
 
-    #[cfg(feature = "image")]
     #[test]
     fn test_invalid_parameter_includes_all_context() {
         let err = DotmaxError::InvalidParameter {
@@ -697,4 +910,30 @@ mod tests {
         assert!(msg.contains("in use"));
         assert!(msg.contains("Close other applications"));
     }
+
+    #[cfg(feature = "video")]
+    #[test]
+    fn test_unsupported_camera_mode_lists_modes() {
+        let err = DotmaxError::UnsupportedCameraMode {
+            device: "/dev/video0".to_string(),
+            requested: "yuyv422 1920x1080 @ 60 fps".to_string(),
+            available: vec!["mjpeg 1280x720".to_string(), "yuyv422 640x480".to_string()],
+        };
+        let msg = format!("{err}");
+        assert!(msg.contains("yuyv422 1920x1080 @ 60 fps"));
+        assert!(msg.contains("mjpeg 1280x720, yuyv422 640x480"));
+    }
+
+    #[test]
+    fn test_invalid_range_names_axis_and_ends() {
+        let err = DotmaxError::InvalidRange {
+            axis: "x".to_string(),
+            start: 1.0,
+            end: 1.0,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Invalid x range: 1..1 (ends must be finite and distinct)"
+        );
+    }
 }
@@ -0,0 +1,418 @@
+//! Exporting grids as HTML and SVG.
+//!
+//! Terminal output only lives in a terminal; screenshots of it blur the
+//! dots. These exports keep a grid sharp and colored wherever it's shared:
+//!
+//! - [`to_html`] writes the braille characters in a `<pre>` block, with
+//!   each cell's color, ready to paste into documentation. It looks like
+//!   the terminal does, as long as the reader's font has braille.
+//! - [`to_svg`] draws every dot as a circle or square, so it looks the same
+//!   whatever fonts are installed and scales to any size.
+//! - `to_png` (with the `image` feature) rasterizes the dots the same way,
+//!   for README screenshots and visual diff artifacts in CI. `to_image`
+//!   gives the pixels without encoding them.
+//!
+//! All take their colors from a [`Theme`]: the page background, and the
+//! color of dots and text in cells without a color of their own. Cells
+//! holding a text character rather than dots (see
+//! [`BrailleGrid::set_char`]) keep it in HTML; SVG and PNG have no glyphs,
+//! so they are left out, as in [Sixel](crate::render::sixel) output.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::export::{to_html, to_svg_with, DotShape, SvgOptions, Theme};
+//! use dotmax::{BrailleGrid, Color};
+//!
+//! let mut grid = BrailleGrid::new(4, 1)?;
+//! grid.set_dot(0, 0)?;
+//! grid.set_cell_color(0, 0, Color::rgb(255, 0, 0))?;
+//!
+//! let html = to_html(&grid);
+//! assert!(html.contains(r#"<span style="color:#ff0000">⠁</span>"#));
+//!
+//! let options = SvgOptions {
+//!     shape: DotShape::Square,
+//!     theme: Theme::LIGHT,
+//!     ..SvgOptions::default()
+//! };
+//! let svg = to_svg_with(&grid, &options);
+//! assert!(svg.contains(r##"<rect x="0" y="0" width="6" height="6" fill="#ff0000"/>"##));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt::Write;
+
+#[cfg(feature = "image")]
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Background and default foreground colors of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Theme {
+    /// Color of dots and text in cells without a color of their own
+    pub foreground: Color,
+    /// Page background
+    pub background: Color,
+}
+
+impl Theme {
+    /// Light gray on near-black, like a terminal's defaults.
+    pub const DARK: Self = Self {
+        foreground: Color::rgb(0xe0, 0xe0, 0xe0),
+        background: Color::rgb(0x1e, 0x1e, 0x1e),
+    };
+
+    /// Near-black on white, for printed pages and light sites.
+    pub const LIGHT: Self = Self {
+        foreground: Color::rgb(0x20, 0x20, 0x20),
+        background: Color::rgb(0xff, 0xff, 0xff),
+    };
+}
+
+impl Default for Theme {
+    /// [`Theme::DARK`].
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+/// How [`to_svg_with`] draws each dot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DotShape {
+    /// A circle, with gaps between neighbors like braille dots
+    #[default]
+    Circle,
+    /// A square filling the dot's whole pitch, so neighbors join up
+    Square,
+}
+
+/// How [`to_svg_with`] lays out and colors dots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SvgOptions {
+    /// Distance between neighboring dots, in SVG user units (pixels)
+    pub dot_size: u32,
+    /// Shape of each dot
+    pub shape: DotShape,
+    /// Colors
+    pub theme: Theme,
+}
+
+impl Default for SvgOptions {
+    /// 6-pixel circles on [`Theme::DARK`].
+    fn default() -> Self {
+        Self {
+            dot_size: 6,
+            shape: DotShape::Circle,
+            theme: Theme::DARK,
+        }
+    }
+}
+
+/// `grid` as an HTML `<pre>` block in the [default theme](Theme::default).
+#[must_use]
+pub fn to_html(grid: &BrailleGrid) -> String {
+    to_html_with(grid, &Theme::default())
+}
+
+/// `grid` as an HTML `<pre>` block, one line per row.
+///
+/// Runs of cells with the same color share a `<span>`; cells without a
+/// color take the theme's foreground from the block. Text is escaped, so
+/// the result can be inserted into a page as is.
+#[must_use]
+pub fn to_html_with(grid: &BrailleGrid, theme: &Theme) -> String {
+    let mut html = format!(
+        "<pre class=\"dotmax\" style=\"background:{};color:{};line-height:1;padding:0.5em\">",
+        hex(theme.background),
+        hex(theme.foreground)
+    );
+    for y in 0..grid.height() {
+        if y > 0 {
+            html.push('\n');
+        }
+        // The color of the open span, if one is open
+        let mut open: Option<Color> = None;
+        for x in 0..grid.width() {
+            let color = grid.get_color(x, y);
+            if color != open {
+                if open.is_some() {
+                    html.push_str("</span>");
+                }
+                if let Some(color) = color {
+                    let _ = write!(html, "<span style=\"color:{}\">", hex(color));
+                }
+                open = color;
+            }
+            push_escaped(&mut html, grid.get_char(x, y));
+        }
+        if open.is_some() {
+            html.push_str("</span>");
+        }
+    }
+    html.push_str("</pre>\n");
+    html
+}
+
+/// `grid` as an SVG image of circular dots in the
+/// [default theme](Theme::default).
+#[must_use]
+pub fn to_svg(grid: &BrailleGrid) -> String {
+    to_svg_with(grid, &SvgOptions::default())
+}
+
+/// `grid` as an SVG image, one shape per set dot.
+///
+/// The image is `dot_width() × dot_size` by `dot_height() × dot_size`
+/// units, filled with the theme's background. Dots in cells with a color
+/// are drawn in it; the rest take the theme's foreground from their group.
+#[must_use]
+#[allow(clippy::cast_precision_loss)] // Dot coordinates are far below 2^52
+pub fn to_svg_with(grid: &BrailleGrid, options: &SvgOptions) -> String {
+    let size = options.dot_size.max(1) as usize;
+    let (width, height) = (grid.dot_width() * size, grid.dot_height() * size);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n\
+         <g fill=\"{}\">\n",
+        hex(options.theme.background),
+        hex(options.theme.foreground)
+    );
+    let radius = size as f64 * 2.0 / 5.0;
+    for dot_y in 0..grid.dot_height() {
+        for dot_x in (0..grid.dot_width()).filter(|&dot_x| grid.is_dot_set(dot_x, dot_y)) {
+            let fill = grid
+                .get_color(dot_x / 2, dot_y / 4)
+                .map(|color| format!(" fill=\"{}\"", hex(color)))
+                .unwrap_or_default();
+            let (left, top) = (dot_x * size, dot_y * size);
+            let _ = match options.shape {
+                DotShape::Circle => writeln!(
+                    svg,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\"{fill}/>",
+                    left as f64 + size as f64 / 2.0,
+                    top as f64 + size as f64 / 2.0,
+                ),
+                DotShape::Square => writeln!(
+                    svg,
+                    "<rect x=\"{left}\" y=\"{top}\" width=\"{size}\" height=\"{size}\"{fill}/>"
+                ),
+            };
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+/// `grid` rasterized with each dot a `scale × scale` block of pixels,
+/// holding a circle or filling it.
+///
+/// The image is `dot_width() × scale` by `dot_height() × scale` pixels on
+/// the theme's background. Circles are as wide as in [`to_svg_with`]; at a
+/// scale of 1 or 2 they fill the whole block.
+#[cfg(feature = "image")]
+#[must_use]
+pub fn to_image(grid: &BrailleGrid, scale: u32, theme: &Theme, shape: DotShape) -> image::RgbImage {
+    let scale = scale.max(1);
+    let to_u32 = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+    let rgb = |color: Color| image::Rgb([color.r, color.g, color.b]);
+    let mut image = image::RgbImage::from_pixel(
+        to_u32(grid.dot_width()).saturating_mul(scale),
+        to_u32(grid.dot_height()).saturating_mul(scale),
+        rgb(theme.background),
+    );
+    let block = dot_pixels(scale, shape);
+    for dot_y in 0..grid.dot_height() {
+        for dot_x in (0..grid.dot_width()).filter(|&dot_x| grid.is_dot_set(dot_x, dot_y)) {
+            let color = grid
+                .get_color(dot_x / 2, dot_y / 4)
+                .unwrap_or(theme.foreground);
+            let (left, top) = (to_u32(dot_x) * scale, to_u32(dot_y) * scale);
+            for &(x, y) in &block {
+                image.put_pixel(left + x, top + y, rgb(color));
+            }
+        }
+    }
+    image
+}
+
+/// `grid` as PNG file contents, with each dot a circle `scale` pixels
+/// across (see [`to_image`]).
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidDimensions`] if the image would be more
+/// than `u32::MAX` pixels wide or high, or [`DotmaxError::Terminal`] if
+/// encoding fails.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::export::{to_png, Theme};
+/// use dotmax::BrailleGrid;
+///
+/// let mut grid = BrailleGrid::new(40, 10)?;
+/// grid.set_dot(0, 0)?;
+/// let png = to_png(&grid, 4, &Theme::LIGHT)?;
+/// assert!(png.starts_with(b"\x89PNG"));
+/// // std::fs::write("docs/screenshot.png", png)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn to_png(grid: &BrailleGrid, scale: u32, theme: &Theme) -> Result<Vec<u8>, DotmaxError> {
+    use image::codecs::png::PngEncoder;
+    use image::{ExtendedColorType, ImageEncoder};
+
+    let scale = scale.max(1);
+    let fits = |dots: usize| {
+        u32::try_from(dots)
+            .ok()
+            .and_then(|dots| dots.checked_mul(scale))
+            .is_some()
+    };
+    if !fits(grid.dot_width()) || !fits(grid.dot_height()) {
+        return Err(DotmaxError::InvalidDimensions {
+            width: grid.width(),
+            height: grid.height(),
+        });
+    }
+
+    let image = to_image(grid, scale, theme, DotShape::Circle);
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            ExtendedColorType::Rgb8,
+        )
+        .map_err(|e| DotmaxError::Terminal(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+    Ok(png)
+}
+
+/// Offsets of the pixels a dot covers within its `scale × scale` block.
+#[cfg(feature = "image")]
+fn dot_pixels(scale: u32, shape: DotShape) -> Vec<(u32, u32)> {
+    let center = f64::from(scale) / 2.0;
+    let radius = f64::from(scale) * 2.0 / 5.0;
+    (0..scale)
+        .flat_map(|y| (0..scale).map(move |x| (x, y)))
+        .filter(|&(x, y)| match shape {
+            DotShape::Square => true,
+            // Pixels whose centers fall inside the circle
+            DotShape::Circle => {
+                (f64::from(x) + 0.5 - center).hypot(f64::from(y) + 0.5 - center) <= radius
+            }
+        })
+        .collect()
+}
+
+/// `#rrggbb`
+fn hex(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// Appends `ch`, escaped for HTML text.
+fn push_escaped(out: &mut String, ch: char) {
+    match ch {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        _ => out.push(ch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> BrailleGrid {
+        let mut grid = BrailleGrid::new(3, 2).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        grid.set_dot(1, 0).unwrap();
+        grid.set_dot(2, 0).unwrap();
+        grid.set_cell_color(1, 0, Color::rgb(0, 128, 255)).unwrap();
+        grid.set_char(2, 1, '<').unwrap();
+        grid
+    }
+
+    #[test]
+    fn test_html_rows_colors_and_escaping() {
+        let html = to_html_with(&sample(), &Theme::LIGHT);
+        assert!(html.starts_with("<pre class=\"dotmax\" style=\"background:#ffffff;color:#202020;"));
+        let body = html
+            .split_once('>')
+            .unwrap()
+            .1
+            .trim_end()
+            .trim_end_matches("</pre>");
+        assert_eq!(body, "⠉<span style=\"color:#0080ff\">⠁</span>⠀\n⠀⠀&lt;");
+    }
+
+    #[test]
+    fn test_html_merges_color_runs() {
+        let mut grid = BrailleGrid::new(3, 1).unwrap();
+        let red = Color::rgb(255, 0, 0);
+        grid.set_cell_color(0, 0, red).unwrap();
+        grid.set_cell_color(1, 0, red).unwrap();
+        let html = to_html(&grid);
+        assert_eq!(html.matches("<span").count(), 1);
+        assert!(html.contains("<span style=\"color:#ff0000\">⠀⠀</span>⠀"));
+    }
+
+    #[test]
+    fn test_svg_draws_each_set_dot() {
+        let svg = to_svg(&sample());
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"36\" height=\"48\"")
+        );
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains("<circle cx=\"3\" cy=\"3\" r=\"2.4\"/>"));
+        // The colored cell's dot carries its own fill
+        assert!(svg.contains("<circle cx=\"15\" cy=\"3\" r=\"2.4\" fill=\"#0080ff\"/>"));
+        assert!(svg.trim_end().ends_with("</g>\n</svg>"));
+    }
+
+    #[test]
+    fn test_svg_squares() {
+        let options = SvgOptions {
+            dot_size: 2,
+            shape: DotShape::Square,
+            theme: Theme::DARK,
+        };
+        let svg = to_svg_with(&sample(), &options);
+        assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#1e1e1e\"/>"));
+        assert!(svg.contains("<g fill=\"#e0e0e0\">"));
+        assert!(svg.contains("<rect x=\"2\" y=\"0\" width=\"2\" height=\"2\"/>"));
+        assert!(!svg.contains("<circle"));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_image_colors_and_shapes() {
+        let image = to_image(&sample(), 3, &Theme::LIGHT, DotShape::Circle);
+        assert_eq!(image.dimensions(), (18, 24));
+        let background = image::Rgb([0xff, 0xff, 0xff]);
+        // A circle leaves its block's corners empty
+        assert_eq!(*image.get_pixel(1, 1), image::Rgb([0x20, 0x20, 0x20]));
+        assert_eq!(*image.get_pixel(0, 0), background);
+        // The dot in the colored cell
+        assert_eq!(*image.get_pixel(7, 1), image::Rgb([0, 128, 255]));
+        // Text cells have no pixels
+        assert!((12..18).all(|x| (12..24).all(|y| *image.get_pixel(x, y) == background)));
+
+        let squares = to_image(&sample(), 3, &Theme::LIGHT, DotShape::Square);
+        assert_eq!(*squares.get_pixel(0, 0), image::Rgb([0x20, 0x20, 0x20]));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_png_round_trips() {
+        let png = to_png(&sample(), 2, &Theme::DARK).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(
+            decoded,
+            to_image(&sample(), 2, &Theme::DARK, DotShape::Circle)
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Building blocks for 2D terminal games.
+//!
+//! A game world is usually bigger than the screen and built from a few
+//! small images repeated: [`Tilemap`] draws such a world from the tiles of
+//! a [`SpriteAtlas`], as seen from a camera that can move a dot at a time.
+//! Sprites go on top with [`BrailleGrid::blit`](crate::BrailleGrid::blit),
+//! hits are tested with [`collide`](crate::collide), and
+//! [`input`](crate::input) and [`AnimationLoop`](crate::AnimationLoop)
+//! drive the rest.
+
+pub mod tilemap;
+
+pub use tilemap::{SpriteAtlas, Tilemap};
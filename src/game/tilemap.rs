@@ -0,0 +1,408 @@
+//! Worlds drawn from a grid of tiles.
+//!
+//! A [`SpriteAtlas`] holds the tile images, all the same size in dots,
+//! usually cut from one sprite sheet drawn or loaded as a [`BrailleGrid`].
+//! A [`Tilemap`] is a 2D array of indices into the atlas, with a camera:
+//! [`render`](Tilemap::render) draws the part of the map the camera sees,
+//! touching only the tiles that are at least partly on screen, so maps
+//! far larger than the terminal cost no more to draw than one screenful.
+//!
+//! Tiles are drawn with [`BlitMode::Or`] and keep the colors they have in
+//! the atlas. Their empty dots are transparent, so the map can go over a
+//! background layer and sprites over it.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::game::{SpriteAtlas, Tilemap};
+//! use dotmax::primitives::{draw_line, draw_rectangle};
+//! use dotmax::BrailleGrid;
+//!
+//! // A sheet of two 8×8-dot tiles: a brick and a ladder
+//! let mut sheet = BrailleGrid::new(8, 2)?;
+//! draw_rectangle(&mut sheet, 0, 0, 8, 8)?;
+//! draw_line(&mut sheet, 9, 0, 9, 7)?;
+//! draw_line(&mut sheet, 14, 0, 14, 7)?;
+//! draw_line(&mut sheet, 9, 4, 14, 4)?;
+//! let atlas = SpriteAtlas::from_sheet(&sheet, 8, 8)?;
+//!
+//! const BRICK: Option<usize> = Some(0);
+//! const LADDER: Option<usize> = Some(1);
+//! let mut map = Tilemap::new(atlas, 100, 20);
+//! for x in 0..100 {
+//!     map.set(x, 19, BRICK)?;
+//! }
+//! map.set(10, 18, LADDER)?;
+//!
+//! // Follow a player walking along the floor
+//! map.set_camera(40, 80);
+//! let mut screen = BrailleGrid::new(40, 20)?;
+//! map.render(&mut screen);
+//! assert!(screen.is_dot_set(0, 72)); // Top edge of the floor
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::error::DotmaxError;
+use crate::grid::{BlitColor, BlitMode, BrailleGrid};
+
+/// Tile images, all the same size, numbered from 0.
+#[derive(Debug, Clone)]
+pub struct SpriteAtlas {
+    tiles: Vec<BrailleGrid>,
+    tile_width: usize,
+    tile_height: usize,
+}
+
+impl SpriteAtlas {
+    /// Cuts `sheet` into `tile_width × tile_height` dot tiles, numbered
+    /// left to right, then top to bottom. Dots past the last whole tile on
+    /// the right or bottom are left out. Dot colors come from their cell
+    /// in the sheet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if a tile size is zero
+    /// or larger than the sheet.
+    pub fn from_sheet(
+        sheet: &BrailleGrid,
+        tile_width: usize,
+        tile_height: usize,
+    ) -> Result<Self, DotmaxError> {
+        let columns = sheet.dot_width().checked_div(tile_width).unwrap_or(0);
+        let rows = sheet.dot_height().checked_div(tile_height).unwrap_or(0);
+        if columns == 0 || rows == 0 {
+            return Err(DotmaxError::InvalidDimensions {
+                width: tile_width,
+                height: tile_height,
+            });
+        }
+        let mut tiles = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                tiles.push(cut(
+                    sheet,
+                    column * tile_width,
+                    row * tile_height,
+                    tile_width,
+                    tile_height,
+                )?);
+            }
+        }
+        Ok(Self {
+            tiles,
+            tile_width,
+            tile_height,
+        })
+    }
+
+    /// An atlas of separately drawn tiles, which must all have the same
+    /// dimensions. Tile sizes are whole cells here; use
+    /// [`from_sheet`](Self::from_sheet) for others.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] with the odd tile's size
+    /// if the tiles differ in size, or with zeros if there are none.
+    pub fn from_tiles(tiles: Vec<BrailleGrid>) -> Result<Self, DotmaxError> {
+        let Some(first) = tiles.first() else {
+            return Err(DotmaxError::InvalidDimensions {
+                width: 0,
+                height: 0,
+            });
+        };
+        let size = first.dimensions();
+        if let Some(odd) = tiles.iter().find(|tile| tile.dimensions() != size) {
+            let (width, height) = odd.dimensions();
+            return Err(DotmaxError::InvalidDimensions { width, height });
+        }
+        Ok(Self {
+            tile_width: first.dot_width(),
+            tile_height: first.dot_height(),
+            tiles,
+        })
+    }
+
+    /// Number of tiles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Whether there are no tiles. Never true for an atlas that was built
+    /// successfully.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Tile `index`, if there is one.
+    #[must_use]
+    pub fn tile(&self, index: usize) -> Option<&BrailleGrid> {
+        self.tiles.get(index)
+    }
+
+    /// Width and height of every tile, in dots.
+    #[must_use]
+    pub const fn tile_size(&self) -> (usize, usize) {
+        (self.tile_width, self.tile_height)
+    }
+}
+
+/// The `width × height` dots of `sheet` at (`x`, `y`) as a grid of their
+/// own, padded with empty dots to whole cells.
+fn cut(
+    sheet: &BrailleGrid,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> Result<BrailleGrid, DotmaxError> {
+    let mut tile = BrailleGrid::new((width + 1) / 2, (height + 3) / 4)?;
+    for dy in 0..height {
+        for dx in 0..width {
+            if sheet.is_dot_set(x + dx, y + dy) {
+                tile.set_dot(dx, dy)?;
+                if let Some(color) = sheet.get_color((x + dx) / 2, (y + dy) / 4) {
+                    tile.set_cell_color(dx / 2, dy / 4, color)?;
+                }
+            }
+        }
+    }
+    Ok(tile)
+}
+
+/// A 2D array of tiles from a [`SpriteAtlas`], seen through a camera.
+#[derive(Debug, Clone)]
+pub struct Tilemap {
+    atlas: SpriteAtlas,
+    width: usize,
+    height: usize,
+    /// Row-major tile indices; `None` is an empty tile
+    tiles: Vec<Option<usize>>,
+    camera: (i32, i32),
+}
+
+impl Tilemap {
+    /// An empty `width × height` tile map, with the camera at the map's
+    /// top-left corner.
+    #[must_use]
+    pub fn new(atlas: SpriteAtlas, width: usize, height: usize) -> Self {
+        Self {
+            atlas,
+            width,
+            height,
+            tiles: vec![None; width * height],
+            camera: (0, 0),
+        }
+    }
+
+    /// A map from rows of tile indices, top row first, with `None` for
+    /// empty tiles. Rows shorter than the longest are padded with empty
+    /// tiles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if an index has no tile
+    /// in `atlas`.
+    pub fn from_rows<R>(atlas: SpriteAtlas, rows: &[R]) -> Result<Self, DotmaxError>
+    where
+        R: AsRef<[Option<usize>]>,
+    {
+        let width = rows.iter().map(|row| row.as_ref().len()).max().unwrap_or(0);
+        let mut map = Self::new(atlas, width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &tile) in row.as_ref().iter().enumerate() {
+                map.set(x, y, tile)?;
+            }
+        }
+        Ok(map)
+    }
+
+    /// Width and height of the map, in tiles.
+    #[must_use]
+    pub const fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Width and height of the whole map, in dots.
+    #[must_use]
+    pub const fn dot_dimensions(&self) -> (usize, usize) {
+        (
+            self.width * self.atlas.tile_width,
+            self.height * self.atlas.tile_height,
+        )
+    }
+
+    /// The atlas tiles are drawn from.
+    #[must_use]
+    pub const fn atlas(&self) -> &SpriteAtlas {
+        &self.atlas
+    }
+
+    /// The tile at (`x`, `y`), or `None` if it's empty or off the map.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            self.tiles[y * self.width + x]
+        } else {
+            None
+        }
+    }
+
+    /// Puts `tile` at (`x`, `y`); `None` empties it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::OutOfBounds`] if (`x`, `y`) is off the map,
+    /// or [`DotmaxError::InvalidParameter`] if `tile` has no image in the
+    /// atlas.
+    pub fn set(&mut self, x: usize, y: usize, tile: Option<usize>) -> Result<(), DotmaxError> {
+        if x >= self.width || y >= self.height {
+            return Err(DotmaxError::OutOfBounds {
+                x,
+                y,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if let Some(index) = tile.filter(|&index| index >= self.atlas.len()) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "tile index".to_string(),
+                value: index.to_string(),
+                min: "0".to_string(),
+                max: (self.atlas.len() - 1).to_string(),
+            });
+        }
+        self.tiles[y * self.width + x] = tile;
+        Ok(())
+    }
+
+    /// Moves the camera so the map dot at (`x`, `y`) is drawn at the top
+    /// left of the grid. It may look past the edges of the map, which show
+    /// as empty.
+    pub fn set_camera(&mut self, x: i32, y: i32) {
+        self.camera = (x, y);
+    }
+
+    /// The map dot the camera shows at the grid's top left.
+    #[must_use]
+    pub const fn camera(&self) -> (i32, i32) {
+        self.camera
+    }
+
+    /// The map position of the tile covering map dot (`x`, `y`), if that's
+    /// on the map. Use it to find what a sprite stands on.
+    #[must_use]
+    pub fn tile_at(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        let column = usize::try_from(x).ok()? / self.atlas.tile_width;
+        let row = usize::try_from(y).ok()? / self.atlas.tile_height;
+        (column < self.width && row < self.height).then_some((column, row))
+    }
+
+    /// Draws the tiles the camera sees onto `grid`, over what's already
+    /// there.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn render(&self, grid: &mut BrailleGrid) {
+        let (tile_width, tile_height) = self.atlas.tile_size();
+        let (camera_x, camera_y) = (i64::from(self.camera.0), i64::from(self.camera.1));
+        // Tiles from the one under the top-left dot to the one under the
+        // bottom-right dot, clamped to the map
+        let visible = |camera: i64, view: usize, tile: usize, tiles: usize| {
+            let (tile, tiles) = (tile as i64, tiles as i64);
+            let first = camera.div_euclid(tile).clamp(0, tiles);
+            let last = (camera + view as i64 - 1)
+                .div_euclid(tile)
+                .clamp(-1, tiles - 1);
+            first as usize..(last + 1).max(first) as usize
+        };
+        let columns = visible(camera_x, grid.dot_width(), tile_width, self.width);
+        let rows = visible(camera_y, grid.dot_height(), tile_height, self.height);
+
+        for y in rows {
+            for x in columns.clone() {
+                let Some(tile) = self.get(x, y).and_then(|index| self.atlas.tile(index)) else {
+                    continue;
+                };
+                let dest_x = (x * tile_width) as i64 - camera_x;
+                let dest_y = (y * tile_height) as i64 - camera_y;
+                grid.blit(
+                    tile,
+                    dest_x as i32,
+                    dest_y as i32,
+                    BlitMode::Or,
+                    BlitColor::Source,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+
+    #[test]
+    fn test_atlas_from_sheet() {
+        // Three 3×3-dot tiles in a row, each with one dot at its center
+        let mut sheet = BrailleGrid::new(5, 1).unwrap();
+        for tile in 0..3 {
+            sheet.set_dot(tile * 3 + 1, 1).unwrap();
+        }
+        sheet.set_cell_color(2, 0, Color::rgb(0, 255, 0)).unwrap();
+
+        let atlas = SpriteAtlas::from_sheet(&sheet, 3, 3).unwrap();
+        assert_eq!(atlas.len(), 3);
+        assert_eq!(atlas.tile_size(), (3, 3));
+        let tile = atlas.tile(1).unwrap();
+        assert_eq!(tile.dimensions(), (2, 1));
+        assert!(tile.is_dot_set(1, 1));
+        assert_eq!(tile.get_color(0, 0), Some(Color::rgb(0, 255, 0)));
+        assert_eq!(
+            (0..6 * 4).filter(|i| tile.is_dot_set(i % 4, i / 4)).count(),
+            1
+        );
+
+        assert!(SpriteAtlas::from_sheet(&sheet, 0, 3).is_err());
+        assert!(SpriteAtlas::from_sheet(&sheet, 3, 5).is_err());
+        assert!(SpriteAtlas::from_tiles(vec![
+            BrailleGrid::new(1, 1).unwrap(),
+            BrailleGrid::new(2, 1).unwrap()
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_render_follows_camera() {
+        let mut block = BrailleGrid::new(2, 1).unwrap();
+        block.set_dot(0, 0).unwrap();
+        let atlas = SpriteAtlas::from_tiles(vec![block]).unwrap();
+        let mut map =
+            Tilemap::from_rows(atlas, &[vec![Some(0), None, Some(0)], vec![None, Some(0)]])
+                .unwrap();
+        assert_eq!(map.dimensions(), (3, 2));
+        assert_eq!(map.dot_dimensions(), (12, 8));
+        assert!(map.set(0, 0, Some(1)).is_err());
+        assert!(map.set(3, 0, None).is_err());
+
+        let render = |map: &Tilemap| {
+            let mut grid = BrailleGrid::new(3, 2).unwrap();
+            map.render(&mut grid);
+            (0..6 * 8)
+                .filter(|i| grid.is_dot_set(i % 6, i / 6))
+                .map(|i| (i % 6, i / 6))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(render(&map), [(0, 0), (4, 4)]);
+        map.set_camera(3, -1);
+        assert_eq!(render(&map), [(5, 1), (1, 5)]);
+        map.set_camera(-5, 0);
+        assert_eq!(render(&map), [(5, 0)]);
+        map.set_camera(100, 0);
+        assert!(render(&map).is_empty());
+
+        assert_eq!(map.tile_at(5, 7), Some((1, 1)));
+        assert_eq!(map.tile_at(-1, 0), None);
+        assert_eq!(map.tile_at(12, 0), None);
+    }
+}
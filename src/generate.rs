@@ -0,0 +1,388 @@
+//! Procedural patterns as intensity buffers.
+//!
+//! A [`Pattern`] fills an [`IntensityBuffer`] with values from 0.0 to 1.0:
+//! smooth noise, plasma, checkerboards, and linear or radial gradients.
+//! They are ready-made content for trying out color schemes and density
+//! sets, and backgrounds for animations. Draw one as characters with
+//! [`IntensityBuffer::render`] or as dots with
+//! [`IntensityBuffer::render_dots`].
+//!
+//! Every pattern also moves: [`Pattern::frame`] takes a time in seconds.
+//! Noise drifts through a third dimension, plasma waves travel, and
+//! checkerboards and gradients scroll. [`Pattern::generate`] is the frame
+//! at time 0.
+//!
+//! Sizes and scales are in buffer values, so a buffer the size of a grid in
+//! dots maps one value to each dot. The same pattern, size, and time always
+//! give the same buffer; noise varies only with its seed.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::density::DensitySet;
+//! use dotmax::generate::Pattern;
+//! use dotmax::{BrailleGrid, ColorScheme};
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! let clouds = Pattern::Noise { scale: 16.0, octaves: 4, seed: 7 };
+//! clouds
+//!     .frame(grid.width(), grid.height(), 1.5)
+//!     .render(&mut grid, &DensitySet::simple(), Some(&ColorScheme::heat_map()))?;
+//!
+//! // Or as dots
+//! let mut dots = BrailleGrid::new(40, 12)?;
+//! Pattern::Plasma { scale: 12.0 }
+//!     .generate(dots.dot_width(), dots.dot_height())
+//!     .render_dots(&mut dots, 0.5)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::f32::consts::TAU;
+
+use crate::density::IntensityBuffer;
+
+/// A procedural pattern; see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pattern {
+    /// Fractal Perlin noise: `octaves` layers of [`Noise`], each twice the
+    /// frequency and half the weight of the last, with features about
+    /// `scale` values across. Drifts at one feature per 4 seconds.
+    Noise {
+        /// Size of the largest features
+        scale: f32,
+        /// Layers of detail (at least 1)
+        octaves: u32,
+        /// Picks one of many unrelated noise fields
+        seed: u64,
+    },
+    /// Interfering sine waves about `scale` values apart, one full cycle
+    /// per second.
+    Plasma {
+        /// Distance between wave crests, roughly
+        scale: f32,
+    },
+    /// Alternating squares `size` values wide, 0.0 in the top-left one,
+    /// scrolling down and to the right one square per second.
+    Checkerboard {
+        /// Side of each square (at least 1)
+        size: usize,
+    },
+    /// 0.0 to 1.0 across the buffer along `angle` radians clockwise from
+    /// the positive x axis, scrolling along it once per second and
+    /// wrapping.
+    LinearGradient {
+        /// Direction of increasing intensity
+        angle: f32,
+    },
+    /// 1.0 at the center fading to 0.0 at the corners, with rings
+    /// pulsing outward once per second.
+    RadialGradient,
+}
+
+impl Pattern {
+    /// The pattern at time 0.
+    #[must_use]
+    pub fn generate(&self, width: usize, height: usize) -> IntensityBuffer {
+        self.frame(width, height, 0.0)
+    }
+
+    /// The pattern `t` seconds in.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::many_single_char_names
+    )]
+    pub fn frame(&self, width: usize, height: usize, t: f32) -> IntensityBuffer {
+        let (w, h) = (width as f32, height as f32);
+        match *self {
+            Self::Noise {
+                scale,
+                octaves,
+                seed,
+            } => {
+                let noise = Noise::new(seed);
+                let scale = scale.max(f32::EPSILON);
+                IntensityBuffer::from_fn(width, height, |x, y| {
+                    let value = noise.fbm(x as f32 / scale, y as f32 / scale, t / 4.0, octaves);
+                    (value * 0.5 + 0.5).clamp(0.0, 1.0)
+                })
+            }
+            Self::Plasma { scale } => {
+                let scale = scale.max(f32::EPSILON);
+                let phase = t * TAU;
+                IntensityBuffer::from_fn(width, height, |x, y| {
+                    let (x, y) = (x as f32 / scale, y as f32 / scale);
+                    let (cx, cy) = (x - w / scale / 2.0, y - h / scale / 2.0);
+                    let sum = (x + phase).sin()
+                        + (y.mul_add(0.5, phase)).sin()
+                        + ((x + y).mul_add(0.5, phase)).sin()
+                        + (cx.hypot(cy) + phase).sin();
+                    sum / 8.0 + 0.5
+                })
+            }
+            Self::Checkerboard { size } => {
+                let size = size.max(1);
+                let shift = (t.max(0.0) * size as f32) as usize;
+                IntensityBuffer::from_fn(width, height, |x, y| {
+                    let (col, row) = (
+                        (x + size * 2 - shift % (size * 2)) / size,
+                        (y + size * 2 - shift % (size * 2)) / size,
+                    );
+                    if (col + row) % 2 == 0 {
+                        0.0
+                    } else {
+                        1.0
+                    }
+                })
+            }
+            Self::LinearGradient { angle } => {
+                let (sin, cos) = angle.sin_cos();
+                // Project the corners to find the span along the direction
+                let corners = [
+                    (0.0, 0.0),
+                    (w - 1.0, 0.0),
+                    (0.0, h - 1.0),
+                    (w - 1.0, h - 1.0),
+                ]
+                .map(|(x, y): (f32, f32)| x.mul_add(cos, y * sin));
+                let min = corners.iter().copied().fold(f32::INFINITY, f32::min);
+                let max = corners.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let span = (max - min).max(f32::EPSILON);
+                IntensityBuffer::from_fn(width, height, |x, y| {
+                    let along = ((x as f32).mul_add(cos, y as f32 * sin) - min) / span;
+                    if t == 0.0 {
+                        along
+                    } else {
+                        (along - t).rem_euclid(1.0)
+                    }
+                })
+            }
+            Self::RadialGradient => {
+                let (cx, cy) = ((w - 1.0) / 2.0, (h - 1.0) / 2.0);
+                let radius = cx.hypot(cy).max(f32::EPSILON);
+                IntensityBuffer::from_fn(width, height, |x, y| {
+                    let distance = (x as f32 - cx).hypot(y as f32 - cy) / radius;
+                    let fade = 1.0 - distance.min(1.0);
+                    if t == 0.0 {
+                        fade
+                    } else {
+                        (fade + t).rem_euclid(1.0)
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Seeded 3D Perlin gradient noise.
+///
+/// Values are roughly -1.0 to 1.0, zero at integer coordinates, and change
+/// smoothly in between. The third coordinate is usually time.
+#[derive(Debug, Clone)]
+pub struct Noise {
+    permutation: [u8; 512],
+}
+
+impl Noise {
+    /// The noise field for `seed`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(seed: u64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        // Xorshift state must be non-zero
+        let mut rng = seed ^ 0x9E37_79B9_7F4A_7C15;
+        for i in (1..table.len()).rev() {
+            rng ^= rng << 13;
+            rng ^= rng >> 7;
+            rng ^= rng << 17;
+            let j = (rng % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        Self {
+            permutation: std::array::from_fn(|i| table[i % 256]),
+        }
+    }
+
+    /// The noise value at `(x, y, z)`.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::many_single_char_names
+    )]
+    pub fn sample(&self, x: f32, y: f32, z: f32) -> f32 {
+        let p = &self.permutation;
+        let (fx, fy, fz) = (x.floor(), y.floor(), z.floor());
+        // Wrap cell coordinates into the table
+        let (xi, yi, zi) = (
+            (fx as i64 & 255) as usize,
+            (fy as i64 & 255) as usize,
+            (fz as i64 & 255) as usize,
+        );
+        let (x, y, z) = (x - fx, y - fy, z - fz);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let a = p[xi] as usize + yi;
+        let (aa, ab) = (p[a] as usize + zi, p[a + 1] as usize + zi);
+        let b = p[xi + 1] as usize + yi;
+        let (ba, bb) = (p[b] as usize + zi, p[b + 1] as usize + zi);
+
+        let corner = |hash: usize, dx: f32, dy: f32, dz: f32| grad(p[hash], x - dx, y - dy, z - dz);
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(aa, 0.0, 0.0, 0.0), corner(ba, 1.0, 0.0, 0.0)),
+                lerp(u, corner(ab, 0.0, 1.0, 0.0), corner(bb, 1.0, 1.0, 0.0)),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    corner(aa + 1, 0.0, 0.0, 1.0),
+                    corner(ba + 1, 1.0, 0.0, 1.0),
+                ),
+                lerp(
+                    u,
+                    corner(ab + 1, 0.0, 1.0, 1.0),
+                    corner(bb + 1, 1.0, 1.0, 1.0),
+                ),
+            ),
+        )
+    }
+
+    /// `octaves` layers of [`sample`](Self::sample) (at least 1), each at
+    /// twice the frequency and half the weight of the one before, scaled
+    /// back to about -1.0 to 1.0.
+    #[must_use]
+    pub fn fbm(&self, x: f32, y: f32, z: f32, octaves: u32) -> f32 {
+        let (mut sum, mut weight, mut total, mut frequency) = (0.0, 1.0, 0.0, 1.0);
+        for octave in 0..octaves.max(1) {
+            // Offset each layer so their zeros don't line up
+            let offset = octave as f32 * 17.31;
+            sum += weight
+                * self.sample(
+                    x.mul_add(frequency, offset),
+                    y.mul_add(frequency, offset),
+                    z * frequency,
+                );
+            total += weight;
+            weight *= 0.5;
+            frequency *= 2.0;
+        }
+        sum / total
+    }
+}
+
+/// Perlin's smootherstep, `6t⁵ - 15t⁴ + 10t³`.
+fn fade(t: f32) -> f32 {
+    t * t * t * t.mul_add(t.mul_add(6.0, -15.0), 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    t.mul_add(b - a, a)
+}
+
+/// Dot product of `(x, y, z)` with one of 12 cube-edge gradients picked by
+/// `hash`.
+#[allow(clippy::many_single_char_names)]
+fn grad(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_range(buffer: &IntensityBuffer) -> bool {
+        buffer.as_slice().iter().all(|v| (0.0..=1.0).contains(v))
+    }
+
+    #[test]
+    fn test_noise_is_seeded_and_smooth() {
+        let noise = Noise::new(1);
+        assert_eq!(noise.sample(3.0, 4.0, 5.0), 0.0);
+        let a = noise.sample(3.3, 4.7, 0.2);
+        assert_eq!(Noise::new(1).sample(3.3, 4.7, 0.2), a);
+        assert_ne!(Noise::new(2).sample(3.3, 4.7, 0.2), a);
+        assert!((noise.sample(3.31, 4.7, 0.2) - a).abs() < 0.05);
+
+        let pattern = Pattern::Noise {
+            scale: 8.0,
+            octaves: 3,
+            seed: 9,
+        };
+        let buffer = pattern.generate(40, 20);
+        assert!(in_range(&buffer));
+        assert_eq!(pattern.generate(40, 20), buffer);
+        assert_ne!(pattern.frame(40, 20, 1.0), buffer);
+    }
+
+    #[test]
+    fn test_plasma_range_and_motion() {
+        let plasma = Pattern::Plasma { scale: 5.0 };
+        let buffer = plasma.generate(30, 10);
+        assert!(in_range(&buffer));
+        assert_ne!(plasma.frame(30, 10, 0.25), buffer);
+        // One full cycle per second
+        let later = plasma.frame(30, 10, 1.0);
+        for (a, b) in buffer.as_slice().iter().zip(later.as_slice()) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_checkerboard_alternates_and_scrolls() {
+        let board = Pattern::Checkerboard { size: 2 };
+        let buffer = board.generate(4, 4);
+        assert_eq!(
+            buffer.as_slice(),
+            [
+                0.0, 0.0, 1.0, 1.0, //
+                0.0, 0.0, 1.0, 1.0, //
+                1.0, 1.0, 0.0, 0.0, //
+                1.0, 1.0, 0.0, 0.0,
+            ]
+        );
+        // Half a second moves one value down and right
+        let moved = board.frame(4, 4, 0.5);
+        assert_eq!(moved.get(1, 1), Some(0.0));
+        assert_eq!(moved.get(0, 0), Some(0.0));
+        assert_eq!(moved.get(1, 0), Some(1.0));
+        assert_eq!(board.frame(4, 4, 2.0), buffer);
+    }
+
+    #[test]
+    fn test_gradients() {
+        let horizontal = Pattern::LinearGradient { angle: 0.0 }.generate(5, 2);
+        assert_eq!(horizontal.get(0, 1), Some(0.0));
+        assert_eq!(horizontal.get(2, 0), Some(0.5));
+        assert_eq!(horizontal.get(4, 0), Some(1.0));
+
+        let down = Pattern::LinearGradient {
+            angle: std::f32::consts::FRAC_PI_2,
+        }
+        .generate(3, 3);
+        assert!((down.get(1, 2).unwrap() - 1.0).abs() < 1e-6);
+        assert!(down.get(1, 0).unwrap().abs() < 1e-6);
+
+        let radial = Pattern::RadialGradient.generate(5, 5);
+        assert_eq!(radial.get(2, 2), Some(1.0));
+        assert_eq!(radial.get(0, 0), Some(0.0));
+        assert!(in_range(&Pattern::RadialGradient.frame(5, 5, 0.3)));
+        assert!(in_range(
+            &Pattern::LinearGradient { angle: 1.0 }.frame(5, 5, 0.7)
+        ));
+    }
+}
@@ -34,6 +34,8 @@
 // Import error types from error module
 use crate::error::DotmaxError;
 
+use std::ops::Range;
+
 // Tracing for structured logging (Story 2.7)
 use tracing::{debug, error, info, instrument};
 
@@ -41,6 +43,9 @@ use tracing::{debug, error, info, instrument};
 const MAX_GRID_WIDTH: usize = 10_000;
 const MAX_GRID_HEIGHT: usize = 10_000;
 
+// Binary frame encoding and serde support
+mod codec;
+
 // ============================================================================
 // Color struct - Extracted from crabmusic/src/visualization/mod.rs
 // ============================================================================
@@ -49,6 +54,7 @@ const MAX_GRID_HEIGHT: usize = 10_000;
 ///
 /// Extracted from crabmusic. Story 2.6 will implement full color rendering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     /// Red component (0-255)
     pub r: u8,
@@ -118,6 +124,59 @@ pub enum BrailleDot {
     Dot8 = 0b1000_0000,
 }
 
+/// Bit for the dot at a dot coordinate within its cell, per the braille
+/// layout documented on [`BrailleDot`].
+pub(crate) const fn dot_mask(dot_x: usize, dot_y: usize) -> u8 {
+    match (dot_x % 2, dot_y % 4) {
+        (0, 3) => BrailleDot::Dot7 as u8,
+        (1, 3) => BrailleDot::Dot8 as u8,
+        (0, row) => 1 << row,
+        (_, row) => 1 << (row + 3),
+    }
+}
+
+/// How [`BrailleGrid::blit`] combines a sprite's dots with the grid's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlitMode {
+    /// The sprite's whole area replaces what is underneath, empty dots
+    /// included.
+    Overwrite,
+    /// Sprite dots are added; empty sprite dots leave the grid alone.
+    Or,
+    /// Sprite dots flip the grid's. Blitting the same sprite at the same
+    /// place twice restores the grid, so a moving sprite can be erased by
+    /// drawing it again.
+    Xor,
+    /// Sprite dots clear the grid's, erasing the sprite's shape.
+    AndNot,
+}
+
+/// What [`BrailleGrid::blit`] does to the colors of cells the sprite draws
+/// into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlitColor {
+    /// Leave the grid's colors alone.
+    Keep,
+    /// Take the sprite's cell color, where it has one.
+    Source,
+    /// Give every cell the sprite draws into this color.
+    Tint(Color),
+}
+
+/// A rectangle of cells that changed, from
+/// [`BrailleGrid::take_dirty_rects`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirtyRect {
+    /// Left column, in cells
+    pub x: usize,
+    /// Top row, in cells
+    pub y: usize,
+    /// Width in cells
+    pub width: usize,
+    /// Height in cells
+    pub height: usize,
+}
+
 // ============================================================================
 // dots_to_char - Extracted from crabmusic/src/visualization/braille.rs:52-56
 // ============================================================================
@@ -213,6 +272,9 @@ pub struct BrailleGrid {
     ///
     /// `None` = use braille dots (default), `Some(char)` = render this character
     characters: Vec<Option<char>>,
+    /// Columns changed in each row since the dirty rects were last taken,
+    /// or `None` while dirty tracking is off
+    dirty: Option<Vec<Range<usize>>>,
 }
 
 impl BrailleGrid {
@@ -271,6 +333,7 @@ impl BrailleGrid {
             patterns: vec![0; size],
             colors: vec![None; size],
             characters: vec![None; size], // Story 4.4: character buffer for density rendering
+            dirty: None,
         })
     }
 
@@ -327,6 +390,13 @@ impl BrailleGrid {
             height = self.height,
             "Clearing all dots in grid"
         );
+        if self.dirty.is_some() {
+            for index in 0..self.patterns.len() {
+                if self.patterns[index] != 0 || self.colors[index].is_some() {
+                    self.touch(index);
+                }
+            }
+        }
         self.patterns.fill(0);
         self.colors.fill(None);
     }
@@ -393,7 +463,10 @@ impl BrailleGrid {
         };
 
         // Set the dot (PRESERVED from crabmusic, line 171)
-        self.patterns[cell_index] |= dot_bit;
+        if self.patterns[cell_index] & dot_bit == 0 {
+            self.patterns[cell_index] |= dot_bit;
+            self.touch(cell_index);
+        }
         Ok(())
     }
 
@@ -438,6 +511,56 @@ impl BrailleGrid {
         Ok((pattern & dot_bit) != 0)
     }
 
+    /// Check whether the dot at a dot coordinate is set
+    ///
+    /// Dot-coordinate counterpart to [`get_dot`](Self::get_dot), for code that
+    /// works on the dot field as a bitmap. Out-of-bounds coordinates read as
+    /// unset rather than failing, so neighborhood scans need no edge cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::BrailleGrid;
+    ///
+    /// let mut grid = BrailleGrid::new(4, 2).unwrap();
+    /// grid.set_dot(3, 5).unwrap();
+    /// assert!(grid.is_dot_set(3, 5));
+    /// assert!(!grid.is_dot_set(2, 5));
+    /// assert!(!grid.is_dot_set(100, 100));
+    /// ```
+    #[must_use]
+    pub fn is_dot_set(&self, dot_x: usize, dot_y: usize) -> bool {
+        if dot_x >= self.dot_width() || dot_y >= self.dot_height() {
+            return false;
+        }
+        let cell_index = (dot_y / 4) * self.width + dot_x / 2;
+        self.patterns[cell_index] & dot_mask(dot_x, dot_y) != 0
+    }
+
+    /// All dots as a row-major `dot_width() × dot_height()` bitmap.
+    pub(crate) fn dot_field(&self) -> Vec<bool> {
+        let (width, height) = (self.dot_width(), self.dot_height());
+        (0..width * height)
+            .map(|i| self.is_dot_set(i % width, i / width))
+            .collect()
+    }
+
+    /// Replace every dot from a bitmap laid out like [`Self::dot_field`].
+    ///
+    /// Colors and text characters are left alone. A short slice leaves the
+    /// remaining dots unset.
+    pub(crate) fn set_dot_field(&mut self, dots: &[bool]) {
+        let width = self.dot_width();
+        let mut patterns = vec![0; self.patterns.len()];
+        for (i, _) in dots.iter().enumerate().filter(|(_, &on)| on) {
+            let (dot_x, dot_y) = (i % width, i / width);
+            if dot_y < self.dot_height() {
+                patterns[(dot_y / 4) * self.width + dot_x / 2] |= dot_mask(dot_x, dot_y);
+            }
+        }
+        self.replace_patterns(&patterns);
+    }
+
     /// Clear a rectangular region of the grid
     ///
     /// **NEW** - Not in crabmusic. Added to satisfy AC #6 requirement.
@@ -478,6 +601,9 @@ impl BrailleGrid {
         for row_idx in y..end_y {
             for col_idx in x..end_x {
                 let cell_index = row_idx * self.width + col_idx;
+                if self.patterns[cell_index] != 0 || self.colors[cell_index].is_some() {
+                    self.touch(cell_index);
+                }
                 self.patterns[cell_index] = 0;
                 self.colors[cell_index] = None;
             }
@@ -595,7 +721,19 @@ impl BrailleGrid {
     /// ```
     pub fn set_raw_patterns(&mut self, data: &[u8]) {
         let copy_len = data.len().min(self.patterns.len());
-        self.patterns[..copy_len].copy_from_slice(&data[..copy_len]);
+        self.replace_patterns(&data[..copy_len]);
+    }
+
+    /// Overwrite the leading patterns with `data`, noting which changed.
+    fn replace_patterns(&mut self, data: &[u8]) {
+        if self.dirty.is_some() {
+            for (index, &pattern) in data.iter().enumerate() {
+                if self.patterns[index] != pattern {
+                    self.touch(index);
+                }
+            }
+        }
+        self.patterns[..data.len()].copy_from_slice(data);
     }
 
     // ========================================================================
@@ -790,6 +928,9 @@ impl BrailleGrid {
         self.height = new_height;
         self.patterns = new_patterns;
         self.colors = new_colors;
+        if self.dirty.is_some() {
+            self.mark_all_dirty();
+        }
 
         Ok(())
     }
@@ -885,7 +1026,10 @@ impl BrailleGrid {
 
         // Set color
         let index = y * self.width + x;
-        self.colors[index] = Some(color);
+        if self.colors[index] != Some(color) {
+            self.colors[index] = Some(color);
+            self.touch(index);
+        }
         Ok(())
     }
 
@@ -916,6 +1060,13 @@ impl BrailleGrid {
     /// assert_eq!(grid.get_color(7, 7), None);
     /// ```
     pub fn clear_colors(&mut self) {
+        if self.dirty.is_some() {
+            for index in 0..self.colors.len() {
+                if self.colors[index].is_some() {
+                    self.touch(index);
+                }
+            }
+        }
         self.colors.fill(None);
     }
 
@@ -979,7 +1130,10 @@ impl BrailleGrid {
 
         // Set character
         let index = y * self.width + x;
-        self.characters[index] = Some(character);
+        if self.characters[index] != Some(character) {
+            self.characters[index] = Some(character);
+            self.touch(index);
+        }
         Ok(())
     }
 
@@ -1009,9 +1163,364 @@ impl BrailleGrid {
     /// assert_eq!(grid.get_char(5, 5), '⠀'); // Empty braille pattern
     /// ```
     pub fn clear_characters(&mut self) {
+        if self.dirty.is_some() {
+            for index in 0..self.characters.len() {
+                if self.characters[index].is_some() {
+                    self.touch(index);
+                }
+            }
+        }
         self.characters.fill(None);
     }
 
+    /// Copy one cell (dots, color, and text character) from another grid.
+    ///
+    /// Both grids must be the same size; callers check this once up front
+    /// instead of per cell. Out-of-bounds coordinates are ignored.
+    pub(crate) fn copy_cell_from(&mut self, src: &Self, x: usize, y: usize) {
+        if x >= self.width || y >= self.height || self.dimensions() != src.dimensions() {
+            return;
+        }
+        let index = y * self.width + x;
+        self.patterns[index] = src.patterns[index];
+        self.colors[index] = src.colors[index];
+        self.characters[index] = src.characters[index];
+        self.touch(index);
+    }
+
+    /// Copy every cell of `src` into this grid with its top-left corner at
+    /// cell `(x, y)`. Cells falling outside this grid are clipped.
+    pub(crate) fn paste(&mut self, src: &Self, x: usize, y: usize) {
+        for src_y in 0..src.height.min(self.height.saturating_sub(y)) {
+            for src_x in 0..src.width.min(self.width.saturating_sub(x)) {
+                let from = src_y * src.width + src_x;
+                let to = (y + src_y) * self.width + x + src_x;
+                self.patterns[to] = src.patterns[from];
+                self.colors[to] = src.colors[from];
+                self.characters[to] = src.characters[from];
+                self.touch(to);
+            }
+        }
+    }
+
+    // ========================================================================
+    // Dot-level Boolean Operations
+    // ========================================================================
+
+    /// Set every dot that is set in `other`.
+    ///
+    /// Together with [`intersect_with`](Self::intersect_with) and
+    /// [`subtract`](Self::subtract), this builds shapes out of others: draw
+    /// each operand on its own grid (a *mask*), then combine. Grids are
+    /// aligned at their top-left cell; the parts of `other` that fall outside
+    /// this grid are ignored. Cells that gain dots from `other` take its
+    /// color, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BrailleGrid, primitives::draw_line};
+    ///
+    /// let mut grid = BrailleGrid::new(10, 5)?;
+    /// draw_line(&mut grid, 0, 0, 19, 0)?;
+    /// let mut mask = BrailleGrid::new(10, 5)?;
+    /// draw_line(&mut mask, 0, 0, 0, 19)?;
+    ///
+    /// grid.union(&mask);
+    /// assert!(grid.is_dot_set(19, 0) && grid.is_dot_set(0, 19));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn union(&mut self, other: &Self) {
+        self.combine(other, |dots, mask| dots | mask);
+    }
+
+    /// Keep only the dots that are also set in `other`.
+    ///
+    /// Dots outside `other`'s area are cleared, since `other` has none
+    /// there. Colors are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BrailleGrid, primitives::{draw_circle_filled, shapes::draw_rectangle_filled}};
+    ///
+    /// // Top half of a disc: the disc clipped to a rectangle
+    /// let mut grid = BrailleGrid::new(20, 10)?;
+    /// draw_circle_filled(&mut grid, 20, 20, 15)?;
+    /// let mut mask = BrailleGrid::new(20, 10)?;
+    /// draw_rectangle_filled(&mut mask, 0, 0, 40, 20)?;
+    ///
+    /// grid.intersect_with(&mask);
+    /// assert!(grid.is_dot_set(20, 10));
+    /// assert!(!grid.is_dot_set(20, 30));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn intersect_with(&mut self, other: &Self) {
+        let (width, height) = (self.width, self.height);
+        for y in 0..height {
+            for x in 0..width {
+                let mask = if x < other.width && y < other.height {
+                    other.patterns[y * other.width + x]
+                } else {
+                    0
+                };
+                let index = y * width + x;
+                if self.patterns[index] & !mask != 0 {
+                    self.patterns[index] &= mask;
+                    self.touch(index);
+                }
+            }
+        }
+    }
+
+    /// Clear every dot that is set in `other`.
+    ///
+    /// Colors are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BrailleGrid, primitives::draw_circle_filled};
+    ///
+    /// // A ring: a disc minus a smaller disc
+    /// let mut grid = BrailleGrid::new(20, 10)?;
+    /// draw_circle_filled(&mut grid, 20, 20, 15)?;
+    /// let mut hole = BrailleGrid::new(20, 10)?;
+    /// draw_circle_filled(&mut hole, 20, 20, 9)?;
+    ///
+    /// grid.subtract(&hole);
+    /// assert!(grid.is_dot_set(32, 20));
+    /// assert!(!grid.is_dot_set(20, 20));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn subtract(&mut self, other: &Self) {
+        self.combine(other, |dots, mask| dots & !mask);
+    }
+
+    /// Apply `op` to each cell pattern of the area both grids share, then
+    /// carry `other`'s colors onto cells that gained dots.
+    fn combine(&mut self, other: &Self, op: impl Fn(u8, u8) -> u8) {
+        for y in 0..self.height.min(other.height) {
+            for x in 0..self.width.min(other.width) {
+                let (to, from) = (y * self.width + x, y * other.width + x);
+                let (before, color_before) = (self.patterns[to], self.colors[to]);
+                self.patterns[to] = op(before, other.patterns[from]);
+                if self.patterns[to] & !before != 0 {
+                    if let Some(color) = other.colors[from] {
+                        self.colors[to] = Some(color);
+                    }
+                }
+                if self.patterns[to] != before || self.colors[to] != color_before {
+                    self.touch(to);
+                }
+            }
+        }
+    }
+
+    // ========================================================================
+    // Sprite Blitting
+    // ========================================================================
+
+    /// Draw `sprite` with its top-left dot at dot `(dest_x, dest_y)`.
+    ///
+    /// `mode` decides how the sprite's dots combine with the grid's, and
+    /// `color` what happens to the colors of cells that a set sprite dot
+    /// lands in ([`BlitMode::AndNot`] erases, so it never changes colors).
+    /// The position is in dots, so sprites can move one dot at a time; it
+    /// may be negative or run past the edges, and the sprite is clipped.
+    /// Text characters are not copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BlitColor, BlitMode, BrailleGrid, Color};
+    ///
+    /// // A 4×4-dot sprite, pre-rendered once
+    /// let mut ball = BrailleGrid::new(2, 1)?;
+    /// for (x, y) in [(1, 0), (2, 0), (0, 1), (3, 1), (0, 2), (3, 2), (1, 3), (2, 3)] {
+    ///     ball.set_dot(x, y)?;
+    /// }
+    ///
+    /// let mut frame = BrailleGrid::new(20, 10)?;
+    /// let red = BlitColor::Tint(Color::rgb(255, 0, 0));
+    /// frame.blit(&ball, 7, 5, BlitMode::Xor, red);
+    /// assert!(frame.is_dot_set(8, 5));
+    /// assert_eq!(frame.get_color(4, 1), Some(Color::rgb(255, 0, 0)));
+    ///
+    /// // Drawing it again in XOR mode erases it
+    /// frame.blit(&ball, 7, 5, BlitMode::Xor, BlitColor::Keep);
+    /// assert!(!frame.is_dot_set(8, 5));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    pub fn blit(
+        &mut self,
+        sprite: &Self,
+        dest_x: i32,
+        dest_y: i32,
+        mode: BlitMode,
+        color: BlitColor,
+    ) {
+        // The part of the sprite that lands on the grid, in sprite dots
+        let visible = |dest: i32, sprite_len: usize, grid_len: usize| {
+            let start = (-i64::from(dest)).max(0) as usize;
+            let end = (grid_len as i64 - i64::from(dest)).clamp(0, sprite_len as i64) as usize;
+            start..end.max(start)
+        };
+        let rows = visible(dest_y, sprite.dot_height(), self.dot_height());
+        let columns = visible(dest_x, sprite.dot_width(), self.dot_width());
+
+        for sprite_y in rows {
+            let y = (i64::from(dest_y) + sprite_y as i64) as usize;
+            for sprite_x in columns.clone() {
+                let x = (i64::from(dest_x) + sprite_x as i64) as usize;
+                let set = sprite.is_dot_set(sprite_x, sprite_y);
+                let index = (y / 4) * self.width + x / 2;
+                let mask = dot_mask(x, y);
+                let (before, color_before) = (self.patterns[index], self.colors[index]);
+                let cell = &mut self.patterns[index];
+                match mode {
+                    BlitMode::Overwrite if !set => *cell &= !mask,
+                    BlitMode::Overwrite | BlitMode::Or if set => *cell |= mask,
+                    BlitMode::Xor if set => *cell ^= mask,
+                    BlitMode::AndNot if set => *cell &= !mask,
+                    _ => {}
+                }
+
+                if set && mode != BlitMode::AndNot {
+                    let new_color = match color {
+                        BlitColor::Keep => None,
+                        BlitColor::Source => {
+                            sprite.colors[(sprite_y / 4) * sprite.width + sprite_x / 2]
+                        }
+                        BlitColor::Tint(tint) => Some(tint),
+                    };
+                    if new_color.is_some() {
+                        self.colors[index] = new_color;
+                    }
+                }
+                if self.patterns[index] != before || self.colors[index] != color_before {
+                    self.touch(index);
+                }
+            }
+        }
+    }
+
+    // ========================================================================
+    // Dirty Tracking
+    // ========================================================================
+
+    /// Turn tracking of changed cells on or off. Off by default.
+    ///
+    /// While on, every change to a cell's dots, color, or text character is
+    /// noted, so a renderer can look at only the cells that changed instead
+    /// of scanning the whole grid; see [`take_dirty_rects`](Self::take_dirty_rects).
+    /// Turning it on (again) marks every cell dirty, since nothing has seen
+    /// them yet.
+    pub fn set_dirty_tracking(&mut self, enabled: bool) {
+        self.dirty = enabled.then(|| vec![0..self.width; self.height]);
+    }
+
+    /// Whether changed cells are being tracked.
+    #[must_use]
+    pub const fn is_tracking_dirty(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Mark every cell dirty, e.g. after the screen was cleared behind the
+    /// grid's back. Does nothing while tracking is off.
+    pub fn mark_all_dirty(&mut self) {
+        if self.dirty.is_some() {
+            self.dirty = Some(vec![0..self.width; self.height]);
+        }
+    }
+
+    /// The cells changed since the dirty rects were last taken, or `None`
+    /// while tracking is off, without resetting them.
+    ///
+    /// Each row's changes are covered by one span of columns, and rows with
+    /// the same span next to each other share a rect. Every changed cell is
+    /// in some rect, but a rect may hold unchanged cells between changed
+    /// ones. Rects are ordered top to bottom and don't overlap.
+    #[must_use]
+    pub fn dirty_rects(&self) -> Option<Vec<DirtyRect>> {
+        let rows = self.dirty.as_ref()?;
+        let mut rects: Vec<DirtyRect> = Vec::new();
+        for (y, span) in rows.iter().enumerate() {
+            if span.is_empty() {
+                continue;
+            }
+            match rects.last_mut() {
+                Some(rect)
+                    if rect.y + rect.height == y
+                        && rect.x == span.start
+                        && rect.x + rect.width == span.end =>
+                {
+                    rect.height += 1;
+                }
+                _ => rects.push(DirtyRect {
+                    x: span.start,
+                    y,
+                    width: span.len(),
+                    height: 1,
+                }),
+            }
+        }
+        Some(rects)
+    }
+
+    /// The cells changed since the last call, as in
+    /// [`dirty_rects`](Self::dirty_rects), then marks every cell clean.
+    /// `None` while tracking is off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{BrailleGrid, Color, DirtyRect};
+    ///
+    /// let mut grid = BrailleGrid::new(300, 100)?;
+    /// grid.set_dirty_tracking(true);
+    /// grid.take_dirty_rects(); // everything, the first time
+    ///
+    /// grid.set_dot(20, 8)?; // cell (10, 2)
+    /// grid.set_cell_color(12, 3, Color::rgb(255, 0, 0))?;
+    /// let rects = grid.take_dirty_rects().unwrap();
+    /// assert_eq!(rects, [
+    ///     DirtyRect { x: 10, y: 2, width: 1, height: 1 },
+    ///     DirtyRect { x: 12, y: 3, width: 1, height: 1 },
+    /// ]);
+    ///
+    /// // Setting a dot that is already set changes nothing
+    /// grid.set_dot(20, 8)?;
+    /// assert_eq!(grid.take_dirty_rects(), Some(vec![]));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn take_dirty_rects(&mut self) -> Option<Vec<DirtyRect>> {
+        let rects = self.dirty_rects()?;
+        if let Some(rows) = self.dirty.as_mut() {
+            rows.fill(0..0);
+        }
+        Some(rects)
+    }
+
+    /// Note that cell `index` changed, while tracking.
+    fn touch(&mut self, index: usize) {
+        if let Some(rows) = self.dirty.as_mut() {
+            let (x, y) = (index % self.width, index / self.width);
+            let span = &mut rows[y];
+            if span.start >= span.end {
+                *span = x..x + 1;
+            } else {
+                span.start = span.start.min(x);
+                span.end = span.end.max(x + 1);
+            }
+        }
+    }
+
     // ========================================================================
     // Story 5.5: Apply Color Scheme to Intensity Buffer
     // ========================================================================
@@ -1114,7 +1623,11 @@ impl BrailleGrid {
                 intensity.clamp(0.0, 1.0)
             };
 
-            self.colors[index] = Some(scheme.sample(normalized));
+            let color = Some(scheme.sample(normalized));
+            if self.colors[index] != color {
+                self.colors[index] = color;
+                self.touch(index);
+            }
         }
 
         Ok(())
@@ -2637,4 +3150,181 @@ mod tests {
         // 0.5 gray should be around 127-128
         assert!(color.r >= 127 && color.r <= 128);
     }
+
+    // ========================================================================
+    // Dot-level Boolean Operation Tests
+    // ========================================================================
+
+    fn grid_with_dots(width: usize, height: usize, dots: &[(usize, usize)]) -> BrailleGrid {
+        let mut grid = BrailleGrid::new(width, height).unwrap();
+        for &(x, y) in dots {
+            grid.set_dot(x, y).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn test_boolean_ops_same_size() {
+        let a = grid_with_dots(2, 2, &[(0, 0), (1, 1), (3, 7)]);
+        let b = grid_with_dots(2, 2, &[(1, 1), (2, 2), (3, 7)]);
+
+        let mut union = a.clone();
+        union.union(&b);
+        let mut intersection = a.clone();
+        intersection.intersect_with(&b);
+        let mut difference = a;
+        difference.subtract(&b);
+
+        for (x, y) in [(0, 0), (1, 1), (2, 2), (3, 7)] {
+            assert!(union.is_dot_set(x, y));
+        }
+        assert!(intersection.is_dot_set(1, 1) && intersection.is_dot_set(3, 7));
+        assert!(!intersection.is_dot_set(0, 0) && !intersection.is_dot_set(2, 2));
+        assert!(difference.is_dot_set(0, 0));
+        assert!(!difference.is_dot_set(1, 1) && !difference.is_dot_set(3, 7));
+    }
+
+    #[test]
+    fn test_boolean_ops_different_sizes_and_color() {
+        let small = grid_with_dots(1, 1, &[(0, 0)]);
+        let mut big = grid_with_dots(3, 2, &[(0, 0), (5, 7)]);
+        big.intersect_with(&small);
+        assert!(big.is_dot_set(0, 0));
+        assert!(!big.is_dot_set(5, 7)); // outside the smaller grid
+
+        let mut small = grid_with_dots(1, 1, &[]);
+        let mut mask = grid_with_dots(3, 2, &[(1, 1), (5, 7)]);
+        mask.enable_color_support();
+        let red = Color::rgb(255, 0, 0);
+        mask.set_cell_color(0, 0, red).unwrap();
+        small.union(&mask);
+        assert!(small.is_dot_set(1, 1));
+        assert_eq!(small.get_color(0, 0), Some(red));
+
+        // No new dots, no color change
+        let mut lit = grid_with_dots(1, 1, &[(1, 1)]);
+        lit.union(&mask);
+        lit.subtract(&mask);
+        assert!(!lit.is_dot_set(1, 1));
+        assert_eq!(lit.get_color(0, 0), None);
+    }
+
+    // ========================================================================
+    // Sprite Blitting Tests
+    // ========================================================================
+
+    #[test]
+    fn test_blit_modes() {
+        // Sprite dots at (0, 0) and (1, 1); the grid already has (1, 1) and (2, 2)
+        let sprite = grid_with_dots(1, 1, &[(0, 0), (1, 1)]);
+        let base = grid_with_dots(2, 1, &[(1, 1), (2, 2)]);
+        let blit = |mode| {
+            let mut grid = base.clone();
+            grid.blit(&sprite, 0, 0, mode, BlitColor::Keep);
+            [(0, 0), (1, 1), (2, 2), (1, 0)].map(|(x, y)| grid.is_dot_set(x, y))
+        };
+        assert_eq!(blit(BlitMode::Or), [true, true, true, false]);
+        assert_eq!(blit(BlitMode::Xor), [true, false, true, false]);
+        assert_eq!(blit(BlitMode::AndNot), [false, false, true, false]);
+
+        // Overwrite clears under the sprite's empty dots, not beyond it
+        let mut grid = grid_with_dots(2, 1, &[(1, 0), (2, 0)]);
+        grid.blit(&sprite, 0, 0, BlitMode::Overwrite, BlitColor::Keep);
+        assert!(grid.is_dot_set(0, 0) && grid.is_dot_set(1, 1));
+        assert!(!grid.is_dot_set(1, 0) && grid.is_dot_set(2, 0));
+    }
+
+    #[test]
+    fn test_blit_clips_and_colors() {
+        let mut sprite = grid_with_dots(2, 1, &[(0, 0), (3, 3)]);
+        let blue = Color::rgb(0, 0, 255);
+        sprite.set_cell_color(0, 0, blue).unwrap();
+
+        // Unaligned and hanging off the top-left: only (3, 3) lands, at (0, 1)
+        let mut grid = BrailleGrid::new(2, 2).unwrap();
+        grid.blit(&sprite, -3, -2, BlitMode::Or, BlitColor::Source);
+        assert!(grid.is_dot_set(0, 1));
+        let lit = grid.get_raw_patterns().iter().filter(|&&p| p != 0).count();
+        assert_eq!(lit, 1);
+        // Dot (3, 3) is in the sprite's uncolored second cell
+        assert_eq!(grid.get_color(0, 0), None);
+
+        // Off the bottom-right edge, and entirely outside
+        grid.blit(&sprite, 3, 7, BlitMode::Or, BlitColor::Source);
+        assert!(grid.is_dot_set(3, 7));
+        assert_eq!(grid.get_color(1, 1), Some(blue));
+        grid.blit(&sprite, 40, -40, BlitMode::Or, BlitColor::Source);
+
+        let green = Color::rgb(0, 255, 0);
+        grid.blit(&sprite, 0, 4, BlitMode::AndNot, BlitColor::Tint(green));
+        assert_eq!(grid.get_color(0, 1), None);
+        grid.blit(&sprite, 0, 4, BlitMode::Or, BlitColor::Tint(green));
+        assert_eq!(grid.get_color(0, 1), Some(green));
+        assert_eq!(grid.get_color(1, 1), Some(green));
+    }
+
+    #[test]
+    fn test_copy_cell_from_needs_same_dimensions() {
+        let mut src = BrailleGrid::new(2, 4).unwrap();
+        src.set_char(1, 1, '#').unwrap();
+
+        let mut short = BrailleGrid::new(2, 2).unwrap();
+        short.copy_cell_from(&src, 1, 1);
+        assert_eq!(short.get_char(1, 1), '⠀');
+
+        let mut same = BrailleGrid::new(2, 4).unwrap();
+        same.copy_cell_from(&src, 1, 1);
+        assert_eq!(same.get_char(1, 1), '#');
+    }
+
+    #[test]
+    fn test_dirty_rects_track_changes() {
+        let mut grid = BrailleGrid::new(20, 10).unwrap();
+        assert_eq!(grid.take_dirty_rects(), None);
+        grid.set_dirty_tracking(true);
+        let all = DirtyRect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 10,
+        };
+        assert_eq!(grid.take_dirty_rects(), Some(vec![all]));
+
+        // A box outline: rows with the same span merge
+        for y in 4..16 {
+            grid.set_dot(4, y).unwrap();
+            grid.set_dot(11, y).unwrap();
+        }
+        let rect = |x, y, width, height| DirtyRect {
+            x,
+            y,
+            width,
+            height,
+        };
+        assert_eq!(grid.dirty_rects(), Some(vec![rect(2, 1, 4, 3)]));
+        assert_eq!(grid.take_dirty_rects(), Some(vec![rect(2, 1, 4, 3)]));
+
+        // Clearing only touches cells that had something to clear
+        grid.set_char(15, 8, '#').unwrap();
+        grid.take_dirty_rects();
+        grid.clear();
+        grid.clear_characters();
+        assert_eq!(
+            grid.take_dirty_rects(),
+            Some(vec![rect(2, 1, 4, 3), rect(15, 8, 1, 1)])
+        );
+
+        let mut sprite = BrailleGrid::new(1, 1).unwrap();
+        sprite.set_dot(0, 0).unwrap();
+        grid.blit(&sprite, -1, 0, BlitMode::Xor, BlitColor::Keep);
+        assert_eq!(grid.take_dirty_rects(), Some(vec![]));
+        grid.blit(&sprite, 38, 36, BlitMode::Xor, BlitColor::Keep);
+        assert_eq!(grid.take_dirty_rects(), Some(vec![rect(19, 9, 1, 1)]));
+
+        grid.resize(5, 5).unwrap();
+        assert_eq!(grid.take_dirty_rects(), Some(vec![rect(0, 0, 5, 5)]));
+        grid.set_dirty_tracking(false);
+        grid.set_dot(0, 0).unwrap();
+        assert_eq!(grid.dirty_rects(), None);
+    }
 }
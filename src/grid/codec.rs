@@ -0,0 +1,378 @@
+//! Compact binary encoding of a [`BrailleGrid`], and its serde support.
+//!
+//! [`BrailleGrid::to_bytes`] packs a grid into a few bytes per cell for
+//! sending frames between processes, such as a render server and thin
+//! terminal clients; [`BrailleGrid::to_bytes_rle`] additionally
+//! run-length encodes them, which suits the long empty and single-color
+//! stretches of most frames. [`BrailleGrid::from_bytes`] reads either.
+
+use super::{BrailleGrid, Color};
+use crate::error::DotmaxError;
+
+const MAGIC: &[u8; 4] = b"DMXG";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 10;
+
+const FLAG_RLE: u8 = 1;
+const FLAG_COLORS: u8 = 1 << 1;
+const FLAG_CHARACTERS: u8 = 1 << 2;
+
+/// Marks a cell without a character.
+const NO_CHARACTER: u32 = u32::MAX;
+
+/// Longest run a control byte can describe.
+const MAX_RUN: usize = 129;
+/// Most literal bytes a control byte can cover.
+const MAX_LITERAL: usize = 128;
+
+impl BrailleGrid {
+    /// Encodes the grid's dots, colors, and characters in a compact binary
+    /// form read by [`from_bytes`](Self::from_bytes).
+    ///
+    /// # Layout
+    ///
+    /// All integers are little-endian.
+    ///
+    /// | Bytes | Contents |
+    /// |-------|----------|
+    /// | 4 | Magic `DMXG` |
+    /// | 1 | Version, currently 1 |
+    /// | 1 | Flags: bit 0 run-length encoded, bit 1 colors, bit 2 characters |
+    /// | 2 | Width in cells |
+    /// | 2 | Height in cells |
+    /// | rest | Body, run-length encoded if flagged |
+    ///
+    /// The body holds one dot pattern byte per cell, row by row. If flagged,
+    /// four bytes per cell of colors follow (1 and then RGB for a color, 0 0 0
+    /// 0 for none), then four bytes per cell of characters (the code point,
+    /// or `0xFFFFFFFF` for none). Grids without colors or characters leave
+    /// those sections out.
+    ///
+    /// Runs are encoded in the style of `PackBits`: a control byte `n` below
+    /// 128 is followed by `n + 1` literal bytes, and one of 128 or more by a
+    /// single byte repeated `n - 126` times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::BrailleGrid;
+    ///
+    /// let mut grid = BrailleGrid::new(80, 24)?;
+    /// grid.set_dot(10, 10)?;
+    ///
+    /// let packed = grid.to_bytes_rle();
+    /// assert!(packed.len() < 100);
+    /// let copy = BrailleGrid::from_bytes(&packed)?;
+    /// assert_eq!(copy.get_raw_patterns(), grid.get_raw_patterns());
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode(false)
+    }
+
+    /// Like [`to_bytes`](Self::to_bytes), run-length encoded. Usually much
+    /// smaller, since most frames are largely empty or one color.
+    #[must_use]
+    pub fn to_bytes_rle(&self) -> Vec<u8> {
+        self.encode(true)
+    }
+
+    /// Decodes a grid written by [`to_bytes`](Self::to_bytes) or
+    /// [`to_bytes_rle`](Self::to_bytes_rle).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidGridData`] if `bytes` isn't a grid in a
+    /// version this build reads, or is truncated or corrupt, and
+    /// [`DotmaxError::InvalidDimensions`] if its size is out of range.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DotmaxError> {
+        let corrupt = |what: &str| DotmaxError::InvalidGridData {
+            reason: what.to_string(),
+        };
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(corrupt("missing header"));
+        }
+        if bytes[4] != VERSION {
+            return Err(corrupt(&format!("unsupported version {}", bytes[4])));
+        }
+        let flags = bytes[5];
+        let width = usize::from(u16::from_le_bytes([bytes[6], bytes[7]]));
+        let height = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+        let mut grid = Self::new(width, height)?;
+
+        let cells = width * height;
+        let has_colors = flags & FLAG_COLORS != 0;
+        let has_characters = flags & FLAG_CHARACTERS != 0;
+        let expected = cells * (1 + 4 * usize::from(has_colors) + 4 * usize::from(has_characters));
+        let body = if flags & FLAG_RLE == 0 {
+            bytes[HEADER_LEN..].to_vec()
+        } else {
+            unpack(&bytes[HEADER_LEN..], expected).ok_or_else(|| corrupt("bad run"))?
+        };
+        if body.len() != expected {
+            return Err(corrupt(&format!(
+                "expected {expected} body bytes, got {}",
+                body.len()
+            )));
+        }
+
+        let (patterns, rest) = body.split_at(cells);
+        grid.patterns.copy_from_slice(patterns);
+        let (colors, characters) = rest.split_at(if has_colors { cells * 4 } else { 0 });
+        for (cell, color) in grid.colors.iter_mut().zip(colors.chunks_exact(4)) {
+            *cell = match color {
+                [0, ..] => None,
+                [1, r, g, b] => Some(Color::rgb(*r, *g, *b)),
+                _ => return Err(corrupt("bad color tag")),
+            };
+        }
+        for (cell, code) in grid.characters.iter_mut().zip(characters.chunks_exact(4)) {
+            let code = u32::from_le_bytes([code[0], code[1], code[2], code[3]]);
+            *cell = match code {
+                NO_CHARACTER => None,
+                code => Some(char::from_u32(code).ok_or_else(|| corrupt("bad character"))?),
+            };
+        }
+        Ok(grid)
+    }
+
+    fn encode(&self, rle: bool) -> Vec<u8> {
+        let has_colors = self.colors.iter().any(Option::is_some);
+        let has_characters = self.characters.iter().any(Option::is_some);
+
+        let mut body = self.patterns.clone();
+        if has_colors {
+            for color in &self.colors {
+                body.extend_from_slice(
+                    &color.map_or([0; 4], |color| [1, color.r, color.g, color.b]),
+                );
+            }
+        }
+        if has_characters {
+            for &ch in &self.characters {
+                body.extend_from_slice(&ch.map_or(NO_CHARACTER, u32::from).to_le_bytes());
+            }
+        }
+
+        let flag = |set: bool, flag: u8| if set { flag } else { 0 };
+        let flags = flag(rle, FLAG_RLE)
+            | flag(has_colors, FLAG_COLORS)
+            | flag(has_characters, FLAG_CHARACTERS);
+        let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&[VERSION, flags]);
+        // Grids are at most 10,000 cells on a side
+        #[allow(clippy::cast_possible_truncation)]
+        for side in [self.width, self.height] {
+            bytes.extend_from_slice(&(side as u16).to_le_bytes());
+        }
+        if rle {
+            pack(&body, &mut bytes);
+        } else {
+            bytes.extend_from_slice(&body);
+        }
+        bytes
+    }
+}
+
+/// Appends `data` to `out` run-length encoded.
+#[allow(clippy::cast_possible_truncation)] // Run and literal lengths fit a control byte
+fn pack(data: &[u8], out: &mut Vec<u8>) {
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i < data.len() {
+        let run = data[i..]
+            .iter()
+            .take(MAX_RUN)
+            .take_while(|&&byte| byte == data[i])
+            .count();
+        // Runs of two cost as much either way, so only longer ones are worth
+        // breaking a literal for
+        if run < 3 {
+            i += run;
+            continue;
+        }
+        flush_literal(&data[literal_start..i], out);
+        out.extend_from_slice(&[(run + 126) as u8, data[i]]);
+        i += run;
+        literal_start = i;
+    }
+    flush_literal(&data[literal_start..], out);
+}
+
+/// Appends `literal` as one or more literal chunks.
+#[allow(clippy::cast_possible_truncation)]
+fn flush_literal(literal: &[u8], out: &mut Vec<u8>) {
+    for chunk in literal.chunks(MAX_LITERAL) {
+        out.push((chunk.len() - 1) as u8);
+        out.extend_from_slice(chunk);
+    }
+}
+
+/// Decodes run-length encoded `data`, or `None` if it's malformed or would
+/// grow past `limit` bytes.
+fn unpack(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(limit);
+    let mut bytes = data.iter();
+    while let Some(&control) = bytes.next() {
+        if control < 128 {
+            let count = usize::from(control) + 1;
+            let literal = bytes.as_slice().get(..count)?;
+            out.extend_from_slice(literal);
+            bytes.nth(count - 1);
+        } else {
+            let &byte = bytes.next()?;
+            out.resize(out.len() + usize::from(control) - 126, byte);
+        }
+        if out.len() > limit {
+            return None;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::de::Error as _;
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::super::{BrailleGrid, Color};
+
+    /// Serializes as the size, dot patterns, colors, and characters; dirty
+    /// tracking isn't kept.
+    impl Serialize for BrailleGrid {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("BrailleGrid", 5)?;
+            state.serialize_field("width", &self.width)?;
+            state.serialize_field("height", &self.height)?;
+            state.serialize_field("patterns", &self.patterns)?;
+            state.serialize_field("colors", &self.colors)?;
+            state.serialize_field("characters", &self.characters)?;
+            state.end()
+        }
+    }
+
+    /// The fields of a serialized grid, checked before becoming one.
+    #[derive(Deserialize)]
+    struct GridFields {
+        width: usize,
+        height: usize,
+        patterns: Vec<u8>,
+        colors: Vec<Option<Color>>,
+        characters: Vec<Option<char>>,
+    }
+
+    impl<'de> Deserialize<'de> for BrailleGrid {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let fields = GridFields::deserialize(deserializer)?;
+            let mut grid = Self::new(fields.width, fields.height).map_err(D::Error::custom)?;
+            let cells = grid.patterns.len();
+            for (name, len) in [
+                ("patterns", fields.patterns.len()),
+                ("colors", fields.colors.len()),
+                ("characters", fields.characters.len()),
+            ] {
+                if len != cells {
+                    return Err(D::Error::custom(format!(
+                        "{name} has {len} entries for {cells} cells"
+                    )));
+                }
+            }
+            grid.patterns = fields.patterns;
+            grid.colors = fields.colors;
+            grid.characters = fields.characters;
+            Ok(grid)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> BrailleGrid {
+        let mut grid = BrailleGrid::new(30, 8).unwrap();
+        for x in 0..60 {
+            grid.set_dot(x, x % 32).unwrap();
+        }
+        grid.set_cell_color(3, 1, Color::rgb(255, 128, 0)).unwrap();
+        grid.set_char(10, 7, '@').unwrap();
+        grid
+    }
+
+    fn assert_same(a: &BrailleGrid, b: &BrailleGrid) {
+        assert_eq!(a.dimensions(), b.dimensions());
+        assert_eq!(a.get_raw_patterns(), b.get_raw_patterns());
+        for y in 0..a.height() {
+            for x in 0..a.width() {
+                assert_eq!(a.get_color(x, y), b.get_color(x, y));
+                assert_eq!(a.get_char(x, y), b.get_char(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let grid = sample();
+        let plain = grid.to_bytes();
+        assert_eq!(plain.len(), HEADER_LEN + 30 * 8 * 9);
+        let packed = grid.to_bytes_rle();
+        assert!(packed.len() < plain.len() / 4);
+        assert_same(&BrailleGrid::from_bytes(&plain).unwrap(), &grid);
+        assert_same(&BrailleGrid::from_bytes(&packed).unwrap(), &grid);
+
+        // A plain grid needs only its patterns
+        let empty = BrailleGrid::new(4, 2).unwrap();
+        assert_eq!(empty.to_bytes().len(), HEADER_LEN + 8);
+        assert_same(
+            &BrailleGrid::from_bytes(&empty.to_bytes_rle()).unwrap(),
+            &empty,
+        );
+    }
+
+    #[test]
+    fn test_rle_edge_cases() {
+        let data: Vec<u8> = (0..=255).chain([7; 300]).chain([1, 2, 2, 3]).collect();
+        let mut packed = Vec::new();
+        pack(&data, &mut packed);
+        assert_eq!(unpack(&packed, data.len()).unwrap(), data);
+        assert!(unpack(&packed, data.len() - 1).is_none());
+        assert!(unpack(&[5, 1, 2], 10).is_none());
+        assert!(unpack(&[200], 10).is_none());
+    }
+
+    #[test]
+    fn test_rejects_bad_bytes() {
+        let bytes = sample().to_bytes();
+        assert!(BrailleGrid::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(matches!(
+            BrailleGrid::from_bytes(b"PNG!"),
+            Err(DotmaxError::InvalidGridData { .. })
+        ));
+        let mut future = bytes.clone();
+        future[4] = 9;
+        let err = BrailleGrid::from_bytes(&future).unwrap_err();
+        assert!(err.to_string().contains("version 9"));
+        let mut zero_width = bytes;
+        zero_width[6..8].copy_from_slice(&[0, 0]);
+        assert!(matches!(
+            BrailleGrid::from_bytes(&zero_width),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+    }
+
+    #[cfg(all(feature = "serde", feature = "json"))]
+    #[test]
+    fn test_serde_round_trip() {
+        let grid = sample();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_same(&serde_json::from_str(&json).unwrap(), &grid);
+        let short = r#"{"width":2,"height":1,"patterns":[0],"colors":[null,null],"characters":[null,null]}"#;
+        let err = serde_json::from_str::<BrailleGrid>(short).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("patterns has 1 entries for 2 cells"));
+    }
+}
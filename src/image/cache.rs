@@ -0,0 +1,538 @@
+//! On-disk cache of rendered grids, keyed by input content and settings.
+//!
+//! Gallery and thumbnail tools render the same files over and over. A
+//! [`RenderCache`] stores each finished [`BrailleGrid`] under a [`CacheKey`]
+//! built from a hash of the input bytes plus everything that affects the
+//! output: [`RenderOptions`], target size, and anything else the caller
+//! tags in. A hit skips decoding entirely.
+//!
+//! Entries are small binary files in one directory. When the directory grows
+//! past its byte budget, the least recently used entries are removed. The
+//! hash is FNV-1a, which is stable across runs and builds; it is not
+//! cryptographic, so don't share a cache directory with untrusted writers.
+//!
+//! [`ImageRenderer::cache`](super::ImageRenderer::cache) and
+//! [`poster_frame_cached`](crate::media::poster_frame_cached) consult a cache
+//! for you.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use dotmax::image::{ImageRenderer, RenderCache};
+//!
+//! # fn main() -> Result<(), dotmax::DotmaxError> {
+//! let cache = RenderCache::open("/tmp/dotmax-cache", 64 * 1024 * 1024)?;
+//!
+//! for path in ["a.png", "b.jpg", "a.png"] {
+//!     // The second a.png is read from the cache without decoding
+//!     let grid = ImageRenderer::new()
+//!         .cache(cache.clone())
+//!         .load_from_path(Path::new(path))?
+//!         .resize(40, 12, true)?
+//!         .render()?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, warn};
+
+use super::RenderOptions;
+use crate::grid::{BrailleGrid, Color};
+use crate::DotmaxError;
+
+/// File name extension for cache entries.
+const EXTENSION: &str = "dmxg";
+
+/// Leading bytes of every entry: magic and format version.
+const HEADER: &[u8; 5] = b"DMXG\x01";
+
+/// FNV-1a, chosen because its output doesn't change between runs or Rust
+/// versions, unlike `DefaultHasher`.
+#[derive(Debug, Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Identifies one rendered output: the input's content plus the settings
+/// used to render it.
+///
+/// Start from the input bytes with [`new`](Self::new), then fold in every
+/// setting that changes the result. Two keys are equal only if the same
+/// data went in, in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(u64);
+
+impl CacheKey {
+    /// A key for `input`, typically the bytes of an image file.
+    #[must_use]
+    pub fn new(input: &[u8]) -> Self {
+        let mut hasher = Fnv1a::default();
+        (input.len() as u64).hash(&mut hasher);
+        hasher.write(input);
+        Self(hasher.finish())
+    }
+
+    /// A key for the contents of the file at `path`, equal to
+    /// [`new`](Self::new) on its bytes. The file is streamed, not loaded
+    /// whole, so this suits large videos.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be read.
+    pub fn from_file(path: &Path) -> Result<Self, DotmaxError> {
+        let mut file = fs::File::open(path)?;
+        let mut hasher = Fnv1a::default();
+        file.metadata()?.len().hash(&mut hasher);
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            match file.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => hasher.write(&buffer[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(Self(hasher.finish()))
+    }
+
+    /// Folds in render options.
+    #[must_use]
+    pub fn options(self, options: &RenderOptions) -> Self {
+        let mut hasher = self.hasher();
+        options.dithering.hash(&mut hasher);
+        options.threshold.hash(&mut hasher);
+        options.brightness.to_bits().hash(&mut hasher);
+        options.contrast.to_bits().hash(&mut hasher);
+        options.gamma.to_bits().hash(&mut hasher);
+        options.color_mode.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    /// Folds in an output size.
+    #[must_use]
+    pub fn size(self, width: usize, height: usize) -> Self {
+        let mut hasher = self.hasher();
+        (width, height).hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    /// Folds in any other setting that affects the output.
+    #[must_use]
+    pub fn tag(self, data: &[u8]) -> Self {
+        let mut hasher = self.hasher();
+        data.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    /// A hasher continuing from this key.
+    const fn hasher(self) -> Fnv1a {
+        Fnv1a(self.0)
+    }
+
+    fn file_name(self) -> String {
+        format!("{:016x}.{EXTENSION}", self.0)
+    }
+}
+
+/// A size-limited directory of rendered grids.
+///
+/// Cloning is cheap and clones share the directory. Several processes may
+/// use the same directory: entries are written to a temporary file and
+/// renamed into place, so readers never see half an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl RenderCache {
+    /// Opens the cache in `dir`, creating the directory if needed. Entries
+    /// are evicted once they add up to more than `max_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the directory can't be created.
+    pub fn open(dir: impl Into<PathBuf>, max_bytes: u64) -> Result<Self, DotmaxError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, max_bytes })
+    }
+
+    /// The cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The byte budget for all entries together.
+    #[must_use]
+    pub const fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    /// Looks up `key`. Unreadable or corrupt entries count as misses and
+    /// are removed.
+    #[must_use]
+    pub fn get(&self, key: &CacheKey) -> Option<BrailleGrid> {
+        let path = self.dir.join(key.file_name());
+        let bytes = fs::read(&path).ok()?;
+        let Some(grid) = decode_grid(&bytes) else {
+            debug!(?path, "Removing corrupt render cache entry");
+            let _ = fs::remove_file(&path);
+            return None;
+        };
+        // Rewrite to bump the modified time, so eviction sees the use
+        if let Err(e) = write_entry(&path, &bytes) {
+            debug!(?path, "Could not refresh render cache entry: {e}");
+        }
+        Some(grid)
+    }
+
+    /// Stores `grid` under `key`, then evicts old entries if the cache is
+    /// over budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the entry can't be written.
+    pub fn put(&self, key: &CacheKey, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        write_entry(&self.dir.join(key.file_name()), &encode_grid(grid))?;
+        self.evict()?;
+        Ok(())
+    }
+
+    /// Returns the cached grid for `key`, or renders, stores, and returns
+    /// it. A failure to store is logged, not returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever `render` returns.
+    pub fn get_or_render(
+        &self,
+        key: &CacheKey,
+        render: impl FnOnce() -> Result<BrailleGrid, DotmaxError>,
+    ) -> Result<BrailleGrid, DotmaxError> {
+        if let Some(grid) = self.get(key) {
+            return Ok(grid);
+        }
+        let grid = render()?;
+        if let Err(e) = self.put(key, &grid) {
+            warn!(dir = ?self.dir, "Could not store render cache entry: {e}");
+        }
+        Ok(grid)
+    }
+
+    /// Total size of all entries in bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the directory can't be read.
+    pub fn size_bytes(&self) -> Result<u64, DotmaxError> {
+        Ok(self.entries()?.iter().map(|entry| entry.1).sum())
+    }
+
+    /// Removes every entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the directory can't be read or
+    /// an entry can't be removed.
+    pub fn clear(&self) -> Result<(), DotmaxError> {
+        for (path, _, _) in self.entries()? {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Every entry with its size and modified time.
+    fn entries(&self) -> io::Result<Vec<(PathBuf, u64, std::time::SystemTime)>> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            // Another process may have evicted it meanwhile
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            let modified = metadata.modified()?;
+            entries.push((path, metadata.len(), modified));
+        }
+        Ok(entries)
+    }
+
+    /// Removes least recently used entries until the total fits the budget.
+    fn evict(&self) -> io::Result<()> {
+        let mut entries = self.entries()?;
+        let mut total: u64 = entries.iter().map(|entry| entry.1).sum();
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        // Oldest first; entries written in the same instant go by name
+        // rather than by directory order
+        entries.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.0.cmp(&b.0)));
+        for (path, len, _) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => total -= len,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => total -= len,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes an entry through a temporary file, so it appears all at once.
+fn write_entry(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp, bytes)?;
+    fs::rename(&temp, path)
+}
+
+/// Serializes a grid: header, width and height as `u32`, the dot patterns,
+/// then a presence bitmap and values for cell colors (RGB) and text
+/// characters (`u32`).
+fn encode_grid(grid: &BrailleGrid) -> Vec<u8> {
+    let (width, height) = (grid.width(), grid.height());
+    let cells = || (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)));
+    let patterns = grid.get_raw_patterns();
+
+    let mut out = HEADER.to_vec();
+    out.extend_from_slice(&u32::try_from(width).unwrap_or(u32::MAX).to_le_bytes());
+    out.extend_from_slice(&u32::try_from(height).unwrap_or(u32::MAX).to_le_bytes());
+    out.extend_from_slice(patterns);
+
+    let colors: Vec<Option<Color>> = cells().map(|(x, y)| grid.get_color(x, y)).collect();
+    push_bitmap(&mut out, colors.iter().map(Option::is_some));
+    for color in colors.iter().flatten() {
+        out.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+
+    // A text character is anything other than the cell's braille glyph
+    let chars: Vec<Option<char>> = cells()
+        .zip(patterns)
+        .map(|((x, y), &pattern)| {
+            let ch = grid.get_char(x, y);
+            (ch != braille_char(pattern)).then_some(ch)
+        })
+        .collect();
+    push_bitmap(&mut out, chars.iter().map(Option::is_some));
+    for ch in chars.iter().flatten() {
+        out.extend_from_slice(&u32::from(*ch).to_le_bytes());
+    }
+    out
+}
+
+/// Reverses [`encode_grid`], or `None` if `bytes` isn't a valid entry.
+fn decode_grid(bytes: &[u8]) -> Option<BrailleGrid> {
+    let mut reader = bytes.strip_prefix(HEADER.as_slice())?;
+    let width = read_u32(&mut reader)? as usize;
+    let height = read_u32(&mut reader)? as usize;
+    let count = width.checked_mul(height)?;
+
+    let mut grid = BrailleGrid::new(width, height).ok()?;
+    grid.set_raw_patterns(take(&mut reader, count)?);
+
+    let has_color = read_bitmap(&mut reader, count)?;
+    for (i, _) in has_color.iter().enumerate().filter(|(_, &set)| set) {
+        let rgb = take(&mut reader, 3)?;
+        grid.set_cell_color(i % width, i / width, Color::rgb(rgb[0], rgb[1], rgb[2]))
+            .ok()?;
+    }
+
+    let has_char = read_bitmap(&mut reader, count)?;
+    for (i, _) in has_char.iter().enumerate().filter(|(_, &set)| set) {
+        let ch = char::from_u32(read_u32(&mut reader)?)?;
+        grid.set_char(i % width, i / width, ch).ok()?;
+    }
+
+    reader.is_empty().then_some(grid)
+}
+
+fn braille_char(pattern: u8) -> char {
+    char::from_u32(0x2800 + u32::from(pattern)).unwrap_or('⠀')
+}
+
+fn push_bitmap(out: &mut Vec<u8>, bits: impl Iterator<Item = bool>) {
+    let bits: Vec<bool> = bits.collect();
+    for chunk in bits.chunks(8) {
+        let byte = chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (i, &bit)| byte | (u8::from(bit) << i));
+        out.push(byte);
+    }
+}
+
+fn read_bitmap(reader: &mut &[u8], count: usize) -> Option<Vec<bool>> {
+    let bytes = take(reader, (count + 7) / 8)?;
+    Some(
+        (0..count)
+            .map(|i| bytes[i / 8] & (1 << (i % 8)) != 0)
+            .collect(),
+    )
+}
+
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if reader.len() < len {
+        return None;
+    }
+    let (head, rest) = reader.split_at(len);
+    *reader = rest;
+    Some(head)
+}
+
+fn read_u32(reader: &mut &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(take(reader, 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dotmax-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let mut grid = BrailleGrid::new(5, 3).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        grid.set_dot(9, 11).unwrap();
+        grid.set_cell_color(2, 1, Color::rgb(10, 20, 30)).unwrap();
+        grid.set_char(3, 2, 'A').unwrap();
+
+        let decoded = decode_grid(&encode_grid(&grid)).unwrap();
+        assert_eq!(decoded.get_raw_patterns(), grid.get_raw_patterns());
+        assert_eq!(decoded.get_color(2, 1), Some(Color::rgb(10, 20, 30)));
+        assert_eq!(decoded.get_color(0, 0), None);
+        assert_eq!(decoded.get_char(3, 2), 'A');
+        assert_eq!(decoded.get_char(0, 0), grid.get_char(0, 0));
+
+        let mut truncated = encode_grid(&grid);
+        truncated.pop();
+        assert!(decode_grid(&truncated).is_none());
+    }
+
+    #[test]
+    fn test_keys_depend_on_every_input() {
+        let base = CacheKey::new(b"image bytes");
+        assert_eq!(base, CacheKey::new(b"image bytes"));
+        assert_ne!(base, CacheKey::new(b"image bytez"));
+
+        let options = RenderOptions::new();
+        let keyed = base.options(&options).size(40, 12);
+        assert_eq!(keyed, base.options(&options).size(40, 12));
+        assert_ne!(keyed, base.options(&options.gamma(0.9)).size(40, 12));
+        assert_ne!(keyed, base.options(&options).size(12, 40));
+        assert_ne!(keyed, keyed.tag(b"t=1s"));
+
+        let dir = temp_dir("key");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("input.bin");
+        fs::write(&path, b"image bytes").unwrap();
+        assert_eq!(CacheKey::from_file(&path).unwrap(), base);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_image_renderer_caches_and_defers_decoding() {
+        use crate::image::ImageRenderer;
+
+        let dir = temp_dir("renderer");
+        let cache = RenderCache::open(&dir, u64::MAX).unwrap();
+        let bytes = fs::read("tests/fixtures/images/sample.png").unwrap();
+        let render = || {
+            ImageRenderer::new()
+                .cache(cache.clone())
+                .load_from_bytes(&bytes)
+                .unwrap()
+                .resize(10, 5, true)
+                .unwrap()
+                .render()
+                .unwrap()
+        };
+
+        let first = render();
+        let stored = cache.size_bytes().unwrap();
+        assert!(stored > 0);
+        let second = render();
+        assert_eq!(first.get_raw_patterns(), second.get_raw_patterns());
+        assert_eq!(cache.size_bytes().unwrap(), stored);
+
+        // Loading only hashes; the decode error comes from render()
+        let mut broken = ImageRenderer::new()
+            .cache(cache.clone())
+            .load_from_bytes(b"not an image")
+            .unwrap();
+        assert!(matches!(
+            broken.render(),
+            Err(DotmaxError::ImageLoad { .. })
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_cache_hit_and_eviction() {
+        let dir = temp_dir("evict");
+        let grid = BrailleGrid::new(20, 10).unwrap();
+        let entry_len = encode_grid(&grid).len() as u64;
+        // Room for two entries
+        let cache = RenderCache::open(&dir, entry_len * 2).unwrap();
+
+        let keys: Vec<CacheKey> = (0u8..3).map(|i| CacheKey::new(&[i])).collect();
+        let mut renders = 0;
+        for key in &keys[..2] {
+            cache
+                .get_or_render(key, || {
+                    renders += 1;
+                    Ok(grid.clone())
+                })
+                .unwrap();
+        }
+        assert_eq!(renders, 2);
+
+        // Using keys[0] leaves keys[1] least recently used; modified times
+        // can be coarse, so let the clock move between steps
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(cache.get(&keys[0]).is_some());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        cache.put(&keys[2], &grid).unwrap();
+
+        assert!(cache.get(&keys[1]).is_none());
+        assert!(cache.get(&keys[0]).is_some());
+        assert_eq!(cache.size_bytes().unwrap(), entry_len * 2);
+
+        cache.clear().unwrap();
+        assert_eq!(cache.size_bytes().unwrap(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
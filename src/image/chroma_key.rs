@@ -0,0 +1,165 @@
+//! Chroma keying (green screen) for live and recorded video.
+//!
+//! [`ChromaKey`] marks pixels close to a key color as transparent and
+//! returns the result as a dot-resolution [`Matte`]. Matching is done on
+//! chroma alone (the Cb/Cr plane of YCbCr), so a green screen that is
+//! brighter at the top than the bottom still keys out evenly, while dark
+//! and gray areas, which have almost no chroma, are kept.
+//!
+//! Video and webcam players take a key through their `chroma_key` setting
+//! and report the matte of each frame; [`KeyedComposite`] layers them over
+//! a background.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::chroma_key::ChromaKey;
+//! use image::{Rgb, RgbImage};
+//!
+//! // Green backdrop with a red subject in the middle
+//! let frame = RgbImage::from_fn(8, 8, |x, y| {
+//!     if (2..6).contains(&x) && (2..6).contains(&y) {
+//!         Rgb([200, 40, 40])
+//!     } else {
+//!         Rgb([30, 220, 50])
+//!     }
+//! });
+//!
+//! let matte = ChromaKey::green().tolerance(0.15).matte(&frame);
+//! assert!(matte.is_opaque(3, 3));
+//! assert!(!matte.is_opaque(0, 0));
+//! ```
+//!
+//! [`KeyedComposite`]: crate::media::KeyedComposite
+
+use image::RgbImage;
+
+use crate::media::Matte;
+
+/// Largest possible distance between two colors on the Cb/Cr plane.
+const MAX_CHROMA_DISTANCE: f32 = 255.0 * std::f32::consts::SQRT_2;
+
+/// Keys out pixels near a color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChromaKey {
+    key: [u8; 3],
+    key_chroma: (f32, f32),
+    tolerance: f32,
+}
+
+impl ChromaKey {
+    /// Keys out colors near `(r, g, b)` with the default tolerance of `0.12`.
+    #[must_use]
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            key: [r, g, b],
+            key_chroma: chroma(r, g, b),
+            tolerance: 0.12,
+        }
+    }
+
+    /// Keys out a typical green screen.
+    #[must_use]
+    pub fn green() -> Self {
+        Self::new(0, 177, 64)
+    }
+
+    /// Keys out a typical blue screen.
+    #[must_use]
+    pub fn blue() -> Self {
+        Self::new(0, 71, 187)
+    }
+
+    /// How far from the key color, as a fraction (`0.0` to `1.0`) of the
+    /// largest possible chroma difference, a pixel may be and still be keyed
+    /// out (default `0.12`). Raise it for unevenly lit screens, lower it if
+    /// parts of the subject disappear.
+    #[must_use]
+    pub const fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The key color.
+    #[must_use]
+    pub const fn key(&self) -> [u8; 3] {
+        self.key
+    }
+
+    /// The tolerance.
+    #[must_use]
+    pub const fn get_tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    /// Whether a pixel is close enough to the key color to be keyed out.
+    #[must_use]
+    pub fn is_keyed(&self, [r, g, b]: [u8; 3]) -> bool {
+        let (cb, cr) = chroma(r, g, b);
+        let distance = (cb - self.key_chroma.0).hypot(cr - self.key_chroma.1);
+        distance / MAX_CHROMA_DISTANCE <= self.tolerance
+    }
+
+    /// A matte the size of `image`, one dot per pixel, with keyed pixels
+    /// transparent. Render the frame at dot resolution (two pixels per cell
+    /// across, four down) for the matte to line up with its braille grid.
+    #[must_use]
+    pub fn matte(&self, image: &RgbImage) -> Matte {
+        let (width, height) = image.dimensions();
+        self.matte_rgb24(image.as_raw(), width as usize * 3, width, height)
+    }
+
+    /// [`matte`](Self::matte) over packed RGB24 rows `stride` bytes apart,
+    /// such as a scaled FFmpeg frame.
+    pub(crate) fn matte_rgb24(&self, data: &[u8], stride: usize, width: u32, height: u32) -> Matte {
+        Matte::from_fn(width as usize, height as usize, |x, y| {
+            let offset = y * stride + x * 3;
+            !self.is_keyed([data[offset], data[offset + 1], data[offset + 2]])
+        })
+    }
+}
+
+/// Cb and Cr (BT.601) of an RGB color.
+fn chroma(r: u8, g: u8, b: u8) -> (f32, f32) {
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    let cb = 0.5f32.mul_add(b, (-0.168_736f32).mul_add(r, -0.331_264 * g));
+    let cr = (-0.081_312f32).mul_add(b, 0.5f32.mul_add(r, -0.418_688 * g));
+    (cb, cr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_keys_shades_of_green_only() {
+        let key = ChromaKey::green();
+        assert!(key.is_keyed([0, 177, 64]));
+        // Same hue in shadow
+        assert!(key.is_keyed([0, 120, 44]));
+        assert!(!key.is_keyed([200, 160, 140])); // skin
+        assert!(!key.is_keyed([20, 20, 20])); // dark hair
+        assert!(!key.is_keyed([128, 128, 128]));
+    }
+
+    #[test]
+    fn test_tolerance_widens_match() {
+        let yellowish = [120, 200, 60];
+        assert!(!ChromaKey::green().tolerance(0.05).is_keyed(yellowish));
+        assert!(ChromaKey::green().tolerance(0.5).is_keyed(yellowish));
+        assert!(ChromaKey::blue().is_keyed([10, 60, 170]));
+    }
+
+    #[test]
+    fn test_matte_matches_image() {
+        let mut image = RgbImage::from_pixel(4, 8, Rgb([0, 177, 64]));
+        image.put_pixel(1, 5, Rgb([255, 255, 255]));
+        let matte = ChromaKey::green().matte(&image);
+
+        assert_eq!((matte.width(), matte.height()), (4, 8));
+        assert!(matte.is_opaque(1, 5));
+        assert!(!matte.is_opaque(0, 0));
+        assert!(!matte.is_opaque(3, 7));
+    }
+}
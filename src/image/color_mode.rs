@@ -284,6 +284,49 @@ pub fn extract_cell_colors(
     cell_width: usize,
     cell_height: usize,
     strategy: ColorSamplingStrategy,
+) -> Vec<Color> {
+    extract_block_colors(image, cell_width, cell_height, 4, strategy)
+}
+
+/// Extract representative color for each sextant cell from an image.
+///
+/// Like [`extract_cell_colors`], but each cell covers a 2×3 pixel block, to
+/// go with [`pixels_to_sextants`](crate::image::mapper::pixels_to_sextants)
+/// and [`RenderMode::Sextant`](crate::render::RenderMode::Sextant).
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::image::color_mode::extract_sextant_colors;
+/// use dotmax::image::ColorSamplingStrategy;
+/// use dotmax::Color;
+/// use image::{DynamicImage, Rgb, RgbImage};
+///
+/// // Red on the top three rows, blue on the next three
+/// let img = RgbImage::from_fn(2, 6, |_, y| {
+///     if y < 3 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) }
+/// });
+/// let img = DynamicImage::ImageRgb8(img);
+///
+/// let colors = extract_sextant_colors(&img, 1, 2, ColorSamplingStrategy::Average);
+/// assert_eq!(colors, vec![Color::rgb(255, 0, 0), Color::rgb(0, 0, 255)]);
+/// ```
+pub fn extract_sextant_colors(
+    image: &DynamicImage,
+    cell_width: usize,
+    cell_height: usize,
+    strategy: ColorSamplingStrategy,
+) -> Vec<Color> {
+    extract_block_colors(image, cell_width, cell_height, 3, strategy)
+}
+
+/// Colors of each 2×`block_height` pixel block, row-major.
+fn extract_block_colors(
+    image: &DynamicImage,
+    cell_width: usize,
+    cell_height: usize,
+    block_height: usize,
+    strategy: ColorSamplingStrategy,
 ) -> Vec<Color> {
     let img_width = image.width() as usize;
     let img_height = image.height() as usize;
@@ -292,13 +335,13 @@ pub fn extract_cell_colors(
 
     for cell_y in 0..cell_height {
         for cell_x in 0..cell_width {
-            // Calculate pixel block bounds (2×4 pixels per braille cell)
+            // Calculate pixel block bounds (2 pixels wide per cell)
             let px_start_x = cell_x * 2;
-            let px_start_y = cell_y * 4;
+            let px_start_y = cell_y * block_height;
 
-            // Collect pixels in 2×4 block
-            let mut block_pixels = Vec::with_capacity(8);
-            for py in 0..4 {
+            // Collect pixels in the block
+            let mut block_pixels = Vec::with_capacity(2 * block_height);
+            for py in 0..block_height {
                 for px in 0..2 {
                     let x = px_start_x + px;
                     let y = px_start_y + py;
@@ -424,16 +467,20 @@ pub fn dominant_color(pixels: &[Rgb<u8>]) -> Color {
         return Color::rgb(0, 0, 0);
     }
 
-    // Count frequency of each color
-    let mut color_counts = std::collections::HashMap::new();
+    // Count frequency of each color, in order of first appearance
+    let mut color_counts: Vec<(Color, usize)> = Vec::new();
     for pixel in pixels {
         let color = Color::rgb(pixel[0], pixel[1], pixel[2]);
-        *color_counts.entry(color).or_insert(0) += 1;
+        match color_counts.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, count)) => *count += 1,
+            None => color_counts.push((color, 1)),
+        }
     }
 
-    // Find color with highest count
+    // Find color with highest count, the first seen on a tie
     color_counts
         .into_iter()
+        .rev()
         .max_by_key(|(_, count)| *count)
         .map_or_else(|| Color::rgb(0, 0, 0), |(color, _)| color)
 }
@@ -944,6 +991,21 @@ mod tests {
         assert_eq!(color, Color::rgb(255, 0, 0)); // Red wins
     }
 
+    #[test]
+    fn test_dominant_color_tie_goes_to_first() {
+        let pixels = vec![
+            Rgb([0, 255, 0]),
+            Rgb([0, 0, 255]),
+            Rgb([255, 0, 0]),
+            Rgb([0, 0, 255]),
+            Rgb([255, 0, 0]),
+            Rgb([0, 255, 0]),
+        ];
+        for _ in 0..10 {
+            assert_eq!(dominant_color(&pixels), Color::rgb(0, 255, 0));
+        }
+    }
+
     #[test]
     fn test_center_pixel_color_empty() {
         let pixels: Vec<Rgb<u8>> = vec![];
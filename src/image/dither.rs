@@ -130,7 +130,7 @@ use crate::image::threshold::{auto_threshold, BinaryImage};
 /// // For no dithering (direct threshold)
 /// let method = DitheringMethod::None;
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DitheringMethod {
     /// Skip dithering and use direct Otsu threshold.
     ///
@@ -0,0 +1,292 @@
+//! Automatic exposure and threshold tracking for live sources.
+//!
+//! A fixed threshold or brightness that looks right in daylight turns a
+//! webcam picture solid black at dusk, or washes it out when a lamp is
+//! switched on. [`AutoExposure`] re-measures the luminance histogram every
+//! few frames and eases its outputs toward the new measurement:
+//!
+//! - **Threshold**: Otsu's threshold for the current frame, so dots keep
+//!   separating subject from background as the light shifts.
+//! - **Brightness**: a gain that pulls the mean luminance toward a target
+//!   level, so the picture stays visible in a dim room.
+//!
+//! Measurements are smoothed with an exponential moving average, so a hand
+//! passing in front of the lens doesn't make the whole picture pump.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::exposure::{AutoExposure, AutoExposureConfig};
+//!
+//! let mut exposure = AutoExposure::new(AutoExposureConfig {
+//!     target_mean: Some(128.0),
+//!     ..AutoExposureConfig::default()
+//! });
+//!
+//! // A dark frame: everything around level 40
+//! let mut histogram = [0u32; 256];
+//! histogram[30] = 500;
+//! histogram[50] = 500;
+//!
+//! if exposure.tick() {
+//!     exposure.measure(&histogram, 1.0);
+//! }
+//! assert_eq!(exposure.threshold(), Some(30));
+//! assert!(exposure.brightness().unwrap() > 1.9); // 128 / 40, capped at 2.0
+//! ```
+//!
+//! [`WebcamPlayer`](crate::media::WebcamPlayer) runs one of these when
+//! configured with `auto_exposure`.
+
+use crate::analysis::ExposureStats;
+
+/// Lowest brightness gain auto-exposure will pick.
+const MIN_BRIGHTNESS: f32 = 0.1;
+
+/// Highest brightness gain auto-exposure will pick, matching the top of
+/// [`ImageRenderer`](crate::image::ImageRenderer)'s brightness range.
+const MAX_BRIGHTNESS: f32 = 2.0;
+
+/// Settings for [`AutoExposure`].
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::image::exposure::AutoExposureConfig;
+///
+/// // Track both threshold and brightness, re-measuring every 5 frames
+/// let config = AutoExposureConfig {
+///     interval: 5,
+///     smoothing: 0.3,
+///     track_threshold: true,
+///     target_mean: Some(118.0),
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoExposureConfig {
+    /// Re-measure every `interval` frames (0 is treated as 1).
+    pub interval: u32,
+
+    /// Weight of each new measurement, `0.0` to `1.0`. Lower values adapt
+    /// more slowly but ride out brief changes; `1.0` follows every
+    /// measurement exactly.
+    pub smoothing: f32,
+
+    /// Track Otsu's threshold.
+    pub track_threshold: bool,
+
+    /// Mean luminance (0-255) to steer brightness toward, or `None` to leave
+    /// brightness alone.
+    pub target_mean: Option<f32>,
+}
+
+impl Default for AutoExposureConfig {
+    /// Threshold tracking only, re-measured every 10 frames (about three
+    /// times a second at 30 fps) with moderate smoothing.
+    fn default() -> Self {
+        Self {
+            interval: 10,
+            smoothing: 0.25,
+            track_threshold: true,
+            target_mean: None,
+        }
+    }
+}
+
+/// Smoothed threshold and brightness that follow changing light.
+///
+/// Call [`tick`](Self::tick) once per frame; when it returns `true`, pass the
+/// frame's luminance histogram to [`measure`](Self::measure). Read the
+/// current values with [`threshold`](Self::threshold) and
+/// [`brightness`](Self::brightness).
+#[derive(Debug, Clone)]
+pub struct AutoExposure {
+    config: AutoExposureConfig,
+    frames_until_measure: u32,
+    threshold: Option<f32>,
+    brightness: Option<f32>,
+}
+
+impl AutoExposure {
+    /// Creates a tracker that measures on the first frame.
+    #[must_use]
+    pub const fn new(config: AutoExposureConfig) -> Self {
+        Self {
+            config,
+            frames_until_measure: 0,
+            threshold: None,
+            brightness: None,
+        }
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub const fn config(&self) -> &AutoExposureConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration and starts over from the next frame.
+    pub fn set_config(&mut self, config: AutoExposureConfig) {
+        self.config = config;
+        self.reset();
+    }
+
+    /// Forgets all measurements, so the next frame is measured and taken
+    /// as-is without smoothing.
+    pub fn reset(&mut self) {
+        self.frames_until_measure = 0;
+        self.threshold = None;
+        self.brightness = None;
+    }
+
+    /// Counts a frame and returns whether it should be measured.
+    pub fn tick(&mut self) -> bool {
+        let due = self.frames_until_measure == 0;
+        self.frames_until_measure = if due {
+            self.config.interval.max(1) - 1
+        } else {
+            self.frames_until_measure - 1
+        };
+        due
+    }
+
+    /// Updates the tracked values from a frame's luminance histogram.
+    ///
+    /// `applied_brightness` is the brightness gain already applied to the
+    /// pixels the histogram was taken from: pass the current
+    /// [`brightness`](Self::brightness) when measuring the adjusted frame, or
+    /// `1.0` for the raw capture.
+    ///
+    /// An empty histogram is ignored.
+    pub fn measure(&mut self, histogram: &[u32; 256], applied_brightness: f32) {
+        if histogram.iter().all(|&count| count == 0) {
+            return;
+        }
+        let stats = ExposureStats::from_histogram(histogram);
+
+        if self.config.track_threshold {
+            self.threshold = Some(self.smooth(self.threshold, f32::from(stats.threshold)));
+        }
+        if let Some(target) = self.config.target_mean {
+            // Undo the gain already applied to get the raw level, then find
+            // the gain that lifts it to the target
+            let raw_mean = stats.mean / applied_brightness.max(MIN_BRIGHTNESS);
+            let wanted = if raw_mean <= 0.0 {
+                MAX_BRIGHTNESS
+            } else {
+                (target / raw_mean).clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS)
+            };
+            self.brightness = Some(self.smooth(self.brightness, wanted));
+        }
+    }
+
+    /// The tracked threshold, or `None` before the first measurement or when
+    /// threshold tracking is off.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn threshold(&self) -> Option<u8> {
+        self.threshold.map(|t| t.round().clamp(0.0, 255.0) as u8)
+    }
+
+    /// The tracked brightness gain, or `None` before the first measurement
+    /// or without a target mean.
+    #[must_use]
+    pub const fn brightness(&self) -> Option<f32> {
+        self.brightness
+    }
+
+    /// Moves `current` toward `measured` by the smoothing weight; the first
+    /// measurement is taken as-is.
+    fn smooth(&self, current: Option<f32>, measured: f32) -> f32 {
+        current.map_or(measured, |current| {
+            let weight = self.config.smoothing.clamp(0.0, 1.0);
+            weight.mul_add(measured - current, current)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Half the pixels at `dark`, half at `bright`.
+    fn two_level_histogram(dark: usize, bright: usize) -> [u32; 256] {
+        let mut histogram = [0u32; 256];
+        histogram[dark] += 500;
+        histogram[bright] += 500;
+        histogram
+    }
+
+    #[test]
+    fn test_measures_every_interval() {
+        let mut exposure = AutoExposure::new(AutoExposureConfig {
+            interval: 3,
+            ..AutoExposureConfig::default()
+        });
+        let due: Vec<bool> = (0..7).map(|_| exposure.tick()).collect();
+        assert_eq!(due, [true, false, false, true, false, false, true]);
+
+        exposure.reset();
+        assert!(exposure.tick());
+    }
+
+    #[test]
+    fn test_threshold_follows_light_smoothly() {
+        let mut exposure = AutoExposure::new(AutoExposureConfig {
+            smoothing: 0.5,
+            ..AutoExposureConfig::default()
+        });
+        assert_eq!(exposure.threshold(), None);
+
+        exposure.measure(&two_level_histogram(20, 100), 1.0);
+        assert_eq!(exposure.threshold(), Some(20));
+
+        // Lights on: Otsu jumps to 150, the tracked value goes halfway
+        exposure.measure(&two_level_histogram(150, 250), 1.0);
+        assert_eq!(exposure.threshold(), Some(85));
+        exposure.measure(&two_level_histogram(150, 250), 1.0);
+        assert_eq!(exposure.threshold(), Some(118));
+
+        assert_eq!(exposure.brightness(), None);
+    }
+
+    #[test]
+    fn test_brightness_accounts_for_applied_gain() {
+        let config = AutoExposureConfig {
+            smoothing: 1.0,
+            track_threshold: false,
+            target_mean: Some(120.0),
+            ..AutoExposureConfig::default()
+        };
+        let mut exposure = AutoExposure::new(config);
+
+        // Raw frame averaging 80: gain 1.5 reaches the target
+        exposure.measure(&two_level_histogram(60, 100), 1.0);
+        assert!((exposure.brightness().unwrap() - 1.5).abs() < 1e-4);
+
+        // The same scene measured after that gain stays put
+        exposure.measure(&two_level_histogram(90, 150), 1.5);
+        assert!((exposure.brightness().unwrap() - 1.5).abs() < 1e-4);
+
+        // Darkness asks for as much gain as allowed
+        exposure.measure(&two_level_histogram(0, 0), 1.5);
+        assert!((exposure.brightness().unwrap() - MAX_BRIGHTNESS).abs() < f32::EPSILON);
+        assert_eq!(exposure.threshold(), None);
+    }
+
+    #[test]
+    fn test_empty_histogram_and_set_config() {
+        let mut exposure = AutoExposure::new(AutoExposureConfig::default());
+        exposure.measure(&[0; 256], 1.0);
+        assert_eq!(exposure.threshold(), None);
+
+        exposure.measure(&two_level_histogram(10, 200), 1.0);
+        assert!(exposure.threshold().is_some());
+        exposure.set_config(AutoExposureConfig {
+            interval: 1,
+            ..AutoExposureConfig::default()
+        });
+        assert_eq!(exposure.threshold(), None);
+        assert_eq!(exposure.config().interval, 1);
+    }
+}
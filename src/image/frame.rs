@@ -0,0 +1,370 @@
+//! Rendering raw pixel buffers straight from a frame producer.
+//!
+//! Webcams, GPU readback, and game engines hand over frames as packed RGB
+//! or RGBA bytes, usually at a much higher resolution than a terminal can
+//! show. A [`RawFrame`] borrows such a buffer as it is, row padding and
+//! all, and [`ImageRenderer::render_frame`](super::ImageRenderer::render_frame)
+//! scales it to the target size straight from those bytes. Only the scaled
+//! frame, a few hundred pixels across, is ever copied.
+//!
+//! For a producer that keeps handing out frames, implement
+//! [`RawFrameSource`] and wrap it in [`RawFrames`], which renders each one
+//! and plays as an [`animation::FrameSource`](crate::animation::FrameSource).
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::{ImageRenderer, PixelFormat, RawFrame};
+//!
+//! // A 640×480 RGB frame whose rows are padded to 2048 bytes
+//! let stride = 2048;
+//! let pixels: Vec<u8> = (0..stride * 480).map(|i| ((i % stride) / 8) as u8).collect();
+//! let frame = RawFrame::new(&pixels, 640, 480, PixelFormat::Rgb8)?.with_stride(stride)?;
+//!
+//! let mut renderer = ImageRenderer::new().resize(40, 12, false)?;
+//! let grid = renderer.render_frame(&frame)?;
+//! assert_eq!(grid.dimensions(), (40, 12));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::Duration;
+
+use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb, Rgba};
+
+use super::resize::resize_view;
+use super::ImageRenderer;
+use crate::animation::FrameSource;
+use crate::{BrailleGrid, DotmaxError};
+
+/// How the bytes of a [`RawFrame`] encode its pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelFormat {
+    /// Three bytes per pixel: red, green, blue
+    Rgb8,
+    /// Four bytes per pixel: red, green, blue, alpha
+    Rgba8,
+}
+
+impl PixelFormat {
+    /// Bytes per pixel.
+    #[must_use]
+    pub const fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgb8 => 3,
+            Self::Rgba8 => 4,
+        }
+    }
+}
+
+/// A borrowed buffer of packed pixels, rows `stride` bytes apart.
+#[derive(Debug, Clone, Copy)]
+pub struct RawFrame<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PixelFormat,
+}
+
+impl<'a> RawFrame<'a> {
+    /// A `width × height` frame of tightly packed rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidImageDimensions`] if either side is 0
+    /// or over 10,000, or [`DotmaxError::FrameBufferTooSmall`] if `data` is
+    /// too short for the frame.
+    pub const fn new(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+    ) -> Result<Self, DotmaxError> {
+        if width == 0 || height == 0 || width > 10_000 || height > 10_000 {
+            return Err(DotmaxError::InvalidImageDimensions { width, height });
+        }
+        Self {
+            data,
+            width,
+            height,
+            stride: width as usize * format.bytes_per_pixel(),
+            format,
+        }
+        .checked()
+    }
+
+    /// The same frame with rows `stride` bytes apart, for buffers that pad
+    /// each row, as FFmpeg and GPU readback often do.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if `stride` is shorter
+    /// than a row, or [`DotmaxError::FrameBufferTooSmall`] if the data is
+    /// too short for the frame.
+    pub fn with_stride(self, stride: usize) -> Result<Self, DotmaxError> {
+        let row = self.row_len();
+        if stride < row {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "stride".to_string(),
+                value: stride.to_string(),
+                min: row.to_string(),
+                max: "unlimited".to_string(),
+            });
+        }
+        Self { stride, ..self }.checked()
+    }
+
+    /// Width in pixels.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in pixels.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Bytes from the start of one row to the start of the next.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The pixel format.
+    #[must_use]
+    pub const fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// The borrowed bytes.
+    #[must_use]
+    pub const fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Scales the frame to fit `width × height` pixels, or to exactly that
+    /// size without `preserve_aspect`, as
+    /// [`resize_to_dimensions`](super::resize_to_dimensions) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidImageDimensions`] if the target size is
+    /// 0 or over 10,000 on either side.
+    pub fn resize(
+        &self,
+        width: u32,
+        height: u32,
+        preserve_aspect: bool,
+    ) -> Result<DynamicImage, DotmaxError> {
+        match self.format {
+            PixelFormat::Rgb8 => self
+                .resize_as::<Rgb<u8>>(width, height, preserve_aspect)
+                .map(DynamicImage::ImageRgb8),
+            PixelFormat::Rgba8 => self
+                .resize_as::<Rgba<u8>>(width, height, preserve_aspect)
+                .map(DynamicImage::ImageRgba8),
+        }
+    }
+
+    /// Resizes through a view of the borrowed rows. A stride that isn't a
+    /// whole number of pixels, or a last row cut short of its padding,
+    /// can't be viewed in place, so the rows are packed into a copy first.
+    fn resize_as<P: Pixel<Subpixel = u8> + 'static>(
+        &self,
+        width: u32,
+        height: u32,
+        preserve_aspect: bool,
+    ) -> Result<ImageBuffer<P, Vec<u8>>, DotmaxError> {
+        let bytes_per_pixel = self.format.bytes_per_pixel();
+        let padded = u32::try_from(self.stride / bytes_per_pixel).ok();
+        let view = padded
+            .filter(|_| self.stride % bytes_per_pixel == 0)
+            .and_then(|padded| ImageBuffer::<P, &[u8]>::from_raw(padded, self.height, self.data));
+        if let Some(buffer) = view {
+            let view = buffer.view(0, 0, self.width, self.height);
+            return resize_view(&*view, width, height, preserve_aspect);
+        }
+        let packed = self
+            .data
+            .chunks(self.stride)
+            .take(self.height as usize)
+            .flat_map(|row| &row[..self.row_len()])
+            .copied()
+            .collect();
+        let buffer = ImageBuffer::<P, Vec<u8>>::from_raw(self.width, self.height, packed)
+            .unwrap_or_else(|| unreachable!("checked() ensures every row is present"));
+        resize_view(&buffer, width, height, preserve_aspect)
+    }
+
+    const fn row_len(&self) -> usize {
+        self.width as usize * self.format.bytes_per_pixel()
+    }
+
+    /// Ensures `data` reaches the end of the last row; its padding may be
+    /// missing.
+    const fn checked(self) -> Result<Self, DotmaxError> {
+        let expected = self.stride * (self.height as usize - 1) + self.row_len();
+        if self.data.len() < expected {
+            return Err(DotmaxError::FrameBufferTooSmall {
+                expected,
+                actual: self.data.len(),
+            });
+        }
+        Ok(self)
+    }
+}
+
+/// A producer of raw pixel frames, such as a camera or a game's framebuffer.
+///
+/// Each frame is borrowed from the source until the next is asked for, so
+/// a producer can keep refilling one buffer.
+pub trait RawFrameSource: Send {
+    /// The next frame and how long to show it, or `None` once the source
+    /// is exhausted.
+    fn next_raw_frame(&mut self) -> Option<Result<(RawFrame<'_>, Duration), DotmaxError>>;
+
+    /// Rewind to the first frame. The default does nothing, which suits
+    /// live sources.
+    fn reset(&mut self) {}
+}
+
+/// Plays a [`RawFrameSource`] as an
+/// [`animation::FrameSource`](crate::animation::FrameSource), rendering
+/// each frame with an [`ImageRenderer`] at the size asked for.
+#[derive(Debug)]
+pub struct RawFrames<S> {
+    source: S,
+    renderer: ImageRenderer,
+}
+
+impl<S: RawFrameSource> RawFrames<S> {
+    /// Renders frames from `source` with `renderer`'s settings. Its resize
+    /// setting only decides whether to preserve the aspect ratio; the size
+    /// comes from each [`next_frame`](FrameSource::next_frame) call.
+    #[must_use]
+    pub const fn new(source: S, renderer: ImageRenderer) -> Self {
+        Self { source, renderer }
+    }
+
+    /// The wrapped source.
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// The renderer, e.g. to change brightness between frames.
+    pub fn renderer_mut(&mut self) -> &mut ImageRenderer {
+        &mut self.renderer
+    }
+}
+
+impl<S: RawFrameSource> FrameSource for RawFrames<S> {
+    fn next_frame(
+        &mut self,
+        width: usize,
+        height: usize,
+    ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+        let (frame, delay) = match self.source.next_raw_frame()? {
+            Ok(next) => next,
+            Err(e) => return Some(Err(e)),
+        };
+        self.renderer.set_cells(width, height);
+        Some(self.renderer.render_frame(&frame).map(|grid| (grid, delay)))
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbaImage;
+
+    fn gradient(width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            Rgba([(x * 255 / width) as u8, (y * 255 / height) as u8, 90, 255])
+        })
+    }
+
+    #[test]
+    fn test_frame_validation() {
+        let data = [0_u8; 30];
+        assert!(RawFrame::new(&data, 0, 2, PixelFormat::Rgb8).is_err());
+        assert!(matches!(
+            RawFrame::new(&data, 4, 3, PixelFormat::Rgb8),
+            Err(DotmaxError::FrameBufferTooSmall {
+                expected: 36,
+                actual: 30
+            })
+        ));
+        let frame = RawFrame::new(&data, 2, 3, PixelFormat::Rgba8).unwrap();
+        assert_eq!(frame.stride(), 8);
+        // The last row needs no padding: 11 + 11 + 8 bytes
+        assert_eq!(frame.with_stride(11).unwrap().stride(), 11);
+        assert!(frame.with_stride(6).is_err());
+        assert!(frame.with_stride(14).is_err());
+    }
+
+    #[test]
+    fn test_render_frame_matches_loaded_image() {
+        let image = gradient(120, 80);
+        let expected = ImageRenderer::new()
+            .load_from_rgba(image.clone())
+            .resize(20, 8, true)
+            .unwrap()
+            .render()
+            .unwrap();
+
+        let mut renderer = ImageRenderer::new().resize(20, 8, true).unwrap();
+        let rgba = renderer.render_from_rgba(image.as_raw(), 120, 80).unwrap();
+        assert_eq!(rgba.get_raw_patterns(), expected.get_raw_patterns());
+
+        // RGB rows padded by 5 bytes, so the stride isn't whole pixels
+        let stride = 120 * 3 + 5;
+        let mut padded = vec![0_u8; stride * 80];
+        for (row, pixels) in padded.chunks_mut(stride).zip(image.rows()) {
+            for (out, pixel) in row.chunks_mut(3).zip(pixels) {
+                out.copy_from_slice(&pixel.0[..3]);
+            }
+        }
+        let frame = RawFrame::new(&padded, 120, 80, PixelFormat::Rgb8)
+            .and_then(|frame| frame.with_stride(stride))
+            .unwrap();
+        let rgb = renderer.render_frame(&frame).unwrap();
+        assert_eq!(rgb.get_raw_patterns(), expected.get_raw_patterns());
+    }
+
+    #[test]
+    fn test_raw_frames_plays_at_requested_size() {
+        struct Counter {
+            pixels: Vec<u8>,
+            left: usize,
+        }
+
+        impl RawFrameSource for Counter {
+            fn next_raw_frame(&mut self) -> Option<Result<(RawFrame<'_>, Duration), DotmaxError>> {
+                self.left = self.left.checked_sub(1)?;
+                let frame = RawFrame::new(&self.pixels, 64, 32, PixelFormat::Rgba8);
+                Some(frame.map(|frame| (frame, Duration::from_millis(40))))
+            }
+        }
+
+        let source = Counter {
+            pixels: gradient(64, 32).into_raw(),
+            left: 2,
+        };
+        let renderer = ImageRenderer::new().resize(1, 1, false).unwrap();
+        let mut frames = RawFrames::new(source, renderer);
+        let (grid, delay) = frames.next_frame(16, 4).unwrap().unwrap();
+        assert_eq!(grid.dimensions(), (16, 4));
+        assert_eq!(delay, Duration::from_millis(40));
+        assert_eq!(
+            frames.next_frame(8, 2).unwrap().unwrap().0.dimensions(),
+            (8, 2)
+        );
+        assert!(frames.next_frame(8, 2).is_none());
+    }
+}
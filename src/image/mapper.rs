@@ -223,6 +223,65 @@ pub fn pixels_to_braille(
     Ok(grid)
 }
 
+/// Convert a binary image to a grid for
+/// [`RenderMode::Sextant`](crate::render::RenderMode::Sextant), mapping each
+/// 2×3 pixel block to one cell.
+///
+/// Pixel rows fill dot rows 0-2 of each cell, which sextant rendering draws
+/// as the cell's three rows; dot row 3 stays empty. Size the image to twice
+/// the cell width by three times the cell height first. Padding works as in
+/// [`pixels_to_braille`].
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidImageDimensions`] if the image is empty.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::image::mapper::pixels_to_sextants;
+/// use dotmax::image::BinaryImage;
+///
+/// // A 4×3 image: one lit column on the left
+/// let pixels = (0..12).map(|i| i % 4 == 0).collect();
+/// let binary = BinaryImage { width: 4, height: 3, pixels };
+///
+/// let grid = pixels_to_sextants(&binary)?;
+/// assert_eq!(grid.dimensions(), (2, 1));
+/// assert!(grid.is_dot_set(0, 2) && !grid.is_dot_set(0, 3));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn pixels_to_sextants(binary: &BinaryImage) -> Result<BrailleGrid, DotmaxError> {
+    if binary.width == 0 || binary.height == 0 {
+        return Err(DotmaxError::InvalidImageDimensions {
+            width: binary.width,
+            height: binary.height,
+        });
+    }
+
+    let (width, height) = (binary.width as usize, binary.height as usize);
+    let mut grid = BrailleGrid::new((width + 1) / 2, (height + 2) / 3)?;
+    info!(
+        "Mapping {}×{} binary image to {}×{} sextant grid",
+        width,
+        height,
+        grid.width(),
+        grid.height()
+    );
+
+    for y in 0..height {
+        // Three pixel rows per cell, in dot rows 0-2 of its four
+        let dot_y = (y / 3) * 4 + y % 3;
+        for x in 0..width {
+            if binary.pixels[y * width + x] {
+                grid.set_dot(x, dot_y)?;
+            }
+        }
+    }
+
+    Ok(grid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -460,4 +519,25 @@ mod tests {
             "2×2 pattern should produce U+2811 (dots 1,5)"
         );
     }
+
+    #[test]
+    fn test_pixels_to_sextants() {
+        // 3×4 image: every pixel lit, so 2×2 cells with the padding empty
+        let binary = create_test_image(3, 4, vec![true; 12]);
+        let grid = pixels_to_sextants(&binary).unwrap();
+        assert_eq!(grid.dimensions(), (2, 2));
+
+        // First cell: all three rows, row 3 empty
+        assert_eq!(grid.get_raw_patterns()[0], 0b0011_1111);
+        // Right column is padding
+        assert_eq!(grid.get_raw_patterns()[1], 0b0000_0111);
+        // Fourth pixel row is the top row of the second cell row
+        assert_eq!(grid.get_raw_patterns()[2], 0b0000_1001);
+
+        let empty = create_test_image(0, 0, vec![]);
+        assert!(matches!(
+            pixels_to_sextants(&empty),
+            Err(DotmaxError::InvalidImageDimensions { .. })
+        ));
+    }
 }
@@ -0,0 +1,256 @@
+//! Photo metadata: dimensions, capture date, camera, and orientation.
+//!
+//! [`metadata`] reads an image's header for its size and its EXIF block
+//! (JPEG, TIFF, PNG, WebP, and HEIF) for the rest. Images without EXIF, or
+//! with a block that doesn't parse, still report their dimensions; the EXIF
+//! fields are just `None`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::image::metadata;
+//!
+//! let info = metadata("holiday.jpg")?;
+//! println!("{}", info.caption()); // "2023-06-01 14:22:05 · Canon EOS R6 · 6000×4000"
+//!
+//! if let Some(date) = &info.date_taken {
+//!     println!("Taken {date}");
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use exif::{DateTime, Exif, In, Tag, Value};
+use tracing::debug;
+
+use crate::DotmaxError;
+
+/// What [`metadata`] found out about an image.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageMetadata {
+    /// Stored width in pixels, before any orientation is applied.
+    pub width: u32,
+
+    /// Stored height in pixels, before any orientation is applied.
+    pub height: u32,
+
+    /// When the photo was taken, as `YYYY-MM-DD HH:MM:SS` in the camera's
+    /// local time. Falls back to the file's modification date tag.
+    pub date_taken: Option<String>,
+
+    /// Camera manufacturer, e.g. `"Canon"`.
+    pub camera_make: Option<String>,
+
+    /// Camera model, e.g. `"Canon EOS R6"`.
+    pub camera_model: Option<String>,
+
+    /// EXIF orientation, 1-8. 1 is upright; 6 and 8 mean the stored image
+    /// is rotated a quarter turn from how it should be shown.
+    pub orientation: Option<u16>,
+}
+
+impl ImageMetadata {
+    /// Width and height as the photo should be shown, swapped when the
+    /// orientation turns it on its side.
+    #[must_use]
+    pub const fn display_dimensions(&self) -> (u32, u32) {
+        match self.orientation {
+            Some(5..=8) => (self.height, self.width),
+            _ => (self.width, self.height),
+        }
+    }
+
+    /// Make and model as one name. Models that already start with the make
+    /// (`"Canon EOS R6"`) aren't prefixed twice.
+    #[must_use]
+    pub fn camera(&self) -> Option<String> {
+        match (&self.camera_make, &self.camera_model) {
+            (Some(make), Some(model)) if model.starts_with(make.as_str()) => Some(model.clone()),
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => make.as_ref().or(model.as_ref()).cloned(),
+        }
+    }
+
+    /// A one-line summary for captions: date, camera, and dimensions,
+    /// skipping whatever is unknown.
+    #[must_use]
+    pub fn caption(&self) -> String {
+        let (width, height) = self.display_dimensions();
+        self.date_taken
+            .iter()
+            .cloned()
+            .chain(self.camera())
+            .chain(std::iter::once(format!("{width}×{height}")))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// Reads an image's dimensions and basic EXIF metadata.
+///
+/// Only the header and EXIF block are read; pixels aren't decoded.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::ImageLoad`] if the file can't be opened or its
+/// format or dimensions can't be determined. Missing or damaged EXIF is
+/// not an error.
+pub fn metadata(path: impl AsRef<Path>) -> Result<ImageMetadata, DotmaxError> {
+    let path = path.as_ref();
+    let (width, height) =
+        image::image_dimensions(path).map_err(|source| DotmaxError::ImageLoad {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    let file = File::open(path).map_err(|e| DotmaxError::ImageLoad {
+        path: path.to_path_buf(),
+        source: image::ImageError::IoError(e),
+    })?;
+    let exif = match exif::Reader::new().read_from_container(&mut BufReader::new(file)) {
+        Ok(exif) => Some(exif),
+        Err(e) => {
+            debug!("No usable EXIF in {:?}: {}", path, e);
+            None
+        }
+    };
+
+    let mut info = ImageMetadata {
+        width,
+        height,
+        ..ImageMetadata::default()
+    };
+    if let Some(exif) = exif {
+        info.date_taken =
+            date_field(&exif, Tag::DateTimeOriginal).or_else(|| date_field(&exif, Tag::DateTime));
+        info.camera_make = text_field(&exif, Tag::Make);
+        info.camera_model = text_field(&exif, Tag::Model);
+        info.orientation = exif
+            .get_field(Tag::Orientation, In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0))
+            .and_then(|value| u16::try_from(value).ok())
+            .filter(|value| (1..=8).contains(value));
+    }
+    Ok(info)
+}
+
+/// The first string of an ASCII field, trimmed; `None` if blank.
+fn text_field(exif: &Exif, tag: Tag) -> Option<String> {
+    let Value::Ascii(strings) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    let text = String::from_utf8_lossy(strings.first()?);
+    let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn date_field(exif: &Exif, tag: Tag) -> Option<String> {
+    let Value::Ascii(strings) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    DateTime::from_ascii(strings.first()?)
+        .ok()
+        .map(|date| date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::experimental::Writer;
+    use exif::Field;
+    use std::io::Cursor;
+
+    fn ascii(tag: Tag, text: &str) -> Field {
+        Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![text.as_bytes().to_vec()]),
+        }
+    }
+
+    /// A 4×2 JPEG carrying `fields` in an APP1 EXIF segment.
+    fn jpeg_with_exif(fields: &[Field]) -> Vec<u8> {
+        let mut tiff = Cursor::new(Vec::new());
+        let mut writer = Writer::new();
+        for field in fields {
+            writer.push_field(field);
+        }
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = Vec::new();
+        image::DynamicImage::new_rgb8(4, 2)
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+            .unwrap();
+
+        // APP1 goes right after the SOI marker
+        let length = u16::try_from(2 + 6 + tiff.len()).unwrap();
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&length.to_be_bytes());
+        segment.extend_from_slice(b"Exif\0\0");
+        segment.extend_from_slice(&tiff);
+        jpeg.splice(2..2, segment);
+        jpeg
+    }
+
+    #[test]
+    fn test_reads_exif_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("photo.jpg");
+        let orientation = Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: Value::Short(vec![6]),
+        };
+        std::fs::write(
+            &path,
+            jpeg_with_exif(&[
+                ascii(Tag::Make, "Canon"),
+                ascii(Tag::Model, "Canon EOS R6"),
+                ascii(Tag::DateTimeOriginal, "2023:06:01 14:22:05"),
+                orientation,
+            ]),
+        )
+        .unwrap();
+
+        let info = metadata(&path).unwrap();
+        assert_eq!((info.width, info.height), (4, 2));
+        assert_eq!(info.display_dimensions(), (2, 4));
+        assert_eq!(info.date_taken.as_deref(), Some("2023-06-01 14:22:05"));
+        assert_eq!(info.camera().as_deref(), Some("Canon EOS R6"));
+        assert_eq!(info.orientation, Some(6));
+        assert_eq!(info.caption(), "2023-06-01 14:22:05 · Canon EOS R6 · 2×4");
+    }
+
+    #[test]
+    fn test_image_without_exif_has_dimensions_only() {
+        let info = metadata("tests/fixtures/images/sample.png").unwrap();
+        assert!(info.width > 0 && info.height > 0);
+        assert_eq!(info.date_taken, None);
+        assert_eq!(info.camera(), None);
+        assert_eq!(info.caption(), format!("{}×{}", info.width, info.height));
+    }
+
+    #[test]
+    fn test_missing_file_is_image_load_error() {
+        assert!(matches!(
+            metadata("tests/fixtures/images/missing.jpg"),
+            Err(DotmaxError::ImageLoad { .. })
+        ));
+    }
+
+    #[test]
+    fn test_camera_joins_make_and_model() {
+        let mut info = ImageMetadata {
+            camera_make: Some("FUJIFILM".to_string()),
+            camera_model: Some("X-T4".to_string()),
+            ..ImageMetadata::default()
+        };
+        assert_eq!(info.camera().as_deref(), Some("FUJIFILM X-T4"));
+        info.camera_model = None;
+        assert_eq!(info.camera().as_deref(), Some("FUJIFILM"));
+    }
+}
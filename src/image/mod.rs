@@ -112,36 +112,57 @@
 //! # }
 //! ```
 
+pub mod cache;
+pub mod chroma_key;
 pub mod color_mode;
 pub mod convert;
 pub mod dither;
+pub mod exposure;
+pub mod frame;
 pub mod loader;
 pub mod mapper;
+pub mod metadata;
+pub mod mode_select;
+pub mod pipeline;
+pub mod render_options;
+pub mod report;
 pub mod resize;
+pub mod roi;
+pub mod stage;
 #[cfg(feature = "svg")]
 pub mod svg;
 pub mod temporal;
 pub mod threshold;
+pub mod viewport;
 
 // Re-export public types and functions for convenience
+pub use cache::{CacheKey, RenderCache};
 pub use color_mode::{render_image_with_color, ColorMode, ColorSamplingStrategy};
 pub use convert::to_grayscale;
 pub use dither::{apply_dithering, apply_dithering_with_custom_threshold, DitheringMethod};
+pub use frame::{PixelFormat, RawFrame, RawFrameSource, RawFrames};
 pub use loader::{load_from_bytes, load_from_path, supported_formats};
-pub use mapper::pixels_to_braille;
+pub use mapper::{pixels_to_braille, pixels_to_sextants};
+pub use metadata::{metadata, ImageMetadata};
+pub use mode_select::{ContentProfile, ModeDecision, ModeReason, ModeSelector};
+pub use pipeline::{Pipeline, PipelineStage};
+pub use render_options::RenderOptions;
+pub use report::{RenderReport, StageReport};
 pub use resize::{resize_to_dimensions, resize_to_terminal};
+pub use stage::{FrameData, InsertionPoint, Stage};
 #[cfg(feature = "svg")]
 pub use svg::{load_svg_from_bytes, load_svg_from_path};
 pub use threshold::{
     adjust_brightness, adjust_contrast, adjust_gamma, apply_threshold, auto_threshold,
     otsu_threshold, BinaryImage,
 };
+pub use viewport::Viewport;
 
 // High-level API types and functions are defined below and automatically exported
 
 use crate::{BrailleGrid, DotmaxError};
 use image::DynamicImage;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, instrument};
 
 /// Resize mode configuration for [`ImageRenderer`].
@@ -207,22 +228,23 @@ enum ResizeMode {
 /// ```
 #[derive(Debug)]
 pub struct ImageRenderer {
-    image: Option<DynamicImage>,
     dithering: DitheringMethod,
     color_mode: ColorMode,
     threshold: Option<u8>,
+    despeckle: Option<u8>,
     resize_mode: ResizeMode,
     brightness: f32,
     contrast: f32,
     gamma: f32,
-    /// ISSUE #3 FIX: Cache for resized image to enable fast re-renders
-    /// when only adjustments (brightness/contrast/gamma) change
-    cached_resized: Option<DynamicImage>,
-    /// Cache for original color image before grayscale conversion
-    /// (needed for color mode rendering)
-    cached_original_resized: Option<DynamicImage>,
-    /// Dimensions used for the cached resized image (to detect terminal resize)
-    cached_dimensions: Option<(u32, u32)>,
+    /// ISSUE #3 FIX: Holds the loaded image and caches each stage, so
+    /// re-renders only redo what changed (see [`Pipeline`])
+    pipeline: Pipeline,
+    /// On-disk cache of finished grids, see [`cache`](Self::cache)
+    cache: Option<RenderCache>,
+    /// Hash of the loaded file's bytes, when loaded with a cache attached
+    source_key: Option<CacheKey>,
+    /// Bytes (and their path, for errors) waiting to be decoded on a cache miss
+    deferred: Option<(Vec<u8>, PathBuf)>,
 }
 
 impl ImageRenderer {
@@ -246,19 +268,20 @@ impl ImageRenderer {
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            image: None,
             dithering: DitheringMethod::FloydSteinberg,
             color_mode: ColorMode::Monochrome,
             threshold: None,
+            despeckle: None,
             resize_mode: ResizeMode::AutoTerminal {
                 preserve_aspect: true,
             },
             brightness: 1.0,
             contrast: 1.0,
             gamma: 1.0,
-            cached_resized: None,
-            cached_original_resized: None,
-            cached_dimensions: None,
+            pipeline: Pipeline::new(),
+            cache: None,
+            source_key: None,
+            deferred: None,
         }
     }
 
@@ -295,6 +318,14 @@ impl ImageRenderer {
     /// ```
     #[instrument(skip(self))]
     pub fn load_from_path(mut self, path: &Path) -> Result<Self, DotmaxError> {
+        if self.cache.is_some() {
+            let bytes = std::fs::read(path).map_err(|e| DotmaxError::ImageLoad {
+                path: path.to_path_buf(),
+                source: image::ImageError::IoError(e),
+            })?;
+            self.defer(bytes, path.to_path_buf());
+            return Ok(self);
+        }
         let img = load_from_path(path)?;
         info!(
             "Loaded image from {:?}, dimensions: {}x{}",
@@ -302,11 +333,9 @@ impl ImageRenderer {
             img.width(),
             img.height()
         );
-        self.image = Some(img);
-        // ISSUE #3: Invalidate cache when new image loaded
-        self.cached_resized = None;
-        self.cached_original_resized = None;
-        self.cached_dimensions = None;
+        // ISSUE #3: Replacing the image invalidates every cached stage
+        self.invalidate();
+        self.pipeline.set_image(img);
         Ok(self)
     }
 
@@ -341,17 +370,19 @@ impl ImageRenderer {
     /// ```
     #[instrument(skip(self, bytes))]
     pub fn load_from_bytes(mut self, bytes: &[u8]) -> Result<Self, DotmaxError> {
+        if self.cache.is_some() {
+            self.defer(bytes.to_vec(), PathBuf::from("<bytes>"));
+            return Ok(self);
+        }
         let img = load_from_bytes(bytes)?;
         info!(
             "Loaded image from bytes, dimensions: {}x{}",
             img.width(),
             img.height()
         );
-        self.image = Some(img);
-        // ISSUE #3: Invalidate cache when new image loaded
-        self.cached_resized = None;
-        self.cached_original_resized = None;
-        self.cached_dimensions = None;
+        // ISSUE #3: Replacing the image invalidates every cached stage
+        self.invalidate();
+        self.pipeline.set_image(img);
         Ok(self)
     }
 
@@ -388,11 +419,8 @@ impl ImageRenderer {
             img.width(),
             img.height()
         );
-        self.image = Some(DynamicImage::ImageRgba8(img));
-        // Invalidate cache when new image loaded
-        self.cached_resized = None;
-        self.cached_original_resized = None;
-        self.cached_dimensions = None;
+        self.invalidate();
+        self.pipeline.set_image(DynamicImage::ImageRgba8(img));
         self
     }
 
@@ -445,11 +473,9 @@ impl ImageRenderer {
             "Loaded SVG from {:?}, rasterized to {}x{}",
             path, width, height
         );
-        self.image = Some(img);
-        // ISSUE #3: Invalidate cache when new image loaded
-        self.cached_resized = None;
-        self.cached_original_resized = None;
-        self.cached_dimensions = None;
+        // ISSUE #3: Replacing the image invalidates every cached stage
+        self.invalidate();
+        self.pipeline.set_image(img);
         Ok(self)
     }
 
@@ -559,14 +585,7 @@ impl ImageRenderer {
     /// # }
     /// ```
     pub fn brightness(mut self, factor: f32) -> Result<Self, DotmaxError> {
-        if !(0.0..=2.0).contains(&factor) {
-            return Err(DotmaxError::InvalidParameter {
-                parameter_name: "brightness".to_string(),
-                value: factor.to_string(),
-                min: "0.0".to_string(),
-                max: "2.0".to_string(),
-            });
-        }
+        threshold::check_range("brightness", factor, &threshold::BRIGHTNESS_RANGE)?;
         self.brightness = factor;
         Ok(self)
     }
@@ -600,14 +619,7 @@ impl ImageRenderer {
     /// # }
     /// ```
     pub fn contrast(mut self, factor: f32) -> Result<Self, DotmaxError> {
-        if !(0.0..=2.0).contains(&factor) {
-            return Err(DotmaxError::InvalidParameter {
-                parameter_name: "contrast".to_string(),
-                value: factor.to_string(),
-                min: "0.0".to_string(),
-                max: "2.0".to_string(),
-            });
-        }
+        threshold::check_range("contrast", factor, &threshold::CONTRAST_RANGE)?;
         self.contrast = factor;
         Ok(self)
     }
@@ -641,14 +653,7 @@ impl ImageRenderer {
     /// # }
     /// ```
     pub fn gamma(mut self, value: f32) -> Result<Self, DotmaxError> {
-        if !(0.1..=3.0).contains(&value) {
-            return Err(DotmaxError::InvalidParameter {
-                parameter_name: "gamma".to_string(),
-                value: value.to_string(),
-                min: "0.1".to_string(),
-                max: "3.0".to_string(),
-            });
-        }
+        threshold::check_range("gamma", value, &threshold::GAMMA_RANGE)?;
         self.gamma = value;
         Ok(self)
     }
@@ -740,6 +745,231 @@ impl ImageRenderer {
         self
     }
 
+    /// Removes isolated dots from the rendered grid.
+    ///
+    /// Dots with fewer than `min_neighbors` set neighbors are cleared after
+    /// dithering; see [`crate::analysis::despeckle`]. `1` removes lone dots
+    /// only and is a good starting point for noisy photos. Off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::image::ImageRenderer;
+    ///
+    /// let renderer = ImageRenderer::new()
+    ///     .despeckle(1);
+    /// ```
+    #[must_use]
+    pub const fn despeckle(mut self, min_neighbors: u8) -> Self {
+        self.despeckle = Some(min_neighbors);
+        self
+    }
+
+    /// Consults `cache` before rendering and stores what it renders.
+    ///
+    /// Attach the cache before loading: [`load_from_path`](Self::load_from_path)
+    /// and [`load_from_bytes`](Self::load_from_bytes) then only read and hash
+    /// the input, and decoding waits until [`render`](Self::render) misses
+    /// the cache, so a hit never decodes. Decode errors surface from `render`
+    /// in that case. Images loaded any other way aren't cached.
+    ///
+    /// The key covers the input bytes, the [`RenderOptions`], the target
+    /// size, aspect handling, and despeckling. Custom [`stage`](Self::stage)s
+    /// can't be keyed, so renderers with any skip the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::image::{ImageRenderer, RenderCache};
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let cache = RenderCache::open("thumbnails", 16 * 1024 * 1024)?;
+    /// let grid = ImageRenderer::new()
+    ///     .cache(cache)
+    ///     .load_from_path(Path::new("photo.jpg"))?
+    ///     .resize(32, 8, true)?
+    ///     .render()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cache(mut self, cache: RenderCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Adds a custom processing [`Stage`] at `at`.
+    ///
+    /// Stages at the same point run in the order they were added and stay
+    /// in place when a new image is loaded. See the [`stage`] module for
+    /// what each insertion point receives.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::image::{FrameData, ImageRenderer, InsertionPoint};
+    ///
+    /// // Draw dark areas as dots instead of light ones
+    /// let renderer = ImageRenderer::new().stage(InsertionPoint::AfterThreshold, |data| {
+    ///     match data {
+    ///         FrameData::Binary(mut binary) => {
+    ///             binary.pixels.iter_mut().for_each(|pixel| *pixel = !*pixel);
+    ///             FrameData::Binary(binary)
+    ///         }
+    ///         other => other,
+    ///     }
+    /// });
+    /// ```
+    #[must_use]
+    pub fn stage(mut self, at: InsertionPoint, stage: impl Stage + 'static) -> Self {
+        self.pipeline.add_stage(at, stage);
+        self
+    }
+
+    /// Records a [`RenderReport`] of per-stage timings and sizes on every
+    /// [`render`](Self::render), for [`last_report`](Self::last_report).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::image::ImageRenderer;
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let mut renderer = ImageRenderer::new()
+    ///     .report(true)
+    ///     .load_from_path(Path::new("photo.jpg"))?;
+    /// renderer.render()?;
+    /// if let Some(report) = renderer.last_report() {
+    ///     eprintln!("{report}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn report(mut self, enabled: bool) -> Self {
+        self.pipeline.set_reporting(enabled);
+        self
+    }
+
+    /// Lets `selector` pick a [`RenderMode`](crate::render::RenderMode) for
+    /// each loaded image, and maps the grid for it: sextant-shaped cells when
+    /// it picks [`Sextant`](crate::render::RenderMode::Sextant), braille
+    /// otherwise. The choice is in [`mode_decision`](Self::mode_decision)
+    /// and the [`RenderReport`]; hand it to
+    /// [`TerminalRenderer::set_render_mode`](crate::TerminalRenderer::set_render_mode)
+    /// before drawing the grid.
+    ///
+    /// The [`cache`](Self::cache) is skipped while a selector is set, since
+    /// the choice depends on the decoded image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::image::{ImageRenderer, ModeSelector};
+    /// use dotmax::TerminalRenderer;
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let mut renderer = ImageRenderer::new()
+    ///     .mode_selector(Some(ModeSelector::detect()))
+    ///     .load_from_path(Path::new("photo.jpg"))?;
+    /// let grid = renderer.render()?;
+    ///
+    /// let mut terminal = TerminalRenderer::new()?;
+    /// if let Some(decision) = renderer.mode_decision() {
+    ///     terminal.set_render_mode(decision.mode);
+    /// }
+    /// terminal.render(&grid)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn mode_selector(mut self, selector: Option<ModeSelector>) -> Self {
+        self.pipeline.set_mode_selector(selector);
+        self
+    }
+
+    /// The mode picked for the last [`render`](Self::render), if a
+    /// [`mode_selector`](Self::mode_selector) is set.
+    #[must_use]
+    pub const fn mode_decision(&self) -> Option<ModeDecision> {
+        self.pipeline.mode_decision()
+    }
+
+    /// Shows only the part of the image `viewport` covers, filling the
+    /// usual target size with it. Panning re-renders from the already
+    /// scaled image; see [`viewport`](self::viewport).
+    ///
+    /// Zoomed renders skip the [`cache`](Self::cache).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::image::{ImageRenderer, Viewport};
+    /// use image::RgbaImage;
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let mut renderer = ImageRenderer::new()
+    ///     .load_from_rgba(RgbaImage::new(400, 200))
+    ///     .resize(40, 10, true)?;
+    ///
+    /// // Arrow keys and +/- in a viewer would do this
+    /// renderer.set_viewport(renderer.viewport().zoom(2.0).pan(-0.25, 0.0));
+    /// let grid = renderer.render()?;
+    /// assert_eq!(grid.dimensions(), (40, 10));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.pipeline.set_viewport(viewport);
+    }
+
+    /// The current viewport, [`Viewport::FULL`] unless one was set.
+    #[must_use]
+    pub const fn viewport(&self) -> Viewport {
+        self.pipeline.viewport()
+    }
+
+    /// The part of the image the last [`render`](Self::render) showed, in
+    /// normalized image coordinates; see
+    /// [`Pipeline::visible_region`].
+    #[must_use]
+    pub const fn visible_region(&self) -> Option<roi::RoiRect> {
+        self.pipeline.visible_region()
+    }
+
+    /// Applies a whole set of [`RenderOptions`] at once, replacing the
+    /// dithering, threshold, brightness, contrast, gamma, and color mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`]; the renderer is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::image::{DitheringMethod, ImageRenderer, RenderOptions};
+    ///
+    /// # fn main() -> Result<(), dotmax::DotmaxError> {
+    /// let options = RenderOptions::new().dithering(DitheringMethod::Bayer).gamma(0.8);
+    /// let renderer = ImageRenderer::new().render_options(options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn render_options(mut self, options: RenderOptions) -> Result<Self, DotmaxError> {
+        options.validate()?;
+        self.dithering = options.dithering;
+        self.threshold = options.threshold;
+        self.brightness = options.brightness;
+        self.contrast = options.contrast;
+        self.gamma = options.gamma;
+        self.color_mode = options.color_mode;
+        Ok(self)
+    }
+
     /// Executes the full image rendering pipeline.
     ///
     /// This method performs the following steps:
@@ -750,6 +980,10 @@ impl ImageRenderer {
     /// 5. Applies dithering or thresholding
     /// 6. Maps pixels to braille dots
     /// 7. Applies colors if color mode is not Monochrome
+    /// 8. Removes isolated dots if [`despeckle`](Self::despeckle) is set
+    ///
+    /// With a [`cache`](Self::cache) attached, a cache hit returns the stored
+    /// grid without running any of these steps.
     ///
     /// # Returns
     ///
@@ -778,186 +1012,192 @@ impl ImageRenderer {
     ///
     /// ## ISSUE #3 FIX: Performance Caching
     ///
-    /// This method now caches the resized image internally. When called multiple times
-    /// with only brightness/contrast/gamma changes, it reuses the cached resized image
-    /// instead of re-loading and re-resizing. This enables responsive parameter adjustments
-    /// (<10ms) suitable for interactive image editing.
-    ///
-    /// Cache is automatically invalidated when:
-    /// - A new image is loaded (`load_from_path`, `load_from_bytes`, `load_svg_from_path`)
-    /// - Resize mode changes
+    /// Rendering goes through a [`Pipeline`], which caches the output of each
+    /// step. When called multiple times, only the steps fed by changed settings
+    /// run again: a brightness change skips re-loading and re-resizing, and a
+    /// color mode change only recolors the cells. This enables responsive
+    /// parameter adjustments (<10ms) suitable for interactive image editing.
     ///
-    /// Cache is preserved (fast path) when only these change:
-    /// - Brightness, contrast, gamma adjustments
-    /// - Color mode
-    /// - Dithering method
-    /// - Threshold value
+    /// Loading a new image, or a change in the target size (e.g. a terminal
+    /// resize), reruns everything from the resize on.
     #[instrument(skip(self))]
-    #[allow(clippy::too_many_lines)]
     pub fn render(&mut self) -> Result<BrailleGrid, DotmaxError> {
-        // Validate image is loaded
-        let img = self
-            .image
-            .as_ref()
-            .ok_or_else(|| DotmaxError::InvalidParameter {
-                parameter_name: "image".to_string(),
-                value: "None".to_string(),
-                min: "Must load image first".to_string(),
-                max: "loaded image".to_string(),
-            })?;
+        let Some((cache, key)) = self.cache_key() else {
+            self.decode_deferred()?;
+            return self.render_pipeline();
+        };
+        // A cache hit renders nothing to report on
+        self.pipeline.clear_report();
+        cache.get_or_render(&key, || {
+            self.decode_deferred()?;
+            self.render_pipeline()
+        })
+    }
 
+    /// The [`RenderReport`] for the last [`render`](Self::render), if
+    /// [`report`](Self::report) is on and that render ran the pipeline
+    /// rather than coming from the [`cache`](Self::cache).
+    #[must_use]
+    pub const fn last_report(&self) -> Option<&RenderReport> {
+        self.pipeline.last_report()
+    }
+
+    /// Renders a frame of packed RGB bytes, `width × height` pixels, without
+    /// copying it or loading it as an image first.
+    ///
+    /// Shorthand for [`render_frame`](Self::render_frame) with a
+    /// [`RawFrame`] of [`PixelFormat::Rgb8`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`RawFrame::new`] for a bad size or short
+    /// buffer, otherwise those of [`render`](Self::render).
+    pub fn render_from_rgb(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<BrailleGrid, DotmaxError> {
+        self.render_frame(&RawFrame::new(data, width, height, PixelFormat::Rgb8)?)
+    }
+
+    /// [`render_from_rgb`](Self::render_from_rgb) for RGBA bytes.
+    ///
+    /// # Errors
+    ///
+    /// As for [`render_from_rgb`](Self::render_from_rgb).
+    pub fn render_from_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<BrailleGrid, DotmaxError> {
+        self.render_frame(&RawFrame::new(data, width, height, PixelFormat::Rgba8)?)
+    }
+
+    /// Replaces the loaded image with `frame` and renders it.
+    ///
+    /// The frame is scaled to the target size straight from its borrowed
+    /// bytes, and only the scaled copy is kept, so feeding a new frame each
+    /// tick costs one resize rather than a full-size copy and decode. Frames
+    /// are live data and skip the [`cache`](Self::cache).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidImageDimensions`] if the target size is
+    /// out of range, or any error from the later steps of
+    /// [`render`](Self::render).
+    #[instrument(skip(self, frame), fields(width = frame.width(), height = frame.height()))]
+    pub fn render_frame(&mut self, frame: &RawFrame<'_>) -> Result<BrailleGrid, DotmaxError> {
+        let (width, height) = self.calculate_target_dimensions();
+        let scaled = frame.resize(width, height, self.preserve_aspect())?;
+        self.invalidate();
+        self.pipeline.set_image(scaled);
+        self.render_pipeline()
+    }
+
+    /// Runs the pipeline described on [`render`](Self::render), without the
+    /// on-disk cache.
+    fn render_pipeline(&mut self) -> Result<BrailleGrid, DotmaxError> {
         info!("Starting image rendering pipeline");
 
-        // Calculate target dimensions
         let (target_width_pixels, target_height_pixels) = self.calculate_target_dimensions();
         debug!(
             "Target dimensions: {}x{} pixels",
             target_width_pixels, target_height_pixels
         );
+        let preserve_aspect = self.preserve_aspect();
 
-        // ISSUE #3 FIX: Check if we can reuse cached resized image
-        // Must check both cache existence AND that dimensions haven't changed (e.g., terminal resize)
-        let dimensions_match = self.cached_dimensions.is_some_and(|(w, h)| {
-            w == target_width_pixels && h == target_height_pixels
-        });
+        // Settings equal to the previous render's leave their stages cached
+        self.pipeline.set_size(
+            target_width_pixels as usize / 2,
+            target_height_pixels as usize / 4,
+            preserve_aspect,
+        )?;
+        self.pipeline.set_options(RenderOptions::from(&*self))?;
+        self.pipeline.set_despeckle(self.despeckle);
 
-        let resized = if let Some(cached) = &self.cached_resized {
-            if dimensions_match {
-                // Fast path: reuse cached resized image (dimensions unchanged)
-                debug!("Using cached resized image (fast path for parameter adjustments)");
-                cached.clone()
-            } else {
-                // Dimensions changed (e.g., terminal resize) - must re-resize
-                debug!(
-                    "Dimensions changed from {:?} to {}x{}, invalidating cache and re-resizing",
-                    self.cached_dimensions, target_width_pixels, target_height_pixels
-                );
-                let resized = match &self.resize_mode {
-                    ResizeMode::AutoTerminal { preserve_aspect }
-                    | ResizeMode::Manual {
-                        preserve_aspect, ..
-                    } => resize_to_dimensions(
-                        img,
-                        target_width_pixels,
-                        target_height_pixels,
-                        *preserve_aspect,
-                    )?,
-                };
-                debug!(
-                    "Image resized to {}x{}, caching for future renders",
-                    resized.width(),
-                    resized.height()
-                );
-                self.cached_resized = Some(resized.clone());
-                self.cached_original_resized = Some(resized.clone());
-                self.cached_dimensions = Some((target_width_pixels, target_height_pixels));
-                resized
-            }
-        } else {
-            // Slow path: resize image and cache it (no cache available)
-            debug!("Resizing image (no cache available)");
-            let resized = match &self.resize_mode {
-                ResizeMode::AutoTerminal { preserve_aspect }
-                | ResizeMode::Manual {
-                    preserve_aspect, ..
-                } => resize_to_dimensions(
-                    img,
-                    target_width_pixels,
-                    target_height_pixels,
-                    *preserve_aspect,
-                )?,
-            };
-            debug!(
-                "Image resized to {}x{}, caching for future renders",
-                resized.width(),
-                resized.height()
-            );
-            self.cached_resized = Some(resized.clone());
-            self.cached_original_resized = Some(resized.clone());
-            self.cached_dimensions = Some((target_width_pixels, target_height_pixels));
-            resized
-        };
+        let grid = self.pipeline.render()?;
+        info!(
+            "Rendering complete: {}x{} braille cells",
+            grid.width(),
+            grid.height()
+        );
+        Ok(grid)
+    }
 
-        // ISSUE #1 FIX: Pass all rendering settings to color pipeline
-        // to ensure consistent behavior across color modes
-        if self.color_mode != ColorMode::Monochrome {
-            info!("Using color rendering pipeline for {:?}", self.color_mode);
-            let cell_width = target_width_pixels as usize / 2;
-            let cell_height = target_height_pixels as usize / 4;
-            return render_image_with_color(
-                &resized,
-                self.color_mode,
-                cell_width,
-                cell_height,
-                self.dithering,
-                self.threshold,
-                self.brightness,
-                self.contrast,
-                self.gamma,
-            );
-        }
+    /// Drops everything derived from the previously loaded image.
+    fn invalidate(&mut self) {
+        self.pipeline.clear();
+        self.source_key = None;
+        self.deferred = None;
+    }
 
-        // Convert to grayscale
-        let mut gray = to_grayscale(&resized);
-        debug!("Converted to grayscale");
+    /// Holds `bytes` undecoded until a cache miss needs them.
+    fn defer(&mut self, bytes: Vec<u8>, path: PathBuf) {
+        info!(
+            "Read {} bytes from {:?}, decoding on cache miss",
+            bytes.len(),
+            path
+        );
+        self.invalidate();
+        self.source_key = Some(CacheKey::new(&bytes));
+        self.deferred = Some((bytes, path));
+    }
 
-        // Apply adjustments (with epsilon for float comparison)
-        const EPSILON: f32 = 0.001;
-        if (self.brightness - 1.0).abs() > EPSILON {
-            gray = adjust_brightness(&gray, self.brightness)?;
-            debug!("Applied brightness adjustment: {}", self.brightness);
-        }
-        if (self.contrast - 1.0).abs() > EPSILON {
-            gray = adjust_contrast(&gray, self.contrast)?;
-            debug!("Applied contrast adjustment: {}", self.contrast);
+    /// Decodes deferred bytes, if any, into the loaded image.
+    fn decode_deferred(&mut self) -> Result<(), DotmaxError> {
+        if let Some((bytes, path)) = self.deferred.take() {
+            let img = load_from_bytes(&bytes).map_err(|e| match e {
+                DotmaxError::ImageLoad { source, .. } => DotmaxError::ImageLoad { path, source },
+                other => other,
+            })?;
+            self.pipeline.set_image(img);
         }
-        if (self.gamma - 1.0).abs() > EPSILON {
-            gray = adjust_gamma(&gray, self.gamma)?;
-            debug!("Applied gamma adjustment: {}", self.gamma);
+        Ok(())
+    }
+
+    /// The cache and the key for the current input and settings, if both
+    /// are available.
+    fn cache_key(&self) -> Option<(RenderCache, CacheKey)> {
+        if self.pipeline.has_stages()
+            || self.pipeline.mode_selector().is_some()
+            || !self.pipeline.viewport().is_full()
+        {
+            return None;
         }
+        let cache = self.cache.clone()?;
+        let (width, height) = self.calculate_target_dimensions();
+        let preserve_aspect = self.preserve_aspect();
+        let key = self
+            .source_key?
+            .options(&RenderOptions::from(self))
+            .size(width as usize, height as usize)
+            .tag(&[
+                u8::from(preserve_aspect),
+                u8::from(self.despeckle.is_some()),
+                self.despeckle.unwrap_or(0),
+            ]);
+        Some((cache, key))
+    }
 
-        // Convert to binary (dithering or threshold)
-        let binary = if self.dithering == DitheringMethod::None {
-            // No dithering - use threshold only
-            if let Some(threshold_value) = self.threshold {
-                debug!(
-                    "Applying manual threshold (no dithering): {}",
-                    threshold_value
-                );
-                apply_threshold(&gray, threshold_value)
-            } else {
-                debug!("Applying automatic Otsu thresholding (no dithering)");
-                // auto_threshold takes DynamicImage, need to convert gray back
-                let gray_dynamic = DynamicImage::ImageLuma8(gray);
-                auto_threshold(&gray_dynamic)
-            }
-        } else {
-            // Dithering enabled - can be combined with manual threshold
-            if let Some(threshold_value) = self.threshold {
-                debug!(
-                    "Applying {:?} dithering with manual threshold: {}",
-                    self.dithering, threshold_value
-                );
-                apply_dithering_with_custom_threshold(&gray, self.dithering, Some(threshold_value))?
-            } else {
-                debug!(
-                    "Applying {:?} dithering with default threshold (127)",
-                    self.dithering
-                );
-                apply_dithering(&gray, self.dithering)?
-            }
+    /// Targets `width × height` cells, keeping the current aspect setting.
+    fn set_cells(&mut self, width: usize, height: usize) {
+        self.resize_mode = ResizeMode::Manual {
+            width,
+            height,
+            preserve_aspect: self.preserve_aspect(),
         };
+    }
 
-        // Map to braille grid
-        let cell_width = target_width_pixels as usize / 2;
-        let cell_height = target_height_pixels as usize / 4;
-        let grid = pixels_to_braille(&binary, cell_width, cell_height)?;
-        info!(
-            "Rendering complete: {}x{} braille cells",
-            cell_width, cell_height
-        );
-
-        Ok(grid)
+    /// Whether the configured resize mode letterboxes to keep the aspect ratio.
+    const fn preserve_aspect(&self) -> bool {
+        match self.resize_mode {
+            ResizeMode::AutoTerminal { preserve_aspect }
+            | ResizeMode::Manual {
+                preserve_aspect, ..
+            } => preserve_aspect,
+        }
     }
 
     /// Helper method to calculate target pixel dimensions based on resize mode.
@@ -983,6 +1223,19 @@ impl Default for ImageRenderer {
     }
 }
 
+impl From<&ImageRenderer> for RenderOptions {
+    fn from(renderer: &ImageRenderer) -> Self {
+        Self {
+            dithering: renderer.dithering,
+            threshold: renderer.threshold,
+            brightness: renderer.brightness,
+            contrast: renderer.contrast,
+            gamma: renderer.gamma,
+            color_mode: renderer.color_mode,
+        }
+    }
+}
+
 /// One-liner convenience function for simple image rendering.
 ///
 /// Loads an image from a file path, automatically resizes it to fit the terminal,
@@ -1043,7 +1296,8 @@ pub fn render_image_simple(path: &Path) -> Result<BrailleGrid, DotmaxError> {
 /// Detects the current terminal size.
 ///
 /// Uses `crossterm::terminal::size()` to get terminal dimensions. If detection fails,
-/// returns a default of 80×24 cells (standard VT100 terminal size).
+/// returns a default of 80×24 cells (standard VT100 terminal size). Margins
+/// reserved with [`set_safe_area`](crate::render::set_safe_area) are excluded.
 ///
 /// # Returns
 ///
@@ -1061,6 +1315,7 @@ pub fn detect_terminal_size() -> (usize, usize) {
     match crossterm::terminal::size() {
         Ok((cols, rows)) => {
             debug!("Detected terminal size: {}x{} cells", cols, rows);
+            let (cols, rows) = crate::render::safe_area().inner_size(cols, rows);
             (cols as usize, rows as usize)
         }
         Err(e) => {
@@ -0,0 +1,407 @@
+//! Picking a [`RenderMode`] from the terminal and the image.
+//!
+//! No single mode suits every image. Braille packs 8 dots into a cell and
+//! keeps fine lines and text legible, but its thin dots carry cell colors
+//! poorly. Sextants hold only 6 pixels per cell, as solid blocks, so a
+//! photo's colors come through at the cost of detail. Sixel beats both
+//! where the terminal draws it, and without Unicode only ASCII is left.
+//!
+//! A [`ModeSelector`] weighs the two by measuring a [`ContentProfile`]:
+//! how colorful the image is and how much fine detail it has. Colorful
+//! images without much detail get sextants; everything else gets braille.
+//! The rules, in order:
+//!
+//! 1. A requested mode ([`ModeSelector::with_mode`], or
+//!    `$DOTMAX_RENDER_MODE` for [`ModeSelector::detect`]) wins, after
+//!    falling back to what the terminal supports
+//!    ([`RenderMode::resolve`]).
+//! 2. Sixel if the terminal draws it.
+//! 3. ASCII without Unicode.
+//! 4. Braille if the [`GlyphPolicy`](crate::render::glyphs::GlyphPolicy)
+//!    says the font lacks braille, since it will lack sextants too.
+//! 5. Sextants for colorful images without much detail, on terminals with
+//!    color.
+//! 6. Otherwise braille.
+//!
+//! Set a selector on a [`Pipeline`](super::Pipeline) or
+//! [`ImageRenderer`](super::ImageRenderer) and the grid is mapped for the
+//! chosen mode; the [`ModeDecision`] shows up in the
+//! [`RenderReport`](super::RenderReport) and should be passed on to
+//! [`TerminalRenderer::set_render_mode`](crate::TerminalRenderer::set_render_mode).
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::{ModeReason, ModeSelector};
+//! use dotmax::render::glyphs::GlyphRepertoire;
+//! use dotmax::render::{RenderMode, TerminalCapabilities};
+//! use image::{DynamicImage, Rgb, RgbImage};
+//!
+//! let caps = TerminalCapabilities::default().with_sixel(false);
+//! let selector = ModeSelector::new(caps).with_glyphs(GlyphRepertoire::Braille);
+//!
+//! // Four flat color bands: all color, no detail
+//! let bands = DynamicImage::ImageRgb8(RgbImage::from_fn(64, 64, |_, y| {
+//!     [Rgb([220, 40, 40]), Rgb([40, 200, 60]), Rgb([40, 60, 220]), Rgb([240, 220, 30])]
+//!         [(y / 16) as usize]
+//! }));
+//! let decision = selector.select(&bands);
+//! assert_eq!(decision.mode, RenderMode::Sextant);
+//! assert_eq!(decision.reason, ModeReason::Colorful);
+//!
+//! // Asking for a mode overrides the choice
+//! let decision = selector.with_mode(Some(RenderMode::Braille)).select(&bands);
+//! assert_eq!(decision.reason, ModeReason::Requested);
+//! ```
+
+use std::fmt;
+
+use image::DynamicImage;
+use tracing::debug;
+
+use crate::render::glyphs::{glyph_policy, GlyphRepertoire};
+use crate::render::{RenderMode, TerminalCapabilities};
+
+/// Largest side, in pixels, that [`ContentProfile::measure`] looks at;
+/// bigger images are scaled down first.
+const SAMPLE_SIZE: u32 = 128;
+
+/// How colorful an image is and how much fine detail it has.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContentProfile {
+    /// Colorfulness from 0 (gray) to 1 (vivid), after Hasler and
+    /// Süsstrunk's metric scaled down by 100. Photos of everyday scenes
+    /// land around 0.2-0.5.
+    pub colorfulness: f32,
+    /// Mean brightness step between neighboring pixels, from 0 (flat) to
+    /// 1 (a one-pixel black and white checkerboard).
+    pub detail: f32,
+}
+
+impl ContentProfile {
+    /// Measures `image`, scaled down to at most 128 pixels a side.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn measure(image: &DynamicImage) -> Self {
+        let sample;
+        let image = if image.width() > SAMPLE_SIZE || image.height() > SAMPLE_SIZE {
+            sample = image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE);
+            &sample
+        } else {
+            image
+        };
+        let rgb = image.to_rgb8();
+        let (width, height) = rgb.dimensions();
+        if width == 0 || height == 0 {
+            return Self {
+                colorfulness: 0.0,
+                detail: 0.0,
+            };
+        }
+
+        // Opponent color axes: red-green and yellow-blue
+        let (mut rg_sum, mut rg_sq, mut yb_sum, mut yb_sq) = (0.0_f64, 0.0, 0.0, 0.0);
+        for pixel in rgb.pixels() {
+            let [r, g, b] = pixel.0.map(f64::from);
+            let rg = r - g;
+            let yb = 0.5_f64.mul_add(r + g, -b);
+            rg_sum += rg;
+            rg_sq += rg * rg;
+            yb_sum += yb;
+            yb_sq += yb * yb;
+        }
+        let count = f64::from(width) * f64::from(height);
+        let (rg_mean, yb_mean) = (rg_sum / count, yb_sum / count);
+        let rg_var = rg_mean.mul_add(-rg_mean, rg_sq / count).max(0.0);
+        let yb_var = yb_mean.mul_add(-yb_mean, yb_sq / count).max(0.0);
+        let colorfulness =
+            0.3_f64.mul_add(rg_mean.hypot(yb_mean), (rg_var + yb_var).sqrt()) / 100.0;
+
+        let luma = image.to_luma8();
+        let (mut steps, mut total) = (0_u64, 0_u64);
+        for (x, y, pixel) in luma.enumerate_pixels() {
+            let value = i32::from(pixel.0[0]);
+            for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                if nx < width && ny < height {
+                    total += u64::from(value.abs_diff(i32::from(luma.get_pixel(nx, ny).0[0])));
+                    steps += 1;
+                }
+            }
+        }
+        let detail = if steps == 0 {
+            0.0
+        } else {
+            total as f64 / steps as f64 / 255.0
+        };
+
+        Self {
+            colorfulness: colorfulness.min(1.0) as f32,
+            detail: detail as f32,
+        }
+    }
+}
+
+/// Why a [`ModeSelector`] picked the mode it did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModeReason {
+    /// The mode was asked for, then resolved against the terminal
+    Requested,
+    /// The terminal draws Sixel graphics
+    Sixel,
+    /// The terminal can't show Unicode
+    NoUnicode,
+    /// The font lacks braille, so sextants are out too
+    LimitedGlyphs,
+    /// Colorful content without much detail: sextants
+    Colorful,
+    /// Colorful content, but too detailed for sextants: braille
+    Detailed,
+    /// Little color to show: braille
+    Monochrome,
+}
+
+impl ModeReason {
+    /// A short description, as shown in reports.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Requested => "requested",
+            Self::Sixel => "terminal draws sixel",
+            Self::NoUnicode => "no unicode",
+            Self::LimitedGlyphs => "font lacks braille",
+            Self::Colorful => "colorful content",
+            Self::Detailed => "detailed content",
+            Self::Monochrome => "little color",
+        }
+    }
+}
+
+/// The mode a [`ModeSelector`] picked, and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModeDecision {
+    /// The mode to render in
+    pub mode: RenderMode,
+    /// Why
+    pub reason: ModeReason,
+}
+
+/// `sextant (colorful content)`
+impl fmt::Display for ModeDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.mode.name(), self.reason.description())
+    }
+}
+
+/// Picks a [`RenderMode`] for each image, following the rules in the
+/// [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeSelector {
+    capabilities: TerminalCapabilities,
+    glyphs: GlyphRepertoire,
+    requested: Option<RenderMode>,
+}
+
+impl ModeSelector {
+    /// Images at least this colorful are candidates for sextants.
+    pub const COLORFUL: f32 = 0.25;
+
+    /// Images with at least this much detail stay in braille.
+    pub const DETAILED: f32 = 0.12;
+
+    /// A selector for a terminal with `capabilities`, using the process's
+    /// [`glyph_policy`] and choosing freely.
+    #[must_use]
+    pub fn new(capabilities: TerminalCapabilities) -> Self {
+        Self {
+            capabilities,
+            glyphs: glyph_policy().repertoire(),
+            requested: None,
+        }
+    }
+
+    /// A selector for the current terminal, honoring `$DOTMAX_RENDER_MODE`
+    /// (`braille`, `sextant`, `ascii`, or `sixel`) as a requested mode.
+    #[must_use]
+    pub fn detect() -> Self {
+        let requested = std::env::var("DOTMAX_RENDER_MODE")
+            .ok()
+            .and_then(|name| RenderMode::from_name(&name));
+        Self::new(TerminalCapabilities::default()).with_mode(requested)
+    }
+
+    /// Always picks `mode`, as far as the terminal allows, or chooses
+    /// freely again with `None`.
+    #[must_use]
+    pub const fn with_mode(mut self, mode: Option<RenderMode>) -> Self {
+        self.requested = mode;
+        self
+    }
+
+    /// Assumes the font covers `glyphs` instead of what the process's
+    /// [`glyph_policy`] says.
+    #[must_use]
+    pub const fn with_glyphs(mut self, glyphs: GlyphRepertoire) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// The terminal capabilities choices are made for.
+    #[must_use]
+    pub const fn capabilities(&self) -> &TerminalCapabilities {
+        &self.capabilities
+    }
+
+    /// The requested mode, if any.
+    #[must_use]
+    pub const fn requested(&self) -> Option<RenderMode> {
+        self.requested
+    }
+
+    /// Picks the mode for `image`.
+    #[must_use]
+    pub fn select(&self, image: &DynamicImage) -> ModeDecision {
+        let caps = &self.capabilities;
+        if let Some(mode) = self.requested {
+            return ModeDecision {
+                mode: mode.resolve(caps),
+                reason: ModeReason::Requested,
+            };
+        }
+        let (mode, reason) = if caps.supports_sixel {
+            (RenderMode::Sixel, ModeReason::Sixel)
+        } else if !caps.supports_unicode {
+            (RenderMode::Ascii, ModeReason::NoUnicode)
+        } else if self.glyphs != GlyphRepertoire::Braille {
+            (RenderMode::Braille, ModeReason::LimitedGlyphs)
+        } else {
+            let profile = ContentProfile::measure(image);
+            debug!(
+                colorfulness = profile.colorfulness,
+                detail = profile.detail,
+                "Measured image content"
+            );
+            if !caps.supports_color || profile.colorfulness < Self::COLORFUL {
+                (RenderMode::Braille, ModeReason::Monochrome)
+            } else if profile.detail >= Self::DETAILED {
+                (RenderMode::Braille, ModeReason::Detailed)
+            } else {
+                (RenderMode::Sextant, ModeReason::Colorful)
+            }
+        };
+        ModeDecision { mode, reason }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    fn selector() -> ModeSelector {
+        let caps = TerminalCapabilities {
+            supports_color: true,
+            supports_truecolor: true,
+            supports_unicode: true,
+            terminal_type: crate::render::TerminalType::Unknown,
+            supports_sixel: false,
+        };
+        ModeSelector::new(caps).with_glyphs(GlyphRepertoire::Braille)
+    }
+
+    fn image(pixel: impl Fn(u32, u32) -> [u8; 3]) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(64, 64, |x, y| Rgb(pixel(x, y))))
+    }
+
+    #[test]
+    fn test_profile_measures_color_and_detail() {
+        let gray = ContentProfile::measure(&image(|_, _| [128, 128, 128]));
+        assert!(gray.colorfulness.abs() < 1e-6 && gray.detail.abs() < 1e-6);
+
+        let checker = ContentProfile::measure(&image(|x, y| [255 * ((x + y) % 2) as u8; 3]));
+        assert!(checker.colorfulness.abs() < 1e-6);
+        assert!((checker.detail - 1.0).abs() < 1e-6);
+
+        let halves =
+            ContentProfile::measure(&image(
+                |x, _| {
+                    if x < 32 {
+                        [255, 0, 0]
+                    } else {
+                        [0, 0, 255]
+                    }
+                },
+            ));
+        assert!(halves.colorfulness > ModeSelector::COLORFUL);
+        assert!(halves.detail < 0.01);
+    }
+
+    #[test]
+    fn test_select_by_content_and_terminal() {
+        let selector = selector();
+        let stripes = image(|x, _| if x < 32 { [230, 30, 30] } else { [30, 30, 230] });
+        let noise = image(|x, y| {
+            if (x * 7 + y * 13) % 5 < 2 {
+                [255, 220, 40]
+            } else {
+                [20, 30, 160]
+            }
+        });
+        let gray = image(|x, _| [(x * 4) as u8; 3]);
+
+        let pick = |selector: ModeSelector, image: &DynamicImage| {
+            let decision = selector.select(image);
+            (decision.mode, decision.reason)
+        };
+        assert_eq!(
+            pick(selector, &stripes),
+            (RenderMode::Sextant, ModeReason::Colorful)
+        );
+        assert_eq!(
+            pick(selector, &noise),
+            (RenderMode::Braille, ModeReason::Detailed)
+        );
+        assert_eq!(
+            pick(selector, &gray),
+            (RenderMode::Braille, ModeReason::Monochrome)
+        );
+        assert_eq!(
+            pick(selector.with_glyphs(GlyphRepertoire::Shades), &stripes),
+            (RenderMode::Braille, ModeReason::LimitedGlyphs)
+        );
+
+        let mut caps = *selector.capabilities();
+        caps.supports_sixel = true;
+        assert_eq!(
+            pick(
+                ModeSelector {
+                    capabilities: caps,
+                    ..selector
+                },
+                &stripes
+            ),
+            (RenderMode::Sixel, ModeReason::Sixel)
+        );
+        caps.supports_unicode = false;
+        caps.supports_sixel = false;
+        let ascii_only = ModeSelector {
+            capabilities: caps,
+            ..selector
+        };
+        assert_eq!(
+            pick(ascii_only, &stripes),
+            (RenderMode::Ascii, ModeReason::NoUnicode)
+        );
+        // A request still falls back to what the terminal supports
+        assert_eq!(
+            pick(ascii_only.with_mode(Some(RenderMode::Sextant)), &stripes),
+            (RenderMode::Ascii, ModeReason::Requested)
+        );
+        assert_eq!(
+            selector
+                .with_mode(Some(RenderMode::Sextant))
+                .select(&gray)
+                .to_string(),
+            "sextant (requested)"
+        );
+    }
+}
@@ -0,0 +1,971 @@
+//! Incremental rendering with per-stage caching.
+//!
+//! A [`Pipeline`] runs the same steps as [`ImageRenderer`](super::ImageRenderer)
+//! (load → resize → adjust → dither → map → color) but keeps every
+//! intermediate result. Each setter marks only the stages its parameter
+//! feeds as stale, and [`Pipeline::render`] reruns from the earliest stale
+//! stage, so an interactive tuning loop pays for what it changed:
+//!
+//! | Change                      | Reruns from               |
+//! |-----------------------------|---------------------------|
+//! | source image                | [`PipelineStage::Load`]   |
+//! | size or aspect handling     | [`PipelineStage::Resize`] |
+//! | viewport                    | [`PipelineStage::Resize`] |
+//! | brightness, contrast, gamma | [`PipelineStage::Adjust`] |
+//! | dithering method, threshold | [`PipelineStage::Dither`] |
+//! | despeckling                 | [`PipelineStage::Map`]    |
+//! | color mode                  | [`PipelineStage::Color`]  |
+//! | mode selector               | [`PipelineStage::Load`]   |
+//!
+//! Setting a parameter to the value it already has invalidates nothing.
+//! A zoomed [`Viewport`] keeps the image scaled to its zoom level apart
+//! from the resize stage's output, so panning only cuts a new window out
+//! of it rather than resampling.
+//! Custom [`Stage`]s run inside the built-in stage that follows their
+//! [`InsertionPoint`], so adding one at [`InsertionPoint::AfterThreshold`]
+//! reruns from [`PipelineStage::Map`].
+//! SVGs and scene snapshots go in as rasterized images, so re-rendering one
+//! at a new brightness or dithering method skips rasterizing and resizing.
+//!
+//! With [`Pipeline::set_reporting`] on, each render also leaves a
+//! [`RenderReport`] of what every stage cost.
+//!
+//! A [`ModeSelector`] set with [`Pipeline::set_mode_selector`] picks a
+//! [`RenderMode`] once per source image. When it picks sextants, the image
+//! is resized to 2×3 pixels per cell and mapped with
+//! [`pixels_to_sextants`](super::pixels_to_sextants); every other mode gets
+//! the usual braille grid.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::{Pipeline, PipelineStage};
+//! use image::{DynamicImage, RgbImage};
+//!
+//! # fn main() -> Result<(), dotmax::DotmaxError> {
+//! let mut pipeline = Pipeline::new();
+//! pipeline.set_image(DynamicImage::ImageRgb8(RgbImage::new(64, 64)));
+//! pipeline.set_size(16, 8, true)?;
+//! let first = pipeline.render()?;
+//!
+//! // Only adjust, dither, map and color run again
+//! let brighter = pipeline.options().brightness(1.4);
+//! pipeline.set_options(brighter)?;
+//! assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Adjust));
+//! let second = pipeline.render()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use image::{DynamicImage, GrayImage};
+use tracing::debug;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::color_mode::{extract_cell_colors, extract_sextant_colors, rgb_to_grayscale_intensity};
+use super::mode_select::{ModeDecision, ModeSelector};
+use super::report::RenderReport;
+use super::roi::RoiRect;
+use super::stage::{run_stages, CustomStage, FrameData, InsertionPoint, Stage};
+use super::viewport::Viewport;
+use super::{
+    adjust_brightness, adjust_contrast, adjust_gamma, apply_dithering,
+    apply_dithering_with_custom_threshold, apply_threshold, auto_threshold, pixels_to_braille,
+    pixels_to_sextants, resize_to_dimensions, to_grayscale, BinaryImage, ColorMode,
+    ColorSamplingStrategy, DitheringMethod, RenderOptions,
+};
+use crate::render::RenderMode;
+use crate::{BrailleGrid, Color, DotmaxError};
+
+/// A step of the [`Pipeline`], in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PipelineStage {
+    /// Take in the source image
+    Load,
+    /// Scale to the target size in dots
+    Resize,
+    /// Convert to grayscale and apply brightness, contrast, and gamma
+    Adjust,
+    /// Reduce to black and white by dithering or thresholding
+    Dither,
+    /// Map pixels to braille dots and despeckle
+    Map,
+    /// Color the cells, unless the color mode is monochrome
+    Color,
+}
+
+impl PipelineStage {
+    /// Every stage, in pipeline order.
+    pub const ALL: [Self; 6] = [
+        Self::Load,
+        Self::Resize,
+        Self::Adjust,
+        Self::Dither,
+        Self::Map,
+        Self::Color,
+    ];
+}
+
+/// An image pipeline that caches each stage's output and reruns only the
+/// stages invalidated since the last [`render`](Self::render).
+///
+/// See the [module documentation](self) for which parameters invalidate
+/// which stages.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    image: Option<DynamicImage>,
+    /// Target size in braille cells
+    width: usize,
+    height: usize,
+    preserve_aspect: bool,
+    options: RenderOptions,
+    despeckle: Option<u8>,
+    stages: Vec<CustomStage>,
+    selector: Option<ModeSelector>,
+    /// The selector's pick for the current image
+    decision: Option<ModeDecision>,
+    /// Earliest stage whose cached output is out of date
+    stale: Option<PipelineStage>,
+    viewport: Viewport,
+    /// The whole image scaled for a zoomed viewport, with the size it was
+    /// scaled to and whether that kept the aspect ratio
+    scaled: Option<((u32, u32, bool), DynamicImage)>,
+    /// The part of the image the last render showed
+    visible: Option<RoiRect>,
+    resized: Option<DynamicImage>,
+    gray: Option<GrayImage>,
+    binary: Option<BinaryImage>,
+    dots: Option<BrailleGrid>,
+    output: Option<BrailleGrid>,
+    reporting: bool,
+    report: Option<RenderReport>,
+}
+
+impl Pipeline {
+    /// An empty pipeline targeting 80×24 cells with the aspect ratio
+    /// preserved and default [`RenderOptions`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            image: None,
+            width: 80,
+            height: 24,
+            preserve_aspect: true,
+            options: RenderOptions::new(),
+            despeckle: None,
+            stages: Vec::new(),
+            selector: None,
+            decision: None,
+            stale: Some(PipelineStage::Load),
+            viewport: Viewport::FULL,
+            scaled: None,
+            visible: None,
+            resized: None,
+            gray: None,
+            binary: None,
+            dots: None,
+            output: None,
+            reporting: false,
+            report: None,
+        }
+    }
+
+    /// Replaces the source image. Every stage reruns on the next render.
+    pub fn set_image(&mut self, image: DynamicImage) {
+        self.image = Some(image);
+        self.invalidate(PipelineStage::Load);
+    }
+
+    /// The source image, if one is set.
+    #[must_use]
+    pub const fn image(&self) -> Option<&DynamicImage> {
+        self.image.as_ref()
+    }
+
+    /// Drops the source image and every cached stage output. Settings and
+    /// custom stages are kept.
+    pub fn clear(&mut self) {
+        self.image = None;
+        self.scaled = None;
+        self.visible = None;
+        self.resized = None;
+        self.gray = None;
+        self.binary = None;
+        self.dots = None;
+        self.output = None;
+        self.report = None;
+        self.decision = None;
+        self.invalidate(PipelineStage::Load);
+    }
+
+    /// Sets the target size in braille cells, and whether to letterbox to
+    /// keep the image's aspect ratio rather than stretch it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if `width` or `height` is
+    /// 0 or exceeds 10,000; the pipeline is left unchanged.
+    pub fn set_size(
+        &mut self,
+        width: usize,
+        height: usize,
+        preserve_aspect: bool,
+    ) -> Result<(), DotmaxError> {
+        if width == 0 || height == 0 || width > 10_000 || height > 10_000 {
+            return Err(DotmaxError::InvalidDimensions { width, height });
+        }
+        if (width, height, preserve_aspect) != (self.width, self.height, self.preserve_aspect) {
+            self.width = width;
+            self.height = height;
+            self.preserve_aspect = preserve_aspect;
+            self.invalidate(PipelineStage::Resize);
+        }
+        Ok(())
+    }
+
+    /// The target size in cells and whether the aspect ratio is preserved.
+    #[must_use]
+    pub const fn size(&self) -> (usize, usize, bool) {
+        (self.width, self.height, self.preserve_aspect)
+    }
+
+    /// Shows the part of the image `viewport` covers, scaled to the target
+    /// size.
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        if viewport != self.viewport {
+            self.viewport = viewport;
+            self.invalidate(PipelineStage::Resize);
+        }
+    }
+
+    /// The current viewport.
+    #[must_use]
+    pub const fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+
+    /// The part of the image the last render showed, in normalized image
+    /// coordinates, for mapping positions on the grid back to the image.
+    /// Wider than the viewport suggests along an axis the image doesn't
+    /// fill, since the whole of it is shown there.
+    #[must_use]
+    pub const fn visible_region(&self) -> Option<RoiRect> {
+        self.visible
+    }
+
+    /// Replaces the render options, invalidating from the earliest stage
+    /// that any changed option feeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`]; the pipeline is left unchanged.
+    #[allow(clippy::float_cmp)] // Any change at all must rerun the stage
+    pub fn set_options(&mut self, options: RenderOptions) -> Result<(), DotmaxError> {
+        options.validate()?;
+        let old = self.options;
+        if options.brightness != old.brightness
+            || options.contrast != old.contrast
+            || options.gamma != old.gamma
+        {
+            self.invalidate(PipelineStage::Adjust);
+        }
+        if options.dithering != old.dithering || options.threshold != old.threshold {
+            self.invalidate(PipelineStage::Dither);
+        }
+        if options.color_mode != old.color_mode {
+            self.invalidate(PipelineStage::Color);
+        }
+        self.options = options;
+        Ok(())
+    }
+
+    /// The current render options.
+    #[must_use]
+    pub const fn options(&self) -> RenderOptions {
+        self.options
+    }
+
+    /// Clears dots with fewer than `min_neighbors` set neighbors after
+    /// mapping (see [`crate::analysis::despeckle`]), or turns that off with
+    /// `None`.
+    pub fn set_despeckle(&mut self, min_neighbors: Option<u8>) {
+        if min_neighbors != self.despeckle {
+            self.despeckle = min_neighbors;
+            self.invalidate(PipelineStage::Map);
+        }
+    }
+
+    /// The despeckle setting.
+    #[must_use]
+    pub const fn despeckle(&self) -> Option<u8> {
+        self.despeckle
+    }
+
+    /// Adds a custom stage at `at`, after any already there.
+    pub fn add_stage(&mut self, at: InsertionPoint, stage: impl Stage + 'static) {
+        self.stages.push(CustomStage {
+            at,
+            stage: Arc::new(stage),
+        });
+        self.invalidate(runs_in(at));
+    }
+
+    /// Removes the custom stages at `at`.
+    pub fn clear_stages(&mut self, at: InsertionPoint) {
+        let before = self.stages.len();
+        self.stages.retain(|custom| custom.at != at);
+        if self.stages.len() != before {
+            self.invalidate(runs_in(at));
+        }
+    }
+
+    /// Whether any custom stages are set.
+    #[must_use]
+    pub fn has_stages(&self) -> bool {
+        !self.stages.is_empty()
+    }
+
+    /// Marks `stage` and everything after it as needing a rerun, e.g. after
+    /// changing a setting held by a custom [`Stage`].
+    pub fn invalidate(&mut self, stage: PipelineStage) {
+        self.stale = Some(self.stale.map_or(stage, |stale| stale.min(stage)));
+    }
+
+    /// Sets the [`ModeSelector`] that picks a render mode for each image,
+    /// or goes back to plain braille with `None`. Every stage reruns on the
+    /// next render.
+    pub fn set_mode_selector(&mut self, selector: Option<ModeSelector>) {
+        if selector != self.selector {
+            self.selector = selector;
+            self.decision = None;
+            self.invalidate(PipelineStage::Load);
+        }
+    }
+
+    /// The mode selector, if one is set.
+    #[must_use]
+    pub const fn mode_selector(&self) -> Option<&ModeSelector> {
+        self.selector.as_ref()
+    }
+
+    /// The mode the selector picked for the last render, which the grid it
+    /// returned was mapped for. `None` without a selector.
+    #[must_use]
+    pub const fn mode_decision(&self) -> Option<ModeDecision> {
+        self.decision
+    }
+
+    /// Turns [`RenderReport`]s on or off. Off by default.
+    pub fn set_reporting(&mut self, enabled: bool) {
+        self.reporting = enabled;
+        if !enabled {
+            self.report = None;
+        }
+    }
+
+    /// The report from the last successful [`render`](Self::render), if
+    /// reporting was on for it.
+    #[must_use]
+    pub const fn last_report(&self) -> Option<&RenderReport> {
+        self.report.as_ref()
+    }
+
+    /// Forgets the last report, for callers that produced a grid without
+    /// rendering.
+    pub(crate) fn clear_report(&mut self) {
+        self.report = None;
+    }
+
+    /// The earliest stage the next [`render`](Self::render) will rerun, or
+    /// `None` if it will return the cached grid.
+    #[must_use]
+    pub const fn stale_stage(&self) -> Option<PipelineStage> {
+        self.stale
+    }
+
+    /// Renders the source image, rerunning only the stale stages.
+    ///
+    /// The result matches what [`ImageRenderer`](super::ImageRenderer) gives
+    /// for the same image and settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if no image is set,
+    /// [`DotmaxError::StageOutputMismatch`] if a custom stage returns the
+    /// wrong kind of data, or any error from a built-in stage. A failed stage
+    /// is rerun on the next render.
+    pub fn render(&mut self) -> Result<BrailleGrid, DotmaxError> {
+        let image = self
+            .image
+            .as_ref()
+            .ok_or_else(|| DotmaxError::InvalidParameter {
+                parameter_name: "image".to_string(),
+                value: "None".to_string(),
+                min: "Must set image first".to_string(),
+                max: "set image".to_string(),
+            })?;
+        self.report = None;
+        let mut report = self.reporting.then(RenderReport::default);
+        let mut record = |stage, time, size| {
+            if let Some(report) = report.as_mut() {
+                report.record(stage, time, size);
+            }
+        };
+        let stale = self.stale.take();
+        debug!("Rendering pipeline, stale from {:?}", stale);
+        let mut rerun = stale == Some(PipelineStage::Load);
+        let from = |stage: PipelineStage| stale.is_some_and(|s| s <= stage);
+
+        if rerun {
+            self.decision = self.selector.map(|selector| selector.select(image));
+            if let Some(decision) = self.decision {
+                debug!("Render mode: {}", decision);
+            }
+        }
+        let sextants = self
+            .decision
+            .is_some_and(|decision| decision.mode == RenderMode::Sextant);
+        let pixels_per_row = if sextants { 3 } else { 4 };
+
+        rerun |= from(PipelineStage::Resize);
+        if stale == Some(PipelineStage::Load) {
+            self.scaled = None;
+        }
+        let target = (
+            (self.width * 2) as u32,
+            (self.height * pixels_per_row) as u32,
+        );
+        let (viewport, scaled, visible) = (self.viewport, &mut self.scaled, &mut self.visible);
+        let (resized, time) = refresh(&mut self.resized, &mut rerun, || {
+            if viewport.is_full() {
+                *visible = Some(RoiRect::FULL);
+                resize_to_dimensions(image, target.0, target.1, self.preserve_aspect)
+            } else {
+                zoomed_window(
+                    image,
+                    scaled,
+                    target,
+                    self.preserve_aspect,
+                    viewport,
+                    visible,
+                )
+            }
+        })?;
+        record(
+            PipelineStage::Resize,
+            time,
+            pixels(resized.width(), resized.height()),
+        );
+
+        let options = &self.options;
+        let stages = &self.stages;
+        rerun |= from(PipelineStage::Adjust);
+        let (gray, time) = refresh(&mut self.gray, &mut rerun, || adjust(resized, options))?;
+        record(
+            PipelineStage::Adjust,
+            time,
+            pixels(gray.width(), gray.height()),
+        );
+
+        rerun |= from(PipelineStage::Dither);
+        let (binary, time) = refresh(&mut self.binary, &mut rerun, || {
+            let at = InsertionPoint::BeforeDither;
+            let gray = run_stages(stages, at, gray, FrameData::Gray, FrameData::into_gray)?;
+            dither(&gray, options)
+        })?;
+        record(
+            PipelineStage::Dither,
+            time,
+            pixels(binary.width, binary.height),
+        );
+
+        rerun |= from(PipelineStage::Map);
+        let despeckle = self.despeckle;
+        let (dots, time) = refresh(&mut self.dots, &mut rerun, || {
+            let at = InsertionPoint::AfterThreshold;
+            let binary = run_stages(
+                stages,
+                at,
+                binary,
+                FrameData::Binary,
+                FrameData::into_binary,
+            )?;
+            let grid = if sextants {
+                pixels_to_sextants(&binary)?
+            } else {
+                pixels_to_braille(&binary, 0, 0)?
+            };
+            Ok(match despeckle {
+                Some(min_neighbors) => crate::analysis::despeckle(&grid, min_neighbors),
+                None => grid,
+            })
+        })?;
+        record(PipelineStage::Map, time, (dots.width(), dots.height()));
+
+        rerun |= from(PipelineStage::Color);
+        let (output, time) = refresh(&mut self.output, &mut rerun, || {
+            let at = InsertionPoint::BeforeColor;
+            let dots = run_stages(stages, at, dots, FrameData::Dots, FrameData::into_dots)?;
+            colorize(&dots, resized, options.color_mode, sextants)
+        })?;
+        record(
+            PipelineStage::Color,
+            time,
+            (output.width(), output.height()),
+        );
+        let output = output.clone();
+        self.report = report.map(|report| RenderReport {
+            mode: self.decision,
+            ..report
+        });
+        Ok(output)
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The built-in stage that custom stages at `at` run inside.
+const fn runs_in(at: InsertionPoint) -> PipelineStage {
+    match at {
+        InsertionPoint::BeforeDither => PipelineStage::Dither,
+        InsertionPoint::AfterThreshold => PipelineStage::Map,
+        InsertionPoint::BeforeColor => PipelineStage::Color,
+    }
+}
+
+/// Returns the cached value in `slot`, or recomputes it when `rerun` is set
+/// or the slot is empty, along with how long a recompute took. A recompute
+/// sets `rerun` so later stages follow. The slot is emptied first, so a
+/// failed stage isn't reused.
+fn refresh<'a, T>(
+    slot: &'a mut Option<T>,
+    rerun: &mut bool,
+    compute: impl FnOnce() -> Result<T, DotmaxError>,
+) -> Result<(&'a T, Option<Duration>), DotmaxError> {
+    if *rerun || slot.is_none() {
+        *rerun = true;
+        *slot = None;
+        let started = Instant::now();
+        let value = compute()?;
+        return Ok((slot.insert(value), Some(started.elapsed())));
+    }
+    Ok((
+        slot.get_or_insert_with(|| unreachable!("slot checked above")),
+        None,
+    ))
+}
+
+/// The window of `image` that `viewport` shows, at up to `target` pixels,
+/// cut from `image` scaled to `target` times the zoom. The scaled image is
+/// kept in `scaled` for the next render at the same zoom. Sets `visible`
+/// to the window in normalized coordinates.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn zoomed_window(
+    image: &DynamicImage,
+    scaled: &mut Option<((u32, u32, bool), DynamicImage)>,
+    target: (u32, u32),
+    preserve_aspect: bool,
+    viewport: Viewport,
+    visible: &mut Option<RoiRect>,
+) -> Result<DynamicImage, DotmaxError> {
+    let zoom = viewport.zoom_level();
+    let size = (
+        (target.0 as f32 * zoom).round() as u32,
+        (target.1 as f32 * zoom).round() as u32,
+        preserve_aspect,
+    );
+    let image: &DynamicImage = match scaled {
+        Some((key, image)) if *key == size => image,
+        _ => {
+            *scaled = None;
+            let image = resize_to_dimensions(image, size.0, size.1, preserve_aspect)?;
+            &scaled.insert((size, image)).1
+        }
+    };
+
+    // Start and length of the window along one axis, kept on the image
+    let window = |length: u32, target: u32, center: f32| {
+        let span = length.min(target);
+        let start = center.mul_add(length as f32, -(span as f32 / 2.0)).round();
+        (start.clamp(0.0, (length - span) as f32) as u32, span)
+    };
+    let (center_x, center_y) = viewport.center();
+    let (x, width) = window(image.width(), target.0, center_x);
+    let (y, height) = window(image.height(), target.1, center_y);
+    *visible = Some(RoiRect {
+        x: x as f32 / image.width() as f32,
+        y: y as f32 / image.height() as f32,
+        width: width as f32 / image.width() as f32,
+        height: height as f32 / image.height() as f32,
+    });
+    Ok(DynamicImage::ImageRgba8(
+        image::imageops::crop_imm(image, x, y, width, height).to_image(),
+    ))
+}
+
+/// Image dimensions as a report size.
+const fn pixels(width: u32, height: u32) -> (usize, usize) {
+    (width as usize, height as usize)
+}
+
+/// Grayscale conversion plus any brightness, contrast, and gamma that
+/// aren't neutral.
+fn adjust(resized: &DynamicImage, options: &RenderOptions) -> Result<GrayImage, DotmaxError> {
+    const EPSILON: f32 = 0.001;
+    let mut gray = to_grayscale(resized);
+    if (options.brightness - 1.0).abs() > EPSILON {
+        gray = adjust_brightness(&gray, options.brightness)?;
+    }
+    if (options.contrast - 1.0).abs() > EPSILON {
+        gray = adjust_contrast(&gray, options.contrast)?;
+    }
+    if (options.gamma - 1.0).abs() > EPSILON {
+        gray = adjust_gamma(&gray, options.gamma)?;
+    }
+    Ok(gray)
+}
+
+/// Dithering, optionally around a manual threshold, or plain thresholding
+/// (Otsu unless a threshold is set) when dithering is off.
+fn dither(gray: &GrayImage, options: &RenderOptions) -> Result<BinaryImage, DotmaxError> {
+    match (options.dithering, options.threshold) {
+        (DitheringMethod::None, Some(threshold)) => Ok(apply_threshold(gray, threshold)),
+        (DitheringMethod::None, None) => {
+            Ok(auto_threshold(&DynamicImage::ImageLuma8(gray.clone())))
+        }
+        (method, Some(threshold)) => {
+            apply_dithering_with_custom_threshold(gray, method, Some(threshold))
+        }
+        (method, None) => apply_dithering(gray, method),
+    }
+}
+
+/// A copy of `dots` with each cell colored from the matching block of
+/// `resized`: 2×3 pixels for `sextants`, 2×4 otherwise.
+fn colorize(
+    dots: &BrailleGrid,
+    resized: &DynamicImage,
+    mode: ColorMode,
+    sextants: bool,
+) -> Result<BrailleGrid, DotmaxError> {
+    let mut grid = dots.clone();
+    if mode == ColorMode::Monochrome {
+        return Ok(grid);
+    }
+    let (width, height) = (grid.width(), grid.height());
+    let colors = if sextants {
+        extract_sextant_colors(resized, width, height, ColorSamplingStrategy::Average)
+    } else {
+        extract_cell_colors(resized, width, height, ColorSamplingStrategy::Average)
+    };
+    for (i, color) in colors.iter().enumerate() {
+        let color = match mode {
+            ColorMode::Grayscale => {
+                let intensity = rgb_to_grayscale_intensity(color);
+                Color::rgb(intensity, intensity, intensity)
+            }
+            ColorMode::Monochrome | ColorMode::TrueColor => *color,
+        };
+        grid.set_cell_color(i % width, i / width, color)?;
+    }
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::ImageRenderer;
+    use image::{Rgba, RgbaImage};
+
+    fn gradient() -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(60, 40, |x, y| {
+            Rgba([(x * 4) as u8, (y * 6) as u8, 128, 255])
+        }))
+    }
+
+    fn pipeline() -> Pipeline {
+        let mut pipeline = Pipeline::new();
+        pipeline.set_image(gradient());
+        pipeline.set_size(20, 10, true).unwrap();
+        pipeline
+    }
+
+    #[test]
+    fn test_parameters_invalidate_their_stage() {
+        let mut pipeline = pipeline();
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Load));
+        pipeline.render().unwrap();
+        assert_eq!(pipeline.stale_stage(), None);
+
+        let options = pipeline.options();
+        pipeline
+            .set_options(options.color_mode(ColorMode::TrueColor))
+            .unwrap();
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Color));
+        pipeline.set_despeckle(Some(1));
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Map));
+        pipeline
+            .set_options(pipeline.options().threshold(Some(100)))
+            .unwrap();
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Dither));
+        pipeline.set_options(pipeline.options().gamma(0.8)).unwrap();
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Adjust));
+        pipeline.set_size(20, 12, true).unwrap();
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Resize));
+        pipeline.render().unwrap();
+
+        // Unchanged values invalidate nothing
+        pipeline.set_size(20, 12, true).unwrap();
+        pipeline.set_options(pipeline.options()).unwrap();
+        pipeline.set_despeckle(Some(1));
+        assert_eq!(pipeline.stale_stage(), None);
+    }
+
+    #[test]
+    fn test_rejected_settings_leave_pipeline_unchanged() {
+        let mut pipeline = pipeline();
+        pipeline.render().unwrap();
+        assert!(pipeline.set_size(0, 10, true).is_err());
+        assert!(pipeline.set_options(pipeline.options().gamma(9.0)).is_err());
+        assert_eq!(pipeline.size(), (20, 10, true));
+        assert_eq!(pipeline.stale_stage(), None);
+    }
+
+    #[test]
+    fn test_render_without_image_fails() {
+        assert!(Pipeline::new().render().is_err());
+        let mut pipeline = pipeline();
+        pipeline.clear();
+        assert!(pipeline.render().is_err());
+    }
+
+    #[test]
+    fn test_matches_image_renderer_after_changes() {
+        let mut pipeline = pipeline();
+        pipeline.render().unwrap();
+
+        for options in [
+            RenderOptions::new()
+                .brightness(1.3)
+                .dithering(DitheringMethod::Atkinson),
+            RenderOptions::new().dithering(DitheringMethod::None),
+            RenderOptions::new()
+                .dithering(DitheringMethod::Bayer)
+                .threshold(Some(90))
+                .color_mode(ColorMode::TrueColor),
+            RenderOptions::new().color_mode(ColorMode::Grayscale),
+        ] {
+            pipeline.set_options(options).unwrap();
+            pipeline.set_despeckle(Some(1));
+            let incremental = pipeline.render().unwrap();
+
+            let expected = ImageRenderer::new()
+                .load_from_rgba(gradient().to_rgba8())
+                .resize(20, 10, true)
+                .unwrap()
+                .render_options(options)
+                .unwrap()
+                .despeckle(1)
+                .render()
+                .unwrap();
+            assert_eq!(incremental.to_unicode_grid(), expected.to_unicode_grid());
+            for y in 0..expected.height() {
+                for x in 0..expected.width() {
+                    assert_eq!(incremental.get_color(x, y), expected.get_color(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_stages() {
+        let mut pipeline = pipeline();
+        let plain = pipeline.render().unwrap();
+
+        let invert = |data: FrameData| match data {
+            FrameData::Binary(mut binary) => {
+                binary.pixels.iter_mut().for_each(|pixel| *pixel = !*pixel);
+                FrameData::Binary(binary)
+            }
+            other => other,
+        };
+        pipeline.add_stage(InsertionPoint::AfterThreshold, invert);
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Map));
+        let inverted = pipeline.render().unwrap();
+        let (w, h) = (plain.width(), plain.height());
+        assert_eq!((inverted.width(), inverted.height()), (w, h));
+        // 60x40 resized into 40x27 pixels: all but the last cell row are
+        // fully covered, so every dot there flips
+        let covered = w * (h - 1);
+        let patterns = plain
+            .get_raw_patterns()
+            .iter()
+            .zip(inverted.get_raw_patterns());
+        for (a, b) in patterns.take(covered) {
+            assert_eq!(a ^ b, 0xFF);
+        }
+
+        pipeline.add_stage(InsertionPoint::BeforeDither, |data| data);
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Dither));
+        pipeline.clear_stages(InsertionPoint::AfterThreshold);
+        pipeline.clear_stages(InsertionPoint::BeforeDither);
+        assert!(!pipeline.has_stages());
+        assert_eq!(
+            pipeline.render().unwrap().get_raw_patterns(),
+            plain.get_raw_patterns()
+        );
+
+        pipeline.add_stage(InsertionPoint::BeforeColor, |_| {
+            FrameData::Gray(GrayImage::new(1, 1))
+        });
+        assert!(matches!(
+            pipeline.render(),
+            Err(DotmaxError::StageOutputMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_report_marks_cached_stages() {
+        let mut pipeline = pipeline();
+        pipeline.render().unwrap();
+        assert!(pipeline.last_report().is_none());
+
+        pipeline.set_reporting(true);
+        pipeline.render().unwrap();
+        let report = pipeline.last_report().unwrap();
+        let stages: Vec<_> = report.stages.iter().map(|r| r.stage).collect();
+        assert_eq!(stages, &PipelineStage::ALL[1..]);
+        assert!(report.stages.iter().all(|r| r.cached));
+
+        let options = pipeline.options().threshold(Some(90));
+        pipeline.set_options(options).unwrap();
+        pipeline.render().unwrap();
+        let report = pipeline.last_report().unwrap();
+        let cached: Vec<_> = report.stages.iter().map(|r| r.cached).collect();
+        assert_eq!(cached, [true, true, false, false, false]);
+        let map = report.stage(PipelineStage::Map).unwrap();
+        assert_eq!((map.width, map.height), (20, 7));
+
+        pipeline.set_reporting(false);
+        assert!(pipeline.last_report().is_none());
+    }
+
+    #[test]
+    fn test_mode_selector_maps_for_picked_mode() {
+        use crate::image::ModeReason;
+        use crate::render::glyphs::GlyphRepertoire;
+        use crate::render::{TerminalCapabilities, TerminalType};
+
+        let caps = TerminalCapabilities {
+            supports_color: true,
+            supports_truecolor: true,
+            supports_unicode: true,
+            terminal_type: TerminalType::Unknown,
+            supports_sixel: false,
+        };
+        let selector = ModeSelector::new(caps).with_glyphs(GlyphRepertoire::Braille);
+        let halves = RgbaImage::from_fn(40, 24, |x, _| {
+            if x < 20 {
+                Rgba([230, 30, 30, 255])
+            } else {
+                Rgba([30, 30, 230, 255])
+            }
+        });
+        let mut pipeline = Pipeline::new();
+        pipeline.set_image(DynamicImage::ImageRgba8(halves));
+        pipeline.set_size(10, 4, false).unwrap();
+        let options = RenderOptions::new()
+            .dithering(DitheringMethod::None)
+            .threshold(Some(0))
+            .color_mode(ColorMode::TrueColor);
+        pipeline.set_options(options).unwrap();
+        pipeline.set_reporting(true);
+        let braille = pipeline.render().unwrap();
+        assert_eq!(pipeline.mode_decision(), None);
+
+        pipeline.set_mode_selector(Some(selector));
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Load));
+        let sextants = pipeline.render().unwrap();
+        let decision = pipeline.mode_decision().unwrap();
+        assert_eq!(decision.mode, RenderMode::Sextant);
+        assert_eq!(decision.reason, ModeReason::Colorful);
+        assert_eq!(pipeline.last_report().unwrap().mode, Some(decision));
+        let resize = pipeline.last_report().unwrap().stage(PipelineStage::Resize);
+        assert_eq!(resize.map(|r| (r.width, r.height)), Some((20, 12)));
+
+        // Full cells in both, but only braille uses dot row 3
+        assert_eq!(braille.get_raw_patterns()[0], 0xFF);
+        assert_eq!(sextants.get_raw_patterns()[0], 0b0011_1111);
+        assert_eq!(sextants.get_color(0, 0), Some(Color::rgb(230, 30, 30)));
+
+        // Asking for braille maps for braille again
+        pipeline.set_mode_selector(Some(selector.with_mode(Some(RenderMode::Braille))));
+        let again = pipeline.render().unwrap();
+        assert_eq!(again.get_raw_patterns(), braille.get_raw_patterns());
+    }
+
+    #[test]
+    fn test_viewport_pans_without_rescaling() {
+        let mut pipeline = pipeline();
+        pipeline.render().unwrap();
+        assert_eq!(pipeline.visible_region(), Some(RoiRect::FULL));
+
+        pipeline.set_viewport(Viewport::FULL.zoom(2.0));
+        assert_eq!(pipeline.stale_stage(), Some(PipelineStage::Resize));
+        let zoomed = pipeline.render().unwrap();
+        assert_eq!(zoomed.dimensions(), (20, 10));
+        let visible = pipeline.visible_region().unwrap();
+        assert_eq!((visible.x, visible.width), (0.25, 0.5));
+        let scaled = pipeline.scaled.as_ref().unwrap().1.as_bytes().as_ptr();
+
+        pipeline.set_viewport(pipeline.viewport().pan(0.5, 0.0));
+        pipeline.render().unwrap();
+        assert_eq!(pipeline.visible_region().unwrap().x, 0.5);
+        assert_eq!(
+            pipeline.scaled.as_ref().unwrap().1.as_bytes().as_ptr(),
+            scaled
+        );
+
+        // Same viewport again invalidates nothing
+        pipeline.set_viewport(pipeline.viewport());
+        assert_eq!(pipeline.stale_stage(), None);
+    }
+
+    #[test]
+    fn test_viewport_shows_only_its_region() {
+        let image = RgbaImage::from_fn(80, 40, |x, _| {
+            let value = if x < 40 { 0 } else { 255 };
+            Rgba([value, value, value, 255])
+        });
+        let mut pipeline = Pipeline::new();
+        pipeline.set_image(DynamicImage::ImageRgba8(image));
+        pipeline.set_size(20, 10, true).unwrap();
+        let options = RenderOptions::new()
+            .dithering(DitheringMethod::None)
+            .threshold(Some(128));
+        pipeline.set_options(options).unwrap();
+        let full = pipeline.render().unwrap();
+        let patterns = full.get_raw_patterns();
+        assert!(patterns.contains(&0) && patterns.contains(&0xFF));
+
+        pipeline.set_viewport(Viewport::crop(RoiRect {
+            x: 0.0,
+            y: 0.25,
+            width: 0.5,
+            height: 0.5,
+        }));
+        let left = pipeline.render().unwrap();
+        let patterns = left.get_raw_patterns();
+        assert!(patterns.iter().all(|&p| p == patterns[0]));
+    }
+}
@@ -0,0 +1,198 @@
+//! Render settings shared by every image pipeline.
+//!
+//! [`ImageRenderer`], [`VideoPlayer`], and [`WebcamPlayer`] all turn pixels
+//! into dots with the same six knobs: dithering, threshold, brightness,
+//! contrast, gamma, and color mode. [`RenderOptions`] bundles them so a
+//! look can be configured once, checked once, and handed to any of them.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::{ColorMode, DitheringMethod, ImageRenderer, RenderOptions};
+//!
+//! # fn main() -> Result<(), dotmax::DotmaxError> {
+//! let options = RenderOptions::new()
+//!     .dithering(DitheringMethod::Atkinson)
+//!     .brightness(1.2)
+//!     .color_mode(ColorMode::Grayscale);
+//! options.validate()?;
+//!
+//! let renderer = ImageRenderer::new().render_options(options)?;
+//! assert_eq!(RenderOptions::from(&renderer), options);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The same value configures players:
+//!
+//! ```no_run
+//! # #[cfg(feature = "video")]
+//! # {
+//! use dotmax::image::RenderOptions;
+//! use dotmax::media::{VideoPlayer, WebcamPlayer};
+//!
+//! let options = RenderOptions::new().threshold(Some(100)).gamma(0.8);
+//!
+//! let video = VideoPlayer::new("clip.mp4")?.render_options(options)?;
+//! let webcam = WebcamPlayer::builder().render_options(options).build()?;
+//! # }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! [`ImageRenderer`]: super::ImageRenderer
+//! [`VideoPlayer`]: crate::media::VideoPlayer
+//! [`WebcamPlayer`]: crate::media::WebcamPlayer
+
+use super::threshold::{check_range, BRIGHTNESS_RANGE, CONTRAST_RANGE, GAMMA_RANGE};
+use super::{ColorMode, DitheringMethod};
+use crate::DotmaxError;
+
+/// Dithering, threshold, tone, and color settings for turning pixels into
+/// dots.
+///
+/// Fields are public; the builder methods are a shorthand for chains.
+/// Nothing is checked until [`validate`](Self::validate), which every
+/// pipeline taking a `RenderOptions` calls for you.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    /// Dithering algorithm.
+    pub dithering: DitheringMethod,
+
+    /// Manual threshold (0-255), or `None` for Otsu's automatic threshold.
+    pub threshold: Option<u8>,
+
+    /// Brightness multiplier, 0.0-2.0 (1.0 leaves the image unchanged).
+    pub brightness: f32,
+
+    /// Contrast multiplier, 0.0-2.0 (1.0 leaves the image unchanged).
+    pub contrast: f32,
+
+    /// Gamma, 0.1-3.0 (1.0 leaves the image unchanged).
+    pub gamma: f32,
+
+    /// Monochrome, grayscale, or true-color output.
+    pub color_mode: ColorMode,
+}
+
+impl Default for RenderOptions {
+    /// The same defaults as [`ImageRenderer::new`]: Floyd-Steinberg
+    /// dithering, Otsu threshold, neutral adjustments, monochrome.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderOptions {
+    /// Creates the default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            dithering: DitheringMethod::FloydSteinberg,
+            threshold: None,
+            brightness: 1.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            color_mode: ColorMode::Monochrome,
+        }
+    }
+
+    /// Sets the dithering algorithm.
+    #[must_use]
+    pub const fn dithering(mut self, method: DitheringMethod) -> Self {
+        self.dithering = method;
+        self
+    }
+
+    /// Sets the manual threshold, or `None` for Otsu.
+    #[must_use]
+    pub const fn threshold(mut self, threshold: Option<u8>) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the brightness multiplier.
+    #[must_use]
+    pub const fn brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Sets the contrast multiplier.
+    #[must_use]
+    pub const fn contrast(mut self, contrast: f32) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    /// Sets the gamma.
+    #[must_use]
+    pub const fn gamma(mut self, gamma: f32) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Sets the color mode.
+    #[must_use]
+    pub const fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Checks brightness, contrast, and gamma against their ranges.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] for the first value out of
+    /// range (or NaN), naming it along with its valid range.
+    pub fn validate(&self) -> Result<(), DotmaxError> {
+        check_range("brightness", self.brightness, &BRIGHTNESS_RANGE)?;
+        check_range("contrast", self.contrast, &CONTRAST_RANGE)?;
+        check_range("gamma", self.gamma, &GAMMA_RANGE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::ImageRenderer;
+
+    #[test]
+    fn test_default_matches_image_renderer() {
+        assert_eq!(
+            RenderOptions::default(),
+            RenderOptions::from(&ImageRenderer::new())
+        );
+        assert!(RenderOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_names_parameter_and_range() {
+        let err = RenderOptions::new().contrast(2.5).validate().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid contrast: 2.5 (valid range: 0.0-2.0)"
+        );
+        assert!(RenderOptions::new().gamma(0.0).validate().is_err());
+        assert!(RenderOptions::new()
+            .brightness(f32::NAN)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_image_renderer_round_trip() {
+        let options = RenderOptions::new()
+            .dithering(DitheringMethod::Bayer)
+            .threshold(Some(90))
+            .brightness(0.5)
+            .contrast(1.5)
+            .gamma(2.0)
+            .color_mode(ColorMode::TrueColor);
+        let renderer = ImageRenderer::new().render_options(options).unwrap();
+        assert_eq!(RenderOptions::from(&renderer), options);
+
+        assert!(ImageRenderer::new()
+            .render_options(options.gamma(9.0))
+            .is_err());
+    }
+}
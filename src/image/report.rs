@@ -0,0 +1,195 @@
+//! Per-stage timings for a [`Pipeline`](super::Pipeline) render.
+//!
+//! When frame rates drop, a [`RenderReport`] shows which step the time went
+//! to instead of leaving it to guesswork. Reports are opt-in: turn them on
+//! with [`Pipeline::set_reporting`](super::Pipeline::set_reporting) or
+//! [`ImageRenderer::report`](super::ImageRenderer::report), render, then read
+//! the report back with `last_report`.
+//!
+//! Stages served from the pipeline's cache are listed as cached with no
+//! time. Custom [`Stage`](super::Stage)s are timed as part of the built-in
+//! stage they run inside. With a [`ModeSelector`](super::ModeSelector) set,
+//! the report also says which render mode it picked and why.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::{Pipeline, PipelineStage};
+//! use image::{DynamicImage, RgbImage};
+//!
+//! # fn main() -> Result<(), dotmax::DotmaxError> {
+//! let mut pipeline = Pipeline::new();
+//! pipeline.set_image(DynamicImage::ImageRgb8(RgbImage::new(640, 480)));
+//! pipeline.set_reporting(true);
+//! pipeline.render()?;
+//!
+//! let report = pipeline.last_report().expect("reporting is on");
+//! let resize = report.stage(PipelineStage::Resize).expect("resize ran");
+//! assert_eq!((resize.width, resize.height), (128, 96)); // 4:3 inside 160×96
+//! println!("{report}");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use super::mode_select::ModeDecision;
+use super::PipelineStage;
+
+/// How one stage of a render went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageReport {
+    /// Which stage
+    pub stage: PipelineStage,
+    /// Time spent computing the stage; zero when cached
+    pub duration: Duration,
+    /// Whether the output was reused from the previous render
+    pub cached: bool,
+    /// Output width: pixels up to [`PipelineStage::Dither`], braille cells
+    /// from [`PipelineStage::Map`] on
+    pub width: usize,
+    /// Output height, in the same unit as `width`
+    pub height: usize,
+}
+
+/// Timings and output sizes for each stage of one render, in pipeline order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderReport {
+    /// Resize, adjust, dither, map, and color, in that order
+    pub stages: Vec<StageReport>,
+    /// The render mode picked for the image, if a mode selector is set
+    pub mode: Option<ModeDecision>,
+}
+
+impl RenderReport {
+    /// The report for `stage`, if it is part of this render.
+    #[must_use]
+    pub fn stage(&self, stage: PipelineStage) -> Option<&StageReport> {
+        self.stages.iter().find(|report| report.stage == stage)
+    }
+
+    /// Time spent in all stages together.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.stages.iter().map(|report| report.duration).sum()
+    }
+
+    /// The stage that took the longest, if any ran.
+    #[must_use]
+    pub fn slowest(&self) -> Option<&StageReport> {
+        self.stages
+            .iter()
+            .filter(|report| !report.cached)
+            .max_by_key(|report| report.duration)
+    }
+
+    /// Records `stage`, which ran for `duration` or was cached if `None`.
+    pub(crate) fn record(
+        &mut self,
+        stage: PipelineStage,
+        duration: Option<Duration>,
+        (width, height): (usize, usize),
+    ) {
+        self.stages.push(StageReport {
+            stage,
+            duration: duration.unwrap_or_default(),
+            cached: duration.is_none(),
+            width,
+            height,
+        });
+    }
+}
+
+/// One line per stage, the picked mode if any, then the total:
+///
+/// ```text
+/// Resize    1.842 ms  160×96 px
+/// Adjust    0.211 ms  160×96 px
+/// Dither    cached    160×96 px
+/// ...
+/// Mode      sextant (colorful content)
+/// Total     2.304 ms
+/// ```
+impl fmt::Display for RenderReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        for report in &self.stages {
+            let unit = if report.stage < PipelineStage::Map {
+                "px"
+            } else {
+                "cells"
+            };
+            let time = if report.cached {
+                "cached".to_string()
+            } else {
+                format!("{:.3} ms", millis(report.duration))
+            };
+            writeln!(
+                f,
+                "{:<9} {:<9} {}×{} {unit}",
+                format!("{:?}", report.stage),
+                time,
+                report.width,
+                report.height
+            )?;
+        }
+        if let Some(mode) = self.mode {
+            writeln!(f, "{:<9} {mode}", "Mode")?;
+        }
+        write!(f, "{:<9} {:.3} ms", "Total", millis(self.total()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::image::ModeReason;
+    use crate::render::RenderMode;
+
+    fn report() -> RenderReport {
+        let mut report = RenderReport::default();
+        let ms = |n| Some(Duration::from_millis(n));
+        report.record(PipelineStage::Resize, None, (160, 96));
+        report.record(PipelineStage::Adjust, ms(2), (160, 96));
+        report.record(PipelineStage::Dither, ms(5), (160, 96));
+        report.record(PipelineStage::Map, ms(1), (80, 24));
+        report.record(PipelineStage::Color, ms(1), (80, 24));
+        report
+    }
+
+    #[test]
+    fn test_totals_and_slowest() {
+        let report = report();
+        assert_eq!(report.total(), Duration::from_millis(9));
+        assert_eq!(report.slowest().unwrap().stage, PipelineStage::Dither);
+        let resize = report.stage(PipelineStage::Resize).unwrap();
+        assert!(resize.cached);
+        assert_eq!(resize.duration, Duration::ZERO);
+        assert!(report.stage(PipelineStage::Load).is_none());
+        assert!(RenderReport::default().slowest().is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let text = report().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Resize    cached    160×96 px");
+        assert_eq!(lines[2], "Dither    5.000 ms  160×96 px");
+        assert_eq!(lines[3], "Map       1.000 ms  80×24 cells");
+        assert_eq!(lines[5], "Total     9.000 ms");
+
+        let with_mode = RenderReport {
+            mode: Some(ModeDecision {
+                mode: RenderMode::Sextant,
+                reason: ModeReason::Colorful,
+            }),
+            ..report()
+        };
+        let text = with_mode.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[5], "Mode      sextant (colorful content)");
+        assert_eq!(lines[6], "Total     9.000 ms");
+    }
+}
@@ -92,7 +92,7 @@
 
 use crate::error::DotmaxError;
 use crate::image::loader::{MAX_IMAGE_HEIGHT, MAX_IMAGE_WIDTH};
-use image::{imageops, DynamicImage};
+use image::{imageops, DynamicImage, GenericImageView, ImageBuffer, Pixel};
 use tracing::debug;
 
 /// Braille cell width in dots (2 dots wide)
@@ -405,6 +405,23 @@ pub fn resize_to_dimensions(
     target_height: u32,
     preserve_aspect: bool,
 ) -> Result<DynamicImage, DotmaxError> {
+    resize_view(image, target_width, target_height, preserve_aspect).map(DynamicImage::ImageRgba8)
+}
+
+/// [`resize_to_dimensions`] for any image view, keeping its pixel type.
+///
+/// Lets borrowed pixel data, such as a [`RawFrame`](super::frame::RawFrame),
+/// be resized without first being copied into a [`DynamicImage`].
+pub(crate) fn resize_view<I>(
+    image: &I,
+    target_width: u32,
+    target_height: u32,
+    preserve_aspect: bool,
+) -> Result<ImageBuffer<I::Pixel, Vec<Subpixel<I>>>, DotmaxError>
+where
+    I: GenericImageView,
+    I::Pixel: 'static,
+{
     // Validate target dimensions
     if target_width == 0 || target_height == 0 {
         return Err(DotmaxError::InvalidImageDimensions {
@@ -452,11 +469,12 @@ pub fn resize_to_dimensions(
     );
 
     // Perform resize with selected filter
-    let resized = imageops::resize(image, final_width, final_height, filter);
-
-    Ok(DynamicImage::ImageRgba8(resized))
+    Ok(imageops::resize(image, final_width, final_height, filter))
 }
 
+/// The channel type of `I`'s pixels.
+type Subpixel<I> = <<I as GenericImageView>::Pixel as Pixel>::Subpixel;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,331 @@
+//! Region-of-interest tracking for live sources.
+//!
+//! A webcam frame is mostly background; scaled down to a terminal, the
+//! person in it ends up a handful of cells wide. [`RoiTracker`] finds where
+//! the picture is changing between frames, which for a video call preview
+//! is the person talking, and returns a crop rectangle centered on that
+//! motion and sized to its spread. Cropping to it before rendering spends
+//! the terminal's few dots on the subject.
+//!
+//! Tracking is frame differencing only: no models, no extra dependencies,
+//! and cheap enough to run on terminal-sized frames every frame. The crop
+//! eases toward each new measurement and holds still when nothing moves, so
+//! a subject who sits still stays framed.
+//!
+//! Rectangles are in normalized coordinates (`0.0..=1.0` on both axes), so
+//! the tracker can watch a small thumbnail while the crop is taken from the
+//! full-resolution frame. Crops are always square in normalized space, which
+//! keeps the aspect ratio of the frame they are cut from.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::roi::{RoiConfig, RoiTracker};
+//! use image::GrayImage;
+//!
+//! let mut tracker = RoiTracker::new(RoiConfig {
+//!     smoothing: 1.0,
+//!     ..RoiConfig::default()
+//! });
+//!
+//! // A bright square moves around the top-left corner
+//! let mut frame = GrayImage::new(100, 100);
+//! tracker.update(&frame);
+//! for y in 10..30 {
+//!     for x in 10..30 {
+//!         frame.put_pixel(x, y, image::Luma([255]));
+//!     }
+//! }
+//! let rect = tracker.update(&frame);
+//!
+//! assert!(rect.width < 1.0);
+//! assert!(rect.x < 0.1 && rect.y < 0.1);
+//! let (x, y, width, height) = rect.to_pixels(1280, 720);
+//! # let _ = (x, y, width, height);
+//! ```
+//!
+//! [`WebcamPlayer`](crate::media::WebcamPlayer) runs one of these when
+//! configured with `roi`.
+
+use image::GrayImage;
+
+/// Crops at least this close to the whole frame count as the whole frame.
+const FULL_FRAME: f32 = 0.999;
+
+/// A crop rectangle in normalized frame coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoiRect {
+    /// Left edge, `0.0` to `1.0`.
+    pub x: f32,
+    /// Top edge, `0.0` to `1.0`.
+    pub y: f32,
+    /// Width as a fraction of the frame.
+    pub width: f32,
+    /// Height as a fraction of the frame.
+    pub height: f32,
+}
+
+impl RoiRect {
+    /// The whole frame.
+    pub const FULL: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    };
+
+    /// Whether this covers (practically) the whole frame, so cropping can
+    /// be skipped.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.width >= FULL_FRAME && self.height >= FULL_FRAME
+    }
+
+    /// Converts to `(x, y, width, height)` in pixels of a `frame_width ×
+    /// frame_height` frame, clamped to lie inside it and at least 1 pixel
+    /// in each direction.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn to_pixels(&self, frame_width: u32, frame_height: u32) -> (u32, u32, u32, u32) {
+        let axis = |start: f32, length: f32, size: u32| -> (u32, u32) {
+            let size_f = size as f32;
+            let length = ((length * size_f).round() as u32).clamp(1, size.max(1));
+            let start = ((start * size_f).round() as u32).min(size.saturating_sub(length));
+            (start, length)
+        };
+        let (x, width) = axis(self.x, self.width, frame_width);
+        let (y, height) = axis(self.y, self.height, frame_height);
+        (x, y, width, height)
+    }
+}
+
+/// Settings for [`RoiTracker`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoiConfig {
+    /// Luminance change (0-255) between frames that counts a pixel as moving.
+    /// Higher values ignore more sensor noise.
+    pub motion_threshold: u8,
+
+    /// Fraction of pixels that must be moving before the crop follows.
+    /// Below this the crop holds where it is.
+    pub min_motion: f32,
+
+    /// Crop size relative to the spread of the moving pixels. Larger values
+    /// leave more room around the subject.
+    pub padding: f32,
+
+    /// Tightest crop as a fraction of the frame, limiting how far the
+    /// tracker zooms in.
+    pub min_scale: f32,
+
+    /// Weight of each new measurement, `0.0` to `1.0`. Lower values pan and
+    /// zoom more gently.
+    pub smoothing: f32,
+}
+
+impl Default for RoiConfig {
+    /// Gentle tracking that zooms in to at most 40% of the frame.
+    fn default() -> Self {
+        Self {
+            motion_threshold: 24,
+            min_motion: 0.002,
+            padding: 1.5,
+            min_scale: 0.4,
+            smoothing: 0.1,
+        }
+    }
+}
+
+/// Follows motion between frames with a smoothed crop rectangle.
+#[derive(Debug, Clone)]
+pub struct RoiTracker {
+    config: RoiConfig,
+    previous: Option<GrayImage>,
+    center: (f32, f32),
+    scale: f32,
+}
+
+impl RoiTracker {
+    /// Creates a tracker that starts on the whole frame.
+    #[must_use]
+    pub const fn new(config: RoiConfig) -> Self {
+        Self {
+            config,
+            previous: None,
+            center: (0.5, 0.5),
+            scale: 1.0,
+        }
+    }
+
+    /// Returns the configuration.
+    #[must_use]
+    pub const fn config(&self) -> &RoiConfig {
+        &self.config
+    }
+
+    /// Replaces the configuration, keeping the current crop.
+    pub fn set_config(&mut self, config: RoiConfig) {
+        self.config = config;
+    }
+
+    /// Drops the previous frame and returns to the whole frame.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.center = (0.5, 0.5);
+        self.scale = 1.0;
+    }
+
+    /// Compares `frame` with the previous one and returns the updated crop.
+    ///
+    /// Frames of a different size than the previous one restart motion
+    /// detection without moving the crop.
+    pub fn update(&mut self, frame: &GrayImage) -> RoiRect {
+        let previous = self.previous.replace(frame.clone());
+        let motion = previous
+            .filter(|previous| previous.dimensions() == frame.dimensions())
+            .and_then(|previous| self.motion(&previous, frame));
+
+        if let Some((center_x, center_y, spread)) = motion {
+            let min_scale = self.config.min_scale.clamp(0.01, 1.0);
+            let scale = (spread * self.config.padding).clamp(min_scale, 1.0);
+            let weight = self.config.smoothing.clamp(0.0, 1.0);
+            let ease = |current: f32, target: f32| weight.mul_add(target - current, current);
+            self.center = (ease(self.center.0, center_x), ease(self.center.1, center_y));
+            self.scale = ease(self.scale, scale);
+        }
+        self.rect()
+    }
+
+    /// The current crop.
+    #[must_use]
+    pub fn rect(&self) -> RoiRect {
+        let half = self.scale / 2.0;
+        let max_start = 1.0 - self.scale;
+        RoiRect {
+            x: (self.center.0 - half).clamp(0.0, max_start),
+            y: (self.center.1 - half).clamp(0.0, max_start),
+            width: self.scale,
+            height: self.scale,
+        }
+    }
+
+    /// Centroid of the moving pixels and their spread (about four standard
+    /// deviations along the wider axis), normalized to the frame. `None`
+    /// when too little is moving.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn motion(&self, previous: &GrayImage, frame: &GrayImage) -> Option<(f32, f32, f32)> {
+        let (width, height) = frame.dimensions();
+        let (mut count, mut sum_x, mut sum_y, mut sum_xx, mut sum_yy) = (0u64, 0.0, 0.0, 0.0, 0.0);
+        for ((x, y, pixel), before) in frame.enumerate_pixels().zip(previous.pixels()) {
+            if pixel[0].abs_diff(before[0]) > self.config.motion_threshold {
+                let fx = (f64::from(x) + 0.5) / f64::from(width);
+                let fy = (f64::from(y) + 0.5) / f64::from(height);
+                count += 1;
+                sum_x += fx;
+                sum_y += fy;
+                sum_xx += fx * fx;
+                sum_yy += fy * fy;
+            }
+        }
+
+        let total = u64::from(width) * u64::from(height);
+        if count == 0 || (count as f64) < f64::from(self.config.min_motion) * total as f64 {
+            return None;
+        }
+        let n = count as f64;
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let std_x = mean_x.mul_add(-mean_x, sum_xx / n).max(0.0).sqrt();
+        let std_y = mean_y.mul_add(-mean_y, sum_yy / n).max(0.0).sqrt();
+        Some((
+            mean_x as f32,
+            mean_y as f32,
+            (4.0 * std_x.max(std_y)) as f32,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    /// A black frame with a white square at `(x, y)`.
+    fn frame_with_square(x: u32, y: u32, size: u32) -> GrayImage {
+        GrayImage::from_fn(100, 100, |px, py| {
+            let inside = (x..x + size).contains(&px) && (y..y + size).contains(&py);
+            Luma([if inside { 255 } else { 0 }])
+        })
+    }
+
+    fn tracker(smoothing: f32) -> RoiTracker {
+        RoiTracker::new(RoiConfig {
+            smoothing,
+            ..RoiConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_follows_motion() {
+        let mut tracker = tracker(1.0);
+        assert_eq!(
+            tracker.update(&frame_with_square(60, 60, 20)),
+            RoiRect::FULL
+        );
+
+        let rect = tracker.update(&frame_with_square(70, 70, 20));
+        assert!(!rect.is_full());
+        assert!(rect.width >= 0.4 && rect.width < 1.0);
+        // Pushed against the bottom-right edges, not past them
+        assert!((rect.x + rect.width - 1.0).abs() < 1e-4);
+        assert!((rect.y + rect.height - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_holds_without_motion() {
+        let mut tracker = tracker(1.0);
+        tracker.update(&frame_with_square(10, 10, 20));
+        let moved = tracker.update(&frame_with_square(20, 10, 20));
+
+        let still = frame_with_square(20, 10, 20);
+        assert_eq!(tracker.update(&still), moved);
+        assert_eq!(tracker.update(&still), moved);
+    }
+
+    #[test]
+    fn test_smoothing_eases_toward_target() {
+        let mut smooth = tracker(0.5);
+        smooth.update(&frame_with_square(10, 10, 20));
+        let rect = smooth.update(&frame_with_square(15, 15, 20));
+        let mut instant = tracker(1.0);
+        instant.update(&frame_with_square(10, 10, 20));
+        let target = instant.update(&frame_with_square(15, 15, 20));
+
+        assert!(rect.width > target.width && rect.width < 1.0);
+
+        smooth.reset();
+        assert_eq!(smooth.rect(), RoiRect::FULL);
+    }
+
+    #[test]
+    fn test_size_change_restarts_detection() {
+        let mut tracker = tracker(1.0);
+        tracker.update(&GrayImage::new(50, 50));
+        assert_eq!(tracker.update(&frame_with_square(0, 0, 20)), RoiRect::FULL);
+    }
+
+    #[test]
+    fn test_to_pixels_clamps_to_frame() {
+        let rect = RoiRect {
+            x: 0.75,
+            y: 0.0,
+            width: 0.5,
+            height: 0.5,
+        };
+        assert_eq!(rect.to_pixels(200, 100), (100, 0, 100, 50));
+        assert_eq!(RoiRect::FULL.to_pixels(640, 480), (0, 0, 640, 480));
+    }
+}
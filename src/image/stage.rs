@@ -0,0 +1,297 @@
+//! Custom processing steps for the image [`Pipeline`](super::Pipeline).
+//!
+//! A [`Stage`] is a function from [`FrameData`] to [`FrameData`], added at
+//! one of three [`InsertionPoint`]s between the built-in steps:
+//!
+//! | Insertion point                    | Runs                            | Sees                  |
+//! |------------------------------------|---------------------------------|-----------------------|
+//! | [`InsertionPoint::BeforeDither`]   | after brightness/contrast/gamma | [`FrameData::Gray`]   |
+//! | [`InsertionPoint::AfterThreshold`] | after dithering or thresholding | [`FrameData::Binary`] |
+//! | [`InsertionPoint::BeforeColor`]    | after mapping and despeckling   | [`FrameData::Dots`]   |
+//!
+//! Stages at the same point run in the order they were added, and each must
+//! return the kind of data it was given. Any closure of the right shape is a
+//! stage; implement the trait directly to give a stage a readable
+//! [`name`](Stage::name) for error messages.
+//!
+//! # Examples
+//!
+//! A tone curve ahead of dithering, with [`ImageRenderer`](super::ImageRenderer):
+//!
+//! ```no_run
+//! use dotmax::image::{FrameData, ImageRenderer, InsertionPoint};
+//! use std::path::Path;
+//!
+//! # fn main() -> Result<(), dotmax::DotmaxError> {
+//! let grid = ImageRenderer::new()
+//!     .load_from_path(Path::new("photo.jpg"))?
+//!     .stage(InsertionPoint::BeforeDither, |data| match data {
+//!         FrameData::Gray(mut gray) => {
+//!             // Lift the shadows
+//!             for pixel in gray.pixels_mut() {
+//!                 pixel.0[0] = ((f32::from(pixel.0[0]) / 255.0).sqrt() * 255.0) as u8;
+//!             }
+//!             FrameData::Gray(gray)
+//!         }
+//!         other => other,
+//!     })
+//!     .render()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use image::GrayImage;
+
+use super::BinaryImage;
+use crate::{BrailleGrid, DotmaxError};
+
+/// What flows through a [`Stage`].
+#[derive(Debug, Clone)]
+pub enum FrameData {
+    /// The adjusted grayscale image, at [`InsertionPoint::BeforeDither`]
+    Gray(GrayImage),
+    /// The black-and-white image, at [`InsertionPoint::AfterThreshold`]
+    Binary(BinaryImage),
+    /// The uncolored braille dots, at [`InsertionPoint::BeforeColor`]
+    Dots(BrailleGrid),
+}
+
+impl FrameData {
+    /// `"gray"`, `"binary"`, or `"dots"`.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Gray(_) => "gray",
+            Self::Binary(_) => "binary",
+            Self::Dots(_) => "dots",
+        }
+    }
+
+    /// The grayscale image, or `self` back if this is another kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` unchanged if it isn't [`FrameData::Gray`].
+    pub fn into_gray(self) -> Result<GrayImage, Self> {
+        match self {
+            Self::Gray(gray) => Ok(gray),
+            other => Err(other),
+        }
+    }
+
+    /// The black-and-white image, or `self` back if this is another kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` unchanged if it isn't [`FrameData::Binary`].
+    pub fn into_binary(self) -> Result<BinaryImage, Self> {
+        match self {
+            Self::Binary(binary) => Ok(binary),
+            other => Err(other),
+        }
+    }
+
+    /// The braille dots, or `self` back if this is another kind.
+    ///
+    /// # Errors
+    ///
+    /// Returns `self` unchanged if it isn't [`FrameData::Dots`].
+    pub fn into_dots(self) -> Result<BrailleGrid, Self> {
+        match self {
+            Self::Dots(grid) => Ok(grid),
+            other => Err(other),
+        }
+    }
+}
+
+/// Where in the pipeline a [`Stage`] runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InsertionPoint {
+    /// On the grayscale image, after adjustments and before dithering
+    BeforeDither,
+    /// On the black-and-white image, before it is mapped to dots
+    AfterThreshold,
+    /// On the dot grid, before cells are colored. Runs in every color
+    /// mode, monochrome included.
+    BeforeColor,
+}
+
+impl InsertionPoint {
+    /// The [`FrameData::kind`] stages at this point receive and return.
+    #[must_use]
+    pub const fn data_kind(self) -> &'static str {
+        match self {
+            Self::BeforeDither => "gray",
+            Self::AfterThreshold => "binary",
+            Self::BeforeColor => "dots",
+        }
+    }
+}
+
+/// A custom processing step; see the [module documentation](self).
+///
+/// Stages are shared between clones of a pipeline, so they take `&self`.
+/// A stage whose behavior depends on settings it holds should be replaced,
+/// or the pipeline told with
+/// [`Pipeline::invalidate`](super::Pipeline::invalidate), when those
+/// settings change; cached results are otherwise reused.
+pub trait Stage: Send + Sync {
+    /// Transforms one frame's data.
+    fn process(&self, data: FrameData) -> FrameData;
+
+    /// A name for error messages. Defaults to the type name.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl<F> Stage for F
+where
+    F: Fn(FrameData) -> FrameData + Send + Sync,
+{
+    fn process(&self, data: FrameData) -> FrameData {
+        self(data)
+    }
+}
+
+/// A stage and where it runs.
+#[derive(Clone)]
+pub(crate) struct CustomStage {
+    pub(crate) at: InsertionPoint,
+    pub(crate) stage: Arc<dyn Stage>,
+}
+
+impl fmt::Debug for CustomStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomStage")
+            .field("at", &self.at)
+            .field("stage", &self.stage.name())
+            .finish()
+    }
+}
+
+/// Runs the stages at `at` over `input`, borrowing it untouched when there
+/// are none. `wrap` and `unwrap` convert to and from the [`FrameData`]
+/// variant for `at`.
+pub(crate) fn run_stages<'a, T: Clone>(
+    stages: &[CustomStage],
+    at: InsertionPoint,
+    input: &'a T,
+    wrap: fn(T) -> FrameData,
+    unwrap: fn(FrameData) -> Result<T, FrameData>,
+) -> Result<Cow<'a, T>, DotmaxError> {
+    let mut value = Cow::Borrowed(input);
+    for custom in stages.iter().filter(|custom| custom.at == at) {
+        let output = custom.stage.process(wrap(value.into_owned()));
+        value = Cow::Owned(
+            unwrap(output).map_err(|found| DotmaxError::StageOutputMismatch {
+                stage: custom.stage.name().to_string(),
+                expected: at.data_kind(),
+                found: found.kind(),
+            })?,
+        );
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Invert;
+
+    impl Stage for Invert {
+        fn process(&self, data: FrameData) -> FrameData {
+            match data {
+                FrameData::Binary(mut binary) => {
+                    binary.pixels.iter_mut().for_each(|pixel| *pixel = !*pixel);
+                    FrameData::Binary(binary)
+                }
+                other => other,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "invert"
+        }
+    }
+
+    fn binary() -> BinaryImage {
+        BinaryImage {
+            width: 2,
+            height: 1,
+            pixels: vec![true, false],
+        }
+    }
+
+    fn custom(at: InsertionPoint, stage: impl Stage + 'static) -> CustomStage {
+        CustomStage {
+            at,
+            stage: Arc::new(stage),
+        }
+    }
+
+    #[test]
+    fn test_run_stages_in_order_at_their_point() {
+        let stages = [
+            custom(InsertionPoint::AfterThreshold, Invert),
+            custom(InsertionPoint::BeforeColor, |_| panic!("wrong point")),
+            custom(InsertionPoint::AfterThreshold, |data: FrameData| {
+                let mut binary = data.into_binary().unwrap();
+                binary.pixels.push(true);
+                FrameData::Binary(binary)
+            }),
+        ];
+        let input = binary();
+        let output = run_stages(
+            &stages,
+            InsertionPoint::AfterThreshold,
+            &input,
+            FrameData::Binary,
+            FrameData::into_binary,
+        )
+        .unwrap();
+        assert_eq!(output.pixels, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_no_stages_borrows_input() {
+        let input = binary();
+        let output = run_stages(
+            &[],
+            InsertionPoint::AfterThreshold,
+            &input,
+            FrameData::Binary,
+            FrameData::into_binary,
+        )
+        .unwrap();
+        assert!(matches!(output, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_wrong_output_kind_is_an_error() {
+        let stages = [custom(InsertionPoint::AfterThreshold, |_| {
+            FrameData::Gray(GrayImage::new(1, 1))
+        })];
+        let err = run_stages(
+            &stages,
+            InsertionPoint::AfterThreshold,
+            &binary(),
+            FrameData::Binary,
+            FrameData::into_binary,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            DotmaxError::StageOutputMismatch {
+                expected: "binary",
+                found: "gray",
+                ..
+            }
+        ));
+    }
+}
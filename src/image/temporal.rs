@@ -7,7 +7,7 @@
 //!
 //! # Algorithms
 //!
-//! Three complementary approaches are provided:
+//! Four complementary approaches are provided:
 //!
 //! 1. **Hysteresis Thresholding** ([`HysteresisFilter`]): Uses two thresholds
 //!    with memory - pixels only switch state when crossing the opposite threshold.
@@ -19,6 +19,14 @@
 //! 3. **Dot-Level Temporal Filtering** ([`DotTemporalFilter`]): Applies IIR filter
 //!    to individual braille dot decisions. Fine-grained control over dot stability.
 //!
+//! 4. **Dot Persistence** ([`DotPersistenceFilter`]): A dot only changes state
+//!    after the new state has held for K consecutive frames. Removes single-frame
+//!    dither noise entirely at the cost of K-1 frames of latency on real changes.
+//!
+//! All of these carry history from frame to frame, which smears hard cuts. The
+//! [`SceneChangeDetector`] compares luminance histograms between frames and lets
+//! [`TemporalCoherence`] drop that history when the picture changes completely.
+//!
 //! # Usage
 //!
 //! For most use cases, the [`TemporalCoherence`] struct provides a unified API
@@ -44,7 +52,8 @@
 //! - Time: O(width × height) per frame
 //! - Target: <1ms overhead for 1080p frames
 
-use image::GrayImage;
+use crate::BrailleGrid;
+use image::{GrayImage, RgbImage};
 
 // ============================================================================
 // Configuration Types
@@ -78,9 +87,13 @@ use image::GrayImage;
 ///     frame_blend_alpha: 0.7,
 ///     dot_filter_enabled: false,
 ///     dot_filter_alpha: 0.5,
+///     persistence_frames: 3,
+///     scene_cut_enabled: true,
+///     scene_cut_threshold: 0.5,
 /// };
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(clippy::struct_excessive_bools)] // One independent switch per stage
 pub struct TemporalConfig {
     /// Enable hysteresis thresholding (dual-threshold with memory).
     pub hysteresis_enabled: bool,
@@ -120,6 +133,21 @@ pub struct TemporalConfig {
     ///
     /// Recommended range: 0.3-0.7.
     pub dot_filter_alpha: f32,
+
+    /// Consecutive frames a dot must hold a new state before it is shown.
+    ///
+    /// `0` or `1` disables the persistence filter. Recommended range: 2-4.
+    pub persistence_frames: u8,
+
+    /// Enable scene cut detection, which clears temporal history on hard cuts.
+    pub scene_cut_enabled: bool,
+
+    /// Histogram difference (0.0-1.0) above which a frame is a scene cut.
+    ///
+    /// The difference is the fraction of pixels that would have to move to a
+    /// different brightness bin to turn one frame's histogram into the other's.
+    /// Recommended range: 0.4-0.6.
+    pub scene_cut_threshold: f32,
 }
 
 impl Default for TemporalConfig {
@@ -135,6 +163,9 @@ impl Default for TemporalConfig {
             frame_blend_alpha: 0.8,
             dot_filter_enabled: false,
             dot_filter_alpha: 0.5,
+            persistence_frames: 0,
+            scene_cut_enabled: false,
+            scene_cut_threshold: 0.5,
         }
     }
 }
@@ -153,6 +184,9 @@ impl TemporalConfig {
             frame_blend_alpha: 0.85,
             dot_filter_enabled: false,
             dot_filter_alpha: 0.5,
+            persistence_frames: 0,
+            scene_cut_enabled: true,
+            scene_cut_threshold: 0.5,
         }
     }
 
@@ -169,6 +203,9 @@ impl TemporalConfig {
             frame_blend_alpha: 0.7,
             dot_filter_enabled: true,
             dot_filter_alpha: 0.6,
+            persistence_frames: 0,
+            scene_cut_enabled: true,
+            scene_cut_threshold: 0.5,
         }
     }
 
@@ -185,6 +222,9 @@ impl TemporalConfig {
             frame_blend_alpha: 0.9,
             dot_filter_enabled: false,
             dot_filter_alpha: 0.7,
+            persistence_frames: 0,
+            scene_cut_enabled: false,
+            scene_cut_threshold: 0.5,
         }
     }
 
@@ -201,6 +241,9 @@ impl TemporalConfig {
             frame_blend_alpha: 1.0,
             dot_filter_enabled: false,
             dot_filter_alpha: 1.0,
+            persistence_frames: 0,
+            scene_cut_enabled: false,
+            scene_cut_threshold: 0.5,
         }
     }
 }
@@ -608,6 +651,221 @@ impl DotTemporalFilter {
     }
 }
 
+// ============================================================================
+// Dot Persistence
+// ============================================================================
+
+/// Per-dot debouncing for braille output.
+///
+/// Each displayed dot keeps its state until the input has disagreed with it
+/// for `frames` consecutive frames. A dot that flickers on for a single frame
+/// never appears, while a real edge moving across the image shows up
+/// `frames - 1` frames late.
+///
+/// Unlike [`DotTemporalFilter`], which averages, this filter counts: one
+/// agreeing frame resets the count, so noise that toggles every other frame
+/// is suppressed completely.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::image::temporal::DotPersistenceFilter;
+///
+/// let mut filter = DotPersistenceFilter::new(3);
+///
+/// // The first frame is shown as-is
+/// assert_eq!(filter.filter(&[false, true]), vec![false, true]);
+///
+/// // The first dot must be on for three frames in a row before it shows
+/// assert_eq!(filter.filter(&[true, true]), vec![false, true]);
+/// assert_eq!(filter.filter(&[true, true]), vec![false, true]);
+/// assert_eq!(filter.filter(&[true, true]), vec![true, true]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DotPersistenceFilter {
+    /// Displayed dot states (None = no frame seen yet).
+    state: Option<Vec<bool>>,
+    /// Consecutive frames each dot's input has disagreed with its state.
+    pending: Vec<u8>,
+    /// Frames required before a dot changes.
+    frames: u8,
+}
+
+impl DotPersistenceFilter {
+    /// Creates a filter requiring `frames` consecutive frames per change.
+    ///
+    /// Values below 1 are treated as 1 (no filtering).
+    #[must_use]
+    pub const fn new(frames: u8) -> Self {
+        Self {
+            state: None,
+            pending: Vec::new(),
+            frames,
+        }
+    }
+
+    /// Filters a frame of binary dot values.
+    ///
+    /// A frame with a different length from the previous one restarts the
+    /// history.
+    pub fn filter(&mut self, dots: &[bool]) -> Vec<bool> {
+        let state = match &mut self.state {
+            Some(state) if state.len() == dots.len() => state,
+            _ => {
+                self.pending = vec![0; dots.len()];
+                self.state = Some(dots.to_vec());
+                return dots.to_vec();
+            }
+        };
+
+        let required = self.frames.max(1);
+        for ((shown, pending), &is_on) in state.iter_mut().zip(&mut self.pending).zip(dots) {
+            if *shown == is_on {
+                *pending = 0;
+            } else {
+                *pending += 1;
+                if *pending >= required {
+                    *shown = is_on;
+                    *pending = 0;
+                }
+            }
+        }
+        state.clone()
+    }
+
+    /// Resets the filter state.
+    pub fn reset(&mut self) {
+        self.state = None;
+        self.pending.clear();
+    }
+
+    /// Sets the number of frames required per change.
+    pub fn set_frames(&mut self, frames: u8) {
+        self.frames = frames;
+    }
+
+    /// Returns the number of frames required per change.
+    #[must_use]
+    pub const fn frames(&self) -> u8 {
+        self.frames
+    }
+}
+
+// ============================================================================
+// Scene Change Detection
+// ============================================================================
+
+/// Number of luminance bins used for scene change histograms.
+const SCENE_HISTOGRAM_BINS: usize = 32;
+
+/// Detects hard cuts by comparing luminance histograms of consecutive frames.
+///
+/// Histograms ignore where things are in the frame, so camera pans and moving
+/// objects produce small differences while a cut to a different shot produces
+/// a large one. The difference is the total variation distance between the
+/// normalized histograms: `0.0` for identical distributions, `1.0` when no
+/// brightness level is shared.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::image::temporal::SceneChangeDetector;
+/// use image::GrayImage;
+///
+/// let mut detector = SceneChangeDetector::new(0.5);
+/// let dark = GrayImage::from_pixel(8, 8, image::Luma([20]));
+/// let bright = GrayImage::from_pixel(8, 8, image::Luma([230]));
+///
+/// assert!(!detector.is_cut(&dark)); // first frame is never a cut
+/// assert!(!detector.is_cut(&dark));
+/// assert!(detector.is_cut(&bright));
+/// assert!((detector.difference() - 1.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SceneChangeDetector {
+    /// Normalized histogram of the previous frame.
+    previous: Option<[f32; SCENE_HISTOGRAM_BINS]>,
+    /// Difference above which a frame counts as a cut.
+    threshold: f32,
+    /// Difference measured on the last frame.
+    difference: f32,
+}
+
+impl SceneChangeDetector {
+    /// Creates a detector that reports cuts above `threshold` (0.0-1.0).
+    #[must_use]
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            previous: None,
+            threshold: threshold.clamp(0.0, 1.0),
+            difference: 0.0,
+        }
+    }
+
+    /// Records a grayscale frame and returns whether it starts a new scene.
+    pub fn is_cut(&mut self, frame: &GrayImage) -> bool {
+        self.compare(frame.pixels().map(|p| p.0[0]))
+    }
+
+    /// Records an RGB frame and returns whether it starts a new scene.
+    pub fn is_cut_rgb(&mut self, frame: &RgbImage) -> bool {
+        self.compare(frame.pixels().map(|p| {
+            let [r, g, b] = p.0;
+            // Integer BT.601 luma
+            #[allow(clippy::cast_possible_truncation)]
+            let luma = ((u32::from(r) * 77 + u32::from(g) * 150 + u32::from(b) * 29) >> 8) as u8;
+            luma
+        }))
+    }
+
+    fn compare(&mut self, luma: impl Iterator<Item = u8>) -> bool {
+        let mut counts = [0u32; SCENE_HISTOGRAM_BINS];
+        let mut total = 0u32;
+        for value in luma {
+            counts[usize::from(value) * SCENE_HISTOGRAM_BINS / 256] += 1;
+            total += 1;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let scale = 1.0 / total.max(1) as f32;
+        #[allow(clippy::cast_precision_loss)]
+        let histogram = counts.map(|c| c as f32 * scale);
+
+        self.difference = self.previous.map_or(0.0, |previous| {
+            previous
+                .iter()
+                .zip(&histogram)
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f32>()
+                / 2.0
+        });
+        self.previous = Some(histogram);
+        self.difference > self.threshold
+    }
+
+    /// Histogram difference measured on the most recent frame.
+    #[must_use]
+    pub const fn difference(&self) -> f32 {
+        self.difference
+    }
+
+    /// Forgets the previous frame, so the next one is never a cut.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.difference = 0.0;
+    }
+
+    /// Sets the cut threshold (0.0-1.0).
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    /// Returns the cut threshold.
+    #[must_use]
+    pub const fn threshold(&self) -> f32 {
+        self.threshold
+    }
+}
+
 // ============================================================================
 // Unified Temporal Coherence
 // ============================================================================
@@ -640,6 +898,10 @@ pub struct TemporalCoherence {
     blender: FrameBlender,
     /// Dot-level filter (created lazily when needed).
     dot_filter: Option<DotTemporalFilter>,
+    /// Dot persistence filter.
+    persistence: DotPersistenceFilter,
+    /// Scene cut detector.
+    scene: SceneChangeDetector,
 }
 
 impl TemporalCoherence {
@@ -654,6 +916,8 @@ impl TemporalCoherence {
             hysteresis: HysteresisFilter::new(config.hysteresis_margin),
             blender: FrameBlender::new(config.frame_blend_alpha),
             dot_filter: None,
+            persistence: DotPersistenceFilter::new(config.persistence_frames),
+            scene: SceneChangeDetector::new(config.scene_cut_threshold),
             config,
         }
     }
@@ -683,15 +947,81 @@ impl TemporalCoherence {
         };
 
         // Step 2: Hysteresis thresholding (if enabled)
+        self.threshold_grayscale(&blended, threshold)
+    }
+
+    /// Checks a grayscale frame for a scene cut and clears history if found.
+    ///
+    /// Call this on each new frame before any other stage. On a cut, blending,
+    /// hysteresis, and dot history are dropped so the frame is rendered fresh
+    /// instead of being smeared with the previous shot.
+    ///
+    /// Returns `true` if the frame was a cut. Always `false` when scene cut
+    /// detection is disabled.
+    pub fn detect_scene_cut(&mut self, frame: &GrayImage) -> bool {
+        let cut = self.config.scene_cut_enabled && self.scene.is_cut(frame);
+        if cut {
+            self.reset_history();
+        }
+        cut
+    }
+
+    /// RGB version of [`detect_scene_cut`](Self::detect_scene_cut).
+    pub fn detect_scene_cut_rgb(&mut self, frame: &RgbImage) -> bool {
+        let cut = self.config.scene_cut_enabled && self.scene.is_cut_rgb(frame);
+        if cut {
+            self.reset_history();
+        }
+        cut
+    }
+
+    /// Applies frame blending to a grayscale frame, if enabled.
+    ///
+    /// Use this ahead of dithering, where the thresholding step of
+    /// [`process_grayscale`](Self::process_grayscale) doesn't apply.
+    pub fn blend_grayscale(&mut self, frame: GrayImage) -> GrayImage {
+        if self.config.frame_blend_enabled {
+            self.blender.blend(&frame)
+        } else {
+            frame
+        }
+    }
+
+    /// Applies frame blending to each channel of an RGB frame, if enabled.
+    pub fn blend_rgb(&mut self, frame: RgbImage) -> RgbImage {
+        if !self.config.frame_blend_enabled {
+            return frame;
+        }
+        // Blend the interleaved channels as one grayscale image three times
+        // as wide; the moving average is per-sample, so this is exact
+        let (width, height) = frame.dimensions();
+        let raw = frame.as_raw();
+        let row = width as usize * 3;
+        let flat = GrayImage::from_fn(width * 3, height, |x, y| {
+            image::Luma([raw[y as usize * row + x as usize]])
+        });
+        let blended = self.blender.blend(&flat);
+        RgbImage::from_fn(width, height, |x, y| {
+            let px = |c| blended.get_pixel(x * 3 + c, y).0[0];
+            image::Rgb([px(0), px(1), px(2)])
+        })
+    }
+
+    /// Converts a grayscale frame to binary, with hysteresis if enabled.
+    ///
+    /// # Returns
+    ///
+    /// Binary image (white = on, black = off).
+    pub fn threshold_grayscale(&mut self, frame: &GrayImage, threshold: u8) -> GrayImage {
         if self.config.hysteresis_enabled {
-            self.hysteresis.apply(&blended, threshold)
+            self.hysteresis.apply(frame, threshold)
         } else {
             // Standard threshold
-            let mut output = GrayImage::new(blended.width(), blended.height());
-            for (i, pixel) in blended.pixels().enumerate() {
+            let mut output = GrayImage::new(frame.width(), frame.height());
+            for (i, pixel) in frame.pixels().enumerate() {
                 let is_on = pixel.0[0] >= threshold;
-                let x = (i % blended.width() as usize) as u32;
-                let y = (i / blended.width() as usize) as u32;
+                let x = (i % frame.width() as usize) as u32;
+                let y = (i / frame.width() as usize) as u32;
                 output.put_pixel(x, y, image::Luma([if is_on { 255 } else { 0 }]));
             }
             output
@@ -712,8 +1042,13 @@ impl TemporalCoherence {
     ///
     /// Filtered dot states.
     pub fn process_dots(&mut self, dots: &[bool], width: usize, height: usize) -> Vec<bool> {
+        let persistent = self.config.persistence_frames > 1;
         if !self.config.dot_filter_enabled {
-            return dots.to_vec();
+            return if persistent {
+                self.persistence.filter(dots)
+            } else {
+                dots.to_vec()
+            };
         }
 
         // Initialize or resize dot filter
@@ -730,7 +1065,25 @@ impl TemporalCoherence {
             }
         }
 
-        self.dot_filter.as_mut().unwrap().filter(dots)
+        let filtered = self.dot_filter.as_mut().unwrap().filter(dots);
+        if persistent {
+            self.persistence.filter(&filtered)
+        } else {
+            filtered
+        }
+    }
+
+    /// Applies the dot-level stages to a rendered grid in place.
+    ///
+    /// Runs the dot filter and persistence filter (whichever are enabled)
+    /// over the grid's dots. Colors and text characters are not touched.
+    pub fn process_grid(&mut self, grid: &mut BrailleGrid) {
+        if !self.config.dot_filter_enabled && self.config.persistence_frames <= 1 {
+            return;
+        }
+        let dots = grid.dot_field();
+        let filtered = self.process_dots(&dots, grid.dot_width(), grid.dot_height());
+        grid.set_dot_field(&filtered);
     }
 
     /// Updates the configuration.
@@ -742,6 +1095,8 @@ impl TemporalCoherence {
         if let Some(ref mut filter) = self.dot_filter {
             filter.set_alpha(config.dot_filter_alpha);
         }
+        self.persistence.set_frames(config.persistence_frames);
+        self.scene.set_threshold(config.scene_cut_threshold);
         self.config = config;
     }
 
@@ -755,11 +1110,18 @@ impl TemporalCoherence {
     ///
     /// Call this when seeking in a video or switching content.
     pub fn reset(&mut self) {
+        self.reset_history();
+        self.scene.reset();
+    }
+
+    /// Clears every stage's history except the scene detector's.
+    fn reset_history(&mut self) {
         self.hysteresis.reset();
         self.blender.reset();
         if let Some(ref mut filter) = self.dot_filter {
             filter.reset();
         }
+        self.persistence.reset();
     }
 }
 
@@ -955,6 +1317,9 @@ mod tests {
             frame_blend_alpha: 0.8,
             dot_filter_enabled: false,
             dot_filter_alpha: 0.5,
+            persistence_frames: 0,
+            scene_cut_enabled: true,
+            scene_cut_threshold: 0.5,
         };
 
         let mut coherence = TemporalCoherence::new(config);
@@ -967,4 +1332,90 @@ mod tests {
         assert_eq!(result.width(), 10);
         assert_eq!(result.height(), 10);
     }
+
+    #[test]
+    fn test_dot_persistence_filter_suppresses_alternating_noise() {
+        let mut filter = DotPersistenceFilter::new(2);
+        assert_eq!(filter.filter(&[false, false]), vec![false, false]);
+        // Dot 0 toggles every frame and never shows; dot 1 turns on for good
+        for frame in 0..6 {
+            let out = filter.filter(&[frame % 2 == 0, true]);
+            assert!(!out[0]);
+            assert_eq!(out[1], frame >= 1);
+        }
+
+        // A length change restarts history
+        assert_eq!(filter.filter(&[true]), vec![true]);
+    }
+
+    #[test]
+    fn test_process_grid_applies_persistence() {
+        let config = TemporalConfig {
+            persistence_frames: 2,
+            ..TemporalConfig::disabled()
+        };
+        let mut coherence = TemporalCoherence::new(config);
+
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        grid.set_cell_color(0, 0, crate::Color::rgb(9, 9, 9)).unwrap();
+        coherence.process_grid(&mut grid);
+
+        let mut noisy = grid.clone();
+        noisy.set_dot(3, 2).unwrap();
+        coherence.process_grid(&mut noisy);
+        assert!(!noisy.is_dot_set(3, 2));
+        assert_eq!(noisy.get_color(0, 0), Some(crate::Color::rgb(9, 9, 9)));
+
+        let mut held = grid.clone();
+        held.set_dot(3, 2).unwrap();
+        coherence.process_grid(&mut held);
+        assert!(held.is_dot_set(3, 2));
+    }
+
+    #[test]
+    fn test_blend_rgb_matches_per_channel_average() {
+        let config = TemporalConfig {
+            frame_blend_enabled: true,
+            frame_blend_alpha: 0.5,
+            ..TemporalConfig::disabled()
+        };
+        let mut coherence = TemporalCoherence::new(config);
+        coherence.blend_rgb(RgbImage::from_pixel(3, 2, image::Rgb([0, 100, 200])));
+        let out = coherence.blend_rgb(RgbImage::from_pixel(3, 2, image::Rgb([200, 100, 0])));
+        assert_eq!(out.get_pixel(2, 1).0, [100, 100, 100]);
+    }
+
+    #[test]
+    fn test_scene_change_detector_ignores_motion() {
+        let mut detector = SceneChangeDetector::new(0.5);
+        // A bright bar sliding across a dark frame keeps the same histogram
+        for offset in 0..4 {
+            let frame = GrayImage::from_fn(16, 8, |x, _| {
+                image::Luma([if (offset..offset + 4).contains(&x) { 220 } else { 30 }])
+            });
+            assert!(!detector.is_cut(&frame));
+            assert!(detector.difference() < 1e-6);
+        }
+        detector.reset();
+        assert!(!detector.is_cut(&GrayImage::from_pixel(16, 8, image::Luma([255]))));
+    }
+
+    #[test]
+    fn test_scene_cut_clears_blend_history() {
+        let config = TemporalConfig {
+            frame_blend_enabled: true,
+            frame_blend_alpha: 0.5,
+            scene_cut_enabled: true,
+            ..TemporalConfig::disabled()
+        };
+        let mut coherence = TemporalCoherence::new(config);
+        let dark = GrayImage::from_pixel(4, 4, image::Luma([0]));
+        let bright = GrayImage::from_pixel(4, 4, image::Luma([200]));
+
+        assert!(!coherence.detect_scene_cut(&dark));
+        coherence.blend_grayscale(dark);
+        assert!(coherence.detect_scene_cut(&bright));
+        // Without the cut this would blend to 100
+        assert_eq!(coherence.blend_grayscale(bright).get_pixel(0, 0).0[0], 200);
+    }
 }
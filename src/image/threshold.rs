@@ -57,8 +57,37 @@
 
 use crate::error::DotmaxError;
 use image::{DynamicImage, GrayImage, Luma};
+use std::ops::RangeInclusive;
 use tracing::debug;
 
+/// Valid brightness factors, shared by every renderer and player that takes one.
+pub(crate) const BRIGHTNESS_RANGE: RangeInclusive<f32> = 0.0..=2.0;
+
+/// Valid contrast factors.
+pub(crate) const CONTRAST_RANGE: RangeInclusive<f32> = 0.0..=2.0;
+
+/// Valid gamma values.
+pub(crate) const GAMMA_RANGE: RangeInclusive<f32> = 0.1..=3.0;
+
+/// Fails with [`DotmaxError::InvalidParameter`] naming `parameter_name` and
+/// `range` if `value` lies outside it (NaN always does).
+pub(crate) fn check_range(
+    parameter_name: &str,
+    value: f32,
+    range: &RangeInclusive<f32>,
+) -> Result<(), DotmaxError> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(DotmaxError::InvalidParameter {
+            parameter_name: parameter_name.to_string(),
+            value: value.to_string(),
+            min: format!("{:?}", range.start()),
+            max: format!("{:?}", range.end()),
+        })
+    }
+}
+
 /// Binary image representation with boolean pixels
 ///
 /// A binary image stores pixels as boolean values where:
@@ -210,50 +239,7 @@ pub fn otsu_threshold(gray: &GrayImage) -> u8 {
         histogram[pixel[0] as usize] += 1;
     }
 
-    let total_pixels = (gray.width() * gray.height()) as f64;
-
-    // Calculate total sum (weighted pixel values)
-    let sum_total: f64 = histogram
-        .iter()
-        .enumerate()
-        .map(|(i, &count)| i as f64 * count as f64)
-        .sum();
-
-    let mut max_variance = 0.0;
-    let mut best_threshold = 0u8;
-
-    let mut weight_background = 0.0;
-    let mut sum_background = 0.0;
-
-    #[allow(clippy::needless_range_loop)]
-    for threshold in 0..256 {
-        // Update background class statistics
-        weight_background += histogram[threshold] as f64;
-
-        if weight_background == 0.0 {
-            continue;
-        }
-
-        let weight_foreground = total_pixels - weight_background;
-
-        if weight_foreground == 0.0 {
-            break;
-        }
-
-        sum_background += threshold as f64 * histogram[threshold] as f64;
-
-        let mean_background = sum_background / weight_background;
-        let mean_foreground = (sum_total - sum_background) / weight_foreground;
-
-        // Calculate between-class variance
-        let variance =
-            weight_background * weight_foreground * (mean_background - mean_foreground).powi(2);
-
-        if variance > max_variance {
-            max_variance = variance;
-            best_threshold = threshold as u8;
-        }
-    }
+    let best_threshold = crate::analysis::otsu_from_histogram(&histogram);
 
     debug!("Calculated Otsu threshold: {}", best_threshold);
 
@@ -382,14 +368,7 @@ pub fn auto_threshold(image: &DynamicImage) -> BinaryImage {
 /// # }
 /// ```
 pub fn adjust_brightness(gray: &GrayImage, factor: f32) -> Result<GrayImage, DotmaxError> {
-    if !(0.0..=2.0).contains(&factor) {
-        return Err(DotmaxError::InvalidParameter {
-            parameter_name: "brightness factor".to_string(),
-            value: factor.to_string(),
-            min: "0.0".to_string(),
-            max: "2.0".to_string(),
-        });
-    }
+    check_range("brightness factor", factor, &BRIGHTNESS_RANGE)?;
 
     debug!("Adjusting brightness by factor {}", factor);
 
@@ -442,14 +421,7 @@ pub fn adjust_brightness(gray: &GrayImage, factor: f32) -> Result<GrayImage, Dot
 /// # }
 /// ```
 pub fn adjust_contrast(gray: &GrayImage, factor: f32) -> Result<GrayImage, DotmaxError> {
-    if !(0.0..=2.0).contains(&factor) {
-        return Err(DotmaxError::InvalidParameter {
-            parameter_name: "contrast factor".to_string(),
-            value: factor.to_string(),
-            min: "0.0".to_string(),
-            max: "2.0".to_string(),
-        });
-    }
+    check_range("contrast factor", factor, &CONTRAST_RANGE)?;
 
     debug!("Adjusting contrast by factor {}", factor);
 
@@ -502,14 +474,7 @@ pub fn adjust_contrast(gray: &GrayImage, factor: f32) -> Result<GrayImage, Dotma
 /// # }
 /// ```
 pub fn adjust_gamma(gray: &GrayImage, gamma: f32) -> Result<GrayImage, DotmaxError> {
-    if !(0.1..=3.0).contains(&gamma) {
-        return Err(DotmaxError::InvalidParameter {
-            parameter_name: "gamma".to_string(),
-            value: gamma.to_string(),
-            min: "0.1".to_string(),
-            max: "3.0".to_string(),
-        });
-    }
+    check_range("gamma", gamma, &GAMMA_RANGE)?;
 
     debug!("Applying gamma correction: {}", gamma);
 
@@ -0,0 +1,197 @@
+//! Zooming into and panning around an image.
+//!
+//! A [`Viewport`] says which part of the source image fills the render
+//! area: how far it's zoomed in and where it's centered. Set one with
+//! [`ImageRenderer::set_viewport`](super::ImageRenderer::set_viewport) (or
+//! [`Pipeline::set_viewport`](super::Pipeline::set_viewport)) and the next
+//! render shows just that part, at the usual target size.
+//!
+//! The pipeline scales the whole image once per zoom level and keeps the
+//! result; each render cuts the visible window out of it and runs the
+//! remaining stages on that window only. Panning therefore never resamples
+//! the image, and zooming resamples it once.
+//!
+//! Positions are normalized source coordinates, `0.0..=1.0` across and
+//! down the image, as in [`RoiRect`].
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::image::viewport::Viewport;
+//! use dotmax::image::roi::RoiRect;
+//!
+//! // Zoom in 4× on the top-left corner, then pan right by half a screen
+//! let view = Viewport::FULL.zoom(4.0).center_on(0.0, 0.0).pan(0.5, 0.0);
+//! assert_eq!(view.zoom_level(), 4.0);
+//! assert_eq!(view.center(), (0.25, 0.125));
+//!
+//! // Show one quadrant
+//! let quadrant = Viewport::crop(RoiRect { x: 0.5, y: 0.5, width: 0.5, height: 0.5 });
+//! assert_eq!(quadrant.zoom_level(), 2.0);
+//! assert_eq!(quadrant.center(), (0.75, 0.75));
+//! ```
+
+use super::roi::RoiRect;
+
+/// The zoom level and center of the part of an image being shown.
+///
+/// Zoom runs from 1.0, the whole image, to [`Viewport::MAX_ZOOM`]. The
+/// center is kept far enough from the edges that the view never runs off
+/// the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    zoom: f32,
+    center_x: f32,
+    center_y: f32,
+}
+
+impl Viewport {
+    /// The whole image.
+    pub const FULL: Self = Self {
+        zoom: 1.0,
+        center_x: 0.5,
+        center_y: 0.5,
+    };
+
+    /// The closest zoom allowed. The image is scaled to the render size
+    /// times the zoom, so this bounds the memory a zoomed render uses.
+    pub const MAX_ZOOM: f32 = 16.0;
+
+    /// Zooms by `factor` about the current center: 2.0 shows half as much
+    /// of the image in each direction, 0.5 twice as much. Ignored unless
+    /// `factor` is positive and finite.
+    #[must_use]
+    pub fn zoom(self, factor: f32) -> Self {
+        if !(factor.is_finite() && factor > 0.0) {
+            return self;
+        }
+        Self {
+            zoom: (self.zoom * factor).clamp(1.0, Self::MAX_ZOOM),
+            ..self
+        }
+        .clamped()
+    }
+
+    /// Moves the view by `dx` widths and `dy` heights of itself, so
+    /// `pan(0.1, 0.0)` shifts the picture a tenth of the screen whatever
+    /// the zoom. Positive values move toward the right and bottom of the
+    /// image.
+    #[must_use]
+    pub fn pan(self, dx: f32, dy: f32) -> Self {
+        if !(dx.is_finite() && dy.is_finite()) {
+            return self;
+        }
+        self.center_on(
+            self.center_x + dx / self.zoom,
+            self.center_y + dy / self.zoom,
+        )
+    }
+
+    /// Centers the view on normalized image position (`x`, `y`), or as
+    /// close as the zoom allows.
+    #[must_use]
+    pub fn center_on(self, x: f32, y: f32) -> Self {
+        if !(x.is_finite() && y.is_finite()) {
+            return self;
+        }
+        Self {
+            center_x: x,
+            center_y: y,
+            ..self
+        }
+        .clamped()
+    }
+
+    /// The view that fits `rect`, in normalized image coordinates, to the
+    /// render area: centered on it and zoomed until its longer side fills
+    /// the view.
+    #[must_use]
+    pub fn crop(rect: RoiRect) -> Self {
+        let span = rect.width.max(rect.height);
+        let zoom = if span > 0.0 {
+            1.0 / span
+        } else {
+            Self::MAX_ZOOM
+        };
+        Self::FULL
+            .zoom(zoom)
+            .center_on(rect.x + rect.width / 2.0, rect.y + rect.height / 2.0)
+    }
+
+    /// How far the view is zoomed in, from 1.0 to [`Self::MAX_ZOOM`].
+    #[must_use]
+    pub const fn zoom_level(&self) -> f32 {
+        self.zoom
+    }
+
+    /// The normalized image position at the center of the view.
+    #[must_use]
+    pub const fn center(&self) -> (f32, f32) {
+        (self.center_x, self.center_y)
+    }
+
+    /// Whether this shows the whole image.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.zoom <= 1.0
+    }
+
+    /// The center moved back inside the range where the view stays on the
+    /// image.
+    fn clamped(self) -> Self {
+        let half = 0.5 / self.zoom;
+        Self {
+            center_x: self.center_x.clamp(half, 1.0 - half),
+            center_y: self.center_y.clamp(half, 1.0 - half),
+            ..self
+        }
+    }
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_clamps_to_range() {
+        assert_eq!(Viewport::FULL.zoom(0.5), Viewport::FULL);
+        assert_eq!(Viewport::FULL.zoom(100.0).zoom_level(), Viewport::MAX_ZOOM);
+        assert_eq!(Viewport::FULL.zoom(f32::NAN), Viewport::FULL);
+        assert_eq!(Viewport::FULL.zoom(-2.0), Viewport::FULL);
+        assert!(Viewport::FULL.zoom(2.0).zoom(0.5).is_full());
+    }
+
+    #[test]
+    fn test_pan_stays_on_image() {
+        let view = Viewport::FULL.zoom(2.0);
+        assert_eq!(view.pan(0.25, 0.0).center(), (0.625, 0.5));
+        assert_eq!(view.pan(10.0, -10.0).center(), (0.75, 0.25));
+        // Nothing to pan over at full view
+        assert_eq!(Viewport::FULL.pan(0.5, 0.5), Viewport::FULL);
+    }
+
+    #[test]
+    fn test_zooming_out_recenters() {
+        let view = Viewport::FULL.zoom(4.0).center_on(1.0, 1.0).zoom(0.5);
+        assert_eq!(view.center(), (0.75, 0.75));
+    }
+
+    #[test]
+    fn test_crop_fits_longer_side() {
+        let view = Viewport::crop(RoiRect {
+            x: 0.0,
+            y: 0.0,
+            width: 0.25,
+            height: 0.125,
+        });
+        assert_eq!(view.zoom_level(), 4.0);
+        assert_eq!(view.center(), (0.125, 0.125));
+        assert!(Viewport::crop(RoiRect::FULL).is_full());
+    }
+}
@@ -0,0 +1,141 @@
+//! Keys mapped to a program's own actions.
+//!
+//! [`ActionMap`] is the game-side counterpart to
+//! [`Keymap`](crate::keymap::Keymap): it binds [`KeyBinding`]s to any
+//! action type the program defines, usually a small `Copy` enum, so the
+//! rest of the program matches on `Action::Jump` instead of key codes and
+//! the controls can be rebound in one place.
+//!
+//! # Examples
+//!
+//! ```
+//! use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+//! use dotmax::input::actions::ActionMap;
+//! use dotmax::input::InputEvent;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+//! enum Action {
+//!     Left,
+//!     Right,
+//!     Fire,
+//! }
+//!
+//! let actions = ActionMap::new()
+//!     .with("a", Action::Left)?
+//!     .with("left", Action::Left)?
+//!     .with("d", Action::Right)?
+//!     .with("space", Action::Fire)?;
+//!
+//! let key = KeyEvent::new(KeyCode::Left, KeyModifiers::NONE);
+//! assert_eq!(actions.action_for(&InputEvent::Terminal(Event::Key(key))), Some(Action::Left));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crossterm::event::KeyEvent;
+
+use super::InputEvent;
+use crate::error::DotmaxError;
+use crate::keymap::KeyBinding;
+
+/// Which keys trigger which of a program's actions.
+///
+/// A key triggers at most one action; an action can have any number of
+/// keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionMap<A> {
+    bindings: Vec<(KeyBinding, A)>,
+}
+
+impl<A> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+}
+
+impl<A: Copy + PartialEq> ActionMap<A> {
+    /// A map with no bindings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a binding written like `"ctrl+s"`, in the form
+    /// [`KeyBinding`] parses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidKeyBinding`] if `key` doesn't parse.
+    pub fn with(mut self, key: &str, action: A) -> Result<Self, DotmaxError> {
+        self.bind(key.parse()?, action);
+        Ok(self)
+    }
+
+    /// Makes `key` trigger `action`, replacing whatever it did before.
+    pub fn bind(&mut self, key: KeyBinding, action: A) {
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self.bindings.push((key, action));
+    }
+
+    /// Removes every key bound to `action`.
+    pub fn unbind(&mut self, action: A) {
+        self.bindings.retain(|&(_, bound)| bound != action);
+    }
+
+    /// The action `event` triggers, if it's a key press bound to one.
+    #[must_use]
+    pub fn action_for(&self, event: &InputEvent) -> Option<A> {
+        event.key().and_then(|key| self.action_for_key(key))
+    }
+
+    /// The action a crossterm key event triggers, if any. Key releases
+    /// trigger nothing.
+    #[must_use]
+    pub fn action_for_key(&self, key: &KeyEvent) -> Option<A> {
+        self.bindings
+            .iter()
+            .find(|(binding, _)| binding.matches(key))
+            .map(|&(_, action)| action)
+    }
+
+    /// The keys bound to `action`, in the order they were bound.
+    pub fn keys_for(&self, action: A) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |&&(_, bound)| bound == action)
+            .map(|&(key, _)| key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+
+    #[test]
+    fn test_rebinding() {
+        let mut actions = ActionMap::new()
+            .with("w", 'u')
+            .unwrap()
+            .with("up", 'u')
+            .unwrap()
+            .with("ctrl+w", 'q')
+            .unwrap();
+        assert!(ActionMap::new().with("hyper+w", 'u').is_err());
+
+        let w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE);
+        assert_eq!(actions.action_for_key(&w), Some('u'));
+        let ctrl_w = KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(actions.action_for_key(&ctrl_w), Some('q'));
+        let mut release = w;
+        release.kind = KeyEventKind::Release;
+        assert_eq!(actions.action_for_key(&release), None);
+
+        actions.bind("w".parse().unwrap(), 'j');
+        assert_eq!(actions.action_for_key(&w), Some('j'));
+        assert_eq!(actions.keys_for('u').count(), 1);
+        actions.unbind('u');
+        assert_eq!(actions.keys_for('u').count(), 0);
+    }
+}
@@ -0,0 +1,308 @@
+//! Game controllers, through the `gilrs` crate.
+//!
+//! [`Gamepads`] reports controller input as [`GamepadEvent`]s, which an
+//! [`EventPump`](super::EventPump) interleaves with terminal events, and
+//! keeps the current state of every button and axis in a
+//! [`GamepadState`] for games that would rather read the sticks each frame
+//! than follow events.
+//!
+//! Buttons and axes use gilrs' names, which follow the positions on an
+//! Xbox-style pad: [`Button::South`] is A on Xbox and Cross on
+//! PlayStation, and [`Button::LeftTrigger`] is the left bumper, with
+//! [`Button::LeftTrigger2`] the analog trigger below it.
+//!
+//! Requires the `gamepad` feature. On Linux, gilrs needs libudev.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::input::gamepad::{Button, GamepadEvent, Gamepads, Stick};
+//! use dotmax::input::{EventPump, InputEvent};
+//! use std::time::Duration;
+//!
+//! let mut pump = EventPump::new().with_gamepads(Gamepads::new()?);
+//! let (mut x, mut y) = (40.0_f32, 20.0_f32);
+//! loop {
+//!     while let Some(event) = pump.poll(Duration::from_millis(16))? {
+//!         if let InputEvent::Gamepad(GamepadEvent::ButtonPressed(_, Button::Start)) = event {
+//!             return Ok(());
+//!         }
+//!     }
+//!     // Move a player dot with the first pad's left stick
+//!     if let Some(state) = pump.gamepads().map(Gamepads::state) {
+//!         let (dx, dy) = state.stick(Default::default(), Stick::Left);
+//!         x += dx * 2.0;
+//!         y += dy * 2.0;
+//!     }
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use gilrs::{EventType, Gilrs};
+use tracing::warn;
+
+pub use gilrs::{Axis, Button};
+
+use crate::error::DotmaxError;
+
+/// Which controller an event came from: 0 for the first one connected,
+/// and so on. A controller that reconnects keeps its id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GamepadId(pub usize);
+
+impl From<gilrs::GamepadId> for GamepadId {
+    fn from(id: gilrs::GamepadId) -> Self {
+        Self(id.into())
+    }
+}
+
+/// An analog stick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stick {
+    /// The left stick
+    Left,
+    /// The right stick
+    Right,
+}
+
+/// A change on a controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+    /// A controller was plugged in or turned on
+    Connected(GamepadId),
+    /// A controller was unplugged or turned off; its buttons read as
+    /// released and its axes as centered from now on
+    Disconnected(GamepadId),
+    /// A button went down
+    ButtonPressed(GamepadId, Button),
+    /// A button came back up
+    ButtonReleased(GamepadId, Button),
+    /// A button's value changed, from 0.0 up to 1.0 when fully pressed.
+    /// Analog triggers send these as they move; digital buttons send them
+    /// alongside presses and releases
+    ButtonChanged(GamepadId, Button, f32),
+    /// A stick or other axis moved to a value from -1.0 to 1.0. Sticks
+    /// read positive to the right and up.
+    AxisMoved(GamepadId, Axis, f32),
+}
+
+impl GamepadEvent {
+    /// The controller the event came from.
+    #[must_use]
+    pub const fn id(&self) -> GamepadId {
+        match *self {
+            Self::Connected(id)
+            | Self::Disconnected(id)
+            | Self::ButtonPressed(id, _)
+            | Self::ButtonReleased(id, _)
+            | Self::ButtonChanged(id, ..)
+            | Self::AxisMoved(id, ..) => id,
+        }
+    }
+
+    /// Converts a gilrs event, dropping the kinds that aren't reported.
+    fn from_gilrs(event: gilrs::Event) -> Option<Self> {
+        let id = GamepadId::from(event.id);
+        Some(match event.event {
+            EventType::Connected => Self::Connected(id),
+            EventType::Disconnected => Self::Disconnected(id),
+            EventType::ButtonPressed(button, _) => Self::ButtonPressed(id, button),
+            EventType::ButtonReleased(button, _) => Self::ButtonReleased(id, button),
+            EventType::ButtonChanged(button, value, _) => Self::ButtonChanged(id, button, value),
+            EventType::AxisChanged(axis, value, _) => Self::AxisMoved(id, axis, value),
+            EventType::ButtonRepeated(..) | EventType::Dropped => return None,
+        })
+    }
+}
+
+/// Buttons held and axis positions of every controller, as of the last
+/// event applied.
+#[derive(Debug, Clone, Default)]
+pub struct GamepadState {
+    connected: HashSet<GamepadId>,
+    pressed: HashSet<(GamepadId, Button)>,
+    buttons: HashMap<(GamepadId, Button), f32>,
+    axes: HashMap<(GamepadId, Axis), f32>,
+}
+
+impl GamepadState {
+    /// No controllers connected.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the state with `event`.
+    pub fn apply(&mut self, event: &GamepadEvent) {
+        match *event {
+            GamepadEvent::Connected(id) => {
+                self.connected.insert(id);
+            }
+            GamepadEvent::Disconnected(id) => {
+                self.connected.remove(&id);
+                self.pressed.retain(|&(pad, _)| pad != id);
+                self.buttons.retain(|&(pad, _), _| pad != id);
+                self.axes.retain(|&(pad, _), _| pad != id);
+            }
+            GamepadEvent::ButtonPressed(id, button) => {
+                self.pressed.insert((id, button));
+            }
+            GamepadEvent::ButtonReleased(id, button) => {
+                self.pressed.remove(&(id, button));
+            }
+            GamepadEvent::ButtonChanged(id, button, value) => {
+                self.buttons.insert((id, button), value);
+            }
+            GamepadEvent::AxisMoved(id, axis, value) => {
+                self.axes.insert((id, axis), value);
+            }
+        }
+    }
+
+    /// Connected controllers, in id order.
+    #[must_use]
+    pub fn connected(&self) -> Vec<GamepadId> {
+        let mut ids: Vec<GamepadId> = self.connected.iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Whether `button` is held on controller `id`.
+    #[must_use]
+    pub fn is_pressed(&self, id: GamepadId, button: Button) -> bool {
+        self.pressed.contains(&(id, button))
+    }
+
+    /// How far `button` is pressed, 0.0 to 1.0. Digital buttons read 0.0
+    /// or 1.0.
+    #[must_use]
+    pub fn button_value(&self, id: GamepadId, button: Button) -> f32 {
+        self.buttons
+            .get(&(id, button))
+            .copied()
+            .unwrap_or_else(|| f32::from(u8::from(self.is_pressed(id, button))))
+    }
+
+    /// The position of `axis`, -1.0 to 1.0, centered at 0.0.
+    #[must_use]
+    pub fn axis(&self, id: GamepadId, axis: Axis) -> f32 {
+        self.axes.get(&(id, axis)).copied().unwrap_or(0.0)
+    }
+
+    /// The position of `stick` as `(x, y)`, each -1.0 to 1.0, with y
+    /// growing downward like grid coordinates so it can be added to a
+    /// position directly.
+    #[must_use]
+    pub fn stick(&self, id: GamepadId, stick: Stick) -> (f32, f32) {
+        let (x, y) = match stick {
+            Stick::Left => (Axis::LeftStickX, Axis::LeftStickY),
+            Stick::Right => (Axis::RightStickX, Axis::RightStickY),
+        };
+        (self.axis(id, x), -self.axis(id, y))
+    }
+}
+
+/// Every game controller on the system.
+pub struct Gamepads {
+    gilrs: Gilrs,
+    state: GamepadState,
+}
+
+impl Gamepads {
+    /// Starts watching for controllers. Those already connected are
+    /// reported as connected straight away, without an event.
+    ///
+    /// On platforms gilrs doesn't support, this succeeds with no
+    /// controllers ever connected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::GamepadError`] if the platform's controller
+    /// API can't be opened.
+    pub fn new() -> Result<Self, DotmaxError> {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => gilrs,
+            Err(gilrs::Error::NotImplemented(gilrs)) => {
+                warn!("Gamepads are not supported on this platform");
+                gilrs
+            }
+            Err(e) => return Err(DotmaxError::GamepadError(e.to_string())),
+        };
+        let mut state = GamepadState::new();
+        for (id, _) in gilrs.gamepads() {
+            state.apply(&GamepadEvent::Connected(id.into()));
+        }
+        Ok(Self { gilrs, state })
+    }
+
+    /// The next controller event, if one is waiting. Never blocks.
+    pub fn poll(&mut self) -> Option<GamepadEvent> {
+        while let Some(event) = self.gilrs.next_event() {
+            if let Some(event) = GamepadEvent::from_gilrs(event) {
+                self.state.apply(&event);
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Button and axis state as of the last event polled.
+    #[must_use]
+    pub const fn state(&self) -> &GamepadState {
+        &self.state
+    }
+
+    /// The name controller `id` reports, if it's connected.
+    #[must_use]
+    pub fn name(&self, id: GamepadId) -> Option<String> {
+        self.gilrs
+            .gamepads()
+            .find(|&(pad, _)| GamepadId::from(pad) == id)
+            .map(|(_, gamepad)| gamepad.name().to_string())
+    }
+}
+
+impl std::fmt::Debug for Gamepads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gamepads")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_follows_events() {
+        let pad = GamepadId(1);
+        let mut state = GamepadState::new();
+        for event in [
+            GamepadEvent::Connected(pad),
+            GamepadEvent::ButtonPressed(pad, Button::South),
+            GamepadEvent::ButtonChanged(pad, Button::RightTrigger2, 0.5),
+            GamepadEvent::AxisMoved(pad, Axis::LeftStickX, -0.25),
+            GamepadEvent::AxisMoved(pad, Axis::LeftStickY, 1.0),
+        ] {
+            assert_eq!(event.id(), pad);
+            state.apply(&event);
+        }
+        assert_eq!(state.connected(), [pad]);
+        assert!(state.is_pressed(pad, Button::South));
+        assert!(!state.is_pressed(GamepadId(0), Button::South));
+        assert_eq!(state.button_value(pad, Button::South), 1.0);
+        assert_eq!(state.button_value(pad, Button::RightTrigger2), 0.5);
+        // Stick up is toward the top of the grid
+        assert_eq!(state.stick(pad, Stick::Left), (-0.25, -1.0));
+        assert_eq!(state.stick(pad, Stick::Right), (0.0, 0.0));
+
+        state.apply(&GamepadEvent::ButtonReleased(pad, Button::South));
+        assert!(!state.is_pressed(pad, Button::South));
+        state.apply(&GamepadEvent::Disconnected(pad));
+        assert!(state.connected().is_empty());
+        assert_eq!(state.axis(pad, Axis::LeftStickX), 0.0);
+    }
+}
@@ -0,0 +1,187 @@
+//! Input for interactive programs: terminal events, and game controllers
+//! with the `gamepad` feature.
+//!
+//! An [`EventPump`] waits on every input source at once and hands back
+//! whichever event comes first as an [`InputEvent`], so a game loop polls
+//! one place between frames instead of juggling crossterm and a controller
+//! library itself.
+//!
+//! Terminal events are crossterm's own [`Event`]s: keys, mouse, resize,
+//! focus, and paste. Mouse events only arrive once mouse capture is
+//! enabled, which [`TerminalRenderer`](crate::TerminalRenderer) leaves to
+//! the application.
+//!
+//! On top of the raw events, [`actions::ActionMap`] turns key presses into
+//! the program's own actions and [`mouse::Mouse`] turns mouse reports into
+//! clicks and drags at dot coordinates. [`AnimationLoop`] can hand every
+//! event to a handler through
+//! [`on_event`](crate::animation::AnimationLoop::on_event), so a game
+//! doesn't need an event loop of its own.
+//!
+//! [`AnimationLoop`]: crate::animation::AnimationLoop
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use crossterm::event::KeyCode;
+//! use dotmax::input::EventPump;
+//! use std::time::Duration;
+//!
+//! let mut pump = EventPump::new();
+//! loop {
+//!     // Draw a frame here, then take input until the next one is due
+//!     while let Some(event) = pump.poll(Duration::from_millis(16))? {
+//!         if event.key().is_some_and(|key| key.code == KeyCode::Char('q')) {
+//!             return Ok(());
+//!         }
+//!     }
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+pub mod actions;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod mouse;
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+
+use crate::error::DotmaxError;
+
+#[cfg(feature = "gamepad")]
+use gamepad::{GamepadEvent, Gamepads};
+
+/// How long to wait on the terminal at a time while controllers are also
+/// being watched.
+#[cfg(feature = "gamepad")]
+const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(4);
+
+/// One event from any input source.
+// Not Eq: gamepad events carry f32 axis values
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    /// A key, mouse, resize, focus, or paste event from the terminal
+    Terminal(Event),
+    /// A game controller button, stick, or connection change
+    #[cfg(feature = "gamepad")]
+    Gamepad(GamepadEvent),
+}
+
+impl InputEvent {
+    /// The key, if this is a key press or repeat. Releases, which only some
+    /// terminals report, are left out.
+    #[must_use]
+    pub fn key(&self) -> Option<&KeyEvent> {
+        if let Self::Terminal(Event::Key(key)) = self {
+            (key.kind != KeyEventKind::Release).then_some(key)
+        } else {
+            None
+        }
+    }
+
+    /// The new terminal size in cells, if this is a resize.
+    #[must_use]
+    pub const fn resize(&self) -> Option<(u16, u16)> {
+        if let Self::Terminal(Event::Resize(width, height)) = self {
+            Some((*width, *height))
+        } else {
+            None
+        }
+    }
+}
+
+/// Waits on the terminal and any attached controllers for the next event.
+#[derive(Debug, Default)]
+pub struct EventPump {
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<Gamepads>,
+}
+
+impl EventPump {
+    /// A pump reading terminal events.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also reports events from `gamepads`.
+    #[cfg(feature = "gamepad")]
+    #[must_use]
+    pub fn with_gamepads(mut self, gamepads: Gamepads) -> Self {
+        self.gamepads = Some(gamepads);
+        self
+    }
+
+    /// The attached controllers, for reading button and stick state
+    /// between events.
+    #[cfg(feature = "gamepad")]
+    #[must_use]
+    pub const fn gamepads(&self) -> Option<&Gamepads> {
+        self.gamepads.as_ref()
+    }
+
+    /// The next event, waiting up to `timeout` for one. A zero timeout
+    /// only takes what's already pending.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if reading terminal events fails.
+    pub fn poll(&mut self, timeout: Duration) -> Result<Option<InputEvent>, DotmaxError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            #[cfg(feature = "gamepad")]
+            if let Some(event) = self.gamepads.as_mut().and_then(Gamepads::poll) {
+                return Ok(Some(InputEvent::Gamepad(event)));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            #[cfg(feature = "gamepad")]
+            let remaining = if self.gamepads.is_some() {
+                remaining.min(GAMEPAD_POLL_INTERVAL)
+            } else {
+                remaining
+            };
+            if event::poll(remaining)? {
+                return Ok(Some(InputEvent::Terminal(event::read()?)));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Every event already pending, without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if reading terminal events fails.
+    pub fn pending(&mut self) -> Result<Vec<InputEvent>, DotmaxError> {
+        let mut events = Vec::new();
+        while let Some(event) = self.poll(Duration::ZERO)? {
+            events.push(event);
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn test_event_accessors() {
+        let press = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        let mut release = press;
+        release.kind = KeyEventKind::Release;
+        assert_eq!(InputEvent::Terminal(Event::Key(press)).key(), Some(&press));
+        assert_eq!(InputEvent::Terminal(Event::Key(release)).key(), None);
+        assert_eq!(
+            InputEvent::Terminal(Event::Resize(80, 24)).resize(),
+            Some((80, 24))
+        );
+        assert_eq!(InputEvent::Terminal(Event::FocusGained).resize(), None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Mouse input in dot coordinates, with clicks and drags picked out.
+//!
+//! Terminals report the mouse a cell at a time. [`Mouse`] turns those
+//! reports into positions on the dot grid, so they line up with what's
+//! drawn, and follows the buttons to tell a click from a drag. Feed it
+//! every [`InputEvent`] and act on the [`Gesture`]s it hands back.
+//!
+//! Mouse events only arrive while mouse capture is on, which
+//! [`AnimationLoop::on_event`](crate::animation::AnimationLoop::on_event)
+//! does for you.
+//!
+//! # Examples
+//!
+//! ```
+//! use crossterm::event::{Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+//! use dotmax::input::mouse::{Gesture, Mouse};
+//! use dotmax::input::InputEvent;
+//!
+//! let mouse_event = |kind, column, row| {
+//!     InputEvent::Terminal(Event::Mouse(MouseEvent {
+//!         kind,
+//!         column,
+//!         row,
+//!         modifiers: KeyModifiers::NONE,
+//!     }))
+//! };
+//!
+//! let mut mouse = Mouse::new();
+//! mouse.update(&mouse_event(MouseEventKind::Down(MouseButton::Left), 3, 2));
+//! let gesture = mouse.update(&mouse_event(MouseEventKind::Up(MouseButton::Left), 3, 2));
+//! // Cell (3, 2) starts at dot (6, 8)
+//! assert_eq!(gesture, Some(Gesture::Click { button: MouseButton::Left, x: 6, y: 8 }));
+//! ```
+
+use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
+
+use super::InputEvent;
+
+/// Something done with the mouse, at dot coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gesture {
+    /// The pointer moved with no button held
+    Move {
+        /// Dot column
+        x: usize,
+        /// Dot row
+        y: usize,
+    },
+    /// A button went down
+    Press {
+        /// The button
+        button: MouseButton,
+        /// Dot column
+        x: usize,
+        /// Dot row
+        y: usize,
+    },
+    /// A button went down and came back up without the pointer moving
+    Click {
+        /// The button
+        button: MouseButton,
+        /// Dot column
+        x: usize,
+        /// Dot row
+        y: usize,
+    },
+    /// The pointer moved with a button held. Sent for every step, so
+    /// `to - from` is how far to pan or move whatever is being dragged.
+    Drag {
+        /// The button held
+        button: MouseButton,
+        /// Where the button went down
+        start: (usize, usize),
+        /// Where the pointer was before this step
+        from: (usize, usize),
+        /// Where the pointer is now
+        to: (usize, usize),
+    },
+    /// The button was released after a drag
+    Drop {
+        /// The button released
+        button: MouseButton,
+        /// Where the button went down
+        start: (usize, usize),
+        /// Where it came up
+        end: (usize, usize),
+    },
+    /// The wheel turned. Positive `lines` scroll down, negative up.
+    Scroll {
+        /// Dot column
+        x: usize,
+        /// Dot row
+        y: usize,
+        /// Notches turned
+        lines: i32,
+    },
+}
+
+/// A button being held, and whether it has moved since.
+#[derive(Debug, Clone, Copy)]
+struct Held {
+    button: MouseButton,
+    start: (usize, usize),
+    dragged: bool,
+}
+
+/// Follows the mouse across events.
+#[derive(Debug, Clone, Default)]
+pub struct Mouse {
+    origin: (u16, u16),
+    position: Option<(usize, usize)>,
+    held: Option<Held>,
+}
+
+impl Mouse {
+    /// A mouse over a grid drawn from the top-left corner of the terminal.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the terminal cell where the grid's top-left cell is drawn, for
+    /// grids that don't start at the corner, e.g. inside a
+    /// [`SafeArea`](crate::SafeArea). Events above or left of it are
+    /// ignored.
+    #[must_use]
+    pub const fn origin(mut self, column: u16, row: u16) -> Self {
+        self.origin = (column, row);
+        self
+    }
+
+    /// The last pointer position seen, in dots.
+    #[must_use]
+    pub const fn position(&self) -> Option<(usize, usize)> {
+        self.position
+    }
+
+    /// The button being held, if any.
+    #[must_use]
+    pub fn held(&self) -> Option<MouseButton> {
+        self.held.map(|held| held.button)
+    }
+
+    /// Converts a terminal cell to the dot at its top-left, or `None` if
+    /// it's outside the grid's origin.
+    #[must_use]
+    pub fn to_dots(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let x = column.checked_sub(self.origin.0)?;
+        let y = row.checked_sub(self.origin.1)?;
+        Some((usize::from(x) * 2, usize::from(y) * 4))
+    }
+
+    /// Takes in `event`, returning the gesture it completes, if it's a
+    /// mouse event.
+    pub fn update(&mut self, event: &InputEvent) -> Option<Gesture> {
+        if let InputEvent::Terminal(Event::Mouse(mouse)) = event {
+            self.handle(mouse)
+        } else {
+            None
+        }
+    }
+
+    /// Takes in a crossterm mouse event directly.
+    pub fn handle(&mut self, event: &MouseEvent) -> Option<Gesture> {
+        let (x, y) = self.to_dots(event.column, event.row)?;
+        let previous = self.position.replace((x, y));
+        match event.kind {
+            MouseEventKind::Down(button) => {
+                self.held = Some(Held {
+                    button,
+                    start: (x, y),
+                    dragged: false,
+                });
+                Some(Gesture::Press { button, x, y })
+            }
+            MouseEventKind::Drag(button) => {
+                let from = previous.unwrap_or((x, y));
+                if from == (x, y) {
+                    return None;
+                }
+                let held = self.held.get_or_insert(Held {
+                    button,
+                    start: from,
+                    dragged: false,
+                });
+                held.dragged = true;
+                Some(Gesture::Drag {
+                    button,
+                    start: held.start,
+                    from,
+                    to: (x, y),
+                })
+            }
+            MouseEventKind::Up(button) => match self.held.take() {
+                Some(held) if held.dragged => Some(Gesture::Drop {
+                    button,
+                    start: held.start,
+                    end: (x, y),
+                }),
+                Some(_) => Some(Gesture::Click { button, x, y }),
+                None => None,
+            },
+            MouseEventKind::Moved => (previous != Some((x, y))).then_some(Gesture::Move { x, y }),
+            MouseEventKind::ScrollDown => Some(Gesture::Scroll { x, y, lines: 1 }),
+            MouseEventKind::ScrollUp => Some(Gesture::Scroll { x, y, lines: -1 }),
+            MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_drag_and_drop() {
+        let left = MouseButton::Left;
+        let mut mouse = Mouse::new().origin(1, 1);
+        assert_eq!(mouse.handle(&event(MouseEventKind::Moved, 0, 5)), None);
+        assert_eq!(
+            mouse.handle(&event(MouseEventKind::Down(left), 2, 2)),
+            Some(Gesture::Press {
+                button: left,
+                x: 2,
+                y: 4
+            })
+        );
+        assert_eq!(mouse.held(), Some(left));
+        assert_eq!(
+            mouse.handle(&event(MouseEventKind::Drag(left), 4, 2)),
+            Some(Gesture::Drag {
+                button: left,
+                start: (2, 4),
+                from: (2, 4),
+                to: (6, 4)
+            })
+        );
+        assert_eq!(
+            mouse.handle(&event(MouseEventKind::Drag(left), 4, 3)),
+            Some(Gesture::Drag {
+                button: left,
+                start: (2, 4),
+                from: (6, 4),
+                to: (6, 8)
+            })
+        );
+        assert_eq!(
+            mouse.handle(&event(MouseEventKind::Up(left), 4, 3)),
+            Some(Gesture::Drop {
+                button: left,
+                start: (2, 4),
+                end: (6, 8)
+            })
+        );
+        assert_eq!(mouse.held(), None);
+        assert_eq!(mouse.position(), Some((6, 8)));
+        assert_eq!(
+            mouse.handle(&event(MouseEventKind::ScrollUp, 1, 1)),
+            Some(Gesture::Scroll {
+                x: 0,
+                y: 0,
+                lines: -1
+            })
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Key bindings for the interactive playback helpers.
+//!
+//! [`quick::show_file_with_keymap`](crate::quick::show_file_with_keymap)
+//! looks up every key press in a [`Keymap`] to decide what to do. The
+//! [default](Keymap::default) map is:
+//!
+//! | Action | Keys |
+//! |--------|------|
+//! | [`Quit`](KeyAction::Quit) | `q`, `esc` |
+//! | [`Pause`](KeyAction::Pause) | `space` |
+//! | [`SeekForward`](KeyAction::SeekForward) | `right` |
+//! | [`SeekBackward`](KeyAction::SeekBackward) | `left` |
+//! | [`CycleDithering`](KeyAction::CycleDithering) | `d` |
+//! | [`CycleScheme`](KeyAction::CycleScheme) | `s` |
+//! | [`ToggleColorMode`](KeyAction::ToggleColorMode) | `c` |
+//! | [`Screenshot`](KeyAction::Screenshot) | `p` |
+//! | [`ToggleDiagnostics`](KeyAction::ToggleDiagnostics) | `i` |
+//!
+//! Keys are written as a name, optionally preceded by `ctrl+`, `alt+`, or
+//! `shift+`: `"q"`, `"ctrl+c"`, `"pagedown"`, `"f5"`. Single characters
+//! match exactly, so `"Q"` is shift-q.
+//!
+//! With the `serde` feature a keymap (de)serializes as a table from action
+//! to keys, so end users can remap controls in a config file:
+//!
+//! ```toml
+//! quit = ["q", "ctrl+c"]
+//! pause = ["space", "k"]
+//! seek_forward = ["l", "right"]
+//! seek_backward = ["j", "left"]
+//! ```
+//!
+//! A loaded map contains only the actions it lists.
+//!
+//! # Examples
+//!
+//! ```
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use dotmax::keymap::{KeyAction, Keymap};
+//!
+//! let mut keymap = Keymap::default();
+//! keymap.bind("k".parse()?, KeyAction::Pause);
+//! keymap.bind("ctrl+c".parse()?, KeyAction::Quit);
+//!
+//! let event = KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE);
+//! assert_eq!(keymap.action_for(&event), Some(KeyAction::Pause));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::DotmaxError;
+
+/// Something a key can do during playback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum KeyAction {
+    /// Stop playback.
+    Quit,
+    /// Pause or resume.
+    Pause,
+    /// Skip ahead five seconds.
+    SeekForward,
+    /// Skip back five seconds.
+    SeekBackward,
+    /// Switch to the next dithering method.
+    CycleDithering,
+    /// Recolor frames with the next color scheme, then turn recoloring off.
+    CycleScheme,
+    /// Switch between monochrome, grayscale, and true color.
+    ToggleColorMode,
+    /// Save the current frame as text.
+    Screenshot,
+    /// Show or hide the frame timing diagnostics.
+    ToggleDiagnostics,
+}
+
+impl KeyAction {
+    /// Every action, in declaration order.
+    pub const ALL: [Self; 9] = [
+        Self::Quit,
+        Self::Pause,
+        Self::SeekForward,
+        Self::SeekBackward,
+        Self::CycleDithering,
+        Self::CycleScheme,
+        Self::ToggleColorMode,
+        Self::Screenshot,
+        Self::ToggleDiagnostics,
+    ];
+}
+
+/// A key plus the modifiers that must be held with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    /// The key.
+    pub code: KeyCode,
+    /// Ctrl, Alt, and Shift as they must be held. Shift is ignored for
+    /// character keys, whose case already says whether it was held.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Binds `code` with no modifiers.
+    #[must_use]
+    pub const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Binds `code` with `modifiers` held.
+    #[must_use]
+    pub const fn with_modifiers(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Whether `event` is a press of this key with exactly these modifiers.
+    #[must_use]
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let relevant = |modifiers: KeyModifiers| {
+            let mut modifiers =
+                modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+            if matches!(self.code, KeyCode::Char(_)) {
+                modifiers.remove(KeyModifiers::SHIFT);
+            }
+            modifiers
+        };
+        event.kind != KeyEventKind::Release
+            && event.code == self.code
+            && relevant(event.modifiers) == relevant(self.modifiers)
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = DotmaxError;
+
+    fn from_str(binding: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| DotmaxError::InvalidKeyBinding {
+            binding: binding.to_string(),
+            reason,
+        };
+
+        // A trailing "+" after a separator is the plus key itself
+        let (prefix, key) = match binding.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None if binding == "+" => (None, "+"),
+            None => match binding.rsplit_once('+') {
+                Some((prefix, key)) => (Some(prefix), key),
+                None => (None, binding),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.into_iter().flat_map(|prefix| prefix.split('+')) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(invalid(format!("unknown modifier {other:?}"))),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            (None, _) => return Err(invalid("missing key".to_string())),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(invalid(format!("unknown key {key:?}"))),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    /// Writes the binding in the form [`FromStr`] accepts.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Which keys trigger which [`KeyAction`]s.
+///
+/// A key triggers at most one action; an action can have any number of
+/// keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "std::collections::BTreeMap<KeyAction, Vec<String>>",
+        try_from = "std::collections::BTreeMap<KeyAction, Vec<String>>"
+    )
+)]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, KeyAction)>,
+}
+
+impl Default for Keymap {
+    /// The bindings listed in the [module docs](self).
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        for (code, action) in [
+            (KeyCode::Char('q'), KeyAction::Quit),
+            (KeyCode::Esc, KeyAction::Quit),
+            (KeyCode::Char(' '), KeyAction::Pause),
+            (KeyCode::Right, KeyAction::SeekForward),
+            (KeyCode::Left, KeyAction::SeekBackward),
+            (KeyCode::Char('d'), KeyAction::CycleDithering),
+            (KeyCode::Char('s'), KeyAction::CycleScheme),
+            (KeyCode::Char('c'), KeyAction::ToggleColorMode),
+            (KeyCode::Char('p'), KeyAction::Screenshot),
+            (KeyCode::Char('i'), KeyAction::ToggleDiagnostics),
+        ] {
+            keymap.bind(KeyBinding::new(code), action);
+        }
+        keymap
+    }
+}
+
+impl Keymap {
+    /// A keymap with no bindings.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Makes `key` trigger `action`, replacing whatever it did before.
+    pub fn bind(&mut self, key: KeyBinding, action: KeyAction) {
+        self.bindings.retain(|(bound, _)| *bound != key);
+        self.bindings.push((key, action));
+    }
+
+    /// Removes every key bound to `action`.
+    pub fn unbind(&mut self, action: KeyAction) {
+        self.bindings.retain(|&(_, bound)| bound != action);
+    }
+
+    /// The action `event` triggers, if any. Key releases trigger nothing.
+    #[must_use]
+    pub fn action_for(&self, event: &KeyEvent) -> Option<KeyAction> {
+        self.bindings
+            .iter()
+            .find(|(key, _)| key.matches(event))
+            .map(|&(_, action)| action)
+    }
+
+    /// The keys bound to `action`, in the order they were bound.
+    pub fn keys_for(&self, action: KeyAction) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |&&(_, bound)| bound == action)
+            .map(|&(key, _)| key)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Keymap> for std::collections::BTreeMap<KeyAction, Vec<String>> {
+    fn from(keymap: Keymap) -> Self {
+        let mut table = Self::new();
+        for (key, action) in keymap.bindings {
+            table.entry(action).or_default().push(key.to_string());
+        }
+        table
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<std::collections::BTreeMap<KeyAction, Vec<String>>> for Keymap {
+    type Error = DotmaxError;
+
+    fn try_from(
+        table: std::collections::BTreeMap<KeyAction, Vec<String>>,
+    ) -> Result<Self, Self::Error> {
+        let mut keymap = Self::empty();
+        for (action, keys) in table {
+            for key in keys {
+                keymap.bind(key.parse()?, action);
+            }
+        }
+        Ok(keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for spec in [
+            "q",
+            "Q",
+            "space",
+            "ctrl+c",
+            "alt+shift+left",
+            "f12",
+            "+",
+            "ctrl++",
+        ] {
+            let binding: KeyBinding = spec.parse().unwrap();
+            assert_eq!(binding.to_string(), spec);
+        }
+        let binding: KeyBinding = "Ctrl+PageDown".parse().unwrap();
+        assert_eq!(
+            binding,
+            KeyBinding::with_modifiers(KeyCode::PageDown, KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_names() {
+        for spec in ["", "ctrl+", "hyper+q", "enterr", "f99"] {
+            assert!(
+                matches!(
+                    spec.parse::<KeyBinding>(),
+                    Err(DotmaxError::InvalidKeyBinding { .. })
+                ),
+                "{spec:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char(' '), KeyModifiers::NONE)),
+            Some(KeyAction::Pause)
+        );
+        // Every action is reachable
+        for action in KeyAction::ALL {
+            assert!(keymap.keys_for(action).next().is_some(), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn test_modifiers_must_match() {
+        let mut keymap = Keymap::empty();
+        keymap.bind("ctrl+c".parse().unwrap(), KeyAction::Quit);
+        keymap.bind("Q".parse().unwrap(), KeyAction::Quit);
+
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('c'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(KeyAction::Quit)
+        );
+        // Terminals report shifted letters with SHIFT held
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('Q'), KeyModifiers::SHIFT)),
+            Some(KeyAction::Quit)
+        );
+    }
+
+    #[test]
+    fn test_rebinding_and_unbinding() {
+        let mut keymap = Keymap::default();
+        keymap.bind(KeyBinding::new(KeyCode::Char('q')), KeyAction::Pause);
+        let q = press(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(keymap.action_for(&q), Some(KeyAction::Pause));
+
+        keymap.unbind(KeyAction::Pause);
+        assert_eq!(keymap.action_for(&q), None);
+        assert_eq!(keymap.keys_for(KeyAction::Quit).count(), 1);
+    }
+
+    #[cfg(feature = "scene")]
+    #[test]
+    fn test_toml_round_trip() {
+        let keymap: Keymap = toml::from_str(
+            r#"
+            quit = ["q", "ctrl+c"]
+            seek_forward = ["l"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            keymap.action_for(&press(KeyCode::Char('l'), KeyModifiers::NONE)),
+            Some(KeyAction::SeekForward)
+        );
+        assert_eq!(keymap.keys_for(KeyAction::Pause).count(), 0);
+
+        let text = toml::to_string(&keymap).unwrap();
+        assert_eq!(toml::from_str::<Keymap>(&text).unwrap(), keymap);
+        assert!(toml::from_str::<Keymap>(r#"quit = ["hyper+q"]"#).is_err());
+    }
+}
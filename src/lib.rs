@@ -68,6 +68,31 @@
 //! a mutex to serialize access. [`BrailleGrid`] buffers can be prepared in
 //! parallel and then rendered sequentially.
 //!
+//! # Determinism
+//!
+//! The same inputs give the same output on every run and platform, and
+//! changing that counts as a breaking change, so golden-file tests of
+//! rendered grids stay stable across releases:
+//!
+//! - **Row-major order.** Whatever walks a grid or an image goes top to
+//!   bottom, then left to right: [`BrailleGrid::get_raw_patterns`],
+//!   [`BrailleGrid::dirty_rects`], the regions from
+//!   [`analysis::connected_components`] and the dots in each, and the
+//!   cells a renderer writes.
+//! - **Stable ties.** When candidates are equally good, the first in
+//!   row-major or input order wins: the most common color of a cell
+//!   (`image::color_mode::dominant_color`), the chart axis label kept when
+//!   two would overlap, and the palette index a color gets in Sixel and
+//!   GIF output. Equal sort keys keep their input order.
+//! - **No hash or directory order.** Hash maps and file system listings
+//!   never decide what comes out or in what order.
+//! - **Seeded randomness.** Effects that look random, such as
+//!   [`dissolve`](animation::dissolve), follow a fixed sequence; a shuffled
+//!   playlist repeats its order once given a seed.
+//! - **Parallel work.** Nothing runs in parallel yet. A parallel path must
+//!   combine its partial results in the order a single thread would, so
+//!   it matches the sequential output exactly.
+//!
 //! # License
 //!
 //! Licensed under either of:
@@ -86,10 +111,17 @@ pub mod render;
 // Utility modules (Epic 5)
 pub mod utils;
 
+// Compiled-in features and linked library versions
+pub mod build_info;
+
 // Re-export public types for convenience
+pub use build_info::{capabilities, BuildInfo};
 pub use error::DotmaxError;
-pub use grid::{BrailleGrid, Color};
-pub use render::{TerminalBackend, TerminalCapabilities, TerminalRenderer, TerminalType};
+pub use grid::{BlitColor, BlitMode, BrailleGrid, Color, DirtyRect};
+pub use render::{
+    render_once_to_stdout, render_to_writer, RenderMode, SafeArea, StatusLine, TerminalBackend,
+    TerminalCapabilities, TerminalRenderer, TerminalType,
+};
 
 // Re-export color capability detection (Epic 5)
 pub use utils::terminal_caps::{detect_color_capability, ColorCapability};
@@ -107,7 +139,7 @@ pub use color::scheme_builder::ColorSchemeBuilder;
 pub use color::apply::{apply_color_scheme, apply_colors_to_grid};
 
 // Re-export animation types (Epic 6, Stories 6.1, 6.2, 6.3, 6.4, 6.5)
-pub use animation::{AnimationLoop, AnimationLoopBuilder, DifferentialRenderer, FrameBuffer, FrameTimer, PrerenderedAnimation};
+pub use animation::{AnimationLoop, AnimationLoopBuilder, DifferentialRenderer, FrameBuffer, FrameSource, FrameTimer, PrerenderedAnimation};
 
 /// Convenience type alias for Results using `DotmaxError`
 ///
@@ -135,6 +167,72 @@ pub mod color;
 // Animation & frame management (Epic 6)
 pub mod animation;
 
+// Noise, plasma, checkerboards, and gradients as intensity buffers
+pub mod generate;
+
+// Named extension registries (frame sources, schemes, density sets, transitions)
+pub mod registry;
+
+// Legends and other chart furniture
+pub mod widgets;
+
+// Line, scatter, bar, and histogram charts
+pub mod plot;
+
+// Key bindings for the interactive helpers
+pub mod keymap;
+
+// Terminal and game controller input for interactive programs
+pub mod input;
+
+// Resume positions and settings for interactive viewers
+#[cfg(feature = "image")]
+pub mod viewer_state;
+
+// Connected regions and morphology on the dot field
+pub mod analysis;
+
+// Watermarks and other grid-on-grid overlays
+pub mod compose;
+
+// HTML and SVG export of grids
+pub mod export;
+
+// Soak tests for long-running animations
+pub mod testing;
+
+// Dot-accurate collision tests for games
+pub mod collide;
+
+// Tilemaps and other 2D game building blocks
+pub mod game;
+
+// Vectors, velocity integration, and bounds for moving objects
+pub mod motion;
+
+// Declarative TOML/JSON scenes
+#[cfg(feature = "scene")]
+pub mod scene;
+
+// Live-coded visuals with Rhai scripts
+#[cfg(feature = "script")]
+pub mod script;
+
+// TTF/OTF text banners
+#[cfg(feature = "text")]
+pub mod text;
+
+// Triangle meshes, cameras, and wireframe and shaded rendering for 3D models
+pub mod mesh;
+
+// Spectrum bars and waveforms for music visualizers
+#[cfg(feature = "viz")]
+pub mod viz;
+
+// CPU, memory, and network metrics for dashboards
+#[cfg(feature = "sysinfo")]
+pub mod system;
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -637,14 +637,90 @@ impl ApngPlayer {
         }
     }
 
-    /// Converts the current canvas to a BrailleGrid.
-    fn canvas_to_grid(&self) -> Result<BrailleGrid> {
-        // Create RGBA image from canvas
-        let img = image::RgbaImage::from_raw(self.canvas_width, self.canvas_height, self.canvas.clone())
+    /// Composites a decoded frame onto the canvas and records its disposal
+    /// for the next frame.
+    fn draw_frame(&mut self, frame: &ApngFrame) {
+        // Save canvas state if this frame has DisposeOp::Previous
+        if frame.dispose_op == DisposeOp::Previous {
+            self.save_canvas_state();
+        }
+
+        // Composite frame onto canvas
+        self.composite_frame(frame);
+
+        // Store disposal info for next frame
+        self.previous_dispose = frame.dispose_op;
+        self.previous_rect = (frame.x_offset, frame.y_offset, frame.width, frame.height);
+        self.current_frame += 1;
+        self.is_first_frame = false;
+    }
+
+    /// Composites frames from the start up to the one on screen at `at`,
+    /// without rendering any of them, and returns the canvas.
+    ///
+    /// Times past the end of the first pass give the last frame. Playback
+    /// restarts from the beginning afterwards.
+    pub(crate) fn canvas_at(&mut self, at: Duration) -> Result<image::RgbaImage> {
+        Ok(self.canvases_at(&[at])?.remove(0))
+    }
+
+    /// [`canvas_at`](Self::canvas_at) for several ascending times, decoding
+    /// the file once.
+    pub(crate) fn canvases_at(&mut self, times: &[Duration]) -> Result<Vec<image::RgbaImage>> {
+        self.reset();
+        let mut images = Vec::with_capacity(times.len());
+        let mut elapsed = Duration::ZERO;
+        let mut drawn = false;
+
+        for &at in times {
+            while elapsed <= at {
+                let Some(Ok(frame)) = self.decode_next_frame() else {
+                    break;
+                };
+                self.apply_previous_disposal();
+                self.draw_frame(&frame);
+                drawn = true;
+                elapsed += frame.delay;
+            }
+            if !drawn {
+                return Err(DotmaxError::ApngError {
+                    path: self.path.clone(),
+                    message: "No decodable frames".to_string(),
+                });
+            }
+            images.push(self.canvas_image()?);
+        }
+
+        self.reset();
+        Ok(images)
+    }
+
+    /// Total display time of one pass, found by decoding every frame.
+    ///
+    /// Playback restarts from the beginning afterwards.
+    pub(crate) fn pass_duration(&mut self) -> Duration {
+        self.reset();
+        let mut total = Duration::ZERO;
+        while let Some(Ok(frame)) = self.decode_next_frame() {
+            total += frame.delay;
+        }
+        self.reset();
+        total
+    }
+
+    /// Copies the current canvas into an image.
+    fn canvas_image(&self) -> Result<image::RgbaImage> {
+        image::RgbaImage::from_raw(self.canvas_width, self.canvas_height, self.canvas.clone())
             .ok_or_else(|| DotmaxError::ApngError {
                 path: self.path.clone(),
                 message: "Failed to create image from canvas".to_string(),
-            })?;
+            })
+    }
+
+    /// Converts the current canvas to a BrailleGrid.
+    fn canvas_to_grid(&self) -> Result<BrailleGrid> {
+        // Create RGBA image from canvas
+        let img = self.canvas_image()?;
 
         // Use ImageRenderer to convert to BrailleGrid
         let grid = ImageRenderer::new()
@@ -740,13 +816,7 @@ impl MediaPlayer for ApngPlayer {
             }
         };
 
-        // Save canvas state if this frame has DisposeOp::Previous
-        if frame.dispose_op == DisposeOp::Previous {
-            self.save_canvas_state();
-        }
-
-        // Composite frame onto canvas
-        self.composite_frame(&frame);
+        self.draw_frame(&frame);
 
         // Convert canvas to BrailleGrid
         let grid = match self.canvas_to_grid() {
@@ -754,12 +824,6 @@ impl MediaPlayer for ApngPlayer {
             Err(e) => return Some(Err(e)),
         };
 
-        // Store disposal info for next frame
-        self.previous_dispose = frame.dispose_op;
-        self.previous_rect = (frame.x_offset, frame.y_offset, frame.width, frame.height);
-        self.current_frame += 1;
-        self.is_first_frame = false;
-
         Some(Ok((grid, frame.delay)))
     }
 
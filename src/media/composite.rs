@@ -0,0 +1,346 @@
+//! Layering keyed media over a background.
+//!
+//! A player configured with a chroma key (see
+//! [`ChromaKey`](crate::image::chroma_key::ChromaKey)) reports a [`Matte`]
+//! for each frame: which dots show the subject and which were keyed out.
+//! [`KeyedComposite`] puts such a player in front of any
+//! [`FrameSource`] and shows the background through the keyed-out dots, the
+//! terminal version of a green-screen overlay.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # #[cfg(feature = "video")]
+//! # {
+//! use dotmax::animation::FrameSource;
+//! use dotmax::image::chroma_key::ChromaKey;
+//! use dotmax::media::{KeyedComposite, WebcamPlayer};
+//!
+//! # fn background() -> Box<dyn FrameSource> { unimplemented!() }
+//! let webcam = WebcamPlayer::builder()
+//!     .chroma_key(Some(ChromaKey::green()))
+//!     .build()?;
+//! let mut composite = KeyedComposite::new(webcam, background());
+//!
+//! while let Some(frame) = composite.next_frame(80, 24) {
+//!     let (grid, delay) = frame?;
+//!     // render `grid`, wait `delay`
+//! #   let _ = (grid, delay);
+//! }
+//! # }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::Duration;
+
+use super::MediaPlayer;
+use crate::animation::FrameSource;
+use crate::{BrailleGrid, DotmaxError};
+
+/// Per-dot opacity of a frame: `true` where the frame shows its subject,
+/// `false` where it was keyed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matte {
+    width: usize,
+    height: usize,
+    opaque: Vec<bool>,
+}
+
+impl Matte {
+    /// A `width × height` dot matte with every dot opaque.
+    #[must_use]
+    pub fn opaque(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            opaque: vec![true; width * height],
+        }
+    }
+
+    /// A `width × height` dot matte whose opacity at `(x, y)` is `f(x, y)`.
+    #[must_use]
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> bool) -> Self {
+        Self {
+            width,
+            height,
+            opaque: (0..width * height)
+                .map(|i| f(i % width, i / width))
+                .collect(),
+        }
+    }
+
+    /// Width in dots.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in dots.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the dot at `(x, y)` is opaque. Dots outside the matte are not.
+    #[must_use]
+    pub fn is_opaque(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.opaque[y * self.width + x]
+    }
+
+    /// Layers `foreground` over `background`: opaque dots come from the
+    /// foreground, the rest from the background.
+    ///
+    /// The result has the foreground's size. Cells with no opaque dots take
+    /// the background's cell whole, including its color and any text;
+    /// cells with at least one opaque dot keep the foreground's color,
+    /// falling back to the background's. Parts of the background outside
+    /// the foreground are ignored.
+    #[must_use]
+    pub fn composite(&self, foreground: &BrailleGrid, background: &BrailleGrid) -> BrailleGrid {
+        let mut out = foreground.clone();
+        let same_size = foreground.dimensions() == background.dimensions();
+
+        for cell_y in 0..foreground.height() {
+            for cell_x in 0..foreground.width() {
+                let opaque = (0..8).any(|i| self.is_opaque(cell_x * 2 + i % 2, cell_y * 4 + i / 2));
+                if !opaque {
+                    if same_size {
+                        out.copy_cell_from(background, cell_x, cell_y);
+                    } else {
+                        let _ = out.clear_region(cell_x, cell_y, 1, 1);
+                    }
+                } else if foreground.get_color(cell_x, cell_y).is_none() {
+                    if let Some(color) = background.get_color(cell_x, cell_y) {
+                        let _ = out.set_cell_color(cell_x, cell_y, color);
+                    }
+                }
+            }
+        }
+
+        let width = foreground.dot_width();
+        let dots: Vec<bool> = (0..width * foreground.dot_height())
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                if self.is_opaque(x, y) {
+                    foreground.is_dot_set(x, y)
+                } else {
+                    background.is_dot_set(x, y)
+                }
+            })
+            .collect();
+        out.set_dot_field(&dots);
+        out
+    }
+}
+
+/// A keyed player layered over a background source.
+///
+/// Each frame comes from the foreground player at its own pace; the
+/// background is asked for a frame of the same size and shows through
+/// wherever the player's [`matte`](MediaPlayer::matte) is transparent.
+/// Players without a matte cover the background entirely. A background
+/// that runs out is rewound.
+///
+/// Implements [`FrameSource`], so it slots into anything that plays one.
+/// Requested sizes are passed on to the player through
+/// [`handle_resize`](MediaPlayer::handle_resize).
+pub struct KeyedComposite<P> {
+    foreground: P,
+    background: Box<dyn FrameSource>,
+    size: Option<(usize, usize)>,
+}
+
+impl<P: std::fmt::Debug> std::fmt::Debug for KeyedComposite<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyedComposite")
+            .field("foreground", &self.foreground)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: MediaPlayer> KeyedComposite<P> {
+    /// Layers `foreground` over `background`.
+    #[must_use]
+    pub fn new(foreground: P, background: Box<dyn FrameSource>) -> Self {
+        Self {
+            foreground,
+            background,
+            size: None,
+        }
+    }
+
+    /// The foreground player.
+    #[must_use]
+    pub const fn foreground(&self) -> &P {
+        &self.foreground
+    }
+
+    /// The foreground player, e.g. to change its key at runtime.
+    pub fn foreground_mut(&mut self) -> &mut P {
+        &mut self.foreground
+    }
+
+    /// Replaces the background source.
+    pub fn set_background(&mut self, background: Box<dyn FrameSource>) {
+        self.background = background;
+    }
+}
+
+impl<P: MediaPlayer> FrameSource for KeyedComposite<P> {
+    fn next_frame(
+        &mut self,
+        width: usize,
+        height: usize,
+    ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+        if self.size != Some((width, height)) {
+            self.foreground.handle_resize(width, height);
+            self.size = Some((width, height));
+        }
+
+        let (foreground, delay) = match self.foreground.next_frame()? {
+            Ok(frame) => frame,
+            Err(e) => return Some(Err(e)),
+        };
+        let Some(matte) = self.foreground.matte() else {
+            return Some(Ok((foreground, delay)));
+        };
+
+        let (fg_width, fg_height) = foreground.dimensions();
+        match background_frame(self.background.as_mut(), fg_width, fg_height) {
+            Some(Ok(background)) => Some(Ok((matte.composite(&foreground, &background), delay))),
+            Some(Err(e)) => Some(Err(e)),
+            None => Some(Ok((foreground, delay))),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.foreground.reset();
+        self.background.reset();
+    }
+}
+
+/// Next frame of `background` at `width × height`, rewinding once if the
+/// source has ended.
+fn background_frame(
+    background: &mut dyn FrameSource,
+    width: usize,
+    height: usize,
+) -> Option<Result<BrailleGrid, DotmaxError>> {
+    let frame = background.next_frame(width, height).or_else(|| {
+        background.reset();
+        background.next_frame(width, height)
+    })?;
+    Some(frame.map(|(grid, _)| grid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    /// Solid frames with the left half of the dots keyed out.
+    #[derive(Debug)]
+    struct HalfKeyed {
+        matte: Option<Matte>,
+        size: (usize, usize),
+    }
+
+    impl MediaPlayer for HalfKeyed {
+        fn next_frame(&mut self) -> Option<crate::Result<(BrailleGrid, Duration)>> {
+            let (width, height) = self.size;
+            let mut grid = BrailleGrid::new(width, height).ok()?;
+            grid.set_raw_patterns(&vec![0xFF; width * height]);
+            self.matte = Some(Matte::from_fn(width * 2, height * 4, |x, _| x >= width));
+            Some(Ok((grid, Duration::from_millis(40))))
+        }
+
+        fn reset(&mut self) {}
+
+        fn frame_count(&self) -> Option<usize> {
+            None
+        }
+
+        fn loop_count(&self) -> Option<u16> {
+            None
+        }
+
+        fn handle_resize(&mut self, width: usize, height: usize) {
+            self.size = (width, height);
+        }
+
+        fn matte(&self) -> Option<&Matte> {
+            self.matte.as_ref()
+        }
+    }
+
+    /// One frame with its top-left cell's text set, then exhausted.
+    struct Labeled {
+        shown: bool,
+    }
+
+    impl FrameSource for Labeled {
+        fn next_frame(
+            &mut self,
+            width: usize,
+            height: usize,
+        ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+            if self.shown {
+                return None;
+            }
+            self.shown = true;
+            let mut grid = BrailleGrid::new(width, height).ok()?;
+            grid.set_char(0, 0, 'B').ok()?;
+            grid.set_cell_color(0, 0, Color::rgb(0, 0, 255)).ok()?;
+            Some(Ok((grid, Duration::from_secs(1))))
+        }
+
+        fn reset(&mut self) {
+            self.shown = false;
+        }
+    }
+
+    #[test]
+    fn test_matte_composite_per_dot() {
+        let mut foreground = BrailleGrid::new(2, 1).unwrap();
+        foreground.set_raw_patterns(&[0xFF, 0xFF]);
+        let mut background = BrailleGrid::new(2, 1).unwrap();
+        background.set_dot(0, 0).unwrap();
+
+        // Only the right column of the second cell is opaque
+        let matte = Matte::from_fn(4, 4, |x, _| x == 3);
+        let out = matte.composite(&foreground, &background);
+
+        assert!(out.is_dot_set(0, 0));
+        assert!(!out.is_dot_set(1, 0));
+        assert!(!out.is_dot_set(2, 0));
+        assert!((0..4).all(|y| out.is_dot_set(3, y)));
+    }
+
+    #[test]
+    fn test_keyed_composite_shows_background() {
+        let player = HalfKeyed {
+            matte: None,
+            size: (0, 0),
+        };
+        let mut composite = KeyedComposite::new(player, Box::new(Labeled { shown: false }));
+
+        for _ in 0..2 {
+            // The background ends after one frame and is rewound
+            let (grid, delay) = composite.next_frame(4, 2).unwrap().unwrap();
+            assert_eq!(delay, Duration::from_millis(40));
+            assert_eq!(grid.get_char(0, 0), 'B');
+            assert_eq!(grid.get_color(0, 0), Some(Color::rgb(0, 0, 255)));
+            assert!(!grid.is_dot_set(0, 4));
+            assert!(grid.is_dot_set(7, 7));
+        }
+    }
+
+    #[test]
+    fn test_opaque_matte() {
+        let matte = Matte::opaque(2, 4);
+        assert!(matte.is_opaque(1, 3));
+        assert!(!matte.is_opaque(2, 0));
+        assert_eq!((matte.width(), matte.height()), (2, 4));
+    }
+}
@@ -0,0 +1,402 @@
+//! Controlling a running player from other threads.
+//!
+//! A render loop owns its player, so anything else that wants to pause it
+//! or change its look (an input thread, a network handler, a timer) would
+//! otherwise need a lock around the player. [`Controlled`] wraps a player
+//! and hands out [`PlayerControl`] handles instead: cheap, cloneable
+//! senders whose commands the wrapper applies at the start of its next
+//! frame.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use crossterm::event::{self, Event, KeyCode};
+//! use dotmax::media::{GifPlayer, MediaPlayer};
+//!
+//! let mut player = GifPlayer::new("clip.gif")?.controlled();
+//! let control = player.control();
+//!
+//! // Keys are read on their own thread; the render loop never blocks on them
+//! std::thread::spawn(move || {
+//!     while let Ok(Event::Key(key)) = event::read() {
+//!         let still_playing = match key.code {
+//!             KeyCode::Char(' ') => control.toggle_pause(),
+//!             KeyCode::Right => control.seek_forward(Duration::from_secs(5)),
+//!             KeyCode::Left => control.seek_backward(Duration::from_secs(5)),
+//!             _ => true,
+//!         };
+//!         if !still_playing {
+//!             break;
+//!         }
+//!     }
+//! });
+//!
+//! while let Some(frame) = player.next_frame() {
+//!     let (grid, delay) = frame?;
+//!     // render `grid`, wait `delay`
+//! #   let _ = (grid, delay);
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+
+use super::{DecodeTiming, Matte, MediaPlayer};
+#[cfg(feature = "image")]
+use crate::image::RenderOptions;
+use crate::{BrailleGrid, Result};
+
+/// How long a paused player's repeated frame stays up before the wrapper
+/// checks for commands again.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A command sent through a [`PlayerControl`].
+#[derive(Debug, Clone, Copy)]
+enum Command {
+    Pause,
+    Resume,
+    TogglePause,
+    SeekTo(Duration),
+    SeekForward(Duration),
+    SeekBackward(Duration),
+    #[cfg(feature = "image")]
+    RenderOptions(RenderOptions),
+}
+
+/// A handle for steering a [`Controlled`] player from any thread.
+///
+/// Clone it freely; every clone talks to the same player. Commands take
+/// effect when the player produces its next frame. Each method returns
+/// `false` once the player has been dropped, so a controlling thread knows
+/// when to stop.
+#[derive(Debug, Clone)]
+pub struct PlayerControl {
+    sender: Sender<Command>,
+}
+
+// Fire-and-forget is the common case; only controlling threads that outlive
+// the player need to check the result
+#[allow(clippy::must_use_candidate)]
+impl PlayerControl {
+    /// Pauses playback, repeating the current frame.
+    pub fn pause(&self) -> bool {
+        self.send(Command::Pause)
+    }
+
+    /// Resumes paused playback.
+    pub fn resume(&self) -> bool {
+        self.send(Command::Resume)
+    }
+
+    /// Pauses if playing, resumes if paused.
+    pub fn toggle_pause(&self) -> bool {
+        self.send(Command::TogglePause)
+    }
+
+    /// Jumps to `position` from the start (see [`MediaPlayer::seek`]).
+    pub fn seek(&self, position: Duration) -> bool {
+        self.send(Command::SeekTo(position))
+    }
+
+    /// Skips ahead by `offset`, stopping at the end if the length is known.
+    pub fn seek_forward(&self, offset: Duration) -> bool {
+        self.send(Command::SeekForward(offset))
+    }
+
+    /// Skips back by `offset`, stopping at the start.
+    pub fn seek_backward(&self, offset: Duration) -> bool {
+        self.send(Command::SeekBackward(offset))
+    }
+
+    /// Replaces the player's render settings (see
+    /// [`MediaPlayer::set_render_options`]). Invalid options surface as an
+    /// error from the player's next frame.
+    #[cfg(feature = "image")]
+    pub fn set_render_options(&self, options: RenderOptions) -> bool {
+        self.send(Command::RenderOptions(options))
+    }
+
+    fn send(&self, command: Command) -> bool {
+        self.sender.send(command).is_ok()
+    }
+}
+
+/// A [`MediaPlayer`] that takes commands from [`PlayerControl`] handles.
+///
+/// Created by [`MediaPlayer::controlled`]. While paused it keeps returning
+/// the last frame with a short delay, so the caller's render loop keeps
+/// running (and keeps applying commands) without any changes.
+///
+/// Render options apply from the next decoded frame; a paused frame shows
+/// them once playback resumes or seeks.
+pub struct Controlled<P> {
+    player: P,
+    sender: Sender<Command>,
+    commands: Receiver<Command>,
+    paused: bool,
+    position: Duration,
+    last_frame: Option<BrailleGrid>,
+}
+
+impl<P: std::fmt::Debug> std::fmt::Debug for Controlled<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Controlled")
+            .field("player", &self.player)
+            .field("paused", &self.paused)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: MediaPlayer> Controlled<P> {
+    /// Wraps `player`, starting unpaused.
+    #[must_use]
+    pub fn new(player: P) -> Self {
+        let (sender, commands) = channel();
+        Self {
+            player,
+            sender,
+            commands,
+            paused: false,
+            position: Duration::ZERO,
+            last_frame: None,
+        }
+    }
+
+    /// A new handle to this player.
+    #[must_use]
+    pub fn control(&self) -> PlayerControl {
+        PlayerControl {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Whether playback is paused, as of the last frame.
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Where the next frame starts: the sum of the delays of the frames
+    /// returned since the start, adjusted by seeks.
+    #[must_use]
+    pub const fn position(&self) -> Duration {
+        self.position
+    }
+
+    /// The wrapped player.
+    pub const fn player(&self) -> &P {
+        &self.player
+    }
+
+    /// The wrapped player, mutably.
+    pub fn player_mut(&mut self) -> &mut P {
+        &mut self.player
+    }
+
+    /// Unwraps the player. Outstanding handles start returning `false`.
+    pub fn into_inner(self) -> P {
+        self.player
+    }
+
+    /// Applies every command received since the last frame.
+    fn apply_commands(&mut self) -> Result<()> {
+        while let Ok(command) = self.commands.try_recv() {
+            match command {
+                Command::Pause => self.paused = true,
+                Command::Resume => self.paused = false,
+                Command::TogglePause => self.paused = !self.paused,
+                Command::SeekTo(position) => self.jump_to(position)?,
+                Command::SeekForward(offset) => {
+                    let target = self.position.saturating_add(offset);
+                    let end = self.player.duration().unwrap_or(Duration::MAX);
+                    self.jump_to(target.min(end))?;
+                }
+                Command::SeekBackward(offset) => {
+                    self.jump_to(self.position.saturating_sub(offset))?;
+                }
+                #[cfg(feature = "image")]
+                Command::RenderOptions(options) => self.player.set_render_options(options)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn jump_to(&mut self, position: Duration) -> Result<()> {
+        self.position = self.player.seek(position)?;
+        // Show the new position even while paused
+        self.last_frame = None;
+        Ok(())
+    }
+}
+
+impl<P: MediaPlayer> MediaPlayer for Controlled<P> {
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        if let Err(e) = self.apply_commands() {
+            return Some(Err(e));
+        }
+        if self.paused {
+            if let Some(grid) = &self.last_frame {
+                return Some(Ok((grid.clone(), PAUSE_POLL_INTERVAL)));
+            }
+        }
+
+        let (grid, delay) = match self.player.next_frame()? {
+            Ok(frame) => frame,
+            Err(e) => return Some(Err(e)),
+        };
+        self.position += delay;
+        self.last_frame = Some(grid.clone());
+        Some(Ok((grid, delay)))
+    }
+
+    fn reset(&mut self) {
+        self.player.reset();
+        self.position = Duration::ZERO;
+        self.last_frame = None;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        self.player.frame_count()
+    }
+
+    fn loop_count(&self) -> Option<u16> {
+        self.player.loop_count()
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.player.duration()
+    }
+
+    fn matte(&self) -> Option<&Matte> {
+        self.player.matte()
+    }
+
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        self.player.decode_timing()
+    }
+
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        self.player.handle_resize(width, height);
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration> {
+        self.jump_to(position)?;
+        Ok(self.position)
+    }
+
+    #[cfg(feature = "image")]
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        self.player.set_render_options(options)
+    }
+
+    #[cfg(feature = "image")]
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        self.player.current_render_options()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ten 100ms frames; frame `n` has dot `(n, 0)` set.
+    #[derive(Debug, Default)]
+    struct Ticker {
+        next: usize,
+    }
+
+    impl MediaPlayer for Ticker {
+        fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+            if self.next == 10 {
+                return None;
+            }
+            let mut grid = BrailleGrid::new(5, 1).ok()?;
+            grid.set_dot(self.next, 0).ok()?;
+            self.next += 1;
+            Some(Ok((grid, Duration::from_millis(100))))
+        }
+
+        fn reset(&mut self) {
+            self.next = 0;
+        }
+
+        fn frame_count(&self) -> Option<usize> {
+            Some(10)
+        }
+
+        fn loop_count(&self) -> Option<u16> {
+            None
+        }
+
+        fn duration(&self) -> Option<Duration> {
+            Some(Duration::from_secs(1))
+        }
+    }
+
+    /// Which frame of a `Ticker` this is.
+    fn frame_number(frame: Option<Result<(BrailleGrid, Duration)>>) -> usize {
+        let (grid, _) = frame.unwrap().unwrap();
+        (0..10).find(|&x| grid.is_dot_set(x, 0)).unwrap()
+    }
+
+    #[test]
+    fn test_pause_repeats_frame() {
+        let mut player = Ticker::default().controlled();
+        let control = player.control();
+        assert_eq!(frame_number(player.next_frame()), 0);
+
+        assert!(control.pause());
+        let (grid, delay) = player.next_frame().unwrap().unwrap();
+        assert!(grid.is_dot_set(0, 0));
+        assert_eq!(delay, PAUSE_POLL_INTERVAL);
+        assert!(player.is_paused());
+
+        assert!(control.toggle_pause());
+        assert_eq!(frame_number(player.next_frame()), 1);
+    }
+
+    #[test]
+    fn test_seek_from_another_thread() {
+        let mut player = Ticker::default().controlled();
+        let control = player.control();
+        player.next_frame();
+
+        std::thread::spawn(move || control.seek(Duration::from_millis(500)))
+            .join()
+            .unwrap();
+        assert_eq!(frame_number(player.next_frame()), 5);
+        assert_eq!(player.position(), Duration::from_millis(600));
+
+        let control = player.control();
+        control.seek_backward(Duration::from_secs(10));
+        assert_eq!(frame_number(player.next_frame()), 0);
+
+        // Clamped to the end: nothing left to play
+        control.seek_forward(Duration::from_secs(10));
+        assert!(player.next_frame().is_none());
+        assert_eq!(player.position(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_seek_while_paused_shows_new_position() {
+        let mut player = Ticker::default().controlled();
+        let control = player.control();
+        player.next_frame();
+        control.pause();
+        control.seek(Duration::from_millis(300));
+
+        assert_eq!(frame_number(player.next_frame()), 3);
+        assert_eq!(frame_number(player.next_frame()), 3);
+    }
+
+    #[test]
+    fn test_control_outlives_player() {
+        let player = Ticker::default().controlled();
+        let control = player.control();
+        drop(player);
+        assert!(!control.pause());
+    }
+}
@@ -44,6 +44,12 @@ pub enum MediaFormat {
     /// Animated PNG (APNG)
     AnimatedPng,
 
+    /// Animated WebP
+    ///
+    /// Initially detected as `StaticImage(WebP)`, then promoted to
+    /// `AnimatedWebp` if the file has animation chunks.
+    AnimatedWebp,
+
     /// SVG vector graphics
     Svg,
 
@@ -60,6 +66,7 @@ impl std::fmt::Display for MediaFormat {
             Self::StaticImage(img) => write!(f, "static image ({})", img),
             Self::AnimatedGif => write!(f, "animated GIF"),
             Self::AnimatedPng => write!(f, "animated PNG (APNG)"),
+            Self::AnimatedWebp => write!(f, "animated WebP"),
             Self::Svg => write!(f, "SVG vector graphics"),
             Self::Video(codec) => write!(f, "video ({})", codec),
             Self::Unknown => write!(f, "unknown format"),
@@ -222,6 +229,11 @@ pub fn detect_format(path: impl AsRef<Path>) -> Result<MediaFormat> {
         return Ok(MediaFormat::AnimatedPng);
     }
 
+    #[cfg(feature = "image")]
+    if matches!(format, MediaFormat::StaticImage(ImageFormat::WebP)) && is_animated_webp(path)? {
+        return Ok(MediaFormat::AnimatedWebp);
+    }
+
     Ok(format)
 }
 
@@ -657,6 +669,61 @@ pub fn is_animated_png_from_bytes(bytes: &[u8]) -> bool {
     png_reader.info().animation_control().is_some()
 }
 
+// ============================================================================
+// Animated WebP Detection
+// ============================================================================
+
+/// Checks if a WebP file is animated.
+///
+/// Animated WebP files use the extended (`VP8X`) format with the animation
+/// flag set, followed by `ANIM` and `ANMF` chunks. Only the chunk headers are
+/// read, not the frame data.
+///
+/// # Returns
+///
+/// - `Ok(true)` if the WebP is animated
+/// - `Ok(false)` if it is a still image, or can't be parsed as WebP
+///
+/// # Errors
+///
+/// Returns `DotmaxError::Terminal` if the file cannot be opened.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::media::is_animated_webp;
+///
+/// if is_animated_webp("animation.webp")? {
+///     println!("This WebP is animated!");
+/// }
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn is_animated_webp(path: impl AsRef<Path>) -> Result<bool> {
+    use std::io::BufReader;
+
+    let path = path.as_ref();
+    let file = File::open(path)?;
+
+    match image_webp::WebPDecoder::new(BufReader::new(file)) {
+        Ok(decoder) => Ok(decoder.is_animated()),
+        Err(e) => {
+            tracing::debug!("WebP decode error for {:?}: {:?}, treating as static", path, e);
+            Ok(false)
+        }
+    }
+}
+
+/// Checks if WebP data is animated by reading from raw bytes.
+///
+/// Returns `false` for still, corrupted, or non-WebP data.
+#[cfg(feature = "image")]
+#[must_use]
+pub fn is_animated_webp_from_bytes(bytes: &[u8]) -> bool {
+    image_webp::WebPDecoder::new(std::io::Cursor::new(bytes))
+        .is_ok_and(|decoder| decoder.is_animated())
+}
+
 // ============================================================================
 // Tests (AC: #2, #3, #4)
 // ============================================================================
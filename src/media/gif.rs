@@ -39,12 +39,12 @@
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::image::ImageRenderer;
 use crate::{BrailleGrid, DotmaxError, Result};
 
-use super::MediaPlayer;
+use super::{DecodeTiming, MediaPlayer};
 
 // ============================================================================
 // GIF Frame Types (AC: #2, #5)
@@ -218,6 +218,9 @@ pub struct GifPlayer {
     /// Terminal dimensions for rendering.
     terminal_width: usize,
     terminal_height: usize,
+
+    /// How long the last frame took to decode and convert.
+    last_timing: Option<DecodeTiming>,
 }
 
 impl std::fmt::Debug for GifPlayer {
@@ -303,6 +306,7 @@ impl GifPlayer {
             previous_rect: (0, 0, 0, 0),
             terminal_width,
             terminal_height,
+            last_timing: None,
         })
     }
 
@@ -450,10 +454,82 @@ impl GifPlayer {
         }
     }
 
-    /// Converts the current canvas to a BrailleGrid.
-    fn canvas_to_grid(&self) -> Result<BrailleGrid> {
-        // Create RGBA image from canvas
-        let img = image::RgbaImage::from_raw(
+    /// Composites a decoded frame onto the canvas and records its disposal
+    /// for the next frame.
+    fn draw_frame(&mut self, frame: &GifFrame) {
+        // Save canvas state if this frame has RestorePrevious disposal
+        if frame.disposal == DisposalMethod::RestorePrevious {
+            self.save_canvas_state();
+        }
+
+        // Composite frame onto canvas
+        self.composite_frame(frame);
+
+        // Store disposal info for next frame
+        self.previous_disposal = frame.disposal;
+        self.previous_rect = (frame.left, frame.top, frame.width as u16, frame.height as u16);
+        self.current_frame += 1;
+    }
+
+    /// Composites frames from the start up to the one on screen at `at`,
+    /// without rendering any of them, and returns the canvas.
+    ///
+    /// Times past the end of the first pass give the last frame. Playback
+    /// restarts from the beginning afterwards.
+    pub(crate) fn canvas_at(&mut self, at: Duration) -> Result<image::RgbaImage> {
+        Ok(self.canvases_at(&[at])?.remove(0))
+    }
+
+    /// [`canvas_at`](Self::canvas_at) for several ascending times, decoding
+    /// the file once.
+    pub(crate) fn canvases_at(&mut self, times: &[Duration]) -> Result<Vec<image::RgbaImage>> {
+        self.reset();
+        let mut images = Vec::with_capacity(times.len());
+        let mut elapsed = Duration::ZERO;
+        let mut drawn = false;
+
+        for &at in times {
+            while elapsed <= at {
+                match self.decode_next_frame() {
+                    Some(Ok(frame)) => {
+                        self.apply_previous_disposal();
+                        self.draw_frame(&frame);
+                        drawn = true;
+                        elapsed += Duration::from_millis(u64::from(frame.delay_ms));
+                    }
+                    Some(Err(e)) if !drawn => return Err(e),
+                    Some(Err(_)) | None => break,
+                }
+            }
+            if !drawn {
+                return Err(DotmaxError::GifError {
+                    path: self.path.clone(),
+                    message: "No decodable frames".to_string(),
+                });
+            }
+            images.push(self.canvas_image()?);
+        }
+
+        self.reset();
+        Ok(images)
+    }
+
+    /// Total display time of one pass, found by decoding every frame.
+    ///
+    /// Playback restarts from the beginning afterwards.
+    pub(crate) fn pass_duration(&mut self) -> Duration {
+        self.reset();
+        let mut total = Duration::ZERO;
+        while let Some(Ok(frame)) = self.decode_next_frame() {
+            total += Duration::from_millis(u64::from(frame.delay_ms));
+        }
+        self.reset();
+        total
+    }
+
+    /// Copies the current canvas into an image.
+    fn canvas_image(&self) -> Result<image::RgbaImage> {
+        image::RgbaImage::from_raw(
             u32::from(self.canvas_width),
             u32::from(self.canvas_height),
             self.canvas.clone(),
@@ -461,7 +537,13 @@ impl GifPlayer {
         .ok_or_else(|| DotmaxError::GifError {
             path: self.path.clone(),
             message: "Failed to create image from canvas".to_string(),
-        })?;
+        })
+    }
+
+    /// Converts the current canvas to a BrailleGrid.
+    fn canvas_to_grid(&self) -> Result<BrailleGrid> {
+        // Create RGBA image from canvas
+        let img = self.canvas_image()?;
 
         // Use ImageRenderer to convert to BrailleGrid
         let grid = ImageRenderer::new()
@@ -498,6 +580,7 @@ impl MediaPlayer for GifPlayer {
         if self.loops_completed {
             return None;
         }
+        let started = Instant::now();
 
         // Apply previous frame's disposal method
         self.apply_previous_disposal();
@@ -560,24 +643,19 @@ impl MediaPlayer for GifPlayer {
             }
         };
 
-        // Save canvas state if this frame has RestorePrevious disposal
-        if frame.disposal == DisposalMethod::RestorePrevious {
-            self.save_canvas_state();
-        }
-
-        // Composite frame onto canvas
-        self.composite_frame(&frame);
+        self.draw_frame(&frame);
+        let decoded = Instant::now();
 
         // Convert canvas to BrailleGrid
         let grid = match self.canvas_to_grid() {
             Ok(g) => g,
             Err(e) => return Some(Err(e)),
         };
-
-        // Store disposal info for next frame
-        self.previous_disposal = frame.disposal;
-        self.previous_rect = (frame.left, frame.top, frame.width as u16, frame.height as u16);
-        self.current_frame += 1;
+        self.last_timing = Some(DecodeTiming {
+            decode: decoded - started,
+            convert: decoded.elapsed(),
+            queue_depth: None,
+        });
 
         let duration = Duration::from_millis(u64::from(frame.delay_ms));
         Some(Ok((grid, duration)))
@@ -606,6 +684,12 @@ impl MediaPlayer for GifPlayer {
         self.frame_count
     }
 
+    /// Returns decode (including compositing) and conversion times of the
+    /// last frame.
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        self.last_timing
+    }
+
     /// Returns the GIF's loop count.
     ///
     /// - `Some(0)` → infinite looping
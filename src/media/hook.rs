@@ -0,0 +1,290 @@
+//! Per-frame hooks for drawing overlays on decoded media.
+//!
+//! [`MediaPlayer::on_frame`] wraps any player so a closure sees every frame
+//! after it is decoded and before it is returned to the caller. The closure
+//! gets the frame's grid to draw on and a [`FrameInfo`] describing where the
+//! frame sits in playback, which is enough for timestamps, progress bars, and
+//! watermarks.
+//!
+//! The wrapper is itself a [`MediaPlayer`], so it works anywhere a player
+//! does: in the `quick` playback helpers, boxed in a
+//! [`MediaContent::Animated`](super::MediaContent::Animated), or in your own
+//! render loop.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::media::{GifPlayer, MediaPlayer};
+//!
+//! let mut player = GifPlayer::new("spinner.gif")?.on_frame(|grid, info| {
+//!     // Stamp the frame number in the top-left corner
+//!     for (i, c) in format!("#{}", info.index).chars().enumerate() {
+//!         if i < grid.width() {
+//!             grid.set_char(i, 0, c)?;
+//!         }
+//!     }
+//!     Ok(())
+//! });
+//!
+//! while let Some(frame) = player.next_frame() {
+//!     let (grid, delay) = frame?;
+//!     // render `grid`, wait `delay`
+//! #   let _ = (grid, delay);
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::Duration;
+
+use super::{Matte, MediaPlayer};
+#[cfg(feature = "image")]
+use crate::image::RenderOptions;
+use crate::widgets::FrameTiming;
+use crate::{BrailleGrid, Result};
+
+/// Where a frame sits in playback, passed to frame hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo {
+    /// Frames returned since playback started or was last reset (0-based).
+    pub index: usize,
+    /// Presentation time of this frame: the sum of the delays of every
+    /// earlier frame since playback started or was last reset.
+    pub timestamp: Duration,
+    /// How long this frame stays on screen.
+    pub delay: Duration,
+    /// Total frames in one pass of the media, if known.
+    pub frame_count: Option<usize>,
+    /// Running time of one pass of the media, if known.
+    pub duration: Option<Duration>,
+}
+
+impl FrameInfo {
+    /// Fraction of the current pass shown once this frame is displayed
+    /// (`0.0` exclusive to `1.0` inclusive), if the frame count is known.
+    ///
+    /// Looping media wraps back to the start each pass.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn progress(&self) -> Option<f32> {
+        self.frame_count
+            .filter(|&n| n > 0)
+            .map(|n| ((self.index % n) + 1) as f32 / n as f32)
+    }
+}
+
+/// How long a player took to produce its last frame, reported by
+/// [`MediaPlayer::decode_timing`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecodeTiming {
+    /// Reading and decompressing the frame
+    pub decode: Duration,
+    /// Turning decoded pixels into dots
+    pub convert: Duration,
+    /// Frames decoded ahead and waiting, for players that decode ahead
+    pub queue_depth: Option<usize>,
+}
+
+impl DecodeTiming {
+    /// Adds the time taken to draw the frame, for a
+    /// [`PlaybackDiagnostics`](crate::widgets::PlaybackDiagnostics) overlay.
+    #[must_use]
+    pub const fn with_render(self, render: Duration) -> FrameTiming {
+        FrameTiming {
+            decode: self.decode,
+            convert: self.convert,
+            render,
+            queue_depth: self.queue_depth,
+        }
+    }
+}
+
+/// A [`MediaPlayer`] that runs a hook on each frame of another player.
+///
+/// Created by [`MediaPlayer::on_frame`].
+pub struct WithFrameHook<P, F> {
+    player: P,
+    hook: F,
+    index: usize,
+    timestamp: Duration,
+}
+
+impl<P, F> WithFrameHook<P, F> {
+    /// The wrapped player.
+    pub const fn player(&self) -> &P {
+        &self.player
+    }
+
+    /// The wrapped player, mutably, e.g. to adjust render settings.
+    pub fn player_mut(&mut self) -> &mut P {
+        &mut self.player
+    }
+
+    /// Unwraps the player, dropping the hook.
+    pub fn into_inner(self) -> P {
+        self.player
+    }
+}
+
+impl<P: std::fmt::Debug, F> std::fmt::Debug for WithFrameHook<P, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WithFrameHook")
+            .field("player", &self.player)
+            .field("index", &self.index)
+            .field("timestamp", &self.timestamp)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, F> WithFrameHook<P, F>
+where
+    P: MediaPlayer,
+    F: FnMut(&mut BrailleGrid, &FrameInfo) -> Result<()> + Send,
+{
+    pub(super) const fn new(player: P, hook: F) -> Self {
+        Self {
+            player,
+            hook,
+            index: 0,
+            timestamp: Duration::ZERO,
+        }
+    }
+}
+
+impl<P, F> MediaPlayer for WithFrameHook<P, F>
+where
+    P: MediaPlayer,
+    F: FnMut(&mut BrailleGrid, &FrameInfo) -> Result<()> + Send,
+{
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        let (mut grid, delay) = match self.player.next_frame()? {
+            Ok(frame) => frame,
+            Err(e) => return Some(Err(e)),
+        };
+        let info = FrameInfo {
+            index: self.index,
+            timestamp: self.timestamp,
+            delay,
+            frame_count: self.player.frame_count(),
+            duration: self.player.duration(),
+        };
+        self.index += 1;
+        self.timestamp += delay;
+        Some((self.hook)(&mut grid, &info).map(|()| (grid, delay)))
+    }
+
+    fn reset(&mut self) {
+        self.player.reset();
+        self.index = 0;
+        self.timestamp = Duration::ZERO;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        self.player.frame_count()
+    }
+
+    fn loop_count(&self) -> Option<u16> {
+        self.player.loop_count()
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.player.duration()
+    }
+
+    fn matte(&self) -> Option<&Matte> {
+        self.player.matte()
+    }
+
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        self.player.decode_timing()
+    }
+
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        self.player.handle_resize(width, height);
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration> {
+        let landed = self.player.seek(position)?;
+        self.timestamp = landed;
+        Ok(landed)
+    }
+
+    #[cfg(feature = "image")]
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        self.player.set_render_options(options)
+    }
+
+    #[cfg(feature = "image")]
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        self.player.current_render_options()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Three 10ms frames of a 2×1 grid.
+    #[derive(Debug, Default)]
+    struct Counter {
+        next: usize,
+    }
+
+    impl MediaPlayer for Counter {
+        fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+            if self.next == 3 {
+                return None;
+            }
+            self.next += 1;
+            Some(BrailleGrid::new(2, 1).map(|g| (g, Duration::from_millis(10))))
+        }
+
+        fn reset(&mut self) {
+            self.next = 0;
+        }
+
+        fn frame_count(&self) -> Option<usize> {
+            Some(3)
+        }
+
+        fn loop_count(&self) -> Option<u16> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_hook_sees_every_frame_with_timing() {
+        let mut seen = Vec::new();
+        {
+            let mut player = Counter::default().on_frame(|grid, info| {
+                grid.set_dot(0, 0)?;
+                seen.push(*info);
+                Ok(())
+            });
+            while let Some(frame) = player.next_frame() {
+                assert!(frame.unwrap().0.is_dot_set(0, 0));
+            }
+        }
+
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[2].index, 2);
+        assert_eq!(seen[2].timestamp, Duration::from_millis(20));
+        assert!((seen[0].progress().unwrap() - 1.0 / 3.0).abs() < 1e-6);
+        assert!((seen[2].progress().unwrap() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_reset_restarts_timing_and_hook_errors_propagate() {
+        let mut player = Counter::default().on_frame(|_, info| {
+            if info.index == 1 {
+                Err(crate::DotmaxError::EmptyDensitySet)
+            } else {
+                Ok(())
+            }
+        });
+        assert!(player.next_frame().unwrap().is_ok());
+        assert!(player.next_frame().unwrap().is_err());
+        player.reset();
+        assert!(player.next_frame().unwrap().is_ok());
+        assert_eq!(player.player().next, 1);
+    }
+}
@@ -27,12 +27,41 @@
 //! ## Vector Graphics
 //! - SVG (requires `svg` feature)
 //!
-//! ## Animated Formats (Future)
-//! - Animated GIF (Story 9.2)
-//! - Animated PNG/APNG (Story 9.3)
-//!
-//! ## Video Formats (Future)
-//! - MP4, MKV, AVI, WebM (Story 9.4)
+//! ## Animated Formats
+//! - Animated GIF via [`GifPlayer`] (Story 9.2)
+//! - Animated PNG/APNG via [`ApngPlayer`] (Story 9.3)
+//! - Animated WebP via [`AnimatedWebpPlayer`]
+//!
+//! ## Video Formats
+//! - MP4, MKV, AVI, WebM, MOV via `VideoPlayer` (Story 9.4, requires the
+//!   `video` feature and FFmpeg)
+//!
+//! ## Live Sources
+//! - Webcams via `WebcamPlayer` (requires the `video` feature and FFmpeg)
+//! - Monitors, screen regions, and windows via `ScreenCapturePlayer`
+//!   (requires the `screen-capture` feature)
+//!
+//! ## Playlists
+//! - [`Playlist`] plays several files of any of the above back to back
+//!
+//! ## Previews
+//! - [`poster_frame`] decodes a single frame at a timestamp for thumbnails;
+//!   [`poster_frame_cached`] keeps the results in an on-disk cache
+//! - [`contact_sheet`] tiles frames sampled across an animation or video
+//!
+//! ## Compositing
+//! - [`KeyedComposite`] layers a chroma-keyed player over any
+//!   [`FrameSource`](crate::animation::FrameSource)
+//!
+//! ## Fallbacks
+//! - [`route`](route::route) decides how to show a file given the compiled-in
+//!   features and the terminal: a placeholder for formats whose feature is
+//!   missing, a poster frame when nobody is watching, density characters
+//!   without Unicode
+//!
+//! ## Remote Control
+//! - [`PlayerControl`] pauses, seeks, and re-tunes a [`Controlled`] player
+//!   from other threads
 //!
 //! # Examples
 //!
@@ -75,29 +104,59 @@
 //! - `DotmaxError::Terminal` for I/O errors (file not found, permission denied)
 //! - `DotmaxError::FormatError` for unsupported/unknown formats
 
+pub mod composite;
+pub mod control;
 mod detect;
 #[cfg(feature = "image")]
 pub mod apng;
 #[cfg(feature = "image")]
 pub mod gif;
+pub mod hook;
+pub mod playlist;
+#[cfg(feature = "image")]
+pub mod poster;
+pub mod route;
 mod router;
+#[cfg(feature = "screen-capture")]
+pub mod screen;
 #[cfg(feature = "video")]
 pub mod video;
 #[cfg(feature = "video")]
 pub mod webcam;
+#[cfg(feature = "image")]
+pub mod webp;
 
 // Public re-exports
+pub use composite::{KeyedComposite, Matte};
+pub use control::{Controlled, PlayerControl};
 pub use detect::{detect_format, detect_format_from_bytes, ImageFormat, MediaFormat, VideoCodec};
 #[cfg(feature = "image")]
 pub use detect::{is_animated_gif, is_animated_gif_from_bytes};
 #[cfg(feature = "image")]
 pub use detect::{is_animated_png, is_animated_png_from_bytes};
 #[cfg(feature = "image")]
+pub use detect::{is_animated_webp, is_animated_webp_from_bytes};
+#[cfg(feature = "image")]
 pub use apng::{ApngFrame, ApngPlayer, BlendOp, DisposeOp};
 #[cfg(feature = "image")]
 pub use gif::{DisposalMethod, GifFrame, GifPlayer};
+pub use hook::{DecodeTiming, FrameInfo, WithFrameHook};
+pub use playlist::{Playlist, RepeatMode};
+#[cfg(feature = "image")]
+pub use poster::{
+    contact_sheet, contact_sheet_sized, poster_frame, poster_frame_cached, poster_frame_image,
+};
 pub use router::{MediaContent, MediaPlayer};
+#[cfg(feature = "screen-capture")]
+pub use screen::{
+    list_capture_sources, CaptureSource, CaptureSourceInfo, ScreenCapturePlayer,
+    ScreenCapturePlayerBuilder,
+};
 #[cfg(feature = "video")]
 pub use video::VideoPlayer;
 #[cfg(feature = "video")]
-pub use webcam::{list_webcams, WebcamDevice, WebcamDeviceId, WebcamPlayer, WebcamPlayerBuilder};
+pub use webcam::{
+    list_webcams, WebcamDevice, WebcamDeviceId, WebcamMode, WebcamPlayer, WebcamPlayerBuilder,
+};
+#[cfg(feature = "image")]
+pub use webp::AnimatedWebpPlayer;
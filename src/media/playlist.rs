@@ -0,0 +1,568 @@
+//! Sequential playback of several media files.
+//!
+//! [`Playlist`] strings a list of images, animations, and videos together
+//! behind a single [`MediaPlayer`]. Each item plays one pass, then the next
+//! item's first frame is returned from the same `next_frame()` call, so there
+//! is no blank frame between items. Static images are held on screen for
+//! [`still_duration`](Playlist::still_duration).
+//!
+//! Items are opened lazily, one at a time, through the same format routing as
+//! [`quick::load_file`](crate::quick::load_file). With the `video` feature,
+//! entries containing `://` are handed straight to FFmpeg, so network streams
+//! work too. Items that fail to open are logged and skipped.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::media::{MediaPlayer, Playlist, RepeatMode};
+//!
+//! let mut playlist = Playlist::new(["intro.gif", "logo.png", "clip.mp4"])?
+//!     .repeat(RepeatMode::All)
+//!     .shuffle(true);
+//!
+//! while let Some(frame) = playlist.next_frame() {
+//!     let (grid, delay) = frame?;
+//!     // render `grid`, wait `delay`
+//! #   let _ = (grid, delay);
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::{DecodeTiming, MediaContent, MediaPlayer};
+#[cfg(feature = "image")]
+use crate::image::RenderOptions;
+use crate::{BrailleGrid, DotmaxError, Result};
+
+/// What a [`Playlist`] does when an item or the whole list finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    /// Play every item once, then stop
+    #[default]
+    Off,
+    /// Replay the current item until skipped
+    One,
+    /// Start over from the first item after the last
+    All,
+}
+
+/// A [`MediaPlayer`] that plays a list of media files back to back.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug)]
+pub struct Playlist {
+    items: Vec<PathBuf>,
+    /// Play order as indices into `items`
+    order: Vec<usize>,
+    /// Position in `order` of the current item
+    cursor: usize,
+    current: Option<Box<dyn MediaPlayer>>,
+    /// Frames returned from the current item in this pass
+    played: usize,
+    finished: bool,
+    repeat: RepeatMode,
+    shuffle: bool,
+    rng: u64,
+    still_duration: Duration,
+    size: Option<(usize, usize)>,
+    #[cfg(feature = "image")]
+    render_options: Option<RenderOptions>,
+}
+
+impl Playlist {
+    /// Creates a playlist from file paths (or stream URLs) in play order.
+    ///
+    /// Nothing is opened until the first frame is requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if `paths` is empty.
+    pub fn new<I>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<PathBuf>,
+    {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hasher};
+
+        let items: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+        if items.is_empty() {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "playlist length".to_string(),
+                value: "0".to_string(),
+                min: "1".to_string(),
+                max: usize::MAX.to_string(),
+            });
+        }
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(items.len());
+
+        Ok(Self {
+            order: (0..items.len()).collect(),
+            items,
+            cursor: 0,
+            current: None,
+            played: 0,
+            finished: false,
+            repeat: RepeatMode::Off,
+            shuffle: false,
+            // Xorshift state must be non-zero
+            rng: hasher.finish() | 1,
+            still_duration: Duration::from_secs(5),
+            size: None,
+            #[cfg(feature = "image")]
+            render_options: None,
+        })
+    }
+
+    /// Sets the repeat mode (default [`RepeatMode::Off`]).
+    #[must_use]
+    pub const fn repeat(mut self, mode: RepeatMode) -> Self {
+        self.repeat = mode;
+        self
+    }
+
+    /// Plays items in random order. See [`set_shuffle`](Self::set_shuffle).
+    #[must_use]
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.set_shuffle(shuffle);
+        self
+    }
+
+    /// Seeds the shuffle so the play order is reproducible.
+    ///
+    /// If shuffle is already on, the order is redrawn from the new seed.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = seed | 1;
+        if self.shuffle {
+            self.reshuffle();
+        }
+        self
+    }
+
+    /// How long static images stay on screen (default 5 seconds).
+    #[must_use]
+    pub const fn still_duration(mut self, duration: Duration) -> Self {
+        self.still_duration = duration;
+        self
+    }
+
+    /// Turns shuffle on or off without interrupting the current item.
+    ///
+    /// Turning it on draws a new random order that starts with the current
+    /// item; turning it off returns to list order from the current item.
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+        let current = self.order[self.cursor];
+        if shuffle {
+            self.reshuffle();
+            let pos = self.order.iter().position(|&i| i == current).unwrap_or(0);
+            self.order.swap(0, pos);
+            self.cursor = 0;
+        } else {
+            self.order = (0..self.items.len()).collect();
+            self.cursor = current;
+        }
+    }
+
+    /// Whether items play in random order.
+    #[must_use]
+    pub const fn is_shuffled(&self) -> bool {
+        self.shuffle
+    }
+
+    /// Changes the repeat mode during playback.
+    pub fn set_repeat(&mut self, mode: RepeatMode) {
+        self.repeat = mode;
+    }
+
+    /// The current repeat mode.
+    #[must_use]
+    pub const fn repeat_mode(&self) -> RepeatMode {
+        self.repeat
+    }
+
+    /// Skips to the next item in play order.
+    ///
+    /// Returns `false`, leaving the current item playing, if this is the last
+    /// item and the repeat mode isn't [`RepeatMode::All`].
+    pub fn next_item(&mut self) -> bool {
+        let moved = self.step_forward();
+        if moved {
+            self.finished = false;
+        }
+        moved
+    }
+
+    /// Goes back to the previous item in play order.
+    ///
+    /// From the first item this wraps to the last under [`RepeatMode::All`]
+    /// and otherwise restarts the first item.
+    pub fn previous_item(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+        } else if self.repeat == RepeatMode::All {
+            self.cursor = self.order.len() - 1;
+        }
+        self.close_current();
+    }
+
+    /// Jumps to `index` in the list as given to [`new`](Self::new),
+    /// regardless of shuffle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if `index` is out of range.
+    pub fn jump_to(&mut self, index: usize) -> Result<()> {
+        let cursor = self.order.iter().position(|&i| i == index).ok_or_else(|| {
+            DotmaxError::InvalidParameter {
+                parameter_name: "playlist index".to_string(),
+                value: index.to_string(),
+                min: "0".to_string(),
+                max: (self.items.len() - 1).to_string(),
+            }
+        })?;
+        self.cursor = cursor;
+        self.close_current();
+        Ok(())
+    }
+
+    /// Index of the current item in the list as given to [`new`](Self::new).
+    #[must_use]
+    pub fn current_index(&self) -> usize {
+        self.order[self.cursor]
+    }
+
+    /// Path of the current item.
+    #[must_use]
+    pub fn current_path(&self) -> &Path {
+        &self.items[self.current_index()]
+    }
+
+    /// All items, in the order given to [`new`](Self::new).
+    #[must_use]
+    pub fn items(&self) -> &[PathBuf] {
+        &self.items
+    }
+
+    /// Number of items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Always `false`; a playlist holds at least one item.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Moves to the next item in play order, wrapping under
+    /// [`RepeatMode::All`]. Returns `false` without moving at the end.
+    fn step_forward(&mut self) -> bool {
+        if self.cursor + 1 < self.order.len() {
+            self.cursor += 1;
+        } else if self.repeat == RepeatMode::All {
+            if self.shuffle {
+                self.reshuffle();
+            }
+            self.cursor = 0;
+        } else {
+            return false;
+        }
+        self.close_current();
+        true
+    }
+
+    /// Handles the current item running out of frames.
+    fn item_ended(&mut self) {
+        // An item that produced nothing is skipped even under RepeatMode::One,
+        // so it can't spin forever
+        if self.repeat == RepeatMode::One && self.played > 0 {
+            if let Some(player) = self.current.as_mut() {
+                player.reset();
+            }
+            self.played = 0;
+        } else if !self.step_forward() {
+            self.finished = true;
+        }
+    }
+
+    fn close_current(&mut self) {
+        self.current = None;
+        self.played = 0;
+        self.finished = false;
+    }
+
+    fn open_current(&self) -> Result<Box<dyn MediaPlayer>> {
+        let path = self.current_path();
+
+        #[cfg(feature = "video")]
+        {
+            if path.to_string_lossy().contains("://") {
+                return Ok(Box::new(super::VideoPlayer::new(path)?));
+            }
+        }
+
+        let mut player: Box<dyn MediaPlayer> = match crate::quick::load_file(path)? {
+            MediaContent::Static(grid) => Box::new(Still {
+                grid,
+                delay: self.still_duration,
+                shown: false,
+            }),
+            MediaContent::Animated(player) => player,
+        };
+        if let Some((width, height)) = self.size {
+            player.handle_resize(width, height);
+        }
+        #[cfg(feature = "image")]
+        if let Some(options) = self.render_options {
+            player.set_render_options(options)?;
+        }
+        Ok(player)
+    }
+
+    /// Fisher-Yates shuffle of the play order.
+    #[allow(clippy::cast_possible_truncation)]
+    fn reshuffle(&mut self) {
+        for i in (1..self.order.len()).rev() {
+            // Xorshift64
+            self.rng ^= self.rng << 13;
+            self.rng ^= self.rng >> 7;
+            self.rng ^= self.rng << 17;
+            let j = (self.rng % (i as u64 + 1)) as usize;
+            self.order.swap(i, j);
+        }
+    }
+}
+
+impl MediaPlayer for Playlist {
+    /// Returns the next frame, moving on to the next item when the current
+    /// one finishes a pass.
+    ///
+    /// Items that fail to open are skipped with a warning. If every item
+    /// fails, the last error is returned.
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        let mut failures = 0;
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            if self.current.is_none() {
+                match self.open_current() {
+                    Ok(player) => {
+                        self.current = Some(player);
+                        self.played = 0;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Skipping playlist item {}: {e}",
+                            self.current_path().display()
+                        );
+                        failures += 1;
+                        if failures >= self.items.len() || !self.step_forward() {
+                            self.finished = true;
+                            return Some(Err(e));
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            let shown = self.played;
+            let player = self.current.as_mut()?;
+            // Looping animations never run out of frames, so a pass ends
+            // once the known frame count has been shown
+            let pass_done =
+                |player: &dyn MediaPlayer| player.frame_count().is_some_and(|n| shown >= n);
+
+            if !pass_done(&**player) {
+                match player.next_frame() {
+                    Some(Ok(frame)) if !pass_done(&**player) => {
+                        self.played += 1;
+                        return Some(Ok(frame));
+                    }
+                    Some(Err(e)) => return Some(Err(e)),
+                    _ => {}
+                }
+            }
+
+            self.item_ended();
+        }
+    }
+
+    /// Returns to the first item in play order.
+    fn reset(&mut self) {
+        self.cursor = 0;
+        self.close_current();
+    }
+
+    /// Always `None`; items are only opened as they play.
+    fn frame_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// `Some(0)` (infinite) when repeating, otherwise `Some(1)`.
+    fn loop_count(&self) -> Option<u16> {
+        match self.repeat {
+            RepeatMode::Off => Some(1),
+            RepeatMode::One | RepeatMode::All => Some(0),
+        }
+    }
+
+    /// The current item's timing.
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        self.current.as_ref()?.decode_timing()
+    }
+
+    /// Resizes the current item and every item opened afterwards.
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        self.size = Some((width, height));
+        if let Some(player) = self.current.as_mut() {
+            player.handle_resize(width, height);
+        }
+    }
+
+    /// Applies the options to the current item and every item opened
+    /// afterwards.
+    #[cfg(feature = "image")]
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        options.validate()?;
+        self.render_options = Some(options);
+        if let Some(player) = self.current.as_mut() {
+            player.set_render_options(options)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        self.current
+            .as_ref()
+            .and_then(MediaPlayer::current_render_options)
+            .or(self.render_options)
+    }
+}
+
+/// A static image shown as a single frame.
+#[derive(Debug)]
+struct Still {
+    grid: BrailleGrid,
+    delay: Duration,
+    shown: bool,
+}
+
+impl MediaPlayer for Still {
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        if self.shown {
+            return None;
+        }
+        self.shown = true;
+        Some(Ok((self.grid.clone(), self.delay)))
+    }
+
+    fn reset(&mut self) {
+        self.shown = false;
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn loop_count(&self) -> Option<u16> {
+        Some(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIF: &str = "tests/fixtures/media/animated.gif";
+    const PNG: &str = "tests/fixtures/media/static_png.png";
+
+    fn delays(playlist: &mut Playlist, limit: usize) -> Vec<u128> {
+        std::iter::from_fn(|| playlist.next_frame())
+            .take(limit)
+            .map(|frame| frame.unwrap().1.as_millis())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_playlist_rejected() {
+        assert!(Playlist::new(Vec::<PathBuf>::new()).is_err());
+    }
+
+    #[test]
+    fn test_items_play_back_to_back_once() {
+        let mut playlist = Playlist::new([GIF, PNG])
+            .unwrap()
+            .still_duration(Duration::from_millis(7));
+        // The GIF loops forever on its own but plays one 4-frame pass here
+        assert_eq!(delays(&mut playlist, 10), [100, 100, 100, 100, 7]);
+    }
+
+    #[test]
+    fn test_repeat_all_wraps_and_one_repeats() {
+        let mut playlist = Playlist::new([PNG, PNG])
+            .unwrap()
+            .repeat(RepeatMode::All)
+            .still_duration(Duration::from_millis(7));
+        assert_eq!(delays(&mut playlist, 5).len(), 5);
+
+        playlist.set_repeat(RepeatMode::One);
+        playlist.reset();
+        delays(&mut playlist, 3);
+        assert_eq!(playlist.current_index(), 0);
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut playlist = Playlist::new([GIF, PNG, GIF]).unwrap();
+        assert!(playlist.next_item());
+        assert_eq!(playlist.current_path(), Path::new(PNG));
+        assert!(playlist.next_item());
+        assert!(!playlist.next_item());
+        assert_eq!(playlist.current_index(), 2);
+
+        playlist.previous_item();
+        assert_eq!(playlist.current_index(), 1);
+        playlist.jump_to(0).unwrap();
+        playlist.previous_item();
+        assert_eq!(playlist.current_index(), 0);
+        assert!(playlist.jump_to(3).is_err());
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation_starting_with_current() {
+        let mut playlist = Playlist::new(["a", "b", "c", "d", "e", "f"]).unwrap();
+        playlist.jump_to(4).unwrap();
+        playlist.set_shuffle(true);
+        assert_eq!(playlist.current_index(), 4);
+
+        let mut seen = playlist.order.clone();
+        seen.sort_unstable();
+        assert_eq!(seen, [0, 1, 2, 3, 4, 5]);
+
+        playlist.set_shuffle(false);
+        assert_eq!(playlist.current_index(), 4);
+        assert_eq!(playlist.order, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_unreadable_items_are_skipped() {
+        let mut playlist = Playlist::new(["missing.gif", PNG])
+            .unwrap()
+            .still_duration(Duration::from_millis(7));
+        assert_eq!(delays(&mut playlist, 3), [7]);
+
+        let mut broken = Playlist::new(["missing.gif"]).unwrap();
+        assert!(broken.next_frame().unwrap().is_err());
+        assert!(broken.next_frame().is_none());
+    }
+}
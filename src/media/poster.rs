@@ -0,0 +1,364 @@
+//! Previews of media files: single poster frames and contact sheets.
+//!
+//! Gallery views and file pickers need one representative frame per file,
+//! not playback. [`poster_frame`] decodes just the frame on screen at a
+//! given time: GIF and APNG frames are composited up to that point without
+//! rendering the earlier ones, and video seeks to the nearest keyframe before
+//! decoding forward. Static images and SVGs ignore the time and return the
+//! image itself.
+//!
+//! [`contact_sheet`] samples frames evenly across an animation or video and
+//! tiles them, each labeled with its timestamp, to preview long content at a
+//! glance.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use dotmax::media::{contact_sheet, poster_frame, poster_frame_image};
+//!
+//! // Ready-to-display grid sized to the terminal
+//! let grid = poster_frame("clip.mp4", Duration::from_secs(10))?;
+//!
+//! // Full-resolution image, e.g. to render at thumbnail size yourself
+//! let image = poster_frame_image("spinner.gif", Duration::ZERO)?;
+//!
+//! // Twelve frames from across the clip, four per row
+//! let sheet = contact_sheet("clip.mp4", 4, 3)?;
+//! # let _ = (grid, image, sheet);
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::path::Path;
+use std::time::Duration;
+
+use image::DynamicImage;
+
+use super::{detect_format, AnimatedWebpPlayer, ApngPlayer, GifPlayer, MediaFormat};
+use crate::image::{CacheKey, ImageRenderer, RenderCache};
+use crate::{BrailleGrid, DotmaxError, Result};
+
+/// Renders the frame of `path` on screen at `at`, sized to the terminal.
+///
+/// Times past the end of the media give its last frame.
+///
+/// # Errors
+///
+/// Returns the same errors as [`poster_frame_image`], or any rendering error.
+pub fn poster_frame(path: impl AsRef<Path>, at: Duration) -> Result<BrailleGrid> {
+    let (width, height) = terminal_cells();
+    ImageRenderer::new()
+        .load_from_rgba(poster_frame_image(path, at)?.into_rgba8())
+        .resize(width, height, true)?
+        .render()
+}
+
+/// [`poster_frame`] through an on-disk [`RenderCache`].
+///
+/// The key is the file's content, the time, and the terminal size, so a
+/// repeat request for the same frame skips decoding. The file is hashed on
+/// every call; that is a full read, though much cheaper than decoding.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::Terminal`] if the file can't be read, or the same
+/// errors as [`poster_frame`] on a cache miss.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use dotmax::image::RenderCache;
+/// use dotmax::media::poster_frame_cached;
+///
+/// let cache = RenderCache::open("posters", 32 * 1024 * 1024)?;
+/// for clip in ["a.mp4", "b.gif", "a.mp4"] {
+///     let grid = poster_frame_cached(clip, Duration::from_secs(5), &cache)?;
+/// }
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn poster_frame_cached(
+    path: impl AsRef<Path>,
+    at: Duration,
+    cache: &RenderCache,
+) -> Result<BrailleGrid> {
+    let path = path.as_ref();
+    let (width, height) = terminal_cells();
+    let key = CacheKey::from_file(path)?
+        .size(width, height)
+        .tag(&at.as_nanos().to_le_bytes());
+    cache.get_or_render(&key, || poster_frame(path, at))
+}
+
+/// Decodes the frame of `path` on screen at `at` as an image at the media's
+/// native resolution.
+///
+/// SVGs are rasterized at the terminal's dot resolution.
+///
+/// # Errors
+///
+/// - [`DotmaxError::FormatError`] for unknown formats, or formats whose
+///   feature (`svg`, `video`) is disabled
+/// - The decoder's own error ([`DotmaxError::GifError`],
+///   [`DotmaxError::ApngError`], [`DotmaxError::WebpError`],
+///   [`DotmaxError::ImageLoad`], ...) if the
+///   file can't be read
+pub fn poster_frame_image(path: impl AsRef<Path>, at: Duration) -> Result<DynamicImage> {
+    let path = path.as_ref();
+    match detect_format(path)? {
+        MediaFormat::StaticImage(_) => crate::image::load_from_path(path),
+        MediaFormat::Svg => {
+            #[cfg(feature = "svg")]
+            {
+                let (width, height) = terminal_cells();
+                crate::image::load_svg_from_path(path, (width * 2) as u32, (height * 4) as u32)
+            }
+            #[cfg(not(feature = "svg"))]
+            {
+                Err(DotmaxError::FormatError {
+                    format: "SVG (requires 'svg' feature)".to_string(),
+                })
+            }
+        }
+        MediaFormat::AnimatedGif => Ok(DynamicImage::ImageRgba8(
+            GifPlayer::new(path)?.canvas_at(at)?,
+        )),
+        MediaFormat::AnimatedPng => Ok(DynamicImage::ImageRgba8(
+            ApngPlayer::new(path)?.canvas_at(at)?,
+        )),
+        MediaFormat::AnimatedWebp => Ok(DynamicImage::ImageRgba8(
+            AnimatedWebpPlayer::new(path)?.canvas_at(at)?,
+        )),
+        MediaFormat::Video(_codec) => {
+            #[cfg(feature = "video")]
+            {
+                let mut player = super::VideoPlayer::new(path)?;
+                Ok(DynamicImage::ImageRgb8(player.frame_image_at(at)?))
+            }
+            #[cfg(not(feature = "video"))]
+            {
+                Err(DotmaxError::FormatError {
+                    format: "video (requires 'video' feature and FFmpeg libraries)".to_string(),
+                })
+            }
+        }
+        MediaFormat::Unknown => Err(DotmaxError::FormatError {
+            format: "unknown format".to_string(),
+        }),
+    }
+}
+
+/// Tiles `cols × rows` frames sampled evenly across `path` into a grid the
+/// size of the terminal, each labeled with its timestamp.
+///
+/// Frames are taken from the middle of equal slices of the running time, so
+/// the first and last tiles sit just inside the start and end.
+///
+/// # Errors
+///
+/// See [`contact_sheet_sized`].
+pub fn contact_sheet(path: impl AsRef<Path>, cols: usize, rows: usize) -> Result<BrailleGrid> {
+    let (width, height) = terminal_cells();
+    contact_sheet_sized(path, cols, rows, width, height)
+}
+
+/// [`contact_sheet`] with explicit sheet dimensions in cells.
+///
+/// Each tile is `width / cols` cells wide and `height / rows` tall, with its
+/// bottom row holding the timestamp and one blank column between tiles.
+///
+/// # Errors
+///
+/// - [`DotmaxError::InvalidParameter`] if `cols` or `rows` is 0
+/// - [`DotmaxError::InvalidDimensions`] if the tiles would be smaller than
+///   2×2 cells
+/// - [`DotmaxError::FormatError`] for static images, unknown formats, or
+///   video without the `video` feature
+/// - [`DotmaxError::VideoError`] if a video doesn't report its duration
+/// - Any decoding error for the file
+pub fn contact_sheet_sized(
+    path: impl AsRef<Path>,
+    cols: usize,
+    rows: usize,
+    width: usize,
+    height: usize,
+) -> Result<BrailleGrid> {
+    for (name, value) in [
+        ("contact sheet columns", cols),
+        ("contact sheet rows", rows),
+    ] {
+        if value == 0 {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: name.to_string(),
+                value: "0".to_string(),
+                min: "1".to_string(),
+                max: usize::MAX.to_string(),
+            });
+        }
+    }
+    let (tile_width, tile_height) = (width / cols, height / rows);
+    if tile_width < 2 || tile_height < 2 {
+        return Err(DotmaxError::InvalidDimensions { width, height });
+    }
+
+    let frames = sample_frames(path.as_ref(), cols * rows)?;
+    let long = frames.last().is_some_and(|(at, _)| at.as_secs() >= 60);
+
+    let mut sheet = BrailleGrid::new(width, height)?;
+    for (i, (at, image)) in frames.into_iter().enumerate() {
+        let (x, y) = ((i % cols) * tile_width, (i / cols) * tile_height);
+        let tile = ImageRenderer::new()
+            .load_from_rgba(image.into_rgba8())
+            .resize(tile_width - 1, tile_height - 1, true)?
+            .render()?;
+        sheet.paste(&tile, x, y);
+
+        let label = timestamp_label(at, long);
+        for (offset, ch) in label.chars().take(tile_width - 1).enumerate() {
+            sheet.set_char(x + offset, y + tile_height - 1, ch)?;
+        }
+    }
+    Ok(sheet)
+}
+
+/// Decodes `count` frames from the middle of equal slices of the media's
+/// running time.
+#[allow(clippy::cast_possible_truncation)]
+fn sample_frames(path: &Path, count: usize) -> Result<Vec<(Duration, DynamicImage)>> {
+    let times = |total: Duration| -> Vec<Duration> {
+        (0..count)
+            .map(|i| total * (2 * i as u32 + 1) / (2 * count as u32))
+            .collect()
+    };
+
+    match detect_format(path)? {
+        MediaFormat::AnimatedGif => {
+            let mut player = GifPlayer::new(path)?;
+            let times = times(player.pass_duration());
+            let images = player.canvases_at(&times)?;
+            Ok(times
+                .into_iter()
+                .zip(images.into_iter().map(DynamicImage::ImageRgba8))
+                .collect())
+        }
+        MediaFormat::AnimatedPng => {
+            let mut player = ApngPlayer::new(path)?;
+            let times = times(player.pass_duration());
+            let images = player.canvases_at(&times)?;
+            Ok(times
+                .into_iter()
+                .zip(images.into_iter().map(DynamicImage::ImageRgba8))
+                .collect())
+        }
+        MediaFormat::AnimatedWebp => {
+            let mut player = AnimatedWebpPlayer::new(path)?;
+            let times = times(player.pass_duration());
+            let images = player.canvases_at(&times)?;
+            Ok(times
+                .into_iter()
+                .zip(images.into_iter().map(DynamicImage::ImageRgba8))
+                .collect())
+        }
+        MediaFormat::Video(_codec) => {
+            #[cfg(feature = "video")]
+            {
+                let mut player = super::VideoPlayer::new(path)?;
+                let total = player.duration().ok_or_else(|| DotmaxError::VideoError {
+                    path: path.to_path_buf(),
+                    message: "Unknown duration; can't sample frames evenly".to_string(),
+                })?;
+                times(total)
+                    .into_iter()
+                    .map(|at| Ok((at, DynamicImage::ImageRgb8(player.frame_image_at(at)?))))
+                    .collect()
+            }
+            #[cfg(not(feature = "video"))]
+            {
+                Err(DotmaxError::FormatError {
+                    format: "video (requires 'video' feature and FFmpeg libraries)".to_string(),
+                })
+            }
+        }
+        MediaFormat::StaticImage(_) | MediaFormat::Svg => Err(DotmaxError::FormatError {
+            format: "still image (contact sheets need animation or video)".to_string(),
+        }),
+        MediaFormat::Unknown => Err(DotmaxError::FormatError {
+            format: "unknown format".to_string(),
+        }),
+    }
+}
+
+/// `12.3s` for short media, `mm:ss` (or `h:mm:ss`) once it runs a minute.
+fn timestamp_label(at: Duration, long: bool) -> String {
+    let secs = at.as_secs();
+    if !long {
+        format!("{:.1}s", at.as_secs_f32())
+    } else if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Terminal size in cells, falling back to 80×24.
+fn terminal_cells() -> (usize, usize) {
+    crossterm::terminal::size().map_or((80, 24), |(w, h)| (w as usize, h as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+
+    #[test]
+    fn test_gif_poster_follows_timestamp() {
+        let path = "tests/fixtures/media/animated.gif";
+        // Four 100ms frames, each a different solid color
+        let first = poster_frame_image(path, Duration::ZERO).unwrap();
+        let third = poster_frame_image(path, Duration::from_millis(250)).unwrap();
+        let past_end = poster_frame_image(path, Duration::from_secs(60)).unwrap();
+        let last = poster_frame_image(path, Duration::from_millis(399)).unwrap();
+
+        assert_eq!(first.dimensions(), (10, 10));
+        assert_ne!(first.get_pixel(0, 0), third.get_pixel(0, 0));
+        assert_eq!(past_end.get_pixel(0, 0), last.get_pixel(0, 0));
+    }
+
+    #[test]
+    fn test_apng_and_static_posters() {
+        let apng = poster_frame_image("tests/fixtures/media/animated.png", Duration::ZERO);
+        assert!(apng.is_ok(), "{:?}", apng.err());
+
+        let grid = poster_frame(
+            "tests/fixtures/media/static_png.png",
+            Duration::from_secs(5),
+        );
+        assert!(grid.is_ok(), "{:?}", grid.err());
+    }
+
+    #[test]
+    fn test_contact_sheet_tiles_and_labels() {
+        let path = "tests/fixtures/media/animated.gif";
+        let sheet = contact_sheet_sized(path, 3, 2, 30, 10).unwrap();
+        assert_eq!(sheet.dimensions(), (30, 10));
+
+        // 400ms in six slices: samples at 33, 100, 166, 233, 300, 366ms
+        let label = |x, y| -> String { (x..x + 4).map(|x| sheet.get_char(x, y)).collect() };
+        assert_eq!(label(0, 4), "0.0s");
+        assert_eq!(label(10, 4), "0.1s");
+        assert_eq!(label(20, 9), "0.4s");
+    }
+
+    #[test]
+    fn test_contact_sheet_rejects_bad_input() {
+        let gif = "tests/fixtures/media/animated.gif";
+        assert!(contact_sheet_sized(gif, 0, 2, 20, 10).is_err());
+        assert!(contact_sheet_sized(gif, 20, 1, 20, 10).is_err());
+        assert!(contact_sheet_sized("tests/fixtures/media/static_png.png", 2, 2, 20, 10).is_err());
+    }
+
+    #[test]
+    fn test_missing_file_errors() {
+        assert!(poster_frame_image("missing.gif", Duration::ZERO).is_err());
+    }
+}
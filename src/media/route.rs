@@ -0,0 +1,298 @@
+//! Choosing how to show a media file with what the build and terminal offer.
+//!
+//! Whether a file can be shown as intended depends on three things besides
+//! its format: which Cargo features were compiled in (see
+//! [`capabilities`](crate::capabilities)), whether the font has braille,
+//! and whether anyone is watching. [`route`] weighs them once and returns a
+//! [`Decision`]: the [`Route`] to take and, when that isn't the one the
+//! format would normally get, the [`Fallback`] that explains why.
+//! [`quick::show_file`](crate::quick::show_file) and
+//! [`quick::load_file`](crate::quick::load_file) follow it, and log the
+//! fallback as a warning, instead of each checking features on its own.
+//!
+//! The rules, in order:
+//!
+//! 1. Unknown formats are an error; there's nothing to fall back to.
+//! 2. SVG without the `svg` feature, or video without `video`, gets a
+//!    [`Placeholder`](Route::Placeholder) card naming the file and the
+//!    missing feature.
+//! 3. Animations and video get a single [`Poster`](Route::Poster) frame
+//!    when stdout isn't a terminal.
+//! 4. Static images get [`Density`](Route::Density) characters when the
+//!    terminal can only show ASCII, which keeps more of the tones than
+//!    dots drawn as ASCII do.
+//! 5. Otherwise each format takes its usual route.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::build_info::{BuildInfo, Feature};
+//! use dotmax::media::route::{route, Environment, Fallback, Route};
+//! use dotmax::media::{MediaFormat, VideoCodec};
+//! use dotmax::render::glyphs::GlyphRepertoire;
+//!
+//! let env = Environment {
+//!     build: BuildInfo {
+//!         version: "0.1.0",
+//!         features: vec![Feature::Image],
+//!         ffmpeg: None,
+//!     },
+//!     glyphs: GlyphRepertoire::Braille,
+//!     interactive: true,
+//! };
+//! let decision = route(MediaFormat::Video(VideoCodec::H264), &env)?;
+//! assert_eq!(decision.route, Route::Placeholder);
+//! assert_eq!(decision.fallback, Some(Fallback::MissingFeature(Feature::Video)));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+
+use super::MediaFormat;
+use crate::build_info::{capabilities, BuildInfo, Feature};
+use crate::render::glyphs::{glyph_policy, GlyphRepertoire};
+use crate::{DotmaxError, Result};
+
+/// How a media file is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Route {
+    /// Render a still image to braille
+    Image,
+    /// Render a still image as density characters, one per cell
+    Density,
+    /// Rasterize an SVG and render it to braille
+    Svg,
+    /// Play a GIF, APNG, or WebP animation
+    Animation,
+    /// Play a video through FFmpeg
+    Video,
+    /// Show one frame of an animation or video as a still
+    Poster,
+    /// Show a card naming the file and why it can't be shown
+    Placeholder,
+}
+
+/// Why a [`Decision`] took a different route than the format's usual one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// The feature needed to decode the format wasn't compiled in
+    MissingFeature(Feature),
+    /// Stdout isn't a terminal, so there's no one to play to
+    NotInteractive,
+    /// The terminal can only show ASCII
+    NoUnicode,
+}
+
+impl fmt::Display for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFeature(feature) => write!(
+                f,
+                "'{feature}' feature not compiled in (rebuild with `--features {feature}`)"
+            ),
+            Self::NotInteractive => f.write_str("stdout is not a terminal, showing one frame"),
+            Self::NoUnicode => f.write_str("terminal shows only ASCII, using density characters"),
+        }
+    }
+}
+
+/// The route [`route`] picked, and why it differs from the usual one, if it
+/// does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decision {
+    /// How to show the file
+    pub route: Route,
+    /// Why this isn't the format's usual route, or `None` if it is
+    pub fallback: Option<Fallback>,
+}
+
+impl Decision {
+    const fn usual(route: Route) -> Self {
+        Self {
+            route,
+            fallback: None,
+        }
+    }
+
+    const fn fallback(route: Route, fallback: Fallback) -> Self {
+        Self {
+            route,
+            fallback: Some(fallback),
+        }
+    }
+}
+
+/// What [`route`] decides with: the build, the font, and whether stdout is
+/// a terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    /// Compiled-in features
+    pub build: BuildInfo,
+    /// The best glyphs the terminal can show (see
+    /// [`glyph_policy`](crate::render::glyphs::glyph_policy))
+    pub glyphs: GlyphRepertoire,
+    /// Whether output goes to a terminal someone can watch
+    pub interactive: bool,
+}
+
+impl Environment {
+    /// This process's build, glyph policy, and stdout.
+    #[must_use]
+    pub fn detect() -> Self {
+        use std::io::IsTerminal;
+
+        Self {
+            build: capabilities(),
+            glyphs: glyph_policy().repertoire(),
+            interactive: std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// Picks how to show media of `format` in `env`, following the rules in the
+/// [module documentation](self).
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::FormatError`] for [`MediaFormat::Unknown`].
+pub fn route(format: MediaFormat, env: &Environment) -> Result<Decision> {
+    let needs = |feature: Feature, route: Route| {
+        if env.build.has(feature) {
+            Decision::usual(route)
+        } else {
+            Decision::fallback(Route::Placeholder, Fallback::MissingFeature(feature))
+        }
+    };
+    let playback = |decision: Decision| {
+        if decision.route == Route::Placeholder || env.interactive {
+            decision
+        } else {
+            Decision::fallback(Route::Poster, Fallback::NotInteractive)
+        }
+    };
+
+    Ok(match format {
+        MediaFormat::StaticImage(_) if env.glyphs == GlyphRepertoire::Ascii => {
+            Decision::fallback(Route::Density, Fallback::NoUnicode)
+        }
+        MediaFormat::StaticImage(_) => Decision::usual(Route::Image),
+        MediaFormat::Svg => needs(Feature::Svg, Route::Svg),
+        MediaFormat::AnimatedGif | MediaFormat::AnimatedPng | MediaFormat::AnimatedWebp => {
+            playback(Decision::usual(Route::Animation))
+        }
+        MediaFormat::Video(_) => playback(needs(Feature::Video, Route::Video)),
+        MediaFormat::Unknown => {
+            return Err(DotmaxError::FormatError {
+                format: "unknown format".to_string(),
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::{ImageFormat, VideoCodec};
+
+    fn env(features: &[Feature], glyphs: GlyphRepertoire, interactive: bool) -> Environment {
+        Environment {
+            build: BuildInfo {
+                version: "0.0.0",
+                features: features.to_vec(),
+                ffmpeg: None,
+            },
+            glyphs,
+            interactive,
+        }
+    }
+
+    #[test]
+    fn test_usual_routes_with_everything() {
+        let full = env(
+            &[Feature::Image, Feature::Svg, Feature::Video],
+            GlyphRepertoire::Braille,
+            true,
+        );
+        let cases = [
+            (MediaFormat::StaticImage(ImageFormat::Png), Route::Image),
+            (MediaFormat::Svg, Route::Svg),
+            (MediaFormat::AnimatedGif, Route::Animation),
+            (MediaFormat::AnimatedWebp, Route::Animation),
+            (MediaFormat::Video(VideoCodec::Vp9), Route::Video),
+        ];
+        for (format, expected) in cases {
+            assert_eq!(route(format, &full).unwrap(), Decision::usual(expected));
+        }
+    }
+
+    #[test]
+    fn test_missing_features_get_placeholders() {
+        let bare = env(&[Feature::Image], GlyphRepertoire::Braille, false);
+        assert_eq!(
+            route(MediaFormat::Svg, &bare).unwrap(),
+            Decision::fallback(Route::Placeholder, Fallback::MissingFeature(Feature::Svg))
+        );
+        // The missing feature matters more than the missing terminal
+        assert_eq!(
+            route(MediaFormat::Video(VideoCodec::H264), &bare).unwrap(),
+            Decision::fallback(Route::Placeholder, Fallback::MissingFeature(Feature::Video))
+        );
+    }
+
+    #[test]
+    fn test_piped_playback_gets_a_poster() {
+        let piped = env(
+            &[Feature::Image, Feature::Video],
+            GlyphRepertoire::Braille,
+            false,
+        );
+        for format in [
+            MediaFormat::AnimatedPng,
+            MediaFormat::Video(VideoCodec::Av1),
+        ] {
+            assert_eq!(
+                route(format, &piped).unwrap(),
+                Decision::fallback(Route::Poster, Fallback::NotInteractive)
+            );
+        }
+        assert_eq!(
+            route(MediaFormat::StaticImage(ImageFormat::Jpeg), &piped)
+                .unwrap()
+                .route,
+            Route::Image
+        );
+    }
+
+    #[test]
+    fn test_ascii_terminals_get_density() {
+        let ascii = env(&[Feature::Image], GlyphRepertoire::Ascii, true);
+        assert_eq!(
+            route(MediaFormat::StaticImage(ImageFormat::Bmp), &ascii).unwrap(),
+            Decision::fallback(Route::Density, Fallback::NoUnicode)
+        );
+        // Shade blocks still carry dots
+        let shades = env(&[Feature::Image], GlyphRepertoire::Shades, true);
+        assert_eq!(
+            route(MediaFormat::StaticImage(ImageFormat::Bmp), &shades)
+                .unwrap()
+                .route,
+            Route::Image
+        );
+    }
+
+    #[test]
+    fn test_unknown_is_an_error() {
+        let full = env(&[Feature::Image], GlyphRepertoire::Braille, true);
+        assert!(matches!(
+            route(MediaFormat::Unknown, &full),
+            Err(DotmaxError::FormatError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fallback_messages() {
+        let missing = Fallback::MissingFeature(Feature::Video).to_string();
+        assert!(missing.contains("--features video"));
+        assert!(Fallback::NoUnicode.to_string().contains("density"));
+    }
+}
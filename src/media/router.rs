@@ -6,6 +6,12 @@
 
 use std::time::Duration;
 
+use super::composite::Matte;
+use super::control::Controlled;
+use super::hook::{DecodeTiming, FrameInfo, WithFrameHook};
+use super::playlist::Playlist;
+#[cfg(feature = "image")]
+use crate::image::RenderOptions;
 use crate::{BrailleGrid, Result};
 
 // ============================================================================
@@ -40,9 +46,9 @@ use crate::{BrailleGrid, Result};
 /// }
 /// ```
 ///
-/// # Future Extensions
+/// # Animated Players
 ///
-/// The `Animated` variant will be populated by:
+/// The `Animated` variant is populated by:
 /// - Story 9.2: Animated GIF playback (`GifPlayer`)
 /// - Story 9.3: Animated PNG playback (`ApngPlayer`)
 /// - Story 9.4: Video playback (`VideoPlayer`)
@@ -102,12 +108,12 @@ pub enum MediaContent {
 /// `MediaPlayer` requires `Send` to allow moving players across threads.
 /// Implementors should ensure thread-safe access to underlying resources.
 ///
-/// # Future Implementation
+/// # Implementations
 ///
-/// This trait will be implemented by:
 /// - `GifPlayer` (Story 9.2)
 /// - `ApngPlayer` (Story 9.3)
-/// - `VideoPlayer` (Story 9.4)
+/// - `VideoPlayer` (Story 9.4, `video` feature)
+/// - `WebcamPlayer` (`video` feature)
 pub trait MediaPlayer: Send + std::fmt::Debug {
     /// Returns the next frame and its display duration.
     ///
@@ -146,6 +152,35 @@ pub trait MediaPlayer: Send + std::fmt::Debug {
     /// - `None` - Loop behavior not specified (default to once)
     fn loop_count(&self) -> Option<u16>;
 
+    /// Returns the running time of one pass of the media, if known.
+    ///
+    /// The default implementation returns `None`. Players that know their
+    /// length up front (such as video with a container duration) override it.
+    fn duration(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns which dots of the last returned frame are opaque, for players
+    /// that key out part of the picture.
+    ///
+    /// The default implementation returns `None`, meaning the whole frame is
+    /// opaque. Players configured with a chroma key override it;
+    /// [`KeyedComposite`](super::KeyedComposite) uses it to show a background
+    /// through the keyed-out dots.
+    fn matte(&self) -> Option<&Matte> {
+        None
+    }
+
+    /// Returns how long the last frame took to decode and convert to dots,
+    /// for players that time those steps separately.
+    ///
+    /// The default implementation returns `None`; callers then count the
+    /// whole of [`next_frame`](Self::next_frame) as decoding. It feeds the
+    /// [`PlaybackDiagnostics`](crate::widgets::PlaybackDiagnostics) overlay.
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        None
+    }
+
     /// Handles terminal resize events.
     ///
     /// Call this method when the terminal size changes to update the
@@ -164,6 +199,175 @@ pub trait MediaPlayer: Send + std::fmt::Debug {
     fn handle_resize(&mut self, _width: usize, _height: usize) {
         // Default: do nothing. Players can override to update their dimensions.
     }
+
+    /// Moves playback so the next frame returned is the first one starting
+    /// at or after `position`, and returns where that frame starts.
+    ///
+    /// The default implementation resets and decodes forward, discarding
+    /// frames, which works for any player that can be reset. Seeking past
+    /// the end leaves the player at the end. Live sources override it to
+    /// fail.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error hit while decoding the skipped frames.
+    fn seek(&mut self, position: Duration) -> Result<Duration> {
+        self.reset();
+        let mut elapsed = Duration::ZERO;
+        while elapsed < position {
+            match self.next_frame() {
+                Some(Ok((_, delay))) if !delay.is_zero() => elapsed += delay,
+                // Zero-length frames can't advance time; stop rather than spin
+                Some(Ok(_)) | None => break,
+                Some(Err(e)) => return Err(e),
+            }
+        }
+        Ok(elapsed)
+    }
+
+    /// Replaces the player's render settings, for players that render
+    /// pixels with [`RenderOptions`] (video and webcam).
+    ///
+    /// The default implementation ignores the options: GIF and APNG frames
+    /// are already dots-ready and have nothing to adjust.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`](crate::DotmaxError::InvalidParameter)
+    /// if the options fail [`RenderOptions::validate`].
+    #[cfg(feature = "image")]
+    fn set_render_options(&mut self, _options: RenderOptions) -> Result<()> {
+        Ok(())
+    }
+
+    /// The player's current render settings, or `None` for players that
+    /// don't render pixels (the default).
+    #[cfg(feature = "image")]
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        None
+    }
+
+    /// Wraps this player so `hook` runs on every frame before it is returned.
+    ///
+    /// The hook can draw on the frame's grid (timestamps, progress bars,
+    /// watermarks) and receives a [`FrameInfo`] with the frame's index and
+    /// timing. An error from the hook is returned in place of the frame.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::media::{GifPlayer, MediaPlayer};
+    /// use dotmax::primitives::draw_line;
+    ///
+    /// // Draw a progress bar along the bottom row of dots
+    /// let player = GifPlayer::new("loading.gif")?.on_frame(|grid, info| {
+    ///     if let Some(progress) = info.progress() {
+    ///         let y = grid.dot_height() as i32 - 1;
+    ///         let end = (progress * grid.dot_width() as f32) as i32 - 1;
+    ///         draw_line(grid, 0, y, end, y)?;
+    ///     }
+    ///     Ok(())
+    /// });
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    fn on_frame<F>(self, hook: F) -> WithFrameHook<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&mut BrailleGrid, &FrameInfo) -> Result<()> + Send,
+    {
+        WithFrameHook::new(self, hook)
+    }
+
+    /// Wraps this player so it can be paused, sought, and re-tuned from
+    /// other threads through [`PlayerControl`](super::PlayerControl)
+    /// handles.
+    ///
+    /// See [`Controlled`] for an example.
+    fn controlled(self) -> Controlled<Self>
+    where
+        Self: Sized,
+    {
+        Controlled::new(self)
+    }
+}
+
+impl<P: MediaPlayer + ?Sized> MediaPlayer for Box<P> {
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        (**self).next_frame()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+
+    fn frame_count(&self) -> Option<usize> {
+        (**self).frame_count()
+    }
+
+    fn loop_count(&self) -> Option<u16> {
+        (**self).loop_count()
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        (**self).duration()
+    }
+
+    fn matte(&self) -> Option<&Matte> {
+        (**self).matte()
+    }
+
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        (**self).decode_timing()
+    }
+
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        (**self).handle_resize(width, height);
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration> {
+        (**self).seek(position)
+    }
+
+    #[cfg(feature = "image")]
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        (**self).set_render_options(options)
+    }
+
+    #[cfg(feature = "image")]
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        (**self).current_render_options()
+    }
+}
+
+impl dyn MediaPlayer {
+    /// Opens a list of media files as one player that plays them in order.
+    ///
+    /// Shorthand for [`Playlist::new`]; the result can be configured further
+    /// and boxed into [`MediaContent::Animated`] like any other player.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`](crate::DotmaxError::InvalidParameter)
+    /// if `paths` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::media::{MediaContent, MediaPlayer, RepeatMode};
+    ///
+    /// let playlist = <dyn MediaPlayer>::open_playlist(["a.gif", "b.mp4"])?
+    ///     .repeat(RepeatMode::All);
+    /// let content = MediaContent::Animated(Box::new(playlist));
+    /// # let _ = content;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn open_playlist<I>(paths: I) -> Result<Playlist>
+    where
+        I: IntoIterator,
+        I::Item: Into<std::path::PathBuf>,
+    {
+        Playlist::new(paths)
+    }
 }
 
 // ============================================================================
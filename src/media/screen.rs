@@ -0,0 +1,614 @@
+//! Live screen capture.
+//!
+//! [`ScreenCapturePlayer`] records a monitor, a region of one, or a single
+//! window and renders each capture to braille, implementing [`MediaPlayer`]
+//! so it plays anywhere a [`WebcamPlayer`](super::WebcamPlayer) would: in a
+//! terminal screen-sharing demo, or a corner of a monitoring dashboard.
+//!
+//! # Requirements
+//!
+//! This module requires the `screen-capture` feature, which captures through
+//! the [`xcap`] crate:
+//! - **Linux**: X11, or Wayland with the screenshot portal (the
+//!   `xcb`, `wayland-client`, and `pipewire` development libraries are
+//!   needed to build)
+//! - **macOS**: Screen Recording permission for the terminal
+//! - **Windows**: no setup
+//!
+//! # Examples
+//!
+//! ## Mirror the Primary Monitor
+//!
+//! ```no_run
+//! use dotmax::media::{MediaPlayer, ScreenCapturePlayer};
+//!
+//! let mut player = ScreenCapturePlayer::new()?;
+//! while let Some(result) = player.next_frame() {
+//!     let (grid, _delay) = result?;
+//!     // Render grid to terminal
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! ## Capture One Window
+//!
+//! ```no_run
+//! use dotmax::media::{list_capture_sources, CaptureSource, ScreenCapturePlayer};
+//!
+//! let sources = list_capture_sources()?;
+//! let editor = sources
+//!     .iter()
+//!     .find(|info| matches!(info.source, CaptureSource::Window(_)) && info.name.contains("vim"))
+//!     .expect("no vim window");
+//!
+//! let player = ScreenCapturePlayer::builder()
+//!     .source(editor.source)
+//!     .fps(5)
+//!     .build()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! ## Watch a Region
+//!
+//! ```no_run
+//! use dotmax::image::ColorMode;
+//! use dotmax::media::ScreenCapturePlayer;
+//!
+//! // The top-left 800×600 pixels of the primary monitor, in color
+//! let player = ScreenCapturePlayer::builder()
+//!     .region(0, 0, 800, 600)
+//!     .color_mode(ColorMode::TrueColor)
+//!     .build()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! # Architecture
+//!
+//! Monitors and windows are looked up by id on every capture rather than
+//! held open, which keeps the player `Send` and lets it report a closed
+//! window or unplugged monitor as an error instead of a stale picture.
+//! Frames are rendered through an [`image::Pipeline`](crate::image::Pipeline),
+//! so an unchanged screen, or a change to color mode alone, doesn't redo the
+//! whole render.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use image::{DynamicImage, RgbaImage};
+use xcap::{Monitor, Window};
+
+use crate::image::{ColorMode, DitheringMethod, Pipeline, RenderOptions};
+use crate::{BrailleGrid, DotmaxError, Result};
+
+use super::MediaPlayer;
+
+/// Capture rate when none is set. Screens change less than camera images,
+/// and each capture is a full-resolution copy of the display.
+const DEFAULT_FPS: u32 = 10;
+
+// ============================================================================
+// Capture sources
+// ============================================================================
+
+/// What a [`ScreenCapturePlayer`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CaptureSource {
+    /// The primary monitor, or the first one if none is marked primary
+    #[default]
+    PrimaryMonitor,
+    /// The monitor with this id
+    Monitor(u32),
+    /// The window with this id
+    Window(u32),
+}
+
+impl fmt::Display for CaptureSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PrimaryMonitor => write!(f, "primary monitor"),
+            Self::Monitor(id) => write!(f, "monitor {id}"),
+            Self::Window(id) => write!(f, "window {id}"),
+        }
+    }
+}
+
+/// A monitor or window that can be captured, as listed by
+/// [`list_capture_sources`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureSourceInfo {
+    /// Pass this to [`ScreenCapturePlayerBuilder::source`] to capture it
+    pub source: CaptureSource,
+    /// Monitor name, or window title (the application name for untitled
+    /// windows)
+    pub name: String,
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+}
+
+/// Lists the monitors, then the visible windows, that can be captured.
+///
+/// Monitors or windows whose details can't be read are left out, as are
+/// minimized windows.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::ScreenCaptureError`] if the display server can't
+/// be queried at all.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::media::list_capture_sources;
+///
+/// for info in list_capture_sources()? {
+///     println!("{}: {} ({}x{})", info.source, info.name, info.width, info.height);
+/// }
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn list_capture_sources() -> Result<Vec<CaptureSourceInfo>> {
+    let monitors = Monitor::all().map_err(|e| capture_error("display", e))?;
+    let windows = Window::all().map_err(|e| capture_error("display", e))?;
+
+    let monitors = monitors.iter().filter_map(|monitor| {
+        Some(CaptureSourceInfo {
+            source: CaptureSource::Monitor(monitor.id().ok()?),
+            name: monitor.name().unwrap_or_default(),
+            width: monitor.width().ok()?,
+            height: monitor.height().ok()?,
+        })
+    });
+    let windows = windows
+        .iter()
+        .filter(|window| !window.is_minimized().unwrap_or(false))
+        .filter_map(|window| {
+            let title = window.title().unwrap_or_default();
+            Some(CaptureSourceInfo {
+                source: CaptureSource::Window(window.id().ok()?),
+                name: if title.is_empty() {
+                    window.app_name().unwrap_or_default()
+                } else {
+                    title
+                },
+                width: window.width().ok()?,
+                height: window.height().ok()?,
+            })
+        });
+    Ok(monitors.chain(windows).collect())
+}
+
+// ============================================================================
+// ScreenCapturePlayer
+// ============================================================================
+
+/// Live screen capture player implementing the [`MediaPlayer`] trait.
+///
+/// Like [`WebcamPlayer`](super::WebcamPlayer), it is a live stream:
+/// - `next_frame()` captures the screen as it is now
+/// - `reset()` is a no-op and `seek()` fails
+/// - `frame_count()` returns `None` (unbounded stream)
+/// - `loop_count()` returns `Some(0)` (infinite)
+///
+/// Each frame's delay is what remains of the frame interval after capturing
+/// and rendering, so playback holds the configured rate when the machine
+/// keeps up.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::media::{MediaPlayer, ScreenCapturePlayer};
+///
+/// let mut player = ScreenCapturePlayer::new()?;
+/// if let Some(Ok((grid, _))) = player.next_frame() {
+///     println!("{}", grid.to_unicode_grid().len());
+/// }
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug)]
+pub struct ScreenCapturePlayer {
+    source: CaptureSource,
+    /// `(x, y, width, height)` in pixels, relative to the monitor or window
+    region: Option<(u32, u32, u32, u32)>,
+    fps: u32,
+    /// Holds the latest capture and caches its render
+    pipeline: Pipeline,
+}
+
+impl ScreenCapturePlayer {
+    /// Creates a player for the primary monitor with default settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::ScreenCaptureError`] if the screen can't be
+    /// captured, e.g. for lack of permission.
+    pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// Returns a builder for choosing the source, region, rate, and render
+    /// settings.
+    #[must_use]
+    pub fn builder() -> ScreenCapturePlayerBuilder {
+        ScreenCapturePlayerBuilder::new()
+    }
+
+    /// The monitor or window being captured.
+    #[must_use]
+    pub const fn source(&self) -> CaptureSource {
+        self.source
+    }
+
+    /// The captured region as `(x, y, width, height)`, or `None` for the
+    /// whole monitor or window.
+    #[must_use]
+    pub const fn region(&self) -> Option<(u32, u32, u32, u32)> {
+        self.region
+    }
+
+    /// Captures per second.
+    #[must_use]
+    pub const fn fps(&self) -> u32 {
+        self.fps
+    }
+
+    /// The despeckle setting.
+    #[must_use]
+    pub const fn get_despeckle(&self) -> Option<u8> {
+        self.pipeline.despeckle()
+    }
+
+    /// Updates the despeckle filter at runtime: dots with fewer than this
+    /// many set neighbors are removed. None disables it.
+    pub fn set_despeckle(&mut self, min_neighbors: Option<u8>) {
+        self.pipeline.set_despeckle(min_neighbors);
+    }
+
+    /// Replaces the dithering, threshold, brightness, contrast, gamma, and
+    /// color mode at runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`]; the current settings are kept.
+    pub fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        self.pipeline.set_options(options)
+    }
+
+    /// Grabs the source as it is now.
+    fn capture(&self) -> Result<RgbaImage> {
+        let error = |e: xcap::XCapError| capture_error(self.source, e);
+        match self.source {
+            CaptureSource::Window(id) => {
+                let window = Window::all()
+                    .map_err(error)?
+                    .into_iter()
+                    .find(|window| window.id().ok() == Some(id))
+                    .ok_or_else(|| capture_error(self.source, "window not found"))?;
+                let image = window.capture_image().map_err(error)?;
+                match self.region {
+                    Some(region) => crop(&image, region)
+                        .ok_or_else(|| capture_error(self.source, region_message(region, &image))),
+                    None => Ok(image),
+                }
+            }
+            CaptureSource::PrimaryMonitor | CaptureSource::Monitor(_) => {
+                let monitors = Monitor::all().map_err(error)?;
+                let monitor = if let CaptureSource::Monitor(id) = self.source {
+                    monitors
+                        .iter()
+                        .find(|monitor| monitor.id().ok() == Some(id))
+                } else {
+                    monitors
+                        .iter()
+                        .find(|monitor| monitor.is_primary().unwrap_or(false))
+                        .or_else(|| monitors.first())
+                }
+                .ok_or_else(|| capture_error(self.source, "monitor not found"))?;
+                match self.region {
+                    Some((x, y, width, height)) => monitor.capture_region(x, y, width, height),
+                    None => monitor.capture_image(),
+                }
+                .map_err(error)
+            }
+        }
+    }
+
+    /// Captures and renders one frame. A capture identical to the last one
+    /// is served from the pipeline's cache.
+    fn capture_frame(&mut self) -> Result<BrailleGrid> {
+        let frame = self.capture()?;
+        if self.pipeline.image().and_then(DynamicImage::as_rgba8) != Some(&frame) {
+            self.pipeline.set_image(DynamicImage::ImageRgba8(frame));
+        }
+        self.pipeline.render()
+    }
+
+    /// Time between captures.
+    fn frame_interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+}
+
+impl MediaPlayer for ScreenCapturePlayer {
+    /// Captures the screen and returns it with the time left until the
+    /// next capture is due.
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        let started = Instant::now();
+        let grid = match self.capture_frame() {
+            Ok(grid) => grid,
+            Err(e) => return Some(Err(e)),
+        };
+        let delay = self.frame_interval().saturating_sub(started.elapsed());
+        Some(Ok((grid, delay)))
+    }
+
+    /// No-op: a live screen can't be rewound.
+    fn reset(&mut self) {
+        tracing::debug!("ScreenCapturePlayer::reset() called - no-op for live streams");
+    }
+
+    /// Returns `None` as screen capture is unbounded.
+    fn frame_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns `Some(0)` indicating infinite looping.
+    fn loop_count(&self) -> Option<u16> {
+        Some(0)
+    }
+
+    /// Renders subsequent frames at the new terminal size.
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        if let Err(e) = self.pipeline.set_size(width, height, true) {
+            tracing::warn!("Ignoring resize to {}x{}: {}", width, height, e);
+        }
+    }
+
+    /// Always fails: a live screen has no past or future to move to.
+    fn seek(&mut self, _position: Duration) -> Result<Duration> {
+        Err(capture_error(self.source, "Cannot seek a live screen"))
+    }
+
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        Self::set_render_options(self, options)
+    }
+
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        Some(self.pipeline.options())
+    }
+}
+
+// ============================================================================
+// ScreenCapturePlayerBuilder
+// ============================================================================
+
+/// Builder for configuring [`ScreenCapturePlayer`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::image::DitheringMethod;
+/// use dotmax::media::{CaptureSource, ScreenCapturePlayer};
+///
+/// let player = ScreenCapturePlayer::builder()
+///     .source(CaptureSource::Monitor(1))
+///     .fps(15)
+///     .dithering(DitheringMethod::FloydSteinberg)
+///     .build()?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScreenCapturePlayerBuilder {
+    source: CaptureSource,
+    region: Option<(u32, u32, u32, u32)>,
+    fps: u32,
+    options: RenderOptions,
+    despeckle: Option<u8>,
+}
+
+impl Default for ScreenCapturePlayerBuilder {
+    fn default() -> Self {
+        Self {
+            source: CaptureSource::PrimaryMonitor,
+            region: None,
+            fps: DEFAULT_FPS,
+            // Bayer is deterministic, so unchanged parts of the screen don't
+            // shimmer between frames the way error diffusion would
+            options: RenderOptions::new().dithering(DitheringMethod::Bayer),
+            despeckle: None,
+        }
+    }
+}
+
+impl ScreenCapturePlayerBuilder {
+    /// Creates a builder for the primary monitor at 10 fps with Bayer
+    /// dithering.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the monitor or window to capture.
+    #[must_use]
+    pub const fn source(mut self, source: CaptureSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Captures only the `width × height` pixel region whose top-left corner
+    /// is `(x, y)`, relative to the monitor or window.
+    #[must_use]
+    pub const fn region(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.region = Some((x, y, width, height));
+        self
+    }
+
+    /// Sets the capture rate in frames per second.
+    #[must_use]
+    pub const fn fps(mut self, fps: u32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Sets the dithering algorithm.
+    #[must_use]
+    pub const fn dithering(mut self, method: DitheringMethod) -> Self {
+        self.options.dithering = method;
+        self
+    }
+
+    /// Sets the color mode for rendering.
+    #[must_use]
+    pub const fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.options.color_mode = mode;
+        self
+    }
+
+    /// Sets the despeckle filter: dots with fewer than this many set
+    /// neighbors are removed. None disables it.
+    #[must_use]
+    pub const fn despeckle(mut self, min_neighbors: Option<u8>) -> Self {
+        self.despeckle = min_neighbors;
+        self
+    }
+
+    /// Applies a whole set of [`RenderOptions`], replacing the dithering
+    /// and color mode along with the adjustments. Checked by
+    /// [`build`](Self::build).
+    #[must_use]
+    pub const fn render_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Checks the settings and captures once, so a missing source or
+    /// missing permission fails here rather than on the first frame.
+    ///
+    /// # Errors
+    ///
+    /// - [`DotmaxError::InvalidParameter`] if the fps is 0 or the render
+    ///   options fail [`RenderOptions::validate`]
+    /// - [`DotmaxError::InvalidDimensions`] if the region is empty
+    /// - [`DotmaxError::ScreenCaptureError`] if the capture fails
+    pub fn build(self) -> Result<ScreenCapturePlayer> {
+        self.validate()?;
+        let (terminal_width, terminal_height) = crate::image::detect_terminal_size();
+        let mut pipeline = Pipeline::new();
+        pipeline.set_size(terminal_width, terminal_height, true)?;
+        pipeline.set_options(self.options)?;
+        pipeline.set_despeckle(self.despeckle);
+
+        let mut player = ScreenCapturePlayer {
+            source: self.source,
+            region: self.region,
+            fps: self.fps,
+            pipeline,
+        };
+        let first = player.capture()?;
+        tracing::debug!(
+            "Capturing {} at {}x{} pixels, {} fps",
+            player.source,
+            first.width(),
+            first.height(),
+            player.fps
+        );
+        player.pipeline.set_image(DynamicImage::ImageRgba8(first));
+        Ok(player)
+    }
+
+    /// The checks [`build`](Self::build) makes before touching the screen.
+    fn validate(&self) -> Result<()> {
+        if self.fps == 0 {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "fps".to_string(),
+                value: "0".to_string(),
+                min: "1".to_string(),
+                max: "display refresh rate".to_string(),
+            });
+        }
+        if let Some((_, _, width, height)) = self.region {
+            if width == 0 || height == 0 {
+                return Err(DotmaxError::InvalidDimensions {
+                    width: width as usize,
+                    height: height as usize,
+                });
+            }
+        }
+        self.options.validate()
+    }
+}
+
+/// Cuts `(x, y, width, height)` out of `image`, or returns `None` if the
+/// region doesn't fit inside it.
+fn crop(image: &RgbaImage, (x, y, width, height): (u32, u32, u32, u32)) -> Option<RgbaImage> {
+    let fits = x
+        .checked_add(width)
+        .is_some_and(|right| right <= image.width())
+        && y.checked_add(height)
+            .is_some_and(|bottom| bottom <= image.height());
+    fits.then(|| image::imageops::crop_imm(image, x, y, width, height).to_image())
+}
+
+fn region_message((x, y, width, height): (u32, u32, u32, u32), image: &RgbaImage) -> String {
+    format!(
+        "region {width}x{height} at ({x}, {y}) is outside the {}x{} window",
+        image.width(),
+        image.height()
+    )
+}
+
+fn capture_error(source: impl fmt::Display, message: impl fmt::Display) -> DotmaxError {
+    DotmaxError::ScreenCaptureError {
+        source_name: source.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ensure ScreenCapturePlayer is Send (required by MediaPlayer trait)
+    fn _assert_screen_capture_player_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<ScreenCapturePlayer>();
+    }
+
+    #[test]
+    fn test_capture_source_display() {
+        assert_eq!(CaptureSource::default().to_string(), "primary monitor");
+        assert_eq!(CaptureSource::Monitor(2).to_string(), "monitor 2");
+        assert_eq!(CaptureSource::Window(77).to_string(), "window 77");
+    }
+
+    #[test]
+    fn test_builder_rejects_bad_settings() {
+        assert!(matches!(
+            ScreenCapturePlayer::builder().fps(0).validate(),
+            Err(DotmaxError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            ScreenCapturePlayer::builder()
+                .region(10, 10, 0, 50)
+                .validate(),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+        let options = RenderOptions::new().brightness(5.0);
+        assert!(ScreenCapturePlayer::builder()
+            .render_options(options)
+            .validate()
+            .is_err());
+        assert!(ScreenCapturePlayer::builder().validate().is_ok());
+    }
+
+    #[test]
+    fn test_crop_region() {
+        let image = RgbaImage::from_fn(8, 6, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        let cropped = crop(&image, (2, 1, 4, 5)).unwrap();
+        assert_eq!(cropped.dimensions(), (4, 5));
+        assert_eq!(cropped.get_pixel(0, 0).0, [2, 1, 0, 255]);
+        assert!(crop(&image, (6, 0, 4, 2)).is_none());
+        assert!(crop(&image, (0, 0, u32::MAX, 2)).is_none());
+    }
+}
@@ -32,6 +32,26 @@
 //! # Ok::<(), dotmax::DotmaxError>(())
 //! ```
 //!
+//! ## Keeping Up in Real Time
+//!
+//! When rendering can't keep up with the frame rate, a
+//! [`realtime`](VideoPlayer::realtime) player drops frames to stay in sync
+//! with the wall clock instead of falling ever further behind:
+//!
+//! ```no_run
+//! use dotmax::media::{MediaPlayer, VideoPlayer};
+//! use std::time::Duration;
+//!
+//! let mut player = VideoPlayer::new("video.mp4")?.realtime(true);
+//! player.seek(Duration::from_secs(90))?;
+//! while let Some(result) = player.next_frame() {
+//!     let (grid, delay) = result?;
+//!     // Render grid, then sleep for delay
+//! }
+//! println!("Dropped {} frames", player.frames_skipped());
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
 //! ## Using as MediaPlayer Trait Object
 //!
 //! ```no_run
@@ -51,19 +71,22 @@
 //! 3. **Scaling**: `software::scaling::Context` converts to RGB24 format
 //! 4. **Rendering**: `ImageRenderer` converts RGB data to `BrailleGrid`
 //!
+//! Only the video stream is decoded; audio tracks are ignored.
+//!
 //! # Thread Safety
 //!
 //! `VideoPlayer` is `Send` but not `Sync`. It can be moved between threads
 //! but should not be accessed from multiple threads simultaneously.
 
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::image::chroma_key::ChromaKey;
 use crate::image::temporal::{TemporalCoherence, TemporalConfig};
-use crate::image::{ColorMode, DitheringMethod, ImageRenderer};
+use crate::image::{ColorMode, DitheringMethod, ImageRenderer, RenderOptions};
 use crate::{BrailleGrid, DotmaxError, Result};
 
-use super::MediaPlayer;
+use super::{DecodeTiming, Matte, MediaPlayer};
 
 extern crate ffmpeg_next as ffmpeg;
 
@@ -88,6 +111,10 @@ struct SendableScaler(ScalingContext);
 #[allow(clippy::non_send_fields_in_send_ty)]
 unsafe impl Send for SendableScaler {}
 
+/// How far behind the clock a realtime player may fall before it assumes
+/// playback was paused, and restarts the clock rather than skipping ahead.
+const MAX_CATCH_UP: Duration = Duration::from_millis(500);
+
 // ============================================================================
 // VideoPlayer (AC: #1, #2, #3, #4, #5, #6, #7)
 // ============================================================================
@@ -173,6 +200,23 @@ pub struct VideoPlayer {
     /// Whether we've sent EOF to decoder.
     eof_sent: bool,
 
+    /// Whether `decoded_frame` holds a frame (left by a seek) that
+    /// `next_frame` should return before decoding another.
+    pending_frame: bool,
+
+    /// Whether to drop late frames to keep up with the wall clock.
+    realtime: bool,
+
+    /// Wall-clock instant and video time of the frame that started the
+    /// realtime clock.
+    clock: Option<(Instant, Duration)>,
+
+    /// Frames dropped to keep up since the player was created.
+    frames_skipped: u64,
+
+    /// How long the last frame took to decode and convert.
+    last_timing: Option<DecodeTiming>,
+
     /// Reusable RGB data buffer to avoid per-frame allocations.
     rgb_buffer: Vec<u8>,
 
@@ -195,8 +239,17 @@ pub struct VideoPlayer {
     /// Color mode for rendering (Monochrome, Grayscale, or TrueColor).
     color_mode: ColorMode,
 
+    /// Minimum set neighbors a dot needs to survive, or None to keep all dots.
+    despeckle: Option<u8>,
+
     /// Temporal coherence processor for reducing flicker.
     temporal_coherence: TemporalCoherence,
+
+    /// Key color to make transparent, or None for an opaque picture.
+    chroma_key: Option<ChromaKey>,
+
+    /// Matte of the last rendered frame, when keying.
+    matte: Option<Matte>,
 }
 
 impl std::fmt::Debug for VideoPlayer {
@@ -209,12 +262,16 @@ impl std::fmt::Debug for VideoPlayer {
             .field("duration", &self.video_duration)
             .field("frame_count", &self.estimated_frame_count)
             .field("current_frame", &self.current_frame)
+            .field("realtime", &self.realtime)
+            .field("frames_skipped", &self.frames_skipped)
             .field("dithering", &self.dithering)
             .field("threshold", &self.threshold)
             .field("brightness", &self.brightness)
             .field("contrast", &self.contrast)
             .field("gamma", &self.gamma)
             .field("color_mode", &self.color_mode)
+            .field("despeckle", &self.despeckle)
+            .field("chroma_key", &self.chroma_key)
             .finish_non_exhaustive()
     }
 }
@@ -367,6 +424,11 @@ impl VideoPlayer {
             decoded_frame: VideoFrame::empty(),
             rgb_frame: VideoFrame::empty(),
             eof_sent: false,
+            pending_frame: false,
+            realtime: false,
+            clock: None,
+            frames_skipped: 0,
+            last_timing: None,
             rgb_buffer: vec![0u8; rgb_buffer_size],
             // Render settings - sensible defaults
             // Use Bayer dithering for video - it's deterministic (same input = same output)
@@ -377,8 +439,11 @@ impl VideoPlayer {
             contrast: 1.0,
             gamma: 1.0,
             color_mode: ColorMode::Monochrome,
+            despeckle: None,
             // Temporal coherence with video preset
             temporal_coherence: TemporalCoherence::new(TemporalConfig::video()),
+            chroma_key: None,
+            matte: None,
         })
     }
 
@@ -412,6 +477,28 @@ impl VideoPlayer {
         self.current_frame
     }
 
+    /// Drops frames that are already late, so playback keeps pace with the
+    /// wall clock when rendering is slower than the frame rate.
+    ///
+    /// The clock starts at the first frame returned, and restarts after a
+    /// seek or reset. A frame is dropped once it's a whole frame interval
+    /// late. Falling more than half a second behind (say, while the caller
+    /// paused) restarts the clock instead of skipping ahead. Off by default,
+    /// which returns every frame.
+    #[must_use]
+    pub fn realtime(mut self, enabled: bool) -> Self {
+        self.realtime = enabled;
+        self.clock = None;
+        self
+    }
+
+    /// Returns how many frames [`realtime`](Self::realtime) playback has
+    /// dropped so far.
+    #[must_use]
+    pub const fn frames_skipped(&self) -> u64 {
+        self.frames_skipped
+    }
+
     // ========== Render Settings Builder Methods ==========
 
     /// Sets the dithering algorithm for binary image conversion.
@@ -576,6 +663,59 @@ impl VideoPlayer {
         self
     }
 
+    /// Removes isolated dithering dots from each frame.
+    ///
+    /// Dots with fewer than `min_neighbors` set neighbors are cleared; see
+    /// [`crate::analysis::despeckle`]. `None` (default) disables the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::media::VideoPlayer;
+    ///
+    /// let player = VideoPlayer::new("video.mp4")?
+    ///     .despeckle(Some(1));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn despeckle(mut self, min_neighbors: Option<u8>) -> Self {
+        self.despeckle = min_neighbors;
+        self
+    }
+
+    /// Keys out pixels near a color, making them transparent when the player
+    /// is layered over a background with
+    /// [`KeyedComposite`](super::KeyedComposite). `None` (default) keeps the
+    /// whole picture.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::image::chroma_key::ChromaKey;
+    /// use dotmax::media::VideoPlayer;
+    ///
+    /// let player = VideoPlayer::new("greenscreen.mp4")?
+    ///     .chroma_key(Some(ChromaKey::green().tolerance(0.15)));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub fn chroma_key(mut self, key: Option<ChromaKey>) -> Self {
+        self.set_chroma_key(key);
+        self
+    }
+
+    /// Applies a whole set of [`RenderOptions`], e.g. one shared with an
+    /// [`ImageRenderer`](crate::image::ImageRenderer).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`].
+    pub fn render_options(mut self, options: RenderOptions) -> Result<Self> {
+        self.set_render_options(options)?;
+        Ok(self)
+    }
+
     // ========== Getters for current render settings ==========
 
     /// Returns the current dithering method.
@@ -614,6 +754,18 @@ impl VideoPlayer {
         self.color_mode
     }
 
+    /// Returns the current despeckle setting.
+    #[must_use]
+    pub const fn get_despeckle(&self) -> Option<u8> {
+        self.despeckle
+    }
+
+    /// Returns the current chroma key.
+    #[must_use]
+    pub const fn get_chroma_key(&self) -> Option<ChromaKey> {
+        self.chroma_key
+    }
+
     // ========== Mutable setters for runtime adjustment ==========
 
     /// Updates the dithering method at runtime.
@@ -646,6 +798,38 @@ impl VideoPlayer {
         self.color_mode = mode;
     }
 
+    /// Updates the despeckle filter at runtime.
+    pub fn set_despeckle(&mut self, min_neighbors: Option<u8>) {
+        self.despeckle = min_neighbors;
+    }
+
+    /// Updates the chroma key at runtime.
+    pub fn set_chroma_key(&mut self, key: Option<ChromaKey>) {
+        self.chroma_key = key;
+        if key.is_none() {
+            self.matte = None;
+        }
+    }
+
+    /// Replaces the dithering, threshold, brightness, contrast, gamma, and
+    /// color mode at runtime. Read them back with
+    /// `RenderOptions::from(&player)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`]; the current settings are kept.
+    pub fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        options.validate()?;
+        self.dithering = options.dithering;
+        self.threshold = options.threshold;
+        self.brightness = options.brightness;
+        self.contrast = options.contrast;
+        self.gamma = options.gamma;
+        self.color_mode = options.color_mode;
+        Ok(())
+    }
+
     // ========== Temporal Coherence Settings ==========
 
     /// Returns a reference to the temporal coherence configuration.
@@ -656,6 +840,9 @@ impl VideoPlayer {
 
     /// Updates the temporal coherence configuration at runtime.
     ///
+    /// Frame blending smooths each decoded frame before it is dithered; the
+    /// dot filter and persistence stages run on the rendered dots.
+    ///
     /// # Arguments
     ///
     /// * `config` - New temporal coherence settings
@@ -698,6 +885,72 @@ impl VideoPlayer {
         self.temporal_coherence.reset();
     }
 
+    /// Returns where the decoded frame sits in the video, from its
+    /// presentation timestamp.
+    #[allow(clippy::cast_precision_loss)]
+    fn decoded_timestamp(&self) -> Option<Duration> {
+        let pts = self.decoded_frame.timestamp()?;
+        let time_base = self.input_context.stream(self.video_stream_index)?.time_base();
+        if time_base.denominator() == 0 {
+            return None;
+        }
+        let secs =
+            pts as f64 * f64::from(time_base.numerator()) / f64::from(time_base.denominator());
+        Duration::try_from_secs_f64(secs).ok()
+    }
+
+    /// Video time of the decoded frame, estimated from the frame index when
+    /// it has no timestamp.
+    #[allow(clippy::cast_precision_loss)]
+    fn media_time(&self) -> Duration {
+        self.decoded_timestamp()
+            .unwrap_or_else(|| self.frame_delay().mul_f64(self.current_frame as f64))
+    }
+
+    /// Moves the demuxer to the keyframe at or before `at`, or back to the
+    /// start if the container can't seek.
+    fn seek_to_keyframe(&mut self, at: Duration) {
+        let target = i64::try_from(at.as_micros()).unwrap_or(i64::MAX);
+        if let Err(e) = self.input_context.seek(target, ..target) {
+            tracing::warn!("Seek to {:?} failed, decoding from the start: {}", at, e);
+            self.reset();
+        } else {
+            self.decoder.flush();
+            self.playback_ended = false;
+            self.eof_sent = false;
+            self.pending_frame = false;
+        }
+    }
+
+    /// Decodes past frames that are a frame interval or more behind the
+    /// realtime clock, starting the clock if needed. Returns `None` if the
+    /// video ends while catching up.
+    fn skip_late_frames(&mut self) -> Option<Result<()>> {
+        let now = Instant::now();
+        let Some((started, origin)) = self.clock else {
+            self.clock = Some((now, self.media_time()));
+            return Some(Ok(()));
+        };
+        let delay = self.frame_delay();
+        loop {
+            let due = started + self.media_time().saturating_sub(origin);
+            let late = now.saturating_duration_since(due);
+            if late > MAX_CATCH_UP {
+                // Paused or stalled rather than slow; carry on from here
+                self.clock = Some((now, self.media_time()));
+                return Some(Ok(()));
+            }
+            if late < delay {
+                return Some(Ok(()));
+            }
+            if let Err(e) = self.decode_next_frame()? {
+                return Some(Err(e));
+            }
+            self.current_frame += 1;
+            self.frames_skipped += 1;
+        }
+    }
+
     /// Decodes the next frame from the video.
     fn decode_next_frame(&mut self) -> Option<Result<()>> {
         if self.playback_ended {
@@ -755,6 +1008,83 @@ impl VideoPlayer {
         }
     }
 
+    /// Seeks to `at` and decodes the frame on screen at that time at the
+    /// video's native resolution, without rendering it.
+    ///
+    /// The seek lands on the keyframe at or before `at`; frames are then
+    /// decoded forward until one reaches `at`. Times past the end give the
+    /// last frame. Playback restarts from the beginning afterwards.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn frame_image_at(&mut self, at: Duration) -> Result<image::RgbImage> {
+        self.seek_to_keyframe(at);
+
+        let time_base = self
+            .input_context
+            .stream(self.video_stream_index)
+            .map(|stream| stream.time_base());
+        let mut last: Option<VideoFrame> = None;
+        while let Some(decoded) = self.decode_next_frame() {
+            decoded?;
+            last = Some(self.decoded_frame.clone());
+            let reached = match (self.decoded_frame.timestamp(), time_base) {
+                (Some(pts), Some(tb)) if tb.denominator() != 0 => {
+                    let secs =
+                        pts as f64 * f64::from(tb.numerator()) / f64::from(tb.denominator());
+                    secs >= at.as_secs_f64()
+                }
+                // Without timestamps, take the first frame after the seek
+                _ => true,
+            };
+            if reached {
+                break;
+            }
+        }
+
+        let frame = last.ok_or_else(|| DotmaxError::VideoError {
+            path: self.path.clone(),
+            message: format!("No frame decoded at {at:?}"),
+        })?;
+        let image = self.frame_to_rgb(&frame);
+        self.reset();
+        image
+    }
+
+    /// Converts a decoded frame to an RGB image at its native size.
+    fn frame_to_rgb(&self, frame: &VideoFrame) -> Result<image::RgbImage> {
+        let mut scaler = ScalingContext::get(
+            frame.format(),
+            frame.width(),
+            frame.height(),
+            Pixel::RGB24,
+            frame.width(),
+            frame.height(),
+            Flags::BILINEAR,
+        )
+        .map_err(|e| DotmaxError::VideoError {
+            path: self.path.clone(),
+            message: format!("Failed to create scaler: {e}"),
+        })?;
+        let mut rgb = VideoFrame::empty();
+        scaler.run(frame, &mut rgb).map_err(|e| DotmaxError::VideoError {
+            path: self.path.clone(),
+            message: format!("Frame scaling error: {e}"),
+        })?;
+
+        let (width, height) = (rgb.width(), rgb.height());
+        let stride = rgb.stride(0);
+        let row_len = width as usize * 3;
+        let data = rgb.data(0);
+        let mut pixels = Vec::with_capacity(row_len * height as usize);
+        for y in 0..height as usize {
+            pixels.extend_from_slice(&data[y * stride..y * stride + row_len]);
+        }
+
+        image::RgbImage::from_raw(width, height, pixels).ok_or_else(|| DotmaxError::VideoError {
+            path: self.path.clone(),
+            message: "Failed to create image from frame data".to_string(),
+        })
+    }
+
     /// Converts the decoded frame to a BrailleGrid.
     fn frame_to_grid(&mut self) -> Result<BrailleGrid> {
         // Scale to RGB24 at terminal dimensions (done by FFmpeg, very fast)
@@ -772,6 +1102,11 @@ impl VideoPlayer {
         let target_width = (self.terminal_width * 2) as u32;
         let target_height = (self.terminal_height * 4) as u32;
 
+        // Key from the unsmoothed frame so the matte follows the picture
+        self.matte = self
+            .chroma_key
+            .map(|key| key.matte_rgb24(data, stride, target_width, target_height));
+
         // Copy RGB data into reusable buffer (avoids per-frame allocation)
         let expected_size = (target_width * target_height * 3) as usize;
         if self.rgb_buffer.len() != expected_size {
@@ -795,6 +1130,13 @@ impl VideoPlayer {
                     message: "Failed to create image from frame data".to_string(),
                 })?;
 
+        // Drop temporal history on hard cuts, then smooth the frame against
+        // recent history before thresholding
+        if self.temporal_coherence.detect_scene_cut_rgb(&img) {
+            tracing::debug!(frame = self.current_frame, "Scene cut detected");
+        }
+        let img = self.temporal_coherence.blend_rgb(img);
+
         // Convert to RGBA for ImageRenderer
         let rgba_img = image::DynamicImage::ImageRgb8(img).into_rgba8();
 
@@ -809,6 +1151,9 @@ impl VideoPlayer {
         if let Some(t) = self.threshold {
             renderer = renderer.threshold(t);
         }
+        if let Some(n) = self.despeckle {
+            renderer = renderer.despeckle(n);
+        }
 
         // Apply adjustments (these return Result, so we chain with ?)
         if (self.brightness - 1.0).abs() > f32::EPSILON {
@@ -821,7 +1166,8 @@ impl VideoPlayer {
             renderer = renderer.gamma(self.gamma)?;
         }
 
-        let grid = renderer.render()?;
+        let mut grid = renderer.render()?;
+        self.temporal_coherence.process_grid(&mut grid);
 
         Ok(grid)
     }
@@ -836,24 +1182,52 @@ impl VideoPlayer {
     }
 }
 
+impl From<&VideoPlayer> for RenderOptions {
+    fn from(player: &VideoPlayer) -> Self {
+        Self {
+            dithering: player.dithering,
+            threshold: player.threshold,
+            brightness: player.brightness,
+            contrast: player.contrast,
+            gamma: player.gamma,
+            color_mode: player.color_mode,
+        }
+    }
+}
+
 impl MediaPlayer for VideoPlayer {
     /// Returns the next frame and its display duration.
     ///
     /// Decodes video frames on-demand using FFmpeg. Returns `None` when
     /// the video ends.
     fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
-        // Decode next frame
-        match self.decode_next_frame() {
-            Some(Ok(())) => {}
-            Some(Err(e)) => return Some(Err(e)),
-            None => return None,
+        let started = Instant::now();
+
+        // Decode next frame, unless a seek left one waiting
+        if !std::mem::take(&mut self.pending_frame) {
+            match self.decode_next_frame() {
+                Some(Ok(())) => {}
+                Some(Err(e)) => return Some(Err(e)),
+                None => return None,
+            }
+        }
+        if self.realtime {
+            if let Err(e) = self.skip_late_frames()? {
+                return Some(Err(e));
+            }
         }
 
         // Convert to grid
+        let decoded = Instant::now();
         let grid = match self.frame_to_grid() {
             Ok(g) => g,
             Err(e) => return Some(Err(e)),
         };
+        self.last_timing = Some(DecodeTiming {
+            decode: decoded - started,
+            convert: decoded.elapsed(),
+            queue_depth: None,
+        });
 
         let delay = self.frame_delay();
         self.current_frame += 1;
@@ -884,6 +1258,8 @@ impl MediaPlayer for VideoPlayer {
         self.current_frame = 0;
         self.playback_ended = false;
         self.eof_sent = false;
+        self.pending_frame = false;
+        self.clock = None;
 
         // Reset temporal coherence state (important when seeking/looping)
         self.temporal_coherence.reset();
@@ -902,6 +1278,52 @@ impl MediaPlayer for VideoPlayer {
         Some(1)
     }
 
+    /// Returns the container duration, if the file reports one.
+    fn duration(&self) -> Option<Duration> {
+        self.video_duration
+    }
+
+    /// Returns decode (including late frames skipped in realtime mode) and
+    /// conversion times of the last frame.
+    fn decode_timing(&self) -> Option<DecodeTiming> {
+        self.last_timing
+    }
+
+    /// Seeks with the container index: jumps to the keyframe at or before
+    /// `position`, then decodes forward without rendering to the first
+    /// frame at or after it. Much faster than the default, which renders
+    /// every frame from the start.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn seek(&mut self, position: Duration) -> Result<Duration> {
+        self.seek_to_keyframe(position);
+        self.temporal_coherence.reset();
+        self.clock = None;
+        let delay = self.frame_delay();
+
+        while let Some(decoded) = self.decode_next_frame() {
+            decoded?;
+            let at = self.decoded_timestamp();
+            // Without timestamps, take the first frame after the keyframe
+            if at.map_or(true, |at| at + delay > position) {
+                let at = at.unwrap_or(position);
+                self.current_frame = (at.as_secs_f64() * self.fps).round() as usize;
+                self.pending_frame = true;
+                return Ok(at);
+            }
+        }
+        // Past the end
+        Ok(self.video_duration.unwrap_or(position))
+    }
+
+    /// Returns the keyed-out dots of the last frame, when a chroma key is set.
+    fn matte(&self) -> Option<&Matte> {
+        self.matte.as_ref()
+    }
+
     /// Updates terminal dimensions for subsequent frame rendering.
     ///
     /// Call this when the terminal is resized to ensure frames are
@@ -941,6 +1363,14 @@ impl MediaPlayer for VideoPlayer {
             }
         }
     }
+
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        VideoPlayer::set_render_options(self, options)
+    }
+
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        Some(RenderOptions::from(self))
+    }
 }
 
 // ============================================================================
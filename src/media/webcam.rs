@@ -58,6 +58,36 @@
 //! # Ok::<(), dotmax::DotmaxError>(())
 //! ```
 //!
+//! ## Adapting to Lighting
+//!
+//! ```no_run
+//! use dotmax::image::exposure::AutoExposureConfig;
+//! use dotmax::media::WebcamPlayer;
+//!
+//! // Re-pick the threshold and steer brightness toward mid-gray as the room
+//! // gets lighter or darker
+//! let player = WebcamPlayer::builder()
+//!     .auto_exposure(Some(AutoExposureConfig {
+//!         target_mean: Some(128.0),
+//!         ..AutoExposureConfig::default()
+//!     }))
+//!     .build()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! ## Framing the Subject
+//!
+//! ```no_run
+//! use dotmax::image::roi::RoiConfig;
+//! use dotmax::media::WebcamPlayer;
+//!
+//! // Follow motion and zoom in on it, like a video call preview
+//! let player = WebcamPlayer::builder()
+//!     .roi(Some(RoiConfig::default()))
+//!     .build()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
 //! # Architecture
 //!
 //! `WebcamPlayer` uses FFmpeg (via `ffmpeg-next` crate) for device capture:
@@ -74,11 +104,14 @@
 
 use std::time::Duration;
 
+use crate::image::chroma_key::ChromaKey;
+use crate::image::exposure::{AutoExposure, AutoExposureConfig};
+use crate::image::roi::{RoiConfig, RoiRect, RoiTracker};
 use crate::image::temporal::{TemporalCoherence, TemporalConfig};
-use crate::image::{ColorMode, DitheringMethod};
+use crate::image::{ColorMode, DitheringMethod, RenderOptions};
 use crate::{BrailleGrid, DotmaxError, Result};
 
-use super::MediaPlayer;
+use super::{Matte, MediaPlayer};
 
 extern crate ffmpeg_next as ffmpeg;
 
@@ -427,6 +460,316 @@ fn parse_dshow_device_list(output: &str) -> Vec<WebcamDevice> {
     devices
 }
 
+// ============================================================================
+// Capture Modes
+// ============================================================================
+
+/// Codecs a camera can deliver compressed frames in, as FFmpeg names them.
+const COMPRESSED_FORMATS: &[&str] = &["mjpeg", "h264", "hevc", "h265"];
+
+/// A capture mode a webcam offers: format, resolution, and frame rate.
+///
+/// Query a device's modes with [`WebcamDevice::supported_modes`] and pass
+/// one to [`WebcamPlayerBuilder::mode`] to capture in it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::media::{list_webcams, WebcamMode, WebcamPlayer};
+///
+/// for camera in list_webcams() {
+///     for mode in camera.supported_modes()? {
+///         println!("{}: {mode}", camera.name);
+///     }
+/// }
+///
+/// // Raw YUYV instead of the default MJPEG
+/// let player = WebcamPlayer::builder()
+///     .mode(WebcamMode::new("yuyv422", 640, 480).fps(30.0))
+///     .build()?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebcamMode {
+    /// Pixel format or codec as FFmpeg names it (`mjpeg`, `yuyv422`, ...).
+    /// Empty when the backend doesn't tie modes to a format (AVFoundation),
+    /// leaving the choice to the camera.
+    pub format: String,
+
+    /// Whether `format` is a compressed codec such as MJPEG rather than raw
+    /// pixels.
+    pub compressed: bool,
+
+    /// Frame width in pixels.
+    pub width: u32,
+
+    /// Frame height in pixels.
+    pub height: u32,
+
+    /// Highest frame rate the mode supports, if the backend reports it
+    /// (V4L2 listings don't).
+    pub fps: Option<f64>,
+}
+
+impl WebcamMode {
+    /// A mode in `format` at `width × height`, at whatever frame rate the
+    /// camera picks.
+    #[must_use]
+    pub fn new(format: impl Into<String>, width: u32, height: u32) -> Self {
+        let format = format.into();
+        Self {
+            compressed: COMPRESSED_FORMATS.contains(&format.to_ascii_lowercase().as_str()),
+            format,
+            width,
+            height,
+            fps: None,
+        }
+    }
+
+    /// Sets the frame rate.
+    #[must_use]
+    pub const fn fps(mut self, fps: f64) -> Self {
+        self.fps = Some(fps);
+        self
+    }
+
+    /// Whether a camera offering `self` can capture `requested`: same
+    /// format and resolution, at no more than this mode's frame rate.
+    fn covers(&self, requested: &Self) -> bool {
+        let format_matches = requested.format.is_empty()
+            || self.format.is_empty()
+            || self.format.eq_ignore_ascii_case(&requested.format);
+        let fps_fits = match (self.fps, requested.fps) {
+            (Some(max), Some(wanted)) => wanted <= max + 0.5,
+            _ => true,
+        };
+        format_matches
+            && self.width == requested.width
+            && self.height == requested.height
+            && fps_fits
+    }
+}
+
+impl std::fmt::Display for WebcamMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.format.is_empty() {
+            write!(f, "{} ", self.format)?;
+        }
+        write!(f, "{}x{}", self.width, self.height)?;
+        if let Some(fps) = self.fps {
+            write!(f, " @ {fps} fps")?;
+        }
+        Ok(())
+    }
+}
+
+impl WebcamDevice {
+    /// Lists the capture modes this camera reports.
+    ///
+    /// Modes are queried through the `ffmpeg` command-line tool, the same
+    /// way [`list_webcams`] finds devices on macOS and Windows. On macOS the
+    /// camera may briefly switch on while it is probed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::WebcamError`] if `ffmpeg` can't be run or the
+    /// platform has no webcam support.
+    pub fn supported_modes(&self) -> Result<Vec<WebcamMode>> {
+        let (device_url, _) = build_device_url(&WebcamDeviceId::Path(self.id.clone()))?;
+        query_modes(&device_url)
+    }
+}
+
+/// Asks FFmpeg which modes the device at `device_url` supports.
+fn query_modes(device_url: &str) -> Result<Vec<WebcamMode>> {
+    #[cfg(target_os = "linux")]
+    let args = ["-f", "v4l2", "-list_formats", "all"];
+    #[cfg(target_os = "macos")]
+    // An impossible size makes AVFoundation print the modes it does support
+    let args = ["-f", "avfoundation", "-video_size", "1x1"];
+    #[cfg(target_os = "windows")]
+    let args = ["-list_options", "true", "-f", "dshow"];
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        return Err(DotmaxError::WebcamError {
+            device: device_url.to_string(),
+            message: "Webcam capture not supported on this platform".to_string(),
+        });
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    {
+        let mut command = std::process::Command::new("ffmpeg");
+        command
+            .arg("-hide_banner")
+            .args(args)
+            .args(["-i", device_url]);
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        // FFmpeg prints the listing to stderr and exits with an error, since
+        // no capture actually starts
+        let output = command.output().map_err(|e| DotmaxError::WebcamError {
+            device: device_url.to_string(),
+            message: format!("Failed to run ffmpeg to query capture modes: {e}"),
+        })?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        #[cfg(target_os = "linux")]
+        let modes = parse_v4l2_modes(&stderr);
+        #[cfg(target_os = "macos")]
+        let modes = parse_avfoundation_modes(&stderr);
+        #[cfg(target_os = "windows")]
+        let modes = parse_dshow_modes(&stderr);
+        Ok(modes)
+    }
+}
+
+/// Parses `ffmpeg -f v4l2 -list_formats all` output:
+///
+/// ```text
+/// [video4linux2,v4l2 @ 0x5581] Compressed:       mjpeg :          Motion-JPEG : 640x480 1280x720
+/// [video4linux2,v4l2 @ 0x5581] Raw       :     yuyv422 :           YUYV 4:2:2 : 640x480 320x240
+/// ```
+///
+/// Stepwise size ranges (`{32-4096, 4}x{...}`) can't be listed and are
+/// skipped.
+#[cfg(any(target_os = "linux", test))]
+fn parse_v4l2_modes(output: &str) -> Vec<WebcamMode> {
+    let mut modes = Vec::new();
+    for line in output.lines() {
+        let Some((_, listing)) = line.split_once("] ") else {
+            continue;
+        };
+        let Some((kind, listing)) = listing.split_once(':') else {
+            continue;
+        };
+        let compressed = match kind.trim() {
+            "Compressed" => true,
+            "Raw" => false,
+            _ => continue,
+        };
+        // The description can itself contain colons ("YUYV 4:2:2")
+        let fields: Vec<&str> = listing.split(" : ").map(str::trim).collect();
+        let [format, _description, sizes] = fields[..] else {
+            continue;
+        };
+        for size in sizes.split_whitespace() {
+            if let Some((width, height)) = parse_size(size) {
+                modes.push(WebcamMode {
+                    format: format.to_string(),
+                    compressed,
+                    width,
+                    height,
+                    fps: None,
+                });
+            }
+        }
+    }
+    modes
+}
+
+/// Parses the mode list AVFoundation prints when asked for an unsupported
+/// size:
+///
+/// ```text
+/// [avfoundation @ 0x7f8e] Supported modes:
+/// [avfoundation @ 0x7f8e]   1280x720@[1.000000 30.000000]fps
+/// ```
+#[cfg(any(target_os = "macos", test))]
+fn parse_avfoundation_modes(output: &str) -> Vec<WebcamMode> {
+    let mut modes: Vec<WebcamMode> = Vec::new();
+    for line in output.lines() {
+        let Some((_, listing)) = line.split_once("] ") else {
+            continue;
+        };
+        let Some((size, rates)) = listing.trim().split_once("@[") else {
+            continue;
+        };
+        let Some((width, height)) = parse_size(size) else {
+            continue;
+        };
+        let fps = rates
+            .trim_end_matches("]fps")
+            .split_whitespace()
+            .filter_map(|rate| rate.parse::<f64>().ok())
+            .reduce(f64::max);
+        let mode = WebcamMode {
+            format: String::new(),
+            compressed: false,
+            width,
+            height,
+            fps,
+        };
+        if !modes.contains(&mode) {
+            modes.push(mode);
+        }
+    }
+    modes
+}
+
+/// Parses `ffmpeg -list_options true -f dshow` output:
+///
+/// ```text
+/// [dshow @ 0000020f]   vcodec=mjpeg  min s=1280x720 fps=30 max s=1280x720 fps=30
+/// [dshow @ 0000020f]   pixel_format=yuyv422  min s=640x480 fps=5 max s=640x480 fps=30
+/// ```
+#[cfg(any(target_os = "windows", test))]
+fn parse_dshow_modes(output: &str) -> Vec<WebcamMode> {
+    let mut modes: Vec<WebcamMode> = Vec::new();
+    for line in output.lines() {
+        let Some((_, listing)) = line.split_once("] ") else {
+            continue;
+        };
+        let listing = listing.trim();
+        let (format, compressed) = if let Some(rest) = listing.strip_prefix("vcodec=") {
+            (rest, true)
+        } else if let Some(rest) = listing.strip_prefix("pixel_format=") {
+            (rest, false)
+        } else {
+            continue;
+        };
+        let format = format.split_whitespace().next().unwrap_or_default();
+        let Some((_, max)) = listing.split_once("max ") else {
+            continue;
+        };
+        let mut size = None;
+        let mut fps = None;
+        for field in max.split_whitespace() {
+            if let Some(value) = field.strip_prefix("s=") {
+                size = parse_size(value);
+            } else if let Some(value) = field.strip_prefix("fps=") {
+                fps = value.parse::<f64>().ok();
+            }
+        }
+        let Some((width, height)) = size else {
+            continue;
+        };
+        let mode = WebcamMode {
+            format: format.to_string(),
+            compressed,
+            width,
+            height,
+            fps,
+        };
+        if !modes.contains(&mode) {
+            modes.push(mode);
+        }
+    }
+    modes
+}
+
+/// Parses `"1280x720"`.
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows", test))]
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (width, height) = size.trim().split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
 // ============================================================================
 // SendableScaler (Thread Safety Wrapper)
 // ============================================================================
@@ -531,8 +874,29 @@ pub struct WebcamPlayer {
     /// Color mode.
     color_mode: ColorMode,
 
+    /// Despeckle neighbor threshold, or None to keep all dots.
+    despeckle: Option<u8>,
+
     /// Temporal coherence processor for reducing flicker.
     temporal_coherence: TemporalCoherence,
+
+    /// Threshold/brightness tracking for changing light, or None for the
+    /// fixed settings above.
+    auto_exposure: Option<AutoExposure>,
+
+    /// Motion tracker that crops to the subject, or None for the whole frame.
+    roi: Option<RoiTracker>,
+
+    /// Native-resolution RGB scaler and frame for ROI crops, created on first
+    /// use.
+    roi_scaler: Option<SendableScaler>,
+    roi_frame: VideoFrame,
+
+    /// Key color to make transparent, or None for an opaque picture.
+    chroma_key: Option<ChromaKey>,
+
+    /// Matte of the last rendered frame, when keying.
+    matte: Option<Matte>,
 }
 
 impl std::fmt::Debug for WebcamPlayer {
@@ -548,6 +912,10 @@ impl std::fmt::Debug for WebcamPlayer {
             .field("contrast", &self.contrast)
             .field("gamma", &self.gamma)
             .field("color_mode", &self.color_mode)
+            .field("despeckle", &self.despeckle)
+            .field("auto_exposure", &self.auto_exposure)
+            .field("roi", &self.roi)
+            .field("chroma_key", &self.chroma_key)
             .finish_non_exhaustive()
     }
 }
@@ -605,7 +973,7 @@ impl WebcamPlayer {
     /// ```
     pub fn from_device(device: impl Into<WebcamDeviceId>) -> Result<Self> {
         let device_id = device.into();
-        Self::open_device(device_id, None, None, None)
+        Self::open_device(device_id, None, None, None, None)
     }
 
     /// Returns a builder for configuring the webcam player.
@@ -634,6 +1002,7 @@ impl WebcamPlayer {
         device_id: WebcamDeviceId,
         requested_resolution: Option<(u32, u32)>,
         requested_fps: Option<u32>,
+        mode: Option<&WebcamMode>,
         render_settings: Option<RenderSettings>,
     ) -> Result<Self> {
         let device_str = device_id.to_string();
@@ -686,19 +1055,35 @@ impl WebcamPlayer {
         let target_fps = requested_fps.unwrap_or(30);
         options.set("framerate", &target_fps.to_string());
 
+        // An explicitly chosen mode's format, if it names one
+        let mode_format = mode.filter(|m| !m.format.is_empty());
+
         // Platform-specific options
         #[cfg(target_os = "linux")]
         {
-            options.set("input_format", "mjpeg"); // Prefer MJPEG for better performance
+            // Prefer MJPEG for better performance
+            options.set(
+                "input_format",
+                mode_format.map_or("mjpeg", |m| m.format.as_str()),
+            );
+        }
+
+        #[cfg(target_os = "macos")]
+        if let Some(mode) = mode_format {
+            options.set("pixel_format", &mode.format);
         }
 
         #[cfg(target_os = "windows")]
         {
-            // Force MJPEG codec - most webcams support 30fps with MJPEG at all resolutions
+            // Default to MJPEG - most webcams support 30fps with MJPEG at all resolutions
             // while raw YUV formats are often limited to lower fps due to USB bandwidth
             // IMPORTANT: vcodec must come BEFORE the -i input in ffmpeg, which means
             // we set it as an input option here
-            options.set("vcodec", "mjpeg");
+            match mode_format {
+                Some(mode) if !mode.compressed => options.set("pixel_format", &mode.format),
+                Some(mode) => options.set("vcodec", &mode.format),
+                None => options.set("vcodec", "mjpeg"),
+            }
             // Keep buffer small to avoid frame queueing
             options.set("rtbufsize", "10M");
             // Low latency settings
@@ -826,8 +1211,14 @@ impl WebcamPlayer {
             contrast: settings.contrast,
             gamma: settings.gamma,
             color_mode: settings.color_mode,
-            // Use webcam preset for temporal coherence (more aggressive smoothing for sensor noise)
-            temporal_coherence: TemporalCoherence::new(TemporalConfig::webcam()),
+            despeckle: settings.despeckle,
+            temporal_coherence: TemporalCoherence::new(settings.temporal),
+            auto_exposure: settings.auto_exposure.map(AutoExposure::new),
+            roi: settings.roi.map(RoiTracker::new),
+            roi_scaler: None,
+            roi_frame: VideoFrame::empty(),
+            chroma_key: settings.chroma_key,
+            matte: None,
         })
     }
 
@@ -899,6 +1290,53 @@ impl WebcamPlayer {
         self
     }
 
+    /// Sets the despeckle filter: dots with fewer than this many set
+    /// neighbors are removed. None disables it.
+    #[must_use]
+    pub const fn despeckle(mut self, min_neighbors: Option<u8>) -> Self {
+        self.despeckle = min_neighbors;
+        self
+    }
+
+    /// Enables automatic threshold/brightness tracking, or disables it with
+    /// None. While enabled, the tracked values take the place of the manual
+    /// threshold and brightness.
+    #[must_use]
+    pub fn auto_exposure(mut self, config: Option<AutoExposureConfig>) -> Self {
+        self.set_auto_exposure(config);
+        self
+    }
+
+    /// Enables region-of-interest tracking, which crops each frame to the
+    /// moving subject, or disables it with None.
+    #[must_use]
+    pub fn roi(mut self, config: Option<RoiConfig>) -> Self {
+        self.set_roi(config);
+        self
+    }
+
+    /// Keys out pixels near a color, making them transparent when the player
+    /// is layered over a background with
+    /// [`KeyedComposite`](super::KeyedComposite). None keeps the whole
+    /// picture.
+    #[must_use]
+    pub fn chroma_key(mut self, key: Option<ChromaKey>) -> Self {
+        self.set_chroma_key(key);
+        self
+    }
+
+    /// Applies a whole set of [`RenderOptions`], e.g. one shared with an
+    /// [`ImageRenderer`](crate::image::ImageRenderer).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`].
+    pub fn render_options(mut self, options: RenderOptions) -> Result<Self> {
+        self.set_render_options(options)?;
+        Ok(self)
+    }
+
     // ========== Getters ==========
 
     /// Returns the current dithering method.
@@ -937,6 +1375,31 @@ impl WebcamPlayer {
         self.color_mode
     }
 
+    /// Returns the current despeckle setting.
+    #[must_use]
+    pub const fn get_despeckle(&self) -> Option<u8> {
+        self.despeckle
+    }
+
+    /// Returns the auto-exposure tracker, whose current threshold and
+    /// brightness can be shown in a status line.
+    #[must_use]
+    pub const fn get_auto_exposure(&self) -> Option<&AutoExposure> {
+        self.auto_exposure.as_ref()
+    }
+
+    /// Returns the current crop, or None when ROI tracking is off.
+    #[must_use]
+    pub fn get_roi(&self) -> Option<RoiRect> {
+        self.roi.as_ref().map(RoiTracker::rect)
+    }
+
+    /// Returns the current chroma key.
+    #[must_use]
+    pub const fn get_chroma_key(&self) -> Option<ChromaKey> {
+        self.chroma_key
+    }
+
     // ========== Mutable setters ==========
 
     /// Updates the dithering method at runtime.
@@ -969,6 +1432,50 @@ impl WebcamPlayer {
         self.color_mode = mode;
     }
 
+    /// Updates the despeckle filter at runtime.
+    pub fn set_despeckle(&mut self, min_neighbors: Option<u8>) {
+        self.despeckle = min_neighbors;
+    }
+
+    /// Updates auto-exposure at runtime. Tracking restarts from the next
+    /// frame.
+    pub fn set_auto_exposure(&mut self, config: Option<AutoExposureConfig>) {
+        self.auto_exposure = config.map(AutoExposure::new);
+    }
+
+    /// Updates ROI tracking at runtime. Tracking starts over from the whole
+    /// frame.
+    pub fn set_roi(&mut self, config: Option<RoiConfig>) {
+        self.roi = config.map(RoiTracker::new);
+    }
+
+    /// Updates the chroma key at runtime.
+    pub fn set_chroma_key(&mut self, key: Option<ChromaKey>) {
+        self.chroma_key = key;
+        if key.is_none() {
+            self.matte = None;
+        }
+    }
+
+    /// Replaces the dithering, threshold, brightness, contrast, gamma, and
+    /// color mode at runtime. Read them back with
+    /// `RenderOptions::from(&player)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the options fail
+    /// [`RenderOptions::validate`]; the current settings are kept.
+    pub fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        options.validate()?;
+        self.dithering = options.dithering;
+        self.threshold = options.threshold;
+        self.brightness = options.brightness;
+        self.contrast = options.contrast;
+        self.gamma = options.gamma;
+        self.color_mode = options.color_mode;
+        Ok(())
+    }
+
     // ========== Temporal Coherence Settings ==========
 
     /// Returns a reference to the temporal coherence configuration.
@@ -979,6 +1486,10 @@ impl WebcamPlayer {
 
     /// Updates the temporal coherence configuration at runtime.
     ///
+    /// Frame blending smooths each captured frame before thresholding,
+    /// hysteresis applies when dithering is [`DitheringMethod::None`], and the
+    /// dot filter and persistence stages run on the rendered dots.
+    ///
     /// # Arguments
     ///
     /// * `config` - New temporal coherence settings
@@ -1100,13 +1611,32 @@ impl WebcamPlayer {
                 message: format!("Frame scaling error: {e}"),
             })?;
 
-        // Get RGB data directly from FFmpeg frame
-        let data = self.rgb_frame.data(0);
-        let stride = self.rgb_frame.stride(0);
         let target_width = (self.terminal_width * 2) as u32;
         let target_height = (self.terminal_height * 4) as u32;
         let pixel_count = (target_width * target_height) as usize;
 
+        // Get RGB data directly from FFmpeg frame, or from the ROI crop
+        let cropped = self.crop_to_roi(target_width, target_height)?;
+        let (data, stride) = match &cropped {
+            Some(crop) => (crop.as_raw().as_slice(), target_width as usize * 3),
+            None => (self.rgb_frame.data(0), self.rgb_frame.stride(0)),
+        };
+        self.matte = self
+            .chroma_key
+            .map(|key| key.matte_rgb24(data, stride, target_width, target_height));
+
+        // Auto-exposure replaces the manual threshold/brightness once it has
+        // measured a frame
+        let (threshold, brightness) =
+            self.auto_exposure
+                .as_ref()
+                .map_or((self.threshold, self.brightness), |auto| {
+                    (
+                        auto.threshold().or(self.threshold),
+                        auto.brightness().unwrap_or(self.brightness),
+                    )
+                });
+
         // For color modes, use the full pipeline
         if self.color_mode != crate::image::ColorMode::Monochrome {
             // Copy RGB data into buffer (needed for color rendering)
@@ -1132,15 +1662,23 @@ impl WebcamPlayer {
                     message: "Failed to create image from frame data".to_string(),
                 })?;
 
+            self.temporal_coherence.detect_scene_cut_rgb(&img);
+            let img = self.temporal_coherence.blend_rgb(img);
             let dynamic_img = image::DynamicImage::ImageRgb8(img);
-            let grid = render_image_with_color(
+            if let Some(auto) = &mut self.auto_exposure {
+                if auto.tick() {
+                    // Measured before adjustments, so no gain applied yet
+                    auto.measure(&crate::analysis::luma_histogram(&dynamic_img), 1.0);
+                }
+            }
+            let mut grid = render_image_with_color(
                 &dynamic_img,
                 self.color_mode,
                 self.terminal_width,
                 self.terminal_height,
                 self.dithering,
-                self.threshold,
-                self.brightness,
+                threshold,
+                brightness,
                 self.contrast,
                 self.gamma,
             )?;
@@ -1150,7 +1688,8 @@ impl WebcamPlayer {
                 self.rgb_buffer = rgb.into_raw();
             }
 
-            return Ok(grid);
+            self.temporal_coherence.process_grid(&mut grid);
+            return Ok(self.apply_despeckle(grid));
         }
 
         // FAST PATH: Monochrome mode - direct grayscale conversion
@@ -1163,7 +1702,7 @@ impl WebcamPlayer {
         }
 
         // Pre-compute adjustment flags outside the loop
-        let apply_brightness = (self.brightness - 1.0).abs() > f32::EPSILON;
+        let apply_brightness = (brightness - 1.0).abs() > f32::EPSILON;
         let apply_contrast = (self.contrast - 1.0).abs() > f32::EPSILON;
         let apply_gamma = (self.gamma - 1.0).abs() > f32::EPSILON;
         let inv_gamma = if apply_gamma { 1.0 / self.gamma } else { 1.0 };
@@ -1184,7 +1723,7 @@ impl WebcamPlayer {
 
                 // Brightness (multiply)
                 if apply_brightness {
-                    luma *= self.brightness;
+                    luma *= brightness;
                 }
 
                 // Contrast (scale from mid-point) using mul_add
@@ -1208,16 +1747,29 @@ impl WebcamPlayer {
                 device: self.device_id.clone(),
                 message: "Failed to create grayscale image".to_string(),
             })?;
+        self.temporal_coherence.detect_scene_cut(&gray);
+        if let Some(auto) = &mut self.auto_exposure {
+            if auto.tick() {
+                auto.measure(&crate::analysis::gray_histogram(&gray), brightness);
+            }
+        }
+        let gray = self.temporal_coherence.blend_grayscale(gray);
 
         // Apply dithering or thresholding
         // Note: For auto_threshold without dithering, we compute Otsu and apply manually
         // to avoid cloning the grayscale image
         let binary = if self.dithering == DitheringMethod::None {
-            let threshold_val = self.threshold.unwrap_or_else(|| {
+            let threshold_val = threshold.unwrap_or_else(|| {
                 crate::image::otsu_threshold(&gray)
             });
-            apply_threshold(&gray, threshold_val)
-        } else if let Some(t) = self.threshold {
+            if self.temporal_coherence.config().hysteresis_enabled {
+                // Hysteresis output is already binary (0 or 255)
+                let stable = self.temporal_coherence.threshold_grayscale(&gray, threshold_val);
+                apply_threshold(&stable, 128)
+            } else {
+                apply_threshold(&gray, threshold_val)
+            }
+        } else if let Some(t) = threshold {
             apply_dithering_with_custom_threshold(&gray, self.dithering, Some(t))?
         } else {
             apply_dithering(&gray, self.dithering)?
@@ -1227,9 +1779,88 @@ impl WebcamPlayer {
         self.rgb_buffer = gray.into_raw();
 
         // Map to braille grid
-        let grid = pixels_to_braille(&binary, self.terminal_width, self.terminal_height)?;
+        let mut grid = pixels_to_braille(&binary, self.terminal_width, self.terminal_height)?;
+        self.temporal_coherence.process_grid(&mut grid);
 
-        Ok(grid)
+        Ok(self.apply_despeckle(grid))
+    }
+
+    /// Feeds the scaled frame to the ROI tracker and, when it has zoomed in,
+    /// cuts its crop from the native-resolution frame and scales that to
+    /// `width × height`. Returns None when there is nothing to crop.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn crop_to_roi(&mut self, width: u32, height: u32) -> Result<Option<image::RgbImage>> {
+        let Some(tracker) = &mut self.roi else {
+            return Ok(None);
+        };
+
+        // Track on the terminal-sized frame the scaler already produced
+        let data = self.rgb_frame.data(0);
+        let stride = self.rgb_frame.stride(0);
+        let thumbnail = image::GrayImage::from_fn(width, height, |x, y| {
+            let offset = y as usize * stride + x as usize * 3;
+            let (r, g, b) = (
+                f32::from(data[offset]),
+                f32::from(data[offset + 1]),
+                f32::from(data[offset + 2]),
+            );
+            image::Luma([0.114f32.mul_add(b, 0.299f32.mul_add(r, 0.587 * g)) as u8])
+        });
+        let rect = tracker.update(&thumbnail);
+        if rect.is_full() {
+            return Ok(None);
+        }
+
+        if self.roi_scaler.is_none() {
+            let scaler = ScalingContext::get(
+                self.decoder.format(),
+                self.width,
+                self.height,
+                Pixel::RGB24,
+                self.width,
+                self.height,
+                Flags::BILINEAR,
+            )
+            .map_err(|e| DotmaxError::WebcamError {
+                device: self.device_id.clone(),
+                message: format!("Failed to create ROI scaler: {e}"),
+            })?;
+            self.roi_scaler = Some(SendableScaler(scaler));
+        }
+        if let Some(scaler) = &mut self.roi_scaler {
+            scaler
+                .0
+                .run(&self.decoded_frame, &mut self.roi_frame)
+                .map_err(|e| DotmaxError::WebcamError {
+                    device: self.device_id.clone(),
+                    message: format!("Frame scaling error: {e}"),
+                })?;
+        }
+
+        let (x, y, crop_width, crop_height) = rect.to_pixels(self.width, self.height);
+        let native = self.roi_frame.data(0);
+        let native_stride = self.roi_frame.stride(0);
+        let mut crop = image::RgbImage::new(crop_width, crop_height);
+        let row_len = crop_width as usize * 3;
+        for (row, out) in crop.chunks_exact_mut(row_len).enumerate() {
+            let start = (y as usize + row) * native_stride + x as usize * 3;
+            out.copy_from_slice(&native[start..start + row_len]);
+        }
+
+        Ok(Some(image::imageops::resize(
+            &crop,
+            width,
+            height,
+            image::imageops::FilterType::Triangle,
+        )))
+    }
+
+    /// Runs the despeckle filter if one is configured.
+    fn apply_despeckle(&self, grid: BrailleGrid) -> BrailleGrid {
+        match self.despeckle {
+            Some(min_neighbors) => crate::analysis::despeckle(&grid, min_neighbors),
+            None => grid,
+        }
     }
 
     /// Calculates the delay for frame timing.
@@ -1242,6 +1873,19 @@ impl WebcamPlayer {
     }
 }
 
+impl From<&WebcamPlayer> for RenderOptions {
+    fn from(player: &WebcamPlayer) -> Self {
+        Self {
+            dithering: player.dithering,
+            threshold: player.threshold,
+            brightness: player.brightness,
+            contrast: player.contrast,
+            gamma: player.gamma,
+            color_mode: player.color_mode,
+        }
+    }
+}
+
 // ============================================================================
 // MediaPlayer Implementation (AC: #1)
 // ============================================================================
@@ -1286,6 +1930,11 @@ impl MediaPlayer for WebcamPlayer {
         Some(0) // Infinite
     }
 
+    /// Returns the keyed-out dots of the last frame, when a chroma key is set.
+    fn matte(&self) -> Option<&Matte> {
+        self.matte.as_ref()
+    }
+
     /// Updates terminal dimensions for rendering.
     fn handle_resize(&mut self, width: usize, height: usize) {
         if self.terminal_width == width && self.terminal_height == height {
@@ -1320,6 +1969,22 @@ impl MediaPlayer for WebcamPlayer {
             }
         }
     }
+
+    /// Always fails: a live camera has no past or future to move to.
+    fn seek(&mut self, _position: Duration) -> Result<Duration> {
+        Err(DotmaxError::WebcamError {
+            device: self.device_id.clone(),
+            message: "Cannot seek a live camera".to_string(),
+        })
+    }
+
+    fn set_render_options(&mut self, options: RenderOptions) -> Result<()> {
+        WebcamPlayer::set_render_options(self, options)
+    }
+
+    fn current_render_options(&self) -> Option<RenderOptions> {
+        Some(RenderOptions::from(self))
+    }
 }
 
 // ============================================================================
@@ -1335,6 +2000,11 @@ struct RenderSettings {
     contrast: f32,
     gamma: f32,
     color_mode: ColorMode,
+    despeckle: Option<u8>,
+    temporal: TemporalConfig,
+    auto_exposure: Option<AutoExposureConfig>,
+    roi: Option<RoiConfig>,
+    chroma_key: Option<ChromaKey>,
 }
 
 impl Default for RenderSettings {
@@ -1348,10 +2018,36 @@ impl Default for RenderSettings {
             contrast: 1.0,
             gamma: 1.0,
             color_mode: ColorMode::Monochrome,
+            despeckle: None,
+            // More aggressive smoothing than video to handle sensor noise
+            temporal: TemporalConfig::webcam(),
+            auto_exposure: None,
+            roi: None,
+            chroma_key: None,
         }
     }
 }
 
+impl RenderSettings {
+    /// The settings shared with other pipelines.
+    const fn options(&self) -> RenderOptions {
+        RenderOptions {
+            dithering: self.dithering,
+            threshold: self.threshold,
+            brightness: self.brightness,
+            contrast: self.contrast,
+            gamma: self.gamma,
+            color_mode: self.color_mode,
+        }
+    }
+
+    /// Checks the image adjustments against the ranges
+    /// [`ImageRenderer`](crate::image::ImageRenderer) accepts.
+    fn validate(&self) -> Result<()> {
+        self.options().validate()
+    }
+}
+
 /// Builder for configuring [`WebcamPlayer`].
 ///
 /// Use this builder to customize webcam capture settings before opening
@@ -1377,6 +2073,7 @@ pub struct WebcamPlayerBuilder {
     device: WebcamDeviceId,
     resolution: Option<(u32, u32)>,
     fps: Option<u32>,
+    mode: Option<WebcamMode>,
     render_settings: RenderSettings,
 }
 
@@ -1416,6 +2113,20 @@ impl WebcamPlayerBuilder {
         self
     }
 
+    /// Captures in a specific mode, overriding [`resolution`](Self::resolution)
+    /// and [`fps`](Self::fps) and the default of MJPEG.
+    ///
+    /// [`build`](Self::build) checks the mode against
+    /// [`WebcamDevice::supported_modes`] and fails with
+    /// [`DotmaxError::UnsupportedCameraMode`] if the camera doesn't offer it.
+    /// If the camera's modes can't be queried, the mode is passed to the
+    /// backend as-is.
+    #[must_use]
+    pub fn mode(mut self, mode: WebcamMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
     /// Sets the dithering algorithm.
     #[must_use]
     pub const fn dithering(mut self, method: DitheringMethod) -> Self {
@@ -1430,21 +2141,72 @@ impl WebcamPlayerBuilder {
         self
     }
 
-    /// Sets the brightness adjustment.
+    /// Sets the despeckle neighbor threshold, or None to disable it.
+    #[must_use]
+    pub const fn despeckle(mut self, min_neighbors: Option<u8>) -> Self {
+        self.render_settings.despeckle = min_neighbors;
+        self
+    }
+
+    /// Sets the temporal smoothing configuration (default:
+    /// [`TemporalConfig::webcam`]).
+    ///
+    /// ```no_run
+    /// use dotmax::image::temporal::TemporalConfig;
+    /// use dotmax::media::WebcamPlayer;
+    ///
+    /// // Dots must hold a new state for three frames before they change
+    /// let player = WebcamPlayer::builder()
+    ///     .temporal(TemporalConfig {
+    ///         persistence_frames: 3,
+    ///         ..TemporalConfig::webcam()
+    ///     })
+    ///     .build()?;
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[must_use]
+    pub const fn temporal(mut self, config: TemporalConfig) -> Self {
+        self.render_settings.temporal = config;
+        self
+    }
+
+    /// Enables automatic threshold/brightness tracking as lighting changes
+    /// (default: off).
+    #[must_use]
+    pub const fn auto_exposure(mut self, config: Option<AutoExposureConfig>) -> Self {
+        self.render_settings.auto_exposure = config;
+        self
+    }
+
+    /// Enables cropping to the moving subject (default: off).
+    #[must_use]
+    pub const fn roi(mut self, config: Option<RoiConfig>) -> Self {
+        self.render_settings.roi = config;
+        self
+    }
+
+    /// Sets a chroma key for green-screen compositing (default: off).
+    #[must_use]
+    pub const fn chroma_key(mut self, key: Option<ChromaKey>) -> Self {
+        self.render_settings.chroma_key = key;
+        self
+    }
+
+    /// Sets the brightness adjustment (0.0-2.0, default 1.0).
     #[must_use]
     pub const fn brightness(mut self, brightness: f32) -> Self {
         self.render_settings.brightness = brightness;
         self
     }
 
-    /// Sets the contrast adjustment.
+    /// Sets the contrast adjustment (0.0-2.0, default 1.0).
     #[must_use]
     pub const fn contrast(mut self, contrast: f32) -> Self {
         self.render_settings.contrast = contrast;
         self
     }
 
-    /// Sets the gamma correction.
+    /// Sets the gamma correction (0.1-3.0, default 1.0).
     #[must_use]
     pub const fn gamma(mut self, gamma: f32) -> Self {
         self.render_settings.gamma = gamma;
@@ -1458,21 +2220,76 @@ impl WebcamPlayerBuilder {
         self
     }
 
+    /// Sets dithering, threshold, brightness, contrast, gamma, and color
+    /// mode from a shared [`RenderOptions`], replacing the webcam defaults
+    /// for all six. Checked by [`build`](Self::build).
+    #[must_use]
+    pub const fn render_options(mut self, options: RenderOptions) -> Self {
+        self.render_settings.dithering = options.dithering;
+        self.render_settings.threshold = options.threshold;
+        self.render_settings.brightness = options.brightness;
+        self.render_settings.contrast = options.contrast;
+        self.render_settings.gamma = options.gamma;
+        self.render_settings.color_mode = options.color_mode;
+        self
+    }
+
     /// Builds the `WebcamPlayer` with the configured settings.
     ///
     /// # Errors
     ///
-    /// Returns camera-specific errors if the device cannot be opened.
+    /// Returns [`DotmaxError::InvalidParameter`] if brightness, contrast, or
+    /// gamma is out of range, checked before the camera is touched.
+    /// Otherwise returns camera-specific errors if the device cannot be
+    /// opened, or [`DotmaxError::UnsupportedCameraMode`] if a
+    /// [`mode`](Self::mode) was requested that the camera doesn't support.
     pub fn build(self) -> Result<WebcamPlayer> {
+        self.render_settings.validate()?;
+        let (resolution, fps) = match &self.mode {
+            Some(mode) => {
+                check_mode(&self.device, mode)?;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let fps = mode.fps.map(|fps| fps.round() as u32).or(self.fps);
+                (Some((mode.width, mode.height)), fps)
+            }
+            None => (self.resolution, self.fps),
+        };
         WebcamPlayer::open_device(
             self.device,
-            self.resolution,
-            self.fps,
+            resolution,
+            fps,
+            self.mode.as_ref(),
             Some(self.render_settings),
         )
     }
 }
 
+/// Fails with [`DotmaxError::UnsupportedCameraMode`] if `device` reports its
+/// modes and none of them covers `mode`.
+fn check_mode(device: &WebcamDeviceId, mode: &WebcamMode) -> Result<()> {
+    let (device_url, _) = build_device_url(device)?;
+    let modes = match query_modes(&device_url) {
+        Ok(modes) if !modes.is_empty() => modes,
+        Ok(_) => {
+            tracing::debug!("{device_url} reported no capture modes, trying {mode} unchecked");
+            return Ok(());
+        }
+        Err(e) => {
+            tracing::debug!("Could not query modes of {device_url}, trying {mode} unchecked: {e}");
+            return Ok(());
+        }
+    };
+    if modes.iter().any(|supported| supported.covers(mode)) {
+        Ok(())
+    } else {
+        Err(DotmaxError::UnsupportedCameraMode {
+            device: device.to_string(),
+            requested: mode.to_string(),
+            available: modes.iter().map(ToString::to_string).collect(),
+        })
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -1650,6 +2467,106 @@ mod tests {
         assert_eq!(WebcamDeviceId::Path("/dev/video0".into()).to_string(), "/dev/video0");
     }
 
+    #[test]
+    fn test_parse_v4l2_modes() {
+        let output = "\
+[video4linux2,v4l2 @ 0x5581] Compressed:       mjpeg :          Motion-JPEG : 640x480 1280x720
+[video4linux2,v4l2 @ 0x5581] Raw       :     yuyv422 :           YUYV 4:2:2 : 640x480
+[video4linux2,v4l2 @ 0x5581] Raw       :        nv12 :       Y/CbCr 4:2:0 : {32-4096, 4}x{32-2304, 2}
+/dev/video0: Immediate exit requested";
+        let modes = parse_v4l2_modes(output);
+        assert_eq!(
+            modes,
+            vec![
+                WebcamMode::new("mjpeg", 640, 480),
+                WebcamMode::new("mjpeg", 1280, 720),
+                WebcamMode::new("yuyv422", 640, 480),
+            ]
+        );
+        assert!(modes[0].compressed && !modes[2].compressed);
+    }
+
+    #[test]
+    fn test_parse_avfoundation_modes() {
+        let output = "\
+[avfoundation @ 0x7f8e] Selected video size (1x1) is not supported by the device.
+[avfoundation @ 0x7f8e] Supported modes:
+[avfoundation @ 0x7f8e]   640x480@[1.000000 30.000000]fps
+[avfoundation @ 0x7f8e]   1280x720@[1.000000 30.000000]fps
+[avfoundation @ 0x7f8e]   1280x720@[1.000000 30.000000]fps";
+        let modes = parse_avfoundation_modes(output);
+        assert_eq!(
+            modes,
+            vec![
+                WebcamMode::new("", 640, 480).fps(30.0),
+                WebcamMode::new("", 1280, 720).fps(30.0),
+            ]
+        );
+        assert_eq!(modes[1].to_string(), "1280x720 @ 30 fps");
+    }
+
+    #[test]
+    fn test_parse_dshow_modes() {
+        let output = "\
+[dshow @ 0000020f] DirectShow video device options (from video devices)
+[dshow @ 0000020f]  Pin \"Capture\" (alternative pin name \"0\")
+[dshow @ 0000020f]   vcodec=mjpeg  min s=1280x720 fps=30 max s=1280x720 fps=30
+[dshow @ 0000020f]   vcodec=mjpeg  min s=1280x720 fps=30 max s=1280x720 fps=30
+[dshow @ 0000020f]   pixel_format=yuyv422  min s=640x480 fps=5 max s=640x480 fps=30";
+        let modes = parse_dshow_modes(output);
+        assert_eq!(
+            modes,
+            vec![
+                WebcamMode::new("mjpeg", 1280, 720).fps(30.0),
+                WebcamMode::new("yuyv422", 640, 480).fps(30.0),
+            ]
+        );
+        assert_eq!(modes[0].to_string(), "mjpeg 1280x720 @ 30 fps");
+    }
+
+    #[test]
+    fn test_webcam_mode_covers() {
+        let supported = WebcamMode::new("MJPEG", 1280, 720).fps(30.0);
+        assert!(supported.covers(&WebcamMode::new("mjpeg", 1280, 720)));
+        assert!(supported.covers(&WebcamMode::new("mjpeg", 1280, 720).fps(15.0)));
+        assert!(!supported.covers(&WebcamMode::new("mjpeg", 1280, 720).fps(60.0)));
+        assert!(!supported.covers(&WebcamMode::new("mjpeg", 640, 480)));
+        assert!(!supported.covers(&WebcamMode::new("yuyv422", 1280, 720)));
+    }
+
+    #[test]
+    fn test_builder_rejects_out_of_range_adjustments() {
+        assert!(RenderSettings::default().validate().is_ok());
+
+        // Fails before any device is opened
+        let err = WebcamPlayerBuilder::new()
+            .contrast(1.5)
+            .gamma(5.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DotmaxError::InvalidParameter { ref parameter_name, ref max, .. }
+                if parameter_name == "gamma" && max == "3.0"
+        ));
+        assert!(WebcamPlayerBuilder::new()
+            .brightness(f32::NAN)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_render_options() {
+        let options = RenderOptions::new().threshold(Some(70)).gamma(1.4);
+        let builder = WebcamPlayerBuilder::new().render_options(options);
+        assert_eq!(builder.render_settings.options(), options);
+        // Replaces the webcam's Bayer default too
+        assert_eq!(
+            builder.render_settings.dithering,
+            DitheringMethod::FloydSteinberg
+        );
+    }
+
     #[test]
     fn test_list_webcams_returns_vec() {
         // This will return actual devices or empty vec in CI
@@ -1691,6 +2608,11 @@ mod tests {
         assert!((settings.contrast - 1.0).abs() < f32::EPSILON);
         assert!((settings.gamma - 1.0).abs() < f32::EPSILON);
         assert_eq!(settings.color_mode, ColorMode::Monochrome);
+        assert_eq!(settings.despeckle, None);
+        assert_eq!(settings.temporal, TemporalConfig::webcam());
+        assert_eq!(settings.auto_exposure, None);
+        assert_eq!(settings.roi, None);
+        assert_eq!(settings.chroma_key, None);
     }
 
     // Note: Tests requiring actual webcam hardware are marked #[ignore]
@@ -0,0 +1,455 @@
+//! Animated WebP playback support.
+//!
+//! This module provides [`AnimatedWebpPlayer`] for animated WebP playback,
+//! implementing the [`MediaPlayer`] trait for integration with the universal
+//! media system.
+//!
+//! # Features
+//!
+//! - Frame-by-frame decoding with per-frame durations
+//! - Loop count handling (finite and infinite loops)
+//! - Blending and disposal handled by the decoder, so every frame is a full
+//!   canvas
+//! - Streaming decode: only the current canvas is held in memory
+//!
+//! Still WebP files play as a single frame; [`detect_format`] only routes
+//! animated ones here.
+//!
+//! [`detect_format`]: super::detect_format
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::media::{AnimatedWebpPlayer, MediaPlayer};
+//!
+//! let mut player = AnimatedWebpPlayer::new("animation.webp")?;
+//! println!("{:?} frames, loop count {:?}", player.frame_count(), player.loop_count());
+//! while let Some(result) = player.next_frame() {
+//!     let (grid, delay) = result?;
+//!     // Render grid and wait for delay
+//! }
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use image_webp::{LoopCount, WebPDecoder};
+
+use crate::image::ImageRenderer;
+use crate::{BrailleGrid, DotmaxError, Result};
+
+use super::MediaPlayer;
+
+/// How long a frame with no duration stays up, as in browsers.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+/// Animated WebP player implementing the [`MediaPlayer`] trait.
+///
+/// # Loop Handling
+///
+/// The loop count from the file's `ANIM` chunk is respected:
+/// - `loop_count() == Some(0)` → infinite loop
+/// - `loop_count() == Some(n)` → play n times
+/// - After all loops complete, `next_frame()` returns `None`
+///
+/// Use [`reset()`](MediaPlayer::reset) to restart from frame 0.
+///
+/// # Thread Safety
+///
+/// `AnimatedWebpPlayer` implements `Send` and can be moved across threads.
+pub struct AnimatedWebpPlayer {
+    /// Path to the WebP file (for error messages).
+    path: PathBuf,
+
+    /// WebP decoder (streaming).
+    decoder: WebPDecoder<BufReader<File>>,
+
+    /// Canvas dimensions in pixels.
+    canvas_width: u32,
+    canvas_height: u32,
+
+    /// The composited canvas, RGBA if the file has alpha and RGB otherwise.
+    canvas: Vec<u8>,
+
+    /// Frames in one pass (1 for a still image).
+    frame_count: usize,
+
+    /// Loop count from the `ANIM` chunk.
+    /// - `Some(0)` → infinite
+    /// - `Some(n)` → play n times
+    webp_loop_count: Option<u16>,
+
+    /// Index of the next frame to decode (0-based).
+    current_frame: usize,
+
+    /// Current loop iteration (1-based).
+    current_loop: u16,
+
+    /// Whether we've completed all loops.
+    loops_completed: bool,
+
+    /// Terminal dimensions for rendering.
+    terminal_width: usize,
+    terminal_height: usize,
+}
+
+impl std::fmt::Debug for AnimatedWebpPlayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnimatedWebpPlayer")
+            .field("path", &self.path)
+            .field("canvas_width", &self.canvas_width)
+            .field("canvas_height", &self.canvas_height)
+            .field("frame_count", &self.frame_count)
+            .field("loop_count", &self.webp_loop_count)
+            .field("current_frame", &self.current_frame)
+            .field("current_loop", &self.current_loop)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AnimatedWebpPlayer {
+    /// Creates a new `AnimatedWebpPlayer` from a WebP file path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file cannot be opened, or
+    /// [`DotmaxError::WebpError`] if it is not a valid WebP.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::media::AnimatedWebpPlayer;
+    ///
+    /// let player = AnimatedWebpPlayer::new("animation.webp")?;
+    /// println!("Canvas: {}x{}", player.canvas_width(), player.canvas_height());
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+
+        let decoder =
+            WebPDecoder::new(BufReader::new(file)).map_err(|e| DotmaxError::WebpError {
+                path: path.clone(),
+                message: format!("Failed to decode WebP: {e}"),
+            })?;
+
+        let (canvas_width, canvas_height) = decoder.dimensions();
+        let canvas_size = decoder
+            .output_buffer_size()
+            .ok_or_else(|| DotmaxError::WebpError {
+                path: path.clone(),
+                message: format!("Canvas too large: {canvas_width}x{canvas_height}"),
+            })?;
+
+        let (frame_count, webp_loop_count) = if decoder.is_animated() {
+            let loops = match decoder.loop_count() {
+                LoopCount::Forever => 0,
+                LoopCount::Times(n) => n.get(),
+            };
+            (decoder.num_frames() as usize, Some(loops))
+        } else {
+            (1, None)
+        };
+
+        // Get terminal size for rendering
+        let (terminal_width, terminal_height) =
+            crossterm::terminal::size().map_or((80, 24), |(w, h)| (w as usize, h as usize));
+
+        Ok(Self {
+            path,
+            decoder,
+            canvas_width,
+            canvas_height,
+            canvas: vec![0u8; canvas_size],
+            frame_count,
+            webp_loop_count,
+            current_frame: 0,
+            current_loop: 1,
+            loops_completed: false,
+            terminal_width,
+            terminal_height,
+        })
+    }
+
+    /// Returns the canvas width in pixels.
+    #[must_use]
+    pub const fn canvas_width(&self) -> u32 {
+        self.canvas_width
+    }
+
+    /// Returns the canvas height in pixels.
+    #[must_use]
+    pub const fn canvas_height(&self) -> u32 {
+        self.canvas_height
+    }
+
+    /// Total display time of one pass.
+    #[must_use]
+    pub fn pass_duration(&self) -> Duration {
+        if self.decoder.is_animated() {
+            Duration::from_millis(self.decoder.loop_duration())
+        } else {
+            DEFAULT_DELAY
+        }
+    }
+
+    /// Decodes the next frame onto the canvas and returns its duration.
+    ///
+    /// Returns `None` at the end of the pass.
+    fn decode_next_frame(&mut self) -> Option<Result<Duration>> {
+        if self.current_frame >= self.frame_count {
+            return None;
+        }
+
+        let decoded = if self.decoder.is_animated() {
+            self.decoder
+                .read_frame(&mut self.canvas)
+                .map(|ms| Duration::from_millis(u64::from(ms)))
+        } else {
+            self.decoder
+                .read_image(&mut self.canvas)
+                .map(|()| Duration::ZERO)
+        };
+        let index = self.current_frame;
+        self.current_frame += 1;
+
+        Some(
+            decoded
+                .map(|delay| {
+                    if delay.is_zero() {
+                        DEFAULT_DELAY
+                    } else {
+                        delay
+                    }
+                })
+                .map_err(|e| DotmaxError::WebpError {
+                    path: self.path.clone(),
+                    message: format!("Failed to decode frame {index}: {e}"),
+                }),
+        )
+    }
+
+    /// Rewinds the decoder to the first frame.
+    fn rewind(&mut self) {
+        if self.decoder.is_animated() {
+            self.decoder.reset_animation();
+        }
+        self.current_frame = 0;
+    }
+
+    /// Composites frames from the start up to the one on screen at `at`,
+    /// without rendering any of them, and returns the canvas.
+    ///
+    /// Times past the end of the first pass give the last frame. Playback
+    /// restarts from the beginning afterwards.
+    pub(crate) fn canvas_at(&mut self, at: Duration) -> Result<image::RgbaImage> {
+        Ok(self.canvases_at(&[at])?.remove(0))
+    }
+
+    /// [`canvas_at`](Self::canvas_at) for several ascending times, decoding
+    /// the file once.
+    pub(crate) fn canvases_at(&mut self, times: &[Duration]) -> Result<Vec<image::RgbaImage>> {
+        self.reset();
+        let mut images = Vec::with_capacity(times.len());
+        let mut elapsed = Duration::ZERO;
+        let mut drawn = false;
+
+        for &at in times {
+            while elapsed <= at {
+                match self.decode_next_frame() {
+                    Some(Ok(delay)) => {
+                        drawn = true;
+                        elapsed += delay;
+                    }
+                    Some(Err(e)) if !drawn => return Err(e),
+                    Some(Err(_)) | None => break,
+                }
+            }
+            if !drawn {
+                return Err(DotmaxError::WebpError {
+                    path: self.path.clone(),
+                    message: "No decodable frames".to_string(),
+                });
+            }
+            images.push(self.canvas_image()?);
+        }
+
+        self.reset();
+        Ok(images)
+    }
+
+    /// Copies the current canvas into an RGBA image.
+    fn canvas_image(&self) -> Result<image::RgbaImage> {
+        let image = if self.decoder.has_alpha() {
+            image::RgbaImage::from_raw(self.canvas_width, self.canvas_height, self.canvas.clone())
+        } else {
+            image::RgbImage::from_raw(self.canvas_width, self.canvas_height, self.canvas.clone())
+                .map(|rgb| image::DynamicImage::ImageRgb8(rgb).into_rgba8())
+        };
+        image.ok_or_else(|| DotmaxError::WebpError {
+            path: self.path.clone(),
+            message: "Failed to create image from canvas".to_string(),
+        })
+    }
+
+    /// Converts the current canvas to a BrailleGrid.
+    fn canvas_to_grid(&self) -> Result<BrailleGrid> {
+        ImageRenderer::new()
+            .load_from_rgba(self.canvas_image()?)
+            .resize(self.terminal_width, self.terminal_height, true)?
+            .render()
+    }
+}
+
+impl MediaPlayer for AnimatedWebpPlayer {
+    /// Returns the next frame and its display duration.
+    ///
+    /// Handles loop iteration automatically. Returns `None` when all loops
+    /// are complete. A frame that fails to decode ends playback after its
+    /// error is returned.
+    fn next_frame(&mut self) -> Option<Result<(BrailleGrid, Duration)>> {
+        if self.loops_completed {
+            return None;
+        }
+
+        let delay = match self.decode_next_frame() {
+            Some(Ok(delay)) => delay,
+            Some(Err(e)) => {
+                self.loops_completed = true;
+                return Some(Err(e));
+            }
+            None => {
+                let should_loop = match self.webp_loop_count {
+                    Some(0) => true, // Infinite loop
+                    Some(n) => self.current_loop < n,
+                    None => false,
+                };
+                if !should_loop {
+                    self.loops_completed = true;
+                    return None;
+                }
+
+                tracing::debug!(
+                    "Loop {} complete ({} frames)",
+                    self.current_loop,
+                    self.frame_count
+                );
+                self.current_loop += 1;
+                self.rewind();
+                return self.next_frame();
+            }
+        };
+
+        Some(self.canvas_to_grid().map(|grid| (grid, delay)))
+    }
+
+    /// Resets playback to the first frame.
+    fn reset(&mut self) {
+        self.rewind();
+        self.current_loop = 1;
+        self.loops_completed = false;
+    }
+
+    /// Returns the number of frames in one pass, read from the file header.
+    fn frame_count(&self) -> Option<usize> {
+        Some(self.frame_count)
+    }
+
+    /// Returns the WebP's loop count.
+    ///
+    /// - `Some(0)` → infinite looping
+    /// - `Some(n)` → loop n times
+    /// - `None` → still image, played once
+    fn loop_count(&self) -> Option<u16> {
+        self.webp_loop_count
+    }
+
+    /// Updates terminal dimensions for subsequent frame rendering.
+    fn handle_resize(&mut self, width: usize, height: usize) {
+        self.terminal_width = width;
+        self.terminal_height = height;
+        tracing::debug!("AnimatedWebpPlayer resized to {}x{}", width, height);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ensure AnimatedWebpPlayer is Send (required by MediaPlayer trait)
+    fn _assert_webp_player_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<AnimatedWebpPlayer>();
+    }
+
+    #[test]
+    fn test_webp_player_new_nonexistent() {
+        assert!(AnimatedWebpPlayer::new("nonexistent.webp").is_err());
+    }
+
+    #[test]
+    fn test_webp_player_new_animated() {
+        let player = AnimatedWebpPlayer::new("tests/fixtures/media/animated.webp").unwrap();
+        assert_eq!((player.canvas_width(), player.canvas_height()), (10, 10));
+        assert_eq!(player.frame_count(), Some(3));
+        assert_eq!(player.loop_count(), Some(0));
+        assert_eq!(player.pass_duration(), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_webp_player_frames_and_delays() {
+        let mut player = AnimatedWebpPlayer::new("tests/fixtures/media/animated.webp").unwrap();
+        player.handle_resize(10, 5);
+
+        for _ in 0..3 {
+            let (grid, delay) = player.next_frame().unwrap().unwrap();
+            assert!(grid.width() > 0 && grid.height() > 0);
+            assert_eq!(delay, Duration::from_millis(100));
+        }
+        // Infinite loop: the fourth frame is the first again
+        assert!(player.next_frame().is_some());
+        assert_eq!(player.current_loop, 2);
+    }
+
+    #[test]
+    fn test_webp_player_loop_twice() {
+        let mut player = AnimatedWebpPlayer::new("tests/fixtures/media/loop_twice.webp").unwrap();
+        player.handle_resize(10, 5);
+        assert_eq!(player.loop_count(), Some(2));
+
+        let mut frames = 0;
+        while let Some(frame) = player.next_frame() {
+            frame.unwrap();
+            frames += 1;
+        }
+        assert_eq!(frames, 4);
+
+        player.reset();
+        assert!(
+            player.next_frame().is_some(),
+            "Should have frame after reset"
+        );
+    }
+
+    #[test]
+    fn test_webp_canvases_at() {
+        let mut player = AnimatedWebpPlayer::new("tests/fixtures/media/animated.webp").unwrap();
+        let images = player
+            .canvases_at(&[Duration::from_millis(50), Duration::from_millis(250)])
+            .unwrap();
+
+        // Each frame lights its own band of columns
+        assert_eq!(images[0].get_pixel(0, 0).0, [255, 255, 255, 255]);
+        assert_eq!(images[0].get_pixel(9, 0).0[3], 0);
+        assert_eq!(images[1].get_pixel(9, 0).0, [255, 255, 255, 255]);
+        assert_eq!(images[1].get_pixel(0, 0).0[3], 0);
+    }
+}
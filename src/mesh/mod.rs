@@ -0,0 +1,549 @@
+//! Triangle meshes, a camera, and wireframe rendering for 3D models.
+//!
+//! A [`Mesh`] is a list of [`Vec3`] vertices and the triangles joining
+//! them. A [`Camera`] orbits the origin and projects points onto a grid's
+//! dots, and [`draw_wireframe`] draws every edge of a mesh through it:
+//!
+//! ```
+//! use dotmax::mesh::{draw_wireframe, Camera, Mesh};
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(40, 20)?;
+//! let camera = Camera::new().orbit(0.6, 0.4);
+//! draw_wireframe(&mut grid, &Mesh::cube(), &camera)?;
+//! assert!(grid.get_raw_patterns().iter().any(|&p| p != 0));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! # Filled Rendering
+//!
+//! [`draw_shaded`] and [`draw_shaded_density`] fill the triangles instead,
+//! keeping the nearest surface at each dot and shading each face by how
+//! directly it faces a [`Light`]: as dithered dots, or as characters from a
+//! [`DensitySet`](crate::density::DensitySet), either one optionally
+//! colored through a [`ColorScheme`](crate::ColorScheme). [`rasterize`]
+//! gives the shaded intensities themselves.
+//!
+//! # Loading Models
+//!
+//! With the `models` feature, meshes load from Wavefront OBJ and from ASCII
+//! or binary STL files. [`Mesh::normalized`] centers a model and scales it
+//! to fit the default camera, whatever units it was saved in:
+//!
+//! ```ignore
+//! use dotmax::mesh::{draw_wireframe, Camera, Mesh};
+//! use dotmax::BrailleGrid;
+//!
+//! let teapot = Mesh::load("teapot.obj")?.normalized();
+//! let mut grid = BrailleGrid::new(80, 24)?;
+//! draw_wireframe(&mut grid, &teapot, &Camera::new())?;
+//! dotmax::quick::show(&grid)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! # Coordinates
+//!
+//! Models use a right-handed system with y up, as OBJ and most modeling
+//! tools do. The camera looks at the origin from `distance` along +z
+//! before its orbit is applied. Braille dots are about square, so the
+//! projection needs no aspect correction.
+
+#[cfg(feature = "models")]
+mod obj;
+mod raster;
+#[cfg(feature = "models")]
+mod stl;
+
+pub use raster::{draw_shaded, draw_shaded_density, rasterize, Light};
+
+use std::ops::{Add, Mul, Neg, Sub};
+#[cfg(feature = "models")]
+use std::path::Path;
+
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use crate::primitives::draw_line;
+#[cfg(feature = "models")]
+use tracing::{debug, info};
+
+/// A point or direction in model space.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec3 {
+    /// Rightward component
+    pub x: f32,
+    /// Upward component
+    pub y: f32,
+    /// Component toward the default camera
+    pub z: f32,
+}
+
+impl Vec3 {
+    /// The zero vector.
+    pub const ZERO: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// A vector from its components.
+    #[must_use]
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The dot product.
+    #[must_use]
+    pub fn dot(self, other: Self) -> f32 {
+        self.x
+            .mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
+    }
+
+    /// The cross product, perpendicular to both vectors.
+    #[must_use]
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y.mul_add(other.z, -self.z * other.y),
+            self.z.mul_add(other.x, -self.x * other.z),
+            self.x.mul_add(other.y, -self.y * other.x),
+        )
+    }
+
+    /// The length.
+    #[must_use]
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// This vector scaled to length 1, or zero if it has no length.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let length = self.length();
+        if length > 0.0 {
+            self * (1.0 / length)
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /// Componentwise minimum.
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Componentwise maximum.
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl From<[f32; 3]> for Vec3 {
+    fn from([x, y, z]: [f32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// Vertices and the triangles joining them.
+///
+/// Triangles are counter-clockwise when seen from outside the model, the
+/// order OBJ and STL both use.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    vertices: Vec<Vec3>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    /// A mesh from vertices and triangles indexing into them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if a triangle refers to a
+    /// vertex that doesn't exist.
+    pub fn new(vertices: Vec<Vec3>, triangles: Vec<[usize; 3]>) -> Result<Self, DotmaxError> {
+        if let Some(&index) = triangles.iter().flatten().find(|&&i| i >= vertices.len()) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "triangle vertex index".to_string(),
+                value: index.to_string(),
+                min: "0".to_string(),
+                max: vertices.len().saturating_sub(1).to_string(),
+            });
+        }
+        Ok(Self {
+            vertices,
+            triangles,
+        })
+    }
+
+    /// A cube with corners at ±1, two triangles per face.
+    #[must_use]
+    pub fn cube() -> Self {
+        let vertices = (0..8)
+            .map(|i| {
+                let sign = |bit: usize| if i & bit == 0 { -1.0 } else { 1.0 };
+                Vec3::new(sign(1), sign(2), sign(4))
+            })
+            .collect();
+        // Corner i has x set by bit 0, y by bit 1, z by bit 2
+        let triangles = vec![
+            [0, 4, 6],
+            [0, 6, 2], // -x
+            [1, 3, 7],
+            [1, 7, 5], // +x
+            [0, 1, 5],
+            [0, 5, 4], // -y
+            [2, 6, 7],
+            [2, 7, 3], // +y
+            [0, 2, 3],
+            [0, 3, 1], // -z
+            [4, 5, 7],
+            [4, 7, 6], // +z
+        ];
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Loads an OBJ or STL file, choosing the parser from the extension
+    /// (`.obj` or `.stl`, any case).
+    ///
+    /// Requires the `models` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be read, and
+    /// [`DotmaxError::ModelError`] for other extensions or if parsing
+    /// fails; see [`from_obj_str`](Self::from_obj_str) and
+    /// [`from_stl_bytes`](Self::from_stl_bytes).
+    #[cfg(feature = "models")]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        info!(path = ?path, "Loading model");
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        let mesh = match extension.as_deref() {
+            Some("obj") => Self::from_obj_str(&std::fs::read_to_string(path)?),
+            Some("stl") => Self::from_stl_bytes(&std::fs::read(path)?),
+            _ => Err(DotmaxError::ModelError(format!(
+                "{}: expected a .obj or .stl file",
+                path.display()
+            ))),
+        }?;
+        debug!(
+            vertices = mesh.vertices.len(),
+            triangles = mesh.triangles.len(),
+            "Loaded model"
+        );
+        Ok(mesh)
+    }
+
+    /// The vertices.
+    #[must_use]
+    pub fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    /// The triangles, as indices into [`vertices`](Self::vertices).
+    #[must_use]
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Whether the mesh has no triangles.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    /// The corners of the smallest axis-aligned box holding every vertex,
+    /// or `None` for a mesh without vertices.
+    #[must_use]
+    pub fn bounds(&self) -> Option<(Vec3, Vec3)> {
+        let (&first, rest) = self.vertices.split_first()?;
+        Some(
+            rest.iter()
+                .fold((first, first), |(min, max), &v| (min.min(v), max.max(v))),
+        )
+    }
+
+    /// This mesh moved so its bounding box is centered on the origin and
+    /// scaled so its longest side spans -1 to 1.
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        let Some((min, max)) = self.bounds() else {
+            return self.clone();
+        };
+        let center = (min + max) * 0.5;
+        let size = max - min;
+        let longest = size.x.max(size.y).max(size.z);
+        let scale = if longest > 0.0 { 2.0 / longest } else { 1.0 };
+        Self {
+            vertices: self
+                .vertices
+                .iter()
+                .map(|&v| (v - center) * scale)
+                .collect(),
+            triangles: self.triangles.clone(),
+        }
+    }
+
+    /// Every edge once, as vertex index pairs with the smaller index first,
+    /// in ascending order.
+    #[must_use]
+    pub fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges: Vec<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+            .map(|[a, b]| [a.min(b), a.max(b)])
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// The unit normal of triangle `index`, facing outward for
+    /// counter-clockwise triangles; zero for degenerate ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    #[must_use]
+    pub fn face_normal(&self, index: usize) -> Vec3 {
+        let [a, b, c] = self.triangles[index].map(|i| self.vertices[i]);
+        (b - a).cross(c - a).normalized()
+    }
+}
+
+/// Looks at the origin from a distance, orbiting around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    fov: f32,
+}
+
+impl Camera {
+    /// Points closer to the camera than this aren't drawn.
+    pub const NEAR: f32 = 0.1;
+
+    /// A camera 6 units out along +z with a 50° vertical field of view,
+    /// which frames a [normalized](Mesh::normalized) mesh at any rotation.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: 0.0,
+            distance: 6.0,
+            fov: 50.0,
+        }
+    }
+
+    /// Turns the model `yaw` radians about the vertical axis, then tilts it
+    /// `pitch` radians toward the camera.
+    #[must_use]
+    pub const fn orbit(mut self, yaw: f32, pitch: f32) -> Self {
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self
+    }
+
+    /// Sets the distance from the origin.
+    #[must_use]
+    pub const fn distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Sets the vertical field of view in degrees (clamped to 1–179).
+    #[must_use]
+    pub fn fov(mut self, degrees: f32) -> Self {
+        self.fov = degrees.clamp(1.0, 179.0);
+        self
+    }
+
+    /// `point` in camera space: orbited, then moved so the camera sits at
+    /// the origin looking down -z.
+    #[must_use]
+    pub fn view(&self, point: Vec3) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let x = point.x.mul_add(cos_yaw, point.z * sin_yaw);
+        let z = (-point.x).mul_add(sin_yaw, point.z * cos_yaw);
+        let y = point.y.mul_add(cos_pitch, -z * sin_pitch);
+        let z = point.y.mul_add(sin_pitch, z * cos_pitch);
+        Vec3::new(x, y, z - self.distance)
+    }
+
+    /// Projects `point` onto a canvas `width` × `height` dots: the dot
+    /// position and the point's distance in front of the camera, or `None`
+    /// if it's behind [`NEAR`](Self::NEAR).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn project(&self, point: Vec3, width: usize, height: usize) -> Option<(f32, f32, f32)> {
+        let view = self.view(point);
+        let depth = -view.z;
+        if depth < Self::NEAR {
+            return None;
+        }
+        let focal = height as f32 / 2.0 / (self.fov.to_radians() / 2.0).tan();
+        Some((
+            (width as f32).mul_add(0.5, view.x * focal / depth),
+            (height as f32).mul_add(0.5, -view.y * focal / depth),
+            depth,
+        ))
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws every edge of `mesh` as seen by `camera`, clipped to the grid.
+///
+/// Edges with an end behind the camera are skipped.
+///
+/// # Errors
+///
+/// Returns an error if drawing a line fails.
+#[allow(clippy::cast_possible_truncation)]
+pub fn draw_wireframe(
+    grid: &mut BrailleGrid,
+    mesh: &Mesh,
+    camera: &Camera,
+) -> Result<(), DotmaxError> {
+    let (width, height) = (grid.dot_width(), grid.dot_height());
+    let projected: Vec<Option<(i32, i32)>> = mesh
+        .vertices
+        .iter()
+        .map(|&v| {
+            camera
+                .project(v, width, height)
+                .map(|(x, y, _)| (x.round() as i32, y.round() as i32))
+        })
+        .collect();
+    for [a, b] in mesh.edges() {
+        if let (Some((x0, y0)), Some((x1, y1))) = (projected[a], projected[b]) {
+            draw_line(grid, x0, y0, x1, y1)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_rejects_bad_indices() {
+        let vertices = vec![
+            Vec3::ZERO,
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        ];
+        assert!(Mesh::new(vertices.clone(), vec![[0, 1, 2]]).is_ok());
+        assert!(matches!(
+            Mesh::new(vertices, vec![[0, 1, 3]]),
+            Err(DotmaxError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cube_faces_point_outward() {
+        let cube = Mesh::cube();
+        assert_eq!(cube.edges().len(), 18);
+        for (index, &[a, b, c]) in cube.triangles().iter().enumerate() {
+            let center =
+                (cube.vertices()[a] + cube.vertices()[b] + cube.vertices()[c]) * (1.0 / 3.0);
+            assert!(cube.face_normal(index).dot(center) > 0.0, "face {index}");
+        }
+    }
+
+    #[test]
+    fn test_normalized_fits_unit_box() {
+        let scaled = Mesh::new(
+            vec![
+                Vec3::new(10.0, 20.0, 30.0),
+                Vec3::new(14.0, 21.0, 31.0),
+                Vec3::new(10.0, 22.0, 30.0),
+            ],
+            vec![[0, 1, 2]],
+        )
+        .unwrap()
+        .normalized();
+        let (min, max) = scaled.bounds().unwrap();
+        assert_eq!(min, Vec3::new(-1.0, -0.5, -0.25));
+        assert_eq!(max, Vec3::new(1.0, 0.5, 0.25));
+    }
+
+    #[test]
+    fn test_project_centers_origin_and_culls_behind() {
+        let camera = Camera::new();
+        let (x, y, depth) = camera.project(Vec3::ZERO, 80, 40).unwrap();
+        assert_eq!((x, y, depth), (40.0, 20.0, 6.0));
+
+        // Up in model space is up on screen
+        let (_, y, _) = camera.project(Vec3::new(0.0, 1.0, 0.0), 80, 40).unwrap();
+        assert!(y < 20.0);
+
+        assert_eq!(camera.project(Vec3::new(0.0, 0.0, 7.0), 80, 40), None);
+    }
+
+    #[test]
+    fn test_wireframe_stays_on_grid() {
+        let mut grid = BrailleGrid::new(20, 10).unwrap();
+        let camera = Camera::new().orbit(0.5, 0.3);
+        draw_wireframe(&mut grid, &Mesh::cube(), &camera).unwrap();
+        let inked = grid.get_raw_patterns().iter().filter(|&&p| p != 0).count();
+        assert!(inked > 20);
+        // The cube fits inside the frame: the border cells stay empty
+        assert!((0..20).all(|x| grid.get_raw_patterns()[x] == 0));
+    }
+}
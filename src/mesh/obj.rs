@@ -0,0 +1,137 @@
+//! Wavefront OBJ parsing.
+//!
+//! Only geometry is read: `v` vertex positions and `f` faces. Texture
+//! coordinates, normals, groups, and materials are skipped. Faces with more
+//! than three corners are split into a fan of triangles from their first
+//! corner, which is exact for the convex polygons modelers write.
+
+use super::{Mesh, Vec3};
+use crate::error::DotmaxError;
+
+impl Mesh {
+    /// Parses the text of a Wavefront OBJ file.
+    ///
+    /// Face corners may be written `v`, `v/vt`, `v//vn`, or `v/vt/vn`, and
+    /// negative indices count back from the last vertex read so far.
+    ///
+    /// Requires the `models` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::ModelError`] naming the line of a vertex
+    /// without three numeric coordinates, a face with fewer than three
+    /// corners, or a face index that doesn't refer to a vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::mesh::Mesh;
+    ///
+    /// let square = Mesh::from_obj_str(
+    ///     "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n",
+    /// )?;
+    /// assert_eq!(square.triangles(), [[0, 1, 2], [0, 2, 3]]);
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn from_obj_str(text: &str) -> Result<Self, DotmaxError> {
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let error = |message: String| {
+                DotmaxError::ModelError(format!("OBJ line {}: {message}", number + 1))
+            };
+            let line = line.split('#').next().unwrap_or("");
+            let mut fields = line.split_whitespace();
+            match fields.next() {
+                Some("v") => {
+                    let coords: Vec<f32> = fields
+                        .take(3)
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| error(format!("bad vertex coordinate: {e}")))?;
+                    let [x, y, z] = coords[..] else {
+                        return Err(error("vertex needs x, y, and z".to_string()));
+                    };
+                    vertices.push(Vec3::new(x, y, z));
+                }
+                Some("f") => {
+                    let corners = fields
+                        .map(|corner| resolve_index(corner, vertices.len()).map_err(&error))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if corners.len() < 3 {
+                        return Err(error(format!("face has {} corners", corners.len())));
+                    }
+                    triangles.extend(
+                        corners
+                            .windows(2)
+                            .skip(1)
+                            .map(|pair| [corners[0], pair[0], pair[1]]),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            vertices,
+            triangles,
+        })
+    }
+}
+
+/// The zero-based vertex index of a face corner like `7`, `7/2/5`, or `-1`.
+fn resolve_index(corner: &str, vertex_count: usize) -> Result<usize, String> {
+    let position = corner.split('/').next().unwrap_or("");
+    let index: i64 = position
+        .parse()
+        .map_err(|_| format!("bad face index {corner:?}"))?;
+    let resolved = if index < 0 {
+        i64::try_from(vertex_count).map_or(-1, |count| count + index)
+    } else {
+        index - 1
+    };
+    usize::try_from(resolved)
+        .ok()
+        .filter(|&i| i < vertex_count)
+        .ok_or_else(|| format!("face index {index} with {vertex_count} vertices"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_vertices_and_fan_triangulates() {
+        let mesh = Mesh::from_obj_str(
+            "# a pentagon\n\
+             o shape\n\
+             v 0 0 0\nv 1 0 0\nv 2 1 0\nv 1 2 0\nv 0 1 0 1.0\n\
+             vt 0 0\nvn 0 0 1\n\
+             usemtl red\n\
+             f 1/1/1 2//1 3 4/1 5\n",
+        )
+        .unwrap();
+        assert_eq!(mesh.vertices().len(), 5);
+        assert_eq!(mesh.vertices()[2], Vec3::new(2.0, 1.0, 0.0));
+        assert_eq!(mesh.triangles(), [[0, 1, 2], [0, 2, 3], [0, 3, 4]]);
+    }
+
+    #[test]
+    fn test_negative_indices_are_relative() {
+        let mesh = Mesh::from_obj_str("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n").unwrap();
+        assert_eq!(mesh.triangles(), [[0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let err = Mesh::from_obj_str("v 0 0 0\nv 1 0\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+
+        let err = Mesh::from_obj_str("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n").unwrap_err();
+        assert!(err.to_string().contains("line 4"), "{err}");
+
+        assert!(Mesh::from_obj_str("v 0 0 0\nv 1 0 0\nf 1 2\n").is_err());
+        assert!(Mesh::from_obj_str("v 0 0 x\n").is_err());
+    }
+}
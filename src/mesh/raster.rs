@@ -0,0 +1,336 @@
+//! Filled, depth-buffered rendering of meshes.
+//!
+//! [`rasterize`] fills every triangle of a mesh at dot resolution, keeping
+//! the nearest surface at each dot in a z-buffer, and shades each triangle
+//! by how directly it faces the [`Light`] (flat Lambert shading). The
+//! result is an [`IntensityBuffer`] with 0.0 wherever nothing was drawn.
+//!
+//! Two ways to put it on a grid:
+//!
+//! - [`draw_shaded`] turns intensities into dots with ordered dithering, so
+//!   lit faces are dense and faces turned away are sparse. With a
+//!   [`ColorScheme`], each cell is also colored by its brightest dot.
+//! - [`draw_shaded_density`] averages each cell's 2×4 dots and draws the
+//!   result as [`DensitySet`] characters, optionally colored.
+//!
+//! Triangles with a corner behind [`Camera::NEAR`] are skipped rather than
+//! clipped, which only matters for a camera inside the model.
+
+use super::{Camera, Mesh, Vec3};
+use crate::color::schemes::ColorScheme;
+use crate::density::{DensitySet, IntensityBuffer};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// 4×4 Bayer matrix; dot `(x, y)` is set when its intensity exceeds
+/// `(BAYER[y % 4][x % 4] + 0.5) / 16`.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A directional light, fixed relative to the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    direction: Vec3,
+    ambient: f32,
+}
+
+impl Light {
+    /// A light shining from `direction` in camera space (x right, y up,
+    /// z toward the viewer), with 0.15 ambient.
+    #[must_use]
+    pub fn new(direction: Vec3) -> Self {
+        Self {
+            direction: direction.normalized(),
+            ambient: 0.15,
+        }
+    }
+
+    /// Sets the intensity of faces turned away from the light (clamped to
+    /// 0–1). At 1.0 every face is fully lit, for a flat silhouette.
+    #[must_use]
+    pub fn ambient(mut self, ambient: f32) -> Self {
+        self.ambient = ambient.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The intensity of a surface with unit `normal`:
+    /// `ambient + (1 - ambient) × max(0, normal · direction)`.
+    #[must_use]
+    pub fn intensity(&self, normal: Vec3) -> f32 {
+        let diffuse = normal.dot(self.direction).max(0.0);
+        (1.0 - self.ambient).mul_add(diffuse, self.ambient)
+    }
+}
+
+impl Default for Light {
+    /// From the upper left, behind the viewer.
+    fn default() -> Self {
+        Self::new(Vec3::new(-1.0, 1.0, 1.5))
+    }
+}
+
+/// Fills `mesh` as seen by `camera` into a `width` × `height` buffer,
+/// one value per dot, nearest surface first.
+///
+/// Triangles are two-sided: a face seen from behind is lit as if it
+/// faced the camera, so open meshes render too.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::mesh::{rasterize, Camera, Light, Mesh};
+///
+/// let buffer = rasterize(&Mesh::cube(), &Camera::new(), &Light::default(), 40, 40);
+/// let center = buffer.get(20, 20).unwrap();
+/// assert!(center > 0.0); // the front face
+/// assert_eq!(buffer.get(0, 0), Some(0.0)); // background
+/// ```
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss,
+    clippy::many_single_char_names
+)]
+pub fn rasterize(
+    mesh: &Mesh,
+    camera: &Camera,
+    light: &Light,
+    width: usize,
+    height: usize,
+) -> IntensityBuffer {
+    let mut intensity = vec![0.0; width * height];
+    // 1 / depth of the nearest surface so far; 0 is infinitely far
+    let mut inverse_depth = vec![0.0_f32; width * height];
+    let projected: Vec<Option<(f32, f32, f32)>> = mesh
+        .vertices()
+        .iter()
+        .map(|&v| camera.project(v, width, height))
+        .collect();
+
+    for &[a, b, c] in mesh.triangles() {
+        let (Some(pa), Some(pb), Some(pc)) = (projected[a], projected[b], projected[c]) else {
+            continue;
+        };
+        let area = edge(pa, pb, pc);
+        if area == 0.0 {
+            continue;
+        }
+
+        // Shade in camera space, facing the viewer
+        let [va, vb, vc] = [a, b, c].map(|i| camera.view(mesh.vertices()[i]));
+        let mut normal = (vb - va).cross(vc - va).normalized();
+        if normal.dot(va) > 0.0 {
+            normal = -normal;
+        }
+        let shade = light.intensity(normal);
+
+        let min_x = pa.0.min(pb.0).min(pc.0).floor().max(0.0) as usize;
+        let min_y = pa.1.min(pb.1).min(pc.1).floor().max(0.0) as usize;
+        let max_x = (pa.0.max(pb.0).max(pc.0).ceil().max(0.0) as usize).min(width);
+        let max_y = (pa.1.max(pb.1).max(pc.1).ceil().max(0.0) as usize).min(height);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = (x as f32 + 0.5, y as f32 + 0.5, 0.0);
+                // Barycentric weights, positive inside for either winding
+                let wa = edge(pb, pc, p) / area;
+                let wb = edge(pc, pa, p) / area;
+                let wc = edge(pa, pb, p) / area;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+                // 1 / depth is linear in screen space
+                let z = wc.mul_add(1.0 / pc.2, wa.mul_add(1.0 / pa.2, wb / pb.2));
+                let index = y * width + x;
+                if z > inverse_depth[index] {
+                    inverse_depth[index] = z;
+                    intensity[index] = shade;
+                }
+            }
+        }
+    }
+
+    IntensityBuffer::new(width, height, intensity)
+        .unwrap_or_else(|_| unreachable!("buffer length is width × height"))
+}
+
+/// Twice the signed area of triangle `a`, `b`, `p` on screen.
+fn edge(a: (f32, f32, f32), b: (f32, f32, f32), p: (f32, f32, f32)) -> f32 {
+    (b.0 - a.0).mul_add(p.1 - a.1, -(b.1 - a.1) * (p.0 - a.0))
+}
+
+/// Fills and shades `mesh` into the dots of `grid`, dithering intensity
+/// into dot density. With a `scheme`, each cell is colored by the
+/// brightest dot in it.
+///
+/// # Errors
+///
+/// Returns an error if setting a dot or color fails.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::mesh::{draw_shaded, Camera, Light, Mesh};
+/// use dotmax::{BrailleGrid, ColorScheme};
+///
+/// let mut grid = BrailleGrid::new(30, 12)?;
+/// let camera = Camera::new().orbit(0.7, 0.5);
+/// draw_shaded(&mut grid, &Mesh::cube(), &camera, &Light::default(), Some(&ColorScheme::heat_map()))?;
+/// assert!(grid.get_color(15, 6).is_some());
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_shaded(
+    grid: &mut BrailleGrid,
+    mesh: &Mesh,
+    camera: &Camera,
+    light: &Light,
+    scheme: Option<&ColorScheme>,
+) -> Result<(), DotmaxError> {
+    let (width, height) = (grid.dot_width(), grid.dot_height());
+    let buffer = rasterize(mesh, camera, light, width, height);
+    let values = buffer.as_slice();
+
+    for (index, &value) in values.iter().enumerate() {
+        let (x, y) = (index % width, index / width);
+        let threshold = (f32::from(BAYER[y % 4][x % 4]) + 0.5) / 16.0;
+        if value > threshold {
+            grid.set_dot(x, y)?;
+        }
+    }
+
+    if let Some(scheme) = scheme {
+        grid.enable_color_support();
+        for cell_y in 0..grid.height() {
+            for cell_x in 0..grid.width() {
+                let brightest = (0..4)
+                    .flat_map(|dy| (0..2).map(move |dx| (cell_x * 2 + dx, cell_y * 4 + dy)))
+                    .map(|(x, y)| values[y * width + x])
+                    .fold(0.0, f32::max);
+                if brightest > 0.0 {
+                    grid.set_cell_color(cell_x, cell_y, scheme.sample(brightest))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fills and shades `mesh`, then draws it as `density` characters, one per
+/// cell, from the mean of the cell's 2×4 dots. With a `scheme`, cells are
+/// also colored by that mean.
+///
+/// # Errors
+///
+/// Returns an error if the grid can't take the characters; see
+/// [`IntensityBuffer::render`].
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::density::DensitySet;
+/// use dotmax::mesh::{draw_shaded_density, Camera, Light, Mesh};
+/// use dotmax::BrailleGrid;
+///
+/// let mut grid = BrailleGrid::new(30, 12)?;
+/// let camera = Camera::new().orbit(0.7, 0.5);
+/// draw_shaded_density(&mut grid, &Mesh::cube(), &camera, &Light::default(), &DensitySet::ascii(), None)?;
+/// assert_ne!(grid.get_char(15, 6), ' ');
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_shaded_density(
+    grid: &mut BrailleGrid,
+    mesh: &Mesh,
+    camera: &Camera,
+    light: &Light,
+    density: &DensitySet,
+    scheme: Option<&ColorScheme>,
+) -> Result<(), DotmaxError> {
+    rasterize(mesh, camera, light, grid.dot_width(), grid.dot_height())
+        .render(grid, density, scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A square facing the camera, and a smaller one in front of it
+    /// tilted 60° back about the x axis.
+    fn two_squares() -> Mesh {
+        let (sin, cos) = 60.0_f32.to_radians().sin_cos();
+        let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+        let back = corners.map(|(x, y)| Vec3::new(x, y, 0.0));
+        let front = corners.map(|(x, y)| Vec3::new(x * 0.5, y * 0.5 * cos, 1.0 - y * 0.5 * sin));
+        let vertices = [back, front].concat();
+        Mesh::new(vertices, vec![[0, 1, 2], [0, 2, 3], [4, 5, 6], [4, 6, 7]]).unwrap()
+    }
+
+    #[test]
+    fn test_light_intensity() {
+        let light = Light::new(Vec3::new(0.0, 0.0, 2.0));
+        assert!((light.intensity(Vec3::new(0.0, 0.0, 1.0)) - 1.0).abs() < 1e-6);
+        assert!((light.intensity(Vec3::new(0.0, 0.0, -1.0)) - 0.15).abs() < 1e-6);
+        assert_eq!(
+            Light::default()
+                .ambient(1.0)
+                .intensity(Vec3::new(1.0, 0.0, 0.0)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_nearest_surface_wins_in_any_order() {
+        let mesh = two_squares();
+        let camera = Camera::new();
+        let light = Light::new(Vec3::new(0.0, 0.0, 1.0)).ambient(0.2);
+        let buffer = rasterize(&mesh, &camera, &light, 40, 40);
+        // The tilted square: 0.2 + 0.8 × cos 60°
+        assert!((buffer.get(20, 20).unwrap() - 0.6).abs() < 1e-4);
+        // Only the back square out here
+        assert!((buffer.get(20, 14).unwrap() - 1.0).abs() < 1e-4);
+        assert_eq!(buffer.get(0, 0), Some(0.0));
+
+        let reversed = Mesh::new(
+            mesh.vertices().to_vec(),
+            mesh.triangles().iter().rev().copied().collect(),
+        )
+        .unwrap();
+        assert_eq!(rasterize(&reversed, &camera, &light, 40, 40), buffer);
+    }
+
+    #[test]
+    fn test_faces_turned_away_are_darker() {
+        let camera = Camera::new().orbit(0.6, 0.4);
+        let buffer = rasterize(&Mesh::cube(), &camera, &Light::default(), 60, 60);
+        let lit: Vec<f32> = buffer
+            .as_slice()
+            .iter()
+            .copied()
+            .filter(|&v| v > 0.0)
+            .collect();
+        let brightest = lit.iter().copied().fold(0.0, f32::max);
+        let darkest = lit.iter().copied().fold(1.0, f32::min);
+        assert!(brightest - darkest > 0.2, "{darkest}..{brightest}");
+    }
+
+    #[test]
+    fn test_draw_shaded_dithers_by_intensity() {
+        let mesh = two_squares();
+        let mut bright = BrailleGrid::new(20, 10).unwrap();
+        draw_shaded(
+            &mut bright,
+            &mesh,
+            &Camera::new(),
+            &Light::default().ambient(1.0),
+            None,
+        )
+        .unwrap();
+        let mut dim = BrailleGrid::new(20, 10).unwrap();
+        let light = Light::new(Vec3::new(0.0, 0.0, -1.0)).ambient(0.3);
+        draw_shaded(&mut dim, &mesh, &Camera::new(), &light, None).unwrap();
+
+        let ink = |grid: &BrailleGrid| -> u32 {
+            grid.get_raw_patterns().iter().map(|p| p.count_ones()).sum()
+        };
+        assert!(ink(&dim) * 2 < ink(&bright));
+        assert_eq!(bright.get_raw_patterns()[0], 0);
+    }
+}
@@ -0,0 +1,211 @@
+//! STL parsing, binary and ASCII.
+//!
+//! STL stores every triangle with its own three corners. Corners at the
+//! same position are merged into one vertex, in order of first appearance,
+//! so edges shared by two triangles are drawn once. Stored facet normals
+//! are ignored; [`Mesh::face_normal`] recomputes them from the winding.
+
+use std::collections::HashMap;
+
+use super::{Mesh, Vec3};
+use crate::error::DotmaxError;
+
+/// Bytes before the triangles of a binary STL: an 80-byte header and a
+/// 32-bit triangle count.
+const HEADER_LEN: usize = 84;
+
+/// Bytes per triangle in a binary STL: normal, three corners, attributes.
+const TRIANGLE_LEN: usize = 50;
+
+impl Mesh {
+    /// Parses an STL file, binary or ASCII.
+    ///
+    /// Data whose length matches the triangle count in a binary header is
+    /// read as binary, even when the header starts with `solid` as some
+    /// exporters write; anything else starting with `solid` is read as
+    /// ASCII.
+    ///
+    /// Requires the `models` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::ModelError`] if the data is neither, or an
+    /// ASCII facet doesn't have three numeric vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::mesh::Mesh;
+    ///
+    /// let stl = "solid tri
+    ///   facet normal 0 0 1
+    ///     outer loop
+    ///       vertex 0 0 0
+    ///       vertex 1 0 0
+    ///       vertex 0 1 0
+    ///     endloop
+    ///   endfacet
+    /// endsolid tri";
+    /// let mesh = Mesh::from_stl_bytes(stl.as_bytes())?;
+    /// assert_eq!(mesh.triangles(), [[0, 1, 2]]);
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn from_stl_bytes(bytes: &[u8]) -> Result<Self, DotmaxError> {
+        if let Some(count) = binary_triangle_count(bytes) {
+            return Ok(from_binary(&bytes[HEADER_LEN..], count));
+        }
+        let start = bytes
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(bytes.len());
+        if bytes[start..].starts_with(b"solid") {
+            let text = std::str::from_utf8(bytes)
+                .map_err(|e| DotmaxError::ModelError(format!("STL: not ASCII text: {e}")))?;
+            return from_ascii(text);
+        }
+        Err(DotmaxError::ModelError(format!(
+            "STL: {} bytes is neither binary STL nor ASCII STL",
+            bytes.len()
+        )))
+    }
+}
+
+/// The triangle count of binary STL data, if its length matches.
+fn binary_triangle_count(bytes: &[u8]) -> Option<usize> {
+    let count = bytes.get(80..HEADER_LEN)?;
+    let count = usize::try_from(u32::from_le_bytes(count.try_into().ok()?)).ok()?;
+    (bytes.len() == HEADER_LEN + count.checked_mul(TRIANGLE_LEN)?).then_some(count)
+}
+
+fn from_binary(data: &[u8], count: usize) -> Mesh {
+    let mut builder = Builder::default();
+    for record in data.chunks_exact(TRIANGLE_LEN).take(count) {
+        // Skip the 12-byte normal; three corners of three f32s follow
+        let mut corners = record[12..48].chunks_exact(12).map(|corner| {
+            let coord = |i: usize| {
+                f32::from_le_bytes([corner[i], corner[i + 1], corner[i + 2], corner[i + 3]])
+            };
+            Vec3::new(coord(0), coord(4), coord(8))
+        });
+        let triangle = [(); 3].map(|()| corners.next().unwrap_or_default());
+        builder.push(triangle);
+    }
+    builder.finish()
+}
+
+fn from_ascii(text: &str) -> Result<Mesh, DotmaxError> {
+    let mut builder = Builder::default();
+    let mut corners = Vec::with_capacity(3);
+    for (number, line) in text.lines().enumerate() {
+        let error =
+            |message: &str| DotmaxError::ModelError(format!("STL line {}: {message}", number + 1));
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("vertex") => {
+                let coords: Vec<f32> = fields
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| error("bad vertex coordinate"))?;
+                let [x, y, z] = coords[..] else {
+                    return Err(error("vertex needs x, y, and z"));
+                };
+                corners.push(Vec3::new(x, y, z));
+            }
+            Some("endfacet") => {
+                let [a, b, c] = corners[..] else {
+                    return Err(error("facet needs three vertices"));
+                };
+                builder.push([a, b, c]);
+                corners.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(builder.finish())
+}
+
+/// Collects triangles, merging corners at identical positions.
+#[derive(Default)]
+struct Builder {
+    vertices: Vec<Vec3>,
+    triangles: Vec<[usize; 3]>,
+    // Keyed on bit patterns; -0.0 and 0.0 stay distinct, which only
+    // costs a duplicate vertex
+    index: HashMap<[u32; 3], usize>,
+}
+
+impl Builder {
+    fn push(&mut self, corners: [Vec3; 3]) {
+        let triangle = corners.map(|v| {
+            let vertices = &mut self.vertices;
+            *self
+                .index
+                .entry([v.x.to_bits(), v.y.to_bits(), v.z.to_bits()])
+                .or_insert_with(|| {
+                    vertices.push(v);
+                    vertices.len() - 1
+                })
+        });
+        self.triangles.push(triangle);
+    }
+
+    fn finish(self) -> Mesh {
+        Mesh {
+            vertices: self.vertices,
+            triangles: self.triangles,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(triangles: &[[[f32; 3]; 3]], header: &[u8]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.resize(80, b' ');
+        bytes.extend(u32::try_from(triangles.len()).unwrap().to_le_bytes());
+        for triangle in triangles {
+            bytes.extend([0u8; 12]);
+            for coord in triangle.iter().flatten() {
+                bytes.extend(coord.to_le_bytes());
+            }
+            bytes.extend([0u8; 2]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_binary_merges_shared_corners() {
+        let bytes = binary(
+            &[
+                [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+                [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+            ],
+            b"exported by a CAD tool",
+        );
+        let mesh = Mesh::from_stl_bytes(&bytes).unwrap();
+        assert_eq!(mesh.vertices().len(), 4);
+        assert_eq!(mesh.triangles(), [[0, 1, 2], [0, 2, 3]]);
+        assert_eq!(mesh.vertices()[3], Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_binary_header_starting_with_solid() {
+        let bytes = binary(
+            &[[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]],
+            b"solid but binary",
+        );
+        assert_eq!(Mesh::from_stl_bytes(&bytes).unwrap().triangles().len(), 1);
+    }
+
+    #[test]
+    fn test_ascii_errors() {
+        let short = "solid x\nfacet normal 0 0 1\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nendloop\nendfacet\nendsolid x\n";
+        let err = Mesh::from_stl_bytes(short.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 7"), "{err}");
+
+        assert!(Mesh::from_stl_bytes(b"not a model").is_err());
+        assert!(Mesh::from_stl_bytes(b"solid x\nvertex 0 0 zero\n").is_err());
+    }
+}
@@ -0,0 +1,468 @@
+//! Vector math and simple physics for things that move around a grid.
+//!
+//! Particles, bouncing logos, and game objects all need the same few
+//! steps each frame: add the forces to the velocity, move by the velocity,
+//! and keep the result on screen. A [`Body`] is a position and a velocity
+//! in dot coordinates, and its methods do those steps:
+//!
+//! - [`Body::integrate`] moves it under an acceleration, updating the
+//!   velocity before the position (semi-implicit Euler), which stays
+//!   stable for springs and orbits where the naive order gains energy.
+//! - [`Body::bounce`] reflects it off the edges of a [`Bounds`], losing
+//!   whatever energy the restitution says.
+//! - [`Body::damp`] bleeds off speed at a rate independent of the step
+//!   size, and [`spring`] gives the acceleration that pulls a body toward a
+//!   target.
+//!
+//! Time steps are in whatever unit the velocities are: seconds with
+//! velocities in dots per second, or `1.0` per frame with velocities in
+//! dots per frame.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::motion::{Body, Bounds, Vec2};
+//! use dotmax::BrailleGrid;
+//!
+//! let grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+//! let bounds = Bounds::of_grid(&grid).shrink(2.0); // radius-2 ball
+//! let gravity = Vec2::new(0.0, 0.5);
+//!
+//! let mut ball = Body::new(Vec2::new(40.0, 10.0), Vec2::new(3.0, 0.0));
+//! for _ in 0..200 {
+//!     ball.integrate(gravity, 1.0);
+//!     if ball.bounce(bounds, 0.8).bottom {
+//!         ball.velocity.x *= 0.98; // floor friction
+//!     }
+//!     assert!(bounds.contains(ball.position));
+//! }
+//! let (x, y) = ball.dot();
+//! # let _ = (x, y);
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::grid::BrailleGrid;
+
+/// A 2D vector: a position, velocity, or acceleration in dot units.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec2 {
+    /// Horizontal component, growing to the right
+    pub x: f32,
+    /// Vertical component, growing downward
+    pub y: f32,
+}
+
+impl Vec2 {
+    /// The zero vector.
+    pub const ZERO: Self = Self { x: 0.0, y: 0.0 };
+
+    /// A vector from its components.
+    #[must_use]
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// The unit vector at `angle` radians clockwise from the positive x
+    /// axis (clockwise on screen, since y grows downward).
+    #[must_use]
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, sin)
+    }
+
+    /// The dot product.
+    #[must_use]
+    pub fn dot(self, other: Self) -> f32 {
+        self.x.mul_add(other.x, self.y * other.y)
+    }
+
+    /// The length.
+    #[must_use]
+    pub fn length(self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// This vector scaled to length 1, or zero if it has no length.
+    #[must_use]
+    pub fn normalized(self) -> Self {
+        let length = self.length();
+        if length > 0.0 {
+            self / length
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /// This vector shortened to at most `max` long, keeping its direction.
+    #[must_use]
+    pub fn clamp_length(self, max: f32) -> Self {
+        let length = self.length();
+        if length > max && length > 0.0 {
+            self * (max.max(0.0) / length)
+        } else {
+            self
+        }
+    }
+
+    /// The point `t` of the way from this one to `other`.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl MulAssign<f32> for Vec2 {
+    fn mul_assign(&mut self, factor: f32) {
+        *self = *self * factor;
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Self;
+
+    fn div(self, divisor: f32) -> Self {
+        Self::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// An axis-aligned rectangle of dot positions a [`Body`] stays inside,
+/// edges included.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    /// Top-left corner
+    pub min: Vec2,
+    /// Bottom-right corner
+    pub max: Vec2,
+}
+
+impl Bounds {
+    /// The rectangle between two corners, in either order.
+    #[must_use]
+    pub fn new(a: Vec2, b: Vec2) -> Self {
+        Self {
+            min: Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /// Every dot of `grid`, from (0, 0) to the last dot of the last cell.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn of_grid(grid: &BrailleGrid) -> Self {
+        Self::new(
+            Vec2::ZERO,
+            Vec2::new(
+                grid.dot_width().saturating_sub(1) as f32,
+                grid.dot_height().saturating_sub(1) as f32,
+            ),
+        )
+    }
+
+    /// These bounds with `margin` taken off every side, for keeping a
+    /// whole shape of that radius on screen rather than just its center.
+    /// Shrinking past the middle leaves a single point.
+    #[must_use]
+    pub fn shrink(self, margin: f32) -> Self {
+        let center = self.min.lerp(self.max, 0.5);
+        let shrink = |min: f32, max: f32, center: f32| {
+            ((min + margin).min(center), (max - margin).max(center))
+        };
+        let (min_x, max_x) = shrink(self.min.x, self.max.x, center.x);
+        let (min_y, max_y) = shrink(self.min.y, self.max.y, center.y);
+        Self {
+            min: Vec2::new(min_x, min_y),
+            max: Vec2::new(max_x, max_y),
+        }
+    }
+
+    /// Whether `point` is inside, edges included.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    /// The nearest point inside to `point`.
+    #[must_use]
+    pub fn clamp(&self, point: Vec2) -> Vec2 {
+        Vec2::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+        )
+    }
+}
+
+/// The edges a [`Body::bounce`] hit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Contact {
+    /// Hit the left edge
+    pub left: bool,
+    /// Hit the right edge
+    pub right: bool,
+    /// Hit the top edge
+    pub top: bool,
+    /// Hit the bottom edge
+    pub bottom: bool,
+}
+
+impl Contact {
+    /// Whether any edge was hit.
+    #[must_use]
+    pub const fn any(&self) -> bool {
+        self.left || self.right || self.top || self.bottom
+    }
+}
+
+/// A point with a velocity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Body {
+    /// Position in dots
+    pub position: Vec2,
+    /// Velocity in dots per time step unit
+    pub velocity: Vec2,
+}
+
+impl Body {
+    /// A body at `position` moving at `velocity`.
+    #[must_use]
+    pub const fn new(position: Vec2, velocity: Vec2) -> Self {
+        Self { position, velocity }
+    }
+
+    /// Advances the body by `dt` under `acceleration`: the velocity
+    /// changes first, then the position moves by the new velocity.
+    pub fn integrate(&mut self, acceleration: Vec2, dt: f32) {
+        self.velocity += acceleration * dt;
+        self.position += self.velocity * dt;
+    }
+
+    /// Scales the velocity down by `rate` per time unit, so that over one
+    /// unit a `rate` of 0.5 halves it whether that unit took one step or
+    /// ten. A `rate` of 0 leaves it alone and 1 stops the body.
+    pub fn damp(&mut self, rate: f32, dt: f32) {
+        let rate = rate.clamp(0.0, 1.0);
+        self.velocity *= (1.0 - rate).powf(dt.max(0.0));
+    }
+
+    /// Keeps the body inside `bounds`. A body past an edge is mirrored back
+    /// inside it, and its velocity across that edge reversed and scaled by
+    /// `restitution`: 1.0 for a perfect bounce, 0.0 to stop dead against
+    /// the edge. Returns the edges hit, which are only the ones the body
+    /// was moving toward.
+    pub fn bounce(&mut self, bounds: Bounds, restitution: f32) -> Contact {
+        let restitution = restitution.max(0.0);
+        let (left, right) = bounce_axis(
+            &mut self.position.x,
+            &mut self.velocity.x,
+            bounds.min.x,
+            bounds.max.x,
+            restitution,
+        );
+        let (top, bottom) = bounce_axis(
+            &mut self.position.y,
+            &mut self.velocity.y,
+            bounds.min.y,
+            bounds.max.y,
+            restitution,
+        );
+        Contact {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+
+    /// The nearest dot to the body's position, for drawing it or testing
+    /// it with [`collide`](crate::collide).
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn dot(&self) -> (i32, i32) {
+        (
+            self.position.x.round() as i32,
+            self.position.y.round() as i32,
+        )
+    }
+}
+
+/// The acceleration of a damped spring pulling a body toward `target`.
+///
+/// That's `stiffness` times the distance to go, less `damping` times the
+/// body's `velocity`. Critical damping, which settles fastest without
+/// overshooting, is `2.0 * stiffness.sqrt()`.
+#[must_use]
+pub fn spring(position: Vec2, velocity: Vec2, target: Vec2, stiffness: f32, damping: f32) -> Vec2 {
+    (target - position) * stiffness - velocity * damping
+}
+
+/// Bounces one coordinate off `min` and `max`, returning whether it hit
+/// each.
+fn bounce_axis(
+    position: &mut f32,
+    velocity: &mut f32,
+    min: f32,
+    max: f32,
+    restitution: f32,
+) -> (bool, bool) {
+    let mut hit = (false, false);
+    if *position < min {
+        *position = (min - *position).mul_add(restitution, min);
+        if *velocity < 0.0 {
+            *velocity = -*velocity * restitution;
+            hit.0 = true;
+        }
+    } else if *position > max {
+        *position = (*position - max).mul_add(-restitution, max);
+        if *velocity > 0.0 {
+            *velocity = -*velocity * restitution;
+            hit.1 = true;
+        }
+    }
+    // A fast body can be mirrored past the far edge
+    *position = position.clamp(min, max);
+    hit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_math() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalized(), Vec2::new(0.6, 0.8));
+        assert_eq!(Vec2::ZERO.normalized(), Vec2::ZERO);
+        assert_eq!(v.clamp_length(2.5), Vec2::new(1.5, 2.0));
+        assert_eq!(v.clamp_length(10.0), v);
+        assert_eq!(v.dot(Vec2::new(1.0, -1.0)), -1.0);
+        assert_eq!(v + v - v * 2.0, Vec2::ZERO);
+        assert_eq!(-v / 2.0, Vec2::new(-1.5, -2.0));
+        assert_eq!(Vec2::ZERO.lerp(v, 0.5), Vec2::new(1.5, 2.0));
+    }
+
+    #[test]
+    fn test_integrate_updates_velocity_first() {
+        let mut body = Body::new(Vec2::ZERO, Vec2::new(1.0, 0.0));
+        body.integrate(Vec2::new(0.0, 2.0), 0.5);
+        assert_eq!(body.velocity, Vec2::new(1.0, 1.0));
+        assert_eq!(body.position, Vec2::new(0.5, 0.5));
+    }
+
+    #[test]
+    fn test_bounce_mirrors_and_reverses() {
+        let bounds = Bounds::new(Vec2::ZERO, Vec2::new(10.0, 10.0));
+        let mut body = Body::new(Vec2::new(12.0, 5.0), Vec2::new(4.0, 1.0));
+        let contact = body.bounce(bounds, 0.5);
+        assert!(contact.right && !contact.left && !contact.top && !contact.bottom);
+        assert_eq!(body.position, Vec2::new(9.0, 5.0));
+        assert_eq!(body.velocity, Vec2::new(-2.0, 1.0));
+
+        // Already heading back in: moved inside but not bounced again
+        let mut body = Body::new(Vec2::new(-1.0, 5.0), Vec2::new(3.0, 0.0));
+        assert!(!body.bounce(bounds, 1.0).any());
+        assert_eq!(body.position, Vec2::new(1.0, 5.0));
+        assert_eq!(body.velocity, Vec2::new(3.0, 0.0));
+
+        // Far past one edge never lands past the other
+        let mut body = Body::new(Vec2::new(5.0, 50.0), Vec2::new(0.0, 40.0));
+        assert!(body.bounce(bounds, 1.0).bottom);
+        assert_eq!(body.position, Vec2::new(5.0, 0.0));
+    }
+
+    #[test]
+    fn test_grid_bounds() {
+        let grid = BrailleGrid::new(10, 5).unwrap();
+        let bounds = Bounds::of_grid(&grid);
+        assert_eq!(bounds.max, Vec2::new(19.0, 19.0));
+        assert_eq!(
+            bounds.shrink(2.0),
+            Bounds::new(Vec2::new(2.0, 2.0), Vec2::new(17.0, 17.0))
+        );
+        assert_eq!(bounds.shrink(100.0).min, Vec2::new(9.5, 9.5));
+        assert_eq!(bounds.shrink(100.0).max, Vec2::new(9.5, 9.5));
+        assert_eq!(bounds.clamp(Vec2::new(-5.0, 30.0)), Vec2::new(0.0, 19.0));
+    }
+
+    #[test]
+    fn test_damping_is_step_independent() {
+        let mut once = Body::new(Vec2::ZERO, Vec2::new(8.0, 0.0));
+        once.damp(0.5, 1.0);
+        let mut twice = Body::new(Vec2::ZERO, Vec2::new(8.0, 0.0));
+        twice.damp(0.5, 0.5);
+        twice.damp(0.5, 0.5);
+        assert_eq!(once.velocity, Vec2::new(4.0, 0.0));
+        assert!((twice.velocity.x - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_spring_settles_on_target() {
+        let target = Vec2::new(10.0, -4.0);
+        let mut body = Body::default();
+        let stiffness = 0.2;
+        for _ in 0..200 {
+            let pull = spring(
+                body.position,
+                body.velocity,
+                target,
+                stiffness,
+                2.0 * stiffness.sqrt(),
+            );
+            body.integrate(pull, 1.0);
+        }
+        assert_eq!(body.dot(), (10, -4));
+        assert!(body.velocity.length() < 0.01);
+    }
+}
@@ -0,0 +1,329 @@
+//! Bar charts and histograms.
+//!
+//! A [`BarChart`] draws one bar per labeled value, with the labels under
+//! the bars. A [`Histogram`] counts raw samples into equal-width bins and
+//! draws the counts as touching bars over a numeric x axis.
+//!
+//! Bars grow from zero, so negative values hang below a zero baseline. If
+//! zero is outside the y range, bars grow from the nearer edge.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::plot::{BarChart, Histogram};
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(60, 12)?;
+//! BarChart::new()
+//!     .bar("mon", 4.0)
+//!     .bar("tue", 7.5)
+//!     .bar("wed", 3.0)
+//!     .render(&mut grid, 0, 0, 30, 12)?;
+//!
+//! let latencies = [12.0, 15.0, 14.0, 30.0, 13.0, 16.0, 14.5, 18.0];
+//! Histogram::new(latencies)
+//!     .bins(4)
+//!     .title("latency (ms)")
+//!     .render(&mut grid, 30, 0, 30, 12)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::ops::Range;
+
+use super::{colored, data_bounds, render_chart, slot, Scale, Ticks, XAxis};
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+use crate::primitives::Canvas;
+
+/// One bar per labeled value.
+#[derive(Debug, Clone, Default)]
+pub struct BarChart {
+    labels: Vec<String>,
+    values: Vec<f64>,
+    color: Option<Color>,
+    title: Option<String>,
+    y_range: Option<Range<f64>>,
+}
+
+impl BarChart {
+    /// An empty chart.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a bar to the right of the existing ones.
+    #[must_use]
+    pub fn bar(mut self, label: impl Into<String>, value: f64) -> Self {
+        self.labels.push(label.into());
+        self.values.push(value);
+        self
+    }
+
+    /// Adds a bar for each `(label, value)` pair.
+    #[must_use]
+    pub fn bars<L: Into<String>>(self, bars: impl IntoIterator<Item = (L, f64)>) -> Self {
+        bars.into_iter()
+            .fold(self, |chart, (label, value)| chart.bar(label, value))
+    }
+
+    /// Draws the bars in `color`.
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Title shown on the top row.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Fixes the y axis to `range` instead of fitting the data.
+    #[must_use]
+    pub const fn y_range(mut self, range: Range<f64>) -> Self {
+        self.y_range = Some(range);
+        self
+    }
+
+    /// Draws the chart into the `width × height` cell region whose top-left
+    /// cell is `(x, y)`. Labels wider than their bar are truncated, and
+    /// dropped if they would still collide.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LineChart::render`](super::LineChart::render).
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        // Keep zero in view so bar lengths compare honestly
+        let y_scale = Scale::or_fit(
+            self.y_range.as_ref(),
+            self.values.iter().copied().chain([0.0]),
+        );
+        render_chart(
+            grid,
+            (x, y, width, height),
+            self.title.as_deref(),
+            &XAxis::Categories(&self.labels),
+            &y_scale,
+            |canvas| {
+                colored(canvas, self.color, |canvas| {
+                    draw_bars(canvas, &self.values, true)
+                })
+            },
+        )
+    }
+}
+
+/// Samples counted into equal-width bins.
+#[derive(Debug, Clone, Default)]
+pub struct Histogram {
+    samples: Vec<f64>,
+    bins: Option<usize>,
+    range: Option<Range<f64>>,
+    color: Option<Color>,
+    title: Option<String>,
+}
+
+impl Histogram {
+    /// A histogram of `samples`. NaN and infinite samples are ignored.
+    #[must_use]
+    pub fn new(samples: impl IntoIterator<Item = f64>) -> Self {
+        Self {
+            samples: samples.into_iter().filter(|s| s.is_finite()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Number of bins (at least 1). Defaults to Sturges' rule,
+    /// `⌈log₂ n⌉ + 1` for `n` samples.
+    #[must_use]
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = Some(bins.max(1));
+        self
+    }
+
+    /// Bins `range` instead of the samples' own span. Samples outside it
+    /// aren't counted.
+    #[must_use]
+    pub const fn range(mut self, range: Range<f64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Draws the bars in `color`.
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Title shown on the top row.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// The binned range and the count in each bin. A sample on the upper
+    /// edge counts toward the last bin.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn counts(&self) -> (Range<f64>, Vec<usize>) {
+        // Samples span the bins exactly; only degenerate data is padded
+        let range = self.range.clone().unwrap_or_else(|| {
+            let (min, max) = data_bounds(self.samples.iter().copied());
+            min..max
+        });
+        let bins = self
+            .bins
+            .unwrap_or_else(|| (self.samples.len().max(1) as f64).log2().ceil() as usize + 1);
+
+        let mut counts = vec![0; bins];
+        let span = range.end - range.start;
+        for &sample in &self.samples {
+            let t = (sample - range.start) / span;
+            if !(0.0..=1.0).contains(&t) {
+                continue;
+            }
+            let bin = ((t * bins as f64) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        (range, counts)
+    }
+
+    /// Draws the histogram into the `width × height` cell region whose
+    /// top-left cell is `(x, y)`.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LineChart::render`](super::LineChart::render).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        let (range, counts) = self.counts();
+        let counts: Vec<f64> = counts.iter().map(|&c| c as f64).collect();
+        let x_ticks = Ticks::new(&Scale::Fixed(range), width / 8);
+        let y_scale = Scale::fit(counts.iter().copied().chain([0.0]));
+
+        render_chart(
+            grid,
+            (x, y, width, height),
+            self.title.as_deref(),
+            &XAxis::Ticks(&x_ticks),
+            &y_scale,
+            |canvas| {
+                colored(canvas, self.color, |canvas| {
+                    draw_bars(canvas, &counts, false)
+                })
+            },
+        )
+    }
+}
+
+/// Fills one bar per value across the canvas, from zero (or the nearer
+/// edge of the y range) to the value. With `gaps`, bars at least three dots
+/// wide leave their last column empty to separate them.
+fn draw_bars(canvas: &mut Canvas<'_>, values: &[f64], gaps: bool) -> Result<(), DotmaxError> {
+    let y_range = canvas.y_range();
+    let base = 0.0_f64.clamp(
+        y_range.start.min(y_range.end),
+        y_range.start.max(y_range.end),
+    );
+    let dot_width = canvas.grid().dot_width();
+    for (i, &value) in values.iter().enumerate() {
+        if !value.is_finite() {
+            continue;
+        }
+        let (left, mut right) = slot(i, values.len(), dot_width);
+        if gaps && right - left >= 3 {
+            right -= 1;
+        }
+        // Skip the y axis column
+        for dot_x in left.max(1)..right {
+            let (world_x, _) = canvas.to_world(dot_x, 0);
+            canvas.line(world_x, base, world_x, value)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_heights_and_labels() {
+        let mut grid = BrailleGrid::new(24, 6).unwrap();
+        BarChart::new()
+            .bars([("a", 10.0), ("b", 5.0)])
+            .render(&mut grid, 0, 0, 24, 6)
+            .unwrap();
+
+        // Five plot rows (20 dots) under y labels "10 " / " 5 " / " 0 "
+        let plot_left = 3 * 2;
+        let height = |dot_x: usize| (0..20).filter(|&y| grid.is_dot_set(dot_x, y)).count();
+        let full = height(plot_left + 8);
+        let half = height(plot_left + 30);
+        assert_eq!(full, 20);
+        assert!((9..=11).contains(&half), "{half}");
+
+        // Centered under each half of the 21-cell plot area
+        assert_eq!(grid.get_char(8, 5), 'a');
+        assert_eq!(grid.get_char(18, 5), 'b');
+    }
+
+    #[test]
+    fn test_negative_bars_hang_below_zero() {
+        let mut grid = BrailleGrid::new(24, 6).unwrap();
+        BarChart::new()
+            .bar("up", 4.0)
+            .bar("down", -4.0)
+            .render(&mut grid, 0, 0, 24, 6)
+            .unwrap();
+
+        // The y axis runs -5..5, so zero is halfway down the 20 dot rows
+        let plot_left = 3 * 2;
+        assert!(grid.is_dot_set(plot_left + 8, 3));
+        assert!(!grid.is_dot_set(plot_left + 8, 16));
+        assert!(grid.is_dot_set(plot_left + 30, 16));
+        assert!(!grid.is_dot_set(plot_left + 30, 3));
+    }
+
+    #[test]
+    fn test_histogram_counts() {
+        let histogram = Histogram::new([0.0, 1.0, 1.5, 2.0, 3.9, 4.0, f64::NAN]).bins(4);
+        let (range, counts) = histogram.counts();
+        assert!((range.start - 0.0).abs() < 1e-12 && (range.end - 4.0).abs() < 1e-12);
+        assert_eq!(counts, vec![1, 2, 1, 2]);
+
+        // Sturges: 6 samples -> 4 bins
+        assert_eq!(
+            Histogram::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+                .counts()
+                .1
+                .len(),
+            4
+        );
+
+        let (_, counts) = Histogram::new([1.0, 5.0, 9.0])
+            .range(0.0..4.0)
+            .bins(2)
+            .counts();
+        assert_eq!(counts, vec![1, 0]);
+    }
+}
@@ -0,0 +1,349 @@
+//! Loading chart [`Series`] from CSV text or JSON values.
+//!
+//! Both loaders read their input as a table of rows and pick columns out of
+//! it with [`Column`], by header name or by position. The x column gives
+//! every series its x coordinates; without one, rows are numbered from 0
+//! as in [`Series::from_values`]. Each y column becomes one series, in the
+//! order asked for, or every numeric column other than x when none are
+//! named.
+//!
+//! Cells that aren't numbers (blanks, `NA`, `null`) become NaN, so a
+//! [`LineChart`](super::LineChart) leaves a gap there rather than the load
+//! failing on one bad reading.
+//!
+//! # CSV
+//!
+//! [`from_csv`] reads comma-separated rows with optional double-quoted
+//! fields (`""` inside quotes is a literal quote). The first row is taken
+//! as a header if any of its fields isn't a number. Blank lines and lines
+//! starting with `#` are skipped. Quoted fields can't span lines.
+//!
+//! # JSON
+//!
+//! [`from_json`], with the `json` feature, reads a `serde_json::Value`
+//! holding an array of rows, where each row is either:
+//!
+//! - a number, for a single column: `[3, 1, 4]`
+//! - an array, with columns by position: `[[0, 3], [1, 1]]`
+//! - an object, with columns by key: `[{"t": 0, "cpu": 3}]`
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::plot::data::{self, Column};
+//! use dotmax::plot::LineChart;
+//! use dotmax::BrailleGrid;
+//!
+//! let csv = "time,cpu,mem\n0,12,40\n1,35,41\n2,80,44\n3,,45\n4,41,45\n";
+//! let series = data::from_csv(csv.as_bytes(), Some("time".into()), &[])?;
+//! assert_eq!(series.len(), 2); // cpu and mem
+//! assert!(series[0].points()[3].1.is_nan());
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! let chart = series.into_iter().fold(LineChart::new(), LineChart::series);
+//! chart.render(&mut grid, 0, 0, 40, 12)?;
+//!
+//! // Columns can also be picked by position
+//! let cpu = data::from_csv(csv.as_bytes(), Some(Column::Index(0)), &[Column::Index(1)])?;
+//! assert_eq!(cpu[0].points()[2], (2.0, 80.0));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::io::{BufRead, BufReader, Read};
+
+use super::Series;
+use crate::error::DotmaxError;
+
+/// A column of a CSV or JSON table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column<'a> {
+    /// The column with this header (CSV) or key (JSON objects)
+    Name(&'a str),
+    /// The column at this position, from 0
+    Index(usize),
+}
+
+impl<'a> From<&'a str> for Column<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl From<usize> for Column<'_> {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+/// Reads CSV from `reader` and returns one series per y column.
+///
+/// `x_col` gives the x coordinates, or row numbers are used without one.
+/// With no `y_cols`, every column other than `x_col` holding at least one
+/// number becomes a series.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::Terminal`] if reading fails, or
+/// [`DotmaxError::DataError`] for an unterminated quote, a column that
+/// doesn't exist, or a table with no numeric columns to plot.
+pub fn from_csv<'a>(
+    reader: impl Read,
+    x_col: Option<Column<'a>>,
+    y_cols: &[Column<'a>],
+) -> Result<Vec<Series>, DotmaxError> {
+    let mut headers = None;
+    let mut rows = Vec::new();
+    for (number, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let line = line.strip_prefix('\u{feff}').unwrap_or(&line);
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = split_record(line).ok_or_else(|| {
+            DotmaxError::DataError(format!("line {}: unterminated quote", number + 1))
+        })?;
+        if headers.is_none() && rows.is_empty() && fields.iter().any(|f| !is_number(f)) {
+            headers = Some(fields);
+        } else {
+            rows.push(fields.iter().map(|field| parse_number(field)).collect());
+        }
+    }
+    Table {
+        headers: headers.unwrap_or_default(),
+        rows,
+    }
+    .series(x_col, y_cols)
+}
+
+/// Reads the rows of a JSON array and returns one series per y column, as
+/// [`from_csv`] does. Object rows name their columns by key; array and
+/// number rows only by position.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::DataError`] if `value` isn't an array of rows, a
+/// column doesn't exist, or there are no numeric columns to plot.
+#[cfg(feature = "json")]
+pub fn from_json<'a>(
+    value: &serde_json::Value,
+    x_col: Option<Column<'a>>,
+    y_cols: &[Column<'a>],
+) -> Result<Vec<Series>, DotmaxError> {
+    use serde_json::Value;
+
+    let items = value
+        .as_array()
+        .ok_or_else(|| DotmaxError::DataError("expected a JSON array of rows".to_string()))?;
+    let mut headers: Vec<String> = Vec::new();
+    let mut rows = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let row = match item {
+            Value::Array(cells) => cells.iter().map(json_number).collect(),
+            Value::Object(fields) => {
+                let mut row = vec![f64::NAN; headers.len()];
+                for (key, cell) in fields {
+                    let column = headers.iter().position(|h| h == key).unwrap_or_else(|| {
+                        headers.push(key.clone());
+                        row.push(f64::NAN);
+                        headers.len() - 1
+                    });
+                    row[column] = json_number(cell);
+                }
+                row
+            }
+            Value::Number(_) | Value::String(_) | Value::Null => vec![json_number(item)],
+            Value::Bool(_) => {
+                return Err(DotmaxError::DataError(format!(
+                    "row {index}: expected a number, array, or object"
+                )))
+            }
+        };
+        rows.push(row);
+    }
+    Table { headers, rows }.series(x_col, y_cols)
+}
+
+#[cfg(feature = "json")]
+fn json_number(value: &serde_json::Value) -> f64 {
+    match value {
+        serde_json::Value::Number(number) => number.as_f64().unwrap_or(f64::NAN),
+        serde_json::Value::String(text) => parse_number(text),
+        _ => f64::NAN,
+    }
+}
+
+/// Rows of numbers, with names for as many columns as are known.
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<f64>>,
+}
+
+impl Table {
+    fn width(&self) -> usize {
+        self.rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.headers.len()])
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn resolve(&self, column: Column<'_>) -> Result<usize, DotmaxError> {
+        match column {
+            Column::Name(name) => self.headers.iter().position(|h| h == name).ok_or_else(|| {
+                let known = if self.headers.is_empty() {
+                    "the data has no header".to_string()
+                } else {
+                    format!("columns are {}", self.headers.join(", "))
+                };
+                DotmaxError::DataError(format!("no column named {name:?}; {known}"))
+            }),
+            Column::Index(index) if index < self.width() => Ok(index),
+            Column::Index(index) => Err(DotmaxError::DataError(format!(
+                "no column {index}; the data has {} columns",
+                self.width()
+            ))),
+        }
+    }
+
+    fn cell(&self, row: usize, column: usize) -> f64 {
+        self.rows[row].get(column).copied().unwrap_or(f64::NAN)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn series(
+        &self,
+        x_col: Option<Column<'_>>,
+        y_cols: &[Column<'_>],
+    ) -> Result<Vec<Series>, DotmaxError> {
+        let x = x_col.map(|column| self.resolve(column)).transpose()?;
+        let ys = if y_cols.is_empty() {
+            let numeric = (0..self.width())
+                .filter(|&column| Some(column) != x)
+                .filter(|&column| {
+                    (0..self.rows.len()).any(|row| self.cell(row, column).is_finite())
+                })
+                .collect::<Vec<_>>();
+            if numeric.is_empty() {
+                return Err(DotmaxError::DataError(
+                    "no numeric columns to plot".to_string(),
+                ));
+            }
+            numeric
+        } else {
+            y_cols
+                .iter()
+                .map(|&column| self.resolve(column))
+                .collect::<Result<_, _>>()?
+        };
+        Ok(ys
+            .into_iter()
+            .map(|y| {
+                Series::new((0..self.rows.len()).map(|row| {
+                    let x = x.map_or(row as f64, |x| self.cell(row, x));
+                    (x, self.cell(row, y))
+                }))
+            })
+            .collect())
+    }
+}
+
+/// Splits one CSV line into trimmed fields, or `None` if a quote is left
+/// open.
+fn split_record(line: &str) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (',', false) => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    fields.push(field.trim().to_string());
+    Some(fields)
+}
+
+fn is_number(field: &str) -> bool {
+    field.is_empty() || field.parse::<f64>().is_ok()
+}
+
+fn parse_number(field: &str) -> f64 {
+    field.trim().parse().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ys(series: &Series) -> Vec<f64> {
+        series.points().iter().map(|&(_, y)| y).collect()
+    }
+
+    #[test]
+    fn test_csv_columns_and_headers() {
+        let csv = "\u{feff}# sensor log\r\nt, \"temp, °C\",note\n0,20.5,ok\n\n1,,\"said \"\"hi\"\"\"\n2,22,ok\n";
+        let series = from_csv(csv.as_bytes(), Some("t".into()), &[]).unwrap();
+        // The note column has no numbers, so only temperature is plotted
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].points()[0], (0.0, 20.5));
+        assert!(series[0].points()[1].1.is_nan());
+        assert_eq!(series[0].points()[2], (2.0, 22.0));
+
+        let by_name = from_csv(csv.as_bytes(), None, &["temp, °C".into()]).unwrap();
+        assert_eq!(by_name[0].points()[2], (2.0, 22.0));
+
+        // Without a header every row is data, numbered from 0 on x
+        let bare = from_csv(&b"3,1\n4,1\n5,9\n"[..], None, &[0.into(), 1.into()]).unwrap();
+        assert_eq!(bare[0].points(), [(0.0, 3.0), (1.0, 4.0), (2.0, 5.0)]);
+        assert_eq!(ys(&bare[1]), [1.0, 1.0, 9.0]);
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        let csv = "a,b\n1,2\n";
+        let missing = from_csv(csv.as_bytes(), Some("c".into()), &[]).unwrap_err();
+        assert!(missing.to_string().contains("columns are a, b"));
+        assert!(from_csv(csv.as_bytes(), None, &[Column::Index(2)]).is_err());
+        assert!(from_csv(&b"1,2\n"[..], Some("a".into()), &[]).is_err());
+        assert!(from_csv(&b"name\nx\n"[..], None, &[]).is_err());
+        let open = from_csv(&b"a,b\n1,\"2\n"[..], None, &[]).unwrap_err();
+        assert!(open.to_string().contains("line 2"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_row_shapes() {
+        use serde_json::json;
+
+        let values = from_json(&json!([3, 1, "4", null]), None, &[]).unwrap();
+        assert_eq!(values[0].points()[2], (2.0, 4.0));
+        assert!(values[0].points()[3].1.is_nan());
+
+        let pairs = from_json(&json!([[0, 3], [1, 1]]), Some(0.into()), &[]).unwrap();
+        assert_eq!(pairs[0].points(), [(0.0, 3.0), (1.0, 1.0)]);
+
+        // Keys missing from a row read as NaN
+        let objects = json!([{"t": 0, "cpu": 12}, {"t": 1, "mem": 40}, {"t": 2, "cpu": 30}]);
+        let series = from_json(&objects, Some("t".into()), &["cpu".into(), "mem".into()]).unwrap();
+        assert_eq!(series[0].points()[2], (2.0, 30.0));
+        assert!(series[0].points()[1].1.is_nan());
+        assert_eq!(series[1].points()[1], (1.0, 40.0));
+
+        assert!(from_json(&json!({"t": [0, 1]}), None, &[]).is_err());
+        assert!(from_json(&json!([true]), None, &[]).is_err());
+    }
+}
@@ -0,0 +1,396 @@
+//! Charts drawn onto a [`BrailleGrid`]: line, scatter, bar, and histogram.
+//!
+//! Like the [widgets](crate::widgets), charts don't own a grid. Each one
+//! renders into a `width × height` cell region of a grid you already have,
+//! so a dashboard is several charts side by side on one frame. The region
+//! is cleared before drawing.
+//!
+//! - [`LineChart`]: one or more [`Series`] joined by lines
+//! - [`ScatterPlot`]: [`Series`] drawn as dots or [markers](crate::primitives::Marker)
+//! - [`BarChart`]: one labeled bar per value
+//! - [`Histogram`]: samples counted into equal-width bins
+//!
+//! [`data`] loads series from CSV files and JSON arrays, and [`stream`]
+//! charts numbers live as they arrive.
+//!
+//! # Layout
+//!
+//! An optional title takes the top row and x tick labels the bottom row.
+//! Y tick labels are right-aligned in a column on the left, and the plot
+//! area fills the rest, with its axes drawn in braille along its left and
+//! bottom edges. Labels are written in the grid's character layer.
+//!
+//! When there isn't room for every tick label, they are placed in order,
+//! y labels from the top down and x labels from left to right, and a label
+//! that would overlap one already placed is dropped. The same data and
+//! size therefore always keep the same labels.
+//!
+//! # Scaling
+//!
+//! Axis ranges fit the data automatically, widened to the nearest "nice"
+//! tick step (1, 2, or 5 times a power of ten) so labels read `0 5 10 15`
+//! rather than `0.3 4.9 9.6`. Set `x_range` or `y_range` to fix an axis
+//! instead; data outside a fixed range is clipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::plot::{LineChart, Series};
+//! use dotmax::BrailleGrid;
+//!
+//! let cpu = [12.0, 18.0, 35.0, 80.0, 64.0, 41.0, 22.0];
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! LineChart::new()
+//!     .title("CPU %")
+//!     .series(Series::from_values(&cpu))
+//!     .y_range(0.0..100.0)
+//!     .render(&mut grid, 0, 0, 40, 12)?;
+//!
+//! assert_eq!(grid.get_char(0, 0), 'C');
+//! assert_eq!(grid.get_char(0, 1), '1'); // "100" at the top of the y axis
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+pub mod bar;
+pub mod data;
+pub mod stream;
+pub mod xy;
+
+pub use bar::{BarChart, Histogram};
+pub use xy::{LineChart, ScatterPlot, Series};
+
+use std::ops::Range;
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+use crate::primitives::Canvas;
+
+/// How an axis picks its range.
+#[derive(Debug, Clone)]
+enum Scale {
+    /// Fit `min..max` of the data, widened to whole tick steps
+    Auto(f64, f64),
+    /// Exactly this range, with ticks at the nice steps inside it
+    Fixed(Range<f64>),
+}
+
+impl Scale {
+    /// An automatic scale over the finite values in `values`.
+    fn fit(values: impl IntoIterator<Item = f64>) -> Self {
+        let (min, max) = data_bounds(values);
+        Self::Auto(min, max)
+    }
+
+    /// A fixed scale if one was set, otherwise one fitted to `values`.
+    fn or_fit(fixed: Option<&Range<f64>>, values: impl IntoIterator<Item = f64>) -> Self {
+        fixed.map_or_else(|| Self::fit(values), |range| Self::Fixed(range.clone()))
+    }
+}
+
+/// The smallest and largest finite values in `values`. Empty data gets
+/// `(0, 1)`, and a single value is padded so the bounds aren't equal.
+fn data_bounds(values: impl IntoIterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+    if min > max {
+        return (0.0, 1.0);
+    }
+    if max - min == 0.0 {
+        let pad = if min == 0.0 { 1.0 } else { min.abs() / 10.0 };
+        return (min - pad, max + pad);
+    }
+    (min, max)
+}
+
+/// An axis range with its tick values.
+#[derive(Debug, Clone)]
+struct Ticks {
+    range: Range<f64>,
+    values: Vec<f64>,
+    precision: usize,
+}
+
+impl Ticks {
+    /// Ticks for `scale` with roughly `intervals` steps across it.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn new(scale: &Scale, intervals: usize) -> Self {
+        let (lo, hi) = match scale {
+            Scale::Auto(min, max) => (*min, *max),
+            Scale::Fixed(range) => (range.start.min(range.end), range.start.max(range.end)),
+        };
+        let step = nice_step(hi - lo, intervals.max(1));
+        if !step.is_finite() || step <= 0.0 {
+            // Non-finite fixed ranges are reported by `Canvas::new`
+            let range = match scale {
+                Scale::Auto(min, max) => *min..*max,
+                Scale::Fixed(range) => range.clone(),
+            };
+            return Self {
+                range,
+                values: Vec::new(),
+                precision: 0,
+            };
+        }
+
+        let (first, last) = match scale {
+            Scale::Auto(..) => ((lo / step).floor(), (hi / step).ceil()),
+            // Small tolerance so a range ending exactly on a step keeps that tick
+            Scale::Fixed(_) => ((lo / step - 1e-9).ceil(), (hi / step + 1e-9).floor()),
+        };
+        let range = match scale {
+            Scale::Auto(..) => first * step..last * step,
+            Scale::Fixed(range) => range.clone(),
+        };
+        let values = (first as i64..=last as i64)
+            .map(|k| k as f64 * step)
+            // Avoid "-0" labels
+            .map(|v| if v.abs() < step * 1e-9 { 0.0 } else { v })
+            .collect();
+        let precision = if step >= 1.0 {
+            0
+        } else {
+            (-step.log10().floor()) as usize
+        };
+        Self {
+            range,
+            values,
+            precision,
+        }
+    }
+
+    fn label(&self, value: f64) -> String {
+        format!("{value:.*}", self.precision)
+    }
+}
+
+/// The smallest step of 1, 2, or 5 times a power of ten that splits `span`
+/// into no more than `intervals` pieces.
+fn nice_step(span: f64, intervals: usize) -> f64 {
+    let raw = span / intervals as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let normalized = raw / magnitude;
+    let nice = if normalized <= 1.0 {
+        1.0
+    } else if normalized <= 2.0 {
+        2.0
+    } else if normalized <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// What goes under the x axis.
+enum XAxis<'a> {
+    /// Numeric ticks
+    Ticks(&'a Ticks),
+    /// One label per category, centered under its slot; the canvas x range
+    /// is `0..labels.len()`
+    Categories(&'a [String]),
+}
+
+/// Draws the title, axes, and tick labels of a chart into the `width ×
+/// height` region at `(x, y)`, and calls `draw` with a canvas over the
+/// plot area.
+///
+/// The chart is assembled in a scratch grid and pasted in, so the region is
+/// cleared and anything outside `grid` is clipped.
+fn render_chart(
+    grid: &mut BrailleGrid,
+    (x, y, width, height): (usize, usize, usize, usize),
+    title: Option<&str>,
+    x_axis: &XAxis<'_>,
+    y_scale: &Scale,
+    draw: impl FnOnce(&mut Canvas<'_>) -> Result<(), DotmaxError>,
+) -> Result<(), DotmaxError> {
+    let title_rows = usize::from(title.is_some());
+    let plot_height = height.saturating_sub(title_rows + 1);
+    if width == 0 || plot_height == 0 {
+        return Err(DotmaxError::InvalidDimensions { width, height });
+    }
+
+    // About one y label every other row
+    let y_ticks = Ticks::new(y_scale, plot_height / 2);
+    let y_labels: Vec<String> = y_ticks.values.iter().map(|&v| y_ticks.label(v)).collect();
+    let label_width = y_labels
+        .iter()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    let plot_left = label_width + 1;
+    let plot_width = width.saturating_sub(plot_left);
+    if plot_width < 2 {
+        return Err(DotmaxError::InvalidDimensions { width, height });
+    }
+
+    let mut chart = BrailleGrid::new(width, height)?;
+    let mut plot = BrailleGrid::new(plot_width, plot_height)?;
+    if let Some(title) = title {
+        put_text(&mut chart, 0, 0, title);
+    }
+
+    let x_range = match x_axis {
+        XAxis::Ticks(ticks) => ticks.range.clone(),
+        XAxis::Categories(labels) => 0.0..labels.len().max(1) as f64,
+    };
+    let mut canvas = Canvas::new(&mut plot, x_range.clone(), y_ticks.range.clone())?;
+
+    // Y labels, skipping any that would share a row with the one above
+    let mut last_row = None;
+    for (value, label) in y_ticks.values.iter().zip(&y_labels).rev() {
+        let Some((_, dot_y)) = canvas.to_dot(x_range.start, *value) else {
+            continue;
+        };
+        let row = title_rows + dot_y / 4;
+        if last_row == Some(row) {
+            continue;
+        }
+        last_row = Some(row);
+        put_text(&mut chart, label_width - label.chars().count(), row, label);
+        let _ = canvas.grid_mut().set_dot(1, dot_y);
+    }
+
+    // X labels, skipping any that would run into the previous one
+    let bottom = height - 1;
+    let mut next_free = 0;
+    let mut put_centered = |chart: &mut BrailleGrid, center: usize, label: &str| {
+        let len = label.chars().count();
+        let start = (center + plot_left)
+            .saturating_sub(len / 2)
+            .min(width.saturating_sub(len));
+        if start >= next_free {
+            put_text(chart, start, bottom, label);
+            next_free = start + len + 1;
+        }
+    };
+    match x_axis {
+        XAxis::Ticks(ticks) => {
+            for &value in &ticks.values {
+                let Some((dot_x, dot_y)) = canvas.to_dot(value, y_ticks.range.start) else {
+                    continue;
+                };
+                put_centered(&mut chart, dot_x / 2, &ticks.label(value));
+                let _ = canvas.grid_mut().set_dot(dot_x, dot_y.saturating_sub(1));
+            }
+        }
+        XAxis::Categories(labels) => {
+            let dot_width = plot_width * 2;
+            for (i, label) in labels.iter().enumerate() {
+                let (left, right) = slot(i, labels.len(), dot_width);
+                let cells = ((right - left) / 2).max(1);
+                let label: String = label.chars().take(cells).collect();
+                put_centered(&mut chart, (left + right) / 4, &label);
+            }
+        }
+    }
+
+    // Axes along the left and bottom edges
+    let (dot_width, dot_height) = (plot_width * 2, plot_height * 4);
+    for dot_y in 0..dot_height {
+        let _ = canvas.grid_mut().set_dot(0, dot_y);
+    }
+    for dot_x in 0..dot_width {
+        let _ = canvas.grid_mut().set_dot(dot_x, dot_height - 1);
+    }
+
+    draw(&mut canvas)?;
+
+    chart.paste(&plot, plot_left, title_rows);
+    grid.paste(&chart, x, y);
+    Ok(())
+}
+
+/// Dot columns `left..right` of slot `index` when `count` slots share
+/// `dot_width` columns.
+const fn slot(index: usize, count: usize, dot_width: usize) -> (usize, usize) {
+    (index * dot_width / count, (index + 1) * dot_width / count)
+}
+
+/// Runs `draw`, then colors every cell whose dots it changed.
+fn colored(
+    canvas: &mut Canvas<'_>,
+    color: Option<Color>,
+    draw: impl FnOnce(&mut Canvas<'_>) -> Result<(), DotmaxError>,
+) -> Result<(), DotmaxError> {
+    let Some(color) = color else {
+        return draw(canvas);
+    };
+    let before = canvas.grid().get_raw_patterns().to_vec();
+    draw(canvas)?;
+    let grid = canvas.grid_mut();
+    let width = grid.width();
+    let changed: Vec<usize> = grid
+        .get_raw_patterns()
+        .iter()
+        .zip(&before)
+        .enumerate()
+        .filter(|(_, (now, was))| now != was)
+        .map(|(index, _)| index)
+        .collect();
+    for index in changed {
+        // In bounds by construction
+        let _ = grid.set_cell_color(index % width, index / width, color);
+    }
+    Ok(())
+}
+
+/// Write `text` into the character layer starting at a cell, clipping at the
+/// grid edges.
+fn put_text(grid: &mut BrailleGrid, x: usize, y: usize, text: &str) {
+    for (offset, ch) in text.chars().enumerate() {
+        // Out-of-bounds cells are clipped
+        let _ = grid.set_char(x + offset, y, ch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_step() {
+        assert!((nice_step(10.0, 5) - 2.0).abs() < 1e-12);
+        assert!((nice_step(100.0, 4) - 50.0).abs() < 1e-12);
+        assert!((nice_step(0.7, 3) - 0.5).abs() < 1e-12);
+        assert!((nice_step(1.0, 10) - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_auto_ticks_widen_to_steps() {
+        let ticks = Ticks::new(&Scale::fit([0.3, 9.6]), 5);
+        assert_eq!(ticks.values, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert!((ticks.range.end - 10.0).abs() < 1e-12);
+        assert_eq!(ticks.label(4.0), "4");
+
+        let ticks = Ticks::new(&Scale::fit([-0.25, 0.25]), 2);
+        assert_eq!(ticks.precision, 1);
+        assert_eq!(ticks.label(ticks.values[0]), "-0.5");
+    }
+
+    #[test]
+    fn test_fixed_ticks_stay_inside() {
+        let ticks = Ticks::new(&Scale::Fixed(0.5..9.5), 5);
+        assert!((ticks.range.start - 0.5).abs() < 1e-12);
+        assert_eq!(ticks.values, vec![2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn test_bounds_pad_degenerate_data() {
+        let (min, max) = data_bounds([5.0, 5.0, f64::NAN]);
+        assert!(min < 5.0 && max > 5.0);
+        assert_eq!(data_bounds([]), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_region_too_small() {
+        let mut grid = BrailleGrid::new(10, 10).unwrap();
+        let result = LineChart::new().render(&mut grid, 0, 0, 10, 1);
+        assert!(matches!(result, Err(DotmaxError::InvalidDimensions { .. })));
+    }
+}
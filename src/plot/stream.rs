@@ -0,0 +1,260 @@
+//! Live charts of numbers arriving on a stream, such as stdin.
+//!
+//! [`NumberStream`] reads lines on a background thread, so a slow producer
+//! never blocks drawing, and hands over the numbers found on each one.
+//! [`StreamPlot`] keeps the latest of those rows and draws them as a
+//! [`LineChart`] whose x axis counts rows, scrolling as new ones arrive.
+//!
+//! Numbers on a line may be separated by whitespace, commas, or
+//! semicolons. Each line is one sample; a line with several numbers feeds
+//! several series, one per column, drawn in different colors. Lines with
+//! no numbers at all, such as a CSV header, are skipped.
+//!
+//! [`quick::plot_stdin`](crate::quick::plot_stdin) puts the two together
+//! for `mycommand | my-tool`.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::plot::stream::StreamPlot;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut plot = StreamPlot::new(100);
+//! for line in ["cpu mem", "12 40", "35, 41", "80;44"] {
+//!     plot.push_line(line);
+//! }
+//! assert_eq!(plot.len(), 3);
+//! assert_eq!(plot.latest(), Some(&[80.0, 44.0][..]));
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! plot.chart().render(&mut grid, 0, 0, 40, 12)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use super::{LineChart, Series};
+use crate::error::DotmaxError;
+use crate::grid::Color;
+
+/// Colors for the columns of a multi-column stream, in order, repeating.
+const COLUMN_COLORS: [Color; 6] = [
+    Color::rgb(90, 200, 250),
+    Color::rgb(255, 170, 50),
+    Color::rgb(120, 220, 110),
+    Color::rgb(240, 90, 120),
+    Color::rgb(190, 140, 255),
+    Color::rgb(240, 230, 100),
+];
+
+/// The numbers on `line`, skipping anything that doesn't parse.
+#[must_use]
+pub fn parse_numbers(line: &str) -> Vec<f64> {
+    line.split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter_map(|token| token.parse().ok())
+        .collect()
+}
+
+/// Lines of numbers read on a background thread.
+///
+/// The thread ends at end of input or on a read error. If the stream is
+/// dropped first, the thread ends after its next line arrives.
+#[derive(Debug)]
+pub struct NumberStream {
+    rows: Receiver<io::Result<Vec<f64>>>,
+    closed: bool,
+}
+
+impl NumberStream {
+    /// Starts reading `reader` line by line.
+    #[must_use]
+    pub fn spawn(reader: impl BufRead + Send + 'static) -> Self {
+        let (sender, rows) = mpsc::channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                match line {
+                    Ok(line) => {
+                        let row = parse_numbers(&line);
+                        if !row.is_empty() && sender.send(Ok(row)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        // Nothing to do if the stream is already gone
+                        let _ = sender.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            rows,
+            closed: false,
+        }
+    }
+
+    /// Starts reading stdin.
+    #[must_use]
+    pub fn stdin() -> Self {
+        Self::spawn(io::BufReader::new(io::stdin()))
+    }
+
+    /// Moves every row that has arrived so far into `plot`, returning
+    /// whether there were any. Never blocks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if reading failed; the stream is
+    /// closed from then on.
+    pub fn drain_into(&mut self, plot: &mut StreamPlot) -> Result<bool, DotmaxError> {
+        let mut received = false;
+        while !self.closed {
+            match self.rows.try_recv() {
+                Ok(Ok(row)) => {
+                    plot.push(&row);
+                    received = true;
+                }
+                Ok(Err(e)) => {
+                    self.closed = true;
+                    return Err(e.into());
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => self.closed = true,
+            }
+        }
+        Ok(received)
+    }
+
+    /// Whether the input has ended, so no more rows will arrive.
+    #[must_use]
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// The most recent rows of a stream of numbers, charted by row number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamPlot {
+    rows: VecDeque<Vec<f64>>,
+    window: usize,
+    /// Rows dropped off the front, so the x of `rows[0]`
+    dropped: usize,
+}
+
+impl StreamPlot {
+    /// A plot keeping the last `window` rows (at least 2).
+    #[must_use]
+    pub fn new(window: usize) -> Self {
+        let window = window.max(2);
+        Self {
+            rows: VecDeque::with_capacity(window),
+            window,
+            dropped: 0,
+        }
+    }
+
+    /// Adds a row, dropping the oldest if the window is full. Empty rows
+    /// are ignored.
+    pub fn push(&mut self, row: &[f64]) {
+        if row.is_empty() {
+            return;
+        }
+        if self.rows.len() == self.window {
+            self.rows.pop_front();
+            self.dropped += 1;
+        }
+        self.rows.push_back(row.to_vec());
+    }
+
+    /// Adds the numbers on `line` as a row, returning whether it had any.
+    pub fn push_line(&mut self, line: &str) -> bool {
+        let row = parse_numbers(line);
+        self.push(&row);
+        !row.is_empty()
+    }
+
+    /// Rows currently kept.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether no rows have arrived yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Rows seen in total, including those dropped from the window.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.dropped + self.rows.len()
+    }
+
+    /// The newest row.
+    #[must_use]
+    pub fn latest(&self) -> Option<&[f64]> {
+        self.rows.back().map(Vec::as_slice)
+    }
+
+    /// A line chart of the window, one series per column. Missing values
+    /// in short rows leave a gap. Add a title or fix the y range on it
+    /// before rendering as needed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn chart(&self) -> LineChart {
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        (0..columns).fold(LineChart::new(), |chart, column| {
+            let points = self.rows.iter().enumerate().map(|(i, row)| {
+                let y = row.get(column).copied().unwrap_or(f64::NAN);
+                ((self.dropped + i) as f64, y)
+            });
+            let series = Series::new(points);
+            chart.series(if columns > 1 {
+                series.color(COLUMN_COLORS[column % COLUMN_COLORS.len()])
+            } else {
+                series
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_parse_and_window() {
+        assert_eq!(parse_numbers(" 1.5\t-2,3e2;x ,"), [1.5, -2.0, 300.0]);
+        assert!(parse_numbers("time,value").is_empty());
+
+        let mut plot = StreamPlot::new(3);
+        for line in ["1", "2 20", "", "3", "4"] {
+            plot.push_line(line);
+        }
+        assert_eq!((plot.len(), plot.total()), (3, 4));
+        assert_eq!(plot.latest(), Some(&[4.0][..]));
+        let mut grid = crate::BrailleGrid::new(30, 8).unwrap();
+        plot.chart().render(&mut grid, 0, 0, 30, 8).unwrap();
+    }
+
+    #[test]
+    fn test_stream_drains_until_eof() {
+        let input = "# header\n1 2\n3,4\n\n5;6\n";
+        let mut stream = NumberStream::spawn(io::Cursor::new(input));
+        let mut plot = StreamPlot::new(10);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !stream.is_closed() && Instant::now() < deadline {
+            stream.drain_into(&mut plot).unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(stream.is_closed());
+        assert_eq!(plot.len(), 3);
+        assert_eq!(plot.latest(), Some(&[5.0, 6.0][..]));
+        assert!(!stream.drain_into(&mut plot).unwrap());
+    }
+}
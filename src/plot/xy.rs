@@ -0,0 +1,340 @@
+//! Charts of `(x, y)` points: [`LineChart`] and [`ScatterPlot`].
+//!
+//! Both take one or more [`Series`]. Each series can have its own color,
+//! and a [`Marker`] drawn at every point; the scatter plot draws a single
+//! dot per point when a series has no marker. Points with a NaN or
+//! infinite coordinate are skipped, and a line chart leaves a gap there.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::plot::{ScatterPlot, Series};
+//! use dotmax::primitives::{Marker, MarkerShape};
+//! use dotmax::{BrailleGrid, Color};
+//!
+//! let measured = Series::new([(1.0, 2.1), (2.0, 3.9), (3.0, 6.2), (4.0, 7.8)])
+//!     .marker(Marker::new(MarkerShape::Cross, 5)?)
+//!     .color(Color::rgb(255, 160, 0));
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! ScatterPlot::new().series(measured).render(&mut grid, 0, 0, 40, 12)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::ops::Range;
+
+use super::{colored, render_chart, Scale, Ticks, XAxis};
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+use crate::primitives::{Canvas, Marker};
+
+/// One set of points in a [`LineChart`] or [`ScatterPlot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    points: Vec<(f64, f64)>,
+    color: Option<Color>,
+    marker: Option<Marker>,
+}
+
+impl Series {
+    /// A series through `points`, in order.
+    #[must_use]
+    pub fn new(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+            color: None,
+            marker: None,
+        }
+    }
+
+    /// A series of evenly spaced samples, at x = 0, 1, 2, ...
+    #[must_use]
+    pub fn from_values(values: &[f64]) -> Self {
+        Self::new(values.iter().enumerate().map(|(i, &y)| (i as f64, y)))
+    }
+
+    /// Draws the series in `color`. Colors apply per cell, so where two
+    /// series share a cell the one drawn later wins.
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Stamps `marker` at every point.
+    #[must_use]
+    pub const fn marker(mut self, marker: Marker) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// The series' points.
+    #[must_use]
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    fn finite_points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.points
+            .iter()
+            .copied()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+    }
+
+    fn stamp_markers(&self, canvas: &mut Canvas<'_>) {
+        if let Some(marker) = &self.marker {
+            let brush = marker.brush();
+            for (x, y) in self.finite_points() {
+                canvas.stamp(&brush, x, y);
+            }
+        }
+    }
+}
+
+/// Series and axis settings shared by both chart types.
+#[derive(Debug, Clone, Default)]
+struct XyChart {
+    series: Vec<Series>,
+    title: Option<String>,
+    x_range: Option<Range<f64>>,
+    y_range: Option<Range<f64>>,
+}
+
+impl XyChart {
+    fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        region: (usize, usize, usize, usize),
+        mut draw: impl FnMut(&Series, &mut Canvas<'_>) -> Result<(), DotmaxError>,
+    ) -> Result<(), DotmaxError> {
+        let points = || self.series.iter().flat_map(Series::finite_points);
+        let x_scale = Scale::or_fit(self.x_range.as_ref(), points().map(|(x, _)| x));
+        let y_scale = Scale::or_fit(self.y_range.as_ref(), points().map(|(_, y)| y));
+        // About one x label every eight cells
+        let x_ticks = Ticks::new(&x_scale, region.2 / 8);
+
+        render_chart(
+            grid,
+            region,
+            self.title.as_deref(),
+            &XAxis::Ticks(&x_ticks),
+            &y_scale,
+            |canvas| {
+                for series in &self.series {
+                    colored(canvas, series.color, |canvas| draw(series, canvas))?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+/// Series joined by straight lines.
+#[derive(Debug, Clone, Default)]
+pub struct LineChart {
+    chart: XyChart,
+}
+
+impl LineChart {
+    /// An empty chart.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a series, drawn over the ones before it.
+    #[must_use]
+    pub fn series(mut self, series: Series) -> Self {
+        self.chart.series.push(series);
+        self
+    }
+
+    /// Title shown on the top row.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.chart.title = Some(title.into());
+        self
+    }
+
+    /// Fixes the x axis to `range` instead of fitting the data.
+    #[must_use]
+    pub const fn x_range(mut self, range: Range<f64>) -> Self {
+        self.chart.x_range = Some(range);
+        self
+    }
+
+    /// Fixes the y axis to `range` instead of fitting the data.
+    #[must_use]
+    pub const fn y_range(mut self, range: Range<f64>) -> Self {
+        self.chart.y_range = Some(range);
+        self
+    }
+
+    /// Draws the chart into the `width × height` cell region whose top-left
+    /// cell is `(x, y)`. Anything outside the grid is clipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if the region has no room
+    /// for a plot area next to the labels, or [`DotmaxError::InvalidRange`]
+    /// if a fixed range is empty or not finite.
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        self.chart
+            .render(grid, (x, y, width, height), |series, canvas| {
+                // Break the line at non-finite points
+                for run in series
+                    .points
+                    .split(|(x, y)| !x.is_finite() || !y.is_finite())
+                {
+                    canvas.polyline(run)?;
+                }
+                series.stamp_markers(canvas);
+                Ok(())
+            })
+    }
+}
+
+/// Series drawn as unconnected points.
+#[derive(Debug, Clone, Default)]
+pub struct ScatterPlot {
+    chart: XyChart,
+}
+
+impl ScatterPlot {
+    /// An empty plot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a series, drawn over the ones before it.
+    #[must_use]
+    pub fn series(mut self, series: Series) -> Self {
+        self.chart.series.push(series);
+        self
+    }
+
+    /// Title shown on the top row.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.chart.title = Some(title.into());
+        self
+    }
+
+    /// Fixes the x axis to `range` instead of fitting the data.
+    #[must_use]
+    pub const fn x_range(mut self, range: Range<f64>) -> Self {
+        self.chart.x_range = Some(range);
+        self
+    }
+
+    /// Fixes the y axis to `range` instead of fitting the data.
+    #[must_use]
+    pub const fn y_range(mut self, range: Range<f64>) -> Self {
+        self.chart.y_range = Some(range);
+        self
+    }
+
+    /// Draws the plot into the `width × height` cell region whose top-left
+    /// cell is `(x, y)`. Anything outside the grid is clipped.
+    ///
+    /// # Errors
+    ///
+    /// As for [`LineChart::render`].
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        self.chart
+            .render(grid, (x, y, width, height), |series, canvas| {
+                if series.marker.is_some() {
+                    series.stamp_markers(canvas);
+                } else {
+                    for (x, y) in series.finite_points() {
+                        canvas.plot(x, y);
+                    }
+                }
+                Ok(())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(grid: &BrailleGrid, y: usize) -> String {
+        (0..grid.width()).map(|x| grid.get_char(x, y)).collect()
+    }
+
+    #[test]
+    fn test_line_chart_layout() {
+        let mut grid = BrailleGrid::new(30, 8).unwrap();
+        LineChart::new()
+            .series(Series::new([(0.0, 0.0), (10.0, 10.0)]))
+            .render(&mut grid, 0, 0, 30, 8)
+            .unwrap();
+
+        // Seven plot rows: ticks every 5 from 0 to 10
+        assert_eq!(row_text(&grid, 0).chars().take(2).collect::<String>(), "10");
+        assert_eq!(grid.get_char(3, 7), '0');
+        assert!(row_text(&grid, 7).ends_with("10"));
+
+        // The line runs corner to corner of the plot area (3 cells in)
+        assert!(grid.is_dot_set(6, 27));
+        assert!(grid.is_dot_set(59, 0));
+    }
+
+    #[test]
+    fn test_line_breaks_at_nan() {
+        let mut grid = BrailleGrid::new(20, 6).unwrap();
+        LineChart::new()
+            .series(Series::new([
+                (0.0, 5.0),
+                (1.0, 5.0),
+                (2.0, f64::NAN),
+                (3.0, 5.0),
+                (4.0, 5.0),
+            ]))
+            .x_range(0.0..4.0)
+            .y_range(0.0..10.0)
+            .render(&mut grid, 0, 0, 20, 6)
+            .unwrap();
+
+        // Plot area starts after "10 " (dot 6) and is 34 dots wide; y = 5
+        // lands on dot row 10
+        let lit: Vec<bool> = (6..40).map(|x| grid.is_dot_set(x, 10)).collect();
+        assert!(lit[4]);
+        assert!(!lit[17]);
+        assert!(lit[30]);
+    }
+
+    #[test]
+    fn test_scatter_colors_series() {
+        let red = Color::rgb(255, 0, 0);
+        let mut grid = BrailleGrid::new(20, 6).unwrap();
+        ScatterPlot::new()
+            .series(Series::new([(5.0, 5.0)]).color(red))
+            .x_range(0.0..10.0)
+            .y_range(0.0..10.0)
+            .render(&mut grid, 0, 0, 20, 6)
+            .unwrap();
+
+        let colored = (0..20)
+            .flat_map(|x| (0..6).map(move |y| (x, y)))
+            .filter(|&(x, y)| grid.get_color(x, y) == Some(red))
+            .count();
+        assert_eq!(colored, 1);
+    }
+}
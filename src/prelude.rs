@@ -28,6 +28,8 @@
 //! - [`draw_circle`], [`draw_circle_colored`]: Circle drawing
 //! - [`draw_rectangle`], [`draw_rectangle_colored`]: Rectangle drawing
 //! - [`draw_polygon`], [`draw_polygon_colored`]: Polygon drawing
+//! - [`Canvas`]: Drawing in world coordinates
+//! - [`draw_text`], [`FontSize`]: Text in dots from the built-in bitmap fonts
 //!
 //! ## Animation
 //!
@@ -59,9 +61,56 @@
 //!
 //! - `ImageRenderer`: High-level image-to-braille rendering
 //! - `DitheringMethod`: Dithering algorithm selection
+//! - `RenderOptions`: Render settings shared by images, video, and webcam
 //! - `show_file`, `load_file`: Universal media display functions
 //! - `MediaContent`, `MediaPlayer`: Media types for animation control
 //!
+//! # API Tiers
+//!
+//! As the crate grows, one prelude for everything would either leave most
+//! of it out or fill every importer's namespace. The public API is split
+//! into tiers instead, each with its own prelude, so an application imports
+//! one line per subsystem it uses:
+//!
+//! | Tier | Prelude | Covers | Stability |
+//! |------|---------|--------|-----------|
+//! | Base | [`prelude::base`](base) | Grids, colors, rendering, errors, color schemes | Stable |
+//! | Drawing | [`prelude::drawing`](drawing) | Primitives, text, transforms, animation loops | Stable |
+//! | Media | [`prelude::media`](media) | Images, animated media, video, webcam (`image` feature) | Stable |
+//! | Widgets | [`prelude::widgets`](widgets) | Legends, overlays, HUDs | Evolving |
+//!
+//! Every tier prelude includes the base tier, so `drawing::*` alone is
+//! enough for a program that draws shapes and shows them:
+//!
+//! ```
+//! use dotmax::prelude::drawing::*;
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! draw_ellipse(&mut grid, 40, 20, 30, 15)?;
+//! # Ok::<(), DotmaxError>(())
+//! ```
+//!
+//! The base tier is called `base` rather than `core` so that
+//! `use dotmax::prelude::*` doesn't shadow the `core` crate.
+//!
+//! # Stability
+//!
+//! What a tier prelude exports is a semver promise:
+//!
+//! - **Stable** tiers only lose items, or change them incompatibly, in a
+//!   breaking release (a minor release while dotmax is 0.x). Additions
+//!   come in any release. Every change to what they export is listed in
+//!   the changelog.
+//! - **Evolving** tiers follow the same rules for names, but a minor
+//!   release may change what their items draw, such as the layout or
+//!   wording of an overlay. Such changes are also listed in the changelog.
+//!
+//! Items reachable only through their module path (for example
+//! `dotmax::image::pipeline`) follow semver too, but aren't part of any
+//! tier; check the changelog before relying on them across releases. The
+//! glob prelude above (`dotmax::prelude::*`) is unchanged by the tiers and
+//! stays as it is.
+//!
 //! # Note on `Result`
 //!
 //! This prelude exports `dotmax::Result<T>` which is an alias for
@@ -164,7 +213,7 @@ pub use crate::{
 
 pub use crate::primitives::{
     draw_circle, draw_circle_colored, draw_line, draw_line_colored, draw_polygon,
-    draw_polygon_colored, draw_rectangle, draw_rectangle_colored,
+    draw_polygon_colored, draw_rectangle, draw_rectangle_colored, draw_text, Canvas, FontSize,
 };
 
 // ============================================================================
@@ -191,7 +240,7 @@ pub use crate::{
 // ============================================================================
 
 #[cfg(feature = "image")]
-pub use crate::image::{DitheringMethod, ImageRenderer};
+pub use crate::image::{DitheringMethod, ImageRenderer, RenderOptions};
 
 // ============================================================================
 // Quick Functions (Story 8.2)
@@ -200,7 +249,7 @@ pub use crate::image::{DitheringMethod, ImageRenderer};
 pub use crate::quick::{grid, grid_sized, show};
 
 #[cfg(feature = "image")]
-pub use crate::quick::{load_file, load_image, load_image_sized, show_file, show_image};
+pub use crate::quick::{load_file, load_image, load_image_sized, play, show_file, show_image};
 
 // ============================================================================
 // Media Types - Feature-Gated (Epic 9: Universal Media Rendering)
@@ -219,6 +268,81 @@ pub use crate::media::{list_webcams, WebcamDevice, WebcamDeviceId, WebcamPlayer,
 #[cfg(feature = "video")]
 pub use crate::quick::{show_webcam, show_webcam_device};
 
+// ============================================================================
+// API Tiers
+// ============================================================================
+
+/// Base tier: grids, colors, rendering, and errors.
+///
+/// **Stability: stable.**
+pub mod base {
+    pub use crate::quick::{grid, grid_sized, show};
+    pub use crate::{
+        apply_color_scheme, apply_colors_to_grid, blue_purple, cyan_magenta,
+        detect_color_capability, get_scheme, grayscale, green_yellow, heat_map, list_schemes,
+        monochrome, rainbow, render_to_writer, BrailleGrid, Color, ColorCapability, ColorScheme,
+        ColorSchemeBuilder, DotmaxError, RenderMode, Result, TerminalBackend,
+        TerminalCapabilities, TerminalRenderer,
+    };
+}
+
+/// Drawing tier: shapes, curves, text, transforms, and animation loops,
+/// plus the [`base`] tier.
+///
+/// **Stability: stable.**
+pub mod drawing {
+    pub use super::base::*;
+    pub use crate::animation::{
+        AnimationLoop, AnimationLoopBuilder, DifferentialRenderer, FrameBuffer, FrameContext,
+        FrameTimer, PrerenderedAnimation, Timestep,
+    };
+    pub use crate::primitives::{
+        draw_arc, draw_arc_colored, draw_arc_thick, draw_circle, draw_circle_aa,
+        draw_circle_colored, draw_circle_filled, draw_circle_thick, draw_cubic_bezier,
+        draw_cubic_bezier_colored, draw_ellipse, draw_ellipse_colored, draw_ellipse_filled,
+        draw_ellipse_thick, draw_line, draw_line_aa, draw_line_colored, draw_line_thick,
+        draw_polygon, draw_polygon_colored, draw_polygon_filled, draw_quadratic_bezier,
+        draw_quadratic_bezier_colored, draw_rectangle, draw_rectangle_colored,
+        draw_rectangle_filled, draw_rectangle_thick, draw_text, flood_fill, measure_text,
+        stroke_path, with_transform, Brush, Canvas, FontSize, Marker, MarkerShape, TextStyle,
+        Transform2D,
+    };
+}
+
+/// Media tier: still images, animated media, and (with the `video`
+/// feature) video and webcams, plus the [`base`] tier.
+///
+/// **Stability: stable.** Which formats load depends on the enabled
+/// features, not on this tier.
+#[cfg(feature = "image")]
+pub mod media {
+    pub use super::base::*;
+    pub use crate::image::{ColorMode, DitheringMethod, ImageRenderer, RenderOptions};
+    pub use crate::media::{
+        detect_format, Controlled, MediaContent, MediaFormat, MediaPlayer, PlayerControl,
+        Playlist, RepeatMode,
+    };
+    pub use crate::quick::{load_file, load_image, load_image_sized, play, show_file, show_image};
+
+    #[cfg(feature = "video")]
+    pub use crate::media::{list_webcams, VideoPlayer, WebcamDeviceId, WebcamPlayer};
+    #[cfg(feature = "video")]
+    pub use crate::quick::{show_webcam, show_webcam_device};
+}
+
+/// Widgets tier: legends, playback overlays, and animation HUDs, plus the
+/// [`base`] tier.
+///
+/// **Stability: evolving.** Names follow semver; what the widgets draw may
+/// change in a minor release.
+pub mod widgets {
+    pub use super::base::*;
+    pub use crate::widgets::{
+        ColorLegend, DensityLegend, FrameTiming, HistogramOverlay, Orientation,
+        PlaybackDiagnostics, PlaybackOsd, StatsHud,
+    };
+}
+
 // ============================================================================
 // Tests (AC: #7)
 // ============================================================================
@@ -253,6 +377,7 @@ mod tests {
         draw_circle(&mut grid, 20, 20, 5).unwrap();
         draw_rectangle(&mut grid, 0, 0, 10, 10).unwrap();
         draw_polygon(&mut grid, &[(0, 0), (10, 0), (5, 10)]).unwrap();
+        draw_text(&mut grid, 0, 0, "hi", FontSize::Small).unwrap();
 
         // Colored variants (with proper arguments)
         let color = Color::rgb(255, 0, 0);
@@ -368,6 +493,28 @@ mod tests {
             |idx| show_webcam_device(idx);
     }
 
+    #[test]
+    fn test_tiers_include_base() {
+        use super::drawing::*;
+
+        let mut grid = BrailleGrid::new(20, 5).unwrap();
+        draw_ellipse(&mut grid, 20, 10, 10, 5).unwrap();
+        let _timestep = Timestep::fixed(std::time::Duration::from_millis(10));
+        let _loop = AnimationLoop::new(20, 5).fps(30);
+    }
+
+    #[test]
+    fn test_tier_globs_combine() {
+        // The same base items arrive through both globs without ambiguity
+        use super::drawing::*;
+        use super::widgets::*;
+
+        let mut grid = BrailleGrid::new(20, 6).unwrap();
+        draw_line(&mut grid, 0, 0, 39, 23).unwrap();
+        StatsHud::new(60).render(&mut grid);
+        let _legend = ColorLegend::new(heat_map());
+    }
+
     #[test]
     fn test_no_naming_conflicts() {
         // This test verifies that all re-exported items can be used together
@@ -0,0 +1,408 @@
+//! Anti-aliased lines and circles using Xiaolin Wu's algorithm.
+//!
+//! A braille dot is either on or off, so a plain Bresenham diagonal steps
+//! visibly at braille resolution. Wu's algorithm instead gives each dot
+//! near the ideal line a fractional *coverage*: how much of the dot the
+//! line passes through. The anti-aliased primitives here write that coverage
+//! into a [`Coverage`] buffer rather than straight onto a grid, and the
+//! buffer is then rendered one of two ways:
+//!
+//! - [`Coverage::render_density`] averages each 2×4 block of dots into a
+//!   cell intensity and draws it with a [`DensitySet`], the
+//!   [`density`](crate::density) module's character ramp.
+//! - [`Coverage::render_blended`] sets the dots whose coverage reaches a
+//!   threshold and dims each cell's color by the coverage of its dots, so
+//!   cells on the edge of a stroke fade into the background.
+//!
+//! Coordinates are `f64` dot coordinates, so endpoints and centers can sit
+//! between dots. Coverage from overlapping strokes is combined with `max`,
+//! never summed past 1.0.
+//!
+//! ## References
+//!
+//! - Wu, X. (1991). "An efficient antialiasing technique", SIGGRAPH '91
+//! - <https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm>
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::{BrailleGrid, Color};
+//! use dotmax::primitives::{draw_circle_aa, draw_line_aa, Coverage};
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! grid.enable_color_support();
+//!
+//! let mut coverage = Coverage::for_grid(&grid);
+//! draw_line_aa(&mut coverage, 0.0, 0.0, 79.0, 30.0);
+//! draw_circle_aa(&mut coverage, 40.0, 24.0, 18.5);
+//! coverage.render_blended(&mut grid, Color::rgb(0, 255, 160), 0.5)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::density::DensitySet;
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Per-dot coverage values in `[0.0, 1.0]`, filled in by the anti-aliased
+/// primitives and rendered onto a [`BrailleGrid`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coverage {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl Coverage {
+    /// An empty buffer `width` × `height` dots in size.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    /// An empty buffer the size of `grid` in dots.
+    #[must_use]
+    pub fn for_grid(grid: &BrailleGrid) -> Self {
+        Self::new(grid.dot_width(), grid.dot_height())
+    }
+
+    /// Width in dots.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in dots.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Coverage of the dot at `(x, y)`, or 0.0 outside the buffer.
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        if x < self.width && y < self.height {
+            self.values[y * self.width + x]
+        } else {
+            0.0
+        }
+    }
+
+    /// Resets every dot to zero coverage.
+    pub fn clear(&mut self) {
+        self.values.fill(0.0);
+    }
+
+    /// Mean coverage of each 2×4 cell, row-major, for
+    /// [`BrailleGrid::render_density`] or
+    /// [`BrailleGrid::apply_color_scheme`].
+    ///
+    /// Dots past the buffer edge count as uncovered.
+    #[must_use]
+    pub fn cell_intensities(&self) -> Vec<f32> {
+        let (cells_x, cells_y) = ((self.width + 1) / 2, (self.height + 3) / 4);
+        let mut intensities = Vec::with_capacity(cells_x * cells_y);
+        for cell_y in 0..cells_y {
+            for cell_x in 0..cells_x {
+                let sum: f32 = cell_dots(cell_x, cell_y).map(|(x, y)| self.get(x, y)).sum();
+                intensities.push(sum / 8.0);
+            }
+        }
+        intensities
+    }
+
+    /// Draws each cell as the `density` character for its mean coverage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::BufferSizeMismatch`] if the buffer was not
+    /// made for a grid of this size.
+    pub fn render_density(
+        &self,
+        grid: &mut BrailleGrid,
+        density: &DensitySet,
+    ) -> Result<(), DotmaxError> {
+        self.check_size(grid)?;
+        grid.render_density(&self.cell_intensities(), density)
+    }
+
+    /// Sets every dot with coverage of at least `threshold` and colors each
+    /// cell with `color` dimmed to the mean coverage of its set dots.
+    ///
+    /// A `threshold` around 0.5 keeps strokes one dot wide; lower values
+    /// thicken them. Cells without set dots keep their color.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::BufferSizeMismatch`] if the buffer was not
+    /// made for a grid of this size.
+    pub fn render_blended(
+        &self,
+        grid: &mut BrailleGrid,
+        color: Color,
+        threshold: f32,
+    ) -> Result<(), DotmaxError> {
+        self.check_size(grid)?;
+        // A zero threshold would light every dot, covered or not
+        let threshold = threshold.max(f32::EPSILON);
+        let (cells_x, cells_y) = grid.dimensions();
+        for cell_y in 0..cells_y {
+            for cell_x in 0..cells_x {
+                let (mut lit, mut sum) = (0_u8, 0.0);
+                for (x, y) in cell_dots(cell_x, cell_y) {
+                    let value = self.get(x, y);
+                    if value >= threshold {
+                        grid.set_dot(x, y)?;
+                        lit += 1;
+                        sum += value;
+                    }
+                }
+                if lit > 0 {
+                    let shade = scale(color, sum / f32::from(lit));
+                    grid.set_cell_color(cell_x, cell_y, shade)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_size(&self, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        if (self.width, self.height) == (grid.dot_width(), grid.dot_height()) {
+            Ok(())
+        } else {
+            Err(DotmaxError::BufferSizeMismatch {
+                expected: grid.dot_width() * grid.dot_height(),
+                actual: self.values.len(),
+            })
+        }
+    }
+
+    /// Raises the coverage at `(x, y)` to `value`, clipping off-buffer dots.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn plot(&mut self, x: f64, y: f64, value: f64) {
+        if x < 0.0 || y < 0.0 || value <= 0.0 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if x < self.width && y < self.height {
+            let dot = &mut self.values[y * self.width + x];
+            *dot = dot.max(value.min(1.0) as f32);
+        }
+    }
+}
+
+/// The 8 dots of cell `(cell_x, cell_y)`.
+fn cell_dots(cell_x: usize, cell_y: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..8).map(move |i| (cell_x * 2 + i % 2, cell_y * 4 + i / 2))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn scale(color: Color, factor: f32) -> Color {
+    let channel = |value: u8| (f32::from(value) * factor).round() as u8;
+    Color::rgb(channel(color.r), channel(color.g), channel(color.b))
+}
+
+/// Draw an anti-aliased line from `(x0, y0)` to `(x1, y1)` into `coverage`.
+///
+/// Each step along the major axis covers the two dots straddling the ideal
+/// line, split by how close the line passes to each. The endpoints are
+/// rounded to the nearest dot along the major axis.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::primitives::{draw_line_aa, Coverage};
+///
+/// let mut coverage = Coverage::new(20, 20);
+/// draw_line_aa(&mut coverage, 0.0, 0.0, 19.0, 9.0);
+/// // At x = 9 the line passes y ≈ 4.26, mostly through dot 4
+/// assert!(coverage.get(9, 4) > coverage.get(9, 5));
+/// assert!(coverage.get(9, 5) > 0.0);
+/// ```
+pub fn draw_line_aa(coverage: &mut Coverage, x0: f64, y0: f64, x1: f64, y1: f64) {
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+    // Walk along x; steep lines are drawn transposed
+    let (x0, y0, x1, y1) = if steep {
+        (y0, x0, y1, x1)
+    } else {
+        (x0, y0, x1, y1)
+    };
+    let (x0, y0, x1, y1) = if x0 > x1 {
+        (x1, y1, x0, y0)
+    } else {
+        (x0, y0, x1, y1)
+    };
+    let mut plot = |x: f64, y: f64, value: f64| {
+        if steep {
+            coverage.plot(y, x, value);
+        } else {
+            coverage.plot(x, y, value);
+        }
+    };
+
+    let gradient = if x1 - x0 == 0.0 {
+        1.0
+    } else {
+        (y1 - y0) / (x1 - x0)
+    };
+
+    // One column per dot from the first endpoint to the last
+    let first = x0.round();
+    #[allow(clippy::cast_possible_truncation)]
+    let columns = (x1.round() - first) as i64;
+    for column in 0..=columns {
+        #[allow(clippy::cast_precision_loss)]
+        let x = first + column as f64;
+        let (dot, frac) = split(gradient.mul_add(x - x0, y0));
+        plot(x, dot, 1.0 - frac);
+        plot(x, dot + 1.0, frac);
+    }
+}
+
+/// Splits `value` into the dot at or above it and how far past that dot
+/// it lies, in `[0.0, 1.0)`.
+fn split(value: f64) -> (f64, f64) {
+    let dot = value.floor();
+    (dot, value - dot)
+}
+
+/// Draw an anti-aliased circle outline centered on `(center_x, center_y)`
+/// into `coverage`.
+///
+/// Wu's circle variant: for each step around one octant, the two dots
+/// either side of the exact radius share the coverage, and the result is
+/// mirrored into the other seven octants.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::primitives::{draw_circle_aa, Coverage};
+///
+/// let mut coverage = Coverage::new(40, 40);
+/// draw_circle_aa(&mut coverage, 20.0, 20.0, 10.5);
+/// // The rim falls halfway between dots 30 and 31
+/// assert!((coverage.get(30, 20) - 0.5).abs() < 1e-6);
+/// assert!((coverage.get(31, 20) - 0.5).abs() < 1e-6);
+/// assert_eq!(coverage.get(20, 20), 0.0);
+/// ```
+pub fn draw_circle_aa(coverage: &mut Coverage, center_x: f64, center_y: f64, radius: f64) {
+    if radius <= 0.0 {
+        if radius == 0.0 {
+            coverage.plot(center_x.round(), center_y.round(), 1.0);
+        }
+        return;
+    }
+
+    let mut plot8 = |along: f64, across: f64, value: f64| {
+        for (dx, dy) in [
+            (along, across),
+            (-along, across),
+            (along, -across),
+            (-along, -across),
+            (across, along),
+            (-across, along),
+            (across, -along),
+            (-across, -along),
+        ] {
+            coverage.plot(center_x + dx, center_y + dy, value);
+        }
+    };
+
+    // One octant, from the top of the circle until the 45° diagonal
+    let limit = radius * std::f64::consts::FRAC_1_SQRT_2;
+    #[allow(clippy::cast_possible_truncation)]
+    let steps = limit.ceil() as i64;
+    for step in 0..=steps {
+        #[allow(clippy::cast_precision_loss)]
+        let along = step as f64;
+        let (dot, frac) = split(radius.mul_add(radius, -(along * along)).max(0.0).sqrt());
+        plot8(along, dot, 1.0 - frac);
+        plot8(along, dot + 1.0, frac);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_aligned_line_is_solid() {
+        let mut coverage = Coverage::new(10, 10);
+        draw_line_aa(&mut coverage, 1.0, 3.0, 8.0, 3.0);
+        assert!((1..=8).all(|x| (coverage.get(x, 3) - 1.0).abs() < 1e-6));
+        assert!((0..10).all(|x| coverage.get(x, 2) == 0.0 && coverage.get(x, 4) == 0.0));
+
+        // Steep lines walk along y
+        coverage.clear();
+        draw_line_aa(&mut coverage, 4.0, 9.0, 4.0, 0.0);
+        assert!((0..10).all(|y| (coverage.get(4, y) - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_diagonal_coverage_is_split() {
+        let mut coverage = Coverage::new(40, 40);
+        draw_line_aa(&mut coverage, 0.0, 0.0, 30.0, 10.0);
+        // y = 1/3 at x = 1: a third of the way from dot 0 to dot 1
+        assert!((coverage.get(1, 0) - 2.0 / 3.0).abs() < 1e-5);
+        assert!((coverage.get(1, 1) - 1.0 / 3.0).abs() < 1e-5);
+        // Every column carries one dot's worth of coverage
+        for x in 1..30 {
+            let column: f32 = (0..40).map(|y| coverage.get(x, y)).sum();
+            assert!((column - 1.0).abs() < 1e-5, "column {x}: {column}");
+        }
+    }
+
+    #[test]
+    fn test_circle_is_symmetric_and_clipped() {
+        let mut coverage = Coverage::new(41, 41);
+        draw_circle_aa(&mut coverage, 20.0, 20.0, 12.3);
+        for y in 0..41 {
+            for x in 0..41 {
+                let value = coverage.get(x, y);
+                assert!((value - coverage.get(40 - x, y)).abs() < 1e-6);
+                assert!((value - coverage.get(y, x)).abs() < 1e-6);
+            }
+        }
+        assert!(coverage.get(32, 20) > coverage.get(33, 20));
+
+        // Mostly off-buffer: no panic, partial arc drawn
+        let mut small = Coverage::new(10, 10);
+        draw_circle_aa(&mut small, -5.0, -5.0, 12.0);
+        assert!(small.values.iter().any(|&v| v > 0.0));
+    }
+
+    #[test]
+    fn test_render_density_and_blended() {
+        let mut grid = BrailleGrid::new(5, 2).unwrap();
+        grid.enable_color_support();
+        let mut coverage = Coverage::for_grid(&grid);
+        draw_line_aa(&mut coverage, 0.0, 0.0, 9.0, 3.0);
+
+        let intensities = coverage.cell_intensities();
+        assert_eq!(intensities.len(), 10);
+        assert!(intensities[0] > 0.0 && intensities[9] == 0.0);
+        coverage
+            .render_density(&mut grid, &DensitySet::simple())
+            .unwrap();
+        assert_eq!(grid.get_char(4, 1), ' ');
+
+        let mut grid = BrailleGrid::new(5, 2).unwrap();
+        grid.enable_color_support();
+        coverage
+            .render_blended(&mut grid, Color::rgb(200, 100, 0), 0.5)
+            .unwrap();
+        assert!(grid.is_dot_set(0, 0) && !grid.is_dot_set(0, 3));
+        let shade = grid.get_color(2, 0).unwrap();
+        assert!(shade.r > 100 && shade.r <= 200 && shade.b == 0, "{shade:?}");
+        assert_eq!(grid.get_color(0, 1), None);
+
+        let mut wrong = BrailleGrid::new(6, 2).unwrap();
+        assert!(matches!(
+            coverage.render_blended(&mut wrong, Color::rgb(1, 1, 1), 0.5),
+            Err(DotmaxError::BufferSizeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,401 @@
+//! Brushes: reusable dot masks stamped onto the grid.
+//!
+//! A [`Brush`] is a small bitmap of dots, optionally colored, with an anchor
+//! point. [`BrailleGrid::stamp`] copies it onto the grid with the anchor at a
+//! dot coordinate, and [`stroke_path`] stamps it repeatedly along a polyline.
+//! Together they cover textured and thick strokes, scatter plot markers, and
+//! paint-style tools without a dedicated primitive for each.
+//!
+//! Stamping only ever sets dots; the brush's unset dots leave the grid as it
+//! was. Dots falling outside the grid are clipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::primitives::{stroke_path, Brush};
+//! use dotmax::{BrailleGrid, Color};
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//!
+//! // A plus-shaped marker
+//! let plus = Brush::from_pattern(&[".#.", "###", ".#."])?.with_color(Color::rgb(255, 0, 0));
+//! grid.stamp(&plus, 10, 10);
+//! assert!(grid.is_dot_set(10, 10) && grid.is_dot_set(9, 10) && !grid.is_dot_set(9, 9));
+//!
+//! // A 3-dot-wide round stroke
+//! stroke_path(&mut grid, &Brush::round(3), &[(0, 30), (40, 20), (79, 30)], 1)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Largest brush side in dots. Brushes are meant to be small; big shapes
+/// belong in the fill primitives.
+const MAX_BRUSH_SIZE: usize = 256;
+
+/// A dot mask with an anchor and optional color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Brush {
+    width: usize,
+    height: usize,
+    mask: Vec<bool>,
+    color: Option<Color>,
+    anchor: (usize, usize),
+}
+
+impl Brush {
+    /// Create a brush from a row-major mask of `width × height` dots.
+    ///
+    /// The anchor starts at the center (`width / 2`, `height / 2`).
+    ///
+    /// # Errors
+    ///
+    /// - [`DotmaxError::InvalidDimensions`] if either side is 0 or above 256
+    /// - [`DotmaxError::BufferSizeMismatch`] if `mask.len() != width * height`
+    pub fn new(width: usize, height: usize, mask: Vec<bool>) -> Result<Self, DotmaxError> {
+        if width == 0 || height == 0 || width > MAX_BRUSH_SIZE || height > MAX_BRUSH_SIZE {
+            return Err(DotmaxError::InvalidDimensions { width, height });
+        }
+        if mask.len() != width * height {
+            return Err(DotmaxError::BufferSizeMismatch {
+                expected: width * height,
+                actual: mask.len(),
+            });
+        }
+        Ok(Self {
+            width,
+            height,
+            mask,
+            color: None,
+            anchor: (width / 2, height / 2),
+        })
+    }
+
+    /// Create a brush from text rows, where `#` (or any character other than
+    /// `.` and space) is a set dot.
+    ///
+    /// Short rows are padded with unset dots to the longest row.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if there are no rows, every
+    /// row is empty, or the pattern exceeds 256 dots on a side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::primitives::Brush;
+    ///
+    /// let x = Brush::from_pattern(&["#.#", ".#.", "#.#"])?;
+    /// assert_eq!((x.width(), x.height()), (3, 3));
+    /// assert!(x.is_set(0, 0) && !x.is_set(1, 0));
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn from_pattern(rows: &[&str]) -> Result<Self, DotmaxError> {
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+        let mut mask = Vec::with_capacity(width * rows.len());
+        for row in rows {
+            let mut cells: Vec<bool> = row.chars().map(|c| c != '.' && c != ' ').collect();
+            cells.resize(width, false);
+            mask.extend(cells);
+        }
+        Self::new(width, rows.len(), mask)
+    }
+
+    /// Create a brush from every dot of a small grid, keeping the color of the
+    /// grid's top-left cell if it has one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if the grid is larger than
+    /// 256 dots on a side.
+    pub fn from_grid(grid: &BrailleGrid) -> Result<Self, DotmaxError> {
+        let (width, height) = (grid.dot_width(), grid.dot_height());
+        let mask = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| grid.is_dot_set(x, y))
+            .collect();
+        let brush = Self::new(width, height, mask)?;
+        Ok(match grid.get_color(0, 0) {
+            Some(color) => brush.with_color(color),
+            None => brush,
+        })
+    }
+
+    /// A solid `size × size` square tip.
+    ///
+    /// A size of 0 is treated as 1.
+    #[must_use]
+    pub fn square(size: usize) -> Self {
+        let size = size.clamp(1, MAX_BRUSH_SIZE);
+        Self::square_mask(size, vec![true; size * size])
+    }
+
+    /// A solid round tip `diameter` dots across.
+    ///
+    /// A diameter of 0 is treated as 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn round(diameter: usize) -> Self {
+        let size = diameter.clamp(1, MAX_BRUSH_SIZE);
+        let center = (size as f32 - 1.0) / 2.0;
+        // Slightly over half the diameter so small tips aren't reduced to a plus
+        let radius_sq = (size as f32 / 2.0 + 0.25).powi(2);
+        let mask = (0..size * size)
+            .map(|i| {
+                let dx = (i % size) as f32 - center;
+                let dy = (i / size) as f32 - center;
+                dx.mul_add(dx, dy * dy) <= radius_sq
+            })
+            .collect();
+        Self::square_mask(size, mask)
+    }
+
+    /// Centered brush from a `size × size` mask the caller has already sized
+    /// correctly (1..=256 dots, `size * size` entries).
+    pub(super) fn square_mask(size: usize, mask: Vec<bool>) -> Self {
+        debug_assert!((1..=MAX_BRUSH_SIZE).contains(&size) && mask.len() == size * size);
+        Self {
+            width: size,
+            height: size,
+            mask,
+            color: None,
+            anchor: (size / 2, size / 2),
+        }
+    }
+
+    /// Color every cell the brush touches when stamped.
+    #[must_use]
+    pub const fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Move the anchor, the dot placed at the stamp position. Values are
+    /// clamped to the brush.
+    #[must_use]
+    pub fn with_anchor(mut self, x: usize, y: usize) -> Self {
+        self.anchor = (x.min(self.width - 1), y.min(self.height - 1));
+        self
+    }
+
+    /// Width in dots.
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in dots.
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Anchor position within the brush, in dots.
+    #[must_use]
+    pub const fn anchor(&self) -> (usize, usize) {
+        self.anchor
+    }
+
+    /// Color applied when stamping, if any.
+    #[must_use]
+    pub const fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Whether the brush has a dot at `(x, y)`. Out-of-range reads are unset.
+    #[must_use]
+    pub fn is_set(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.mask[y * self.width + x]
+    }
+}
+
+impl BrailleGrid {
+    /// Stamp a brush with its anchor at dot `(x, y)`.
+    ///
+    /// Sets the brush's dots (clipping at the grid edges) and, if the brush
+    /// has a color, colors every cell that received a dot.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn stamp(&mut self, brush: &Brush, x: i32, y: i32) {
+        let (ax, ay) = (brush.anchor.0 as i32, brush.anchor.1 as i32);
+        let (max_x, max_y) = (self.dot_width() as i64, self.dot_height() as i64);
+        for by in 0..brush.height {
+            let dot_y = i64::from(y - ay) + by as i64;
+            if dot_y < 0 || dot_y >= max_y {
+                continue;
+            }
+            for bx in 0..brush.width {
+                let dot_x = i64::from(x - ax) + bx as i64;
+                if !brush.mask[by * brush.width + bx] || dot_x < 0 || dot_x >= max_x {
+                    continue;
+                }
+                let (dot_x, dot_y) = (dot_x as usize, dot_y as usize);
+                let _ = self.set_dot(dot_x, dot_y);
+                if let Some(color) = brush.color {
+                    let _ = self.set_cell_color(dot_x / 2, dot_y / 4, color);
+                }
+            }
+        }
+    }
+}
+
+/// Stamp `brush` along a polyline, every `spacing` dots.
+///
+/// The path is walked dot by dot with Bresenham steps, and the spacing count
+/// carries across vertices so stamps stay evenly spaced around corners. A
+/// spacing of 1 gives a continuous stroke; larger values give dotted or
+/// textured strokes. A single point is stamped once; an empty path draws
+/// nothing.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidParameter`] if `spacing` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::primitives::{stroke_path, Brush};
+/// use dotmax::BrailleGrid;
+///
+/// let mut grid = BrailleGrid::new(20, 2)?;
+/// // A dotted line: one 2×2 square every 4 dots
+/// stroke_path(&mut grid, &Brush::square(2), &[(1, 3), (37, 3)], 4)?;
+/// assert!(grid.is_dot_set(0, 2) && !grid.is_dot_set(2, 2));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn stroke_path(
+    grid: &mut BrailleGrid,
+    brush: &Brush,
+    points: &[(i32, i32)],
+    spacing: u32,
+) -> Result<(), DotmaxError> {
+    if spacing == 0 {
+        return Err(DotmaxError::InvalidParameter {
+            parameter_name: "stroke spacing".to_string(),
+            value: "0".to_string(),
+            min: "1".to_string(),
+            max: u32::MAX.to_string(),
+        });
+    }
+    let Some(&(x, y)) = points.first() else {
+        return Ok(());
+    };
+    grid.stamp(brush, x, y);
+
+    let mut since_stamp = 0;
+    for segment in points.windows(2) {
+        let ((mut x, mut y), (x1, y1)) = (segment[0], segment[1]);
+        let (dx, dy) = ((x1 - x).abs(), -(y1 - y).abs());
+        let (sx, sy) = ((x1 - x).signum(), (y1 - y).signum());
+        let mut err = dx + dy;
+        while (x, y) != (x1, y1) {
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+            since_stamp += 1;
+            if since_stamp == spacing {
+                grid.stamp(brush, x, y);
+                since_stamp = 0;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_dots(grid: &BrailleGrid) -> u32 {
+        grid.get_raw_patterns().iter().map(|p| p.count_ones()).sum()
+    }
+
+    #[test]
+    fn test_new_validates() {
+        assert!(matches!(
+            Brush::new(0, 3, vec![]),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+        assert!(matches!(
+            Brush::new(2, 2, vec![true; 3]),
+            Err(DotmaxError::BufferSizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(Brush::from_pattern(&[]).is_err());
+    }
+
+    #[test]
+    fn test_from_pattern_pads_short_rows() {
+        let brush = Brush::from_pattern(&["###", "#"]).unwrap();
+        assert_eq!((brush.width(), brush.height()), (3, 2));
+        assert!(brush.is_set(0, 1) && !brush.is_set(1, 1));
+        assert_eq!(brush.anchor(), (1, 1));
+    }
+
+    #[test]
+    fn test_round_brush_shape() {
+        assert_eq!(Brush::round(1), Brush::square(1));
+        let round = Brush::round(5);
+        assert!(round.is_set(2, 2) && round.is_set(0, 2) && round.is_set(2, 0));
+        assert!(!round.is_set(0, 0));
+    }
+
+    #[test]
+    fn test_stamp_uses_anchor_and_clips() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        grid.stamp(&Brush::square(3), 0, 0);
+        // Centered 3×3 at the corner: only the 2×2 on-grid quarter lands
+        assert_eq!(count_dots(&grid), 4);
+
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        grid.stamp(&Brush::square(3).with_anchor(0, 0), 0, 0);
+        assert_eq!(count_dots(&grid), 9);
+        assert!(grid.is_dot_set(2, 2) && !grid.is_dot_set(3, 3));
+    }
+
+    #[test]
+    fn test_stamp_colors_touched_cells() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        let red = Color::rgb(255, 0, 0);
+        grid.stamp(&Brush::square(1).with_color(red), 2, 5);
+        assert_eq!(grid.get_color(1, 1), Some(red));
+        assert_eq!(grid.get_color(0, 0), None);
+    }
+
+    #[test]
+    fn test_from_grid_round_trip() {
+        let mut src = BrailleGrid::new(2, 1).unwrap();
+        src.set_dot(0, 0).unwrap();
+        src.set_dot(3, 3).unwrap();
+        let brush = Brush::from_grid(&src).unwrap().with_anchor(0, 0);
+        let mut dst = BrailleGrid::new(2, 1).unwrap();
+        dst.stamp(&brush, 0, 0);
+        assert_eq!(dst.get_raw_patterns(), src.get_raw_patterns());
+    }
+
+    #[test]
+    fn test_stroke_spacing() {
+        let dot = Brush::square(1);
+        let mut solid = BrailleGrid::new(10, 1).unwrap();
+        stroke_path(&mut solid, &dot, &[(0, 0), (19, 0)], 1).unwrap();
+        assert_eq!(count_dots(&solid), 20);
+
+        let mut dotted = BrailleGrid::new(10, 1).unwrap();
+        stroke_path(&mut dotted, &dot, &[(0, 0), (9, 0), (9, 3)], 3).unwrap();
+        // Stamps at 0,3,6,9 along x then (9,3) after turning the corner
+        assert_eq!(count_dots(&dotted), 5);
+        assert!(dotted.is_dot_set(9, 3));
+
+        assert!(stroke_path(&mut dotted, &dot, &[(0, 0)], 0).is_err());
+        assert!(stroke_path(&mut dotted, &dot, &[], 1).is_ok());
+    }
+}
@@ -0,0 +1,357 @@
+//! Drawing in world coordinates instead of dots.
+//!
+//! A [`Canvas`] borrows a [`BrailleGrid`] and maps a rectangle of `f64`
+//! world space onto its dots, so data can be drawn in its own units. By
+//! default the y axis points up, as on a chart: the start of the y range is
+//! the bottom row. Either axis can be flipped.
+//!
+//! Anything drawn outside the ranges is clipped. Lines are clipped before
+//! they are rasterized, so a segment running to `1e12` costs no more than
+//! one that stays on screen.
+//!
+//! The canvas works alongside the dot-space primitives: [`Canvas::to_dot`]
+//! converts a point for them, [`Canvas::stamp`] places a [`Brush`] or
+//! [`Marker`](super::Marker) at a world position, and
+//! [`Canvas::grid_mut`] hands back the grid.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::primitives::Canvas;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?; // 80×40 dots
+//! let mut canvas = Canvas::new(&mut grid, 0.0..std::f64::consts::TAU, -1.0..1.0)?;
+//!
+//! // A sine wave, sampled once per dot column
+//! let points: Vec<(f64, f64)> = (0..80)
+//!     .map(|i| {
+//!         let x = f64::from(i) / 79.0 * std::f64::consts::TAU;
+//!         (x, x.sin())
+//!     })
+//!     .collect();
+//! canvas.polyline(&points)?;
+//!
+//! // The x axis
+//! canvas.line(0.0, 0.0, std::f64::consts::TAU, 0.0)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::ops::Range;
+
+use super::brush::Brush;
+use super::line::draw_line;
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// A world-coordinate view of a [`BrailleGrid`].
+#[derive(Debug)]
+pub struct Canvas<'g> {
+    grid: &'g mut BrailleGrid,
+    x_range: Range<f64>,
+    y_range: Range<f64>,
+}
+
+impl<'g> Canvas<'g> {
+    /// Maps `x_range` across the grid's dot columns, left to right, and
+    /// `y_range` up its dot rows, bottom to top.
+    ///
+    /// Both ends of each range are inside the canvas: `x_range.start` lands
+    /// on the first dot column and `x_range.end` on the last.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidRange`] if either range has equal ends
+    /// or an end that is NaN or infinite.
+    pub fn new(
+        grid: &'g mut BrailleGrid,
+        x_range: Range<f64>,
+        y_range: Range<f64>,
+    ) -> Result<Self, DotmaxError> {
+        check_range("x", &x_range)?;
+        check_range("y", &y_range)?;
+        Ok(Self {
+            grid,
+            x_range,
+            y_range,
+        })
+    }
+
+    /// Mirrors the x axis, so `x_range.start` is on the right.
+    #[must_use]
+    pub const fn flip_x(mut self) -> Self {
+        self.x_range = self.x_range.end..self.x_range.start;
+        self
+    }
+
+    /// Mirrors the y axis, so `y_range.start` is on the top row (the
+    /// grid's own orientation).
+    #[must_use]
+    pub const fn flip_y(mut self) -> Self {
+        self.y_range = self.y_range.end..self.y_range.start;
+        self
+    }
+
+    /// The x range, from the left edge to the right edge.
+    #[must_use]
+    pub fn x_range(&self) -> Range<f64> {
+        self.x_range.clone()
+    }
+
+    /// The y range, from the bottom edge to the top edge.
+    #[must_use]
+    pub fn y_range(&self) -> Range<f64> {
+        self.y_range.clone()
+    }
+
+    /// The underlying grid.
+    #[must_use]
+    pub fn grid(&self) -> &BrailleGrid {
+        self.grid
+    }
+
+    /// The underlying grid, for drawing with dot-space primitives.
+    pub fn grid_mut(&mut self) -> &mut BrailleGrid {
+        self.grid
+    }
+
+    /// The dot nearest world point `(x, y)`, or `None` if it falls outside
+    /// the grid.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn to_dot(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        let (dot_x, dot_y) = self.to_dot_f64(x, y);
+        let (dot_x, dot_y) = (dot_x.round(), dot_y.round());
+        let in_bounds = |value: f64, len: usize| (0.0..len as f64).contains(&value);
+        (in_bounds(dot_x, self.grid.dot_width()) && in_bounds(dot_y, self.grid.dot_height()))
+            .then_some((dot_x as usize, dot_y as usize))
+    }
+
+    /// The world point at the center of dot `(dot_x, dot_y)`.
+    #[must_use]
+    pub fn to_world(&self, dot_x: usize, dot_y: usize) -> (f64, f64) {
+        let (max_x, max_y) = self.dot_extent();
+        let fx = if max_x == 0.0 {
+            0.0
+        } else {
+            dot_x as f64 / max_x
+        };
+        let fy = if max_y == 0.0 {
+            0.0
+        } else {
+            1.0 - dot_y as f64 / max_y
+        };
+        (lerp(&self.x_range, fx), lerp(&self.y_range, fy))
+    }
+
+    /// Sets the dot at world point `(x, y)`. Points outside the canvas, and
+    /// NaN coordinates, are ignored.
+    pub fn plot(&mut self, x: f64, y: f64) {
+        if let Some((dot_x, dot_y)) = self.to_dot(x, y) {
+            // In bounds by construction
+            let _ = self.grid.set_dot(dot_x, dot_y);
+        }
+    }
+
+    /// Draws a line between two world points, clipped to the canvas.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`draw_line`]; with endpoints clipped to the
+    /// grid there are none in practice.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn line(&mut self, x0: f64, y0: f64, x1: f64, y1: f64) -> Result<(), DotmaxError> {
+        let start = self.to_dot_f64(x0, y0);
+        let end = self.to_dot_f64(x1, y1);
+        if ![start.0, start.1, end.0, end.1]
+            .iter()
+            .all(|v| v.is_finite())
+        {
+            return Ok(());
+        }
+
+        // Clip to one dot beyond each edge, so rounding the clipped ends
+        // never pulls a visible dot off the line
+        let (max_x, max_y) = self.dot_extent();
+        let bounds = ((-1.0, -1.0), (max_x + 1.0, max_y + 1.0));
+        let Some((start, end)) = clip_segment(start, end, bounds.0, bounds.1) else {
+            return Ok(());
+        };
+        draw_line(
+            self.grid,
+            start.0.round() as i32,
+            start.1.round() as i32,
+            end.0.round() as i32,
+            end.1.round() as i32,
+        )
+    }
+
+    /// Draws connected lines through `points`. A single point is plotted.
+    ///
+    /// # Errors
+    ///
+    /// As for [`line`](Self::line).
+    pub fn polyline(&mut self, points: &[(f64, f64)]) -> Result<(), DotmaxError> {
+        if let [(x, y)] = points {
+            self.plot(*x, *y);
+        }
+        for pair in points.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            self.line(x0, y0, x1, y1)?;
+        }
+        Ok(())
+    }
+
+    /// Stamps `brush` with its anchor on world point `(x, y)`, clipping at
+    /// the grid edges. NaN coordinates are ignored.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn stamp(&mut self, brush: &Brush, x: f64, y: f64) {
+        let (dot_x, dot_y) = self.to_dot_f64(x, y);
+        if dot_x.is_nan() || dot_y.is_nan() {
+            return;
+        }
+        // Saturating casts keep far-off points far off
+        self.grid
+            .stamp(brush, dot_x.round() as i32, dot_y.round() as i32);
+    }
+
+    /// The largest dot coordinate on each axis, as `f64`.
+    fn dot_extent(&self) -> (f64, f64) {
+        (
+            self.grid.dot_width().saturating_sub(1) as f64,
+            self.grid.dot_height().saturating_sub(1) as f64,
+        )
+    }
+
+    /// World to fractional dot coordinates, unclipped.
+    fn to_dot_f64(&self, x: f64, y: f64) -> (f64, f64) {
+        let (max_x, max_y) = self.dot_extent();
+        (
+            unlerp(&self.x_range, x) * max_x,
+            (1.0 - unlerp(&self.y_range, y)) * max_y,
+        )
+    }
+}
+
+fn check_range(axis: &str, range: &Range<f64>) -> Result<(), DotmaxError> {
+    if range.start.is_finite() && range.end.is_finite() && range.end - range.start != 0.0 {
+        Ok(())
+    } else {
+        Err(DotmaxError::InvalidRange {
+            axis: axis.to_string(),
+            start: range.start,
+            end: range.end,
+        })
+    }
+}
+
+/// Where `value` sits in `range`: 0.0 at the start, 1.0 at the end.
+fn unlerp(range: &Range<f64>, value: f64) -> f64 {
+    (value - range.start) / (range.end - range.start)
+}
+
+fn lerp(range: &Range<f64>, t: f64) -> f64 {
+    (range.end - range.start).mul_add(t, range.start)
+}
+
+/// Liang-Barsky clipping of the segment `p0`-`p1` to the box `min`-`max`.
+pub(super) fn clip_segment(
+    p0: (f64, f64),
+    p1: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-dx, p0.0 - min.0),
+        (dx, max.0 - p0.0),
+        (-dy, p0.1 - min.1),
+        (dy, max.1 - p0.1),
+    ] {
+        if p == 0.0 {
+            // Parallel to this edge: entirely inside or entirely outside
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    (t0 <= t1).then(|| {
+        (
+            (dx.mul_add(t0, p0.0), dy.mul_add(t0, p0.1)),
+            (dx.mul_add(t1, p0.0), dy.mul_add(t1, p0.1)),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corners_map_to_grid_corners() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap(); // 20×20 dots
+        let canvas = Canvas::new(&mut grid, -1.0..1.0, 0.0..100.0).unwrap();
+        assert_eq!(canvas.to_dot(-1.0, 0.0), Some((0, 19)));
+        assert_eq!(canvas.to_dot(1.0, 100.0), Some((19, 0)));
+        assert_eq!(canvas.to_dot(1.1, 50.0), None);
+        assert_eq!(canvas.to_dot(f64::NAN, 50.0), None);
+        assert_eq!(canvas.to_world(19, 0), (1.0, 100.0));
+    }
+
+    #[test]
+    fn test_flipping_axes() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        let canvas = Canvas::new(&mut grid, 0.0..1.0, 0.0..1.0)
+            .unwrap()
+            .flip_x()
+            .flip_y();
+        assert_eq!(canvas.to_dot(0.0, 0.0), Some((19, 0)));
+        assert_eq!(canvas.x_range(), 1.0..0.0);
+    }
+
+    #[test]
+    fn test_plot_and_line() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        let mut canvas = Canvas::new(&mut grid, 0.0..19.0, 0.0..19.0).unwrap();
+        canvas.plot(3.0, 19.0);
+        canvas.plot(100.0, 100.0); // ignored
+        canvas.line(0.0, 0.0, 19.0, 0.0).unwrap();
+        assert!(grid.is_dot_set(3, 0));
+        assert!((0..20).all(|x| grid.is_dot_set(x, 19)));
+    }
+
+    #[test]
+    fn test_far_off_lines_are_clipped_first() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        let mut canvas = Canvas::new(&mut grid, 0.0..1.0, 0.0..1.0).unwrap();
+        // Would be ~1e13 Bresenham steps unclipped
+        canvas.line(-1e12, 0.5, 1e12, 0.5).unwrap();
+        canvas.line(5.0, 5.0, 6.0, 6.0).unwrap(); // entirely off canvas
+        canvas.line(f64::INFINITY, 0.0, 0.0, 0.0).unwrap();
+        let set = (0..20).filter(|&x| grid.is_dot_set(x, 10)).count();
+        assert_eq!(set, 20);
+    }
+
+    #[test]
+    fn test_stamp_at_world_point() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        let mut canvas = Canvas::new(&mut grid, 0.0..19.0, 0.0..19.0).unwrap();
+        canvas.stamp(&Brush::square(3), 10.0, 9.0);
+        assert!(grid.is_dot_set(10, 10));
+    }
+
+    #[test]
+    fn test_rejects_degenerate_ranges() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        assert!(matches!(
+            Canvas::new(&mut grid, 1.0..1.0, 0.0..1.0),
+            Err(DotmaxError::InvalidRange { ref axis, .. }) if axis == "x"
+        ));
+        assert!(Canvas::new(&mut grid, 0.0..1.0, 0.0..f64::NAN).is_err());
+    }
+}
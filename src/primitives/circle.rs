@@ -230,6 +230,99 @@ pub fn draw_circle_thick(
     Ok(())
 }
 
+/// Draw a circle that looks round with the current font's dot spacing.
+///
+/// Uses the dot aspect from [`cell_metrics`](crate::utils::cell_metrics::cell_metrics)
+/// (set after calibration) to shorten or stretch the vertical radius, so the
+/// shape is drawn as an ellipse in dot space but appears circular on screen.
+/// With default metrics this matches [`draw_circle`] / [`draw_circle_filled`].
+///
+/// # Arguments
+///
+/// * `grid` - Mutable reference to `BrailleGrid` to draw on
+/// * `center_x`, `center_y` - Circle center in dot coordinates (signed for clipping)
+/// * `radius` - Horizontal radius in dots
+/// * `filled` - Fill the interior instead of drawing the outline
+///
+/// # Errors
+///
+/// May propagate errors from `draw_line()` if grid operations fail.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::utils::cell_metrics::{set_cell_metrics, CellMetrics};
+/// use dotmax::{BrailleGrid, primitives::draw_circle_aspect};
+///
+/// let mut grid = BrailleGrid::new(80, 24)?;
+/// set_cell_metrics(CellMetrics::new(1.2)?); // from calibration
+/// draw_circle_aspect(&mut grid, 80, 48, 30, false)?; // 30 dots wide, 25 tall
+/// # set_cell_metrics(CellMetrics::default());
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss
+)]
+pub fn draw_circle_aspect(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius: u32,
+    filled: bool,
+) -> Result<(), DotmaxError> {
+    let aspect = crate::utils::cell_metrics::cell_metrics().dot_aspect();
+    if (aspect - 1.0).abs() < f32::EPSILON {
+        return if filled {
+            draw_circle_filled(grid, center_x, center_y, radius)
+        } else {
+            draw_circle(grid, center_x, center_y, radius)
+        };
+    }
+    if radius == 0 {
+        plot_dot_clipped(grid, center_x, center_y);
+        return Ok(());
+    }
+
+    let rx = radius as f32;
+    let ry = (rx / aspect).max(0.5);
+
+    if filled {
+        // Scanline fill using the ellipse equation: x = rx * sqrt(1 - (y/ry)²)
+        let ry_i32 = ry.round() as i32;
+        for dy in -ry_i32..=ry_i32 {
+            let t = (dy as f32 / ry).clamp(-1.0, 1.0);
+            let x_offset = (rx * t.mul_add(-t, 1.0).sqrt()).round() as i32;
+            draw_line(
+                grid,
+                center_x - x_offset,
+                center_y + dy,
+                center_x + x_offset,
+                center_y + dy,
+            )?;
+        }
+        return Ok(());
+    }
+
+    // Outline: join points sampled densely enough that segments are ~1 dot long
+    let steps = ((rx.max(ry) * std::f32::consts::TAU).ceil() as u32).max(8);
+    let point = |i: u32| {
+        let angle = i as f32 / steps as f32 * std::f32::consts::TAU;
+        (
+            center_x + (rx * angle.cos()).round() as i32,
+            center_y + (ry * angle.sin()).round() as i32,
+        )
+    };
+    let mut prev = point(0);
+    for i in 1..=steps {
+        let next = point(i);
+        draw_line(grid, prev.0, prev.1, next.0, next.1)?;
+        prev = next;
+    }
+    Ok(())
+}
+
 /// Plot 8 symmetric dots for the midpoint circle algorithm.
 ///
 /// Given a point (x, y) relative to center, plots all 8 symmetric points:
@@ -674,4 +767,30 @@ mod tests {
         let dot_count = count_dots(&grid);
         assert!(dot_count > 200, "Large circle should have many dots");
     }
+
+    #[test]
+    fn test_draw_circle_aspect_follows_cell_metrics() {
+        use crate::utils::cell_metrics::{set_cell_metrics, CellMetrics};
+
+        // Single test so no other test observes the changed global metrics
+        let mut plain = BrailleGrid::new(40, 20).unwrap();
+        let mut aspect = BrailleGrid::new(40, 20).unwrap();
+        draw_circle(&mut plain, 40, 40, 20).unwrap();
+        draw_circle_aspect(&mut aspect, 40, 40, 20, false).unwrap();
+        assert_eq!(plain.get_raw_patterns(), aspect.get_raw_patterns());
+
+        set_cell_metrics(CellMetrics::new(2.0).unwrap());
+        let mut outline = BrailleGrid::new(40, 20).unwrap();
+        let mut filled = BrailleGrid::new(40, 20).unwrap();
+        draw_circle_aspect(&mut outline, 40, 40, 20, false).unwrap();
+        draw_circle_aspect(&mut filled, 40, 40, 20, true).unwrap();
+        set_cell_metrics(CellMetrics::default());
+
+        // Full width, half height
+        assert!(is_dot_set(&outline, 20, 40) && is_dot_set(&outline, 60, 40));
+        assert!(is_dot_set(&outline, 40, 30) && is_dot_set(&outline, 40, 50));
+        assert!(!is_dot_set(&outline, 40, 20));
+        assert!(is_dot_set(&filled, 40, 40));
+        assert!(!is_dot_set(&filled, 40, 25));
+    }
 }
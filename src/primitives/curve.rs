@@ -0,0 +1,406 @@
+//! Bezier curves and circular arcs.
+//!
+//! Curves are flattened into short straight segments, each about two dots
+//! long, and the segments are walked with Bresenham steps. Thick and colored
+//! variants stroke the same path with a round [`Brush`], so the stroke is
+//! centered on the curve and keeps its width through bends.
+//!
+//! ## Coordinate System
+//!
+//! Functions use **dot coordinates** (not cell coordinates). Points are
+//! signed `(x, y)` pairs, so curves may start, end, or bend outside the grid;
+//! off-grid dots are clipped.
+//!
+//! ## References
+//!
+//! - <https://en.wikipedia.org/wiki/B%C3%A9zier_curve>
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::primitives::{draw_arc, draw_cubic_bezier_colored};
+//! use dotmax::{BrailleGrid, Color};
+//! use std::f64::consts::PI;
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+//!
+//! // An S-curve, 2 dots thick
+//! let blue = Color::rgb(80, 160, 255);
+//! draw_cubic_bezier_colored(&mut grid, (0, 40), (30, -20), (50, 70), (79, 10), blue, Some(2))?;
+//!
+//! // The bottom half of a circle: from the right (0) clockwise to the left (π)
+//! draw_arc(&mut grid, 40, 24, 20, 0.0, PI)?;
+//! assert!(grid.is_dot_set(40, 44));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::f64::consts::TAU;
+
+use super::brush::{stroke_path, Brush};
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// A point in dot coordinates.
+type Point = (i32, i32);
+
+/// Draw a quadratic Bezier curve from `p0` to `p2`, pulled toward the
+/// control point `p1`.
+///
+/// # Errors
+///
+/// Currently no error conditions; returns `Result` for consistency with
+/// the other primitives.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_quadratic_bezier};
+///
+/// let mut grid = BrailleGrid::new(40, 10)?;
+/// draw_quadratic_bezier(&mut grid, (0, 39), (40, -39), (79, 39))?; // an arch
+/// assert!(grid.is_dot_set(40, 0));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_quadratic_bezier(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+) -> Result<(), DotmaxError> {
+    stroke(grid, &flatten_quadratic(p0, p1, p2), 1, None)
+}
+
+/// Draw a quadratic Bezier curve `thickness` dots wide.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is 0.
+pub fn draw_quadratic_bezier_thick(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    thickness: u32,
+) -> Result<(), DotmaxError> {
+    stroke(grid, &flatten_quadratic(p0, p1, p2), thickness, None)
+}
+
+/// Draw a quadratic Bezier curve and color the cells it passes through.
+///
+/// `thickness` of `None` draws a 1-dot curve, like
+/// [`draw_line_colored`](super::draw_line_colored).
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is `Some(0)`.
+pub fn draw_quadratic_bezier_colored(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    color: Color,
+    thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    let points = flatten_quadratic(p0, p1, p2);
+    stroke(grid, &points, thickness.unwrap_or(1), Some(color))
+}
+
+/// Draw a cubic Bezier curve from `p0` to `p3`, leaving `p0` toward `p1`
+/// and arriving at `p3` from `p2`.
+///
+/// # Errors
+///
+/// Currently no error conditions; returns `Result` for consistency with
+/// the other primitives.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_cubic_bezier};
+///
+/// let mut grid = BrailleGrid::new(40, 10)?;
+/// draw_cubic_bezier(&mut grid, (0, 20), (20, -10), (60, 50), (79, 20))?;
+/// assert!(grid.is_dot_set(0, 20) && grid.is_dot_set(79, 20));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_cubic_bezier(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+) -> Result<(), DotmaxError> {
+    stroke(grid, &flatten_cubic(p0, p1, p2, p3), 1, None)
+}
+
+/// Draw a cubic Bezier curve `thickness` dots wide.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is 0.
+pub fn draw_cubic_bezier_thick(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    thickness: u32,
+) -> Result<(), DotmaxError> {
+    stroke(grid, &flatten_cubic(p0, p1, p2, p3), thickness, None)
+}
+
+/// Draw a cubic Bezier curve and color the cells it passes through.
+///
+/// `thickness` of `None` draws a 1-dot curve.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is `Some(0)`.
+pub fn draw_cubic_bezier_colored(
+    grid: &mut BrailleGrid,
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    color: Color,
+    thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    let points = flatten_cubic(p0, p1, p2, p3);
+    stroke(grid, &points, thickness.unwrap_or(1), Some(color))
+}
+
+/// Draw part of a circle outline, from `start_angle` to `end_angle`.
+///
+/// Angles are in radians, with 0 pointing right. Because the grid's y axis
+/// points down, increasing angles turn clockwise on screen: π/2 is straight
+/// down. The arc runs from the start angle to the end angle, so a negative
+/// sweep (`end_angle < start_angle`) turns counter-clockwise. Sweeps beyond
+/// a full turn draw the whole circle.
+///
+/// # Errors
+///
+/// Currently no error conditions; returns `Result` for consistency with
+/// the other primitives.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_arc};
+/// use std::f64::consts::FRAC_PI_2;
+///
+/// let mut grid = BrailleGrid::new(20, 10)?;
+/// // Top-right quarter: from straight up (-π/2) clockwise to the right (0)
+/// draw_arc(&mut grid, 20, 20, 15, -FRAC_PI_2, 0.0)?;
+/// assert!(grid.is_dot_set(20, 5) && grid.is_dot_set(35, 20));
+/// assert!(!grid.is_dot_set(5, 20));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_arc(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius: u32,
+    start_angle: f64,
+    end_angle: f64,
+) -> Result<(), DotmaxError> {
+    let points = flatten_arc((center_x, center_y), radius, start_angle, end_angle);
+    stroke(grid, &points, 1, None)
+}
+
+/// Draw an arc `thickness` dots wide, centered on the radius.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is 0.
+pub fn draw_arc_thick(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius: u32,
+    start_angle: f64,
+    end_angle: f64,
+    thickness: u32,
+) -> Result<(), DotmaxError> {
+    let points = flatten_arc((center_x, center_y), radius, start_angle, end_angle);
+    stroke(grid, &points, thickness, None)
+}
+
+/// Draw an arc and color the cells it passes through.
+///
+/// `thickness` of `None` draws a 1-dot arc.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is `Some(0)`.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_arc_colored(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius: u32,
+    start_angle: f64,
+    end_angle: f64,
+    color: Color,
+    thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    let points = flatten_arc((center_x, center_y), radius, start_angle, end_angle);
+    stroke(grid, &points, thickness.unwrap_or(1), Some(color))
+}
+
+/// Strokes `points` with a round tip `thickness` dots across.
+fn stroke(
+    grid: &mut BrailleGrid,
+    points: &[Point],
+    thickness: u32,
+    color: Option<Color>,
+) -> Result<(), DotmaxError> {
+    if thickness == 0 {
+        return Err(DotmaxError::InvalidThickness { thickness: 0 });
+    }
+    let mut brush = Brush::round(thickness as usize);
+    if let Some(color) = color {
+        brush = brush.with_color(color);
+    }
+    stroke_path(grid, &brush, points, 1)
+}
+
+/// Samples `point(t)` for t in 0..=1 often enough that consecutive samples
+/// are about two dots apart along a path at most `length` dots long.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn sample(length: f64, point: impl Fn(f64) -> (f64, f64)) -> Vec<Point> {
+    // Cap the segment count so wild control points can't allocate unbounded
+    let segments = (length / 2.0).ceil().clamp(1.0, 10_000.0) as u32;
+    let mut points: Vec<Point> = Vec::with_capacity(segments as usize + 1);
+    for i in 0..=segments {
+        let (x, y) = point(f64::from(i) / f64::from(segments));
+        let dot = (x.round() as i32, y.round() as i32);
+        if points.last() != Some(&dot) {
+            points.push(dot);
+        }
+    }
+    points
+}
+
+/// Length of the polyline through `points`, an upper bound on the length
+/// of a Bezier curve with those control points.
+fn control_length(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0).hypot(pair[1].1 - pair[0].1))
+        .sum()
+}
+
+fn flatten_quadratic(p0: Point, p1: Point, p2: Point) -> Vec<Point> {
+    let [p0, p1, p2] = [p0, p1, p2].map(|(x, y)| (f64::from(x), f64::from(y)));
+    sample(control_length(&[p0, p1, p2]), |t| {
+        let u = 1.0 - t;
+        let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+        (
+            a.mul_add(p0.0, b.mul_add(p1.0, c * p2.0)),
+            a.mul_add(p0.1, b.mul_add(p1.1, c * p2.1)),
+        )
+    })
+}
+
+fn flatten_cubic(p0: Point, p1: Point, p2: Point, p3: Point) -> Vec<Point> {
+    let [p0, p1, p2, p3] = [p0, p1, p2, p3].map(|(x, y)| (f64::from(x), f64::from(y)));
+    sample(control_length(&[p0, p1, p2, p3]), |t| {
+        let u = 1.0 - t;
+        // Bernstein weights
+        let w = [u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t];
+        (
+            w[0].mul_add(p0.0, w[1].mul_add(p1.0, w[2].mul_add(p2.0, w[3] * p3.0))),
+            w[0].mul_add(p0.1, w[1].mul_add(p1.1, w[2].mul_add(p2.1, w[3] * p3.1))),
+        )
+    })
+}
+
+fn flatten_arc(center: Point, radius: u32, start_angle: f64, end_angle: f64) -> Vec<Point> {
+    let sweep = (end_angle - start_angle).clamp(-TAU, TAU);
+    let (cx, cy) = (f64::from(center.0), f64::from(center.1));
+    let radius = f64::from(radius);
+    sample(radius * sweep.abs(), |t| {
+        let angle = sweep.mul_add(t, start_angle);
+        (
+            radius.mul_add(angle.cos(), cx),
+            radius.mul_add(angle.sin(), cy),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_straight_bezier_matches_line() {
+        let mut curve = BrailleGrid::new(20, 5).unwrap();
+        let mut line = BrailleGrid::new(20, 5).unwrap();
+        draw_quadratic_bezier(&mut curve, (0, 0), (20, 10), (39, 19)).unwrap();
+        draw_cubic_bezier(&mut line, (0, 0), (13, 6), (26, 13), (39, 19)).unwrap();
+        // Both are straight diagonals: every column has exactly one dot
+        for grid in [&curve, &line] {
+            for x in 0..40 {
+                let set = (0..20).filter(|&y| grid.is_dot_set(x, y)).count();
+                assert!((1..=2).contains(&set), "column {x} has {set} dots");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bezier_endpoints_and_bulge() {
+        let mut grid = BrailleGrid::new(40, 10).unwrap();
+        draw_quadratic_bezier(&mut grid, (0, 39), (40, -39), (79, 39)).unwrap();
+        assert!(grid.is_dot_set(0, 39) && grid.is_dot_set(79, 39));
+        // The midpoint of an arch is halfway to the control point
+        assert!(grid.is_dot_set(40, 0));
+        assert!(!grid.is_dot_set(40, 39));
+    }
+
+    #[test]
+    fn test_arc_directions() {
+        let mut clockwise = BrailleGrid::new(20, 10).unwrap();
+        draw_arc(&mut clockwise, 20, 20, 10, 0.0, PI).unwrap();
+        assert!(clockwise.is_dot_set(20, 30)); // bottom
+        assert!(!clockwise.is_dot_set(20, 10)); // top
+
+        let mut counter = BrailleGrid::new(20, 10).unwrap();
+        draw_arc(&mut counter, 20, 20, 10, 0.0, -PI).unwrap();
+        assert!(counter.is_dot_set(20, 10));
+        assert!(!counter.is_dot_set(20, 30));
+
+        let mut full = BrailleGrid::new(20, 10).unwrap();
+        draw_arc(&mut full, 20, 20, 10, 0.0, 10.0 * PI).unwrap();
+        assert!(full.is_dot_set(20, 10) && full.is_dot_set(20, 30));
+    }
+
+    #[test]
+    fn test_thick_and_colored_variants() {
+        let red = Color::rgb(255, 0, 0);
+        let mut grid = BrailleGrid::new(20, 10).unwrap();
+        draw_arc_thick(&mut grid, 20, 20, 10, 0.0, PI, 3).unwrap();
+        assert!(grid.is_dot_set(20, 29) && grid.is_dot_set(20, 30) && grid.is_dot_set(20, 31));
+
+        draw_cubic_bezier_colored(&mut grid, (0, 0), (5, 0), (10, 0), (15, 0), red, None).unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(red));
+        assert_eq!(grid.get_color(7, 0), Some(red));
+        assert_eq!(grid.get_color(9, 0), None);
+
+        assert!(matches!(
+            draw_quadratic_bezier_thick(&mut grid, (0, 0), (1, 1), (2, 2), 0),
+            Err(DotmaxError::InvalidThickness { thickness: 0 })
+        ));
+        assert!(draw_arc_colored(&mut grid, 0, 0, 5, 0.0, 1.0, red, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_clipping_and_degenerate_curves() {
+        let mut grid = BrailleGrid::new(5, 2).unwrap();
+        draw_cubic_bezier(&mut grid, (-100, -100), (500, 0), (-500, 8), (100, 100)).unwrap();
+        draw_quadratic_bezier(&mut grid, (3, 3), (3, 3), (3, 3)).unwrap();
+        assert!(grid.is_dot_set(3, 3));
+        draw_arc(&mut grid, 1, 1, 0, 0.0, PI).unwrap();
+        assert!(grid.is_dot_set(1, 1));
+    }
+}
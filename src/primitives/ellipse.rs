@@ -0,0 +1,390 @@
+//! Axis-aligned ellipses using the midpoint ellipse algorithm.
+//!
+//! The midpoint ellipse algorithm extends Bresenham's circle algorithm to
+//! two radii. It walks one quadrant in two regions, stepping in x where the
+//! curve is flatter than 45° and in y where it is steeper, and mirrors each
+//! point into the other three quadrants.
+//!
+//! ## Algorithm Properties
+//!
+//! - **Integer-only**: Decision variables are kept in `i64`, scaled by 4 to
+//!   avoid the algorithm's half-dot fractions
+//! - **O(rx + ry) complexity**
+//! - **4-way symmetry**: Plot 4 mirrored points per iteration
+//!
+//! ## Coordinate System
+//!
+//! Functions use **dot coordinates** (not cell coordinates), with a signed
+//! center for clipping and unsigned radii. A radius of 0 flattens the
+//! ellipse to a line.
+//!
+//! ## References
+//!
+//! - Foley & Van Dam, "Computer Graphics: Principles and Practice", Section 3.3
+//! - <https://en.wikipedia.org/wiki/Midpoint_circle_algorithm>
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+use crate::primitives::{draw_line, draw_line_colored};
+
+/// Draw an ellipse outline with horizontal radius `radius_x` and vertical
+/// radius `radius_y`.
+///
+/// With equal radii this draws the same circle as
+/// [`draw_circle`](super::draw_circle).
+///
+/// # Errors
+///
+/// Currently no error conditions; returns `Result` for consistency with
+/// the other primitives.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_ellipse};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+/// draw_ellipse(&mut grid, 40, 24, 30, 15)?;
+/// assert!(grid.is_dot_set(70, 24) && grid.is_dot_set(40, 9));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_ellipse(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius_x: u32,
+    radius_y: u32,
+) -> Result<(), DotmaxError> {
+    let (max_x, max_y) = dot_bounds(grid);
+    for_each_outline_point(radius_x, radius_y, |x, y| {
+        for (px, py) in mirrored(center_x, center_y, x, y) {
+            if px >= 0 && py >= 0 && px < max_x && py < max_y {
+                // Safe to convert to usize - we checked px >= 0 and py >= 0
+                #[allow(clippy::cast_sign_loss)]
+                let _ = grid.set_dot(px as usize, py as usize);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Draw a filled ellipse.
+///
+/// # Errors
+///
+/// May propagate errors from `draw_line()` if grid operations fail.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_ellipse_filled};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?;
+/// draw_ellipse_filled(&mut grid, 40, 24, 30, 15)?;
+/// assert!(grid.is_dot_set(40, 24) && grid.is_dot_set(65, 24));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_ellipse_filled(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius_x: u32,
+    radius_y: u32,
+) -> Result<(), DotmaxError> {
+    for (dy, span) in scanlines(radius_x, radius_y) {
+        draw_line(
+            grid,
+            center_x - span,
+            center_y + dy,
+            center_x + span,
+            center_y + dy,
+        )?;
+    }
+    Ok(())
+}
+
+/// Draw a thick ellipse outline.
+///
+/// Like [`draw_circle_thick`](super::draw_circle_thick), the outline grows
+/// outward: it covers the ring between the ellipse with the given radii and
+/// the one with both radii `thickness - 1` larger, with no gaps between
+/// rings.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidThickness`] if `thickness` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::draw_ellipse_thick};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?;
+/// draw_ellipse_thick(&mut grid, 40, 24, 30, 15, 3)?;
+/// assert!(grid.is_dot_set(70, 24) && grid.is_dot_set(72, 24));
+/// assert!(!grid.is_dot_set(40, 24));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_ellipse_thick(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius_x: u32,
+    radius_y: u32,
+    thickness: u32,
+) -> Result<(), DotmaxError> {
+    if thickness == 0 {
+        return Err(DotmaxError::InvalidThickness { thickness: 0 });
+    }
+    if thickness == 1 {
+        return draw_ellipse(grid, center_x, center_y, radius_x, radius_y);
+    }
+    ring(grid, center_x, center_y, radius_x, radius_y, thickness)
+}
+
+/// Draw an ellipse, outline or filled, and color the cells it covers.
+///
+/// # Errors
+///
+/// May propagate errors from `draw_line_colored()` if grid operations fail.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, Color, primitives::draw_ellipse_colored};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?;
+/// let green = Color::rgb(0, 200, 80);
+/// draw_ellipse_colored(&mut grid, 40, 24, 30, 15, green, false)?;
+/// assert_eq!(grid.get_color(35, 6), Some(green)); // rightmost dot, (70, 24)
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_ellipse_colored(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius_x: u32,
+    radius_y: u32,
+    color: Color,
+    filled: bool,
+) -> Result<(), DotmaxError> {
+    if filled {
+        for (dy, span) in scanlines(radius_x, radius_y) {
+            let y = center_y + dy;
+            draw_line_colored(grid, center_x - span, y, center_x + span, y, color, None)?;
+        }
+        return Ok(());
+    }
+    let (max_x, max_y) = dot_bounds(grid);
+    for_each_outline_point(radius_x, radius_y, |x, y| {
+        for (px, py) in mirrored(center_x, center_y, x, y) {
+            if px >= 0 && py >= 0 && px < max_x && py < max_y {
+                #[allow(clippy::cast_sign_loss)]
+                let (dot_x, dot_y) = (px as usize, py as usize);
+                let _ = grid.set_dot(dot_x, dot_y);
+                let _ = grid.set_cell_color(dot_x / 2, dot_y / 4, color);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Grid size in dots, as signed bounds for clipping.
+const fn dot_bounds(grid: &BrailleGrid) -> (i32, i32) {
+    // Safe casts: grid dimensions are bounded by terminal size (<10000)
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    (grid.dot_width() as i32, grid.dot_height() as i32)
+}
+
+/// The four points mirroring quadrant offset `(x, y)` about the center.
+const fn mirrored(center_x: i32, center_y: i32, x: i32, y: i32) -> [(i32, i32); 4] {
+    [
+        (center_x + x, center_y + y),
+        (center_x - x, center_y + y),
+        (center_x + x, center_y - y),
+        (center_x - x, center_y - y),
+    ]
+}
+
+/// Calls `plot` with each outline offset `(x, y)` of one quadrant, from
+/// `(0, radius_y)` to `(radius_x, 0)`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn for_each_outline_point(radius_x: u32, radius_y: u32, mut plot: impl FnMut(i32, i32)) {
+    // A zero radius flattens the ellipse to a line along the other axis
+    if radius_x == 0 || radius_y == 0 {
+        (0..=radius_x as i32).for_each(|x| plot(x, 0));
+        (0..=radius_y as i32).for_each(|y| plot(0, y));
+        return;
+    }
+    let (rx, ry) = (i64::from(radius_x), i64::from(radius_y));
+    let (rx2, ry2) = (rx * rx, ry * ry);
+    let (mut x, mut y) = (0_i64, ry);
+    // Partial derivatives of the ellipse equation, 2·ry²·x and 2·rx²·y
+    let (mut dx, mut dy) = (0, 2 * rx2 * y);
+
+    // Region 1: slope shallower than -1, step in x
+    let mut decision = 4 * ry2 - 4 * rx2 * ry + rx2;
+    while dx < dy {
+        plot(x as i32, y as i32);
+        x += 1;
+        dx += 2 * ry2;
+        if decision < 0 {
+            decision += 4 * (ry2 + dx);
+        } else {
+            y -= 1;
+            dy -= 2 * rx2;
+            decision += 4 * (ry2 + dx - dy);
+        }
+    }
+
+    // Region 2: slope steeper than -1, step in y
+    decision = ry2 * (2 * x + 1).pow(2) + 4 * rx2 * (y - 1).pow(2) - 4 * rx2 * ry2;
+    while y >= 0 {
+        plot(x as i32, y as i32);
+        y -= 1;
+        dy -= 2 * rx2;
+        if decision > 0 {
+            decision += 4 * (rx2 - dy);
+        } else {
+            x += 1;
+            dx += 2 * ry2;
+            decision += 4 * (rx2 - dy + dx);
+        }
+    }
+}
+
+/// Half-width of the filled ellipse on each row, as `(dy, span)` pairs
+/// from top to bottom. Spans reach the outermost outline dot of the row, so
+/// the fill always covers the outline.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn scanlines(radius_x: u32, radius_y: u32) -> impl Iterator<Item = (i32, i32)> {
+    let mut spans = vec![0; radius_y as usize + 1];
+    for_each_outline_point(radius_x, radius_y, |x, y| {
+        spans[y as usize] = spans[y as usize].max(x);
+    });
+    let ry = radius_y as i32;
+    (-ry..=ry).map(move |dy| (dy, spans[dy.unsigned_abs() as usize]))
+}
+
+/// Fills the ring from the ellipse at `radius_x`, `radius_y` outward by
+/// `thickness` dots, one row at a time.
+#[allow(clippy::cast_sign_loss)]
+fn ring(
+    grid: &mut BrailleGrid,
+    center_x: i32,
+    center_y: i32,
+    radius_x: u32,
+    radius_y: u32,
+    thickness: u32,
+) -> Result<(), DotmaxError> {
+    // Innermost outline dot on each row, so the ring covers the thin outline
+    let mut inner = vec![i32::MAX; radius_y as usize + 1];
+    for_each_outline_point(radius_x, radius_y, |x, y| {
+        inner[y as usize] = inner[y as usize].min(x);
+    });
+
+    let grow = thickness - 1;
+    for (dy, outer) in scanlines(radius_x + grow, radius_y + grow) {
+        let y = center_y + dy;
+        match inner.get(dy.unsigned_abs() as usize) {
+            Some(&inner) if dy.unsigned_abs() < radius_y => {
+                draw_line(grid, center_x + inner, y, center_x + outer, y)?;
+                draw_line(grid, center_x - outer, y, center_x - inner, y)?;
+            }
+            // Above or below the inner ellipse, or on its top or bottom row
+            _ => draw_line(grid, center_x - outer, y, center_x + outer, y)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::draw_circle;
+
+    fn set_dots(grid: &BrailleGrid) -> Vec<(usize, usize)> {
+        (0..grid.dot_height())
+            .flat_map(|y| (0..grid.dot_width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| grid.is_dot_set(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_equal_radii_match_circle() {
+        for radius in [1, 5, 12, 19] {
+            let mut ellipse = BrailleGrid::new(20, 10).unwrap();
+            let mut circle = BrailleGrid::new(20, 10).unwrap();
+            draw_ellipse(&mut ellipse, 20, 20, radius, radius).unwrap();
+            draw_circle(&mut circle, 20, 20, radius).unwrap();
+            assert_eq!(set_dots(&ellipse), set_dots(&circle), "radius {radius}");
+        }
+    }
+
+    #[test]
+    fn test_extremes_and_symmetry() {
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        draw_ellipse(&mut grid, 40, 24, 30, 10).unwrap();
+        for (x, y) in [(70, 24), (10, 24), (40, 14), (40, 34)] {
+            assert!(grid.is_dot_set(x, y), "({x}, {y})");
+        }
+        assert!(!grid.is_dot_set(40, 24));
+        for (x, y) in set_dots(&grid) {
+            assert!(grid.is_dot_set(80 - x, y) && grid.is_dot_set(x, 48 - y));
+        }
+    }
+
+    #[test]
+    fn test_degenerate_radii() {
+        let mut grid = BrailleGrid::new(10, 3).unwrap();
+        draw_ellipse(&mut grid, 10, 6, 5, 0).unwrap();
+        assert_eq!(
+            set_dots(&grid),
+            (5..=15).map(|x| (x, 6)).collect::<Vec<_>>()
+        );
+
+        let mut grid = BrailleGrid::new(10, 3).unwrap();
+        draw_ellipse_filled(&mut grid, 10, 6, 0, 0).unwrap();
+        assert_eq!(set_dots(&grid), [(10, 6)]);
+    }
+
+    #[test]
+    fn test_filled_and_thick() {
+        let mut filled = BrailleGrid::new(20, 10).unwrap();
+        draw_ellipse_filled(&mut filled, 20, 20, 15, 8).unwrap();
+        let mut outline = BrailleGrid::new(20, 10).unwrap();
+        draw_ellipse(&mut outline, 20, 20, 15, 8).unwrap();
+        // The fill covers the outline
+        assert!(set_dots(&outline)
+            .iter()
+            .all(|&(x, y)| filled.is_dot_set(x, y)));
+
+        let mut thick = BrailleGrid::new(20, 10).unwrap();
+        draw_ellipse_thick(&mut thick, 20, 20, 15, 8, 3).unwrap();
+        assert!(set_dots(&outline)
+            .iter()
+            .all(|&(x, y)| thick.is_dot_set(x, y)));
+        // No gaps along the horizontal axis: 15, 16, 17 on each side
+        assert!((35..=37).all(|x| thick.is_dot_set(x, 20)));
+        assert!(!thick.is_dot_set(34, 20) && !thick.is_dot_set(38, 20));
+
+        assert!(matches!(
+            draw_ellipse_thick(&mut thick, 0, 0, 1, 1, 0),
+            Err(DotmaxError::InvalidThickness { thickness: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_colored_and_clipped() {
+        let blue = Color::rgb(0, 0, 255);
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        draw_ellipse_colored(&mut grid, 0, 0, 12, 30, blue, true).unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(blue));
+        assert_eq!(grid.get_color(9, 4), None);
+
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        draw_ellipse_colored(&mut grid, 10, 10, 6, 4, blue, false).unwrap();
+        assert_eq!(grid.get_color(8, 2), Some(blue)); // (16, 10)
+        assert_eq!(grid.get_color(5, 2), None); // center cell
+    }
+}
@@ -0,0 +1,186 @@
+//! Flood fill for regions bounded by set dots.
+//!
+//! [`draw_polygon_filled`](super::draw_polygon_filled) needs the outline as
+//! a vertex list. Flood fill works from what is already on the grid: start
+//! at an empty dot inside any closed outline, however it was drawn, and
+//! every empty dot reachable from it without crossing a set dot is filled.
+//!
+//! ## Algorithm
+//!
+//! Span-based flood fill: each step fills a whole horizontal run of empty
+//! dots and queues the runs directly above and below it, so memory grows
+//! with the number of runs rather than the number of dots.
+//!
+//! Regions are **4-connected** (up, down, left, right). Bresenham lines are
+//! 8-connected, so a diagonal drawn with [`draw_line`](super::draw_line)
+//! still seals a region; an 8-connected fill would leak through it.
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Fill the empty region containing dot `(dot_x, dot_y)`.
+///
+/// Returns the number of dots set, which is 0 if the starting dot is
+/// already set.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::OutOfBounds`] if the starting dot is outside the
+/// grid.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::{draw_line, flood_fill}};
+///
+/// let mut grid = BrailleGrid::new(20, 10)?; // 40×40 dots
+/// // A triangle from three loose segments
+/// draw_line(&mut grid, 20, 2, 36, 30)?;
+/// draw_line(&mut grid, 36, 30, 4, 30)?;
+/// draw_line(&mut grid, 4, 30, 20, 2)?;
+///
+/// let filled = flood_fill(&mut grid, 20, 20)?;
+/// assert!(filled > 100);
+/// assert!(grid.is_dot_set(20, 10));
+/// assert!(!grid.is_dot_set(2, 2)); // outside stays empty
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn flood_fill(
+    grid: &mut BrailleGrid,
+    dot_x: usize,
+    dot_y: usize,
+) -> Result<usize, DotmaxError> {
+    fill_region(grid, dot_x, dot_y, None)
+}
+
+/// Fill the empty region containing dot `(dot_x, dot_y)`, optionally
+/// coloring every cell the fill touches.
+///
+/// Same fill as [`flood_fill`]. With `Some(color)`, cells that gain at
+/// least one dot take that color; cells the fill doesn't reach keep theirs.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::OutOfBounds`] if the starting dot is outside the
+/// grid.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, Color, primitives::{draw_circle, fill_region}};
+///
+/// let mut grid = BrailleGrid::new(20, 10)?;
+/// grid.enable_color_support();
+/// draw_circle(&mut grid, 20, 20, 12)?;
+///
+/// let teal = Color::rgb(0, 160, 160);
+/// fill_region(&mut grid, 20, 20, Some(teal))?;
+/// assert_eq!(grid.get_color(10, 5), Some(teal));
+/// assert_eq!(grid.get_color(0, 0), None);
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn fill_region(
+    grid: &mut BrailleGrid,
+    dot_x: usize,
+    dot_y: usize,
+    color: Option<Color>,
+) -> Result<usize, DotmaxError> {
+    let (width, height) = (grid.dot_width(), grid.dot_height());
+    if dot_x >= width || dot_y >= height {
+        return Err(DotmaxError::OutOfBounds {
+            x: dot_x,
+            y: dot_y,
+            width,
+            height,
+        });
+    }
+
+    let mut filled = 0;
+    // Seeds still to scan: any empty dot of a run not yet filled
+    let mut seeds = vec![(dot_x, dot_y)];
+    while let Some((x, y)) = seeds.pop() {
+        if grid.is_dot_set(x, y) {
+            continue;
+        }
+        // Widen to the whole empty run through (x, y)
+        let mut left = x;
+        while left > 0 && !grid.is_dot_set(left - 1, y) {
+            left -= 1;
+        }
+        let mut right = x;
+        while right + 1 < width && !grid.is_dot_set(right + 1, y) {
+            right += 1;
+        }
+
+        for run_x in left..=right {
+            grid.set_dot(run_x, y)?;
+            if let Some(color) = color {
+                grid.set_cell_color(run_x / 2, y / 4, color)?;
+            }
+        }
+        filled += right - left + 1;
+
+        // One seed per empty run in the rows above and below
+        for row in [y.checked_sub(1), Some(y + 1).filter(|&row| row < height)]
+            .into_iter()
+            .flatten()
+        {
+            let mut in_run = false;
+            for run_x in left..=right {
+                let empty = !grid.is_dot_set(run_x, row);
+                if empty && !in_run {
+                    seeds.push((run_x, row));
+                }
+                in_run = empty;
+            }
+        }
+    }
+
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{draw_line, draw_rectangle};
+
+    #[test]
+    fn test_fills_inside_rectangle_only() {
+        let mut grid = BrailleGrid::new(20, 10).unwrap();
+        draw_rectangle(&mut grid, 5, 5, 20, 10).unwrap();
+        // Interior is 18×8 dots
+        assert_eq!(flood_fill(&mut grid, 10, 10).unwrap(), 18 * 8);
+        assert!(grid.is_dot_set(6, 6) && grid.is_dot_set(23, 13));
+        assert!(!grid.is_dot_set(4, 10) && !grid.is_dot_set(26, 10));
+
+        // Filling again finds nothing left to fill
+        assert_eq!(flood_fill(&mut grid, 10, 10).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_diagonal_boundary_does_not_leak() {
+        let mut grid = BrailleGrid::new(10, 5).unwrap(); // 20×20 dots
+        draw_line(&mut grid, 0, 19, 19, 0).unwrap();
+        let filled = flood_fill(&mut grid, 0, 0).unwrap();
+        // Strictly above the anti-diagonal x + y = 19
+        assert_eq!(filled, (0..19).sum::<usize>() + 19);
+        assert!(!grid.is_dot_set(19, 19));
+    }
+
+    #[test]
+    fn test_concave_region_and_errors() {
+        // A "U" whose arms require seeding runs in both directions
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        draw_line(&mut grid, 8, 0, 8, 15).unwrap();
+        draw_line(&mut grid, 12, 0, 12, 15).unwrap();
+        draw_line(&mut grid, 8, 15, 12, 15).unwrap();
+        flood_fill(&mut grid, 0, 0).unwrap();
+        assert!(grid.is_dot_set(19, 0) && grid.is_dot_set(10, 19));
+        assert!(!grid.is_dot_set(10, 5));
+
+        assert!(matches!(
+            flood_fill(&mut grid, 20, 0),
+            Err(DotmaxError::OutOfBounds { x: 20, .. })
+        ));
+    }
+}
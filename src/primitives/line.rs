@@ -24,6 +24,7 @@
 //! - Foley & Van Dam, "Computer Graphics: Principles and Practice"
 //! - <https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm>
 
+use crate::color::schemes::ColorScheme;
 use crate::error::DotmaxError;
 use crate::grid::{BrailleGrid, Color};
 
@@ -323,12 +324,115 @@ pub fn draw_line_colored(
     y1: i32,
     color: Color,
     thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    draw_line_shaded(grid, x0, y0, x1, y1, &|_| color, thickness)
+}
+
+/// Draw a line whose color fades from `start` at `(x0, y0)` to `end` at
+/// `(x1, y1)`.
+///
+/// Charts use gradient lines to show direction or magnitude along a
+/// segment. Each cell takes the color of the last dot drawn in it, so the
+/// gradient steps once per cell rather than once per dot.
+///
+/// # Errors
+///
+/// * Returns `Err(DotmaxError::InvalidThickness)` if thickness is Some(0)
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, Color, primitives::draw_line_gradient};
+///
+/// let mut grid = BrailleGrid::new(80, 24)?;
+/// grid.enable_color_support();
+///
+/// let blue = Color::rgb(0, 0, 255);
+/// let red = Color::rgb(255, 0, 0);
+/// draw_line_gradient(&mut grid, 0, 48, 159, 48, blue, red, None)?;
+/// let first = grid.get_color(0, 12).unwrap();
+/// assert!(first.b > 250 && first.r < 5); // nearly pure blue
+/// assert_eq!(grid.get_color(79, 12), Some(red));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn draw_line_gradient(
+    grid: &mut BrailleGrid,
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    start: Color,
+    end: Color,
+    thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let lerp = |a: u8, b: u8, t: f32| {
+        (f32::from(b) - f32::from(a))
+            .mul_add(t, f32::from(a))
+            .round() as u8
+    };
+    let shade = |t: f32| {
+        Color::rgb(
+            lerp(start.r, end.r, t),
+            lerp(start.g, end.g, t),
+            lerp(start.b, end.b, t),
+        )
+    };
+    draw_line_shaded(grid, x0, y0, x1, y1, &shade, thickness)
+}
+
+/// Draw a line colored by sampling `scheme` from 0.0 at `(x0, y0)` to 1.0
+/// at `(x1, y1)`.
+///
+/// Like [`draw_line_gradient`], but through all the stops of a
+/// [`ColorScheme`] instead of between two colors.
+///
+/// # Errors
+///
+/// * Returns `Err(DotmaxError::InvalidThickness)` if thickness is Some(0)
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, ColorScheme, primitives::draw_line_scheme};
+///
+/// let mut grid = BrailleGrid::new(80, 24)?;
+/// grid.enable_color_support();
+///
+/// let heat = ColorScheme::heat_map();
+/// draw_line_scheme(&mut grid, 0, 95, 159, 0, &heat, Some(2))?;
+/// assert_eq!(grid.get_color(79, 0), Some(heat.sample(1.0)));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_line_scheme(
+    grid: &mut BrailleGrid,
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    scheme: &ColorScheme,
+    thickness: Option<u32>,
+) -> Result<(), DotmaxError> {
+    draw_line_shaded(grid, x0, y0, x1, y1, &|t| scheme.sample(t), thickness)
+}
+
+/// Colored line where `shade` maps the position along the line, from 0.0
+/// at the start to 1.0 at the end, to a color
+fn draw_line_shaded(
+    grid: &mut BrailleGrid,
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    shade: &dyn Fn(f32) -> Color,
+    thickness: Option<u32>,
 ) -> Result<(), DotmaxError> {
     // Handle thickness
     match thickness {
-        None | Some(1) => draw_line_colored_impl(grid, x0, y0, x1, y1, color),
+        None | Some(1) => draw_line_colored_impl(grid, x0, y0, x1, y1, shade),
         Some(0) => Err(DotmaxError::InvalidThickness { thickness: 0 }),
-        Some(t) => draw_line_thick_colored_impl(grid, x0, y0, x1, y1, color, t),
+        Some(t) => draw_line_thick_colored_impl(grid, x0, y0, x1, y1, shade, t),
     }
 }
 
@@ -340,7 +444,7 @@ fn draw_line_colored_impl(
     y0: i32,
     x1: i32,
     y1: i32,
-    color: Color,
+    shade: &dyn Fn(f32) -> Color,
 ) -> Result<(), DotmaxError> {
     // Get grid bounds in dots
     #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
@@ -359,6 +463,11 @@ fn draw_line_colored_impl(
     let mut x = x0;
     let mut y = y0;
 
+    // Bresenham takes one step per dot along the major axis
+    #[allow(clippy::cast_precision_loss)]
+    let steps = dx.max(dy).max(1) as f32;
+    let mut step = 0;
+
     loop {
         // Set dot and color if within bounds
         if x >= 0 && x < max_x && y >= 0 && y < max_y {
@@ -375,13 +484,15 @@ fn draw_line_colored_impl(
             let cell_y = dot_y / 4; // 4 dots per cell vertically
 
             // Set cell color
-            let _ = grid.set_cell_color(cell_x, cell_y, color);
+            #[allow(clippy::cast_precision_loss)]
+            let _ = grid.set_cell_color(cell_x, cell_y, shade(step as f32 / steps));
         }
 
         // Check if we've reached the end point
         if x == x1 && y == y1 {
             break;
         }
+        step += 1;
 
         let e2 = 2 * err;
 
@@ -406,7 +517,7 @@ fn draw_line_thick_colored_impl(
     y0: i32,
     x1: i32,
     y1: i32,
-    color: Color,
+    shade: &dyn Fn(f32) -> Color,
     thickness: u32,
 ) -> Result<(), DotmaxError> {
     // Calculate perpendicular direction for thickness
@@ -422,7 +533,7 @@ fn draw_line_thick_colored_impl(
         let half_thick = (thickness / 2) as i32;
         for i in -(half_thick)..=(half_thick) {
             for j in -(half_thick)..=(half_thick) {
-                draw_line_colored_impl(grid, x0 + i, y0 + j, x0 + i, y0 + j, color)?;
+                draw_line_colored_impl(grid, x0 + i, y0 + j, x0 + i, y0 + j, shade)?;
             }
         }
         return Ok(());
@@ -448,7 +559,7 @@ fn draw_line_thick_colored_impl(
             y0 + offset_y,
             x1 + offset_x,
             y1 + offset_y,
-            color,
+            shade,
         )?;
     }
 
@@ -647,4 +758,38 @@ mod tests {
             thin_dots
         );
     }
+
+    #[test]
+    fn test_gradient_endpoints_and_midpoint() {
+        let mut grid = BrailleGrid::new(50, 2).unwrap();
+        grid.enable_color_support();
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        draw_line_gradient(&mut grid, 0, 0, 99, 0, black, white, None).unwrap();
+
+        assert_eq!(grid.get_color(0, 0), Some(Color::rgb(3, 3, 3)));
+        assert_eq!(grid.get_color(49, 0), Some(white));
+        let middle = grid.get_color(25, 0).unwrap();
+        assert!((125..=132).contains(&middle.r), "{middle:?}");
+
+        // Reversing the endpoints reverses the gradient
+        grid.clear_colors();
+        draw_line_gradient(&mut grid, 99, 0, 0, 0, black, white, None).unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(white));
+    }
+
+    #[test]
+    fn test_scheme_line_and_thickness() {
+        let mut grid = BrailleGrid::new(20, 20).unwrap();
+        grid.enable_color_support();
+        let scheme = ColorScheme::rainbow();
+        draw_line_scheme(&mut grid, 20, 0, 20, 79, &scheme, Some(3)).unwrap();
+        // Parallel strokes share the gradient position along the line
+        assert_eq!(grid.get_color(9, 19), Some(scheme.sample(1.0)));
+        assert_eq!(grid.get_color(10, 19), Some(scheme.sample(1.0)));
+        assert!(matches!(
+            draw_line_scheme(&mut grid, 0, 0, 5, 5, &scheme, Some(0)),
+            Err(DotmaxError::InvalidThickness { thickness: 0 })
+        ));
+    }
 }
@@ -0,0 +1,294 @@
+//! Predefined marker shapes for scatter plots and annotations.
+//!
+//! A [`Marker`] is a shape, a size from 3 to 9 dots, and a filled or outline
+//! style. It renders to a [`Brush`], so placing one is a single
+//! [`BrailleGrid::stamp`] with the marker centered on the data point.
+//!
+//! At braille resolution the smallest sizes are only a few dots across, so
+//! shapes at size 3 are approximations; size 5 and up are clearly
+//! distinguishable.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::primitives::{Marker, MarkerShape};
+//! use dotmax::{BrailleGrid, Color};
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! let marker = Marker::new(MarkerShape::Diamond, 7)?.outline();
+//! let brush = marker.brush().with_color(Color::rgb(0, 200, 255));
+//!
+//! for &(x, y) in &[(10, 10), (30, 20), (50, 15)] {
+//!     grid.stamp(&brush, x, y);
+//! }
+//! assert!(grid.is_dot_set(10, 7)); // top tip of the first diamond
+//! assert!(!grid.is_dot_set(10, 10)); // outline markers are hollow
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use super::brush::Brush;
+use crate::error::DotmaxError;
+
+/// Smallest marker size in dots
+pub const MIN_MARKER_SIZE: usize = 3;
+
+/// Largest marker size in dots
+pub const MAX_MARKER_SIZE: usize = 9;
+
+/// Shape of a [`Marker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerShape {
+    /// Disc / ring
+    Circle,
+    /// Axis-aligned square
+    Square,
+    /// Upward-pointing triangle
+    Triangle,
+    /// Square rotated 45°
+    Diamond,
+    /// Plus sign (`+`)
+    Cross,
+    /// Diagonal cross (`×`)
+    X,
+    /// Five-pointed star
+    Star,
+}
+
+impl MarkerShape {
+    /// All shapes, in a stable order for cycling through series.
+    pub const ALL: [Self; 7] = [
+        Self::Circle,
+        Self::Square,
+        Self::Triangle,
+        Self::Diamond,
+        Self::Cross,
+        Self::X,
+        Self::Star,
+    ];
+}
+
+/// A marker shape at a given size and style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Marker {
+    shape: MarkerShape,
+    size: usize,
+    filled: bool,
+}
+
+impl Marker {
+    /// Create a filled marker `size` dots across.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if `size` is not within
+    /// [`MIN_MARKER_SIZE`]..=[`MAX_MARKER_SIZE`].
+    pub fn new(shape: MarkerShape, size: usize) -> Result<Self, DotmaxError> {
+        if !(MIN_MARKER_SIZE..=MAX_MARKER_SIZE).contains(&size) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "marker size".to_string(),
+                value: size.to_string(),
+                min: MIN_MARKER_SIZE.to_string(),
+                max: MAX_MARKER_SIZE.to_string(),
+            });
+        }
+        Ok(Self {
+            shape,
+            size,
+            filled: true,
+        })
+    }
+
+    /// Draw only the edge of the shape. For [`MarkerShape::Cross`] and
+    /// [`MarkerShape::X`] this means one-dot-wide strokes instead of thick ones.
+    #[must_use]
+    pub const fn outline(mut self) -> Self {
+        self.filled = false;
+        self
+    }
+
+    /// Draw the shape solid (the default).
+    #[must_use]
+    pub const fn filled(mut self) -> Self {
+        self.filled = true;
+        self
+    }
+
+    /// The marker's shape.
+    #[must_use]
+    pub const fn shape(&self) -> MarkerShape {
+        self.shape
+    }
+
+    /// Width and height in dots.
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the marker is drawn solid.
+    #[must_use]
+    pub const fn is_filled(&self) -> bool {
+        self.filled
+    }
+
+    /// Render the marker as a brush anchored at its center.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn brush(&self) -> Brush {
+        let n = self.size;
+        let c = (n as f32 - 1.0) / 2.0;
+        // Shapes are tested at offsets from the center, so they come out
+        // mirror-symmetric; the 0.5 margins keep even sizes from losing their
+        // tips between dot positions
+        let inside = |x: usize, y: usize| -> bool {
+            let (dx, dy) = (x as f32 - c, y as f32 - c);
+            match self.shape {
+                MarkerShape::Circle => dx.hypot(dy) <= c + 0.3,
+                MarkerShape::Square => true,
+                MarkerShape::Diamond => dx.abs() + dy.abs() <= c + 0.5,
+                MarkerShape::Triangle => dx.abs() <= (y as f32 / (n as f32 - 1.0)).mul_add(c, 0.5),
+                MarkerShape::Cross => {
+                    let half = if self.filled { n as f32 / 6.0 } else { 0.5 };
+                    dx.abs() <= half || dy.abs() <= half
+                }
+                MarkerShape::X => {
+                    let half = if self.filled { 0.75 } else { 0.01 };
+                    (dx.abs() - dy.abs()).abs() <= half
+                }
+                MarkerShape::Star => in_star(dx / (c + 0.5), dy / (c + 0.5)),
+            }
+        };
+
+        let solid: Vec<bool> = (0..n * n).map(|i| inside(i % n, i / n)).collect();
+        let hollow = !self.filled && !matches!(self.shape, MarkerShape::Cross | MarkerShape::X);
+        let mask = if hollow {
+            // Keep dots with at least one unset 4-neighbor (or the edge)
+            let at = |x: usize, y: usize| solid[y * n + x];
+            (0..n * n)
+                .map(|i| {
+                    let (x, y) = (i % n, i / n);
+                    at(x, y)
+                        && (x == 0
+                            || y == 0
+                            || x == n - 1
+                            || y == n - 1
+                            || !at(x - 1, y)
+                            || !at(x + 1, y)
+                            || !at(x, y - 1)
+                            || !at(x, y + 1))
+                })
+                .collect()
+        } else {
+            solid
+        };
+
+        Brush::square_mask(n, mask)
+    }
+}
+
+/// Point-in-polygon test for a unit five-pointed star centered on the origin,
+/// with one point straight up.
+fn in_star(x: f32, y: f32) -> bool {
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    let vertex = |i: usize| {
+        #[allow(clippy::cast_precision_loss)]
+        let angle = (i as f32).mul_add(PI / 5.0, -FRAC_PI_2);
+        let r = if i % 2 == 0 { 1.05 } else { 0.5 };
+        (r * angle.cos(), r * angle.sin())
+    };
+    // Even-odd ray casting over the 10 outline vertices
+    let mut inside = false;
+    for i in 0..10 {
+        let (x0, y0) = vertex(i);
+        let (x1, y1) = vertex((i + 1) % 10);
+        if (y0 > y) != (y1 > y) && x < (x1 - x0) * (y - y0) / (y1 - y0) + x0 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dots(brush: &Brush) -> usize {
+        (0..brush.height())
+            .flat_map(|y| (0..brush.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| brush.is_set(x, y))
+            .count()
+    }
+
+    #[test]
+    fn test_size_range() {
+        assert!(Marker::new(MarkerShape::Circle, 2).is_err());
+        assert!(Marker::new(MarkerShape::Circle, 10).is_err());
+        assert_eq!(Marker::new(MarkerShape::Star, 9).unwrap().size(), 9);
+    }
+
+    #[test]
+    fn test_every_marker_is_nonempty_and_mirror_symmetric() {
+        for shape in MarkerShape::ALL {
+            for size in MIN_MARKER_SIZE..=MAX_MARKER_SIZE {
+                for marker in [
+                    Marker::new(shape, size).unwrap(),
+                    Marker::new(shape, size).unwrap().outline(),
+                ] {
+                    let brush = marker.brush();
+                    assert!(dots(&brush) > 0, "{marker:?}");
+                    for y in 0..size {
+                        for x in 0..size {
+                            assert_eq!(
+                                brush.is_set(x, y),
+                                brush.is_set(size - 1 - x, y),
+                                "{marker:?} at ({x}, {y})"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_outline_is_hollow_subset_of_filled() {
+        for shape in [
+            MarkerShape::Circle,
+            MarkerShape::Square,
+            MarkerShape::Diamond,
+            MarkerShape::Triangle,
+        ] {
+            let filled = Marker::new(shape, 7).unwrap().brush();
+            let outline = Marker::new(shape, 7).unwrap().outline().brush();
+            assert!(dots(&outline) < dots(&filled), "{shape:?}");
+            for y in 0..7 {
+                for x in 0..7 {
+                    assert!(!outline.is_set(x, y) || filled.is_set(x, y), "{shape:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shape_landmarks() {
+        let diamond = Marker::new(MarkerShape::Diamond, 5).unwrap().brush();
+        assert!(diamond.is_set(2, 0) && !diamond.is_set(0, 0));
+
+        let triangle = Marker::new(MarkerShape::Triangle, 5).unwrap().brush();
+        assert!(triangle.is_set(2, 0) && !triangle.is_set(1, 0));
+        assert!(triangle.is_set(0, 4) && triangle.is_set(4, 4));
+
+        let cross = Marker::new(MarkerShape::Cross, 5)
+            .unwrap()
+            .outline()
+            .brush();
+        assert_eq!(dots(&cross), 9);
+
+        let x = Marker::new(MarkerShape::X, 5).unwrap().outline().brush();
+        assert!(x.is_set(0, 0) && x.is_set(4, 4) && !x.is_set(2, 0));
+
+        let star = Marker::new(MarkerShape::Star, 9).unwrap().brush();
+        assert!(star.is_set(4, 0) && star.is_set(4, 4) && !star.is_set(0, 0));
+    }
+}
@@ -1,12 +1,21 @@
 //! Drawing primitives for braille graphics.
 //!
 //! This module provides geometric drawing capabilities using industry-standard algorithms:
-//! - Lines: Bresenham's line algorithm (integer-only, all octants)
+//! - Lines: Bresenham's line algorithm (integer-only, all octants), solid or gradient colored
 //! - Circles: Bresenham's circle algorithm (midpoint circle, 8-way symmetry)
+//! - Ellipses: Midpoint ellipse algorithm with independent x and y radii
+//! - Curves: Quadratic and cubic Bezier curves and circular arcs
 //! - Rectangles: Outline, filled, and thick border variants
-//! - Polygons: Outline and filled from arbitrary vertex lists
+//! - Polygons: Outline and filled from arbitrary vertex lists, with holes and fill rules
+//! - Flood fill: Span fill of empty regions bounded by set dots
+//! - Brushes: Dot masks stamped at a point or along a path
+//! - Markers: Scatter plot shapes (circle, square, triangle, ...) as brushes
+//! - Anti-aliasing: Xiaolin Wu lines and circles rendered from fractional dot coverage
+//! - Canvas: Drawing in `f64` world coordinates mapped onto the dots
+//! - Transforms: Affine translate/rotate/scale applied to lines, polygons, circles, and blits
+//! - Text: Labels rasterized from built-in 3×5 and 5×7 bitmap fonts
 //!
-//! All primitives operate on `BrailleGrid` using dot coordinates (not cell coordinates).
+//! All primitives except [`Canvas`] operate on `BrailleGrid` using dot coordinates (not cell coordinates).
 //! Grid is `width*2 × height*4` dots where each cell is 2×4 dots.
 //!
 //! # Examples
@@ -21,13 +30,40 @@
 //! # Ok::<(), dotmax::DotmaxError>(())
 //! ```
 
+pub mod antialias;
+pub mod brush;
+pub mod canvas;
 pub mod circle;
+pub mod curve;
+pub mod ellipse;
+pub mod fill;
 pub mod line;
+pub mod marker;
 pub mod shapes;
+pub mod text;
+pub mod transform;
 
-pub use circle::{draw_circle, draw_circle_colored, draw_circle_filled, draw_circle_thick};
-pub use line::{draw_line, draw_line_colored, draw_line_thick};
+pub use antialias::{draw_circle_aa, draw_line_aa, Coverage};
+pub use brush::{stroke_path, Brush};
+pub use canvas::Canvas;
+pub use circle::{
+    draw_circle, draw_circle_aspect, draw_circle_colored, draw_circle_filled, draw_circle_thick,
+};
+pub use curve::{
+    draw_arc, draw_arc_colored, draw_arc_thick, draw_cubic_bezier, draw_cubic_bezier_colored,
+    draw_cubic_bezier_thick, draw_quadratic_bezier, draw_quadratic_bezier_colored,
+    draw_quadratic_bezier_thick,
+};
+pub use ellipse::{draw_ellipse, draw_ellipse_colored, draw_ellipse_filled, draw_ellipse_thick};
+pub use fill::{fill_region, flood_fill};
+pub use line::{
+    draw_line, draw_line_colored, draw_line_gradient, draw_line_scheme, draw_line_thick,
+};
+pub use marker::{Marker, MarkerShape};
 pub use shapes::{
-    draw_polygon, draw_polygon_colored, draw_polygon_filled, draw_rectangle,
-    draw_rectangle_colored, draw_rectangle_filled, draw_rectangle_thick,
+    draw_contours_colored, draw_contours_filled, draw_polygon, draw_polygon_colored,
+    draw_polygon_filled, draw_rectangle, draw_rectangle_colored, draw_rectangle_filled,
+    draw_rectangle_thick, FillRule,
 };
+pub use text::{draw_text, measure_text, FontSize, TextRotation, TextStyle};
+pub use transform::{with_transform, Transform2D, Transformed};
@@ -13,7 +13,9 @@
 //!
 //! **Polygon Drawing:**
 //! - Outline: Lines connecting consecutive vertices (closed path)
-//! - Filled: Scanline fill algorithm with edge table (even-odd rule)
+//! - Filled: Scanline fill algorithm with active edge table (even-odd rule)
+//! - Contours: Several polygons filled as one shape, with holes, under an
+//!   even-odd or nonzero [`FillRule`]
 //!
 //! ## Coordinate System
 //!
@@ -356,11 +358,11 @@ pub fn draw_polygon(grid: &mut BrailleGrid, vertices: &[(i32, i32)]) -> Result<(
 ///
 /// # Algorithm
 ///
-/// Uses scanline fill with edge table:
+/// Uses scanline fill with an active edge table:
 /// 1. Build edge table with y-min, y-max, x-intercept for each edge
 /// 2. For each scanline y from `y_min` to `y_max`:
-///    - Find intersections with all polygon edges
-///    - Sort intersections by x coordinate
+///    - Add edges starting on this row to the active list, drop finished ones
+///    - Sort the active edges' intersections by x coordinate
 ///    - Fill spans between pairs (even-odd rule)
 ///
 /// # Errors
@@ -370,100 +372,202 @@ pub fn draw_polygon_filled(
     grid: &mut BrailleGrid,
     vertices: &[(i32, i32)],
 ) -> Result<(), DotmaxError> {
-    // Build edge table: store (y_min, y_max, x_at_y_min, dx/dy) for each edge
-    #[derive(Debug)]
-    struct Edge {
-        y_min: i32,
-        y_max: i32,
-        x_at_y_min: f64,
-        inv_slope: f64, // dx/dy
-    }
-
-    // Validate minimum vertex count
-    if vertices.len() < 3 {
-        return Err(DotmaxError::InvalidPolygon {
-            reason: format!("Polygon requires ≥3 vertices, got {}", vertices.len()),
-        });
-    }
-
-    // Find y-range of polygon
-    let mut y_min = vertices[0].1;
-    let mut y_max = vertices[0].1;
-    for &(_, y) in vertices {
-        y_min = y_min.min(y);
-        y_max = y_max.max(y);
-    }
+    fill_contours(grid, &[vertices], FillRule::EvenOdd, None)
+}
 
-    let mut edges = Vec::new();
+/// Rule deciding which regions of overlapping or nested contours are inside.
+///
+/// Both rules count how many edges a ray from a point to the left edge of
+/// the grid crosses. They differ only where contours overlap or nest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside if the ray crosses an odd number of edges. Any nested contour
+    /// is a hole, whichever way it winds.
+    #[default]
+    EvenOdd,
+    /// Inside if edges crossing downward and upward don't cancel out. A
+    /// nested contour is a hole only if it winds the opposite way to the one
+    /// around it, as in TrueType glyph outlines.
+    NonZero,
+}
 
-    // Process each edge (vertex[i], vertex[i+1])
-    for i in 0..vertices.len() {
-        let (x0, y0) = vertices[i];
-        let (x1, y1) = vertices[(i + 1) % vertices.len()];
+/// Draw a filled shape made of several contours, such as a ring or a glyph
+/// with holes.
+///
+/// Each contour is a closed polygon. Which areas are filled where contours
+/// nest or overlap is decided by `rule`; with [`FillRule::EvenOdd`], a
+/// single contour fills exactly like [`draw_polygon_filled`].
+///
+/// # Errors
+///
+/// Returns `InvalidPolygon` if `contours` is empty or any contour has fewer
+/// than 3 vertices.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, primitives::shapes::{draw_contours_filled, FillRule}};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+///
+/// // A square frame: the inner square is cut out of the outer one
+/// let outer = [(10, 4), (70, 4), (70, 44), (10, 44)];
+/// let inner = [(25, 14), (55, 14), (55, 34), (25, 34)];
+/// draw_contours_filled(&mut grid, &[&outer, &inner], FillRule::EvenOdd)?;
+///
+/// assert!(grid.is_dot_set(15, 24)); // in the frame
+/// assert!(!grid.is_dot_set(40, 24)); // in the hole
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+///
+/// # Algorithm
+///
+/// Scanline fill with an active edge table: edges are sorted by their top
+/// row and enter the active list when the scanline reaches them, so each
+/// row only looks at the edges that cross it. Crossings carry the edge's
+/// direction for [`FillRule::NonZero`].
+pub fn draw_contours_filled(
+    grid: &mut BrailleGrid,
+    contours: &[&[(i32, i32)]],
+    rule: FillRule,
+) -> Result<(), DotmaxError> {
+    fill_contours(grid, contours, rule, None)
+}
 
-        // Skip horizontal edges (no contribution to scanline fill)
-        if y0 == y1 {
-            continue;
-        }
+/// Draw a filled multi-contour shape and color the cells it covers.
+///
+/// Same fill as [`draw_contours_filled`].
+///
+/// # Errors
+///
+/// Returns `InvalidPolygon` if `contours` is empty or any contour has fewer
+/// than 3 vertices.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{BrailleGrid, Color};
+/// use dotmax::primitives::shapes::{draw_contours_colored, FillRule};
+///
+/// let mut grid = BrailleGrid::new(40, 12)?;
+/// grid.enable_color_support();
+///
+/// // Clockwise outer square, counter-clockwise inner one: a hole under NonZero
+/// let outer = [(0, 0), (40, 0), (40, 40), (0, 40)];
+/// let inner = [(10, 10), (10, 30), (30, 30), (30, 10)];
+/// let orange = Color::rgb(255, 140, 0);
+/// draw_contours_colored(&mut grid, &[&outer, &inner], FillRule::NonZero, orange)?;
+/// assert_eq!(grid.get_color(1, 1), Some(orange));
+/// assert_eq!(grid.get_color(10, 5), None);
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_contours_colored(
+    grid: &mut BrailleGrid,
+    contours: &[&[(i32, i32)]],
+    rule: FillRule,
+    color: Color,
+) -> Result<(), DotmaxError> {
+    fill_contours(grid, contours, rule, Some(color))
+}
 
-        // Determine edge orientation
-        #[allow(clippy::cast_precision_loss)]
-        let (y_min_edge, y_max_edge, x_at_min, dx, dy) = if y0 < y1 {
-            (
-                y0,
-                y1,
-                f64::from(x0),
-                f64::from(x1 - x0),
-                f64::from(y1 - y0),
-            )
-        } else {
-            (
-                y1,
-                y0,
-                f64::from(x1),
-                f64::from(x0 - x1),
-                f64::from(y0 - y1),
-            )
-        };
+/// An edge in the active edge table.
+#[derive(Debug)]
+struct Edge {
+    y_min: i32,
+    y_max: i32,
+    x_at_y_min: f64,
+    inv_slope: f64, // dx/dy
+    /// +1 for edges running down the screen, -1 for edges running up
+    winding: i32,
+}
 
-        edges.push(Edge {
-            y_min: y_min_edge,
-            y_max: y_max_edge,
-            x_at_y_min: x_at_min,
-            inv_slope: dx / dy,
+/// Scanline fill shared by the filled polygon and contour functions.
+fn fill_contours(
+    grid: &mut BrailleGrid,
+    contours: &[&[(i32, i32)]],
+    rule: FillRule,
+    color: Option<Color>,
+) -> Result<(), DotmaxError> {
+    if contours.is_empty() {
+        return Err(DotmaxError::InvalidPolygon {
+            reason: "Shape requires at least one contour".to_string(),
         });
     }
 
-    // Scanline fill
-    for y in y_min..=y_max {
-        // Find intersections for this scanline
-        let mut intersections = Vec::new();
-
-        for edge in &edges {
-            // Check if scanline intersects this edge (use y_min <= y < y_max to handle vertices correctly)
-            if y >= edge.y_min && y < edge.y_max {
-                // Calculate x-intersection at this y
-                #[allow(clippy::cast_precision_loss)]
-                let offset = f64::from(y - edge.y_min);
-                #[allow(clippy::suboptimal_flops)]
-                let x_intersection = edge.x_at_y_min + edge.inv_slope * offset;
-                intersections.push(x_intersection);
+    // Build edge table, skipping horizontal edges (no contribution to scanline fill)
+    let mut edges = Vec::new();
+    for vertices in contours {
+        if vertices.len() < 3 {
+            return Err(DotmaxError::InvalidPolygon {
+                reason: format!("Polygon requires ≥3 vertices, got {}", vertices.len()),
+            });
+        }
+        for i in 0..vertices.len() {
+            let (x0, y0) = vertices[i];
+            let (x1, y1) = vertices[(i + 1) % vertices.len()];
+            if y0 == y1 {
+                continue;
             }
+            let (top, bottom, winding) = if y0 < y1 {
+                ((x0, y0), (x1, y1), 1)
+            } else {
+                ((x1, y1), (x0, y0), -1)
+            };
+            edges.push(Edge {
+                y_min: top.1,
+                y_max: bottom.1,
+                x_at_y_min: f64::from(top.0),
+                inv_slope: f64::from(bottom.0 - top.0) / f64::from(bottom.1 - top.1),
+                winding,
+            });
         }
+    }
+    edges.sort_by_key(|edge| edge.y_min);
+
+    // Only scanlines on the grid can produce dots
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let last_row = grid.dot_height() as i32 - 1;
+    let first_row = edges.first().map_or(0, |edge| edge.y_min.max(0));
+    let end_row = edges.iter().map(|edge| edge.y_max).max().unwrap_or(0);
+
+    let mut pending = edges.iter().peekable();
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    for y in first_row..=end_row.min(last_row) {
+        // Edges cross rows y_min <= y < y_max, which handles shared vertices
+        while let Some(edge) = pending.next_if(|edge| edge.y_min <= y) {
+            active.push(edge);
+        }
+        active.retain(|edge| edge.y_max > y);
 
-        // Sort intersections by x coordinate
-        intersections.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Fill spans between pairs (even-odd rule)
-        for pair in intersections.chunks(2) {
-            if pair.len() == 2 {
-                #[allow(clippy::cast_possible_truncation)]
-                let x_start = pair[0].round() as i32;
+        crossings.clear();
+        crossings.extend(active.iter().map(|edge| {
+            let offset = f64::from(y - edge.y_min);
+            (
+                edge.inv_slope.mul_add(offset, edge.x_at_y_min),
+                edge.winding,
+            )
+        }));
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Fill spans where the winding count says we're inside
+        let mut count = 0;
+        for pair in crossings.windows(2) {
+            count += match rule {
+                FillRule::EvenOdd => 1,
+                FillRule::NonZero => pair[0].1,
+            };
+            let inside = match rule {
+                FillRule::EvenOdd => count % 2 != 0,
+                FillRule::NonZero => count != 0,
+            };
+            if inside {
                 #[allow(clippy::cast_possible_truncation)]
-                let x_end = pair[1].round() as i32;
-
-                // Draw horizontal line span
-                draw_line(grid, x_start, y, x_end, y)?;
+                let (x_start, x_end) = (pair[0].0.round() as i32, pair[1].0.round() as i32);
+                match color {
+                    Some(color) => draw_line_colored(grid, x_start, y, x_end, y, color, None)?,
+                    None => draw_line(grid, x_start, y, x_end, y)?,
+                }
             }
         }
     }
@@ -863,4 +967,61 @@ mod tests {
         // Should render without crash (even-odd rule)
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_contours_even_odd_hole() {
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        let outer = [(10, 4), (70, 4), (70, 44), (10, 44)];
+        let inner = [(25, 14), (55, 14), (55, 34), (25, 34)];
+        draw_contours_filled(&mut grid, &[&outer, &inner], FillRule::EvenOdd).unwrap();
+        assert!(grid.is_dot_set(15, 24) && grid.is_dot_set(65, 24));
+        assert!(grid.is_dot_set(40, 8) && grid.is_dot_set(40, 40));
+        assert!(!grid.is_dot_set(40, 24));
+        assert!(!grid.is_dot_set(5, 24));
+    }
+
+    #[test]
+    fn test_contours_nonzero_depends_on_winding() {
+        let outer = [(0, 0), (40, 0), (40, 40), (0, 40)];
+        let same_way = [(10, 10), (30, 10), (30, 30), (10, 30)];
+        let opposite = [(10, 10), (10, 30), (30, 30), (30, 10)];
+
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        draw_contours_filled(&mut grid, &[&outer, &same_way], FillRule::NonZero).unwrap();
+        assert!(grid.is_dot_set(20, 20)); // no hole
+
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        draw_contours_filled(&mut grid, &[&outer, &opposite], FillRule::NonZero).unwrap();
+        assert!(!grid.is_dot_set(20, 20));
+        assert!(grid.is_dot_set(5, 20));
+
+        // Even-odd ignores direction
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        draw_contours_filled(&mut grid, &[&outer, &same_way], FillRule::EvenOdd).unwrap();
+        assert!(!grid.is_dot_set(20, 20));
+    }
+
+    #[test]
+    fn test_contours_match_single_polygon_and_validate() {
+        let star = [(40, 2), (45, 40), (10, 20), (70, 20), (35, 40)];
+        let mut polygon = BrailleGrid::new(40, 12).unwrap();
+        draw_polygon_filled(&mut polygon, &star).unwrap();
+        let mut contours = BrailleGrid::new(40, 12).unwrap();
+        draw_contours_filled(&mut contours, &[&star], FillRule::EvenOdd).unwrap();
+        assert_eq!(polygon.to_unicode_grid(), contours.to_unicode_grid());
+
+        // The star's pentagon center is a hole under even-odd but filled under nonzero
+        let mut nonzero = BrailleGrid::new(40, 12).unwrap();
+        draw_contours_filled(&mut nonzero, &[&star], FillRule::NonZero).unwrap();
+        assert!(!polygon.is_dot_set(40, 24) && nonzero.is_dot_set(40, 24));
+
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        let line: &[(i32, i32)] = &[(0, 0), (5, 5)];
+        for contours in [&[][..], &[&star[..], line][..]] {
+            assert!(matches!(
+                draw_contours_filled(&mut grid, contours, FillRule::NonZero),
+                Err(DotmaxError::InvalidPolygon { .. })
+            ));
+        }
+    }
 }
@@ -0,0 +1,593 @@
+//! Text drawn in dots using built-in bitmap fonts.
+//!
+//! [`draw_text`] rasterizes a string into the grid's dots, so labels sit on
+//! the same 2×4-per-cell resolution as the rest of a drawing instead of
+//! snapping to whole cells the way [`BrailleGrid::set_char`] does.
+//!
+//! Two fonts are embedded:
+//!
+//! | [`FontSize`] | Glyph  | Line height | Characters                        |
+//! |--------------|--------|-------------|-----------------------------------|
+//! | `Small`      | 3×5    | 6 dots      | printable ASCII, capitals only    |
+//! | `Large`      | 5×7    | 8 dots      | printable ASCII                   |
+//!
+//! The small font draws lowercase letters as capitals. Characters outside
+//! printable ASCII are drawn as `?`, and `\n` starts a new line.
+//!
+//! A [`TextStyle`] adds integer scaling, quarter-turn rotation, and color.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::primitives::text::{draw_text, FontSize, TextRotation, TextStyle};
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+//! draw_text(&mut grid, 2, 2, "hello", FontSize::Small)?;
+//!
+//! // A y-axis label, reading bottom to top, twice the size
+//! let style = TextStyle::new(FontSize::Large)
+//!     .scale(2)
+//!     .rotation(TextRotation::Ccw90);
+//! draw_text(&mut grid, 60, 0, "Y", style)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Largest [`TextStyle::scale`] factor
+pub const MAX_TEXT_SCALE: u32 = 16;
+
+/// One of the embedded bitmap fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontSize {
+    /// 3×5 dot capitals; fits a line of text in under two cells
+    #[default]
+    Small,
+    /// 5×7 dots with lowercase; a line is exactly two cells tall
+    Large,
+}
+
+impl FontSize {
+    /// Glyph width and height in dots, before scaling.
+    #[must_use]
+    pub const fn glyph_size(self) -> (usize, usize) {
+        match self {
+            Self::Small => (3, 5),
+            Self::Large => (5, 7),
+        }
+    }
+
+    /// Column bitmasks for `c`, bit 0 at the top.
+    fn columns(self, c: char) -> &'static [u8] {
+        let c = if c.is_ascii_graphic() || c == ' ' {
+            c
+        } else {
+            '?'
+        };
+        match self {
+            Self::Small => {
+                let c = c.to_ascii_uppercase() as usize;
+                // The table skips the lowercase range
+                let index = if c <= usize::from(b'`') {
+                    c - 32
+                } else {
+                    c - 58
+                };
+                &SMALL[index]
+            }
+            Self::Large => &LARGE[c as usize - 32],
+        }
+    }
+}
+
+/// Quarter-turn rotation of drawn text.
+///
+/// Rotation is about the text's bounding box: whatever the rotation, the
+/// box's top-left corner lands at the `x`, `y` passed to [`draw_text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextRotation {
+    /// Left to right
+    #[default]
+    None,
+    /// Turned clockwise, reading top to bottom
+    Cw90,
+    /// Upside down
+    Half,
+    /// Turned counter-clockwise, reading bottom to top
+    Ccw90,
+}
+
+/// Font, scale, rotation, and color for [`draw_text`].
+///
+/// A bare [`FontSize`] converts into the default style for that font:
+/// unscaled, unrotated, and uncolored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextStyle {
+    size: FontSize,
+    scale: u32,
+    rotation: TextRotation,
+    color: Option<Color>,
+}
+
+impl TextStyle {
+    /// Unscaled, unrotated, uncolored text in `size`.
+    #[must_use]
+    pub const fn new(size: FontSize) -> Self {
+        Self {
+            size,
+            scale: 1,
+            rotation: TextRotation::None,
+            color: None,
+        }
+    }
+
+    /// Draw every font pixel as a `scale`×`scale` block of dots.
+    /// Must be within 1..=[`MAX_TEXT_SCALE`]; checked when drawing.
+    #[must_use]
+    pub const fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Rotate the text by a quarter turn or a half turn.
+    #[must_use]
+    pub const fn rotation(mut self, rotation: TextRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Color the cells the text touches.
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// The font.
+    #[must_use]
+    pub const fn size(&self) -> FontSize {
+        self.size
+    }
+
+    /// Scaled metrics of `text` laid out unrotated.
+    fn layout(&self, text: &str) -> Layout {
+        let (glyph_w, glyph_h) = self.size.glyph_size();
+        let scale = self.scale as usize;
+        let (advance, line_height) = ((glyph_w + 1) * scale, (glyph_h + 1) * scale);
+        let (columns, lines) = text.lines().fold((0, 0), |(columns, lines), line| {
+            (columns.max(line.chars().count()), lines + 1)
+        });
+        Layout {
+            // No gap after the last glyph or below the last line
+            width: (columns * advance).saturating_sub(scale),
+            height: (lines * line_height).saturating_sub(scale),
+            advance,
+            line_height,
+        }
+    }
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self::new(FontSize::default())
+    }
+}
+
+impl From<FontSize> for TextStyle {
+    fn from(size: FontSize) -> Self {
+        Self::new(size)
+    }
+}
+
+/// Dimensions of a block of text, in unrotated dots.
+struct Layout {
+    width: usize,
+    height: usize,
+    advance: usize,
+    line_height: usize,
+}
+
+/// Draw `text` with its top-left corner at dot `(x, y)`.
+///
+/// `style` is a [`FontSize`] or a full [`TextStyle`]. Glyphs are one dot
+/// apart and lines one dot apart, both multiplied by the scale. Text that
+/// runs off the grid is clipped; nothing wraps.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidParameter`] if the style's scale is not
+/// within 1..=[`MAX_TEXT_SCALE`].
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::primitives::text::{draw_text, FontSize, TextStyle};
+/// use dotmax::{BrailleGrid, Color};
+///
+/// let mut grid = BrailleGrid::new(40, 10)?;
+/// draw_text(&mut grid, 0, 0, "Score: 120", FontSize::Large)?;
+/// draw_text(
+///     &mut grid,
+///     0,
+///     10,
+///     "GAME OVER",
+///     TextStyle::new(FontSize::Small).scale(2).color(Color::rgb(255, 64, 64)),
+/// )?;
+///
+/// assert!(grid.is_dot_set(0, 1)); // left stroke of the 'S'
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn draw_text(
+    grid: &mut BrailleGrid,
+    x: i32,
+    y: i32,
+    text: &str,
+    style: impl Into<TextStyle>,
+) -> Result<(), DotmaxError> {
+    let style = style.into();
+    if !(1..=MAX_TEXT_SCALE).contains(&style.scale) {
+        return Err(DotmaxError::InvalidParameter {
+            parameter_name: "text scale".to_string(),
+            value: style.scale.to_string(),
+            min: "1".to_string(),
+            max: MAX_TEXT_SCALE.to_string(),
+        });
+    }
+
+    let layout = style.layout(text);
+    let scale = style.scale as usize;
+    // Safe casts: grid dimensions are bounded by terminal size (<10000)
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let (max_x, max_y) = (grid.dot_width() as i32, grid.dot_height() as i32);
+    // Maps a dot of the unrotated block onto the grid
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let place = |u: usize, v: usize| -> (i32, i32) {
+        let (u, v) = (u as i32, v as i32);
+        let (width, height) = (layout.width as i32, layout.height as i32);
+        match style.rotation {
+            TextRotation::None => (x + u, y + v),
+            TextRotation::Cw90 => (x + height - 1 - v, y + u),
+            TextRotation::Half => (x + width - 1 - u, y + height - 1 - v),
+            TextRotation::Ccw90 => (x + v, y + width - 1 - u),
+        }
+    };
+
+    for (row, line) in text.lines().enumerate() {
+        let top = row * layout.line_height;
+        for (index, c) in line.chars().enumerate() {
+            let left = index * layout.advance;
+            for (column, &bits) in style.size.columns(c).iter().enumerate() {
+                for pixel in (0..8).filter(|pixel| bits & (1 << pixel) != 0) {
+                    for (du, dv) in (0..scale).flat_map(|du| (0..scale).map(move |dv| (du, dv))) {
+                        let (gx, gy) = place(left + column * scale + du, top + pixel * scale + dv);
+                        if gx < 0 || gx >= max_x || gy < 0 || gy >= max_y {
+                            continue;
+                        }
+                        // Safe to convert to usize - we checked gx >= 0 and gy >= 0
+                        #[allow(clippy::cast_sign_loss)]
+                        let (dot_x, dot_y) = (gx as usize, gy as usize);
+                        let _ = grid.set_dot(dot_x, dot_y);
+                        if let Some(color) = style.color {
+                            let _ = grid.set_cell_color(dot_x / 2, dot_y / 4, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Width and height in dots that [`draw_text`] would cover, after rotation.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::primitives::text::{measure_text, FontSize, TextRotation, TextStyle};
+///
+/// assert_eq!(measure_text("hi", FontSize::Large), (11, 7));
+/// assert_eq!(measure_text("a\nbc", FontSize::Small), (7, 11));
+///
+/// let style = TextStyle::new(FontSize::Small).scale(2).rotation(TextRotation::Cw90);
+/// assert_eq!(measure_text("hi", style), (10, 14));
+/// ```
+#[must_use]
+pub fn measure_text(text: &str, style: impl Into<TextStyle>) -> (usize, usize) {
+    let style = style.into();
+    let layout = style.layout(text);
+    match style.rotation {
+        TextRotation::None | TextRotation::Half => (layout.width, layout.height),
+        TextRotation::Cw90 | TextRotation::Ccw90 => (layout.height, layout.width),
+    }
+}
+
+// Glyph columns, bit 0 at the top. The small font has no lowercase entries:
+// ' '..='`' followed by '{'..='~'.
+
+const SMALL: [[u8; 3]; 69] = [
+    [0x00, 0x00, 0x00], // ' '
+    [0x00, 0x17, 0x00], // '!'
+    [0x03, 0x00, 0x03], // '"'
+    [0x1F, 0x0A, 0x1F], // '#'
+    [0x12, 0x1F, 0x09], // '$'
+    [0x19, 0x04, 0x13], // '%'
+    [0x0A, 0x15, 0x1A], // '&'
+    [0x00, 0x03, 0x00], // "'"
+    [0x00, 0x0E, 0x11], // '('
+    [0x11, 0x0E, 0x00], // ')'
+    [0x0A, 0x04, 0x0A], // '*'
+    [0x04, 0x0E, 0x04], // '+'
+    [0x10, 0x08, 0x00], // ','
+    [0x04, 0x04, 0x04], // '-'
+    [0x00, 0x10, 0x00], // '.'
+    [0x18, 0x04, 0x03], // '/'
+    [0x1F, 0x11, 0x1F], // '0'
+    [0x12, 0x1F, 0x10], // '1'
+    [0x19, 0x15, 0x12], // '2'
+    [0x11, 0x15, 0x0A], // '3'
+    [0x07, 0x04, 0x1F], // '4'
+    [0x17, 0x15, 0x09], // '5'
+    [0x1E, 0x15, 0x1D], // '6'
+    [0x01, 0x19, 0x07], // '7'
+    [0x1F, 0x15, 0x1F], // '8'
+    [0x17, 0x15, 0x0F], // '9'
+    [0x00, 0x0A, 0x00], // ':'
+    [0x10, 0x0A, 0x00], // ';'
+    [0x04, 0x0A, 0x11], // '<'
+    [0x0A, 0x0A, 0x0A], // '='
+    [0x11, 0x0A, 0x04], // '>'
+    [0x01, 0x15, 0x02], // '?'
+    [0x0E, 0x15, 0x16], // '@'
+    [0x1E, 0x05, 0x1E], // 'A'
+    [0x1F, 0x15, 0x0A], // 'B'
+    [0x0E, 0x11, 0x11], // 'C'
+    [0x1F, 0x11, 0x0E], // 'D'
+    [0x1F, 0x15, 0x11], // 'E'
+    [0x1F, 0x05, 0x01], // 'F'
+    [0x0E, 0x11, 0x1D], // 'G'
+    [0x1F, 0x04, 0x1F], // 'H'
+    [0x11, 0x1F, 0x11], // 'I'
+    [0x08, 0x10, 0x0F], // 'J'
+    [0x1F, 0x04, 0x1B], // 'K'
+    [0x1F, 0x10, 0x10], // 'L'
+    [0x1F, 0x06, 0x1F], // 'M'
+    [0x1F, 0x01, 0x1E], // 'N'
+    [0x0E, 0x11, 0x0E], // 'O'
+    [0x1F, 0x05, 0x02], // 'P'
+    [0x0E, 0x19, 0x16], // 'Q'
+    [0x1F, 0x05, 0x1A], // 'R'
+    [0x12, 0x15, 0x09], // 'S'
+    [0x01, 0x1F, 0x01], // 'T'
+    [0x1F, 0x10, 0x1F], // 'U'
+    [0x0F, 0x10, 0x0F], // 'V'
+    [0x1F, 0x0C, 0x1F], // 'W'
+    [0x1B, 0x04, 0x1B], // 'X'
+    [0x03, 0x1C, 0x03], // 'Y'
+    [0x19, 0x15, 0x13], // 'Z'
+    [0x1F, 0x11, 0x00], // '['
+    [0x03, 0x04, 0x18], // '\\'
+    [0x00, 0x11, 0x1F], // ']'
+    [0x02, 0x01, 0x02], // '^'
+    [0x10, 0x10, 0x10], // '_'
+    [0x01, 0x02, 0x00], // '`'
+    [0x04, 0x1F, 0x11], // '{'
+    [0x00, 0x1F, 0x00], // '|'
+    [0x11, 0x1F, 0x04], // '}'
+    [0x04, 0x06, 0x02], // '~'
+];
+
+const LARGE: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // "'"
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The grid's dots as rows of `#` and `.`, cropped to `w`×`h`.
+    fn dots(grid: &BrailleGrid, w: usize, h: usize) -> Vec<String> {
+        (0..h)
+            .map(|y| {
+                (0..w)
+                    .map(|x| if grid.is_dot_set(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_glyph() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        draw_text(&mut grid, 0, 0, "A", FontSize::Small).unwrap();
+        assert_eq!(
+            dots(&grid, 4, 6),
+            [".#..", "#.#.", "###.", "#.#.", "#.#.", "...."]
+        );
+    }
+
+    #[test]
+    fn test_small_font_draws_lowercase_as_capitals() {
+        let mut lower = BrailleGrid::new(10, 2).unwrap();
+        let mut upper = BrailleGrid::new(10, 2).unwrap();
+        draw_text(&mut lower, 0, 0, "hi{~", FontSize::Small).unwrap();
+        draw_text(&mut upper, 0, 0, "HI{~", FontSize::Small).unwrap();
+        assert_eq!(dots(&lower, 20, 8), dots(&upper, 20, 8));
+    }
+
+    #[test]
+    fn test_large_glyph_and_spacing() {
+        let mut grid = BrailleGrid::new(6, 2).unwrap();
+        draw_text(&mut grid, 1, 0, "||", FontSize::Large).unwrap();
+        // Bar in the middle column of each 5-wide glyph, 6 dots apart
+        for y in 0..7 {
+            assert!(grid.is_dot_set(3, y));
+            assert!(grid.is_dot_set(9, y));
+        }
+        assert!(!grid.is_dot_set(3, 7));
+    }
+
+    #[test]
+    fn test_scale_and_rotation() {
+        let style = TextStyle::new(FontSize::Small).scale(2);
+        let mut plain = BrailleGrid::new(4, 3).unwrap();
+        draw_text(&mut plain, 0, 0, "L", style).unwrap();
+        // Vertical stroke two dots wide, foot two dots tall
+        assert_eq!(dots(&plain, 6, 10)[0], "##....");
+        assert_eq!(dots(&plain, 6, 10)[9], "######");
+
+        let mut turned = BrailleGrid::new(5, 2).unwrap();
+        draw_text(&mut turned, 0, 0, "L", style.rotation(TextRotation::Cw90)).unwrap();
+        let rows = dots(&turned, 10, 6);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, dot) in row.chars().enumerate() {
+                // Clockwise: (x, y) comes from unrotated (y, 9 - x)
+                assert_eq!(dot == '#', plain.is_dot_set(y, 9 - x), "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_clipping_and_unknown_characters() {
+        // Partly off the grid: clipped, not an error
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        draw_text(&mut grid, -2, -2, "é\nX", FontSize::Small).unwrap();
+
+        let mut question = BrailleGrid::new(4, 2).unwrap();
+        let mut accented = BrailleGrid::new(4, 2).unwrap();
+        draw_text(&mut question, 0, 0, "?", FontSize::Large).unwrap();
+        draw_text(&mut accented, 0, 0, "é", FontSize::Large).unwrap();
+        assert_eq!(dots(&question, 8, 8), dots(&accented, 8, 8));
+    }
+
+    #[test]
+    fn test_color_and_scale_limits() {
+        let red = Color::rgb(255, 0, 0);
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        draw_text(
+            &mut grid,
+            0,
+            0,
+            "I",
+            TextStyle::new(FontSize::Small).color(red),
+        )
+        .unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(red));
+        assert_eq!(grid.get_color(3, 1), None);
+
+        let zero = TextStyle::new(FontSize::Small).scale(0);
+        assert!(matches!(
+            draw_text(&mut grid, 0, 0, "I", zero),
+            Err(DotmaxError::InvalidParameter { .. })
+        ));
+        assert!(draw_text(&mut grid, 0, 0, "I", zero.scale(MAX_TEXT_SCALE + 1)).is_err());
+    }
+
+    #[test]
+    fn test_measure_matches_drawing() {
+        let style = TextStyle::new(FontSize::Large).rotation(TextRotation::Ccw90);
+        let (w, h) = measure_text("Hi\nthere", style);
+        assert_eq!((w, h), (15, 29));
+        let mut grid = BrailleGrid::new(10, 10).unwrap();
+        draw_text(&mut grid, 0, 0, "Hi\nthere", style).unwrap();
+        let rows = dots(&grid, 20, 40);
+        assert!(rows[h..].iter().all(|row| !row.contains('#')));
+        assert!(rows.iter().all(|row| !row[w..].contains('#')));
+        assert_eq!(measure_text("", FontSize::Small), (0, 0));
+    }
+}
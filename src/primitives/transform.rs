@@ -0,0 +1,460 @@
+//! Affine transforms for drawing rotated, scaled, and moved shapes.
+//!
+//! A [`Transform2D`] is a 2×3 affine matrix over dot coordinates. Build one
+//! from [`translate`](Transform2D::translate), [`rotate`](Transform2D::rotate),
+//! and [`scale`](Transform2D::scale) steps, then draw through it with
+//! [`with_transform`]: the closure gets a [`Transformed`] context whose
+//! primitives take untransformed `f64` coordinates, so a sprite can be
+//! described once and drawn at any position, angle, or size.
+//!
+//! Angles are in radians. The y axis points down the screen, so a positive
+//! angle turns clockwise as seen on the terminal.
+//!
+//! Under a non-uniform scale a circle becomes an ellipse, so circles are
+//! drawn as polygons through the transformed outline rather than with the
+//! midpoint algorithm.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::f64::consts::FRAC_PI_4;
+//! use dotmax::BrailleGrid;
+//! use dotmax::primitives::{with_transform, Transform2D};
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?; // 80×48 dots
+//!
+//! // A 20×20 square centered on the origin, turned 45° and moved to the
+//! // middle of the grid
+//! let square = [(-10.0, -10.0), (10.0, -10.0), (10.0, 10.0), (-10.0, 10.0)];
+//! let transform = Transform2D::rotate(FRAC_PI_4).then(&Transform2D::translate(40.0, 24.0));
+//! with_transform(&mut grid, &transform, |ctx| ctx.draw_polygon_filled(&square))?;
+//!
+//! assert!(grid.is_dot_set(40, 24 - 13)); // top corner of the diamond
+//! assert!(!grid.is_dot_set(30, 14)); // where the unrotated corner was
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::f64::consts::TAU;
+
+use super::canvas::clip_segment;
+use super::line::draw_line;
+use super::shapes::{draw_contours_filled, FillRule};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// A 2D affine transform, mapping `(x, y)` to
+/// `(a·x + c·y + tx, b·x + d·y + ty)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    /// Scale and rotation part, column-major: `[a, b, c, d]`
+    pub matrix: [f64; 4],
+    /// Translation part, `(tx, ty)`
+    pub translation: (f64, f64),
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform2D {
+    /// The transform that leaves every point where it is.
+    #[must_use]
+    pub const fn identity() -> Self {
+        Self {
+            matrix: [1.0, 0.0, 0.0, 1.0],
+            translation: (0.0, 0.0),
+        }
+    }
+
+    /// Moves points by `(dx, dy)` dots.
+    #[must_use]
+    pub const fn translate(dx: f64, dy: f64) -> Self {
+        Self {
+            matrix: [1.0, 0.0, 0.0, 1.0],
+            translation: (dx, dy),
+        }
+    }
+
+    /// Turns points about the origin by `angle` radians, clockwise on
+    /// screen.
+    #[must_use]
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            matrix: [cos, sin, -sin, cos],
+            translation: (0.0, 0.0),
+        }
+    }
+
+    /// Turns points about `(cx, cy)` by `angle` radians.
+    #[must_use]
+    pub fn rotate_about(angle: f64, cx: f64, cy: f64) -> Self {
+        Self::translate(-cx, -cy)
+            .then(&Self::rotate(angle))
+            .then(&Self::translate(cx, cy))
+    }
+
+    /// Scales points away from the origin by `sx` horizontally and `sy`
+    /// vertically. A negative factor mirrors that axis.
+    #[must_use]
+    pub const fn scale(sx: f64, sy: f64) -> Self {
+        Self {
+            matrix: [sx, 0.0, 0.0, sy],
+            translation: (0.0, 0.0),
+        }
+    }
+
+    /// This transform followed by `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let [a, b, c, d] = self.matrix;
+        let [na, nb, nc, nd] = next.matrix;
+        Self {
+            matrix: [
+                na.mul_add(a, nc * b),
+                nb.mul_add(a, nd * b),
+                na.mul_add(c, nc * d),
+                nb.mul_add(c, nd * d),
+            ],
+            translation: next.apply(self.translation.0, self.translation.1),
+        }
+    }
+
+    /// Where `(x, y)` ends up.
+    #[must_use]
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        let m = self.matrix;
+        let (tx, ty) = self.translation;
+        (
+            m[0].mul_add(x, m[2].mul_add(y, tx)),
+            m[1].mul_add(x, m[3].mul_add(y, ty)),
+        )
+    }
+
+    /// The transform that undoes this one, or `None` if this one collapses
+    /// the plane onto a line or a point (a zero scale, for instance).
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        let [a, b, c, d] = self.matrix;
+        let det = a.mul_add(d, -(b * c));
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let matrix = [d / det, -b / det, -c / det, a / det];
+        let (tx, ty) = self.translation;
+        let inverse = Self {
+            matrix,
+            translation: (0.0, 0.0),
+        };
+        let (itx, ity) = inverse.apply(tx, ty);
+        Some(Self {
+            matrix,
+            translation: (-itx, -ity),
+        })
+    }
+
+    /// The most a unit length can be stretched, used to size polygon
+    /// approximations.
+    fn max_stretch(&self) -> f64 {
+        let [a, b, c, d] = self.matrix;
+        a.hypot(b).max(c.hypot(d))
+    }
+}
+
+/// Runs `draw` with a context that applies `transform` to every primitive
+/// drawn through it, and returns what `draw` returns.
+pub fn with_transform<R>(
+    grid: &mut BrailleGrid,
+    transform: &Transform2D,
+    draw: impl FnOnce(&mut Transformed<'_>) -> R,
+) -> R {
+    draw(&mut Transformed {
+        grid,
+        transform: *transform,
+    })
+}
+
+/// Drawing context handed out by [`with_transform`].
+#[derive(Debug)]
+pub struct Transformed<'g> {
+    grid: &'g mut BrailleGrid,
+    transform: Transform2D,
+}
+
+impl Transformed<'_> {
+    /// The transform being applied.
+    #[must_use]
+    pub const fn transform(&self) -> &Transform2D {
+        &self.transform
+    }
+
+    /// The underlying grid, for drawing without the transform.
+    pub fn grid_mut(&mut self) -> &mut BrailleGrid {
+        self.grid
+    }
+
+    /// Draws a line between two points.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`draw_line`]; with endpoints clipped to the
+    /// grid there are none in practice.
+    pub fn draw_line(&mut self, x0: f64, y0: f64, x1: f64, y1: f64) -> Result<(), DotmaxError> {
+        let start = self.transform.apply(x0, y0);
+        let end = self.transform.apply(x1, y1);
+        self.line_dots(start, end)
+    }
+
+    /// Draws a closed polygon outline.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPolygon` if `vertices.len()` < 3.
+    pub fn draw_polygon(&mut self, vertices: &[(f64, f64)]) -> Result<(), DotmaxError> {
+        check_vertices(vertices)?;
+        let points = self.map(vertices);
+        self.outline(&points)
+    }
+
+    /// Draws a filled polygon, even-odd rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPolygon` if `vertices.len()` < 3.
+    pub fn draw_polygon_filled(&mut self, vertices: &[(f64, f64)]) -> Result<(), DotmaxError> {
+        check_vertices(vertices)?;
+        let points = self.map(vertices);
+        self.fill(&points)
+    }
+
+    /// Draws a circle outline, which comes out as an ellipse under a
+    /// non-uniform scale.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from [`draw_line`]; there are none in practice.
+    pub fn draw_circle(&mut self, cx: f64, cy: f64, radius: f64) -> Result<(), DotmaxError> {
+        let points = self.circle_points(cx, cy, radius);
+        self.outline(&points)
+    }
+
+    /// Draws a filled circle.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from the polygon fill; there are none in practice.
+    pub fn draw_circle_filled(&mut self, cx: f64, cy: f64, radius: f64) -> Result<(), DotmaxError> {
+        let points = self.circle_points(cx, cy, radius);
+        self.fill(&points)
+    }
+
+    /// Copies the set dots of `src`, with its top-left corner at `(x, y)`.
+    ///
+    /// Source dot `(i, j)` covers the square from `(x + i, y + j)` to
+    /// `(x + i + 1, y + j + 1)` before the transform. Each destination dot
+    /// samples the source square its center maps back into, so a
+    /// scaled-up sprite stays solid instead of spreading into separate
+    /// dots. Cell colors from `src` carry over to the cells that receive
+    /// dots. A transform that collapses the plane draws nothing.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn blit(&mut self, src: &BrailleGrid, x: f64, y: f64) {
+        let Some(inverse) = self.transform.inverse() else {
+            return;
+        };
+        let (width, height) = (src.dot_width() as f64, src.dot_height() as f64);
+
+        // Destination bounding box of the source's dot area
+        let corners = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)]
+            .map(|(dx, dy)| self.transform.apply(x + dx, y + dy));
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for (px, py) in corners {
+            min = (min.0.min(px), min.1.min(py));
+            max = (max.0.max(px), max.1.max(py));
+        }
+        let x_range = min.0.floor().max(0.0) as usize
+            ..(max.0.ceil().max(0.0) as usize).min(self.grid.dot_width());
+        let y_range = min.1.floor().max(0.0) as usize
+            ..(max.1.ceil().max(0.0) as usize).min(self.grid.dot_height());
+
+        for dot_y in y_range {
+            for dot_x in x_range.clone() {
+                // Sample at the center of the destination dot
+                let (lx, ly) = inverse.apply(dot_x as f64 + 0.5, dot_y as f64 + 0.5);
+                let (sx, sy) = ((lx - x).floor(), (ly - y).floor());
+                if sx < 0.0 || sy < 0.0 || sx >= width || sy >= height {
+                    continue;
+                }
+                let (sx, sy) = (sx as usize, sy as usize);
+                if src.is_dot_set(sx, sy) {
+                    // In bounds by construction
+                    let _ = self.grid.set_dot(dot_x, dot_y);
+                    if let Some(color) = src.get_color(sx / 2, sy / 4) {
+                        let _ = self.grid.set_cell_color(dot_x / 2, dot_y / 4, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn map(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        points
+            .iter()
+            .map(|&(x, y)| self.transform.apply(x, y))
+            .collect()
+    }
+
+    /// Transformed points around a circle, about two dots apart.
+    fn circle_points(&self, cx: f64, cy: f64, radius: f64) -> Vec<(f64, f64)> {
+        let length = TAU * radius.abs() * self.transform.max_stretch();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let segments = (length / 2.0).ceil().clamp(8.0, 10_000.0) as u32;
+        (0..segments)
+            .map(|i| {
+                let angle = TAU * f64::from(i) / f64::from(segments);
+                let (sin, cos) = angle.sin_cos();
+                self.transform
+                    .apply(radius.mul_add(cos, cx), radius.mul_add(sin, cy))
+            })
+            .collect()
+    }
+
+    fn outline(&mut self, points: &[(f64, f64)]) -> Result<(), DotmaxError> {
+        for (i, &start) in points.iter().enumerate() {
+            self.line_dots(start, points[(i + 1) % points.len()])?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn fill(&mut self, points: &[(f64, f64)]) -> Result<(), DotmaxError> {
+        // Saturating casts keep far-off vertices far off
+        let vertices: Vec<(i32, i32)> = points
+            .iter()
+            .map(|&(x, y)| (x.round() as i32, y.round() as i32))
+            .collect();
+        draw_contours_filled(self.grid, &[&vertices], FillRule::EvenOdd)
+    }
+
+    /// Draws a line between two transformed points, clipped to the grid.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn line_dots(&mut self, start: (f64, f64), end: (f64, f64)) -> Result<(), DotmaxError> {
+        if ![start.0, start.1, end.0, end.1]
+            .iter()
+            .all(|v| v.is_finite())
+        {
+            return Ok(());
+        }
+        // One dot beyond each edge, so rounding never pulls a visible dot
+        // off the line
+        let max = (self.grid.dot_width() as f64, self.grid.dot_height() as f64);
+        let Some((start, end)) = clip_segment(start, end, (-1.0, -1.0), max) else {
+            return Ok(());
+        };
+        draw_line(
+            self.grid,
+            start.0.round() as i32,
+            start.1.round() as i32,
+            end.0.round() as i32,
+            end.1.round() as i32,
+        )
+    }
+}
+
+fn check_vertices(vertices: &[(f64, f64)]) -> Result<(), DotmaxError> {
+    if vertices.len() < 3 {
+        return Err(DotmaxError::InvalidPolygon {
+            reason: format!("Polygon requires ≥3 vertices, got {}", vertices.len()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_close(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_compose_and_invert() {
+        let transform = Transform2D::scale(2.0, 3.0)
+            .then(&Transform2D::rotate(FRAC_PI_2))
+            .then(&Transform2D::translate(10.0, 5.0));
+        // (1, 1) → (2, 3) → rotated a quarter turn clockwise (-3, 2) → (7, 7)
+        assert_close(transform.apply(1.0, 1.0), (7.0, 7.0));
+
+        let inverse = transform.inverse().unwrap();
+        assert_close(inverse.apply(7.0, 7.0), (1.0, 1.0));
+        assert_close(
+            Transform2D::rotate_about(FRAC_PI_2, 5.0, 5.0).apply(10.0, 5.0),
+            (5.0, 10.0),
+        );
+        assert!(Transform2D::scale(0.0, 1.0).inverse().is_none());
+        assert_eq!(Transform2D::default(), Transform2D::identity());
+    }
+
+    #[test]
+    fn test_identity_matches_dot_primitives() {
+        let mut direct = BrailleGrid::new(20, 10).unwrap();
+        crate::primitives::draw_line(&mut direct, 3, 4, 35, 30).unwrap();
+        let mut transformed = BrailleGrid::new(20, 10).unwrap();
+        with_transform(&mut transformed, &Transform2D::identity(), |ctx| {
+            ctx.draw_line(3.0, 4.0, 35.0, 30.0)
+        })
+        .unwrap();
+        assert_eq!(direct.get_raw_patterns(), transformed.get_raw_patterns());
+    }
+
+    #[test]
+    fn test_scaled_circle_is_ellipse() {
+        let mut grid = BrailleGrid::new(40, 12).unwrap();
+        let transform = Transform2D::scale(3.0, 1.0).then(&Transform2D::translate(40.0, 24.0));
+        with_transform(&mut grid, &transform, |ctx| ctx.draw_circle(0.0, 0.0, 10.0)).unwrap();
+        assert!(grid.is_dot_set(70, 24) && grid.is_dot_set(10, 24));
+        assert!(grid.is_dot_set(40, 14) && grid.is_dot_set(40, 34));
+        assert!(!grid.is_dot_set(40, 24));
+
+        let result = with_transform(&mut grid, &transform, |ctx| {
+            ctx.draw_polygon(&[(0.0, 0.0), (1.0, 1.0)])
+        });
+        assert!(matches!(result, Err(DotmaxError::InvalidPolygon { .. })));
+    }
+
+    #[test]
+    fn test_blit_rotated_and_scaled() {
+        let mut sprite = BrailleGrid::new(2, 1).unwrap(); // 4×4 dots
+        for x in 0..4 {
+            sprite.set_dot(x, 0).unwrap(); // top row
+        }
+
+        // A quarter turn about the center of the sprite's first dot puts
+        // the row in a column
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        let turn = Transform2D::rotate_about(FRAC_PI_2, 10.5, 10.5);
+        with_transform(&mut grid, &turn, |ctx| ctx.blit(&sprite, 10.0, 10.0));
+        for y in 10..14 {
+            assert!(grid.is_dot_set(10, y));
+        }
+        assert!(!grid.is_dot_set(11, 10));
+
+        // Doubling the size fills every dot, with no gaps between samples
+        let mut grid = BrailleGrid::new(10, 5).unwrap();
+        with_transform(&mut grid, &Transform2D::scale(2.0, 2.0), |ctx| {
+            ctx.blit(&sprite, 0.0, 0.0);
+        });
+        assert!((0..8).all(|x| grid.is_dot_set(x, 0) && grid.is_dot_set(x, 1)));
+        assert!(!grid.is_dot_set(0, 2) && !grid.is_dot_set(8, 0));
+    }
+}
@@ -41,6 +41,20 @@
 //! # Ok::<(), dotmax::DotmaxError>(())
 //! ```
 //!
+//! ## Plot Some Numbers
+//!
+//! ```no_run
+//! use dotmax::quick;
+//!
+//! let latencies = [12.0, 15.5, 11.2, 30.8, 14.1, 13.9, 45.0, 12.7];
+//!
+//! // A line chart filling the terminal, redrawn on resize until a keypress
+//! quick::plot(&latencies)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+//!
+//! [`plot_stdin`] does the same live for numbers piped in.
+//!
 //! ## Load an Image for Manipulation
 //!
 //! ```ignore
@@ -64,6 +78,11 @@
 //! - **Dithering**: Floyd-Steinberg (best quality for most images)
 //! - **Aspect ratio**: Preserved (no distortion)
 //! - **Wait behavior**: `show()` and `show_image()` wait for any keypress before returning
+//! - **Playback**: animations and video show a status bar that auto-hides; space pauses,
+//!   arrows seek, `q` quits, any unbound key stops (see [`crate::keymap`] to rebind)
+//! - **Piped output**: when stdout isn't a terminal, the `show*` functions print the
+//!   grid as plain text (the first frame, for animations and video) and return at once.
+//!   Output is sized to `$COLUMNS`×`$LINES` or 80×24; see [`set_pipe_width`]
 //!
 //! # Performance
 //!
@@ -82,7 +101,15 @@
 //! [`BrailleGrid`]: crate::BrailleGrid
 //! [`TerminalRenderer`]: crate::TerminalRenderer
 
+#[cfg(feature = "image")]
+use crate::keymap::Keymap;
+use crate::plot::stream::{NumberStream, StreamPlot};
+use crate::plot::{LineChart, ScatterPlot, Series};
+#[cfg(feature = "image")]
+use crate::viewer_state::{FileKey, ViewerEntry, ViewerState};
 use crate::{BrailleGrid, Result, TerminalRenderer};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 // ============================================================================
 // Terminal Size Detection (AC: #6)
@@ -94,17 +121,24 @@ const DEFAULT_HEIGHT: usize = 24;
 
 /// Detects the current terminal size with fallback.
 ///
-/// Uses `crossterm::terminal::size()` to detect terminal dimensions.
+/// Uses `crossterm::terminal::size()` to detect terminal dimensions, less any
+/// [safe area](crate::render::set_safe_area) margins.
 /// Returns `(80, 24)` if detection fails (e.g., running without a terminal).
+/// When stdout isn't a terminal, returns the [piped output](set_pipe_width)
+/// size instead.
 ///
 /// # Returns
 ///
 /// Tuple of `(width, height)` in terminal cells.
 #[inline]
 fn terminal_size() -> (usize, usize) {
-    crossterm::terminal::size()
-        .map(|(w, h)| (w as usize, h as usize))
-        .unwrap_or((DEFAULT_WIDTH, DEFAULT_HEIGHT))
+    if !stdout_is_terminal() {
+        return pipe_size();
+    }
+    crossterm::terminal::size().map_or((DEFAULT_WIDTH, DEFAULT_HEIGHT), |(w, h)| {
+        let (w, h) = crate::render::safe_area().inner_size(w, h);
+        (w as usize, h as usize)
+    })
 }
 
 /// Waits for any keypress.
@@ -122,6 +156,119 @@ fn wait_for_key() -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// Piped Output
+// ============================================================================
+
+/// Width set by [`set_pipe_width`], or 0 if unset.
+static PIPE_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether stdout is a terminal rather than a file or pipe.
+fn stdout_is_terminal() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+/// Set the width, in cells, of output printed when stdout isn't a
+/// terminal, for the rest of the process.
+///
+/// With `None` (the default) the width comes from `$COLUMNS`, or is 80 if
+/// that isn't set. The height comes from `$LINES`, or is 24. Colors are
+/// left out unless `$CLICOLOR_FORCE` is set (and `$NO_COLOR` isn't).
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// // `my-tool > out.txt` writes 120 columns of braille
+/// quick::set_pipe_width(Some(120));
+/// quick::plot(&[3.0, 1.0, 4.0, 1.0, 5.0])?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn set_pipe_width(width: Option<usize>) {
+    PIPE_WIDTH.store(width.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// The `(width, height)` of piped output; see [`set_pipe_width`].
+fn pipe_size() -> (usize, usize) {
+    let width = Some(PIPE_WIDTH.load(Ordering::Relaxed)).filter(|&w| w > 0);
+    pipe_size_from(
+        width,
+        std::env::var("COLUMNS").ok().as_deref(),
+        std::env::var("LINES").ok().as_deref(),
+    )
+}
+
+/// Testable core of [`pipe_size`]. Unparseable or zero sizes are ignored.
+fn pipe_size_from(
+    width: Option<usize>,
+    columns: Option<&str>,
+    lines: Option<&str>,
+) -> (usize, usize) {
+    let parse = |value: Option<&str>| {
+        value
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|&n| n > 0)
+    };
+    (
+        width.or_else(|| parse(columns)).unwrap_or(DEFAULT_WIDTH),
+        parse(lines).unwrap_or(DEFAULT_HEIGHT),
+    )
+}
+
+/// Whether piped output keeps its colors, following the `NO_COLOR` and
+/// `CLICOLOR_FORCE` conventions: off unless forced, and `NO_COLOR` wins.
+fn pipe_colors_from(no_color: Option<&str>, clicolor_force: Option<&str>) -> bool {
+    let set = |value: Option<&str>| value.is_some_and(|v| !v.is_empty());
+    !set(no_color) && set(clicolor_force) && clicolor_force != Some("0")
+}
+
+/// Prints `grid` for a file or pipe, with colors only if asked for.
+fn print_piped(grid: &BrailleGrid) -> Result<()> {
+    let colors = pipe_colors_from(
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("CLICOLOR_FORCE").ok().as_deref(),
+    );
+    crate::render::print_grid(grid, colors)
+}
+
+// ============================================================================
+// Final Frame Persistence
+// ============================================================================
+
+static KEEP_FINAL_FRAME: AtomicBool = AtomicBool::new(false);
+
+/// Leave the last frame on screen when display ends, for the rest of the
+/// process.
+///
+/// By default [`show`] and the `show_*` functions restore the terminal to
+/// how it was, so what they displayed disappears. With `keep` set, the
+/// last frame shown (the grid itself for [`show`], the final frame of an
+/// animation or video, wherever playback stopped) is printed to the normal
+/// screen with its colors once the terminal is restored, where it stays in
+/// scrollback. The playback bar is never included.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// quick::set_keep_final_frame(true);
+/// let grid = quick::plot_grid(&[9.0, 7.0, 4.0, 1.0], 40, 12)?;
+/// quick::show(&grid)?;
+/// println!("^ still here after the keypress");
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn set_keep_final_frame(keep: bool) {
+    KEEP_FINAL_FRAME.store(keep, Ordering::Relaxed);
+}
+
+/// Whether [`set_keep_final_frame`] is on.
+#[must_use]
+pub fn keep_final_frame() -> bool {
+    KEEP_FINAL_FRAME.load(Ordering::Relaxed)
+}
+
 // ============================================================================
 // Core Functions (AC: #2, #3)
 // ============================================================================
@@ -195,7 +342,11 @@ pub fn grid_sized(width: usize, height: usize) -> Result<BrailleGrid> {
 /// 1. Initializes the terminal (enters raw mode, alternate screen)
 /// 2. Renders the grid
 /// 3. Waits for any keypress
-/// 4. Cleans up terminal state
+/// 4. Cleans up terminal state, printing the grid to the normal screen if
+///    [`set_keep_final_frame`] is on
+///
+/// When stdout isn't a terminal, the grid is printed as text instead and
+/// the function returns without waiting.
 ///
 /// # Arguments
 ///
@@ -223,13 +374,370 @@ pub fn grid_sized(width: usize, height: usize) -> Result<BrailleGrid> {
 /// For non-blocking rendering or more control over terminal state,
 /// use [`TerminalRenderer`] directly.
 pub fn show(grid: &BrailleGrid) -> Result<()> {
+    if !stdout_is_terminal() {
+        return print_piped(grid);
+    }
     let mut renderer = TerminalRenderer::new()?;
     renderer.render(grid)?;
     wait_for_key()?;
-    // TerminalRenderer::drop() handles cleanup automatically
+    renderer.cleanup()?;
+    if keep_final_frame() {
+        crate::render::render_once_to_stdout(grid)?;
+    }
     Ok(())
 }
 
+// ============================================================================
+// Plotting
+// ============================================================================
+
+/// Plots `values` as a line chart filling the terminal and waits for a
+/// keypress.
+///
+/// Values go at x = 0, 1, 2, ..., and both axes fit the data. The chart
+/// is redrawn at the new size whenever the terminal is resized. When
+/// stdout isn't a terminal, it is printed once as text instead, like
+/// [`show`] does.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::Terminal` for I/O errors, or
+/// `DotmaxError::TerminalBackend` if the terminal is too small.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// let samples: Vec<f64> = (0..200).map(|i| (f64::from(i) / 10.0).sin()).collect();
+/// quick::plot(&samples)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn plot(values: &[f64]) -> Result<()> {
+    show_chart(|width, height| plot_grid(values, width, height))
+}
+
+/// Plots `points` as a scatter plot filling the terminal and waits for a
+/// keypress, as [`plot`] does.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::Terminal` for I/O errors, or
+/// `DotmaxError::TerminalBackend` if the terminal is too small.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// let heights_weights = [(160.0, 55.0), (172.0, 68.5), (181.0, 80.2), (168.0, 61.0)];
+/// quick::scatter(&heights_weights)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn scatter(points: &[(f64, f64)]) -> Result<()> {
+    show_chart(|width, height| scatter_grid(points, width, height))
+}
+
+/// The chart [`plot`] shows, drawn into a new `width × height` grid
+/// instead of displayed.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::InvalidDimensions` if width or height is 0 or
+/// exceeds 10,000.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::quick;
+///
+/// let grid = quick::plot_grid(&[1.0, 4.0, 9.0, 16.0], 40, 12)?;
+/// assert_eq!(grid.dimensions(), (40, 12));
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn plot_grid(values: &[f64], width: usize, height: usize) -> Result<BrailleGrid> {
+    let mut grid = BrailleGrid::new(width, height)?;
+    LineChart::new()
+        .series(Series::from_values(values))
+        .render(&mut grid, 0, 0, width, height)?;
+    Ok(grid)
+}
+
+/// The chart [`scatter`] shows, drawn into a new `width × height` grid
+/// instead of displayed.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::InvalidDimensions` if width or height is 0 or
+/// exceeds 10,000.
+pub fn scatter_grid(points: &[(f64, f64)], width: usize, height: usize) -> Result<BrailleGrid> {
+    let mut grid = BrailleGrid::new(width, height)?;
+    ScatterPlot::new()
+        .series(Series::new(points.iter().copied()))
+        .render(&mut grid, 0, 0, width, height)?;
+    Ok(grid)
+}
+
+/// Charts numbers read from stdin live, as they arrive.
+///
+/// For `mycommand | my-tool`: each line of input is a sample, and several
+/// numbers on a line (separated by spaces, commas, or semicolons) are
+/// several series; see [`plot::stream`](crate::plot::stream). The chart
+/// shows the last 500 lines, redrawn as each arrives, and its title shows
+/// the newest values. `q`, Esc, or Ctrl+C stops early.
+///
+/// At end of input the final chart stays up until a keypress, and is
+/// printed to the normal screen once the terminal is restored. When stdout
+/// isn't a terminal, the whole input is read and the final chart printed
+/// as text.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::Terminal` if reading stdin fails or for terminal
+/// I/O errors, or `DotmaxError::TerminalBackend` if the terminal is too
+/// small.
+///
+/// # Examples
+///
+/// ```no_run
+/// // ping example.com | awk -F'time=' '{ print $2 + 0 }' | my-tool
+/// dotmax::quick::plot_stdin()?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn plot_stdin() -> Result<()> {
+    show_stream(NumberStream::stdin())
+}
+
+/// [`plot_stdin`] for numbers from any reader, such as a socket or a
+/// child process's output.
+///
+/// # Errors
+///
+/// As for [`plot_stdin`].
+pub fn plot_stream(reader: impl std::io::BufRead + Send + 'static) -> Result<()> {
+    show_stream(NumberStream::spawn(reader))
+}
+
+/// Lines of input the streaming plots keep on screen.
+const STREAM_WINDOW: usize = 500;
+
+/// The loop behind [`plot_stdin`] and [`plot_stream`].
+fn show_stream(mut stream: NumberStream) -> Result<()> {
+    use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+    use std::time::Duration;
+
+    let mut plot = StreamPlot::new(STREAM_WINDOW);
+    let draw = |plot: &StreamPlot, status: &str| {
+        let (width, height) = terminal_size();
+        let mut grid = BrailleGrid::new(width, height)?;
+        plot.chart()
+            .title(stream_title(plot, status))
+            .render(&mut grid, 0, 0, width, height)?;
+        Ok::<_, crate::DotmaxError>(grid)
+    };
+
+    if !stdout_is_terminal() {
+        while !stream.is_closed() {
+            if !stream.drain_into(&mut plot)? {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        return print_piped(&draw(&plot, "end of input")?);
+    }
+
+    let mut renderer = TerminalRenderer::new()?;
+    let status = |stream: &NumberStream| {
+        if stream.is_closed() {
+            "end of input, any key exits"
+        } else {
+            "q quits"
+        }
+    };
+    let mut grid = draw(&plot, status(&stream))?;
+    let result = (|| -> Result<()> {
+        let mut stale = true;
+        loop {
+            let open = !stream.is_closed();
+            stale |= stream.drain_into(&mut plot)?;
+            stale |= open && stream.is_closed();
+            if stale {
+                grid = draw(&plot, status(&stream))?;
+                renderer.render(&grid)?;
+                stale = false;
+            }
+            if !event::poll(Duration::from_millis(30))? {
+                continue;
+            }
+            match event::read()? {
+                Event::Key(key) => {
+                    let ctrl_c = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if stream.is_closed()
+                        || ctrl_c
+                        || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                    {
+                        return Ok(());
+                    }
+                }
+                Event::Resize(..) => stale = true,
+                _ => {}
+            }
+        }
+    })();
+    renderer.cleanup()?;
+    // Piping data in is usually about where it ends up, so the last chart
+    // stays in scrollback whatever `set_keep_final_frame` says
+    crate::render::render_once_to_stdout(&grid)?;
+    result
+}
+
+/// The newest values, how many lines have arrived, and `status`, for the
+/// chart title.
+fn stream_title(plot: &StreamPlot, status: &str) -> String {
+    let latest = plot.latest().map_or_else(String::new, |row| {
+        let values: Vec<String> = row.iter().map(f64::to_string).collect();
+        values.join("  ")
+    });
+    format!("{latest}  [{} lines, {status}]", plot.total())
+}
+
+/// Shows the grid `draw` makes at the terminal size until a keypress,
+/// drawing it again after each resize.
+fn show_chart(draw: impl Fn(usize, usize) -> Result<BrailleGrid>) -> Result<()> {
+    use crossterm::event::{self, Event};
+
+    let (width, height) = terminal_size();
+    let mut grid = draw(width, height)?;
+    if !stdout_is_terminal() {
+        return print_piped(&grid);
+    }
+    let mut renderer = TerminalRenderer::new()?;
+    let result = (|| -> Result<()> {
+        loop {
+            renderer.render(&grid)?;
+            match event::read()? {
+                Event::Key(_) => return Ok(()),
+                Event::Resize(..) => {
+                    let (width, height) = terminal_size();
+                    grid = draw(width, height)?;
+                }
+                _ => {}
+            }
+        }
+    })();
+    renderer.cleanup()?;
+    if keep_final_frame() {
+        crate::render::render_once_to_stdout(&grid)?;
+    }
+    result
+}
+
+// ============================================================================
+// QR Codes - Feature-gated
+// ============================================================================
+
+/// Modules of light margin a QR code needs on every side to scan.
+#[cfg(feature = "qr")]
+const QR_QUIET_ZONE: usize = 4;
+
+/// Renders `data` as a QR code sized to the terminal.
+///
+/// Each module is drawn as a square of dots, as large as fits the terminal
+/// (see [`render_qr_sized`]), so short URLs come out big enough to scan
+/// from across a desk and long text still fits. Braille packs 8 dots into a
+/// cell where block characters manage 2, so codes four times denser than
+/// block-drawn ones stay on screen.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::QrError` if `data` is too long for a QR code
+/// (about 2,900 bytes).
+///
+/// # Examples
+///
+/// ```ignore
+/// // Requires `qr` feature
+/// use dotmax::quick;
+///
+/// let grid = quick::render_qr("https://example.com/pair?code=4821")?;
+/// quick::show(&grid)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "qr")]
+pub fn render_qr(data: &str) -> Result<BrailleGrid> {
+    let code = encode_qr(data)?;
+    let (width, height) = terminal_size();
+    let span = code.width() + QR_QUIET_ZONE * 2;
+    let module_dots = (width * 2 / span).min(height * 4 / span).max(1);
+    qr_grid(&code, module_dots)
+}
+
+/// Renders `data` as a QR code with each module `module_dots` dots square.
+///
+/// The code sits in the top-left corner of a grid just big enough for it
+/// and a four-module quiet zone on every side. Dots mark the light modules
+/// and the quiet zone, so on the usual light-on-dark terminal the code
+/// shows dark modules on a light field, the way scanners expect.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::QrError` if `data` is too long for a QR code, or
+/// `DotmaxError::InvalidParameter` if `module_dots` is 0.
+///
+/// # Examples
+///
+/// ```ignore
+/// // Requires `qr` feature
+/// use dotmax::quick;
+///
+/// // "hello" fits the smallest code: 21 modules plus 8 of quiet zone
+/// let grid = quick::render_qr_sized("hello", 1)?;
+/// assert_eq!(grid.dimensions(), (15, 8)); // 29×29 dots, rounded up to cells
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "qr")]
+pub fn render_qr_sized(data: &str, module_dots: usize) -> Result<BrailleGrid> {
+    if module_dots == 0 {
+        return Err(crate::DotmaxError::InvalidParameter {
+            parameter_name: "QR module size".to_string(),
+            value: "0".to_string(),
+            min: "1".to_string(),
+            max: "unbounded".to_string(),
+        });
+    }
+    qr_grid(&encode_qr(data)?, module_dots)
+}
+
+#[cfg(feature = "qr")]
+fn encode_qr(data: &str) -> Result<qrcode::QrCode> {
+    qrcode::QrCode::new(data.as_bytes()).map_err(|e| crate::DotmaxError::QrError(e.to_string()))
+}
+
+/// Draws `code` with its quiet zone, setting a dot for every light module.
+#[cfg(feature = "qr")]
+fn qr_grid(code: &qrcode::QrCode, module_dots: usize) -> Result<BrailleGrid> {
+    let modules = code.width();
+    let span = (modules + QR_QUIET_ZONE * 2) * module_dots;
+    // Round up to whole cells; the spare dots widen the quiet zone
+    let mut grid = BrailleGrid::new((span + 1) / 2, (span + 3) / 4)?;
+    let colors = code.to_colors();
+    let is_dark = |dot_x: usize, dot_y: usize| {
+        let (x, y) = (dot_x / module_dots, dot_y / module_dots);
+        let inside = QR_QUIET_ZONE..QR_QUIET_ZONE + modules;
+        inside.contains(&x)
+            && inside.contains(&y)
+            && colors[(y - QR_QUIET_ZONE) * modules + x - QR_QUIET_ZONE] == qrcode::Color::Dark
+    };
+    for dot_y in 0..grid.dot_height() {
+        for dot_x in 0..grid.dot_width() {
+            if !is_dark(dot_x, dot_y) {
+                grid.set_dot(dot_x, dot_y)?;
+            }
+        }
+    }
+    Ok(grid)
+}
+
 // ============================================================================
 // Image Functions (AC: #4, #5, #7) - Feature-gated
 // ============================================================================
@@ -268,6 +776,37 @@ pub fn show_image(path: impl AsRef<std::path::Path>) -> Result<()> {
     show(&grid)
 }
 
+/// Like [`show_image`], with a caption along the bottom row.
+///
+/// The caption is [`ImageMetadata::caption`](crate::image::ImageMetadata::caption):
+/// the date taken, camera, and dimensions from the photo's EXIF, leaving
+/// out whatever the file doesn't record. It is clipped to the grid width.
+///
+/// # Errors
+///
+/// Returns `DotmaxError::ImageLoad` if the file doesn't exist or format
+/// is unsupported, or terminal errors during display.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// quick::show_image_with_caption("holiday.jpg")?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn show_image_with_caption(path: impl AsRef<std::path::Path>) -> Result<()> {
+    let path = path.as_ref();
+    let caption = crate::image::metadata(path)?.caption();
+    let mut grid = load_image(path)?;
+    let bottom = grid.height().saturating_sub(1);
+    for (x, c) in caption.chars().enumerate().take(grid.width()) {
+        grid.set_char(x, bottom, c)?;
+    }
+    show(&grid)
+}
+
 /// Loads an image into a [`BrailleGrid`] for further manipulation.
 ///
 /// Like `show_image()` but returns the grid instead of displaying it.
@@ -372,8 +911,17 @@ pub fn load_image_sized(
 ///
 /// - **Static Images**: PNG, JPEG, GIF (static), BMP, WebP, TIFF
 /// - **Vector Graphics**: SVG (requires `svg` feature)
-/// - **Animated** (future): Animated GIF, APNG
-/// - **Video** (future): MP4, MKV, AVI, WebM
+/// - **Animated**: Animated GIF, APNG, animated WebP
+/// - **Video**: MP4, MKV, AVI, WebM, MOV (requires `video` feature)
+///
+/// # Fallbacks
+///
+/// The path taken is decided by [`media::route`](crate::media::route),
+/// which falls back rather than failing where it can: SVG or video without
+/// its feature shows a card naming the missing feature, animations and
+/// video piped to a file print one frame, and images on terminals that
+/// only show ASCII are drawn with density characters. Fallbacks are logged
+/// as `tracing` warnings.
 ///
 /// # Arguments
 ///
@@ -382,7 +930,7 @@ pub fn load_image_sized(
 /// # Errors
 ///
 /// - `DotmaxError::Terminal` - File not found or read error
-/// - `DotmaxError::FormatError` - Unsupported or unknown format
+/// - `DotmaxError::FormatError` - Unknown format
 /// - `DotmaxError::ImageLoad` - Image decode error
 ///
 /// # Examples
@@ -402,56 +950,141 @@ pub fn load_image_sized(
 /// Format is detected by reading the first 16 bytes (magic bytes) of the file.
 /// If magic bytes are inconclusive, the file extension is used as a fallback.
 /// This detection is fast (<5ms) regardless of file size.
+///
+/// # Playback Controls
+///
+/// Animations and video respond to the [default keymap](Keymap::default):
+/// space pauses, the arrow keys seek five seconds, `q` or Esc quits, and
+/// any unbound key stops as well. Use [`show_file_with_keymap`] to change
+/// them.
 #[cfg(feature = "image")]
 pub fn show_file(path: impl AsRef<std::path::Path>) -> Result<()> {
-    use crate::media::{detect_format, MediaFormat};
-    use crate::DotmaxError;
+    show_file_with_keymap(path, &Keymap::default())
+}
+
+/// Like [`show_file`], with playback controls taken from `keymap`.
+///
+/// Every [`KeyAction`](crate::keymap::KeyAction) works during animation
+/// and video playback. Seeking, dithering, and color-mode changes apply to
+/// media that support them (dithering and color mode only affect video);
+/// scheme cycling recolors any frame by dot density. Screenshots are saved
+/// as braille text in the working directory. Keys without a binding stop
+/// playback, so a map can't trap the viewer. Static images and SVGs are
+/// shown as with [`show_file`].
+///
+/// # Errors
+///
+/// The same as [`show_file`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::keymap::{KeyAction, Keymap};
+/// use dotmax::quick;
+///
+/// let mut keymap = Keymap::default();
+/// keymap.bind("k".parse()?, KeyAction::Pause);
+/// keymap.bind("l".parse()?, KeyAction::SeekForward);
+/// keymap.bind("j".parse()?, KeyAction::SeekBackward);
+///
+/// quick::show_file_with_keymap("clip.gif", &keymap)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+///
+/// With the `serde` feature the map can come from a config file (see the
+/// [`keymap`](crate::keymap) module docs for the format).
+#[cfg(feature = "image")]
+pub fn show_file_with_keymap(path: impl AsRef<std::path::Path>, keymap: &Keymap) -> Result<()> {
+    show_file_session(path.as_ref(), keymap, &mut ViewerEntry::default())
+}
 
+/// Like [`show_file_with_keymap`], resuming from and saving to `state`.
+///
+/// If `state` has a record for this file (matched by contents, see
+/// [`FileKey`]), playback starts at its position with its dithering, color
+/// mode, and scheme. On exit the file's record is replaced with where
+/// playback stopped and the settings in use, and `state` is saved. Media
+/// played to the end restart from the beginning next time.
+///
+/// # Errors
+///
+/// The same as [`show_file`], plus
+/// [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the file
+/// can't be hashed or the state can't be saved.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::keymap::Keymap;
+/// use dotmax::quick;
+/// use dotmax::viewer_state::ViewerState;
+///
+/// let mut state = ViewerState::load(ViewerState::default_path().unwrap())?;
+/// quick::show_file_resuming("lecture.mp4", &Keymap::default(), &mut state)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn show_file_resuming(
+    path: impl AsRef<std::path::Path>,
+    keymap: &Keymap,
+    state: &mut ViewerState,
+) -> Result<()> {
     let path = path.as_ref();
+    let key = FileKey::of(path)?;
+    let mut session = state.get(key).cloned().unwrap_or_default();
+
+    let result = show_file_session(path, keymap, &mut session);
+    state.record(key, session);
+    state.save()?;
+    result
+}
+
+/// Shows any media file, restoring and updating `session` for animations
+/// and video.
+#[cfg(feature = "image")]
+fn show_file_session(
+    path: &std::path::Path,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    use crate::media::route::{route, Environment, Route};
+    use crate::media::{detect_format, MediaFormat};
+
     let format = detect_format(path)?;
+    let decision = route(format, &Environment::detect())?;
+    note_fallback(path, decision);
 
-    match format {
-        MediaFormat::StaticImage(_) => {
-            // Route to existing show_image() implementation
-            show_image(path)
-        }
-        MediaFormat::Svg => {
-            // Route to SVG rendering (if feature enabled)
+    match decision.route {
+        Route::Image => show_image(path),
+        Route::Density => show(&load_density(path)?),
+        Route::Svg => {
             #[cfg(feature = "svg")]
             {
                 show_svg(path)
             }
             #[cfg(not(feature = "svg"))]
             {
-                Err(DotmaxError::FormatError {
-                    format: "SVG (requires 'svg' feature)".to_string(),
-                })
+                Err(crate::DotmaxError::FeatureDisabled { feature: "svg" })
             }
         }
-        MediaFormat::AnimatedGif => {
-            // Route to animated GIF playback (Story 9.2)
-            play_animated_gif(path)
-        }
-        MediaFormat::AnimatedPng => {
-            // Route to APNG playback (Story 9.3)
-            play_animated_png(path)
-        }
-        MediaFormat::Video(_codec) => {
-            // Route to video playback (Story 9.4)
+        Route::Animation => match format {
+            MediaFormat::AnimatedPng => play_animated_png(path, keymap, session),
+            MediaFormat::AnimatedWebp => play_animated_webp(path, keymap, session),
+            _ => play_animated_gif(path, keymap, session),
+        },
+        Route::Video => {
             #[cfg(feature = "video")]
             {
-                play_video(path)
+                play_video(path, keymap, session)
             }
             #[cfg(not(feature = "video"))]
             {
-                Err(DotmaxError::FormatError {
-                    format: "video (requires 'video' feature and FFmpeg libraries)".to_string(),
-                })
+                Err(crate::DotmaxError::FeatureDisabled { feature: "video" })
             }
         }
-        MediaFormat::Unknown => Err(DotmaxError::FormatError {
-            format: "unknown format".to_string(),
-        }),
+        // Resumed files show the frame where they were left
+        Route::Poster => show(&crate::media::poster_frame(path, session.position)?),
+        Route::Placeholder => show(&placeholder_card(path, decision)?),
     }
 }
 
@@ -460,11 +1093,16 @@ pub fn show_file(path: impl AsRef<std::path::Path>) -> Result<()> {
 /// This function detects the file format and loads it into the appropriate
 /// variant of [`crate::media::MediaContent`]:
 /// - Static images → `MediaContent::Static(BrailleGrid)`
-/// - Animated content → `MediaContent::Animated(Box<dyn MediaPlayer>)` (future)
+/// - Animated content → `MediaContent::Animated(Box<dyn MediaPlayer>)`
 ///
 /// Use this when you need programmatic access to the loaded content rather
 /// than immediate display.
 ///
+/// Formats are routed as for [`show_file`], as if to a terminal: SVG or
+/// video without its feature loads as a static card naming the missing
+/// feature, and images load as density characters when the terminal only
+/// shows ASCII.
+///
 /// # Arguments
 ///
 /// * `path` - Path to any supported media file
@@ -488,77 +1126,301 @@ pub fn show_file(path: impl AsRef<std::path::Path>) -> Result<()> {
 ///
 /// let content = quick::load_file("image.png")?;
 ///
-/// match content {
-///     MediaContent::Static(grid) => {
-///         println!("Loaded static image: {}x{}", grid.width(), grid.height());
-///         quick::show(&grid)?;
-///     }
-///     MediaContent::Animated(mut player) => {
-///         println!("Loaded animation with {:?} frames", player.frame_count());
-///         // Play frames...
-///     }
-/// }
+/// match content {
+///     MediaContent::Static(grid) => {
+///         println!("Loaded static image: {}x{}", grid.width(), grid.height());
+///         quick::show(&grid)?;
+///     }
+///     MediaContent::Animated(mut player) => {
+///         println!("Loaded animation with {:?} frames", player.frame_count());
+///         // Play frames...
+///     }
+/// }
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn load_file(path: impl AsRef<std::path::Path>) -> Result<crate::media::MediaContent> {
+    use crate::media::route::{route, Environment, Route};
+    use crate::media::{detect_format, MediaContent, MediaFormat};
+    use std::time::Duration;
+
+    let path = path.as_ref();
+    let format = detect_format(path)?;
+    // Nothing is displayed yet, so route as if to a terminal
+    let env = Environment {
+        interactive: true,
+        ..Environment::detect()
+    };
+    let decision = route(format, &env)?;
+    note_fallback(path, decision);
+
+    match decision.route {
+        Route::Image => Ok(MediaContent::Static(load_image(path)?)),
+        Route::Density => Ok(MediaContent::Static(load_density(path)?)),
+        Route::Svg => {
+            #[cfg(feature = "svg")]
+            {
+                Ok(MediaContent::Static(load_svg(path)?))
+            }
+            #[cfg(not(feature = "svg"))]
+            {
+                Err(crate::DotmaxError::FeatureDisabled { feature: "svg" })
+            }
+        }
+        Route::Animation => {
+            use crate::media::{AnimatedWebpPlayer, ApngPlayer, GifPlayer};
+            let player: Box<dyn crate::media::MediaPlayer> = match format {
+                MediaFormat::AnimatedPng => Box::new(ApngPlayer::new(path)?),
+                MediaFormat::AnimatedWebp => Box::new(AnimatedWebpPlayer::new(path)?),
+                _ => Box::new(GifPlayer::new(path)?),
+            };
+            Ok(MediaContent::Animated(player))
+        }
+        Route::Video => {
+            #[cfg(feature = "video")]
+            {
+                let player = crate::media::VideoPlayer::new(path)?;
+                Ok(MediaContent::Animated(Box::new(player)))
+            }
+            #[cfg(not(feature = "video"))]
+            {
+                Err(crate::DotmaxError::FeatureDisabled { feature: "video" })
+            }
+        }
+        Route::Poster => Ok(MediaContent::Static(crate::media::poster_frame(
+            path,
+            Duration::ZERO,
+        )?)),
+        Route::Placeholder => Ok(MediaContent::Static(placeholder_card(path, decision)?)),
+    }
+}
+
+/// Logs why `decision` took a fallback route, if it did. Printing a
+/// single frame to a pipe is expected, so that one is only debug output.
+#[cfg(feature = "image")]
+fn note_fallback(path: &std::path::Path, decision: crate::media::route::Decision) {
+    use crate::media::route::Fallback;
+
+    match decision.fallback {
+        Some(Fallback::NotInteractive) => {
+            tracing::debug!("{}: {}", path.display(), Fallback::NotInteractive);
+        }
+        Some(fallback) => tracing::warn!("{}: {fallback}", path.display()),
+        None => {}
+    }
+}
+
+/// Renders the image at `path` as ASCII density characters sized to the
+/// terminal, one character per cell.
+#[cfg(feature = "image")]
+fn load_density(path: &std::path::Path) -> Result<BrailleGrid> {
+    use crate::density::DensitySet;
+    use image::imageops::FilterType;
+
+    let (w, h) = terminal_size();
+    let to_u32 = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+    // Cells are about twice as tall as wide: fit to square pixels, then
+    // halve the rows
+    let fitted = crate::image::load_from_path(path)?.resize(
+        to_u32(w),
+        to_u32(h.saturating_mul(2)),
+        FilterType::Triangle,
+    );
+    let (width, height) = (fitted.width().max(1), ((fitted.height() + 1) / 2).max(1));
+    let luma = fitted
+        .resize_exact(width, height, FilterType::Triangle)
+        .to_luma8();
+
+    let mut grid = BrailleGrid::new(width as usize, height as usize)?;
+    let intensities: Vec<f32> = luma.pixels().map(|p| f32::from(p.0[0]) / 255.0).collect();
+    grid.render_density(&intensities, &DensitySet::ascii())?;
+    Ok(grid)
+}
+
+/// A terminal-sized grid with the file's name and the reason it can't be
+/// shown centered on it.
+#[cfg(feature = "image")]
+fn placeholder_card(
+    path: &std::path::Path,
+    decision: crate::media::route::Decision,
+) -> Result<BrailleGrid> {
+    let (w, h) = terminal_size();
+    let mut grid = BrailleGrid::new(w, h)?;
+    let name = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let reason = decision.fallback.map_or_else(
+        || "cannot be shown".to_string(),
+        |fallback| fallback.to_string(),
+    );
+
+    let lines = [name, reason];
+    let top = h.saturating_sub(lines.len()) / 2;
+    for (row, line) in lines.iter().enumerate().take(h) {
+        let chars: Vec<char> = line.chars().take(w).collect();
+        let left = (w - chars.len()) / 2;
+        for (x, c) in chars.into_iter().enumerate() {
+            grid.set_char(left + x, top + row, c)?;
+        }
+    }
+    Ok(grid)
+}
+
+/// Displays any supported media file, running `on_frame` on each frame first.
+///
+/// Works like [`show_file`], but every frame passes through `on_frame` before
+/// it is rendered, so you can draw overlays such as timestamps, progress bars,
+/// or watermarks. Static images are treated as a single frame with index 0.
+///
+/// # Errors
+///
+/// Returns the same errors as [`show_file`], plus any error returned by
+/// `on_frame`, which stops playback.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// // Show elapsed time in the top-left corner
+/// quick::show_file_with("clip.gif", |grid, info| {
+///     let label = format!("{:.1}s", info.timestamp.as_secs_f32());
+///     for (x, c) in label.chars().enumerate().take(grid.width()) {
+///         grid.set_char(x, 0, c)?;
+///     }
+///     Ok(())
+/// })?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "image")]
+pub fn show_file_with<F>(path: impl AsRef<std::path::Path>, mut on_frame: F) -> Result<()>
+where
+    F: FnMut(&mut BrailleGrid, &crate::media::FrameInfo) -> Result<()> + Send,
+{
+    use crate::media::{FrameInfo, MediaContent, MediaPlayer};
+    use std::time::Duration;
+
+    match load_file(path)? {
+        MediaContent::Static(mut grid) => {
+            let info = FrameInfo {
+                index: 0,
+                timestamp: Duration::ZERO,
+                delay: Duration::ZERO,
+                frame_count: Some(1),
+                duration: None,
+            };
+            on_frame(&mut grid, &info)?;
+            show(&grid)
+        }
+        MediaContent::Animated(player) => play_player(
+            &mut player.on_frame(on_frame),
+            &Keymap::default(),
+            &mut ViewerEntry::default(),
+        ),
+    }
+}
+
+/// Plays any supported media file once through, returning when it ends.
+///
+/// The format is detected with [`detect_format`](crate::media::detect_format)
+/// and the file opened with the matching
+/// [`MediaPlayer`](crate::media::MediaPlayer), as for [`load_file`]. Frames
+/// are drawn by an [`AnimationLoop`](crate::animation::AnimationLoop) at the
+/// terminal size, each held for its own delay, so `q` or Ctrl+C stops early
+/// and the terminal is restored however playback ends. Looping GIFs play as
+/// many times as the file asks, which for most is forever.
+///
+/// This is the call for command-line tools that just want to show a file
+/// and carry on: unlike [`show_file`], there are no playback controls or
+/// status bar, and nothing waits for a keypress after the last frame.
+/// Static images are still shown until a keypress, as with [`show`]. When
+/// stdout isn't a terminal, the first frame is printed as text.
+///
+/// Playback uses [adaptive quality](crate::animation::AnimationLoopBuilder::adaptive_quality):
+/// when frames can't keep up, as over a slow SSH link, players that render
+/// pixels stop dithering first, then colors and resolution are dropped.
+///
+/// # Errors
+///
+/// The same as [`load_file`], plus any frame decode error, which stops
+/// playback, and `DotmaxError::Terminal` for terminal I/O errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// quick::play("intro.gif")?;
+/// println!("Ready.");
 /// # Ok::<(), dotmax::DotmaxError>(())
 /// ```
 #[cfg(feature = "image")]
-pub fn load_file(path: impl AsRef<std::path::Path>) -> Result<crate::media::MediaContent> {
-    use crate::media::{detect_format, MediaContent, MediaFormat};
-    use crate::DotmaxError;
+pub fn play(path: impl AsRef<std::path::Path>) -> Result<()> {
+    use crate::media::MediaContent;
 
-    let path = path.as_ref();
-    let format = detect_format(path)?;
+    match load_file(path)? {
+        MediaContent::Static(grid) => show(&grid),
+        MediaContent::Animated(mut player) => play_in_loop(player.as_mut()),
+    }
+}
+
+/// Ticks per second of the loop behind [`play`]; frames are held to within
+/// a tick of their delay.
+#[cfg(feature = "image")]
+const PLAY_FPS: u32 = 60;
 
-    match format {
-        MediaFormat::StaticImage(_) => {
-            // Load via existing load_image() and wrap in MediaContent::Static
-            let grid = load_image(path)?;
-            Ok(MediaContent::Static(grid))
+/// Plays `player` to its end in an [`AnimationLoop`](crate::animation::AnimationLoop).
+#[cfg(feature = "image")]
+fn play_in_loop(player: &mut dyn crate::media::MediaPlayer) -> Result<()> {
+    use crate::animation::{frame_quality, AnimationLoop, Quality};
+    use crate::{BlitColor, BlitMode};
+    use std::time::Instant;
+
+    if !stdout_is_terminal() {
+        if let Some(frame_result) = player.next_frame() {
+            print_piped(&frame_result?.0)?;
         }
-        MediaFormat::Svg => {
-            // Load SVG (if feature enabled)
-            #[cfg(feature = "svg")]
-            {
-                let grid = load_svg(path)?;
-                Ok(MediaContent::Static(grid))
-            }
-            #[cfg(not(feature = "svg"))]
-            {
-                Err(DotmaxError::FormatError {
-                    format: "SVG (requires 'svg' feature)".to_string(),
-                })
+        return Ok(());
+    }
+
+    let (width, height) = terminal_size();
+    let mut frame: Option<BrailleGrid> = None;
+    let mut next_due = Instant::now();
+    // The player's own settings, and the level last applied to them
+    let base_options = player.current_render_options();
+    let mut applied = Quality::Full;
+    let builder = AnimationLoop::new(width, height)
+        .fps(PLAY_FPS)
+        .adaptive_quality(true);
+    let builder = if keep_final_frame() {
+        builder.keep_final_frame()
+    } else {
+        builder
+    };
+    builder
+        .on_frame(|_, buffer| {
+            let quality = frame_quality();
+            if let Some(options) = base_options.filter(|_| quality != applied) {
+                player.set_render_options(quality.render_options(options))?;
+                applied = quality;
             }
-        }
-        MediaFormat::AnimatedGif => {
-            // Load animated GIF player (Story 9.2)
-            use crate::media::GifPlayer;
-            let player = GifPlayer::new(path)?;
-            Ok(MediaContent::Animated(Box::new(player)))
-        }
-        MediaFormat::AnimatedPng => {
-            // Load animated PNG player (Story 9.3)
-            use crate::media::ApngPlayer;
-            let player = ApngPlayer::new(path)?;
-            Ok(MediaContent::Animated(Box::new(player)))
-        }
-        MediaFormat::Video(_codec) => {
-            // Load video player (Story 9.4)
-            #[cfg(feature = "video")]
-            {
-                use crate::media::VideoPlayer;
-                let player = VideoPlayer::new(path)?;
-                Ok(MediaContent::Animated(Box::new(player)))
+            let now = Instant::now();
+            if now >= next_due {
+                let Some(frame_result) = player.next_frame() else {
+                    return Ok(false);
+                };
+                let (grid, delay) = frame_result?;
+                // Slip rather than rush when decoding falls behind
+                next_due = (next_due + delay).max(now);
+                frame = Some(grid);
             }
-            #[cfg(not(feature = "video"))]
-            {
-                Err(DotmaxError::FormatError {
-                    format: "video (requires 'video' feature and FFmpeg libraries)".to_string(),
-                })
+            if let Some(grid) = &frame {
+                buffer.blit(grid, 0, 0, BlitMode::Overwrite, BlitColor::Source);
             }
-        }
-        MediaFormat::Unknown => Err(DotmaxError::FormatError {
-            format: "unknown format".to_string(),
-        }),
-    }
+            Ok(true)
+        })
+        .run()
 }
 
 // ============================================================================
@@ -573,56 +1435,12 @@ pub fn load_file(path: impl AsRef<std::path::Path>) -> Result<crate::media::Medi
 /// 3. Plays frames with correct timing until keypress or loop completion
 /// 4. Cleans up terminal state
 #[cfg(feature = "image")]
-fn play_animated_gif(path: impl AsRef<std::path::Path>) -> Result<()> {
-    use crate::media::{GifPlayer, MediaPlayer};
-    use crossterm::event::{self, Event, KeyCode};
-    use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
-    use crossterm::{cursor, execute};
-    use std::io::stdout;
-    use std::time::{Duration, Instant};
-
-    let mut player = GifPlayer::new(path)?;
-
-    // Enter raw mode and alternate screen
-    terminal::enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
-
-    let mut renderer = TerminalRenderer::new()?;
-
-    // Play frames
-    let result = (|| -> Result<()> {
-        while let Some(frame_result) = player.next_frame() {
-            let (grid, delay) = frame_result?;
-
-            // Render frame
-            renderer.render(&grid)?;
-
-            // Wait for frame duration, checking for keypress
-            let deadline = Instant::now() + delay;
-            while Instant::now() < deadline {
-                // Check for keypress with short timeout
-                if event::poll(Duration::from_millis(10))? {
-                    if let Event::Key(key_event) = event::read()? {
-                        // Stop on any key (except modifiers alone)
-                        if !matches!(key_event.code, KeyCode::Modifier(_)) {
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-        }
-
-        // Animation complete - wait for final keypress
-        wait_for_key()?;
-        Ok(())
-    })();
-
-    // Cleanup - always restore terminal state
-    execute!(stdout, cursor::Show, LeaveAlternateScreen)?;
-    terminal::disable_raw_mode()?;
-
-    result
+fn play_animated_gif(
+    path: impl AsRef<std::path::Path>,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    play_player(&mut crate::media::GifPlayer::new(path)?, keymap, session)
 }
 
 // ============================================================================
@@ -637,56 +1455,26 @@ fn play_animated_gif(path: impl AsRef<std::path::Path>) -> Result<()> {
 /// 3. Plays frames with correct timing until keypress or loop completion
 /// 4. Cleans up terminal state
 #[cfg(feature = "image")]
-fn play_animated_png(path: impl AsRef<std::path::Path>) -> Result<()> {
-    use crate::media::{ApngPlayer, MediaPlayer};
-    use crossterm::event::{self, Event, KeyCode};
-    use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
-    use crossterm::{cursor, execute};
-    use std::io::stdout;
-    use std::time::{Duration, Instant};
-
-    let mut player = ApngPlayer::new(path)?;
-
-    // Enter raw mode and alternate screen
-    terminal::enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
-
-    let mut renderer = TerminalRenderer::new()?;
-
-    // Play frames
-    let result = (|| -> Result<()> {
-        while let Some(frame_result) = player.next_frame() {
-            let (grid, delay) = frame_result?;
-
-            // Render frame
-            renderer.render(&grid)?;
-
-            // Wait for frame duration, checking for keypress
-            let deadline = Instant::now() + delay;
-            while Instant::now() < deadline {
-                // Check for keypress with short timeout
-                if event::poll(Duration::from_millis(10))? {
-                    if let Event::Key(key_event) = event::read()? {
-                        // Stop on any key (except modifiers alone)
-                        if !matches!(key_event.code, KeyCode::Modifier(_)) {
-                            return Ok(());
-                        }
-                    }
-                }
-            }
-        }
-
-        // Animation complete - wait for final keypress
-        wait_for_key()?;
-        Ok(())
-    })();
-
-    // Cleanup - always restore terminal state
-    execute!(stdout, cursor::Show, LeaveAlternateScreen)?;
-    terminal::disable_raw_mode()?;
+fn play_animated_png(
+    path: impl AsRef<std::path::Path>,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    play_player(&mut crate::media::ApngPlayer::new(path)?, keymap, session)
+}
 
-    result
+/// Plays an animated WebP in the terminal, like [`play_animated_gif`].
+#[cfg(feature = "image")]
+fn play_animated_webp(
+    path: impl AsRef<std::path::Path>,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    play_player(
+        &mut crate::media::AnimatedWebpPlayer::new(path)?,
+        keymap,
+        session,
+    )
 }
 
 // ============================================================================
@@ -701,15 +1489,123 @@ fn play_animated_png(path: impl AsRef<std::path::Path>) -> Result<()> {
 /// 3. Plays frames with correct timing until keypress or video completion
 /// 4. Cleans up terminal state
 #[cfg(feature = "video")]
-fn play_video(path: impl AsRef<std::path::Path>) -> Result<()> {
-    use crate::media::{MediaPlayer, VideoPlayer};
-    use crossterm::event::{self, Event, KeyCode};
+fn play_video(
+    path: impl AsRef<std::path::Path>,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    // Drop frames rather than drift when rendering can't keep up
+    let mut player = crate::media::VideoPlayer::new(path)?.realtime(true);
+    play_player(&mut player, keymap, session)
+}
+
+// ============================================================================
+// Shared Playback Loop
+// ============================================================================
+
+/// How far the seek keys jump.
+#[cfg(feature = "image")]
+const SEEK_STEP: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Plays any media player in the terminal.
+///
+/// This function:
+/// 1. Initializes the terminal (raw mode, alternate screen)
+/// 2. Plays frames with correct timing until quit or playback completion,
+///    with a [`PlaybackOsd`](crate::widgets::PlaybackOsd) along the bottom row
+///    that shows at start and auto-hides, and a
+///    [`PlaybackDiagnostics`](crate::widgets::PlaybackDiagnostics) overlay
+///    toggled by key, handling key presses through `keymap`
+/// 3. Waits for a final keypress if playback ran to the end
+/// 4. Cleans up terminal state, then prints the last frame if
+///    [`set_keep_final_frame`] is on
+#[cfg(feature = "image")]
+fn play_player(
+    player: &mut dyn crate::media::MediaPlayer,
+    keymap: &Keymap,
+    session: &mut ViewerEntry,
+) -> Result<()> {
+    use crate::color::schemes::{get_scheme, list_schemes};
+    use crate::image::RenderOptions;
+    use crate::keymap::KeyAction;
+    use crate::media::{DecodeTiming, FrameInfo};
+    use crate::widgets::{PlaybackDiagnostics, PlaybackOsd};
+    use crate::ColorScheme;
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
     use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
     use crossterm::{cursor, execute};
     use std::io::stdout;
     use std::time::{Duration, Instant};
 
-    let mut player = VideoPlayer::new(path)?;
+    // Draws the frame, recolored and with the overlays on a copy when needed
+    fn draw(
+        renderer: &mut TerminalRenderer,
+        grid: &BrailleGrid,
+        osd: &PlaybackOsd,
+        diagnostics: &PlaybackDiagnostics,
+        scheme: Option<&ColorScheme>,
+    ) -> Result<()> {
+        if osd.is_visible() || diagnostics.is_visible() || scheme.is_some() {
+            let mut shown = grid.clone();
+            if let Some(scheme) = scheme {
+                recolor_by_density(&mut shown, scheme);
+            }
+            if osd.is_visible() {
+                osd.render(&mut shown);
+            }
+            diagnostics.render(&mut shown);
+            renderer.render(&shown)?;
+        } else {
+            renderer.render(grid)?;
+        }
+        Ok(())
+    }
+
+    // Pick up where the session left off
+    let mut index = 0;
+    let mut timestamp = Duration::ZERO;
+    if !session.position.is_zero() {
+        match player.seek(session.position) {
+            Ok(landed) => {
+                index = estimate_frame_index(&*player, landed, 0);
+                timestamp = landed;
+            }
+            Err(e) => tracing::warn!("Could not resume at {:?}: {e}", session.position),
+        }
+    }
+    let initial_options = player.current_render_options();
+    if let Some(options) = initial_options {
+        player.set_render_options(RenderOptions {
+            dithering: session.dithering.unwrap_or(options.dithering),
+            color_mode: session.color_mode.unwrap_or(options.color_mode),
+            ..options
+        })?;
+    }
+    let scheme_names = list_schemes();
+    // Index into `scheme_names` of the recoloring scheme, if any
+    let mut scheme: Option<(usize, ColorScheme)> = session.scheme.as_deref().and_then(|name| {
+        let position = scheme_names.iter().position(|n| n == name)?;
+        Some((position, get_scheme(name)?))
+    });
+    // Where the frame on screen started, and whether playback ran out
+    let mut shown_at = timestamp;
+    let mut finished = false;
+
+    // Nobody to watch it play; print the first frame as a still
+    if !stdout_is_terminal() {
+        if let Some(frame_result) = player.next_frame() {
+            let (mut grid, _) = frame_result?;
+            if let Some((_, scheme)) = &scheme {
+                recolor_by_density(&mut grid, scheme);
+            }
+            print_piped(&grid)?;
+        }
+        return Ok(());
+    }
+
+    // The frame on screen, kept for printing after cleanup
+    let keep = keep_final_frame();
+    let mut last_frame: Option<BrailleGrid> = None;
 
     // Enter raw mode and alternate screen
     terminal::enable_raw_mode()?;
@@ -717,42 +1613,265 @@ fn play_video(path: impl AsRef<std::path::Path>) -> Result<()> {
     execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
 
     let mut renderer = TerminalRenderer::new()?;
+    let mut osd = PlaybackOsd::new();
+    osd.wake();
+    let mut diagnostics = PlaybackDiagnostics::new();
 
     // Play frames
     let result = (|| -> Result<()> {
-        while let Some(frame_result) = player.next_frame() {
+        loop {
+            let requested = Instant::now();
+            let Some(frame_result) = player.next_frame() else {
+                break;
+            };
             let (grid, delay) = frame_result?;
+            // Players that don't split their time count it all as decoding
+            let decode_timing = player.decode_timing().unwrap_or_else(|| DecodeTiming {
+                decode: requested.elapsed(),
+                ..DecodeTiming::default()
+            });
+
+            osd.update(&FrameInfo {
+                index,
+                timestamp,
+                delay,
+                frame_count: player.frame_count(),
+                duration: player.duration(),
+            });
+            shown_at = timestamp;
+            index += 1;
+            timestamp += delay;
 
             // Render frame
-            renderer.render(&grid)?;
+            let render_started = Instant::now();
+            draw(
+                &mut renderer,
+                &grid,
+                &osd,
+                &diagnostics,
+                scheme.as_ref().map(|(_, s)| s),
+            )?;
+            let shown = Instant::now();
+            diagnostics.record(
+                decode_timing.with_render(shown - render_started),
+                shown_at,
+                shown,
+            );
+            let mut osd_shown = osd.is_visible();
+            if keep {
+                last_frame = Some(grid.clone());
+            }
 
-            // Wait for frame duration, checking for keypress
-            let deadline = Instant::now() + delay;
-            while Instant::now() < deadline {
+            // Wait for frame duration, checking for keypress. Time spent
+            // paused doesn't count against the frame's delay.
+            let mut remaining = delay;
+            let mut last_tick = Instant::now();
+            while osd.is_paused() || !remaining.is_zero() {
                 // Check for keypress with short timeout
                 if event::poll(Duration::from_millis(10))? {
                     if let Event::Key(key_event) = event::read()? {
-                        // Stop on any key (except modifiers alone)
-                        if !matches!(key_event.code, KeyCode::Modifier(_)) {
-                            return Ok(());
+                        if key_event.kind == KeyEventKind::Release {
+                            continue;
+                        }
+                        match keymap.action_for(&key_event) {
+                            // Modifiers alone don't count as a keypress
+                            None if matches!(key_event.code, KeyCode::Modifier(_)) => {}
+                            // Stop on quit or any unbound key
+                            Some(KeyAction::Quit) | None => return Ok(()),
+                            Some(KeyAction::Pause) => {
+                                osd.set_paused(!osd.is_paused());
+                                if !osd.is_paused() {
+                                    // Time spent paused isn't drift
+                                    diagnostics.resync(
+                                        timestamp.saturating_sub(remaining),
+                                        Instant::now(),
+                                    );
+                                }
+                            }
+                            Some(action @ (KeyAction::SeekForward | KeyAction::SeekBackward)) => {
+                                // `timestamp` is where the next frame starts
+                                let target = if action == KeyAction::SeekForward {
+                                    let end = player.duration().unwrap_or(Duration::MAX);
+                                    timestamp.saturating_add(SEEK_STEP).min(end)
+                                } else {
+                                    timestamp.saturating_sub(delay).saturating_sub(SEEK_STEP)
+                                };
+                                match player.seek(target) {
+                                    Ok(landed) => {
+                                        index = estimate_frame_index(&*player, landed, index);
+                                        timestamp = landed;
+                                        diagnostics.resync(landed, Instant::now());
+                                        osd.wake();
+                                        // Show the frame at the new position now
+                                        break;
+                                    }
+                                    Err(e) => tracing::warn!("Seek failed: {e}"),
+                                }
+                            }
+                            Some(KeyAction::CycleDithering) => {
+                                if let Some(options) = player.current_render_options() {
+                                    let next = next_dithering(options.dithering);
+                                    player.set_render_options(options.dithering(next))?;
+                                }
+                            }
+                            Some(KeyAction::ToggleColorMode) => {
+                                if let Some(options) = player.current_render_options() {
+                                    let next = next_color_mode(options.color_mode);
+                                    player.set_render_options(options.color_mode(next))?;
+                                }
+                            }
+                            Some(KeyAction::CycleScheme) => {
+                                let next = scheme.as_ref().map_or(0, |&(i, _)| i + 1);
+                                scheme = scheme_names
+                                    .get(next)
+                                    .and_then(|name| get_scheme(name))
+                                    .map(|s| (next, s));
+                            }
+                            Some(KeyAction::Screenshot) => match save_screenshot(&grid) {
+                                Ok(path) => {
+                                    tracing::info!("Saved screenshot to {}", path.display());
+                                }
+                                Err(e) => tracing::warn!("Screenshot failed: {e}"),
+                            },
+                            Some(KeyAction::ToggleDiagnostics) => diagnostics.toggle(),
                         }
+                        osd.wake();
+                        draw(
+                            &mut renderer,
+                            &grid,
+                            &osd,
+                            &diagnostics,
+                            scheme.as_ref().map(|(_, s)| s),
+                        )?;
+                        osd_shown = true;
                     }
                 }
+
+                let now = Instant::now();
+                if !osd.is_paused() {
+                    remaining = remaining.saturating_sub(now - last_tick);
+                }
+                last_tick = now;
+
+                // Redraw once when the OSD auto-hides mid-frame
+                if osd_shown && !osd.is_visible() {
+                    draw(
+                        &mut renderer,
+                        &grid,
+                        &osd,
+                        &diagnostics,
+                        scheme.as_ref().map(|(_, s)| s),
+                    )?;
+                    osd_shown = false;
+                }
             }
         }
 
-        // Video complete - wait for final keypress
+        // Playback complete - wait for final keypress
+        finished = true;
         wait_for_key()?;
         Ok(())
     })();
 
     // Cleanup - always restore terminal state
+    drop(renderer);
     execute!(stdout, cursor::Show, LeaveAlternateScreen)?;
     terminal::disable_raw_mode()?;
+    if let Some(mut grid) = last_frame {
+        if let Some((_, scheme)) = &scheme {
+            recolor_by_density(&mut grid, scheme);
+        }
+        crate::render::render_once_to_stdout(&grid)?;
+    }
+
+    // Remember only what differs from how the player started
+    session.position = if finished { Duration::ZERO } else { shown_at };
+    if let (Some(initial), Some(current)) = (initial_options, player.current_render_options()) {
+        session.dithering = (current.dithering != initial.dithering).then_some(current.dithering);
+        session.color_mode =
+            (current.color_mode != initial.color_mode).then_some(current.color_mode);
+    }
+    session.scheme = scheme.map(|(i, _)| scheme_names[i].clone());
 
     result
 }
 
+/// Colors each cell of `grid` by how many of its dots are set.
+#[cfg(feature = "image")]
+fn recolor_by_density(grid: &mut BrailleGrid, scheme: &crate::ColorScheme) {
+    let width = grid.width();
+    let patterns = grid.get_raw_patterns().to_vec();
+    for (i, pattern) in patterns.into_iter().enumerate() {
+        let density = pattern.count_ones() as f32 / 8.0;
+        // In bounds by construction
+        let _ = grid.set_cell_color(i % width, i / width, scheme.sample(density));
+    }
+}
+
+/// Where the frame counter should be after landing on `position`, for OSDs
+/// that show progress by frame count. Keeps `current` when the player can't
+/// say.
+#[cfg(feature = "image")]
+fn estimate_frame_index(
+    player: &dyn crate::media::MediaPlayer,
+    position: std::time::Duration,
+    current: usize,
+) -> usize {
+    match (player.frame_count(), player.duration()) {
+        (Some(count), Some(total)) if !total.is_zero() => {
+            (count as f64 * position.as_secs_f64() / total.as_secs_f64()) as usize
+        }
+        _ if position.is_zero() => 0,
+        _ => current,
+    }
+}
+
+/// The dithering method after `method` in the playback cycle.
+#[cfg(feature = "image")]
+const fn next_dithering(method: crate::image::DitheringMethod) -> crate::image::DitheringMethod {
+    use crate::image::DitheringMethod;
+    match method {
+        DitheringMethod::FloydSteinberg => DitheringMethod::Bayer,
+        DitheringMethod::Bayer => DitheringMethod::Atkinson,
+        DitheringMethod::Atkinson => DitheringMethod::None,
+        DitheringMethod::None => DitheringMethod::FloydSteinberg,
+    }
+}
+
+/// The color mode after `mode` in the playback cycle.
+#[cfg(feature = "image")]
+const fn next_color_mode(mode: crate::image::ColorMode) -> crate::image::ColorMode {
+    use crate::image::ColorMode;
+    match mode {
+        ColorMode::Monochrome => ColorMode::Grayscale,
+        ColorMode::Grayscale => ColorMode::TrueColor,
+        ColorMode::TrueColor => ColorMode::Monochrome,
+    }
+}
+
+/// Writes `grid` as braille text to `dotmax-screenshot-<unix time>.txt` in
+/// the working directory.
+#[cfg(feature = "image")]
+fn save_screenshot(grid: &BrailleGrid) -> std::io::Result<std::path::PathBuf> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let path = std::path::PathBuf::from(format!("dotmax-screenshot-{secs}.txt"));
+    let text: String = grid
+        .to_unicode_grid()
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .chain(std::iter::once('\n'))
+                .collect::<String>()
+        })
+        .collect();
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
 // ============================================================================
 // Webcam Helper Functions (Story 9.6)
 // ============================================================================
@@ -827,6 +1946,59 @@ pub fn show_webcam_device(device: impl Into<crate::media::WebcamDeviceId>) -> Re
     play_webcam_internal(WebcamPlayer::from_device(device)?)
 }
 
+/// Frames read and discarded before [`webcam_snapshot`] keeps one, about a
+/// third of a second at 30 fps. Cameras start dark or with the wrong white
+/// balance and settle over their first few frames.
+#[cfg(feature = "video")]
+const SNAPSHOT_WARMUP_FRAMES: usize = 10;
+
+/// Takes a single still from a webcam, rendered at terminal size.
+///
+/// Opens the camera, lets it settle for a few warm-up frames so its
+/// exposure has adjusted, renders the next frame, and releases the device
+/// before returning. Nothing is drawn to the terminal.
+///
+/// # Arguments
+///
+/// * `device` - Device identifier (index, path, or name), as for
+///   [`show_webcam_device`]
+///
+/// # Errors
+///
+/// - `DotmaxError::CameraNotFound` - Specified device not found
+/// - `DotmaxError::CameraInUse` - Camera is in use
+/// - `DotmaxError::CameraPermissionDenied` - No permission
+/// - `DotmaxError::WebcamError` - Capture failed, or the stream ended before
+///   a frame arrived
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::quick;
+///
+/// let still = quick::webcam_snapshot(0)?;
+/// quick::show(&still)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[cfg(feature = "video")]
+pub fn webcam_snapshot(device: impl Into<crate::media::WebcamDeviceId>) -> Result<BrailleGrid> {
+    use crate::media::{MediaPlayer, WebcamPlayer};
+
+    let mut player = WebcamPlayer::from_device(device)?;
+    let mut last = None;
+    for _ in 0..=SNAPSHOT_WARMUP_FRAMES {
+        match player.next_frame() {
+            Some(frame) => last = Some(frame?.0),
+            None => break,
+        }
+    }
+    // The device is released when `player` drops here
+    last.ok_or_else(|| crate::DotmaxError::WebcamError {
+        device: player.device_id().to_string(),
+        message: "Stream ended before a frame was captured".to_string(),
+    })
+}
+
 /// Internal function for webcam playback loop.
 #[cfg(feature = "video")]
 fn play_webcam_internal(mut player: crate::media::WebcamPlayer) -> Result<()> {
@@ -837,6 +2009,14 @@ fn play_webcam_internal(mut player: crate::media::WebcamPlayer) -> Result<()> {
     use std::io::stdout;
     use std::time::{Duration, Instant};
 
+    // Piped: print a single snapshot
+    if !stdout_is_terminal() {
+        if let Some(frame_result) = player.next_frame() {
+            print_piped(&frame_result?.0)?;
+        }
+        return Ok(());
+    }
+
     // Enter raw mode and alternate screen
     terminal::enable_raw_mode()?;
     let mut stdout = stdout();
@@ -942,6 +2122,25 @@ mod tests {
         let (_w, _h) = terminal_size();
     }
 
+    #[test]
+    fn test_pipe_size_sources() {
+        assert_eq!(pipe_size_from(None, None, None), (80, 24));
+        assert_eq!(pipe_size_from(None, Some("132"), Some("50")), (132, 50));
+        assert_eq!(pipe_size_from(Some(100), Some("132"), None), (100, 24));
+        // Garbage and zero fall back
+        assert_eq!(pipe_size_from(None, Some("wide"), Some("0")), (80, 24));
+    }
+
+    #[test]
+    fn test_pipe_colors_conventions() {
+        assert!(!pipe_colors_from(None, None));
+        assert!(pipe_colors_from(None, Some("1")));
+        assert!(!pipe_colors_from(None, Some("0")));
+        assert!(!pipe_colors_from(Some("1"), Some("1")));
+        // An empty NO_COLOR doesn't count as set
+        assert!(pipe_colors_from(Some(""), Some("1")));
+    }
+
     // ========================================================================
     // Grid Creation Tests
     // ========================================================================
@@ -988,6 +2187,39 @@ mod tests {
         }
     }
 
+    // ========================================================================
+    // Plotting Tests
+    // ========================================================================
+
+    #[test]
+    fn test_plot_grid_fills_requested_size() {
+        let grid = plot_grid(&[3.0, 1.0, 4.0, 1.0, 5.0, 9.0], 30, 10).unwrap();
+        assert_eq!(grid.dimensions(), (30, 10));
+        assert!(grid.get_raw_patterns().iter().any(|&pattern| pattern != 0));
+        // Nothing to plot still draws the axes
+        assert!(plot_grid(&[], 30, 10).is_ok());
+        assert!(plot_grid(&[1.0], 0, 10).is_err());
+    }
+
+    #[test]
+    fn test_stream_title() {
+        let mut plot = StreamPlot::new(10);
+        assert_eq!(stream_title(&plot, "q quits"), "  [0 lines, q quits]");
+        plot.push(&[1.5, 20.0]);
+        assert_eq!(
+            stream_title(&plot, "end of input"),
+            "1.5  20  [1 lines, end of input]"
+        );
+    }
+
+    #[test]
+    fn test_scatter_grid_draws_points() {
+        let points = [(0.0, 0.0), (1.0, 2.0), (2.0, 1.0), (f64::NAN, 3.0)];
+        let grid = scatter_grid(&points, 30, 10).unwrap();
+        assert_eq!(grid.dimensions(), (30, 10));
+        assert!(grid.get_raw_patterns().iter().any(|&pattern| pattern != 0));
+    }
+
     // ========================================================================
     // Image Function Tests (feature-gated)
     // ========================================================================
@@ -1038,6 +2270,56 @@ mod tests {
     // via integration tests and examples rather than unit tests.
     // See tests/quick_test.rs and examples/quick_demo.rs
 
+    // ========================================================================
+    // QR Code Tests
+    // ========================================================================
+
+    #[cfg(feature = "qr")]
+    mod qr_tests {
+        use super::*;
+        use crate::DotmaxError;
+
+        #[test]
+        fn test_render_qr_sized_has_quiet_zone_and_finders() {
+            // "hello" fits a 21-module code; with the quiet zone, 29 dots
+            let grid = render_qr_sized("hello", 1).unwrap();
+            assert_eq!(grid.dimensions(), (15, 8));
+
+            // Quiet zone is light, so its dots are set
+            assert!((0..29).all(|i| grid.is_dot_set(i, 3) && grid.is_dot_set(3, i)));
+            // Top-left finder: dark ring, light ring, dark 3×3 center
+            assert!(!grid.is_dot_set(4, 4));
+            assert!(grid.is_dot_set(5, 5));
+            assert!(!grid.is_dot_set(7, 7));
+            // Top-right and bottom-left finders
+            assert!(!grid.is_dot_set(24, 4) && !grid.is_dot_set(4, 24));
+
+            let double = render_qr_sized("hello", 2).unwrap();
+            assert_eq!(double.dimensions(), (29, 15));
+            assert!(double.is_dot_set(7, 7) && !double.is_dot_set(8, 8));
+            assert!(!double.is_dot_set(9, 9));
+        }
+
+        #[test]
+        fn test_render_qr_fits_terminal() {
+            let (width, height) = terminal_size();
+            let grid = render_qr("https://example.com/pair?code=4821").unwrap();
+            assert!(grid.width() <= width && grid.height() <= height);
+        }
+
+        #[test]
+        fn test_render_qr_errors() {
+            assert!(matches!(
+                render_qr_sized("x", 0),
+                Err(DotmaxError::InvalidParameter { .. })
+            ));
+            assert!(matches!(
+                render_qr(&"x".repeat(5000)),
+                Err(DotmaxError::QrError(_))
+            ));
+        }
+    }
+
     // ========================================================================
     // Universal Media Function Tests (Story 9.1)
     // ========================================================================
@@ -1103,5 +2385,23 @@ mod tests {
             let result = show_file("nonexistent_file_12345.png");
             assert!(result.is_err(), "show_file should fail for nonexistent file");
         }
+
+        #[test]
+        fn test_placeholder_card_names_file_and_feature() {
+            use crate::build_info::Feature;
+            use crate::media::route::{Decision, Fallback, Route};
+
+            let decision = Decision {
+                route: Route::Placeholder,
+                fallback: Some(Fallback::MissingFeature(Feature::Video)),
+            };
+            let grid = placeholder_card(Path::new("clips/talk.mp4"), decision).unwrap();
+            let text: String = (0..grid.height())
+                .flat_map(|y| (0..grid.width()).map(move |x| (x, y)))
+                .map(|(x, y)| grid.get_char(x, y))
+                .collect();
+            assert!(text.contains("talk.mp4"));
+            assert!(text.contains("--features video"));
+        }
     }
 }
@@ -0,0 +1,324 @@
+//! Named registries for extensions.
+//!
+//! Applications and third-party crates register frame sources, color schemes,
+//! density sets, and transitions under a name at startup; players, CLIs, and
+//! scene loaders then look them up by that name without depending on the crate
+//! that provided them.
+//!
+//! Registration is explicit: call the `register_*` functions once, typically
+//! from a plugin crate's `install()` function invoked in `main`. Lookups see the
+//! built-in entries too, so `color_scheme("rainbow")` works with nothing
+//! registered. A registered entry with the same name as a built-in replaces it.
+//!
+//! Names are case-insensitive. The `list_*` functions return the built-in
+//! names first, in their usual order, then the registered ones sorted.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::registry;
+//! use dotmax::{Color, ColorScheme};
+//!
+//! // A plugin crate would do this in its install() function
+//! let sunset = ColorScheme::new("sunset", vec![Color::rgb(255, 200, 0), Color::rgb(120, 0, 60)])?;
+//! registry::register_color_scheme(sunset);
+//!
+//! // Anywhere else in the program
+//! assert!(registry::color_scheme("Sunset").is_some());
+//! assert!(registry::list_color_schemes().contains(&"sunset".to_string()));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::animation::{cut, dissolve, wipe, FrameSource, Transition};
+use crate::color::schemes::{get_scheme, list_schemes, ColorScheme};
+use crate::density::{get_set, list_sets, DensitySet};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+use tracing::debug;
+
+/// Creates a fresh [`FrameSource`] each time a player asks for one by name.
+pub type FrameSourceFactory =
+    Arc<dyn Fn() -> Result<Box<dyn FrameSource>, DotmaxError> + Send + Sync>;
+
+#[derive(Default)]
+struct Registry {
+    color_schemes: BTreeMap<String, ColorScheme>,
+    density_sets: BTreeMap<String, DensitySet>,
+    frame_sources: BTreeMap<String, FrameSourceFactory>,
+    transitions: BTreeMap<String, Transition>,
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(Registry::default()))
+}
+
+fn key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Run `f` with read access. A panic in another thread while registering
+/// leaves the maps in a usable state, so poisoning is ignored.
+fn read<T>(f: impl FnOnce(&Registry) -> T) -> T {
+    f(&registry().read().unwrap_or_else(PoisonError::into_inner))
+}
+
+fn write(f: impl FnOnce(&mut Registry)) {
+    f(&mut registry().write().unwrap_or_else(PoisonError::into_inner));
+}
+
+/// Merge built-in names with registered ones, keeping order and dropping duplicates.
+fn merged(builtin: Vec<String>, registered: impl Iterator<Item = String>) -> Vec<String> {
+    let mut names = builtin;
+    for name in registered {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+// ============================================================================
+// Color schemes
+// ============================================================================
+
+/// Register a color scheme under its own [`ColorScheme::name`].
+pub fn register_color_scheme(scheme: ColorScheme) {
+    let name = key(scheme.name());
+    debug!(name = %name, "Registering color scheme");
+    write(|r| {
+        r.color_schemes.insert(name, scheme);
+    });
+}
+
+/// Look up a color scheme, registered entries first, then the built-ins
+/// (including their aliases, see [`get_scheme`]).
+#[must_use]
+pub fn color_scheme(name: &str) -> Option<ColorScheme> {
+    read(|r| r.color_schemes.get(&key(name)).cloned()).or_else(|| get_scheme(name))
+}
+
+/// Built-in scheme names followed by registered ones, sorted.
+#[must_use]
+pub fn list_color_schemes() -> Vec<String> {
+    merged(
+        list_schemes(),
+        read(|r| r.color_schemes.keys().cloned().collect::<Vec<_>>()).into_iter(),
+    )
+}
+
+// ============================================================================
+// Density sets
+// ============================================================================
+
+/// Register a density set under its `name` field.
+pub fn register_density_set(set: DensitySet) {
+    let name = key(&set.name);
+    debug!(name = %name, "Registering density set");
+    write(|r| {
+        r.density_sets.insert(name, set);
+    });
+}
+
+/// Look up a density set, registered entries first, then the built-ins
+/// (see [`get_set`]).
+#[must_use]
+pub fn density_set(name: &str) -> Option<DensitySet> {
+    read(|r| r.density_sets.get(&key(name)).cloned()).or_else(|| get_set(name))
+}
+
+/// Built-in density set names followed by registered ones, sorted.
+#[must_use]
+pub fn list_density_sets() -> Vec<String> {
+    merged(
+        list_sets(),
+        read(|r| r.density_sets.keys().cloned().collect::<Vec<_>>()).into_iter(),
+    )
+}
+
+// ============================================================================
+// Frame sources
+// ============================================================================
+
+/// Register a factory that creates a [`FrameSource`] on demand.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::animation::FrameSource;
+/// use dotmax::{registry, BrailleGrid, DotmaxError};
+/// use std::time::Duration;
+///
+/// struct Blank;
+///
+/// impl FrameSource for Blank {
+///     fn next_frame(&mut self, w: usize, h: usize) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+///         Some(BrailleGrid::new(w, h).map(|g| (g, Duration::from_millis(100))))
+///     }
+/// }
+///
+/// registry::register_frame_source("blank", || Ok(Box::new(Blank)));
+///
+/// let mut source = registry::frame_source("blank").unwrap()()?;
+/// let (frame, _) = source.next_frame(10, 2).unwrap()?;
+/// assert_eq!(frame.dimensions(), (10, 2));
+/// # Ok::<(), DotmaxError>(())
+/// ```
+pub fn register_frame_source<F>(name: &str, factory: F)
+where
+    F: Fn() -> Result<Box<dyn FrameSource>, DotmaxError> + Send + Sync + 'static,
+{
+    let name = key(name);
+    debug!(name = %name, "Registering frame source");
+    write(|r| {
+        r.frame_sources.insert(name, Arc::new(factory));
+    });
+}
+
+/// Look up a frame source factory by name.
+#[must_use]
+pub fn frame_source(name: &str) -> Option<FrameSourceFactory> {
+    read(|r| r.frame_sources.get(&key(name)).cloned())
+}
+
+/// Names of all registered frame sources, sorted. There are no built-in
+/// frame sources.
+#[must_use]
+pub fn list_frame_sources() -> Vec<String> {
+    read(|r| r.frame_sources.keys().cloned().collect())
+}
+
+// ============================================================================
+// Transitions
+// ============================================================================
+
+const BUILTIN_TRANSITIONS: [&str; 3] = ["cut", "wipe", "dissolve"];
+
+/// Register a transition function.
+pub fn register_transition<F>(name: &str, transition: F)
+where
+    F: Fn(&BrailleGrid, &BrailleGrid, f32) -> Result<BrailleGrid, DotmaxError>
+        + Send
+        + Sync
+        + 'static,
+{
+    let name = key(name);
+    debug!(name = %name, "Registering transition");
+    write(|r| {
+        r.transitions.insert(name, Arc::new(transition));
+    });
+}
+
+/// Look up a transition, registered entries first, then the built-ins
+/// (`cut`, `wipe`, `dissolve`).
+#[must_use]
+pub fn transition(name: &str) -> Option<Transition> {
+    let name = key(name);
+    read(|r| r.transitions.get(&name).cloned()).or_else(|| -> Option<Transition> {
+        match name.as_str() {
+            "cut" => Some(Arc::new(cut)),
+            "wipe" => Some(Arc::new(wipe)),
+            "dissolve" => Some(Arc::new(dissolve)),
+            _ => None,
+        }
+    })
+}
+
+/// Built-in transition names followed by registered ones, sorted.
+#[must_use]
+pub fn list_transitions() -> Vec<String> {
+    let builtin = BUILTIN_TRANSITIONS
+        .iter()
+        .map(|s| (*s).to_string())
+        .collect();
+    merged(
+        builtin,
+        read(|r| r.transitions.keys().cloned().collect::<Vec<_>>()).into_iter(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+    use std::time::Duration;
+
+    // The registry is process-global and tests run in parallel, so every test
+    // uses names no other test touches.
+
+    #[test]
+    fn test_builtins_available_without_registration() {
+        assert!(color_scheme("heatmap").is_some());
+        assert!(density_set("BRAILLE").is_some());
+        assert!(transition("Wipe").is_some());
+        assert!(list_color_schemes().starts_with(&list_schemes()));
+        assert_eq!(
+            list_density_sets()[..4],
+            ["ascii", "simple", "blocks", "braille"]
+        );
+    }
+
+    #[test]
+    fn test_registered_scheme_overrides_builtin() {
+        let custom = ColorScheme::new("Grayscale", vec![Color::rgb(9, 9, 9)]).unwrap();
+        register_color_scheme(custom);
+        assert_eq!(
+            color_scheme("grayscale").unwrap().colors(),
+            &[Color::rgb(9, 9, 9)]
+        );
+        let count = list_color_schemes()
+            .iter()
+            .filter(|n| *n == "grayscale")
+            .count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_register_density_set() {
+        let set = DensitySet::new("Test Dots".to_string(), vec![' ', '.', ':']).unwrap();
+        register_density_set(set);
+        assert_eq!(density_set("test dots").unwrap().characters.len(), 3);
+        assert!(list_density_sets().contains(&"test dots".to_string()));
+    }
+
+    #[derive(Debug)]
+    struct Counter(u32);
+
+    impl FrameSource for Counter {
+        fn next_frame(
+            &mut self,
+            width: usize,
+            height: usize,
+        ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+            self.0 += 1;
+            (self.0 <= 2).then(|| BrailleGrid::new(width, height).map(|g| (g, Duration::ZERO)))
+        }
+    }
+
+    #[test]
+    fn test_frame_source_factory_creates_fresh_sources() {
+        register_frame_source("test-counter", || Ok(Box::new(Counter(0))));
+        let factory = frame_source("TEST-COUNTER").unwrap();
+        let mut a = factory().unwrap();
+        assert!(a.next_frame(2, 2).is_some());
+        assert!(a.next_frame(2, 2).is_some());
+        assert!(a.next_frame(2, 2).is_none());
+        let mut b = factory().unwrap();
+        assert!(b.next_frame(2, 2).is_some());
+        assert!(list_frame_sources().contains(&"test-counter".to_string()));
+        assert!(frame_source("test-missing").is_none());
+    }
+
+    #[test]
+    fn test_register_transition() {
+        register_transition("test-always-to", |_from, to, _p| Ok(to.clone()));
+        let from = BrailleGrid::new(2, 2).unwrap();
+        let mut to = BrailleGrid::new(2, 2).unwrap();
+        to.set_dot(0, 0).unwrap();
+        let out = transition("test-always-to").unwrap()(&from, &to, 0.0).unwrap();
+        assert!(!out.is_empty(0, 0));
+        assert!(list_transitions().contains(&"test-always-to".to_string()));
+    }
+}
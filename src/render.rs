@@ -21,23 +21,37 @@
 //! renderer.cleanup().expect("Failed to cleanup");
 //! ```
 
+pub mod glyphs;
+pub mod recording;
+pub mod sixel;
+pub mod stats;
+pub mod writer;
+
+use crate::density::SIMPLE_DENSITY;
 use crate::error::DotmaxError;
-use crate::grid::BrailleGrid;
+use crate::grid::{BrailleGrid, Color};
 use crossterm::{
-    cursor::MoveTo,
-    execute,
+    cursor::{MoveTo, Show},
+    execute, queue,
+    style::{Color as CrosstermColor, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
     terminal::{
         disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
         LeaveAlternateScreen,
     },
 };
+use glyphs::{glyph_policy, GlyphRepertoire};
 use ratatui::{
     backend::CrosstermBackend,
+    layout::Rect,
     text::{Line, Span},
     widgets::Paragraph,
-    Terminal,
+    Terminal, TerminalOptions, Viewport,
 };
-use std::io::{self, Stdout};
+use stats::{CountingWriter, RenderStats};
+use std::io::{self, Stdout, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
 // Tracing for structured logging (Story 2.7)
 use tracing::{debug, error, info, instrument};
@@ -181,8 +195,22 @@ impl TerminalType {
 
 /// Terminal capabilities information
 ///
-/// Provides information about what features the terminal supports.
+/// Provides information about what features the terminal supports. Fields
+/// may be added in later versions, so outside this crate, start from
+/// [`default`](Self::default) (the detected capabilities) and adjust it with
+/// the `with_*` methods.
+///
+/// ```
+/// use dotmax::render::TerminalCapabilities;
+///
+/// let caps = TerminalCapabilities::default()
+///     .with_sixel(false)
+///     .with_truecolor(false);
+/// assert!(!caps.supports_sixel);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] // Independent feature flags
+#[non_exhaustive]
 pub struct TerminalCapabilities {
     /// Whether the terminal supports basic ANSI colors (16 colors)
     pub supports_color: bool,
@@ -192,6 +220,8 @@ pub struct TerminalCapabilities {
     pub supports_unicode: bool,
     /// The detected terminal type (for viewport detection)
     pub terminal_type: TerminalType,
+    /// Whether the terminal draws Sixel graphics (see [`sixel::detect_support`])
+    pub supports_sixel: bool,
 }
 
 impl Default for TerminalCapabilities {
@@ -203,10 +233,379 @@ impl Default for TerminalCapabilities {
             supports_truecolor: true,
             supports_unicode: true,
             terminal_type: TerminalType::detect(),
+            supports_sixel: sixel::detect_support(),
+        }
+    }
+}
+
+impl TerminalCapabilities {
+    /// Sets whether basic ANSI colors are supported.
+    #[must_use]
+    pub const fn with_color(mut self, supported: bool) -> Self {
+        self.supports_color = supported;
+        self
+    }
+
+    /// Sets whether 24-bit RGB colors are supported.
+    #[must_use]
+    pub const fn with_truecolor(mut self, supported: bool) -> Self {
+        self.supports_truecolor = supported;
+        self
+    }
+
+    /// Sets whether braille and other Unicode glyphs display.
+    #[must_use]
+    pub const fn with_unicode(mut self, supported: bool) -> Self {
+        self.supports_unicode = supported;
+        self
+    }
+
+    /// Sets the terminal type used for viewport handling.
+    #[must_use]
+    pub const fn with_terminal_type(mut self, terminal_type: TerminalType) -> Self {
+        self.terminal_type = terminal_type;
+        self
+    }
+
+    /// Sets whether Sixel graphics are drawn.
+    #[must_use]
+    pub const fn with_sixel(mut self, supported: bool) -> Self {
+        self.supports_sixel = supported;
+        self
+    }
+}
+
+/// How [`TerminalRenderer`] draws a grid.
+///
+/// Modes fall back along the chain Sixel → braille → ASCII density when the
+/// terminal lacks what a mode needs, and sextants fall back to ASCII; see
+/// [`resolve`](Self::resolve).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RenderMode {
+    /// One braille character per cell (the default)
+    #[default]
+    Braille,
+    /// Each dot as a block of pixels, via [`sixel`]
+    ///
+    /// Support is recognized from `$TERM` and `$TERM_PROGRAM` (see
+    /// [`sixel::detect_support`]); the terminal is never queried. xterm only
+    /// draws Sixel when started with `-ti vt340`, which only a DA1 (`CSI c`)
+    /// query would reveal, so it falls back to braille unless
+    /// `DOTMAX_SIXEL=1` is set.
+    Sixel,
+    /// One ASCII character per cell, denser for cells with more dots set
+    Ascii,
+    /// One 2×3 sextant block character per cell, from the Unicode 13
+    /// "Symbols for Legacy Computing" block.
+    ///
+    /// Dot rows 0-2 of each cell are the three sextant rows; row 3 is
+    /// merged into the bottom one, so a grid drawn for braille still shows
+    /// everything, squashed. Grids built for this mode, such as those from
+    /// `image::mapper::pixels_to_sextants`, leave row 3 empty. Cells hold 6
+    /// pixels instead of 8 dots, but as solid blocks they show cell colors
+    /// far better than thin braille dots do. The font must cover U+1FB00.
+    Sextant,
+}
+
+impl RenderMode {
+    /// Every mode, in declaration order.
+    pub const ALL: [Self; 4] = [Self::Braille, Self::Sixel, Self::Ascii, Self::Sextant];
+
+    /// Lowercase name, as accepted by `$DOTMAX_RENDER_MODE`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Braille => "braille",
+            Self::Sixel => "sixel",
+            Self::Ascii => "ascii",
+            Self::Sextant => "sextant",
+        }
+    }
+
+    /// The mode called `name` (case-insensitive), if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The first mode along the fallback chain, starting at this one, that
+    /// `capabilities` supports.
+    ///
+    /// ```
+    /// use dotmax::render::{RenderMode, TerminalCapabilities};
+    ///
+    /// let caps = TerminalCapabilities::default().with_sixel(false);
+    /// assert_eq!(RenderMode::Sixel.resolve(&caps), RenderMode::Braille);
+    /// ```
+    #[must_use]
+    pub const fn resolve(self, capabilities: &TerminalCapabilities) -> Self {
+        match self {
+            Self::Sixel if capabilities.supports_sixel => Self::Sixel,
+            Self::Sixel | Self::Braille if capabilities.supports_unicode => Self::Braille,
+            Self::Sextant if capabilities.supports_unicode => Self::Sextant,
+            _ => Self::Ascii,
+        }
+    }
+}
+
+/// The character shown for cell `(x, y)` in text `mode`: its text
+/// character if it has one, otherwise a glyph for its dots.
+pub(crate) fn display_char(grid: &BrailleGrid, x: usize, y: usize, mode: RenderMode) -> char {
+    let ch = grid.get_char(x, y);
+    let pattern = grid.get_raw_patterns()[y * grid.width() + x];
+    if ch != crate::grid::dots_to_char(pattern) {
+        return ch;
+    }
+    match mode {
+        RenderMode::Sextant => sextant_for_dots(pattern),
+        RenderMode::Ascii => GlyphRepertoire::Ascii.cell_char(pattern),
+        RenderMode::Braille | RenderMode::Sixel => glyph_policy().cell_char(pattern),
+    }
+}
+
+/// The sextant character for a cell's dots, with dot row 3 merged into the
+/// bottom sextant row.
+fn sextant_for_dots(pattern: u8) -> char {
+    // Sextant bits run left to right, top to bottom: 1 is top-left, 32
+    // bottom-right
+    let lit = |mask: u8| u8::from(pattern & mask != 0);
+    let sextants = lit(0b0000_0001)
+        | lit(0b0000_1000) << 1
+        | lit(0b0000_0010) << 2
+        | lit(0b0001_0000) << 3
+        | lit(0b0100_0100) << 4
+        | lit(0b1010_0000) << 5;
+    match sextants {
+        0 => ' ',
+        // The two half blocks and the full block predate Unicode 13, so
+        // U+1FB00 onwards counts through the rest, skipping the halves
+        0b01_0101 => '▌',
+        0b10_1010 => '▐',
+        0b11_1111 => '█',
+        n => {
+            let skipped = u32::from(n > 0b01_0101) + u32::from(n > 0b10_1010);
+            char::from_u32(0x1FB00 + u32::from(n) - 1 - skipped).unwrap_or('█')
+        }
+    }
+}
+
+/// The [`SIMPLE_DENSITY`] character for a cell with `dots` of its 8 dots set.
+fn ascii_for_dots(dots: u32) -> char {
+    // Ten characters, so 0-8 dots spread over indices 0-9
+    SIMPLE_DENSITY
+        .chars()
+        .nth(dots.min(8) as usize * 9 / 8)
+        .unwrap_or('@')
+}
+
+// ============================================================================
+// Safe Area - margins kept free of output
+// ============================================================================
+
+/// Margins, in cells, that rendering leaves untouched.
+///
+/// Some terminals clip the last column or row, and some setups keep a shell
+/// prompt or status bar at the bottom of the screen. A safe area reserves
+/// those cells: [`TerminalRenderer`] draws inside it, and its reported size,
+/// [`image::detect_terminal_size`](crate::image::detect_terminal_size) (used
+/// by `resize_to_terminal`), and the [`quick`](crate::quick) helpers all
+/// exclude the margins, so grids sized to the terminal fit inside.
+///
+/// Set one for the whole process with [`set_safe_area`], or per renderer
+/// with [`TerminalRenderer::set_safe_area`].
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::SafeArea;
+///
+/// // Two rows for a prompt, and stay out of the last column
+/// let area = SafeArea { bottom: 2, right: 1, ..SafeArea::NONE };
+/// assert_eq!(area.inner_size(80, 24), (79, 22));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SafeArea {
+    /// Rows reserved at the top
+    pub top: u16,
+    /// Rows reserved at the bottom
+    pub bottom: u16,
+    /// Columns reserved on the left
+    pub left: u16,
+    /// Columns reserved on the right
+    pub right: u16,
+}
+
+impl SafeArea {
+    /// No margins: the whole terminal is usable.
+    pub const NONE: Self = Self {
+        top: 0,
+        bottom: 0,
+        left: 0,
+        right: 0,
+    };
+
+    /// The usable size of a `width × height` terminal, never below 1×1.
+    #[must_use]
+    pub const fn inner_size(&self, width: u16, height: u16) -> (u16, u16) {
+        let width = width.saturating_sub(self.left.saturating_add(self.right));
+        let height = height.saturating_sub(self.top.saturating_add(self.bottom));
+        (
+            if width == 0 { 1 } else { width },
+            if height == 0 { 1 } else { height },
+        )
+    }
+
+    const fn pack(self) -> u64 {
+        self.top as u64
+            | (self.bottom as u64) << 16
+            | (self.left as u64) << 32
+            | (self.right as u64) << 48
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn unpack(bits: u64) -> Self {
+        Self {
+            top: bits as u16,
+            bottom: (bits >> 16) as u16,
+            left: (bits >> 32) as u16,
+            right: (bits >> 48) as u16,
         }
     }
 }
 
+/// The process-wide safe area, packed by [`SafeArea::pack`]
+static SAFE_AREA_BITS: AtomicU64 = AtomicU64::new(0);
+
+/// Set the safe area for renderers created afterwards and for terminal size
+/// detection, for the rest of the process.
+pub fn set_safe_area(area: SafeArea) {
+    debug!(?area, "Setting safe area");
+    SAFE_AREA_BITS.store(area.pack(), Ordering::Relaxed);
+}
+
+/// The safe area set by [`set_safe_area`], or [`SafeArea::NONE`].
+#[must_use]
+pub fn safe_area() -> SafeArea {
+    SafeArea::unpack(SAFE_AREA_BITS.load(Ordering::Relaxed))
+}
+
+// ============================================================================
+// Status Line - a text row kept apart from the grid
+// ============================================================================
+
+/// A line of text for the row [`TerminalRenderer::set_status_line`]
+/// reserves, built from segments that can each have their own color.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::render::StatusLine;
+/// use dotmax::Color;
+///
+/// let status = StatusLine::new("demo.gif  ")
+///     .colored("▶ playing", Color::rgb(0, 200, 0))
+///     .text("  00:12 / 01:30")
+///     .background(Color::rgb(40, 40, 40));
+/// assert_eq!(status.plain_text(), "demo.gif  ▶ playing  00:12 / 01:30");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StatusLine {
+    segments: Vec<(String, Option<Color>)>,
+    background: Option<Color>,
+}
+
+impl StatusLine {
+    /// A status line starting with `text` in the terminal's default color.
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::default().text(text)
+    }
+
+    /// Appends `text` in the terminal's default color.
+    #[must_use]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.segments.push((text.into(), None));
+        self
+    }
+
+    /// Appends `text` in `color`.
+    #[must_use]
+    pub fn colored(mut self, text: impl Into<String>, color: Color) -> Self {
+        self.segments.push((text.into(), Some(color)));
+        self
+    }
+
+    /// Fills the whole row with `color` behind the text.
+    #[must_use]
+    pub const fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// The text of every segment, without colors.
+    #[must_use]
+    pub fn plain_text(&self) -> String {
+        self.segments
+            .iter()
+            .map(|(text, _)| text.as_str())
+            .collect()
+    }
+}
+
+impl From<&str> for StatusLine {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for StatusLine {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Splits the terminal's `full` area into the grid area inside the safe
+/// area and, with `status`, the row below it for the status line. The
+/// status row never includes the terminal's bottom-right cell.
+fn layout(full: Rect, safe_area: SafeArea, status: bool) -> (Rect, Option<Rect>) {
+    let (width, height) = safe_area.inner_size(full.width, full.height);
+    let inner = Rect {
+        x: full.x + safe_area.left.min(full.width.saturating_sub(width)),
+        y: full.y + safe_area.top.min(full.height.saturating_sub(height)),
+        width: width.min(full.width),
+        height: height.min(full.height),
+    };
+    if !status || inner.height == 0 {
+        return (inner, None);
+    }
+
+    let mut row = Rect {
+        y: inner.bottom() - 1,
+        height: 1,
+        ..inner
+    };
+    // Writing the last cell of the screen can scroll it
+    if row.right() == full.right() && row.bottom() == full.bottom() {
+        row.width = row.width.saturating_sub(1);
+    }
+    let grid = Rect {
+        height: inner.height - 1,
+        ..inner
+    };
+    (grid, Some(row))
+}
+
+const fn to_crossterm(color: Color) -> CrosstermColor {
+    CrosstermColor::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
+
 // ============================================================================
 // Terminal Backend Trait - ADR 0004
 // ============================================================================
@@ -271,13 +670,38 @@ pub trait TerminalBackend {
 /// renderer.cleanup().expect("Failed to cleanup terminal");
 /// ```
 pub struct TerminalRenderer {
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    terminal: Terminal<CrosstermBackend<CountingWriter<Stdout>>>,
     #[allow(dead_code)] // Reserved for future resize detection (Story 2.5)
     last_size: (u16, u16),
     /// Detected terminal type for viewport handling
     terminal_type: TerminalType,
     /// Whether this is the first render (clear needed) or subsequent (skip clear for performance)
     first_render: bool,
+    /// Margins left free of output
+    safe_area: SafeArea,
+    /// Requested render mode; resolved against the capabilities when drawing
+    render_mode: RenderMode,
+    /// Pixel size of a dot in Sixel mode
+    sixel_options: sixel::SixelOptions,
+    /// Text for the reserved bottom row, if any
+    status_line: Option<StatusLine>,
+    /// Whether the status row needs drawing (changed, or the screen was cleared)
+    status_dirty: bool,
+    /// Alternate screen or an inline block
+    screen: Screen,
+    /// Whether `cleanup` has already restored the terminal
+    released: bool,
+    /// Running count of bytes written to the terminal
+    bytes_written: Arc<AtomicU64>,
+}
+
+/// Where a [`TerminalRenderer`] draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    /// The whole alternate screen, left on cleanup
+    Alternate,
+    /// `height` rows at the cursor in the normal screen
+    Inline { height: u16 },
 }
 
 impl TerminalRenderer {
@@ -348,8 +772,9 @@ impl TerminalRenderer {
 
         // Create Ratatui terminal
         // Extracted from crabmusic/src/rendering/mod.rs:108-110
-        let backend = CrosstermBackend::new(stdout);
-        let terminal = Terminal::new(backend)?;
+        let writer = CountingWriter::new(stdout);
+        let bytes_written = writer.counter();
+        let terminal = Terminal::new(CrosstermBackend::new(writer))?;
 
         // Detect terminal type for viewport handling (Story 2.8)
         let terminal_type = TerminalType::detect();
@@ -360,11 +785,139 @@ impl TerminalRenderer {
             "Terminal renderer initialized successfully with terminal type detection"
         );
 
-        Ok(Self {
+        Ok(Self::with_terminal(
+            terminal,
+            bytes_written,
+            Screen::Alternate,
+            terminal_type,
+            (width, height),
+        ))
+    }
+
+    /// Initialize a renderer that draws a block of `height` rows at the
+    /// cursor instead of taking over the screen
+    ///
+    /// Nothing above the block is touched, and each frame redraws the same
+    /// rows, so output printed before it stays in scrollback. When there
+    /// isn't room below the cursor the terminal scrolls up to make some.
+    /// On cleanup the last frame is left where it is and the cursor moves
+    /// to the line below it, ready for whatever prints next — a good fit
+    /// for progress displays in build tools and REPLs.
+    ///
+    /// Raw mode is left alone, so Ctrl+C still interrupts the process.
+    /// [`get_terminal_size`](Self::get_terminal_size) reports the block's
+    /// size, and the [safe area](Self::set_safe_area) and
+    /// [status line](Self::set_status_line) apply within it.
+    ///
+    /// # Errors
+    /// Returns `DotmaxError::InvalidDimensions` if `height` is 0
+    /// Returns `DotmaxError::Terminal` if the cursor position can't be read
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::{BrailleGrid, TerminalRenderer};
+    ///
+    /// println!("Building...");
+    /// let mut renderer = TerminalRenderer::inline(3)?;
+    /// let (width, height) = renderer.get_terminal_size()?;
+    /// let mut grid = BrailleGrid::new(width as usize, height as usize)?;
+    /// for step in 0..grid.dot_width() {
+    ///     for y in 0..grid.dot_height() {
+    ///         grid.set_dot(step, y)?;
+    ///     }
+    ///     renderer.render(&grid)?;
+    /// }
+    /// renderer.cleanup()?; // the full bar stays on screen
+    /// println!("Done");
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    #[instrument]
+    pub fn inline(height: u16) -> Result<Self, DotmaxError> {
+        let (width, rows) = crossterm::terminal::size()?;
+        if height == 0 {
+            return Err(DotmaxError::InvalidDimensions {
+                width: usize::from(width),
+                height: 0,
+            });
+        }
+        let height = height.min(rows);
+
+        // The cursor is hidden while drawing; don't leave it that way
+        let original_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let _ = execute!(io::stdout(), Show);
+            original_hook(panic_info);
+        }));
+
+        let writer = CountingWriter::new(io::stdout());
+        let bytes_written = writer.counter();
+        let terminal = Terminal::with_options(
+            CrosstermBackend::new(writer),
+            TerminalOptions {
+                viewport: Viewport::Inline(height),
+            },
+        )?;
+
+        let terminal_type = TerminalType::detect();
+        info!(
+            width = width,
+            height = height,
+            terminal_type = terminal_type.name(),
+            "Inline terminal renderer initialized"
+        );
+
+        Ok(Self::with_terminal(
+            terminal,
+            bytes_written,
+            Screen::Inline { height },
+            terminal_type,
+            (width, height),
+        ))
+    }
+
+    fn with_terminal(
+        terminal: Terminal<CrosstermBackend<CountingWriter<Stdout>>>,
+        bytes_written: Arc<AtomicU64>,
+        screen: Screen,
+        terminal_type: TerminalType,
+        last_size: (u16, u16),
+    ) -> Self {
+        Self {
             terminal,
-            last_size: (width, height),
+            last_size,
             terminal_type,
             first_render: true,
+            safe_area: safe_area(),
+            render_mode: RenderMode::Braille,
+            sixel_options: sixel::SixelOptions {
+                // Opaque, so each frame covers the last
+                background: Some(Color::black()),
+                ..sixel::SixelOptions::for_terminal()
+            },
+            status_line: None,
+            status_dirty: false,
+            screen,
+            released: false,
+            bytes_written,
+        }
+    }
+
+    /// Whether this renderer draws inline rather than on the alternate
+    /// screen; see [`inline`](Self::inline).
+    #[must_use]
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.screen, Screen::Inline { .. })
+    }
+
+    /// The area of the terminal this renderer draws in.
+    fn viewport(&mut self) -> Result<Rect, DotmaxError> {
+        Ok(match self.screen {
+            Screen::Alternate => {
+                let size = self.terminal.size()?;
+                Rect::new(0, 0, size.width, size.height)
+            }
+            Screen::Inline { .. } => self.terminal.get_frame().area(),
         })
     }
 
@@ -374,7 +927,9 @@ impl TerminalRenderer {
     /// Ratatui handles differential rendering automatically.
     ///
     /// Extracted and adapted from crabmusic/src/rendering/mod.rs:202-254
-    /// Adapted to use `BrailleGrid::to_unicode_grid()` from Story 2.2
+    /// Adapted to use `BrailleGrid::to_unicode_grid()` from Story 2.2. Cells
+    /// holding a text character (density rendering, labels) show that
+    /// character instead of their dots, matching `BrailleGrid::get_char()`.
     ///
     /// # Arguments
     /// * `grid` - The braille grid to render
@@ -393,6 +948,12 @@ impl TerminalRenderer {
     /// ```
     #[instrument(skip(self, grid))]
     pub fn render(&mut self, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        let mode = self.render_mode();
+        if mode == RenderMode::Sixel {
+            return self.render_sixel(grid);
+        }
+        let started = Instant::now();
+        let bytes_before = self.bytes_written();
         let (grid_width, grid_height) = grid.dimensions();
         debug!(
             grid_width = grid_width,
@@ -407,13 +968,23 @@ impl TerminalRenderer {
         if self.first_render {
             self.terminal.clear()?;
             self.first_render = false;
+            self.status_dirty = true;
         }
 
-        // Convert grid to Unicode characters using Story 2.2 functionality
-        let unicode_grid = grid.to_unicode_grid();
-
+        // Convert grid to Unicode characters; text characters take precedence over dots
+        let unicode_grid: Vec<Vec<char>> = (0..grid_height)
+            .map(|y| {
+                (0..grid_width)
+                    .map(|x| display_char(grid, x, y, mode))
+                    .collect()
+            })
+            .collect();
+        let converted = Instant::now();
+
+        let safe_area = self.safe_area;
+        let has_status = self.status_line.is_some();
         self.terminal.draw(|frame| {
-            let area = frame.area();
+            let (area, _) = layout(frame.area(), safe_area, has_status);
 
             // DEBUG: Log the actual rendering area
             debug!(
@@ -476,9 +1047,39 @@ impl TerminalRenderer {
             frame.render_widget(paragraph, area);
         })?;
 
+        self.draw_status_if_dirty()?;
+        self.report_stats(
+            grid,
+            bytes_before,
+            &[
+                ("convert", converted - started),
+                ("draw", converted.elapsed()),
+            ],
+        );
         Ok(())
     }
 
+    /// Bytes written to the terminal since the renderer was created.
+    pub(crate) fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Sends [render statistics](stats) for `grid`, if they're enabled.
+    fn report_stats(
+        &self,
+        grid: &BrailleGrid,
+        bytes_before: u64,
+        stages: &[(&'static str, std::time::Duration)],
+    ) {
+        if !stats::enabled() {
+            return;
+        }
+        let mut report = RenderStats::for_grid("terminal", grid);
+        report.bytes_emitted = self.bytes_written() - bytes_before;
+        report.stages = stages.to_vec();
+        stats::report(&report);
+    }
+
     /// Clear the terminal display
     ///
     /// # Errors
@@ -493,7 +1094,8 @@ impl TerminalRenderer {
     /// Extracted from crabmusic/src/rendering/mod.rs:282-285
     ///
     /// # Returns
-    /// A tuple of (width, height) in characters
+    /// A tuple of (width, height) in characters, excluding the
+    /// [safe area](Self::set_safe_area) margins
     ///
     /// # Errors
     /// Returns `DotmaxError::Terminal` if querying terminal size fails
@@ -515,8 +1117,19 @@ impl TerminalRenderer {
         // Story 2.8: Return the viewport size (not buffer size)
         // The offset is applied during rendering in render(), not here
         // This ensures that grid sizing matches the actual visible viewport
-        let offset = self.terminal_type.viewport_height_offset(size.height);
-        let viewport_height = size.height.saturating_sub(offset);
+        let (offset, viewport_height) = match self.screen {
+            // Inline blocks sit inside the visible area already
+            Screen::Inline { height } => (0, height.min(size.height)),
+            Screen::Alternate => {
+                let offset = self.terminal_type.viewport_height_offset(size.height);
+                (offset, size.height.saturating_sub(offset))
+            }
+        };
+        let (usable_width, mut usable_height) =
+            self.safe_area.inner_size(size.width, viewport_height);
+        if self.status_line.is_some() {
+            usable_height = usable_height.saturating_sub(1).max(1);
+        }
 
         debug!(
             terminal_type = self.terminal_type.name(),
@@ -525,10 +1138,207 @@ impl TerminalRenderer {
             viewport_width = size.width,
             viewport_height = viewport_height,
             offset = offset,
+            usable_width = usable_width,
+            usable_height = usable_height,
             "Terminal size query (returning viewport dimensions for grid sizing)"
         );
 
-        Ok((size.width, viewport_height))
+        Ok((usable_width, usable_height))
+    }
+
+    /// Draw with `mode` from now on. If the terminal can't display it, the
+    /// next mode along the fallback chain is used instead; see
+    /// [`RenderMode::resolve`].
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+        // The screen no longer matches what ratatui last drew
+        self.first_render = true;
+    }
+
+    /// The mode grids are actually drawn in, after falling back from the
+    /// requested one.
+    #[must_use]
+    pub fn render_mode(&self) -> RenderMode {
+        self.render_mode.resolve(&self.capabilities())
+    }
+
+    /// Pixel size and colors of dots in Sixel mode. Defaults to dots sized
+    /// to match the terminal's cells, white on black.
+    pub fn set_sixel_options(&mut self, options: sixel::SixelOptions) {
+        self.sixel_options = options;
+    }
+
+    /// Draws `grid` as a Sixel image at the top-left of the safe area,
+    /// cropped to fit inside it.
+    fn render_sixel(&mut self, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        let started = Instant::now();
+        let bytes_before = self.bytes_written();
+        if self.first_render {
+            self.terminal.clear()?;
+            self.first_render = false;
+            self.status_dirty = true;
+        }
+        let (width, height) = self.get_terminal_size()?;
+        let visible = (
+            grid.width().min(usize::from(width)),
+            grid.height().min(usize::from(height)),
+        );
+        let mut cropped;
+        let grid = if visible == grid.dimensions() {
+            grid
+        } else {
+            cropped = BrailleGrid::new(visible.0, visible.1)?;
+            cropped.paste(grid, 0, 0);
+            &cropped
+        };
+
+        let (area, _) = layout(self.viewport()?, self.safe_area, false);
+        let data = sixel::encode_grid(grid, &self.sixel_options);
+        let encoded = Instant::now();
+        let out = self.terminal.backend_mut();
+        execute!(out, MoveTo(area.x, area.y))?;
+        out.write_all(data.as_bytes())?;
+        out.flush()?;
+        self.draw_status_if_dirty()?;
+        self.report_stats(
+            grid,
+            bytes_before,
+            &[("encode", encoded - started), ("draw", encoded.elapsed())],
+        );
+        Ok(())
+    }
+
+    /// Reserve the bottom row of the safe area for `status`, or give it
+    /// back to the grid with `None`.
+    ///
+    /// The status line is drawn straight to the terminal rather than
+    /// through the grid, so [`update_status`](Self::update_status) can
+    /// change it between frames without redrawing them, and a grid sized
+    /// with [`get_terminal_size`](Self::get_terminal_size) never overlaps
+    /// it. Nothing is ever written to the terminal's bottom-right cell,
+    /// which on many terminals would scroll the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use dotmax::render::StatusLine;
+    /// use dotmax::{BrailleGrid, Color, TerminalRenderer};
+    ///
+    /// let mut renderer = TerminalRenderer::new()?;
+    /// renderer.set_status_line(Some(StatusLine::new("frame 0")));
+    ///
+    /// let (width, height) = renderer.get_terminal_size()?; // one row short
+    /// let grid = BrailleGrid::new(width as usize, height as usize)?;
+    /// for frame in 1..100 {
+    ///     renderer.render(&grid)?;
+    ///     renderer.update_status(
+    ///         StatusLine::new(format!("frame {frame}")).colored("  ● live", Color::rgb(255, 0, 0)),
+    ///     )?;
+    /// }
+    /// # Ok::<(), dotmax::DotmaxError>(())
+    /// ```
+    pub fn set_status_line(&mut self, status: Option<StatusLine>) {
+        if self.status_line.is_some() != status.is_some() {
+            // The grid area changes size; start from a clean screen
+            self.first_render = true;
+        }
+        self.status_line = status;
+        self.status_dirty = true;
+    }
+
+    /// Replace the status line text and draw it immediately, without
+    /// touching the grid. Enables the status line if it was off; the grid
+    /// area shrinks at the next render.
+    ///
+    /// # Errors
+    /// Returns `DotmaxError::Terminal` if writing to the terminal fails
+    pub fn update_status(&mut self, status: StatusLine) -> Result<(), DotmaxError> {
+        if self.status_line.as_ref() == Some(&status) {
+            return Ok(());
+        }
+        self.set_status_line(Some(status));
+        if self.first_render {
+            // Drawn after the screen is cleared by the next render
+            return Ok(());
+        }
+        self.draw_status_if_dirty()
+    }
+
+    /// The current status line, if one is reserved.
+    #[must_use]
+    pub const fn status_line(&self) -> Option<&StatusLine> {
+        self.status_line.as_ref()
+    }
+
+    /// Write the status line to its row if it changed since it was last
+    /// drawn, padded with spaces to cover what was there before.
+    pub(crate) fn draw_status_if_dirty(&mut self) -> Result<(), DotmaxError> {
+        if !self.status_dirty {
+            return Ok(());
+        }
+        self.status_dirty = false;
+        let full = self.viewport()?;
+        let Some(status) = &self.status_line else {
+            return Ok(());
+        };
+        let (_, Some(row)) = layout(full, self.safe_area, true) else {
+            return Ok(());
+        };
+
+        let out = self.terminal.backend_mut();
+        queue!(out, MoveTo(row.x, row.y))?;
+        if let Some(background) = status.background {
+            queue!(out, SetBackgroundColor(to_crossterm(background)))?;
+        }
+        let mut remaining = usize::from(row.width);
+        for (text, color) in &status.segments {
+            let text: String = text.chars().take(remaining).collect();
+            remaining -= text.chars().count();
+            match color {
+                Some(color) => queue!(out, SetForegroundColor(to_crossterm(*color)))?,
+                None => queue!(out, SetForegroundColor(CrosstermColor::Reset))?,
+            }
+            queue!(out, Print(text))?;
+        }
+        queue!(out, Print(" ".repeat(remaining)), ResetColor)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Keep `area`'s margins free of output from now on. The screen is
+    /// cleared on the next render so nothing is left behind in them.
+    ///
+    /// Defaults to the process-wide [`safe_area`] at creation.
+    pub fn set_safe_area(&mut self, area: SafeArea) {
+        self.safe_area = area;
+        self.first_render = true;
+    }
+
+    /// The margins this renderer leaves free.
+    #[must_use]
+    pub const fn safe_area(&self) -> SafeArea {
+        self.safe_area
+    }
+
+    /// The terminal area [`render`](Self::render) draws the grid into, for
+    /// output that bypasses ratatui and addresses cells directly. For an
+    /// [inline](Self::inline) renderer it starts at the block's first row,
+    /// not the top of the screen.
+    pub(crate) fn grid_area(&mut self) -> Result<Rect, DotmaxError> {
+        let full = self.viewport()?;
+        Ok(layout(full, self.safe_area, self.status_line.is_some()).0)
+    }
+
+    /// Whether the next [`render`](Self::render) clears the screen, because
+    /// nothing drawn so far can be trusted to still be there.
+    pub(crate) const fn needs_full_render(&self) -> bool {
+        self.first_render
+    }
+
+    /// The writer behind the terminal, so direct output is counted and
+    /// ordered with everything else this renderer writes.
+    pub(crate) fn writer(&mut self) -> &mut impl Write {
+        self.terminal.backend_mut()
     }
 
     /// Clean up and restore terminal state
@@ -538,10 +1348,33 @@ impl TerminalRenderer {
     ///
     /// Extracted from crabmusic/src/rendering/mod.rs:263-265
     ///
+    /// An [inline](Self::inline) renderer leaves its last frame in place and
+    /// moves the cursor to the start of the line below it.
+    ///
     /// # Errors
     /// Returns `DotmaxError::Terminal` if cleanup fails
     pub fn cleanup(&mut self) -> Result<(), DotmaxError> {
-        Self::restore_terminal()
+        // Leaving the alternate screen twice can move the cursor back to
+        // where it was when we entered
+        if self.released {
+            return Ok(());
+        }
+        self.released = true;
+        match self.screen {
+            Screen::Alternate => Self::restore_terminal(),
+            Screen::Inline { .. } => {
+                let area = self.viewport()?;
+                let out = self.terminal.backend_mut();
+                // "\r\n" from the last row scrolls if the block is at the bottom
+                execute!(
+                    out,
+                    MoveTo(0, area.bottom().saturating_sub(1)),
+                    Print("\r\n"),
+                    Show
+                )?;
+                Ok(())
+            }
+        }
     }
 
     /// Restore terminal to original state (static for panic handler)
@@ -583,6 +1416,118 @@ impl Drop for TerminalRenderer {
     }
 }
 
+/// Print `grid` to stdout as lines of text, coloring colored cells with
+/// ANSI escapes.
+///
+/// No terminal setup is involved: nothing is cleared, and the grid is left
+/// in the normal screen buffer (and its scrollback) like any other output.
+/// That makes it suitable for static output from scripts, including when
+/// stdout is redirected to a file. It can also leave the last frame of an
+/// animation behind once a [`TerminalRenderer`] has been cleaned up —
+/// call it after [`cleanup`](TerminalRenderer::cleanup), since raw mode
+/// would stop each line from starting at the left edge.
+///
+/// # Errors
+/// Returns `DotmaxError::Terminal` if writing to stdout fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use dotmax::{render_once_to_stdout, BrailleGrid, Color};
+///
+/// let mut grid = BrailleGrid::new(20, 2)?;
+/// for x in 0..40 {
+///     grid.set_dot(x, x / 5)?;
+/// }
+/// grid.set_cell_color(0, 0, Color::rgb(0, 200, 255))?;
+/// render_once_to_stdout(&grid)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn render_once_to_stdout(grid: &BrailleGrid) -> Result<(), DotmaxError> {
+    print_grid(grid, true)
+}
+
+/// Write `grid` to `out` as lines of text, coloring colored cells with
+/// ANSI escapes, exactly as [`render_once_to_stdout`] prints it.
+///
+/// Nothing about a terminal is assumed, so `out` can be a file, a socket,
+/// or a buffer in a test. To stream frames that redraw in place, use a
+/// [`WriterBackend`](writer::WriterBackend).
+///
+/// # Errors
+/// Returns `DotmaxError::Terminal` if writing to `out` fails
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::{render_to_writer, BrailleGrid};
+///
+/// let mut grid = BrailleGrid::new(3, 2)?;
+/// grid.set_dot(0, 0)?;
+/// let mut out = Vec::new();
+/// render_to_writer(&mut out, &grid)?;
+/// assert_eq!(String::from_utf8(out).unwrap(), "⠁⠀⠀\n⠀⠀⠀\n");
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+pub fn render_to_writer(out: &mut impl Write, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+    write_lines(out, grid, true)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Prints `grid` to stdout as lines of text, with or without its colors.
+pub(crate) fn print_grid(grid: &BrailleGrid, colors: bool) -> Result<(), DotmaxError> {
+    let started = Instant::now();
+    let mut out = CountingWriter::new(io::stdout().lock());
+    write_lines(&mut out, grid, colors)?;
+    out.flush()?;
+    if stats::enabled() {
+        let mut report = RenderStats::for_grid("stdout", grid);
+        report.bytes_emitted = out.written();
+        report.stages = vec![("write", started.elapsed())];
+        stats::report(&report);
+    }
+    Ok(())
+}
+
+/// Writes `grid` one line per row.
+fn write_lines(out: &mut impl Write, grid: &BrailleGrid, colors: bool) -> io::Result<()> {
+    for y in 0..grid.height() {
+        write_row(out, grid, y, colors, RenderMode::Braille)?;
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Writes row `y` of `grid` without a line ending, with cells drawn as
+/// `mode` draws them. With `colors`, the foreground color changes only
+/// where it differs from the previous cell and is reset at the end of the
+/// row.
+pub(crate) fn write_row(
+    out: &mut impl Write,
+    grid: &BrailleGrid,
+    y: usize,
+    colors: bool,
+    mode: RenderMode,
+) -> io::Result<()> {
+    let mut current = None;
+    for x in 0..grid.width() {
+        let color = grid.get_color(x, y).filter(|_| colors);
+        if color != current {
+            match color {
+                Some(color) => queue!(out, SetForegroundColor(to_crossterm(color)))?,
+                None => queue!(out, ResetColor)?,
+            }
+            current = color;
+        }
+        queue!(out, Print(display_char(grid, x, y, mode)))?;
+    }
+    if current.is_some() {
+        queue!(out, ResetColor)?;
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -776,6 +1721,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_render_mode_fallback_chain() {
+        let full = TerminalCapabilities {
+            supports_sixel: true,
+            ..TerminalCapabilities::default()
+        };
+        assert_eq!(RenderMode::Sixel.resolve(&full), RenderMode::Sixel);
+
+        let no_sixel = TerminalCapabilities {
+            supports_sixel: false,
+            ..full
+        };
+        assert_eq!(RenderMode::Sixel.resolve(&no_sixel), RenderMode::Braille);
+
+        let ascii_only = TerminalCapabilities {
+            supports_unicode: false,
+            ..no_sixel
+        };
+        assert_eq!(RenderMode::Sixel.resolve(&ascii_only), RenderMode::Ascii);
+        assert_eq!(RenderMode::Braille.resolve(&ascii_only), RenderMode::Ascii);
+        assert_eq!(RenderMode::Ascii.resolve(&full), RenderMode::Ascii);
+        assert_eq!(RenderMode::Sextant.resolve(&full), RenderMode::Sextant);
+        assert_eq!(RenderMode::Sextant.resolve(&ascii_only), RenderMode::Ascii);
+        assert_eq!(RenderMode::from_name(" Sextant "), Some(RenderMode::Sextant));
+        assert_eq!(RenderMode::from_name("halfblock"), None);
+    }
+
+    #[test]
+    fn test_sextant_for_dots() {
+        assert_eq!(sextant_for_dots(0), ' ');
+        assert_eq!(sextant_for_dots(0b0000_0001), '\u{1FB00}'); // top-left
+        assert_eq!(sextant_for_dots(0b0000_1001), '\u{1FB02}'); // top row
+        assert_eq!(sextant_for_dots(0b0000_0111), '▌');
+        assert_eq!(sextant_for_dots(0b0011_1000), '▐');
+        assert_eq!(sextant_for_dots(0b0011_1111), '█');
+        // Everything but the top-left sextant is the last code point
+        assert_eq!(sextant_for_dots(0b1111_1110), '\u{1FB3B}');
+        // Row 3 lands in the bottom row
+        assert_eq!(sextant_for_dots(0b1100_0000), sextant_for_dots(0b0010_0100));
+
+        // One distinct glyph for each of the 64 combinations
+        let mut glyphs: Vec<char> = (0..=0b0011_1111).map(sextant_for_dots).collect();
+        glyphs.sort_unstable();
+        glyphs.dedup();
+        assert_eq!(glyphs.len(), 64);
+    }
+
+    #[test]
+    fn test_ascii_for_dots() {
+        assert_eq!(ascii_for_dots(0), ' ');
+        assert_eq!(ascii_for_dots(4), '=');
+        assert_eq!(ascii_for_dots(8), '@');
+    }
+
     #[test]
     fn test_terminal_capabilities_includes_terminal_type() {
         let caps = TerminalCapabilities::default();
@@ -800,6 +1799,95 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_safe_area_inner_size() {
+        let area = SafeArea {
+            bottom: 2,
+            right: 1,
+            ..SafeArea::NONE
+        };
+        assert_eq!(area.inner_size(80, 24), (79, 22));
+        assert_eq!(SafeArea::NONE.inner_size(80, 24), (80, 24));
+        // Margins larger than the terminal leave one cell
+        let huge = SafeArea {
+            top: 30,
+            left: 100,
+            ..area
+        };
+        assert_eq!(huge.inner_size(80, 24), (1, 1));
+    }
+
+    #[test]
+    fn test_safe_area_pack_round_trip() {
+        let area = SafeArea {
+            top: 1,
+            bottom: 65535,
+            left: 7,
+            right: 300,
+        };
+        assert_eq!(SafeArea::unpack(area.pack()), area);
+    }
+
+    #[test]
+    fn test_layout_reserves_status_row() {
+        let full = Rect::new(0, 0, 80, 24);
+        let (grid, status) = layout(full, SafeArea::NONE, false);
+        assert_eq!((grid, status), (full, None));
+
+        // The status row is the last one, minus the bottom-right cell
+        let (grid, status) = layout(full, SafeArea::NONE, true);
+        assert_eq!(grid, Rect::new(0, 0, 80, 23));
+        assert_eq!(status, Some(Rect::new(0, 23, 79, 1)));
+
+        // Inside the safe area, clear of the terminal's last cell already
+        let area = SafeArea {
+            bottom: 2,
+            left: 1,
+            ..SafeArea::NONE
+        };
+        let (grid, status) = layout(full, area, true);
+        assert_eq!(grid, Rect::new(1, 0, 79, 21));
+        assert_eq!(status, Some(Rect::new(1, 21, 79, 1)));
+    }
+
+    #[test]
+    fn test_layout_inside_inline_block() {
+        // A 3-row inline block that starts on row 30 of the screen
+        let full = Rect::new(0, 30, 80, 3);
+        let area = SafeArea {
+            top: 1,
+            left: 2,
+            ..SafeArea::NONE
+        };
+        let (grid, status) = layout(full, area, true);
+        assert_eq!(grid, Rect::new(2, 31, 78, 1));
+        assert_eq!(status, Some(Rect::new(2, 32, 77, 1)));
+    }
+
+    #[test]
+    fn test_write_lines() {
+        let mut grid = BrailleGrid::new(3, 2).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        let mut out = Vec::new();
+        write_lines(&mut out, &grid, true).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "⠁⠀⠀\n⠀⠀⠀\n");
+
+        // One escape per color run, reset before the line ends
+        let red = Color::rgb(255, 0, 0);
+        grid.set_cell_color(1, 0, red).unwrap();
+        grid.set_cell_color(2, 0, red).unwrap();
+        let mut out = Vec::new();
+        write_lines(&mut out, &grid, true).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let first_line = text.lines().next().unwrap();
+        assert_eq!(first_line, "⠁\x1b[38;2;255;0;0m⠀⠀\x1b[0m");
+
+        // Without colors the escapes are left out
+        let mut out = Vec::new();
+        write_lines(&mut out, &grid, false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "⠁⠀⠀\n⠀⠀⠀\n");
+    }
+
     #[test]
     fn test_terminal_renderer_creation() {
         let _renderer = require_terminal!();
@@ -0,0 +1,390 @@
+//! Falling back to the glyphs a terminal's font actually has.
+//!
+//! Braille needs a font with the U+2800 block, which not every locale or
+//! console font provides. Where it's missing, a grid is still readable with
+//! coarser glyphs, so output degrades along a fixed chain of
+//! [`GlyphRepertoire`]s, best first:
+//!
+//! 1. [`Braille`](GlyphRepertoire::Braille): one braille character per
+//!    cell, all 8 dots.
+//! 2. [`Quadrants`](GlyphRepertoire::Quadrants): quadrant block elements
+//!    (`▘▝▀▖▌▞▛…`), each quadrant lit if either of its 2 dots is.
+//! 3. [`Shades`](GlyphRepertoire::Shades): the shade blocks `░▒▓█`, by how
+//!    many dots are set.
+//! 4. [`Ascii`](GlyphRepertoire::Ascii): the
+//!    [`SIMPLE_DENSITY`](crate::density::SIMPLE_DENSITY) ramp.
+//!
+//! A [`GlyphPolicy`] records the best repertoire the terminal can show.
+//! The process-wide policy ([`glyph_policy`], or [`set_glyph_policy`] to
+//! override detection) is applied by [`TerminalRenderer`] and
+//! [`render_once_to_stdout`] when drawing dots, and by
+//! [`render_density`](crate::BrailleGrid::render_density), which swaps a
+//! density set the terminal can't show for one it can. Sixel output is
+//! pixels and doesn't depend on the font.
+//!
+//! [`TerminalRenderer`]: super::TerminalRenderer
+//! [`render_once_to_stdout`]: super::render_once_to_stdout
+//!
+//! # Detection
+//!
+//! Nothing is queried from the terminal. In order:
+//!
+//! 1. `$DOTMAX_GLYPHS` set to `braille`, `quadrants`, `shades`, or `ascii`
+//!    wins.
+//! 2. A locale (`$LC_ALL`, `$LC_CTYPE`, then `$LANG`) naming a character set
+//!    other than UTF-8, such as `en_US.ISO-8859-1`, means ASCII. A locale
+//!    without a character set (`C`, `POSIX`) says nothing either way, since
+//!    containers often run UTF-8 terminals with it.
+//! 3. `TERM=linux`, the Linux virtual console, means shades: its default
+//!    font carries the IBM PC block characters but neither braille nor
+//!    quadrants. `vt100`-style terminals mean ASCII.
+//! 4. Otherwise braille.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::render::glyphs::{GlyphPolicy, GlyphRepertoire};
+//!
+//! let console = GlyphPolicy::detect_with_env(None, None, Some("linux"));
+//! assert_eq!(console.repertoire(), GlyphRepertoire::Shades);
+//!
+//! // A full cell, then the top-left 2×2 dots
+//! assert_eq!(console.cell_char(0xFF), '█');
+//! assert_eq!(GlyphRepertoire::Quadrants.cell_char(0b0001_1011), '▀');
+//! ```
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use tracing::debug;
+
+use super::ascii_for_dots;
+use crate::density::DensitySet;
+use crate::grid::dots_to_char;
+
+/// A set of glyphs used to draw cells, from richest to plainest.
+///
+/// Each repertoire includes everything in the ones after it, so the derived
+/// ordering puts the best first: a *greater* repertoire is a plainer one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GlyphRepertoire {
+    /// Braille patterns, and any other Unicode
+    Braille,
+    /// Block elements (U+2580-U+259F), including quadrants
+    Quadrants,
+    /// Shade and half blocks from the IBM PC set: `░▒▓█▀▄▌▐`
+    Shades,
+    /// Printable ASCII
+    Ascii,
+}
+
+/// Quadrant glyphs indexed by lit quadrants: bit 0 top-left, 1 top-right,
+/// 2 bottom-left, 3 bottom-right.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// The block characters in the IBM PC character set.
+const PC_BLOCKS: &str = "░▒▓█▀▄▌▐";
+
+impl GlyphRepertoire {
+    /// All repertoires, best first.
+    pub const ALL: [Self; 4] = [Self::Braille, Self::Quadrants, Self::Shades, Self::Ascii];
+
+    /// Lowercase name, as accepted by `$DOTMAX_GLYPHS`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Braille => "braille",
+            Self::Quadrants => "quadrants",
+            Self::Shades => "shades",
+            Self::Ascii => "ascii",
+        }
+    }
+
+    /// The repertoire called `name` (case-insensitive), if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|repertoire| repertoire.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Whether `ch` can be shown with this repertoire.
+    #[must_use]
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            Self::Braille => true,
+            Self::Quadrants => ch == ' ' || ch.is_ascii_graphic() || ('▀'..='▟').contains(&ch),
+            Self::Shades => ch == ' ' || ch.is_ascii_graphic() || PC_BLOCKS.contains(ch),
+            Self::Ascii => ch == ' ' || ch.is_ascii_graphic(),
+        }
+    }
+
+    /// The glyph for a cell with dot bits `pattern` (see
+    /// [`BrailleDot`](crate::grid::BrailleDot)).
+    #[must_use]
+    pub fn cell_char(self, pattern: u8) -> char {
+        match self {
+            Self::Braille => dots_to_char(pattern),
+            Self::Quadrants => {
+                // Dots 1-2, 4-5, 3-7, and 6-8 make up the four quadrants
+                let lit = |mask: u8| u8::from(pattern & mask != 0);
+                let index = lit(0b0000_0011)
+                    | lit(0b0001_1000) << 1
+                    | lit(0b0100_0100) << 2
+                    | lit(0b1010_0000) << 3;
+                QUADRANTS[usize::from(index)]
+            }
+            Self::Shades => match pattern.count_ones() {
+                0 => ' ',
+                1 | 2 => '░',
+                3..=5 => '▒',
+                6 | 7 => '▓',
+                _ => '█',
+            },
+            Self::Ascii => ascii_for_dots(pattern.count_ones()),
+        }
+    }
+}
+
+/// The best glyph repertoire a terminal can show, and how to draw within it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlyphPolicy {
+    repertoire: GlyphRepertoire,
+}
+
+impl Default for GlyphPolicy {
+    /// Everything available: braille.
+    fn default() -> Self {
+        Self::new(GlyphRepertoire::Braille)
+    }
+}
+
+impl GlyphPolicy {
+    /// A policy allowing `repertoire` and everything plainer.
+    #[must_use]
+    pub const fn new(repertoire: GlyphRepertoire) -> Self {
+        Self { repertoire }
+    }
+
+    /// The policy for this process's terminal, from the environment (see
+    /// the [module docs](self)). Cached for the process.
+    #[must_use]
+    pub fn detect() -> Self {
+        static DETECTED: OnceLock<GlyphPolicy> = OnceLock::new();
+        *DETECTED.get_or_init(|| {
+            let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+            let locale = var("LC_ALL")
+                .or_else(|| var("LC_CTYPE"))
+                .or_else(|| var("LANG"));
+            let policy = Self::detect_with_env(
+                var("DOTMAX_GLYPHS").as_deref(),
+                locale.as_deref(),
+                var("TERM").as_deref(),
+            );
+            debug!(
+                repertoire = policy.repertoire.name(),
+                "Glyph repertoire detected"
+            );
+            policy
+        })
+    }
+
+    /// [`detect`](Self::detect) with explicit environment values, uncached
+    /// (for testing). `locale` is the first of `$LC_ALL`, `$LC_CTYPE`, and
+    /// `$LANG` that is set.
+    #[must_use]
+    pub fn detect_with_env(
+        dotmax_glyphs: Option<&str>,
+        locale: Option<&str>,
+        term: Option<&str>,
+    ) -> Self {
+        if let Some(repertoire) = dotmax_glyphs.and_then(GlyphRepertoire::from_name) {
+            return Self::new(repertoire);
+        }
+
+        // language_TERRITORY.codeset@modifier
+        let codeset = locale
+            .and_then(|locale| locale.split('@').next())
+            .and_then(|locale| locale.split_once('.'))
+            .map(|(_, codeset)| codeset.to_ascii_lowercase().replace('-', ""));
+        if codeset.is_some_and(|codeset| codeset != "utf8") {
+            return Self::new(GlyphRepertoire::Ascii);
+        }
+
+        let term = term.unwrap_or_default().to_ascii_lowercase();
+        if term == "linux" {
+            Self::new(GlyphRepertoire::Shades)
+        } else if term.starts_with("vt") {
+            Self::new(GlyphRepertoire::Ascii)
+        } else {
+            Self::new(GlyphRepertoire::Braille)
+        }
+    }
+
+    /// The best repertoire allowed.
+    #[must_use]
+    pub const fn repertoire(&self) -> GlyphRepertoire {
+        self.repertoire
+    }
+
+    /// `requested`, or the best allowed repertoire if `requested` is richer
+    /// than that.
+    #[must_use]
+    pub fn resolve(&self, requested: GlyphRepertoire) -> GlyphRepertoire {
+        requested.max(self.repertoire)
+    }
+
+    /// The glyph for a cell with dot bits `pattern`, in the best allowed
+    /// repertoire.
+    #[must_use]
+    pub fn cell_char(&self, pattern: u8) -> char {
+        self.repertoire.cell_char(pattern)
+    }
+
+    /// `set` if every one of its characters can be shown; otherwise
+    /// [`DensitySet::blocks`] where shade blocks can be, and
+    /// [`DensitySet::simple`] where only ASCII can.
+    #[must_use]
+    pub fn density_set<'a>(&self, set: &'a DensitySet) -> Cow<'a, DensitySet> {
+        if set
+            .characters
+            .iter()
+            .all(|&ch| self.repertoire.contains(ch))
+        {
+            Cow::Borrowed(set)
+        } else if self.repertoire <= GlyphRepertoire::Shades {
+            Cow::Owned(DensitySet::blocks())
+        } else {
+            Cow::Owned(DensitySet::simple())
+        }
+    }
+}
+
+/// The process-wide repertoire: 0 until set, else its index in
+/// [`GlyphRepertoire::ALL`] plus one
+static POLICY: AtomicU8 = AtomicU8::new(0);
+
+/// Use `policy` for rendering and density sets for the rest of the process,
+/// instead of the detected one.
+pub fn set_glyph_policy(policy: GlyphPolicy) {
+    debug!(
+        repertoire = policy.repertoire.name(),
+        "Setting glyph policy"
+    );
+    let index = GlyphRepertoire::ALL
+        .iter()
+        .position(|&repertoire| repertoire == policy.repertoire)
+        .unwrap_or(0);
+    #[allow(clippy::cast_possible_truncation)]
+    POLICY.store(index as u8 + 1, Ordering::Relaxed);
+}
+
+/// The policy set by [`set_glyph_policy`], or [`GlyphPolicy::detect`].
+#[must_use]
+pub fn glyph_policy() -> GlyphPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        0 => GlyphPolicy::detect(),
+        stored => GlyphPolicy::new(GlyphRepertoire::ALL[usize::from(stored - 1).min(3)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_with_env() {
+        let detect = |glyphs, locale, term| GlyphPolicy::detect_with_env(glyphs, locale, term);
+        let repertoire = |policy: GlyphPolicy| policy.repertoire();
+
+        assert_eq!(
+            repertoire(detect(None, None, None)),
+            GlyphRepertoire::Braille
+        );
+        let utf8 = detect(None, Some("en_US.UTF-8"), Some("xterm-256color"));
+        assert_eq!(repertoire(utf8), GlyphRepertoire::Braille);
+        let latin1 = detect(None, Some("de_DE.ISO-8859-1@euro"), Some("xterm"));
+        assert_eq!(repertoire(latin1), GlyphRepertoire::Ascii);
+        // No codeset: no evidence either way
+        assert_eq!(
+            repertoire(detect(None, Some("C"), None)),
+            GlyphRepertoire::Braille
+        );
+        assert_eq!(
+            repertoire(detect(None, Some("C.utf8"), Some("linux"))),
+            GlyphRepertoire::Shades
+        );
+        assert_eq!(
+            repertoire(detect(None, None, Some("vt220"))),
+            GlyphRepertoire::Ascii
+        );
+
+        // The override wins over everything, and bad values are ignored
+        let forced = detect(Some("Quadrants"), Some("en_US.ISO-8859-1"), Some("linux"));
+        assert_eq!(repertoire(forced), GlyphRepertoire::Quadrants);
+        assert_eq!(
+            repertoire(detect(Some("emoji"), None, None)),
+            GlyphRepertoire::Braille
+        );
+    }
+
+    #[test]
+    fn test_cell_chars() {
+        let full = 0xFF;
+        let top_left_dot = 0b0000_0001;
+        let left_column = 0b0100_0111;
+        for (repertoire, expected) in [
+            (GlyphRepertoire::Braille, ['⣿', '⠁', '⡇']),
+            (GlyphRepertoire::Quadrants, ['█', '▘', '▌']),
+            (GlyphRepertoire::Shades, ['█', '░', '▒']),
+            (GlyphRepertoire::Ascii, ['@', '.', '=']),
+        ] {
+            let chars = [full, top_left_dot, left_column].map(|p| repertoire.cell_char(p));
+            assert_eq!(chars, expected, "{repertoire:?}");
+            assert!(chars.iter().all(|&ch| repertoire.contains(ch)));
+            let blank = if repertoire == GlyphRepertoire::Braille {
+                '⠀'
+            } else {
+                ' '
+            };
+            assert_eq!(repertoire.cell_char(0), blank);
+        }
+
+        // Every quadrant glyph is distinct, one per combination
+        let mut seen: Vec<char> = (0..=255)
+            .map(|p| GlyphRepertoire::Quadrants.cell_char(p))
+            .collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), 16);
+    }
+
+    #[test]
+    fn test_density_set_fallback() {
+        let braille = DensitySet::braille();
+        let simple = DensitySet::simple();
+
+        let full = GlyphPolicy::default();
+        assert!(matches!(full.density_set(&braille), Cow::Borrowed(_)));
+
+        let shades = GlyphPolicy::new(GlyphRepertoire::Shades);
+        assert_eq!(shades.density_set(&braille).name, "Blocks");
+        assert_eq!(shades.density_set(&DensitySet::blocks()).name, "Blocks");
+        assert!(matches!(shades.density_set(&simple), Cow::Borrowed(_)));
+
+        let ascii = GlyphPolicy::new(GlyphRepertoire::Ascii);
+        assert_eq!(ascii.density_set(&DensitySet::blocks()).name, simple.name);
+        assert_eq!(
+            ascii.resolve(GlyphRepertoire::Braille),
+            GlyphRepertoire::Ascii
+        );
+        assert_eq!(
+            full.resolve(GlyphRepertoire::Shades),
+            GlyphRepertoire::Shades
+        );
+    }
+}
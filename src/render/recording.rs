@@ -0,0 +1,396 @@
+//! Recording rendered frames for replay outside the program.
+//!
+//! A [`Recorder`] turns a sequence of [`BrailleGrid`] frames into terminal
+//! output with timing, in one of two [formats](RecordingFormat):
+//!
+//! - **asciicast v2** (`.cast`), which `asciinema play` replays and the
+//!   asciinema web player embeds in a page.
+//! - **Raw ANSI**, the escape sequences alone, which `cat` replays at
+//!   full speed. An optional timing file gives the delay before each chunk
+//!   in the two-column format written by `script --timing`.
+//!
+//! Each frame rewrites only the rows that changed since the previous one,
+//! positioning the cursor explicitly, so recordings of mostly static
+//! scenes stay small. Cell colors are kept as 24-bit color escapes.
+//!
+//! Recording is independent of [`TerminalRenderer`](super::TerminalRenderer):
+//! record each grid right after rendering it, or record without a terminal
+//! at all to produce demos offline.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::render::recording::Recorder;
+//! use dotmax::BrailleGrid;
+//! use std::time::Duration;
+//!
+//! let mut recorder = Recorder::create("sweep.cast")?.title("sweep");
+//! for frame in 0..60 {
+//!     let mut grid = BrailleGrid::new(40, 10)?;
+//!     for y in 0..40 {
+//!         grid.set_dot(frame + y / 4, y)?;
+//!     }
+//!     recorder.record_at(&grid, Duration::from_millis(frame as u64 * 33))?;
+//! }
+//! recorder.finish()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::queue;
+use crossterm::style::ResetColor;
+use crossterm::terminal::{Clear, ClearType};
+
+use super::{write_row, RenderMode};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// The file format a [`Recorder`] writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// asciinema's asciicast v2: a JSON header line, then one JSON event
+    /// per frame
+    Asciicast,
+    /// Escape sequences only, with timing written separately if at all
+    Ansi,
+}
+
+impl RecordingFormat {
+    /// [`Asciicast`](Self::Asciicast) for a `.cast` path,
+    /// [`Ansi`](Self::Ansi) for anything else.
+    #[must_use]
+    pub fn for_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("cast") => Self::Asciicast,
+            _ => Self::Ansi,
+        }
+    }
+}
+
+/// Writes grid frames as a replayable terminal recording.
+///
+/// Call [`finish`](Self::finish) at the end; it restores the cursor and
+/// flushes the output.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    out: W,
+    timing: Option<W>,
+    format: RecordingFormat,
+    title: Option<String>,
+    size: Option<(usize, usize)>,
+    colors: bool,
+    started: Option<Instant>,
+    /// Time of the last chunk written
+    last: Duration,
+    /// Size of the last frame
+    dimensions: Option<(usize, usize)>,
+    /// Rows as last written, to skip unchanged ones
+    rows: Vec<Vec<u8>>,
+    frames: usize,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// A recorder writing to a new file at `path`, in the
+    /// [format that suits its extension](RecordingFormat::for_path).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be created.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        let file = File::create(path)?;
+        Ok(Self::new(
+            BufWriter::new(file),
+            RecordingFormat::for_path(path),
+        ))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// A recorder writing `format` to `out`, with colors.
+    pub const fn new(out: W, format: RecordingFormat) -> Self {
+        Self {
+            out,
+            timing: None,
+            format,
+            title: None,
+            size: None,
+            colors: true,
+            started: None,
+            last: Duration::ZERO,
+            dimensions: None,
+            rows: Vec::new(),
+            frames: 0,
+        }
+    }
+
+    /// A recorder writing an asciicast to `out`.
+    pub const fn asciicast(out: W) -> Self {
+        Self::new(out, RecordingFormat::Asciicast)
+    }
+
+    /// A recorder writing raw ANSI to `out`.
+    pub const fn ansi(out: W) -> Self {
+        Self::new(out, RecordingFormat::Ansi)
+    }
+
+    /// Sets the title in the asciicast header.
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the terminal size in the asciicast header, in cells. Defaults
+    /// to the size of the first frame; set it if later frames are larger.
+    #[must_use]
+    pub const fn size(mut self, width: usize, height: usize) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Whether to keep cell colors (default `true`).
+    #[must_use]
+    pub const fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Writes a line of timing to `timing` for each chunk of raw ANSI
+    /// output: the seconds since the previous chunk and the chunk's length
+    /// in bytes. Asciicasts carry their own timing and ignore this.
+    #[must_use]
+    pub fn timing(mut self, timing: W) -> Self {
+        self.timing = Some(timing);
+        self
+    }
+
+    /// Frames recorded so far.
+    #[must_use]
+    pub const fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Records `grid`, timed from the first frame recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if writing fails.
+    pub fn record(&mut self, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        self.record_at(grid, started.elapsed())
+    }
+
+    /// Records `grid` shown at `at` from the start of the recording, for
+    /// frames whose timing is known in advance. Times earlier than the
+    /// previous frame's are moved up to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if writing fails.
+    pub fn record_at(&mut self, grid: &BrailleGrid, at: Duration) -> Result<(), DotmaxError> {
+        let mut chunk = Vec::new();
+        let dimensions = grid.dimensions();
+        if self.dimensions != Some(dimensions) {
+            if self.dimensions.is_none() {
+                self.size.get_or_insert(dimensions);
+                self.write_header()?;
+                queue!(chunk, Hide)?;
+            }
+            queue!(chunk, Clear(ClearType::All))?;
+            self.dimensions = Some(dimensions);
+            self.rows.clear();
+        }
+        for y in 0..dimensions.1 {
+            let mut row = Vec::new();
+            write_row(&mut row, grid, y, self.colors, RenderMode::Braille)?;
+            if self.rows.get(y) != Some(&row) {
+                queue!(chunk, MoveTo(0, to_u16(y)))?;
+                chunk.extend_from_slice(&row);
+                if y < self.rows.len() {
+                    self.rows[y] = row;
+                } else {
+                    self.rows.push(row);
+                }
+            }
+        }
+        self.frames += 1;
+        self.write_chunk(&chunk, at)
+    }
+
+    /// Leaves the cursor below the last frame and visible again, flushes
+    /// the output, and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if writing fails.
+    pub fn finish(mut self) -> Result<W, DotmaxError> {
+        if self.frames == 0 {
+            self.write_header()?;
+        }
+        let mut chunk = Vec::new();
+        queue!(chunk, ResetColor, MoveTo(0, to_u16(self.rows.len())), Show)?;
+        self.write_chunk(&chunk, self.last)?;
+        if let Some(timing) = &mut self.timing {
+            timing.flush()?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Writes the asciicast header line; nothing for raw ANSI.
+    fn write_header(&mut self) -> Result<(), DotmaxError> {
+        if self.format != RecordingFormat::Asciicast {
+            return Ok(());
+        }
+        let (width, height) = self.size.unwrap_or((80, 24));
+        let mut header = format!(r#"{{"version": 2, "width": {width}, "height": {height}"#);
+        if let Some(title) = &self.title {
+            header.push_str(r#", "title": "#);
+            push_json_string(&mut header, title);
+        }
+        header.push('}');
+        writeln!(self.out, "{header}")?;
+        Ok(())
+    }
+
+    /// Writes `chunk` as shown at `at`, skipping empty chunks.
+    fn write_chunk(&mut self, chunk: &[u8], at: Duration) -> Result<(), DotmaxError> {
+        let at = at.max(self.last);
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        match self.format {
+            RecordingFormat::Asciicast => {
+                let mut event = format!("[{:.6}, \"o\", ", at.as_secs_f64());
+                push_json_string(&mut event, &String::from_utf8_lossy(chunk));
+                event.push(']');
+                writeln!(self.out, "{event}")?;
+            }
+            RecordingFormat::Ansi => {
+                self.out.write_all(chunk)?;
+                if let Some(timing) = &mut self.timing {
+                    let delay = at.saturating_sub(self.last).as_secs_f64();
+                    writeln!(timing, "{delay:.6} {}", chunk.len())?;
+                }
+            }
+        }
+        self.last = at;
+        Ok(())
+    }
+}
+
+/// Clamps a row or column index to what a cursor movement can address.
+fn to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+/// Appends `text` to `out` as a quoted JSON string.
+fn push_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(ch));
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+
+    fn frame(dots: &[(usize, usize)]) -> BrailleGrid {
+        let mut grid = BrailleGrid::new(4, 3).unwrap();
+        for &(x, y) in dots {
+            grid.set_dot(x, y).unwrap();
+        }
+        grid
+    }
+
+    #[test]
+    fn test_asciicast_events() {
+        let mut out = Vec::new();
+        let mut recorder = Recorder::asciicast(&mut out).title("a \"demo\"");
+        let mut first = frame(&[(0, 0)]);
+        first.set_cell_color(1, 2, Color::rgb(255, 0, 0)).unwrap();
+        recorder.record_at(&first, Duration::ZERO).unwrap();
+        // Only the second row changes
+        let mut second = first.clone();
+        second.set_dot(2, 4).unwrap();
+        recorder
+            .record_at(&second, Duration::from_millis(250))
+            .unwrap();
+        // Nothing changes, so no event
+        recorder
+            .record_at(&second, Duration::from_millis(500))
+            .unwrap();
+        assert_eq!(recorder.frames(), 3);
+        recorder.finish().unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"version": 2, "width": 4, "height": 3, "title": "a \"demo\""}"#
+        );
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with(r#"[0.000000, "o", "\u001b[?25l\u001b[2J\u001b[1;1H⠁"#));
+        assert!(lines[1].contains(r"\u001b[38;2;255;0;0m"));
+        assert!(lines[2].starts_with(r#"[0.250000, "o", "\u001b[2;1H"#));
+        assert!(!lines[2].contains("[1;1H"));
+        assert!(lines[3].starts_with("[0.250000, "));
+        assert!(lines[3].contains("\\u001b[?25h"));
+    }
+
+    #[test]
+    fn test_ansi_with_timing() {
+        let mut out = Vec::new();
+        let mut timing = Vec::new();
+        let mut recorder = Recorder::ansi(&mut out).colors(false).timing(&mut timing);
+        recorder
+            .record_at(&frame(&[]), Duration::from_secs(1))
+            .unwrap();
+        // A different size clears and redraws everything
+        let wide = BrailleGrid::new(6, 3).unwrap();
+        recorder
+            .record_at(&wide, Duration::from_millis(1500))
+            .unwrap();
+        recorder.finish().unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.matches("\x1b[2J").count(), 2);
+        assert!(text.contains(&format!("\x1b[3;1H{}", "\u{2800}".repeat(6))));
+        let timing = String::from_utf8(timing).unwrap();
+        let delays: Vec<&str> = timing
+            .lines()
+            .map(|line| line.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(delays, ["1.000000", "0.500000", "0.000000"]);
+        let bytes: usize = timing
+            .lines()
+            .map(|line| line.split(' ').nth(1).unwrap().parse::<usize>().unwrap())
+            .sum();
+        assert_eq!(bytes, text.len());
+        assert_eq!(
+            RecordingFormat::for_path("demo.CAST"),
+            RecordingFormat::Asciicast
+        );
+        assert_eq!(RecordingFormat::for_path("demo.ans"), RecordingFormat::Ansi);
+    }
+}
@@ -0,0 +1,383 @@
+//! Sixel graphics output.
+//!
+//! Sixel is a bitmap format that xterm (started with `-ti vt340`), mlterm,
+//! WezTerm, foot, and a few other terminals draw inline. Instead of one
+//! braille character per 2×4 dots, each dot becomes a solid block of
+//! pixels, so lines and images come out without the gaps between braille
+//! dots.
+//!
+//! [`encode_grid`] turns a [`BrailleGrid`] into a Sixel escape sequence,
+//! with each cell's color applied to its dots. Text characters in the grid
+//! have no pixels and are left out. With the `image` feature,
+//! [`encode_image`] encodes a full-color image directly.
+//!
+//! Most users don't call these directly: set
+//! [`RenderMode::Sixel`](super::RenderMode::Sixel) on a
+//! [`TerminalRenderer`](super::TerminalRenderer) and it falls back to
+//! braille on terminals without Sixel support.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::render::sixel::{encode_grid, SixelOptions};
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(4, 2)?;
+//! grid.set_dot(0, 0)?;
+//!
+//! let sixel = encode_grid(&grid, &SixelOptions::default());
+//! assert!(sixel.starts_with("\x1bP0;1;0q"));
+//! assert!(sixel.ends_with("\x1b\\"));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::OnceLock;
+
+use tracing::debug;
+
+use crate::grid::{BrailleGrid, Color};
+use crate::utils::cell_metrics::query_cell_pixel_size;
+
+/// Most colors a Sixel palette is guaranteed to hold.
+const MAX_PALETTE: usize = 256;
+
+/// How [`encode_grid`] turns dots into pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SixelOptions {
+    /// Pixels per dot, horizontally.
+    pub dot_width: u16,
+    /// Pixels per dot, vertically.
+    pub dot_height: u16,
+    /// Color of dots in cells without a color of their own.
+    pub foreground: Color,
+    /// Color of unset dots, or `None` to leave them transparent so whatever
+    /// was on screen shows through.
+    pub background: Option<Color>,
+}
+
+impl Default for SixelOptions {
+    /// 5×5-pixel dots (a 2×4-dot cell on a 10×20-pixel font), white on
+    /// transparent.
+    fn default() -> Self {
+        Self {
+            dot_width: 5,
+            dot_height: 5,
+            foreground: Color::white(),
+            background: None,
+        }
+    }
+}
+
+impl SixelOptions {
+    /// Dot sizes that make a grid cover the same area as it would in
+    /// braille, for a font whose cells are `cell_width × cell_height`
+    /// pixels.
+    #[must_use]
+    pub fn for_cell_size(cell_width: u16, cell_height: u16) -> Self {
+        Self {
+            dot_width: (cell_width / 2).max(1),
+            dot_height: (cell_height / 4).max(1),
+            ..Self::default()
+        }
+    }
+
+    /// [`for_cell_size`](Self::for_cell_size) with the cell size reported
+    /// by the terminal, or the defaults if it doesn't report pixel sizes.
+    #[must_use]
+    pub fn for_terminal() -> Self {
+        query_cell_pixel_size().map_or_else(Self::default, |cell| {
+            Self::for_cell_size(cell.width, cell.height)
+        })
+    }
+}
+
+/// Encodes a grid's dots as a Sixel image.
+///
+/// The image is `dot_width() × dot_width` by `dot_height() × dot_height`
+/// pixels. Grids with more than 256 distinct colors are reduced to a
+/// 6×6×6 color cube.
+#[must_use]
+pub fn encode_grid(grid: &BrailleGrid, options: &SixelOptions) -> String {
+    let (dot_w, dot_h) = (
+        usize::from(options.dot_width.max(1)),
+        usize::from(options.dot_height.max(1)),
+    );
+    let (width, height) = (grid.dot_width() * dot_w, grid.dot_height() * dot_h);
+
+    let dots: Vec<Option<Color>> = (0..grid.dot_height())
+        .flat_map(|dot_y| (0..grid.dot_width()).map(move |dot_x| (dot_x, dot_y)))
+        .map(|(dot_x, dot_y)| {
+            if grid.is_dot_set(dot_x, dot_y) {
+                Some(
+                    grid.get_color(dot_x / 2, dot_y / 4)
+                        .unwrap_or(options.foreground),
+                )
+            } else {
+                options.background
+            }
+        })
+        .collect();
+    let colors: Vec<Color> = dots.iter().flatten().copied().collect();
+    let (palette, lookup) = build_palette(&colors);
+
+    let mut lookup = lookup.into_iter();
+    let mut pixels = vec![None; width * height];
+    for (i, dot) in dots.iter().enumerate() {
+        let Some(index) = dot.and_then(|_| lookup.next()) else {
+            continue;
+        };
+        let (dot_x, dot_y) = (i % grid.dot_width(), i / grid.dot_width());
+        for y in dot_y * dot_h..(dot_y + 1) * dot_h {
+            let row = y * width;
+            pixels[row + dot_x * dot_w..row + (dot_x + 1) * dot_w].fill(Some(index));
+        }
+    }
+    encode(width, height, palette.as_slice(), &pixels)
+}
+
+/// Encodes an image as Sixel, reduced to a 6×6×6 color cube. Transparent
+/// pixels (alpha below 128) are left unpainted.
+#[cfg(feature = "image")]
+#[must_use]
+pub fn encode_image(image: &image::DynamicImage) -> String {
+    let rgba = image.to_rgba8();
+    let palette: Vec<Color> = (0..216).map(cube_color).collect();
+    let pixels: Vec<Option<u8>> = rgba
+        .pixels()
+        .map(|p| (p[3] >= 128).then(|| cube_index(Color::rgb(p[0], p[1], p[2]))))
+        .collect();
+    encode(
+        rgba.width() as usize,
+        rgba.height() as usize,
+        &palette,
+        &pixels,
+    )
+}
+
+/// Whether the terminal is known to draw Sixel graphics.
+///
+/// Set `DOTMAX_SIXEL=1` or `DOTMAX_SIXEL=0` to override. Otherwise this
+/// recognizes terminals by `$TERM` and `$TERM_PROGRAM`; it doesn't query
+/// the terminal, so xterm (which only supports Sixel in VT340 mode) is
+/// reported as unsupported. The result is cached for the process.
+pub fn detect_support() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        let var = |name| std::env::var(name).ok();
+        let supported = detect_support_with_env(
+            var("DOTMAX_SIXEL").as_deref(),
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+        );
+        debug!(supported, "Sixel support detected");
+        supported
+    })
+}
+
+/// [`detect_support`] with explicit environment values, uncached (for
+/// testing).
+#[must_use]
+pub fn detect_support_with_env(
+    dotmax_sixel: Option<&str>,
+    term: Option<&str>,
+    term_program: Option<&str>,
+) -> bool {
+    match dotmax_sixel.map(str::trim) {
+        Some("1" | "true" | "yes") => return true,
+        Some("0" | "false" | "no") => return false,
+        _ => {}
+    }
+    let term = term.unwrap_or_default().to_lowercase();
+    let program = term_program.unwrap_or_default().to_lowercase();
+    ["sixel", "mlterm", "foot", "contour", "yaft"]
+        .iter()
+        .any(|name| term.contains(name))
+        || ["wezterm", "mlterm", "contour"]
+            .iter()
+            .any(|name| program.contains(name))
+}
+
+/// Deduplicates `colors` into a palette of at most 256 entries, returning
+/// it with the palette index for each input color.
+fn build_palette(colors: &[Color]) -> (Vec<Color>, Vec<u8>) {
+    let mut palette = Vec::new();
+    let mut seen = HashMap::new();
+    let mut lookup = Vec::with_capacity(colors.len());
+    for &color in colors {
+        let index = *seen.entry(color).or_insert_with(|| {
+            palette.push(color);
+            palette.len() - 1
+        });
+        lookup.push(index);
+    }
+    if palette.len() <= MAX_PALETTE {
+        // Indices fit in a u8 by the check above
+        #[allow(clippy::cast_possible_truncation)]
+        let lookup = lookup.into_iter().map(|i| i as u8).collect();
+        return (palette, lookup);
+    }
+    debug!(
+        colors = palette.len(),
+        "Too many colors for a Sixel palette, using a color cube"
+    );
+    let lookup = colors.iter().map(|&c| cube_index(c)).collect();
+    ((0..216).map(cube_color).collect(), lookup)
+}
+
+/// Nearest entry in the 6×6×6 color cube.
+#[allow(clippy::cast_possible_truncation)]
+fn cube_index(color: Color) -> u8 {
+    let level = |c: u8| ((u16::from(c) * 5 + 127) / 255) as u8;
+    level(color.r) * 36 + level(color.g) * 6 + level(color.b)
+}
+
+/// Color of entry `index` in the 6×6×6 color cube.
+fn cube_color(index: u8) -> Color {
+    let level = |l: u8| l * 51;
+    Color::rgb(level(index / 36), level(index / 6 % 6), level(index % 6))
+}
+
+/// Writes the Sixel sequence for `pixels` (palette indices, row-major;
+/// `None` is left unpainted).
+fn encode(width: usize, height: usize, palette: &[Color], pixels: &[Option<u8>]) -> String {
+    // P2 = 1: pixels we don't paint keep their current color
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    let percent = |c: u8| (u32::from(c) * 100 + 127) / 255;
+    for (index, color) in palette.iter().enumerate() {
+        let _ = write!(
+            out,
+            "#{index};2;{};{};{}",
+            percent(color.r),
+            percent(color.g),
+            percent(color.b)
+        );
+    }
+
+    for band in (0..height).step_by(6) {
+        // One row of sixels per color used in this band
+        let mut rows: Vec<Option<Vec<u8>>> = vec![None; palette.len()];
+        for (bit, y) in (band..(band + 6).min(height)).enumerate() {
+            for (x, pixel) in pixels[y * width..(y + 1) * width].iter().enumerate() {
+                if let Some(color) = pixel {
+                    rows[usize::from(*color)].get_or_insert_with(|| vec![0; width])[x] |= 1 << bit;
+                }
+            }
+        }
+
+        let mut first = true;
+        for (color, sixels) in rows.iter().enumerate() {
+            let Some(sixels) = sixels else {
+                continue;
+            };
+            if !first {
+                // Back to the start of the band for the next color
+                out.push('$');
+            }
+            first = false;
+            let _ = write!(out, "#{color}");
+            push_run_length(&mut out, sixels);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Appends one band's sixels for one color, with `!count` repeats for runs
+/// and trailing blanks dropped.
+fn push_run_length(out: &mut String, bits: &[u8]) {
+    let end = bits.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    let mut i = 0;
+    while i < end {
+        let run = bits[i..end].iter().take_while(|&&b| b == bits[i]).count();
+        let sixel = char::from(63 + bits[i]);
+        if run > 3 {
+            let _ = write!(out, "!{run}{sixel}");
+        } else {
+            out.extend(std::iter::repeat(sixel).take(run));
+        }
+        i += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_dot_one_pixel() {
+        let mut grid = BrailleGrid::new(1, 1).unwrap();
+        grid.set_dot(1, 0).unwrap();
+        let options = SixelOptions {
+            dot_width: 1,
+            dot_height: 1,
+            ..SixelOptions::default()
+        };
+        let sixel = encode_grid(&grid, &options);
+
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;2;4#0;2;100;100;100"));
+        // Column 0 empty ('?'), column 1 with its top pixel ('@')
+        assert!(sixel.ends_with("#0?@-\x1b\\"), "{sixel:?}");
+    }
+
+    #[test]
+    fn test_run_length_and_colors() {
+        let mut grid = BrailleGrid::new(4, 1).unwrap();
+        for x in 0..8 {
+            grid.set_dot(x, 0).unwrap();
+        }
+        grid.set_cell_color(3, 0, Color::rgb(255, 0, 0)).unwrap();
+        let options = SixelOptions {
+            dot_width: 1,
+            dot_height: 1,
+            ..SixelOptions::default()
+        };
+        let sixel = encode_grid(&grid, &options);
+
+        assert!(sixel.contains("#1;2;100;0;0"));
+        // White dots in the first six columns, then red ones at columns 6-7
+        assert!(sixel.ends_with("#0!6@$#1!6?@@-\x1b\\"), "{sixel:?}");
+    }
+
+    #[test]
+    fn test_background_and_dot_size() {
+        let grid = BrailleGrid::new(1, 1).unwrap();
+        let options = SixelOptions {
+            dot_width: 2,
+            dot_height: 3,
+            foreground: Color::white(),
+            background: Some(Color::black()),
+        };
+        let sixel = encode_grid(&grid, &options);
+
+        assert!(sixel.contains("\"1;1;4;12#0;2;0;0;0"));
+        // Two bands of six full rows, four pixels wide
+        assert!(sixel.ends_with("#0!4~-#0!4~-\x1b\\"), "{sixel:?}");
+    }
+
+    #[test]
+    fn test_palette_overflow_uses_cube() {
+        let colors: Vec<Color> = (0..=255u8)
+            .flat_map(|r| [Color::rgb(r, 0, 0), Color::rgb(r, 1, 0)])
+            .collect();
+        let (palette, lookup) = build_palette(&colors);
+        assert_eq!(palette.len(), 216);
+        assert_eq!(lookup[0], 0);
+        assert_eq!(palette[usize::from(lookup[511])], Color::rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn test_detect_support_with_env() {
+        assert!(detect_support_with_env(None, Some("foot"), None));
+        assert!(detect_support_with_env(
+            None,
+            Some("xterm-256color"),
+            Some("WezTerm")
+        ));
+        assert!(!detect_support_with_env(None, Some("xterm-256color"), None));
+        assert!(detect_support_with_env(Some("1"), Some("xterm"), None));
+        assert!(!detect_support_with_env(Some("0"), Some("mlterm"), None));
+    }
+}
@@ -0,0 +1,297 @@
+//! Machine-readable statistics for each render.
+//!
+//! With an output configured, every [`TerminalRenderer::render`] call and
+//! every [`render_once_to_stdout`] call (including the piped output of the
+//! `quick` helpers) writes one line of JSON describing what it drew:
+//!
+//! ```json
+//! {"source":"terminal","width":80,"height":24,"dots_set":1532,"colored_cells":0,"colors_used":0,"bytes_emitted":6144,"timings_us":{"convert":210,"draw":640,"total":850}}
+//! ```
+//!
+//! Output goes to stderr or is appended to a file, one report per line, so
+//! CI scripts can assert on how much was drawn and how long it took. Set it
+//! in code with [`set_stats_output`], or from outside the program with the
+//! `DOTMAX_STATS` environment variable: `stderr` (or `-`) for stderr,
+//! anything else for a file path.
+//!
+//! Reporting never fails a render; a report that can't be written is logged
+//! and dropped.
+//!
+//! [`TerminalRenderer::render`]: super::TerminalRenderer::render
+//! [`render_once_to_stdout`]: super::render_once_to_stdout
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::render::stats::{set_stats_output, StatsOutput};
+//! use dotmax::{render_once_to_stdout, BrailleGrid};
+//!
+//! set_stats_output(Some(StatsOutput::File("render-stats.jsonl".into())));
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! grid.set_dot(0, 0)?;
+//! render_once_to_stdout(&grid)?; // appends a report to render-stats.jsonl
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::grid::BrailleGrid;
+
+/// Where render statistics are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsOutput {
+    /// One line per report on stderr
+    Stderr,
+    /// One line per report, appended to the file (created if missing)
+    File(PathBuf),
+}
+
+impl StatsOutput {
+    /// Reads a `DOTMAX_STATS` value: `stderr` or `-` for stderr, any other
+    /// non-empty value as a file path. Empty means no output.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "" => None,
+            "stderr" | "-" => Some(Self::Stderr),
+            path => Some(Self::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// The configured output, read from `DOTMAX_STATS` on first use.
+fn output() -> &'static Mutex<Option<StatsOutput>> {
+    static OUTPUT: OnceLock<Mutex<Option<StatsOutput>>> = OnceLock::new();
+    OUTPUT.get_or_init(|| {
+        let from_env = std::env::var("DOTMAX_STATS").ok();
+        Mutex::new(from_env.as_deref().and_then(StatsOutput::parse))
+    })
+}
+
+/// Send render statistics to `output` for the rest of the process, or turn
+/// them off with `None`. Overrides `DOTMAX_STATS`.
+pub fn set_stats_output(output_to: Option<StatsOutput>) {
+    *output().lock().unwrap_or_else(PoisonError::into_inner) = output_to;
+}
+
+/// Where render statistics currently go, if anywhere.
+#[must_use]
+pub fn stats_output() -> Option<StatsOutput> {
+    output()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// What one render drew, and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Which entry point rendered: `"terminal"` for
+    /// [`TerminalRenderer`](super::TerminalRenderer), `"stdout"` for
+    /// one-shot text output, `"writer"` for a
+    /// [`WriterBackend`](super::writer::WriterBackend).
+    pub source: &'static str,
+    /// Grid width in cells.
+    pub width: usize,
+    /// Grid height in cells.
+    pub height: usize,
+    /// Number of dots set.
+    pub dots_set: usize,
+    /// Number of cells with a color.
+    pub colored_cells: usize,
+    /// Number of distinct cell colors.
+    pub colors_used: usize,
+    /// Bytes written to the output, escape sequences included.
+    pub bytes_emitted: u64,
+    /// Time spent in each stage, in order.
+    pub stages: Vec<(&'static str, Duration)>,
+}
+
+impl RenderStats {
+    /// Statistics for `grid` with no output or timings yet.
+    #[must_use]
+    pub fn for_grid(source: &'static str, grid: &BrailleGrid) -> Self {
+        let dots_set = grid
+            .get_raw_patterns()
+            .iter()
+            .map(|pattern| pattern.count_ones() as usize)
+            .sum();
+        let mut colors = HashSet::new();
+        let mut colored_cells = 0;
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if let Some(color) = grid.get_color(x, y) {
+                    colored_cells += 1;
+                    colors.insert(color);
+                }
+            }
+        }
+        Self {
+            source,
+            width: grid.width(),
+            height: grid.height(),
+            dots_set,
+            colored_cells,
+            colors_used: colors.len(),
+            bytes_emitted: 0,
+            stages: Vec::new(),
+        }
+    }
+
+    /// The stage timings added up.
+    #[must_use]
+    pub fn total_time(&self) -> Duration {
+        self.stages.iter().map(|&(_, time)| time).sum()
+    }
+
+    /// The report as a single line of JSON, with timings in whole
+    /// microseconds.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"source\":\"{}\",\"width\":{},\"height\":{},\"dots_set\":{},\
+             \"colored_cells\":{},\"colors_used\":{},\"bytes_emitted\":{},\"timings_us\":{{",
+            self.source,
+            self.width,
+            self.height,
+            self.dots_set,
+            self.colored_cells,
+            self.colors_used,
+            self.bytes_emitted,
+        );
+        for (name, time) in &self.stages {
+            let _ = write!(json, "\"{name}\":{},", time.as_micros());
+        }
+        let _ = write!(json, "\"total\":{}}}}}", self.total_time().as_micros());
+        json
+    }
+}
+
+/// Whether reports are being written, so callers can skip measuring.
+pub(crate) fn enabled() -> bool {
+    output()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .is_some()
+}
+
+/// Writes `stats` to the configured output, if any.
+pub(crate) fn report(stats: &RenderStats) {
+    let Some(output) = stats_output() else {
+        return;
+    };
+    let line = stats.to_json() + "\n";
+    let result = match &output {
+        StatsOutput::Stderr => io::stderr().lock().write_all(line.as_bytes()),
+        StatsOutput::File(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes())),
+    };
+    if let Err(e) = result {
+        warn!(?output, "Could not write render stats: {e}");
+    }
+}
+
+/// A writer that counts the bytes passed through it. The count is shared,
+/// so it can still be read once the writer is owned by a terminal backend.
+pub(crate) struct CountingWriter<W> {
+    inner: W,
+    written: Arc<AtomicU64>,
+}
+
+impl<W> CountingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            written: Arc::default(),
+        }
+    }
+
+    /// A handle to the running byte count.
+    pub(crate) fn counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.written)
+    }
+
+    /// Bytes written so far.
+    pub(crate) fn written(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.written.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Color;
+
+    #[test]
+    fn test_stats_for_grid_and_json() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        grid.set_dot(7, 7).unwrap();
+        grid.set_cell_color(0, 0, Color::rgb(255, 0, 0)).unwrap();
+        grid.set_cell_color(1, 0, Color::rgb(255, 0, 0)).unwrap();
+        grid.set_cell_color(2, 1, Color::rgb(0, 0, 255)).unwrap();
+
+        let mut stats = RenderStats::for_grid("stdout", &grid);
+        assert_eq!(
+            (stats.dots_set, stats.colored_cells, stats.colors_used),
+            (2, 3, 2)
+        );
+
+        stats.bytes_emitted = 42;
+        stats.stages = vec![
+            ("convert", Duration::from_micros(150)),
+            ("draw", Duration::from_micros(1200)),
+        ];
+        assert_eq!(
+            stats.to_json(),
+            "{\"source\":\"stdout\",\"width\":4,\"height\":2,\"dots_set\":2,\
+             \"colored_cells\":3,\"colors_used\":2,\"bytes_emitted\":42,\
+             \"timings_us\":{\"convert\":150,\"draw\":1200,\"total\":1350}}"
+        );
+    }
+
+    #[test]
+    fn test_parse_output() {
+        assert_eq!(StatsOutput::parse(""), None);
+        assert_eq!(StatsOutput::parse("-"), Some(StatsOutput::Stderr));
+        assert_eq!(StatsOutput::parse("stderr"), Some(StatsOutput::Stderr));
+        assert_eq!(
+            StatsOutput::parse("out/stats.jsonl"),
+            Some(StatsOutput::File(PathBuf::from("out/stats.jsonl")))
+        );
+    }
+
+    #[test]
+    fn test_counting_writer() {
+        let mut writer = CountingWriter::new(Vec::new());
+        writer.write_all(b"\x1b[0m").unwrap();
+        write!(writer, "⠁").unwrap();
+        assert_eq!(writer.written(), 7);
+        assert_eq!(writer.inner.len(), 7);
+    }
+}
@@ -0,0 +1,290 @@
+//! Rendering to any [`Write`] instead of a terminal.
+//!
+//! [`TerminalRenderer`](super::TerminalRenderer) takes over stdout: it
+//! enters raw mode and the alternate screen and asks the terminal for its
+//! size. A [`WriterBackend`] does none of that. It writes the same escape
+//! sequences a terminal would be sent to whatever it's given, so frames can
+//! go to a file, a socket, a tmux pane opened as a file, or a `Vec<u8>` in
+//! a test, and a program can render with no terminal at all.
+//!
+//! Each frame is written whole: every row, with the cursor moved to its
+//! start, so any frame can be shown without the ones before it. The first
+//! frame, and any frame of a different size, hides the cursor and clears
+//! the screen first; [`finish`](WriterBackend::finish) shows the cursor
+//! again below the last frame.
+//!
+//! The screen size reported through [`TerminalBackend::size`] is whatever
+//! was set with [`screen_size`](WriterBackend::screen_size), since there's
+//! no terminal to ask. Grids are never cropped to it.
+//!
+//! For plain text with no cursor movement, one line per row, use
+//! [`render_to_writer`](super::render_to_writer) instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::render::writer::WriterBackend;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut backend = WriterBackend::new(Vec::new());
+//! let mut grid = BrailleGrid::new(10, 2)?;
+//! for x in 0..20 {
+//!     grid.set_dot(x, 3)?;
+//!     backend.draw(&grid)?;
+//! }
+//! let bytes = backend.finish()?;
+//! assert!(String::from_utf8(bytes).unwrap().contains('⣀'));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::io::Write;
+use std::time::Instant;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::queue;
+use crossterm::style::{Print, ResetColor};
+use crossterm::terminal::{Clear, ClearType};
+
+use super::sixel::{self, SixelOptions};
+use super::stats::{self, RenderStats};
+use super::{write_row, RenderMode, TerminalBackend, TerminalCapabilities, TerminalType};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// A [`TerminalBackend`] that writes ANSI output to any [`Write`].
+///
+/// Call [`finish`](Self::finish) at the end to restore the cursor and get
+/// the writer back.
+#[derive(Debug)]
+pub struct WriterBackend<W: Write> {
+    out: W,
+    screen_size: (u16, u16),
+    mode: RenderMode,
+    colors: bool,
+    sixel_options: SixelOptions,
+    /// Size of the last frame drawn, `None` before the first
+    dimensions: Option<(usize, usize)>,
+}
+
+impl<W: Write> WriterBackend<W> {
+    /// A backend writing braille with colors to `out`, reporting an 80×24
+    /// screen.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            screen_size: (80, 24),
+            mode: RenderMode::Braille,
+            colors: true,
+            sixel_options: SixelOptions::default(),
+            dimensions: None,
+        }
+    }
+
+    /// Sets the screen size [`TerminalBackend::size`] reports, in cells.
+    #[must_use]
+    pub const fn screen_size(mut self, width: u16, height: u16) -> Self {
+        self.screen_size = (width, height);
+        self
+    }
+
+    /// Sets how grids are drawn. Every mode is written as asked, since the
+    /// reader's capabilities are unknown; [`RenderMode::Sixel`] writes one
+    /// image per frame.
+    #[must_use]
+    pub const fn mode(mut self, mode: RenderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Whether to keep cell colors (default `true`).
+    #[must_use]
+    pub const fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Pixel size and colors of dots in Sixel mode.
+    #[must_use]
+    pub const fn sixel_options(mut self, options: SixelOptions) -> Self {
+        self.sixel_options = options;
+        self
+    }
+
+    /// The writer, for inspecting what was written so far.
+    pub const fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    /// Writes `grid` as one complete frame at the top-left of the screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if writing fails.
+    pub fn draw(&mut self, grid: &BrailleGrid) -> Result<(), DotmaxError> {
+        let started = Instant::now();
+        let mut frame = Vec::new();
+        self.start_frame(&mut frame, grid.dimensions())?;
+        if self.mode == RenderMode::Sixel {
+            queue!(frame, MoveTo(0, 0))?;
+            frame.extend_from_slice(sixel::encode_grid(grid, &self.sixel_options).as_bytes());
+        } else {
+            for y in 0..grid.height() {
+                queue!(frame, MoveTo(0, to_u16(y)))?;
+                write_row(&mut frame, grid, y, self.colors, self.mode)?;
+            }
+        }
+        self.out.write_all(&frame)?;
+        self.out.flush()?;
+        if stats::enabled() {
+            let mut report = RenderStats::for_grid("writer", grid);
+            report.bytes_emitted = frame.len() as u64;
+            report.stages = vec![("write", started.elapsed())];
+            stats::report(&report);
+        }
+        Ok(())
+    }
+
+    /// Leaves the cursor visible at the start of the line below the last
+    /// frame, flushes the output, and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if writing fails.
+    pub fn finish(mut self) -> Result<W, DotmaxError> {
+        if let Some((_, height)) = self.dimensions {
+            queue!(self.out, ResetColor, MoveTo(0, to_u16(height)), Show)?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Hides the cursor and clears the screen before the first frame and
+    /// whenever the frame size changes.
+    fn start_frame(
+        &mut self,
+        frame: &mut Vec<u8>,
+        dimensions: (usize, usize),
+    ) -> Result<(), DotmaxError> {
+        if self.dimensions != Some(dimensions) {
+            if self.dimensions.is_none() {
+                queue!(frame, Hide)?;
+            }
+            queue!(frame, Clear(ClearType::All))?;
+            self.dimensions = Some(dimensions);
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> TerminalBackend for WriterBackend<W> {
+    fn size(&self) -> Result<(u16, u16), DotmaxError> {
+        Ok(self.screen_size)
+    }
+
+    /// Writes `content` as a frame, one line of it per row.
+    fn render(&mut self, content: &str) -> Result<(), DotmaxError> {
+        let lines: Vec<&str> = content.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max();
+        let mut frame = Vec::new();
+        self.start_frame(&mut frame, (width.unwrap_or(0), lines.len()))?;
+        for (y, line) in lines.into_iter().enumerate() {
+            queue!(frame, MoveTo(0, to_u16(y)), Print(line))?;
+        }
+        self.out.write_all(&frame)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), DotmaxError> {
+        queue!(self.out, Clear(ClearType::All), MoveTo(0, 0))?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// What the configured mode and colors need, as there's no terminal to
+    /// detect them from.
+    fn capabilities(&self) -> TerminalCapabilities {
+        TerminalCapabilities {
+            supports_color: self.colors,
+            supports_truecolor: self.colors,
+            supports_unicode: self.mode != RenderMode::Ascii,
+            terminal_type: TerminalType::Unknown,
+            supports_sixel: self.mode == RenderMode::Sixel,
+        }
+    }
+}
+
+/// Clamps a row index to what a cursor movement can address.
+fn to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn text(bytes: &[u8]) -> String {
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_every_frame_is_complete() {
+        let mut backend = WriterBackend::new(Vec::new());
+        let mut grid = BrailleGrid::new(3, 2).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        backend.draw(&grid).unwrap();
+        let first = backend.get_ref().len();
+        assert!(text(backend.get_ref()).starts_with("\x1b[?25l\x1b[2J\x1b[1;1H⠁⠀⠀\x1b[2;1H"));
+
+        // Unchanged frames are still written in full, without the clear
+        backend.draw(&grid).unwrap();
+        let second = text(&backend.get_ref()[first..]);
+        assert_eq!(second, "\x1b[1;1H⠁⠀⠀\x1b[2;1H⠀⠀⠀");
+
+        // A new size clears again
+        backend.draw(&BrailleGrid::new(2, 1).unwrap()).unwrap();
+        let out = text(&backend.finish().unwrap());
+        assert!(out.ends_with("\x1b[2J\x1b[1;1H⠀⠀\x1b[0m\x1b[2;1H\x1b[?25h"));
+    }
+
+    #[test]
+    fn test_modes_and_colors() {
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        grid.set_cell_color(0, 0, Color::rgb(255, 0, 0)).unwrap();
+
+        let mut plain = WriterBackend::new(Vec::new())
+            .colors(false)
+            .mode(RenderMode::Ascii);
+        plain.draw(&grid).unwrap();
+        let out = text(&plain.finish().unwrap());
+        assert!(!out.contains("38;2"));
+        assert!(!out.contains('⠁'));
+
+        let mut colored = WriterBackend::new(Vec::new());
+        colored.draw(&grid).unwrap();
+        assert!(text(colored.get_ref()).contains("\x1b[38;2;255;0;0m⠁"));
+    }
+
+    #[test]
+    fn test_backend_trait() {
+        let mut backend = WriterBackend::new(Vec::new()).screen_size(120, 40);
+        assert_eq!(backend.size().unwrap(), (120, 40));
+        assert!(!backend.capabilities().supports_sixel);
+
+        backend.render("ab\ncd").unwrap();
+        backend.clear().unwrap();
+        let out = text(&backend.finish().unwrap());
+        assert_eq!(
+            out,
+            "\x1b[?25l\x1b[2J\x1b[1;1Hab\x1b[2;1Hcd\x1b[2J\x1b[1;1H\x1b[0m\x1b[3;1H\x1b[?25h"
+        );
+    }
+
+    #[test]
+    fn test_finish_without_frames_writes_nothing() {
+        let backend = WriterBackend::new(Vec::new());
+        assert!(backend.finish().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! Easing curves for scene animations.
+//!
+//! An easing curve maps normalized animation progress (`0.0..=1.0`) to an
+//! interpolation factor. Names follow the CSS convention so scene files read
+//! naturally: `linear`, `ease-in`, `ease-out`, `ease-in-out`, and the
+//! `-cubic` variants for a stronger acceleration.
+
+use serde::{Deserialize, Serialize};
+
+/// Interpolation curve applied to a [`Tween`](super::Tween).
+///
+/// The quadratic curves (`EaseIn`, `EaseOut`, `EaseInOut`) are gentle and suit
+/// most UI-style motion; the cubic curves accelerate harder.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::scene::Easing;
+///
+/// assert_eq!(Easing::Linear.apply(0.25), 0.25);
+/// assert!(Easing::EaseIn.apply(0.5) < 0.5);
+/// assert!(Easing::EaseOut.apply(0.5) > 0.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Easing {
+    /// Constant speed
+    #[default]
+    Linear,
+    /// Quadratic acceleration from zero velocity
+    EaseIn,
+    /// Quadratic deceleration to zero velocity
+    EaseOut,
+    /// Quadratic acceleration until halfway, then deceleration
+    EaseInOut,
+    /// Cubic acceleration from zero velocity
+    EaseInCubic,
+    /// Cubic deceleration to zero velocity
+    EaseOutCubic,
+    /// Cubic acceleration until halfway, then deceleration
+    EaseInOutCubic,
+}
+
+impl Easing {
+    /// Map progress `t` to an interpolation factor.
+    ///
+    /// `t` is clamped to `0.0..=1.0`; every curve returns exactly `0.0` at the
+    /// start and `1.0` at the end.
+    #[must_use]
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0f32).mul_add(t, 2.0).powi(2) / 2.0
+                }
+            }
+            Self::EaseInCubic => t * t * t,
+            Self::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0f32).mul_add(t, 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 7] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::EaseInCubic,
+        Easing::EaseOutCubic,
+        Easing::EaseInOutCubic,
+    ];
+
+    #[test]
+    fn test_endpoints_are_exact() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < f32::EPSILON, "{easing:?} at 0");
+            assert!(
+                (easing.apply(1.0) - 1.0).abs() < f32::EPSILON,
+                "{easing:?} at 1"
+            );
+        }
+    }
+
+    #[test]
+    fn test_progress_is_clamped() {
+        for easing in ALL {
+            assert!(easing.apply(-1.0).abs() < f32::EPSILON);
+            assert!((easing.apply(2.0) - 1.0).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_curves_are_monotonic() {
+        for easing in ALL {
+            let mut prev = 0.0;
+            for i in 1..=100 {
+                let v = easing.apply(i as f32 / 100.0);
+                assert!(v >= prev, "{easing:?} decreased at step {i}");
+                prev = v;
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_out_symmetry() {
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!((Easing::EaseInOutCubic.apply(0.5) - 0.5).abs() < 1e-6);
+    }
+}
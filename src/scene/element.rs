@@ -0,0 +1,727 @@
+//! Scene elements, colors, and property tweens.
+//!
+//! Shapes (`line`, `rect`, `circle`, `polygon`) are positioned in **dot**
+//! coordinates, exactly like the functions in [`crate::primitives`]. Text and
+//! images are positioned in **cell** coordinates because they occupy whole
+//! terminal cells.
+
+use super::easing::Easing;
+use crate::grid::{BrailleGrid, Color};
+use crate::primitives::{
+    draw_circle, draw_circle_filled, draw_line, draw_polygon, draw_polygon_filled, draw_rectangle,
+    draw_rectangle_filled,
+};
+use crate::DotmaxError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "image")]
+use std::path::PathBuf;
+#[cfg(feature = "image")]
+use std::sync::OnceLock;
+
+// ============================================================================
+// SceneColor - "#rrggbb", "#rgb", a color name, or [r, g, b]
+// ============================================================================
+
+/// A color as written in a scene file.
+///
+/// Accepted forms:
+/// - Hex strings: `"#ff8800"` or the short form `"#f80"`
+/// - Names: `black`, `white`, `red`, `green`, `blue`, `yellow`, `cyan`,
+///   `magenta`, `gray`/`grey`, `orange`
+/// - RGB arrays: `[255, 136, 0]`
+///
+/// Serializes back to the `"#rrggbb"` form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneColor(pub Color);
+
+impl SceneColor {
+    /// Parse a hex string or color name.
+    ///
+    /// Returns `None` if the string is not a recognized color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::{scene::SceneColor, Color};
+    ///
+    /// assert_eq!(SceneColor::parse("#ff0000"), Some(SceneColor(Color::rgb(255, 0, 0))));
+    /// assert_eq!(SceneColor::parse("#0f0"), Some(SceneColor(Color::rgb(0, 255, 0))));
+    /// assert_eq!(SceneColor::parse("Cyan"), Some(SceneColor(Color::rgb(0, 255, 255))));
+    /// assert_eq!(SceneColor::parse("nope"), None);
+    /// ```
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some(hex) = text.strip_prefix('#') {
+            return parse_hex(hex).map(Self);
+        }
+        let color = match text.to_lowercase().as_str() {
+            "black" => Color::black(),
+            "white" => Color::white(),
+            "red" => Color::rgb(255, 0, 0),
+            "green" => Color::rgb(0, 255, 0),
+            "blue" => Color::rgb(0, 0, 255),
+            "yellow" => Color::rgb(255, 255, 0),
+            "cyan" => Color::rgb(0, 255, 255),
+            "magenta" => Color::rgb(255, 0, 255),
+            "gray" | "grey" => Color::rgb(128, 128, 128),
+            "orange" => Color::rgb(255, 165, 0),
+            _ => return None,
+        };
+        Some(Self(color))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        6 => Some(Color::rgb(
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => {
+            // "#f80" expands each digit: f -> ff, 8 -> 88, 0 -> 00
+            let expand = |s: &str| channel(s).map(|v| v * 17);
+            Some(Color::rgb(
+                expand(&hex[0..1])?,
+                expand(&hex[1..2])?,
+                expand(&hex[2..3])?,
+            ))
+        }
+        _ => None,
+    }
+}
+
+impl Serialize for SceneColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Color { r, g, b } = self.0;
+        serializer.serialize_str(&format!("#{r:02x}{g:02x}{b:02x}"))
+    }
+}
+
+impl<'de> Deserialize<'de> for SceneColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Rgb([u8; 3]),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => Self::parse(&text).ok_or_else(|| {
+                serde::de::Error::custom(format!(
+                    "invalid color '{text}' (expected \"#rrggbb\", \"#rgb\", a color name, or [r, g, b])"
+                ))
+            }),
+            Repr::Rgb([r, g, b]) => Ok(Self(Color::rgb(r, g, b))),
+        }
+    }
+}
+
+// ============================================================================
+// Tween - animates one numeric property over time
+// ============================================================================
+
+/// Animates one numeric property of an element from `from` to `to`.
+///
+/// The tween starts `start` seconds into the scene and runs for `duration`
+/// seconds. Before it starts the element keeps its declared value; once it has
+/// finished the property holds `to`. When several tweens target the same
+/// property, later entries override earlier ones once they have started.
+///
+/// ```toml
+/// [[element.animate]]
+/// property = "radius"
+/// from = 4
+/// to = 20
+/// start = 0.5
+/// duration = 1.5
+/// easing = "ease-out"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tween {
+    /// Name of the animated property (see [`Shape::property_names`])
+    pub property: String,
+    /// Value at the start of the tween
+    pub from: f32,
+    /// Value at the end of the tween
+    pub to: f32,
+    /// Start time in seconds (default 0)
+    #[serde(default)]
+    pub start: f32,
+    /// Length in seconds; 0 jumps straight to `to`
+    pub duration: f32,
+    /// Interpolation curve (default `linear`)
+    #[serde(default)]
+    pub easing: Easing,
+}
+
+impl Tween {
+    /// Value of the tween at scene time `t`, or `None` if it hasn't started.
+    #[must_use]
+    pub fn value_at(&self, t: f32) -> Option<f32> {
+        if t < self.start {
+            return None;
+        }
+        let progress = if self.duration > 0.0 {
+            (t - self.start) / self.duration
+        } else {
+            1.0
+        };
+        let factor = self.easing.apply(progress);
+        Some((self.to - self.from).mul_add(factor, self.from))
+    }
+}
+
+// ============================================================================
+// Shape - what an element draws
+// ============================================================================
+
+/// Geometry of a scene element, selected by the `type` key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Shape {
+    /// Line segment between two dot positions
+    Line {
+        /// Start X in dots
+        x1: f32,
+        /// Start Y in dots
+        y1: f32,
+        /// End X in dots
+        x2: f32,
+        /// End Y in dots
+        y2: f32,
+    },
+    /// Axis-aligned rectangle with its top-left corner at (`x`, `y`)
+    Rect {
+        /// Left edge in dots
+        x: f32,
+        /// Top edge in dots
+        y: f32,
+        /// Width in dots
+        width: f32,
+        /// Height in dots
+        height: f32,
+        /// Fill the interior instead of drawing the outline
+        #[serde(default)]
+        filled: bool,
+    },
+    /// Circle centered at (`x`, `y`)
+    Circle {
+        /// Center X in dots
+        x: f32,
+        /// Center Y in dots
+        y: f32,
+        /// Radius in dots
+        radius: f32,
+        /// Fill the interior instead of drawing the outline
+        #[serde(default)]
+        filled: bool,
+    },
+    /// Closed polygon; `x`/`y` translate every point
+    Polygon {
+        /// Vertices in dots, e.g. `[[0, 0], [10, 0], [5, 8]]`
+        points: Vec<[f32; 2]>,
+        /// Horizontal offset in dots (default 0)
+        #[serde(default)]
+        x: f32,
+        /// Vertical offset in dots (default 0)
+        #[serde(default)]
+        y: f32,
+        /// Fill the interior instead of drawing the outline
+        #[serde(default)]
+        filled: bool,
+    },
+    /// Text written one character per cell starting at cell (`x`, `y`)
+    Text {
+        /// Column in cells
+        x: f32,
+        /// Row in cells
+        y: f32,
+        /// The text to write (no wrapping; clipped at the grid edge)
+        text: String,
+    },
+    /// Image file rendered into a `width`×`height` cell box at cell (`x`, `y`)
+    ///
+    /// Relative paths are resolved against the scene file's directory when the
+    /// scene is loaded with [`Scene::load`](super::Scene::load).
+    #[cfg(feature = "image")]
+    Image {
+        /// Path to the image file
+        path: PathBuf,
+        /// Column in cells
+        x: f32,
+        /// Row in cells
+        y: f32,
+        /// Box width in cells (aspect ratio is preserved)
+        width: usize,
+        /// Box height in cells (aspect ratio is preserved)
+        height: usize,
+    },
+}
+
+impl Shape {
+    /// Names of the properties that can be animated with a [`Tween`].
+    #[must_use]
+    pub const fn property_names(&self) -> &'static [&'static str] {
+        match self {
+            Self::Line { .. } => &["x1", "y1", "x2", "y2"],
+            Self::Rect { .. } => &["x", "y", "width", "height"],
+            Self::Circle { .. } => &["x", "y", "radius"],
+            Self::Polygon { .. } | Self::Text { .. } => &["x", "y"],
+            #[cfg(feature = "image")]
+            Self::Image { .. } => &["x", "y"],
+        }
+    }
+
+    fn property_mut(&mut self, name: &str) -> Option<&mut f32> {
+        match (self, name) {
+            (Self::Line { x1, .. }, "x1") => Some(x1),
+            (Self::Line { y1, .. }, "y1") => Some(y1),
+            (Self::Line { x2, .. }, "x2") => Some(x2),
+            (Self::Line { y2, .. }, "y2") => Some(y2),
+            (Self::Rect { width, .. }, "width") => Some(width),
+            (Self::Rect { height, .. }, "height") => Some(height),
+            (Self::Circle { radius, .. }, "radius") => Some(radius),
+            (
+                Self::Rect { x, .. }
+                | Self::Circle { x, .. }
+                | Self::Polygon { x, .. }
+                | Self::Text { x, .. },
+                "x",
+            ) => Some(x),
+            (
+                Self::Rect { y, .. }
+                | Self::Circle { y, .. }
+                | Self::Polygon { y, .. }
+                | Self::Text { y, .. },
+                "y",
+            ) => Some(y),
+            #[cfg(feature = "image")]
+            (Self::Image { x, .. }, "x") => Some(x),
+            #[cfg(feature = "image")]
+            (Self::Image { y, .. }, "y") => Some(y),
+            _ => None,
+        }
+    }
+
+    const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Line { .. } => "line",
+            Self::Rect { .. } => "rect",
+            Self::Circle { .. } => "circle",
+            Self::Polygon { .. } => "polygon",
+            Self::Text { .. } => "text",
+            #[cfg(feature = "image")]
+            Self::Image { .. } => "image",
+        }
+    }
+}
+
+// ============================================================================
+// Element - shape + color + animations
+// ============================================================================
+
+/// One drawable item in a [`Scene`](super::Scene).
+///
+/// In TOML each element is an `[[element]]` table whose `type` key selects the
+/// [`Shape`]; the shape's fields sit alongside `color` and any `animate` tweens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Element {
+    /// What to draw
+    #[serde(flatten)]
+    pub shape: Shape,
+    /// Optional color applied to every cell the element touches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<SceneColor>,
+    /// Property animations, applied in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub animate: Vec<Tween>,
+    /// Rendered image, loaded on first draw
+    #[cfg(feature = "image")]
+    #[serde(skip)]
+    image_cache: OnceLock<BrailleGrid>,
+}
+
+impl Element {
+    /// Create an element with no color and no animations.
+    #[must_use]
+    pub const fn new(shape: Shape) -> Self {
+        Self {
+            shape,
+            color: None,
+            animate: Vec::new(),
+            #[cfg(feature = "image")]
+            image_cache: OnceLock::new(),
+        }
+    }
+
+    /// The element's shape with all tweens applied at scene time `t`.
+    #[must_use]
+    pub fn shape_at(&self, t: f32) -> Shape {
+        let mut shape = self.shape.clone();
+        for tween in &self.animate {
+            if let Some(value) = tween.value_at(t) {
+                if let Some(slot) = shape.property_mut(&tween.property) {
+                    *slot = value;
+                }
+            }
+        }
+        shape
+    }
+
+    pub(crate) fn validate(&self, index: usize) -> Result<(), DotmaxError> {
+        if let Shape::Polygon { points, filled, .. } = &self.shape {
+            let min = if *filled { 3 } else { 2 };
+            if points.len() < min {
+                return Err(DotmaxError::InvalidPolygon {
+                    reason: format!(
+                        "element {index}: polygon requires ≥{min} points, got {}",
+                        points.len()
+                    ),
+                });
+            }
+        }
+
+        let allowed = self.shape.property_names();
+        for tween in &self.animate {
+            if !allowed.contains(&tween.property.as_str()) {
+                return Err(DotmaxError::SceneError(format!(
+                    "element {index}: cannot animate '{}' on a {} (animatable: {})",
+                    tween.property,
+                    self.shape.type_name(),
+                    allowed.join(", ")
+                )));
+            }
+            if !tween.duration.is_finite() || tween.duration < 0.0 || !tween.start.is_finite() {
+                return Err(DotmaxError::SceneError(format!(
+                    "element {index}: tween for '{}' needs a finite start and a duration ≥ 0",
+                    tween.property
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "image")]
+    pub(crate) fn resolve_paths(&mut self, base: &std::path::Path) {
+        if let Shape::Image { path, .. } = &mut self.shape {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+    }
+
+    /// Draw the element at scene time `t`.
+    ///
+    /// `scratch` must have the same dimensions as `grid`; shapes are drawn
+    /// there first so the element color can be applied to exactly the cells
+    /// the shape touched.
+    pub(crate) fn draw(
+        &self,
+        grid: &mut BrailleGrid,
+        scratch: &mut BrailleGrid,
+        t: f32,
+    ) -> Result<(), DotmaxError> {
+        let color = self.color.map(|c| c.0);
+        match self.shape_at(t) {
+            Shape::Text { x, y, text } => {
+                draw_text(grid, round(x), round(y), &text, color);
+                Ok(())
+            }
+            #[cfg(feature = "image")]
+            Shape::Image {
+                path,
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let rendered = self.rendered_image(&path, width, height)?;
+                composite(rendered, grid, round(x), round(y), color);
+                Ok(())
+            }
+            shape => {
+                scratch.clear();
+                draw_shape(scratch, &shape)?;
+                composite(scratch, grid, 0, 0, color);
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(feature = "image")]
+    fn rendered_image(
+        &self,
+        path: &std::path::Path,
+        width: usize,
+        height: usize,
+    ) -> Result<&BrailleGrid, DotmaxError> {
+        if let Some(grid) = self.image_cache.get() {
+            return Ok(grid);
+        }
+        let grid = crate::image::ImageRenderer::new()
+            .load_from_path(path)?
+            .resize(width, height, true)?
+            .render()?;
+        Ok(self.image_cache.get_or_init(|| grid))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn round(value: f32) -> i32 {
+    value.round() as i32
+}
+
+#[allow(clippy::cast_sign_loss)]
+fn round_len(value: f32) -> u32 {
+    round(value).max(0) as u32
+}
+
+fn draw_shape(grid: &mut BrailleGrid, shape: &Shape) -> Result<(), DotmaxError> {
+    match *shape {
+        Shape::Line { x1, y1, x2, y2 } => {
+            draw_line(grid, round(x1), round(y1), round(x2), round(y2))
+        }
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            filled,
+        } => {
+            let (w, h) = (round_len(width), round_len(height));
+            // A tween may shrink a rectangle to nothing; that's not an error here
+            if w == 0 || h == 0 {
+                return Ok(());
+            }
+            if filled {
+                draw_rectangle_filled(grid, round(x), round(y), w, h)
+            } else {
+                draw_rectangle(grid, round(x), round(y), w, h)
+            }
+        }
+        Shape::Circle {
+            x,
+            y,
+            radius,
+            filled,
+        } => {
+            if filled {
+                draw_circle_filled(grid, round(x), round(y), round_len(radius))
+            } else {
+                draw_circle(grid, round(x), round(y), round_len(radius))
+            }
+        }
+        Shape::Polygon {
+            ref points,
+            x,
+            y,
+            filled,
+        } => {
+            let vertices: Vec<(i32, i32)> = points
+                .iter()
+                .map(|[px, py]| (round(px + x), round(py + y)))
+                .collect();
+            if filled {
+                draw_polygon_filled(grid, &vertices)
+            } else {
+                draw_polygon(grid, &vertices)
+            }
+        }
+        // Text and images are cell-based and handled by `Element::draw`
+        Shape::Text { .. } => Ok(()),
+        #[cfg(feature = "image")]
+        Shape::Image { .. } => Ok(()),
+    }
+}
+
+/// Write `text` one character per cell, clipping at the grid edges.
+fn draw_text(grid: &mut BrailleGrid, x: i32, y: i32, text: &str, color: Option<Color>) {
+    let Ok(row) = usize::try_from(y) else {
+        return;
+    };
+    if row >= grid.height() {
+        return;
+    }
+    for (offset, ch) in text.chars().enumerate() {
+        let Some(col) = i64::from(x)
+            .checked_add(offset as i64)
+            .and_then(|c| usize::try_from(c).ok())
+        else {
+            continue;
+        };
+        if col >= grid.width() {
+            break;
+        }
+        // Bounds were checked above, so these cannot fail
+        let _ = grid.set_char(col, row, ch);
+        if let Some(color) = color {
+            let _ = grid.set_cell_color(col, row, color);
+        }
+    }
+}
+
+/// OR the non-empty cells of `src` into `dst` at a cell offset.
+///
+/// Cells take `color` if given, otherwise whatever color `src` carried.
+fn composite(
+    src: &BrailleGrid,
+    dst: &mut BrailleGrid,
+    offset_x: i32,
+    offset_y: i32,
+    color: Option<Color>,
+) {
+    let (src_w, dst_w, dst_h) = (src.width(), dst.width(), dst.height());
+    let src_patterns = src.get_raw_patterns();
+    let mut patterns = dst.get_raw_patterns().to_vec();
+    let mut colored = Vec::new();
+
+    for (index, &pattern) in src_patterns.iter().enumerate() {
+        if pattern == 0 {
+            continue;
+        }
+        let (sx, sy) = (index % src_w, index / src_w);
+        let (Ok(dx), Ok(dy)) = (
+            usize::try_from(sx as i64 + i64::from(offset_x)),
+            usize::try_from(sy as i64 + i64::from(offset_y)),
+        ) else {
+            continue;
+        };
+        if dx >= dst_w || dy >= dst_h {
+            continue;
+        }
+        patterns[dy * dst_w + dx] |= pattern;
+        if let Some(c) = color.or_else(|| src.get_color(sx, sy)) {
+            colored.push((dx, dy, c));
+        }
+    }
+
+    dst.set_raw_patterns(&patterns);
+    for (x, y, c) in colored {
+        // In bounds by construction
+        let _ = dst.set_cell_color(x, y, c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scene_color_parse_forms() {
+        assert_eq!(
+            SceneColor::parse("#102030").map(|c| c.0),
+            Some(Color::rgb(0x10, 0x20, 0x30))
+        );
+        assert_eq!(SceneColor::parse("#fff").map(|c| c.0), Some(Color::white()));
+        assert_eq!(
+            SceneColor::parse("GREY").map(|c| c.0),
+            Some(Color::rgb(128, 128, 128))
+        );
+        assert!(SceneColor::parse("#12345").is_none());
+        assert!(SceneColor::parse("#gggggg").is_none());
+    }
+
+    #[test]
+    fn test_tween_holds_until_start_and_after_end() {
+        let tween = Tween {
+            property: "x".into(),
+            from: 0.0,
+            to: 10.0,
+            start: 1.0,
+            duration: 2.0,
+            easing: Easing::Linear,
+        };
+        assert_eq!(tween.value_at(0.5), None);
+        assert_eq!(tween.value_at(1.0), Some(0.0));
+        assert_eq!(tween.value_at(2.0), Some(5.0));
+        assert_eq!(tween.value_at(10.0), Some(10.0));
+    }
+
+    #[test]
+    fn test_zero_duration_tween_jumps_to_end() {
+        let tween = Tween {
+            property: "x".into(),
+            from: 0.0,
+            to: 7.0,
+            start: 0.0,
+            duration: 0.0,
+            easing: Easing::EaseIn,
+        };
+        assert_eq!(tween.value_at(0.0), Some(7.0));
+    }
+
+    #[test]
+    fn test_shape_at_applies_tweens_in_order() {
+        let mut element = Element::new(Shape::Circle {
+            x: 5.0,
+            y: 5.0,
+            radius: 1.0,
+            filled: false,
+        });
+        for (from, to, start) in [(1.0, 4.0, 0.0), (10.0, 20.0, 1.0)] {
+            element.animate.push(Tween {
+                property: "radius".into(),
+                from,
+                to,
+                start,
+                duration: 1.0,
+                easing: Easing::Linear,
+            });
+        }
+        let radius = |t| match element.shape_at(t) {
+            Shape::Circle { radius, .. } => radius,
+            _ => unreachable!(),
+        };
+        assert!((radius(0.5) - 2.5).abs() < 1e-6);
+        assert!((radius(1.5) - 15.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_property() {
+        let mut element = Element::new(Shape::Text {
+            x: 0.0,
+            y: 0.0,
+            text: "hi".into(),
+        });
+        element.animate.push(Tween {
+            property: "radius".into(),
+            from: 0.0,
+            to: 1.0,
+            start: 0.0,
+            duration: 1.0,
+            easing: Easing::Linear,
+        });
+        let err = element.validate(3).unwrap_err();
+        assert!(matches!(err, DotmaxError::SceneError(ref m) if m.contains("element 3")));
+    }
+
+    #[test]
+    fn test_text_is_clipped_and_colored() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        let red = Color::rgb(255, 0, 0);
+        draw_text(&mut grid, -1, 1, "abcdef", Some(red));
+        assert_eq!(grid.get_char(0, 1), 'b');
+        assert_eq!(grid.get_char(3, 1), 'e');
+        assert_eq!(grid.get_color(0, 1), Some(red));
+        draw_text(&mut grid, 0, 5, "off-grid", None);
+    }
+
+    #[test]
+    fn test_composite_offsets_and_colors_cells() {
+        let mut src = BrailleGrid::new(2, 1).unwrap();
+        src.set_dot(0, 0).unwrap();
+        let mut dst = BrailleGrid::new(4, 2).unwrap();
+        let blue = Color::rgb(0, 0, 255);
+        composite(&src, &mut dst, 3, 1, Some(blue));
+        assert!(!dst.is_empty(3, 1));
+        assert_eq!(dst.get_color(3, 1), Some(blue));
+        assert_eq!(dst.get_color(0, 0), None);
+    }
+}
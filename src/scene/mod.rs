@@ -0,0 +1,454 @@
+//! Declarative scenes loaded from TOML or JSON.
+//!
+//! A scene file describes a fixed-size canvas, a list of elements (shapes,
+//! text, images), and optional tweens that animate element properties over
+//! time. This lets people who don't write Rust author terminal graphics for
+//! any dotmax-based player.
+//!
+//! Requires the `scene` feature.
+//!
+//! # Format
+//!
+//! ```toml
+//! width = 40        # cells
+//! height = 12       # cells
+//! fps = 30          # default 30
+//! duration = 2.0    # seconds; 0 means a still image (default)
+//! loop = true       # restart after `duration` (default false)
+//!
+//! [[element]]
+//! type = "circle"   # line | rect | circle | polygon | text | image
+//! x = 40            # shapes use dot coordinates
+//! y = 24
+//! radius = 4
+//! color = "#ff8800"
+//!
+//! [[element.animate]]
+//! property = "radius"
+//! from = 4
+//! to = 20
+//! duration = 2.0
+//! easing = "ease-in-out"
+//!
+//! [[element]]
+//! type = "text"     # text and images use cell coordinates
+//! x = 2
+//! y = 0
+//! text = "hello"
+//! ```
+//!
+//! The same structure in JSON uses an `"elements"` array.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::scene::Scene;
+//!
+//! let scene = Scene::from_toml_str(r#"
+//!     width = 20
+//!     height = 5
+//!     duration = 1.0
+//!
+//!     [[element]]
+//!     type = "line"
+//!     x1 = 0
+//!     y1 = 0
+//!     x2 = 39
+//!     y2 = 19
+//! "#)?;
+//!
+//! let grid = scene.render_frame(0.5)?;
+//! assert_eq!(grid.dimensions(), (20, 5));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+mod easing;
+mod element;
+
+pub use easing::Easing;
+pub use element::{Element, SceneColor, Shape, Tween};
+
+use crate::animation::{AnimationLoop, PrerenderedAnimation};
+use crate::{BrailleGrid, DotmaxError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, info};
+
+/// Same bounds as [`AnimationLoop`] and [`PrerenderedAnimation`]
+const MIN_FPS: u32 = 1;
+const MAX_FPS: u32 = 240;
+
+/// Same limit as [`BrailleGrid::new`]
+const MAX_DIMENSION: usize = 10_000;
+
+const fn default_fps() -> u32 {
+    30
+}
+
+/// A declarative scene: canvas size, timing, and elements.
+///
+/// Build one with [`Scene::load`], [`Scene::from_toml_str`], or
+/// [`Scene::from_json_str`]; all three validate the scene before returning it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scene {
+    /// Canvas width in cells
+    pub width: usize,
+    /// Canvas height in cells
+    pub height: usize,
+    /// Playback frame rate (1-240, default 30)
+    #[serde(default = "default_fps")]
+    pub fps: u32,
+    /// Length in seconds; 0 means the scene is a still image
+    #[serde(default)]
+    pub duration: f32,
+    /// Restart from the beginning after `duration` seconds
+    #[serde(default, rename = "loop")]
+    pub looping: bool,
+    /// Elements, drawn in order (later elements draw on top)
+    #[serde(default, rename = "element", alias = "elements")]
+    pub elements: Vec<Element>,
+}
+
+impl Scene {
+    /// Parse and validate a scene from TOML text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::SceneError`] for malformed TOML, plus any error
+    /// from [`Scene::validate`].
+    pub fn from_toml_str(text: &str) -> Result<Self, DotmaxError> {
+        let scene: Self =
+            toml::from_str(text).map_err(|e| DotmaxError::SceneError(format!("TOML: {e}")))?;
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    /// Parse and validate a scene from JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::SceneError`] for malformed JSON, plus any error
+    /// from [`Scene::validate`].
+    pub fn from_json_str(text: &str) -> Result<Self, DotmaxError> {
+        let scene: Self = serde_json::from_str(text)
+            .map_err(|e| DotmaxError::SceneError(format!("JSON: {e}")))?;
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    /// Load a scene file, choosing the parser from the extension.
+    ///
+    /// `.json` files are parsed as JSON; everything else as TOML. Relative
+    /// image paths inside the scene are resolved against the file's directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be read, otherwise
+    /// the same errors as [`Scene::from_toml_str`] / [`Scene::from_json_str`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        info!(path = ?path, "Loading scene");
+        let text = std::fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        #[allow(unused_mut)]
+        let mut scene = if is_json {
+            Self::from_json_str(&text)?
+        } else {
+            Self::from_toml_str(&text)?
+        };
+
+        #[cfg(feature = "image")]
+        if let Some(base) = path.parent() {
+            for element in &mut scene.elements {
+                element.resolve_paths(base);
+            }
+        }
+
+        debug!(
+            elements = scene.elements.len(),
+            duration = scene.duration,
+            "Scene loaded"
+        );
+        Ok(scene)
+    }
+
+    /// Check dimensions, timing, and every element.
+    ///
+    /// # Errors
+    ///
+    /// - [`DotmaxError::InvalidDimensions`] if width or height is 0 or exceeds 10,000
+    /// - [`DotmaxError::InvalidPolygon`] for polygons with too few points
+    /// - [`DotmaxError::SceneError`] for an out-of-range fps or duration, or a
+    ///   tween that targets a property the element doesn't have
+    pub fn validate(&self) -> Result<(), DotmaxError> {
+        if self.width == 0
+            || self.height == 0
+            || self.width > MAX_DIMENSION
+            || self.height > MAX_DIMENSION
+        {
+            return Err(DotmaxError::InvalidDimensions {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if !(MIN_FPS..=MAX_FPS).contains(&self.fps) {
+            return Err(DotmaxError::SceneError(format!(
+                "fps must be {MIN_FPS}-{MAX_FPS}, got {}",
+                self.fps
+            )));
+        }
+        if !self.duration.is_finite() || self.duration < 0.0 {
+            return Err(DotmaxError::SceneError(format!(
+                "duration must be ≥ 0 seconds, got {}",
+                self.duration
+            )));
+        }
+        for (index, element) in self.elements.iter().enumerate() {
+            element.validate(index)?;
+        }
+        Ok(())
+    }
+
+    /// Number of frames in one pass through the scene (at least 1).
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn frame_count(&self) -> u64 {
+        let frames = (self.duration * self.fps as f32).ceil() as u64;
+        frames.max(1)
+    }
+
+    /// Scene time in seconds for a frame number, wrapping when looping.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn time_at_frame(&self, frame: u64) -> f32 {
+        let t = frame as f32 / self.fps as f32;
+        if self.looping && self.duration > 0.0 {
+            t % self.duration
+        } else {
+            t.min(self.duration)
+        }
+    }
+
+    /// Render the scene at time `t` (seconds) into a new grid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid can't be created or an image element
+    /// fails to load.
+    pub fn render_frame(&self, t: f32) -> Result<BrailleGrid, DotmaxError> {
+        let mut grid = BrailleGrid::new(self.width, self.height)?;
+        self.render_into(&mut grid, t)?;
+        Ok(grid)
+    }
+
+    /// Render the scene at time `t` (seconds) into an existing grid.
+    ///
+    /// The grid is cleared first (dots, colors, and text). It doesn't have to
+    /// match the scene size; elements are clipped to whatever fits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an image element fails to load.
+    pub fn render_into(&self, grid: &mut BrailleGrid, t: f32) -> Result<(), DotmaxError> {
+        let mut scratch = BrailleGrid::new(grid.width(), grid.height())?;
+        self.render_with(grid, &mut scratch, t)
+    }
+
+    /// [`render_into`](Self::render_into) with a caller-owned scratch grid
+    /// for shapes, so playback can reuse one across frames. `scratch` is
+    /// resized to match `grid` when needed.
+    fn render_with(
+        &self,
+        grid: &mut BrailleGrid,
+        scratch: &mut BrailleGrid,
+        t: f32,
+    ) -> Result<(), DotmaxError> {
+        grid.clear();
+        grid.clear_characters();
+        if scratch.dimensions() != grid.dimensions() {
+            scratch.resize(grid.width(), grid.height())?;
+        }
+        for element in &self.elements {
+            element.draw(grid, scratch, t)?;
+        }
+        Ok(())
+    }
+
+    /// Render every frame of one pass into a [`PrerenderedAnimation`].
+    ///
+    /// Useful for saving a scene with
+    /// [`PrerenderedAnimation::save_to_file`] or playing it without
+    /// per-frame rendering cost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any frame fails to render.
+    pub fn prerender(&self) -> Result<PrerenderedAnimation, DotmaxError> {
+        let mut animation = PrerenderedAnimation::new(self.fps);
+        let mut scratch = BrailleGrid::new(self.width, self.height)?;
+        for frame in 0..self.frame_count() {
+            let mut grid = BrailleGrid::new(self.width, self.height)?;
+            self.render_with(&mut grid, &mut scratch, self.time_at_frame(frame))?;
+            animation.add_frame(grid);
+        }
+        Ok(animation)
+    }
+
+    /// Play the scene in the terminal until it ends or the user presses
+    /// `q` / Ctrl+C.
+    ///
+    /// Still scenes (`duration = 0`) and looping scenes stay on screen until
+    /// the user quits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal setup or rendering fails.
+    pub fn play(&self) -> Result<(), DotmaxError> {
+        let frames = self.frame_count();
+        let ends = !self.looping && self.duration > 0.0;
+        let mut scratch = BrailleGrid::new(self.width, self.height)?;
+        AnimationLoop::new(self.width, self.height)
+            .fps(self.fps)
+            .on_frame(|frame, grid| {
+                if ends && frame >= frames {
+                    return Ok(false);
+                }
+                self.render_with(grid, &mut scratch, self.time_at_frame(frame))?;
+                Ok(true)
+            })
+            .run()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    const ANIMATED: &str = r##"
+        width = 10
+        height = 4
+        fps = 10
+        duration = 1.0
+
+        [[element]]
+        type = "rect"
+        x = 0
+        y = 0
+        width = 4
+        height = 4
+        filled = true
+        color = "#00ff00"
+
+        [[element.animate]]
+        property = "x"
+        from = 0
+        to = 16
+        duration = 1.0
+    "##;
+
+    #[test]
+    fn test_toml_and_json_parse_to_same_scene() {
+        let toml = Scene::from_toml_str(ANIMATED).unwrap();
+        let json = Scene::from_json_str(
+            r#"{
+                "width": 10, "height": 4, "fps": 10, "duration": 1.0,
+                "elements": [{
+                    "type": "rect", "x": 0, "y": 0, "width": 4, "height": 4,
+                    "filled": true, "color": [0, 255, 0],
+                    "animate": [{"property": "x", "from": 0, "to": 16, "duration": 1.0}]
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(toml.elements[0].shape, json.elements[0].shape);
+        assert_eq!(toml.elements[0].color, json.elements[0].color);
+        assert_eq!(toml.elements[0].animate, json.elements[0].animate);
+    }
+
+    #[test]
+    fn test_defaults() {
+        let scene = Scene::from_toml_str("width = 5\nheight = 5").unwrap();
+        assert_eq!(scene.fps, 30);
+        assert!(scene.duration.abs() < f32::EPSILON);
+        assert!(!scene.looping);
+        assert!(scene.elements.is_empty());
+        assert_eq!(scene.frame_count(), 1);
+    }
+
+    #[test]
+    fn test_animation_moves_element() {
+        let scene = Scene::from_toml_str(ANIMATED).unwrap();
+        let start = scene.render_frame(0.0).unwrap();
+        let end = scene.render_frame(1.0).unwrap();
+        assert!(!start.is_empty(0, 0));
+        assert_eq!(start.get_color(0, 0), Some(Color::rgb(0, 255, 0)));
+        assert!(end.is_empty(0, 0));
+        assert!(!end.is_empty(8, 0));
+    }
+
+    #[test]
+    fn test_prerender_frame_count() {
+        let scene = Scene::from_toml_str(ANIMATED).unwrap();
+        assert_eq!(scene.frame_count(), 10);
+        assert_eq!(scene.prerender().unwrap().frame_count(), 10);
+    }
+
+    #[test]
+    fn test_time_at_frame_wraps_when_looping() {
+        let mut scene = Scene::from_toml_str(ANIMATED).unwrap();
+        assert!((scene.time_at_frame(15) - 1.0).abs() < 1e-6);
+        scene.looping = true;
+        assert!((scene.time_at_frame(15) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_render_into_clears_previous_text() {
+        let scene = Scene::from_toml_str("width = 4\nheight = 1").unwrap();
+        let mut grid = BrailleGrid::new(4, 1).unwrap();
+        grid.set_char(0, 0, 'x').unwrap();
+        scene.render_into(&mut grid, 0.0).unwrap();
+        assert_eq!(grid.get_char(0, 0), '⠀');
+    }
+
+    #[test]
+    fn test_render_with_resizes_scratch() {
+        let scene = Scene::from_toml_str(ANIMATED).unwrap();
+        let mut scratch = BrailleGrid::new(1, 1).unwrap();
+        let mut wide = BrailleGrid::new(20, 4).unwrap();
+        scene.render_with(&mut wide, &mut scratch, 1.0).unwrap();
+
+        assert_eq!(scratch.dimensions(), (20, 4));
+        assert!(!wide.is_empty(9, 0));
+        assert!(wide.is_empty(10, 0));
+    }
+
+    #[test]
+    fn test_invalid_scenes_are_rejected() {
+        assert!(matches!(
+            Scene::from_toml_str("width = 0\nheight = 5"),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+        assert!(matches!(
+            Scene::from_toml_str("width = 5\nheight = 5\nfps = 0"),
+            Err(DotmaxError::SceneError(_))
+        ));
+        assert!(matches!(
+            Scene::from_toml_str("width = 5\nheight = 5\n[[element]]\ntype = \"star\""),
+            Err(DotmaxError::SceneError(_))
+        ));
+        assert!(matches!(
+            Scene::from_toml_str(
+                "width = 5\nheight = 5\n[[element]]\ntype = \"polygon\"\nfilled = true\npoints = [[0, 0], [1, 1]]"
+            ),
+            Err(DotmaxError::InvalidPolygon { .. })
+        ));
+        assert!(matches!(
+            Scene::from_json_str("{not json"),
+            Err(DotmaxError::SceneError(_))
+        ));
+    }
+}
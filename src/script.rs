@@ -0,0 +1,619 @@
+//! Live-coded visuals with Rhai scripts.
+//!
+//! A script is a [Rhai](https://rhai.rs) file that defines `draw(t)`, where `t`
+//! is the elapsed time in seconds. [`LiveScript::run`] calls it once per frame
+//! and recompiles the file whenever it changes on disk, so you can edit the
+//! script in one window and watch the result in another.
+//!
+//! Requires the `script` feature.
+//!
+//! # Script API
+//!
+//! Drawing functions take **dot** coordinates, like [`crate::primitives`]. Any
+//! argument may be an integer or a float.
+//!
+//! | Function | Effect |
+//! |----------|--------|
+//! | `width()`, `height()` | Canvas size in dots |
+//! | `cols()`, `rows()` | Canvas size in cells |
+//! | `dot(x, y)` | Set one dot (off-canvas dots are ignored) |
+//! | `line(x0, y0, x1, y1)` | Line segment |
+//! | `circle(x, y, r)`, `fill_circle(x, y, r)` | Circle outline / disc |
+//! | `rect(x, y, w, h)`, `fill_rect(x, y, w, h)` | Rectangle outline / filled |
+//! | `polygon(points)`, `fill_polygon(points)` | Closed polygon from `[[x, y], ...]` |
+//! | `text(col, row, s)` | Write text at a **cell** position |
+//! | `color(r, g, b)`, `no_color()` | Set / clear the color for later calls |
+//!
+//! The color resets to none at the start of every frame.
+//!
+//! ```rhai
+//! fn draw(t) {
+//!     let cx = width() / 2;
+//!     let cy = height() / 2;
+//!     color(255, 128, 0);
+//!     circle(cx + 20.0 * t.sin(), cy, 10);
+//!     text(0, 0, "t = " + t);
+//! }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::{script::LiveScript, BrailleGrid};
+//!
+//! let mut script = LiveScript::from_source("fn draw(t) { line(0, 0, width() - 1, 0); }")?;
+//! let mut grid = BrailleGrid::new(10, 2)?;
+//! script.draw(&mut grid, 0.0)?;
+//! assert!(!grid.is_empty(9, 0));
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::animation::AnimationLoop;
+use crate::primitives::{
+    draw_circle, draw_circle_colored, draw_circle_filled, draw_line, draw_line_colored,
+    draw_polygon, draw_polygon_colored, draw_polygon_filled, draw_rectangle,
+    draw_rectangle_colored, draw_rectangle_filled,
+};
+use crate::{BrailleGrid, Color, DotmaxError};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, AST, INT};
+use std::cell::RefCell;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Instant, SystemTime};
+use tracing::{debug, info, warn};
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Operation budget per `draw` call, so an accidental infinite loop surfaces
+/// as an error instead of freezing the terminal
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+/// Name of the per-frame entry point every script must define
+const ENTRY_POINT: &str = "draw";
+
+/// Drawing state shared between the engine's registered functions and
+/// [`LiveScript::draw`].
+struct Canvas {
+    grid: BrailleGrid,
+    color: Option<Color>,
+}
+
+/// A compiled Rhai script with a `draw(t)` function, optionally backed by a
+/// file that is recompiled when it changes.
+pub struct LiveScript {
+    path: Option<PathBuf>,
+    engine: Engine,
+    ast: AST,
+    canvas: Rc<RefCell<Canvas>>,
+    modified: Option<SystemTime>,
+    last_error: Option<String>,
+}
+
+impl fmt::Debug for LiveScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LiveScript")
+            .field("path", &self.path)
+            .field("modified", &self.modified)
+            .field("last_error", &self.last_error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LiveScript {
+    /// Load and compile a script file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the file can't be read, or
+    /// [`DotmaxError::ScriptError`] if it doesn't compile or lacks `draw(t)`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        info!(path = ?path, "Loading script");
+        let source = std::fs::read_to_string(path)?;
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+        let mut script = Self::from_source(&source)
+            .map_err(|e| DotmaxError::ScriptError(format!("{}: {e}", path.display())))?;
+        script.path = Some(path.to_path_buf());
+        script.modified = modified;
+        Ok(script)
+    }
+
+    /// Compile a script from a string. The result never reloads.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::ScriptError`] if the source doesn't compile or
+    /// lacks `draw(t)`.
+    pub fn from_source(source: &str) -> Result<Self, DotmaxError> {
+        // Placeholder grid; `draw` swaps the caller's grid in for each call
+        let canvas = Rc::new(RefCell::new(Canvas {
+            grid: BrailleGrid::new(1, 1)?,
+            color: None,
+        }));
+        let engine = build_engine(&canvas);
+        let ast = compile(&engine, source)?;
+        Ok(Self {
+            path: None,
+            engine,
+            ast,
+            canvas,
+            modified: None,
+            last_error: None,
+        })
+    }
+
+    /// The file this script was loaded from, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The most recent compile or runtime error, cleared by the next
+    /// successful reload or draw.
+    #[must_use]
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Recompile the script if its file changed since the last load.
+    ///
+    /// Returns `Ok(true)` when a new version was compiled. If the new version
+    /// fails to compile, the previous one stays active so playback can
+    /// continue, and the error is returned (and kept in [`last_error`]).
+    ///
+    /// [`last_error`]: LiveScript::last_error
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`] if the changed file can't be read, or
+    /// [`DotmaxError::ScriptError`] if it no longer compiles.
+    pub fn reload_if_changed(&mut self) -> Result<bool, DotmaxError> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified.is_none() || modified == self.modified {
+            return Ok(false);
+        }
+        // Record the timestamp first so a broken file is reported once, not every frame
+        self.modified = modified;
+
+        let source = std::fs::read_to_string(path)?;
+        match compile(&self.engine, &source) {
+            Ok(ast) => {
+                info!(path = ?path, "Script reloaded");
+                self.ast = ast;
+                self.last_error = None;
+                Ok(true)
+            }
+            Err(e) => {
+                let message = format!("{}: {e}", path.display());
+                warn!(error = %message, "Script reload failed, keeping previous version");
+                self.last_error = Some(message.clone());
+                Err(DotmaxError::ScriptError(message))
+            }
+        }
+    }
+
+    /// Call the script's `draw(t)` on `grid`.
+    ///
+    /// The grid is not cleared first, so scripts can accumulate drawing across
+    /// calls; [`LiveScript::run`] hands it a cleared buffer every frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::ScriptError`] if the script raises an error or
+    /// exceeds its operation budget.
+    pub fn draw(&mut self, grid: &mut BrailleGrid, t: f64) -> Result<(), DotmaxError> {
+        {
+            let mut canvas = self.canvas.borrow_mut();
+            std::mem::swap(&mut canvas.grid, grid);
+            canvas.color = None;
+        }
+
+        let mut scope = Scope::new();
+        let result = self
+            .engine
+            .call_fn::<Dynamic>(&mut scope, &self.ast, ENTRY_POINT, (t,));
+
+        std::mem::swap(&mut self.canvas.borrow_mut().grid, grid);
+
+        match result {
+            Ok(_) => {
+                self.last_error = None;
+                Ok(())
+            }
+            Err(e) => {
+                let message = self
+                    .path
+                    .as_ref()
+                    .map_or_else(|| e.to_string(), |path| format!("{}: {e}", path.display()));
+                self.last_error = Some(message.clone());
+                Err(DotmaxError::ScriptError(message))
+            }
+        }
+    }
+
+    /// Play the script full-screen until the user presses `q` / Ctrl+C.
+    ///
+    /// The file is checked for changes before every frame. Compile and runtime
+    /// errors don't stop playback; the error text is shown on screen until the
+    /// script is fixed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal setup or rendering fails.
+    pub fn run(&mut self, width: usize, height: usize, fps: u32) -> Result<(), DotmaxError> {
+        let start = Instant::now();
+        // A failed reload keeps the old version running, so its error has to
+        // stay on screen until a later reload succeeds
+        let mut compile_error: Option<String> = None;
+        AnimationLoop::new(width, height)
+            .fps(fps)
+            .on_frame(|_frame, grid| {
+                grid.clear_characters();
+                match self.reload_if_changed() {
+                    Ok(true) => compile_error = None,
+                    Ok(false) => {}
+                    Err(e) => compile_error = Some(e.to_string()),
+                }
+                let runtime_error = self
+                    .draw(grid, start.elapsed().as_secs_f64())
+                    .err()
+                    .map(|e| e.to_string());
+                if let Some(message) = compile_error.as_ref().or(runtime_error.as_ref()) {
+                    debug!(error = %message, "Showing script error");
+                    show_error(grid, message);
+                }
+                Ok(true)
+            })
+            .run()
+    }
+}
+
+fn compile(engine: &Engine, source: &str) -> Result<AST, DotmaxError> {
+    let ast = engine
+        .compile(source)
+        .map_err(|e| DotmaxError::ScriptError(e.to_string()))?;
+    let has_entry = ast
+        .iter_functions()
+        .any(|f| f.name == ENTRY_POINT && f.params.len() == 1);
+    if !has_entry {
+        return Err(DotmaxError::ScriptError(format!(
+            "script must define `fn {ENTRY_POINT}(t)`"
+        )));
+    }
+    Ok(ast)
+}
+
+/// Write an error message over the bottom rows of the grid, wrapping at the
+/// grid width.
+fn show_error(grid: &mut BrailleGrid, message: &str) {
+    let width = grid.width();
+    let chars: Vec<char> = message.chars().filter(|c| !c.is_control()).collect();
+    let lines: Vec<&[char]> = chars.chunks(width.max(1)).collect();
+    let first_row = grid.height().saturating_sub(lines.len());
+    for (row, line) in lines.iter().enumerate().take(grid.height()) {
+        for (col, &ch) in line.iter().enumerate() {
+            let _ = grid.set_char(col, first_row + row, ch);
+            let _ = grid.set_cell_color(col, first_row + row, Color::rgb(255, 80, 80));
+        }
+    }
+}
+
+// ============================================================================
+// Engine bindings
+// ============================================================================
+
+fn num(value: &Dynamic) -> ScriptResult<f64> {
+    if let Ok(f) = value.as_float() {
+        return Ok(f);
+    }
+    if let Ok(i) = value.as_int() {
+        return Ok(i as f64);
+    }
+    Err(format!("expected a number, got {}", value.type_name()).into())
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn coord(value: &Dynamic) -> ScriptResult<i32> {
+    Ok(num(value)?.round() as i32)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn size(value: &Dynamic) -> ScriptResult<u32> {
+    Ok(num(value)?.round().max(0.0) as u32)
+}
+
+fn points(array: &Array) -> ScriptResult<Vec<(i32, i32)>> {
+    array
+        .iter()
+        .map(|point| {
+            let pair = point
+                .read_lock::<Array>()
+                .ok_or("polygon points must be [x, y] arrays")?;
+            match pair.as_slice() {
+                [x, y] => Ok((coord(x)?, coord(y)?)),
+                _ => Err("polygon points must be [x, y] arrays".into()),
+            }
+        })
+        .collect()
+}
+
+// Rhai's native functions must return its boxed error type
+#[allow(clippy::unnecessary_box_returns)]
+fn script_err(err: DotmaxError) -> Box<EvalAltResult> {
+    err.to_string().into()
+}
+
+/// Register every drawing function on a fresh engine, each sharing `canvas`.
+#[allow(clippy::many_single_char_names)]
+fn build_engine(canvas: &Rc<RefCell<Canvas>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let c = Rc::clone(canvas);
+    engine.register_fn("width", move || c.borrow().grid.dot_width() as INT);
+    let c = Rc::clone(canvas);
+    engine.register_fn("height", move || c.borrow().grid.dot_height() as INT);
+    let c = Rc::clone(canvas);
+    engine.register_fn("cols", move || c.borrow().grid.width() as INT);
+    let c = Rc::clone(canvas);
+    engine.register_fn("rows", move || c.borrow().grid.height() as INT);
+
+    let c = Rc::clone(canvas);
+    engine.register_fn("color", move |r: INT, g: INT, b: INT| {
+        let channel = |v: INT| v.clamp(0, 255) as u8;
+        c.borrow_mut().color = Some(Color::rgb(channel(r), channel(g), channel(b)));
+    });
+    let c = Rc::clone(canvas);
+    engine.register_fn("no_color", move || c.borrow_mut().color = None);
+
+    let c = Rc::clone(canvas);
+    engine.register_fn("dot", move |x: Dynamic, y: Dynamic| -> ScriptResult<()> {
+        let (x, y) = (coord(&x)?, coord(&y)?);
+        let mut canvas = c.borrow_mut();
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            return Ok(());
+        };
+        if canvas.grid.set_dot(x, y).is_ok() {
+            if let Some(color) = canvas.color {
+                let _ = canvas.grid.set_cell_color(x / 2, y / 4, color);
+            }
+        }
+        Ok(())
+    });
+
+    let c = Rc::clone(canvas);
+    engine.register_fn(
+        "line",
+        move |x0: Dynamic, y0: Dynamic, x1: Dynamic, y1: Dynamic| -> ScriptResult<()> {
+            let (x0, y0, x1, y1) = (coord(&x0)?, coord(&y0)?, coord(&x1)?, coord(&y1)?);
+            let mut canvas = c.borrow_mut();
+            let result = match canvas.color {
+                Some(color) => draw_line_colored(&mut canvas.grid, x0, y0, x1, y1, color, None),
+                None => draw_line(&mut canvas.grid, x0, y0, x1, y1),
+            };
+            result.map_err(script_err)
+        },
+    );
+
+    for (name, filled) in [("circle", false), ("fill_circle", true)] {
+        let c = Rc::clone(canvas);
+        engine.register_fn(
+            name,
+            move |x: Dynamic, y: Dynamic, r: Dynamic| -> ScriptResult<()> {
+                let (x, y, r) = (coord(&x)?, coord(&y)?, size(&r)?);
+                let mut canvas = c.borrow_mut();
+                let result = match (canvas.color, filled) {
+                    (Some(color), _) => {
+                        draw_circle_colored(&mut canvas.grid, x, y, r, color, filled)
+                    }
+                    (None, true) => draw_circle_filled(&mut canvas.grid, x, y, r),
+                    (None, false) => draw_circle(&mut canvas.grid, x, y, r),
+                };
+                result.map_err(script_err)
+            },
+        );
+    }
+
+    for (name, filled) in [("rect", false), ("fill_rect", true)] {
+        let c = Rc::clone(canvas);
+        engine.register_fn(
+            name,
+            move |x: Dynamic, y: Dynamic, w: Dynamic, h: Dynamic| -> ScriptResult<()> {
+                let (x, y, w, h) = (coord(&x)?, coord(&y)?, size(&w)?, size(&h)?);
+                let mut canvas = c.borrow_mut();
+                let result = match (canvas.color, filled) {
+                    (Some(color), _) => {
+                        draw_rectangle_colored(&mut canvas.grid, x, y, w, h, color, filled)
+                    }
+                    (None, true) => draw_rectangle_filled(&mut canvas.grid, x, y, w, h),
+                    (None, false) => draw_rectangle(&mut canvas.grid, x, y, w, h),
+                };
+                result.map_err(script_err)
+            },
+        );
+    }
+
+    let c = Rc::clone(canvas);
+    engine.register_fn("polygon", move |pts: Array| -> ScriptResult<()> {
+        let vertices = points(&pts)?;
+        let mut canvas = c.borrow_mut();
+        let result = match canvas.color {
+            Some(color) => draw_polygon_colored(&mut canvas.grid, &vertices, color, true),
+            None => draw_polygon(&mut canvas.grid, &vertices),
+        };
+        result.map_err(script_err)
+    });
+
+    let c = Rc::clone(canvas);
+    engine.register_fn("fill_polygon", move |pts: Array| -> ScriptResult<()> {
+        let vertices = points(&pts)?;
+        let mut canvas = c.borrow_mut();
+        let Some(color) = canvas.color else {
+            return draw_polygon_filled(&mut canvas.grid, &vertices).map_err(script_err);
+        };
+        // No colored fill primitive: color every cell the fill changed
+        let before = canvas.grid.get_raw_patterns().to_vec();
+        draw_polygon_filled(&mut canvas.grid, &vertices).map_err(script_err)?;
+        let width = canvas.grid.width();
+        let changed: Vec<usize> = canvas
+            .grid
+            .get_raw_patterns()
+            .iter()
+            .zip(&before)
+            .enumerate()
+            .filter_map(|(i, (after, before))| (after != before).then_some(i))
+            .collect();
+        for index in changed {
+            let _ = canvas
+                .grid
+                .set_cell_color(index % width, index / width, color);
+        }
+        Ok(())
+    });
+
+    let c = Rc::clone(canvas);
+    engine.register_fn(
+        "text",
+        move |col: Dynamic, row: Dynamic, text: &str| -> ScriptResult<()> {
+            let (col, row) = (coord(&col)?, coord(&row)?);
+            let mut canvas = c.borrow_mut();
+            let Ok(row) = usize::try_from(row) else {
+                return Ok(());
+            };
+            let color = canvas.color;
+            for (offset, ch) in text.chars().enumerate() {
+                let Ok(x) = usize::try_from(i64::from(col) + offset as i64) else {
+                    continue;
+                };
+                // Off-grid characters are clipped, matching the shape primitives
+                if canvas.grid.set_char(x, row, ch).is_ok() {
+                    if let Some(color) = color {
+                        let _ = canvas.grid.set_cell_color(x, row, color);
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_missing_draw_is_rejected() {
+        let err = LiveScript::from_source("fn setup() {}").unwrap_err();
+        assert!(matches!(err, DotmaxError::ScriptError(ref m) if m.contains("draw")));
+    }
+
+    #[test]
+    fn test_syntax_error_is_reported() {
+        let err = LiveScript::from_source("fn draw(t) { line(0, 0, ").unwrap_err();
+        assert!(matches!(err, DotmaxError::ScriptError(_)));
+    }
+
+    #[test]
+    fn test_draw_receives_time_and_accepts_floats() {
+        let mut script =
+            LiveScript::from_source("fn draw(t) { dot(t * 2.0, 0); dot(1, 4); }").unwrap();
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        script.draw(&mut grid, 1.5).unwrap();
+        // Dot (3, 0) is the top-right dot of cell (1, 0); (1, 4) of cell (0, 1)
+        assert!(grid.get_dot(1, 0, 3).unwrap());
+        assert!(grid.get_dot(0, 1, 3).unwrap());
+    }
+
+    #[test]
+    fn test_color_applies_until_cleared() {
+        let source = r#"
+            fn draw(t) {
+                color(255, 0, 0);
+                fill_rect(0, 0, 2, 4);
+                no_color();
+                fill_rect(2, 0, 2, 4);
+                color(0, 0, 255);
+                text(2, 0, "x");
+            }
+        "#;
+        let mut script = LiveScript::from_source(source).unwrap();
+        let mut grid = BrailleGrid::new(3, 1).unwrap();
+        script.draw(&mut grid, 0.0).unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(grid.get_color(1, 0), None);
+        assert_eq!(grid.get_char(2, 0), 'x');
+        assert_eq!(grid.get_color(2, 0), Some(Color::rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn test_dimensions_match_target_grid() {
+        let source = "fn draw(t) { text(0, 0, `${width()}x${height()} ${cols()}x${rows()}`); }";
+        let mut script = LiveScript::from_source(source).unwrap();
+        let mut grid = BrailleGrid::new(20, 3).unwrap();
+        script.draw(&mut grid, 0.0).unwrap();
+        let row: String = (0..10).map(|x| grid.get_char(x, 0)).collect();
+        assert_eq!(row, "40x12 20x3");
+    }
+
+    #[test]
+    fn test_polygon_and_runtime_errors() {
+        let mut script = LiveScript::from_source(
+            "fn draw(t) { color(0, 255, 0); fill_polygon([[0, 0], [7, 0], [0, 7]]); polygon([1]); }",
+        )
+        .unwrap();
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        let err = script.draw(&mut grid, 0.0).unwrap_err();
+        assert!(matches!(err, DotmaxError::ScriptError(_)));
+        assert!(script.last_error().is_some());
+        // Drawing before the error is kept and the grid is handed back
+        assert_eq!(grid.dimensions(), (4, 2));
+        assert_eq!(grid.get_color(0, 0), Some(Color::rgb(0, 255, 0)));
+    }
+
+    #[test]
+    fn test_infinite_loop_hits_operation_budget() {
+        let mut script = LiveScript::from_source("fn draw(t) { loop {} }").unwrap();
+        let mut grid = BrailleGrid::new(2, 2).unwrap();
+        assert!(script.draw(&mut grid, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_reload_keeps_previous_version_on_error() {
+        let mut file = tempfile::Builder::new().suffix(".rhai").tempfile().unwrap();
+        write!(file, "fn draw(t) {{ dot(0, 0); }}").unwrap();
+        let mut script = LiveScript::load(file.path()).unwrap();
+        assert!(!script.reload_if_changed().unwrap());
+
+        // Force a different mtime; some filesystems have coarse timestamps
+        let broken = "fn draw(t) { dot(";
+        std::fs::write(file.path(), broken).unwrap();
+        script.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(script.reload_if_changed().is_err());
+        assert!(script.last_error().is_some());
+
+        let mut grid = BrailleGrid::new(2, 2).unwrap();
+        script.draw(&mut grid, 0.0).unwrap();
+        assert!(grid.get_dot(0, 0, 0).unwrap());
+
+        std::fs::write(file.path(), "fn draw(t) { dot(3, 7); }").unwrap();
+        script.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(script.reload_if_changed().unwrap());
+        assert!(script.last_error().is_none());
+    }
+
+    #[test]
+    fn test_show_error_wraps_at_bottom() {
+        let mut grid = BrailleGrid::new(4, 3).unwrap();
+        show_error(&mut grid, "abcdef");
+        assert_eq!(grid.get_char(0, 1), 'a');
+        assert_eq!(grid.get_char(0, 2), 'e');
+    }
+}
@@ -0,0 +1,361 @@
+//! CPU, memory, and network metrics as chart data.
+//!
+//! [`SystemMetrics`] samples the machine through the `sysinfo` crate,
+//! giving a [`SystemSample`] of plain numbers each time it's asked.
+//! [`SystemMonitor`] keeps a history of those samples in
+//! [`StreamPlot`]s and draws a `top`-style dashboard from them: line
+//! charts of CPU, memory, and network use, with a bar per CPU core as
+//! gauges beside them when there's room. As a [`FrameSource`] it samples
+//! once per frame, so any player can show it.
+//!
+//! Requires the `sysinfo` feature.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::animation::FrameSource;
+//! use dotmax::system::SystemMonitor;
+//! use dotmax::TerminalRenderer;
+//!
+//! let mut renderer = TerminalRenderer::new()?;
+//! let mut monitor = SystemMonitor::new();
+//! for _ in 0..20 {
+//!     let (width, height) = renderer.get_terminal_size()?;
+//!     if let Some(frame) = monitor.next_frame(width.into(), height.into()) {
+//!         let (grid, delay) = frame?;
+//!         renderer.render(&grid)?;
+//!         std::thread::sleep(delay);
+//!     }
+//! }
+//! renderer.cleanup()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::{Duration, Instant};
+
+use sysinfo::{Networks, System, MINIMUM_CPU_UPDATE_INTERVAL};
+
+use crate::animation::FrameSource;
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+use crate::plot::stream::StreamPlot;
+use crate::plot::BarChart;
+
+/// Dashboards at least this wide get per-core gauges.
+const GAUGE_MIN_WIDTH: usize = 60;
+
+/// One reading of the machine's load.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemSample {
+    /// Use of all CPUs together, 0-100
+    pub cpu: f64,
+    /// Use of each CPU core, 0-100
+    pub cores: Vec<f64>,
+    /// Memory in use, in bytes
+    pub memory_used: u64,
+    /// Installed memory, in bytes
+    pub memory_total: u64,
+    /// Bytes per second received over all network interfaces
+    pub received: f64,
+    /// Bytes per second sent over all network interfaces
+    pub transmitted: f64,
+}
+
+impl SystemSample {
+    /// Memory in use as a percentage of the total, 0-100.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn memory_percent(&self) -> f64 {
+        if self.memory_total == 0 {
+            0.0
+        } else {
+            self.memory_used as f64 / self.memory_total as f64 * 100.0
+        }
+    }
+}
+
+/// Samples CPU, memory, and network use.
+///
+/// CPU use is measured between consecutive samples, so the first one reads
+/// zero and samples closer together than
+/// [`MINIMUM_CPU_UPDATE_INTERVAL`] are imprecise.
+pub struct SystemMetrics {
+    system: System,
+    networks: Networks,
+    last: Instant,
+}
+
+impl SystemMetrics {
+    /// Starts measuring from now.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu();
+        system.refresh_memory();
+        Self {
+            system,
+            networks: Networks::new_with_refreshed_list(),
+            last: Instant::now(),
+        }
+    }
+
+    /// Load since the previous sample, or since [`new`](Self::new) for the
+    /// first.
+    pub fn sample(&mut self) -> SystemSample {
+        self.system.refresh_cpu();
+        self.system.refresh_memory();
+        self.networks.refresh();
+        let seconds = self.last.elapsed().as_secs_f64().max(1e-3);
+        self.last = Instant::now();
+
+        #[allow(clippy::cast_precision_loss)]
+        let (received, transmitted) =
+            self.networks
+                .list()
+                .values()
+                .fold((0.0, 0.0), |(received, transmitted), data| {
+                    (
+                        received + data.received() as f64 / seconds,
+                        transmitted + data.transmitted() as f64 / seconds,
+                    )
+                });
+        SystemSample {
+            cpu: f64::from(self.system.global_cpu_info().cpu_usage()),
+            cores: self
+                .system
+                .cpus()
+                .iter()
+                .map(|cpu| f64::from(cpu.cpu_usage()))
+                .collect(),
+            memory_used: self.system.used_memory(),
+            memory_total: self.system.total_memory(),
+            received,
+            transmitted,
+        }
+    }
+}
+
+impl Default for SystemMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SystemMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SystemMetrics")
+            .field("cpus", &self.system.cpus().len())
+            .field("networks", &self.networks.list().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A live dashboard of system load, built from [`StreamPlot`]s and a
+/// [`BarChart`].
+#[derive(Debug)]
+pub struct SystemMonitor {
+    metrics: SystemMetrics,
+    interval: Duration,
+    cpu: StreamPlot,
+    memory: StreamPlot,
+    /// Received and sent, in KiB/s
+    network: StreamPlot,
+    latest: Option<SystemSample>,
+}
+
+impl SystemMonitor {
+    /// A monitor sampling once a second and keeping the last 120 samples.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            metrics: SystemMetrics::new(),
+            interval: Duration::from_secs(1),
+            cpu: StreamPlot::new(120),
+            memory: StreamPlot::new(120),
+            network: StreamPlot::new(120),
+            latest: None,
+        }
+    }
+
+    /// Time between samples, at least [`MINIMUM_CPU_UPDATE_INTERVAL`].
+    #[must_use]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(MINIMUM_CPU_UPDATE_INTERVAL);
+        self
+    }
+
+    /// How many samples the charts show (at least 2).
+    #[must_use]
+    pub fn history(mut self, samples: usize) -> Self {
+        self.cpu = StreamPlot::new(samples);
+        self.memory = StreamPlot::new(samples);
+        self.network = StreamPlot::new(samples);
+        self
+    }
+
+    /// Adds a sample to the history. [`next_frame`](FrameSource::next_frame)
+    /// does this with a fresh one each frame.
+    pub fn push(&mut self, sample: SystemSample) {
+        self.cpu.push(&[sample.cpu]);
+        self.memory.push(&[sample.memory_percent()]);
+        self.network
+            .push(&[sample.received / 1024.0, sample.transmitted / 1024.0]);
+        self.latest = Some(sample);
+    }
+
+    /// The most recent sample.
+    #[must_use]
+    pub const fn latest(&self) -> Option<&SystemSample> {
+        self.latest.as_ref()
+    }
+
+    /// Draws the dashboard over all of `grid`: CPU, memory, and network
+    /// charts stacked on the left, and per-core gauges on the right if the
+    /// grid is at least 60 cells wide.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if the grid is too small
+    /// to fit the charts, at least 9 rows.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(&self, grid: &mut BrailleGrid) -> Result<(), DotmaxError> {
+        let (width, height) = grid.dimensions();
+        let gauge_width = if width >= GAUGE_MIN_WIDTH {
+            width / 3
+        } else {
+            0
+        };
+        let chart_width = width - gauge_width;
+        let chart_height = height / 3;
+        let sample = self.latest.clone().unwrap_or_default();
+
+        self.cpu
+            .chart()
+            .title(format!("CPU {:.1}%", sample.cpu))
+            .y_range(0.0..100.0)
+            .render(grid, 0, 0, chart_width, chart_height)?;
+        self.memory
+            .chart()
+            .title(format!(
+                "Memory {} of {} ({:.0}%)",
+                format_bytes(sample.memory_used as f64),
+                format_bytes(sample.memory_total as f64),
+                sample.memory_percent()
+            ))
+            .y_range(0.0..100.0)
+            .render(grid, 0, chart_height, chart_width, chart_height)?;
+        self.network
+            .chart()
+            .title(format!(
+                "Network in {}/s, out {}/s",
+                format_bytes(sample.received),
+                format_bytes(sample.transmitted)
+            ))
+            .render(
+                grid,
+                0,
+                chart_height * 2,
+                chart_width,
+                height - chart_height * 2,
+            )?;
+
+        if gauge_width > 0 {
+            BarChart::new()
+                .bars(
+                    sample
+                        .cores
+                        .iter()
+                        .enumerate()
+                        .map(|(core, &usage)| (core.to_string(), usage)),
+                )
+                .title("Cores %")
+                .color(Color::rgb(90, 200, 250))
+                .y_range(0.0..100.0)
+                .render(grid, chart_width, 0, gauge_width, height)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for SystemMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameSource for SystemMonitor {
+    fn next_frame(
+        &mut self,
+        width: usize,
+        height: usize,
+    ) -> Option<Result<(BrailleGrid, Duration), DotmaxError>> {
+        let sample = self.metrics.sample();
+        self.push(sample);
+        let frame = BrailleGrid::new(width, height).and_then(|mut grid| {
+            self.render(&mut grid)?;
+            Ok((grid, self.interval))
+        });
+        Some(frame)
+    }
+}
+
+/// `bytes` in B, KiB, MiB, or GiB, whichever keeps it under 1024.
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_from_samples() {
+        let mut monitor = SystemMonitor::new().history(10);
+        for i in 0..12 {
+            monitor.push(SystemSample {
+                cpu: f64::from(i) * 5.0,
+                cores: vec![10.0, 90.0],
+                memory_used: 3 << 30,
+                memory_total: 12 << 30,
+                received: 2048.0,
+                transmitted: 512.0,
+            });
+        }
+        assert_eq!(monitor.latest().unwrap().memory_percent(), 25.0);
+
+        let mut grid = BrailleGrid::new(90, 30).unwrap();
+        monitor.render(&mut grid).unwrap();
+        let row = |y: usize| (0..90).map(|x| grid.get_char(x, y)).collect::<String>();
+        assert!(row(0).starts_with("CPU 55.0%"));
+        assert!(row(0).contains("Cores %"));
+        assert!(row(10).starts_with("Memory 3.0 GiB of 12.0 GiB (25%)"));
+        assert!(row(20).starts_with("Network in 2.0 KiB/s, out 512 B/s"));
+
+        // Narrow grids leave the gauges out
+        let mut narrow = BrailleGrid::new(40, 9).unwrap();
+        monitor.render(&mut narrow).unwrap();
+        assert!(BrailleGrid::new(40, 6)
+            .and_then(|mut small| monitor.render(&mut small))
+            .is_err());
+    }
+
+    #[test]
+    fn test_live_frame() {
+        let mut monitor = SystemMonitor::new().interval(Duration::ZERO);
+        let (grid, delay) = monitor.next_frame(80, 24).unwrap().unwrap();
+        assert_eq!(grid.dimensions(), (80, 24));
+        assert_eq!(delay, MINIMUM_CPU_UPDATE_INTERVAL);
+        assert!(monitor.latest().unwrap().memory_total > 0);
+    }
+}
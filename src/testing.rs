@@ -0,0 +1,468 @@
+//! Soak tests: running an animation for hours to catch slow leaks.
+//!
+//! A kiosk or dashboard left running for weeks fails in ways a quick test
+//! never sees: a cache that grows by a few bytes a frame, a file opened
+//! each minute and never closed, a frame that takes a little longer every
+//! hour. [`soak`] runs an [`AnimationLoop`] headless for a given time, at
+//! its target FPS, rendering every frame to a sink as a terminal would be
+//! sent it, and measures those three things:
+//!
+//! - **Memory**: resident set size after a warm-up, at the end, and at its
+//!   peak.
+//! - **Frame time**: the mean time spent drawing and encoding a frame (not
+//!   waiting for the next one) just after the warm-up and at the end.
+//! - **Descriptors**: open file descriptors after the warm-up and at the
+//!   end.
+//!
+//! The warm-up is the first tenth of the run, so caches filled on the first
+//! frames don't count as growth. [`SoakReport::assert_within`] then checks
+//! the results against a [`SoakBudget`].
+//!
+//! Memory and descriptors are read from `/proc/self` on Linux (descriptors
+//! also from `/dev/fd` on macOS); where neither exists they are `None` and
+//! their budgets pass.
+//!
+//! Soak tests are opt-in: nothing runs them unless asked, and long ones
+//! belong behind `#[ignore]` so `cargo test` stays fast.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use dotmax::animation::AnimationLoop;
+//! use dotmax::testing::{soak, SoakBudget};
+//!
+//! // In a test marked #[ignore = "runs for two hours"]
+//! let mut animation = AnimationLoop::new(120, 40).fps(30).on_frame(|frame, grid| {
+//!     grid.set_dot(frame as usize % 240, 80)?;
+//!     Ok(true)
+//! });
+//! let report = soak(&mut animation, Duration::from_secs(2 * 60 * 60))?;
+//! println!("{report}");
+//! report.assert_within(&SoakBudget::default());
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+use std::io;
+use std::time::{Duration, Instant};
+
+use tracing::info;
+
+use crate::animation::{AnimationLoop, FrameBuffer, FrameTimer};
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use crate::input::InputEvent;
+use crate::render::writer::WriterBackend;
+
+/// Number of equal slices a soak is split into; the first is the warm-up.
+const WINDOWS: usize = 10;
+
+/// How often memory and descriptors are sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often progress is logged.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The most a [`SoakReport`] may show before
+/// [`assert_within`](SoakReport::assert_within) fails.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakBudget {
+    /// Resident memory growth after the warm-up, in bytes
+    pub max_memory_growth: u64,
+    /// How much slower the last frames may be than the first, as a
+    /// fraction: 0.5 allows 50% slower
+    pub max_frame_time_drift: f64,
+    /// Descriptors opened after the warm-up and still open at the end
+    pub max_descriptor_growth: usize,
+}
+
+impl Default for SoakBudget {
+    /// 16 MiB of memory growth, 50% frame time drift, no leaked
+    /// descriptors.
+    fn default() -> Self {
+        Self {
+            max_memory_growth: 16 * 1024 * 1024,
+            max_frame_time_drift: 0.5,
+            max_descriptor_growth: 0,
+        }
+    }
+}
+
+/// What [`soak`] measured.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoakReport {
+    /// Frames drawn
+    pub frames: u64,
+    /// How long the soak ran
+    pub elapsed: Duration,
+    /// Whether the frame callback stopped the loop before time was up
+    pub stopped_early: bool,
+    /// Resident memory after the warm-up, in bytes
+    pub memory_start: Option<u64>,
+    /// Resident memory at the end, in bytes
+    pub memory_end: Option<u64>,
+    /// Highest resident memory sampled, in bytes
+    pub memory_peak: Option<u64>,
+    /// Mean time to draw and encode a frame just after the warm-up
+    pub frame_time_start: Duration,
+    /// Mean time to draw and encode a frame at the end
+    pub frame_time_end: Duration,
+    /// Open descriptors after the warm-up
+    pub descriptors_start: Option<usize>,
+    /// Open descriptors at the end
+    pub descriptors_end: Option<usize>,
+}
+
+impl SoakReport {
+    /// Bytes of resident memory gained after the warm-up (negative if it
+    /// shrank), if memory could be measured.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)] // Resident memory is far below 2^63
+    pub fn memory_growth(&self) -> Option<i64> {
+        Some(self.memory_end? as i64 - self.memory_start? as i64)
+    }
+
+    /// How much slower the last frames were than the first, as a fraction
+    /// (negative if faster). Zero when no frames were timed.
+    #[must_use]
+    pub fn frame_time_drift(&self) -> f64 {
+        let start = self.frame_time_start.as_secs_f64();
+        if start == 0.0 {
+            0.0
+        } else {
+            self.frame_time_end.as_secs_f64() / start - 1.0
+        }
+    }
+
+    /// Descriptors opened after the warm-up and still open at the end
+    /// (negative if some were closed), if they could be counted.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)] // Descriptor counts are small
+    pub fn descriptor_growth(&self) -> Option<isize> {
+        Some(self.descriptors_end? as isize - self.descriptors_start? as isize)
+    }
+
+    /// A description of each way this report exceeds `budget`; empty if it
+    /// doesn't.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
+    pub fn violations(&self, budget: &SoakBudget) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(growth) = self.memory_growth() {
+            if growth > budget.max_memory_growth.min(i64::MAX as u64) as i64 {
+                violations.push(format!(
+                    "memory grew by {} KiB (budget {} KiB)",
+                    growth / 1024,
+                    budget.max_memory_growth / 1024
+                ));
+            }
+        }
+        let drift = self.frame_time_drift();
+        if drift > budget.max_frame_time_drift {
+            violations.push(format!(
+                "frame time drifted {:+.0}% ({:?} to {:?}, budget {:+.0}%)",
+                drift * 100.0,
+                self.frame_time_start,
+                self.frame_time_end,
+                budget.max_frame_time_drift * 100.0
+            ));
+        }
+        if let Some(growth) = self.descriptor_growth() {
+            if growth > budget.max_descriptor_growth as isize {
+                violations.push(format!(
+                    "{growth} file descriptors leaked (budget {})",
+                    budget.max_descriptor_growth
+                ));
+            }
+        }
+        violations
+    }
+
+    /// Panics, listing every violation and the report, if this report
+    /// exceeds `budget`.
+    ///
+    /// # Panics
+    ///
+    /// If [`violations`](Self::violations) isn't empty.
+    pub fn assert_within(&self, budget: &SoakBudget) {
+        let violations = self.violations(budget);
+        assert!(
+            violations.is_empty(),
+            "soak test over budget: {}\n{self}",
+            violations.join("; ")
+        );
+    }
+}
+
+/// `soak: 108000 frames in 3600.0s, memory 12.1 MiB -> 12.3 MiB (peak
+/// 12.4 MiB), frame time 410µs -> 415µs, descriptors 5 -> 5`
+impl fmt::Display for SoakReport {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mib = |bytes: Option<u64>| {
+            bytes.map_or_else(
+                || "?".to_string(),
+                |bytes| format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+            )
+        };
+        let count = |n: Option<usize>| n.map_or_else(|| "?".to_string(), |n| n.to_string());
+        write!(
+            f,
+            "soak: {} frames in {:.1}s{}, memory {} -> {} (peak {}), frame time {:?} -> {:?}, \
+             descriptors {} -> {}",
+            self.frames,
+            self.elapsed.as_secs_f64(),
+            if self.stopped_early {
+                " (stopped early)"
+            } else {
+                ""
+            },
+            mib(self.memory_start),
+            mib(self.memory_end),
+            mib(self.memory_peak),
+            self.frame_time_start,
+            self.frame_time_end,
+            count(self.descriptors_start),
+            count(self.descriptors_end),
+        )
+    }
+}
+
+/// Runs `animation` headless for `duration` and reports how its memory,
+/// frame time, and open descriptors changed.
+///
+/// Frames are drawn at the loop's target FPS, from frame 0, and written as
+/// ANSI output to [`io::sink`]; no terminal is touched and the event
+/// handler is never called. The soak ends early if the frame callback
+/// returns `Ok(false)`.
+///
+/// # Errors
+///
+/// Returns the frame callback's error, which ends the soak.
+pub fn soak<F, H>(
+    animation: &mut AnimationLoop<F, H>,
+    duration: Duration,
+) -> Result<SoakReport, DotmaxError>
+where
+    F: FnMut(u64, &mut BrailleGrid) -> Result<bool, DotmaxError>,
+    H: FnMut(&InputEvent) -> Result<bool, DotmaxError>,
+{
+    info!(
+        width = animation.width(),
+        height = animation.height(),
+        fps = animation.target_fps(),
+        seconds = duration.as_secs(),
+        "Starting soak test"
+    );
+    let mut frame_buffer = FrameBuffer::new(animation.width(), animation.height());
+    let mut timer = FrameTimer::new(animation.target_fps());
+    let mut backend = WriterBackend::new(io::sink());
+
+    // Total frame work time and frame count in each slice of the run
+    let mut windows = [(Duration::ZERO, 0_u32); WINDOWS];
+    #[allow(clippy::cast_possible_truncation)] // WINDOWS is small
+    let warm_up = duration / WINDOWS as u32;
+    let mut memory_start = None;
+    let mut descriptors_start = None;
+    let mut memory_peak = resident_memory();
+    let mut next_sample = Instant::now();
+    let mut next_progress = Instant::now() + PROGRESS_INTERVAL;
+    let mut warmed_up = warm_up.is_zero();
+    if warmed_up {
+        memory_start = memory_peak;
+        descriptors_start = open_descriptors();
+    }
+
+    let started = Instant::now();
+    let mut frames = 0;
+    let mut stopped_early = false;
+    while started.elapsed() < duration {
+        let work = Instant::now();
+        let back = frame_buffer.get_back_buffer();
+        back.clear();
+        if !animation.draw_frame(frames, back)? {
+            stopped_early = true;
+            break;
+        }
+        frame_buffer.swap_buffers();
+        backend.draw(frame_buffer.get_front_buffer())?;
+        let window = window_of(started.elapsed(), duration);
+        windows[window].0 += work.elapsed();
+        windows[window].1 += 1;
+        frames += 1;
+
+        let now = Instant::now();
+        if now >= next_sample {
+            let memory = resident_memory();
+            memory_peak = memory_peak.max(memory);
+            if !warmed_up && now - started >= warm_up {
+                warmed_up = true;
+                memory_start = memory;
+                descriptors_start = open_descriptors();
+            }
+            next_sample = now + SAMPLE_INTERVAL;
+        }
+        if now >= next_progress {
+            info!(frames, elapsed = ?now - started, memory = ?memory_peak, "Soak test running");
+            next_progress = now + PROGRESS_INTERVAL;
+        }
+        timer.wait_for_next_frame();
+    }
+
+    let memory_end = resident_memory();
+    let report = SoakReport {
+        frames,
+        elapsed: started.elapsed(),
+        stopped_early,
+        // A soak stopped during the warm-up is measured from its start
+        memory_start: if warmed_up { memory_start } else { memory_peak },
+        memory_end,
+        memory_peak: memory_peak.max(memory_end),
+        frame_time_start: first_timed(windows.iter().skip(1).chain(&windows[..1])),
+        frame_time_end: first_timed(windows.iter().rev()),
+        descriptors_start: if warmed_up {
+            descriptors_start
+        } else {
+            open_descriptors()
+        },
+        descriptors_end: open_descriptors(),
+    };
+    info!(%report, "Soak test finished");
+    Ok(report)
+}
+
+/// The slice of a run of length `duration` that `elapsed` falls in.
+fn window_of(elapsed: Duration, duration: Duration) -> usize {
+    if duration.is_zero() {
+        return WINDOWS - 1;
+    }
+    let fraction = elapsed.as_secs_f64() / duration.as_secs_f64();
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    let window = (fraction * WINDOWS as f64) as usize;
+    window.min(WINDOWS - 1)
+}
+
+/// Mean frame time of the first window with frames in it.
+fn first_timed<'a>(windows: impl Iterator<Item = &'a (Duration, u32)>) -> Duration {
+    windows
+        .filter(|(_, count)| *count > 0)
+        .map(|&(total, count)| total / count)
+        .next()
+        .unwrap_or_default()
+}
+
+/// This process's resident set size in bytes, where `/proc` reports it.
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// How many file descriptors this process has open, where the system lists
+/// them.
+fn open_descriptors() -> Option<usize> {
+    ["/proc/self/fd", "/dev/fd"]
+        .into_iter()
+        .find_map(|dir| std::fs::read_dir(dir).ok())
+        .map(Iterator::count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> SoakReport {
+        SoakReport {
+            frames: 100,
+            elapsed: Duration::from_secs(10),
+            stopped_early: false,
+            memory_start: Some(10 << 20),
+            memory_end: Some(11 << 20),
+            memory_peak: Some(12 << 20),
+            frame_time_start: Duration::from_micros(400),
+            frame_time_end: Duration::from_micros(500),
+            descriptors_start: Some(5),
+            descriptors_end: Some(5),
+        }
+    }
+
+    #[test]
+    fn test_growth_and_drift() {
+        let report = report();
+        assert_eq!(report.memory_growth(), Some(1 << 20));
+        assert!((report.frame_time_drift() - 0.25).abs() < 1e-9);
+        assert_eq!(report.descriptor_growth(), Some(0));
+        assert!(report.violations(&SoakBudget::default()).is_empty());
+        report.assert_within(&SoakBudget::default());
+    }
+
+    #[test]
+    fn test_violations_name_each_budget() {
+        let mut report = report();
+        report.memory_end = Some(40 << 20);
+        report.frame_time_end = Duration::from_millis(1);
+        report.descriptors_end = Some(7);
+        let violations = report.violations(&SoakBudget::default());
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].starts_with("memory grew by 30720 KiB"));
+        assert!(violations[1].starts_with("frame time drifted +150%"));
+        assert_eq!(violations[2], "2 file descriptors leaked (budget 0)");
+    }
+
+    #[test]
+    #[should_panic(expected = "soak test over budget")]
+    fn test_assert_within_panics_over_budget() {
+        let mut report = report();
+        report.descriptors_end = Some(6);
+        report.assert_within(&SoakBudget::default());
+    }
+
+    #[test]
+    fn test_unmeasured_budgets_pass() {
+        let mut report = report();
+        report.memory_start = None;
+        report.descriptors_end = None;
+        report.frame_time_start = Duration::ZERO;
+        assert!(report.violations(&SoakBudget::default()).is_empty());
+    }
+
+    #[test]
+    fn test_short_soak_runs_headless() {
+        let mut animation = AnimationLoop::new(20, 5).fps(240).on_frame(|frame, grid| {
+            grid.set_dot(frame as usize % 40, 10)?;
+            Ok(true)
+        });
+        let report = soak(&mut animation, Duration::from_millis(200)).unwrap();
+        assert!(report.frames > 0);
+        assert!(!report.stopped_early);
+        assert!(report.frame_time_start > Duration::ZERO);
+        if cfg!(target_os = "linux") {
+            assert!(report.memory_peak.is_some());
+            assert!(report.descriptors_end.is_some());
+        }
+    }
+
+    #[test]
+    fn test_callback_can_end_soak() {
+        let mut animation = AnimationLoop::new(4, 2)
+            .fps(240)
+            .on_frame(|frame, _| Ok(frame < 3));
+        let report = soak(&mut animation, Duration::from_secs(60)).unwrap();
+        assert_eq!(report.frames, 3);
+        assert!(report.stopped_early);
+    }
+
+    #[test]
+    fn test_windows() {
+        let duration = Duration::from_secs(10);
+        assert_eq!(window_of(Duration::ZERO, duration), 0);
+        assert_eq!(window_of(Duration::from_millis(1500), duration), 1);
+        assert_eq!(window_of(Duration::from_secs(11), duration), WINDOWS - 1);
+    }
+}
@@ -0,0 +1,488 @@
+//! Text banners rasterized from TTF/OTF fonts.
+//!
+//! Where [`primitives::text`](crate::primitives::text) draws from small
+//! built-in bitmap fonts, this module renders any TrueType or OpenType font
+//! at any pixel size, so titles and figlet-style banners keep the font's
+//! shapes at braille resolution. A [`Banner`] lays out one or more lines
+//! with the font's kerning and places them relative to an anchor point.
+//!
+//! Sizes are in dots: a banner at size 32 is about 32 dots (8 cells) from
+//! ascender to descender.
+//!
+//! Requires the `text` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::text::{Align, Banner, Font, VerticalAlign};
+//! use dotmax::BrailleGrid;
+//!
+//! let font = Font::from_path("tests/fixtures/fonts/Tuffy.ttf")?;
+//!
+//! // A grid sized to fit
+//! let title = Banner::new(&font, "dotmax").size(24.0).render()?;
+//!
+//! // Or centered on a point of an existing grid
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! Banner::new(&font, "Game\nOver")
+//!     .size(16.0)
+//!     .align(Align::Center)
+//!     .vertical_align(VerticalAlign::Middle)
+//!     .draw(&mut grid, 40, 20)?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+use std::path::Path;
+
+use ab_glyph::{point, Font as _, FontArc, Glyph, PxScale, ScaleFont};
+
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+/// Largest [`Banner::size`], in dots
+pub const MAX_FONT_SIZE: f32 = 1000.0;
+
+/// A loaded TTF/OTF font. Cloning is cheap; clones share the font data.
+#[derive(Clone)]
+pub struct Font(FontArc);
+
+impl Font {
+    /// Parses font data, such as the contents of a `.ttf` or `.otf` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::FontError`] if `data` isn't a valid font.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, DotmaxError> {
+        FontArc::try_from_vec(data)
+            .map(Self)
+            .map_err(|e| DotmaxError::FontError(e.to_string()))
+    }
+
+    /// Reads and parses a font file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::FontError`] if the file can't be read or isn't
+    /// a valid font.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DotmaxError> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .map_err(|e| DotmaxError::FontError(format!("{}: {e}", path.display())))?;
+        Self::from_bytes(data).map_err(|e| match e {
+            DotmaxError::FontError(message) => {
+                DotmaxError::FontError(format!("{}: {message}", path.display()))
+            }
+            other => other,
+        })
+    }
+
+    /// Whether the font has a glyph for `c`. Missing characters are drawn
+    /// as the font's fallback glyph, often an empty box.
+    #[must_use]
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.0.glyph_id(c).0 != 0
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("glyphs", &self.0.glyph_count())
+            .finish()
+    }
+}
+
+/// Which point of a [`Banner`] lines up with the anchor horizontally, and
+/// how lines of different widths line up with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Align {
+    /// Left edges at the anchor
+    #[default]
+    Left,
+    /// Centers at the anchor
+    Center,
+    /// Right edges at the anchor
+    Right,
+}
+
+/// Which point of a [`Banner`] lines up with the anchor vertically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VerticalAlign {
+    /// Top of the first line's ascent
+    #[default]
+    Top,
+    /// Middle of the whole block
+    Middle,
+    /// Baseline of the first line
+    Baseline,
+    /// Bottom of the last line's descent
+    Bottom,
+}
+
+/// One or more lines of text in a [`Font`], ready to draw.
+///
+/// `\n` starts a new line. Nothing wraps.
+#[derive(Debug, Clone)]
+pub struct Banner {
+    font: Font,
+    text: String,
+    size: f32,
+    align: Align,
+    vertical_align: VerticalAlign,
+    kerning: bool,
+    line_spacing: f32,
+    threshold: f32,
+    color: Option<Color>,
+}
+
+/// Glyphs positioned along their line's baseline, starting at x = 0.
+struct Line {
+    glyphs: Vec<Glyph>,
+    width: f32,
+}
+
+/// A laid-out banner, in dots relative to its top-left corner.
+struct Layout {
+    lines: Vec<Line>,
+    width: f32,
+    height: f32,
+    ascent: f32,
+    line_height: f32,
+}
+
+impl Banner {
+    /// `text` in `font` at 16 dots, left- and top-aligned, kerned.
+    #[must_use]
+    pub fn new(font: &Font, text: impl Into<String>) -> Self {
+        Self {
+            font: font.clone(),
+            text: text.into(),
+            size: 16.0,
+            align: Align::Left,
+            vertical_align: VerticalAlign::Top,
+            kerning: true,
+            line_spacing: 1.0,
+            threshold: 0.5,
+            color: None,
+        }
+    }
+
+    /// Font size in dots, from ascender to descender. Must be greater than
+    /// zero and at most [`MAX_FONT_SIZE`]; checked when drawing.
+    #[must_use]
+    pub const fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Horizontal alignment to the anchor, and of lines to each other.
+    #[must_use]
+    pub const fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Vertical alignment to the anchor.
+    #[must_use]
+    pub const fn vertical_align(mut self, vertical_align: VerticalAlign) -> Self {
+        self.vertical_align = vertical_align;
+        self
+    }
+
+    /// Whether to apply the font's kerning pairs. On by default.
+    #[must_use]
+    pub const fn kerning(mut self, kerning: bool) -> Self {
+        self.kerning = kerning;
+        self
+    }
+
+    /// Multiplier on the font's line height for multi-line text.
+    /// Defaults to 1.0.
+    #[must_use]
+    pub const fn line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// How much of a dot a glyph must cover for the dot to be set, from 0.0
+    /// to 1.0. Lower values draw bolder strokes. Defaults to 0.5.
+    #[must_use]
+    pub const fn threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Color the cells the text touches.
+    #[must_use]
+    pub const fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Width and height in dots of the laid-out text, rounded up.
+    #[must_use]
+    pub fn measure(&self) -> (usize, usize) {
+        let layout = self.layout();
+        (dots(layout.width), dots(layout.height))
+    }
+
+    /// Draws the banner with its anchor point at dot `(x, y)`; see
+    /// [`Align`] and [`VerticalAlign`] for which point that is. Dots off the
+    /// grid are clipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the size or threshold
+    /// is out of range.
+    pub fn draw(&self, grid: &mut BrailleGrid, x: i32, y: i32) -> Result<(), DotmaxError> {
+        self.validate()?;
+        let layout = self.layout();
+        #[allow(clippy::cast_precision_loss)]
+        let (x, y) = (x as f32, y as f32);
+        let left = match self.align {
+            Align::Left => x,
+            Align::Center => x - layout.width / 2.0,
+            Align::Right => x - layout.width,
+        };
+        let top = match self.vertical_align {
+            VerticalAlign::Top => y,
+            VerticalAlign::Middle => y - layout.height / 2.0,
+            VerticalAlign::Baseline => y - layout.ascent,
+            VerticalAlign::Bottom => y - layout.height,
+        };
+        self.draw_block(grid, &layout, left, top);
+        Ok(())
+    }
+
+    /// Renders the banner into a new grid just big enough to hold it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if the size or threshold
+    /// is out of range.
+    pub fn render(&self) -> Result<BrailleGrid, DotmaxError> {
+        self.validate()?;
+        let layout = self.layout();
+        let (width, height) = (dots(layout.width), dots(layout.height));
+        let mut grid = BrailleGrid::new((width + 1).max(2) / 2, (height + 3).max(4) / 4)?;
+        self.draw_block(&mut grid, &layout, 0.0, 0.0);
+        Ok(grid)
+    }
+
+    /// Draws `layout` with its top-left corner at dot `(left, top)`.
+    fn draw_block(&self, grid: &mut BrailleGrid, layout: &Layout, left: f32, top: f32) {
+        // Safe casts: grid dimensions are bounded by terminal size (<10000)
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let (max_x, max_y) = (grid.dot_width() as i32, grid.dot_height() as i32);
+        #[allow(clippy::cast_precision_loss)]
+        for (index, line) in layout.lines.iter().enumerate() {
+            let baseline = (index as f32).mul_add(layout.line_height, top + layout.ascent);
+            let indent = match self.align {
+                Align::Left => 0.0,
+                Align::Center => (layout.width - line.width) / 2.0,
+                Align::Right => layout.width - line.width,
+            };
+            for glyph in &line.glyphs {
+                let mut glyph = glyph.clone();
+                glyph.position = point(left + indent + glyph.position.x, baseline);
+                let Some(outline) = self.font.0.outline_glyph(glyph) else {
+                    continue; // Whitespace
+                };
+                let bounds = outline.px_bounds();
+                #[allow(clippy::cast_possible_truncation)]
+                let (origin_x, origin_y) = (bounds.min.x as i32, bounds.min.y as i32);
+                outline.draw(|gx, gy, coverage| {
+                    if coverage < self.threshold {
+                        return;
+                    }
+                    #[allow(clippy::cast_possible_wrap)]
+                    let (dot_x, dot_y) = (origin_x + gx as i32, origin_y + gy as i32);
+                    if dot_x < 0 || dot_x >= max_x || dot_y < 0 || dot_y >= max_y {
+                        return;
+                    }
+                    // Safe to convert to usize - we checked both are >= 0
+                    #[allow(clippy::cast_sign_loss)]
+                    let (dot_x, dot_y) = (dot_x as usize, dot_y as usize);
+                    let _ = grid.set_dot(dot_x, dot_y);
+                    if let Some(color) = self.color {
+                        let _ = grid.set_cell_color(dot_x / 2, dot_y / 4, color);
+                    }
+                });
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), DotmaxError> {
+        if !(self.size > 0.0 && self.size <= MAX_FONT_SIZE) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "font size".to_string(),
+                value: self.size.to_string(),
+                min: "0 (exclusive)".to_string(),
+                max: MAX_FONT_SIZE.to_string(),
+            });
+        }
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "coverage threshold".to_string(),
+                value: self.threshold.to_string(),
+                min: "0.0".to_string(),
+                max: "1.0".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn layout(&self) -> Layout {
+        let font = self.font.0.as_scaled(PxScale::from(self.size));
+        let ascent = font.ascent();
+        let glyph_height = ascent - font.descent();
+        let line_height = (glyph_height + font.line_gap()) * self.line_spacing;
+
+        let lines: Vec<Line> = self
+            .text
+            .lines()
+            .map(|text| {
+                let mut caret = 0.0;
+                let mut previous = None;
+                let mut glyphs = Vec::new();
+                for c in text.chars() {
+                    let id = font.glyph_id(c);
+                    if let (true, Some(previous)) = (self.kerning, previous) {
+                        caret += font.kern(previous, id);
+                    }
+                    glyphs.push(id.with_scale_and_position(font.scale(), point(caret, 0.0)));
+                    caret += font.h_advance(id);
+                    previous = Some(id);
+                }
+                Line {
+                    glyphs,
+                    width: caret,
+                }
+            })
+            .collect();
+
+        let width = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        #[allow(clippy::cast_precision_loss)]
+        let height = match lines.len() {
+            0 => 0.0,
+            n => ((n - 1) as f32).mul_add(line_height, glyph_height),
+        };
+        Layout {
+            lines,
+            width,
+            height,
+            ascent,
+            line_height,
+        }
+    }
+}
+
+/// A length in dots, rounded up to whole dots.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn dots(length: f32) -> usize {
+    length.max(0.0).ceil() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuffy() -> Font {
+        Font::from_path("tests/fixtures/fonts/Tuffy.ttf").unwrap()
+    }
+
+    /// Leftmost and rightmost set dot columns, if any dot is set.
+    fn dot_columns(grid: &BrailleGrid) -> Option<(usize, usize)> {
+        let columns: Vec<usize> = (0..grid.dot_width())
+            .filter(|&x| (0..grid.dot_height()).any(|y| grid.is_dot_set(x, y)))
+            .collect();
+        Some((*columns.first()?, *columns.last()?))
+    }
+
+    #[test]
+    fn test_invalid_font_data() {
+        assert!(matches!(
+            Font::from_bytes(b"not a font".to_vec()),
+            Err(DotmaxError::FontError(_))
+        ));
+        let err = Font::from_path("tests/fixtures/fonts/missing.ttf").unwrap_err();
+        assert!(err.to_string().contains("missing.ttf"));
+    }
+
+    #[test]
+    fn test_render_fits_measurement() {
+        let font = tuffy();
+        assert!(font.has_glyph('A'));
+        let banner = Banner::new(&font, "Hello").size(24.0);
+        let (width, height) = banner.measure();
+        assert!((20..=24).contains(&height), "height {height}");
+        let grid = banner.render().unwrap();
+        assert_eq!(grid.dimensions(), ((width + 1) / 2, (height + 3) / 4));
+        assert!(dot_columns(&grid).is_some());
+
+        let (_, two_lines) = Banner::new(&font, "Hello\nthere").size(24.0).measure();
+        assert!(two_lines > height * 2 - 2);
+        assert_eq!(Banner::new(&font, "").measure(), (0, 0));
+    }
+
+    #[test]
+    fn test_alignment_to_anchor() {
+        let font = tuffy();
+        let banner = Banner::new(&font, "Right").size(16.0);
+        let (width, _) = banner.measure();
+
+        let mut grid = BrailleGrid::new(40, 5).unwrap();
+        banner
+            .clone()
+            .align(Align::Right)
+            .draw(&mut grid, 60, 0)
+            .unwrap();
+        let (left, right) = dot_columns(&grid).unwrap();
+        assert!(right < 60 && left >= 60 - width);
+
+        let mut grid = BrailleGrid::new(40, 5).unwrap();
+        banner.align(Align::Center).draw(&mut grid, 40, 0).unwrap();
+        let (left, right) = dot_columns(&grid).unwrap();
+        assert!(left.abs_diff(80 - right) <= 3, "{left}..{right}");
+    }
+
+    #[test]
+    fn test_vertical_alignment() {
+        let font = tuffy();
+        let banner = Banner::new(&font, "x").size(16.0);
+        let mut top = BrailleGrid::new(10, 10).unwrap();
+        banner.clone().draw(&mut top, 0, 0).unwrap();
+        let mut bottom = BrailleGrid::new(10, 10).unwrap();
+        let (_, height) = banner.measure();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let y = height as i32 + 20;
+        banner
+            .vertical_align(VerticalAlign::Bottom)
+            .draw(&mut bottom, 0, y)
+            .unwrap();
+        let lowest = |grid: &BrailleGrid| {
+            (0..grid.dot_height())
+                .rev()
+                .find(|&y| (0..grid.dot_width()).any(|x| grid.is_dot_set(x, y)))
+                .unwrap()
+        };
+        assert_eq!(lowest(&bottom), lowest(&top) + 20);
+    }
+
+    #[test]
+    fn test_out_of_range_settings() {
+        let font = tuffy();
+        let mut grid = BrailleGrid::new(4, 4).unwrap();
+        for banner in [
+            Banner::new(&font, "a").size(0.0),
+            Banner::new(&font, "a").size(f32::NAN),
+            Banner::new(&font, "a").threshold(1.5),
+        ] {
+            assert!(matches!(
+                banner.draw(&mut grid, 0, 0),
+                Err(DotmaxError::InvalidParameter { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,529 @@
+//! Braille cell metrics and dot-aspect calibration.
+//!
+//! A braille cell is 2 dots wide and 4 dots tall. With a typical 1:2 terminal
+//! font, one dot step is as tall as it is wide, so a 20×20-dot square looks
+//! square. In practice fonts draw braille glyphs with their own margins and
+//! dot spacing, so the *visual* step between dots is often taller or wider
+//! than that ideal and circles come out as ellipses.
+//!
+//! [`CellMetrics`] records that correction as a single number, the **dot
+//! aspect**: the visual height of one vertical dot step divided by the visual
+//! width of one horizontal dot step. `1.0` is ideal; `1.2` means dots are
+//! spaced 20% further apart vertically than horizontally.
+//!
+//! Terminals that report their size in pixels (`TIOCGWINSZ` on Unix) give
+//! the cell's pixel size, and with it the *geometric* dot aspect:
+//! [`CellMetrics::from_terminal`] reads it, and a [`CellSizeWatcher`] notices
+//! when it changes, as it does when the user zooms the font. This ignores
+//! the font's own glyph margins, so where the terminal doesn't report pixels
+//! or the result still looks off, the dot aspect is measured with the
+//! user's help instead: [`render_calibration_pattern`] draws a row of candidate
+//! squares, the user picks the one that looks square, and
+//! [`CellMetrics::from_candidate`] turns that choice into metrics.
+//! [`calibrate`] runs the whole routine interactively. Store the result with
+//! [`set_cell_metrics`]; aspect-aware drawing such as
+//! [`draw_circle_aspect`](crate::primitives::draw_circle_aspect) reads it back
+//! through [`cell_metrics`].
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::utils::cell_metrics::{cell_metrics, set_cell_metrics, CellMetrics};
+//!
+//! // The user picked the candidate drawn for a 1.25 dot aspect
+//! let metrics = CellMetrics::new(1.25)?;
+//! set_cell_metrics(metrics);
+//!
+//! // A 40-dot-wide shape needs 32 dots of height to look square
+//! assert_eq!(cell_metrics().square_height(40.0), 32.0);
+//! # set_cell_metrics(CellMetrics::default());
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+use crate::primitives::draw_rectangle;
+use crate::render::TerminalRenderer;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tracing::{debug, info};
+
+/// Smallest accepted dot aspect
+pub const MIN_DOT_ASPECT: f32 = 0.5;
+
+/// Largest accepted dot aspect
+pub const MAX_DOT_ASPECT: f32 = 2.0;
+
+/// Dot aspects drawn by [`render_calibration_pattern`], left to right.
+///
+/// Spaced in roughly 10% steps around the ideal 1.0, which covers the fonts
+/// seen in practice; the pattern is labelled 1-7 in this order.
+pub const CALIBRATION_CANDIDATES: [f32; 7] = [0.75, 0.85, 0.93, 1.0, 1.08, 1.17, 1.3];
+
+/// Measured braille dot spacing for the current terminal font.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CellMetrics {
+    dot_aspect: f32,
+}
+
+impl Default for CellMetrics {
+    /// Ideal square dot spacing (dot aspect 1.0).
+    fn default() -> Self {
+        Self { dot_aspect: 1.0 }
+    }
+}
+
+impl CellMetrics {
+    /// Create metrics from a measured dot aspect.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidParameter`] if `dot_aspect` is not within
+    /// [`MIN_DOT_ASPECT`]..=[`MAX_DOT_ASPECT`].
+    pub fn new(dot_aspect: f32) -> Result<Self, DotmaxError> {
+        if !(MIN_DOT_ASPECT..=MAX_DOT_ASPECT).contains(&dot_aspect) {
+            return Err(DotmaxError::InvalidParameter {
+                parameter_name: "dot aspect".to_string(),
+                value: dot_aspect.to_string(),
+                min: MIN_DOT_ASPECT.to_string(),
+                max: MAX_DOT_ASPECT.to_string(),
+            });
+        }
+        Ok(Self { dot_aspect })
+    }
+
+    /// Metrics for the candidate the user picked from the calibration
+    /// pattern (0-based index into [`CALIBRATION_CANDIDATES`]).
+    ///
+    /// Returns `None` for an out-of-range index.
+    #[must_use]
+    pub fn from_candidate(index: usize) -> Option<Self> {
+        CALIBRATION_CANDIDATES
+            .get(index)
+            .map(|&dot_aspect| Self { dot_aspect })
+    }
+
+    /// Visual height of a vertical dot step divided by the visual width of a
+    /// horizontal one.
+    #[must_use]
+    pub const fn dot_aspect(&self) -> f32 {
+        self.dot_aspect
+    }
+
+    /// Height in dots that looks as tall as `width` dots look wide.
+    #[must_use]
+    pub fn square_height(&self, width: f32) -> f32 {
+        width / self.dot_aspect
+    }
+
+    /// Width in dots that looks as wide as `height` dots look tall.
+    #[must_use]
+    pub fn square_width(&self, height: f32) -> f32 {
+        height * self.dot_aspect
+    }
+
+    /// Practical advice for getting cleaner braille output with this font.
+    ///
+    /// Always returns at least one line; the first line summarizes how far the
+    /// measurement is from ideal.
+    #[must_use]
+    pub fn font_advice(&self) -> Vec<&'static str> {
+        let deviation = (self.dot_aspect - 1.0).abs();
+        let mut advice = Vec::new();
+        if deviation < 0.05 {
+            advice.push("Dot spacing is close to square; no correction needed.");
+        } else if self.dot_aspect > 1.0 {
+            advice.push(
+                "Dots are spaced further apart vertically than horizontally; \
+                 shapes drawn without correction will look tall.",
+            );
+            advice.push(
+                "Reducing the terminal's line spacing (line height) usually brings \
+                 the dot aspect closer to 1.0.",
+            );
+        } else {
+            advice.push(
+                "Dots are spaced further apart horizontally than vertically; \
+                 shapes drawn without correction will look wide.",
+            );
+            advice.push(
+                "Reducing letter spacing or choosing a narrower monospace font \
+                 usually brings the dot aspect closer to 1.0.",
+            );
+        }
+        if deviation >= 0.15 {
+            advice.push(
+                "Fonts with dedicated braille glyphs (rather than a fallback font) \
+                 render dots with more even spacing.",
+            );
+        }
+        advice.push(
+            "Disable ligatures and font fallback substitution for the braille block \
+             (U+2800-U+28FF) if the terminal allows it.",
+        );
+        advice
+    }
+}
+
+// ============================================================================
+// Cell size reported by the terminal
+// ============================================================================
+
+/// Size of one terminal cell in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CellPixelSize {
+    /// Cell width in pixels
+    pub width: u16,
+    /// Cell height in pixels
+    pub height: u16,
+}
+
+/// The cell size in pixels, from the terminal's window size divided by its
+/// columns and rows.
+///
+/// Returns `None` where the terminal reports no pixel size (it reports 0,
+/// or the query fails), which includes most Windows consoles and many
+/// multiplexers.
+#[must_use]
+pub fn query_cell_pixel_size() -> Option<CellPixelSize> {
+    let size = crossterm::terminal::window_size().ok()?;
+    cell_pixel_size(size.width, size.height, size.columns, size.rows)
+}
+
+/// Cell size for a window of `width × height` pixels holding
+/// `columns × rows` cells, if all four are known.
+fn cell_pixel_size(width: u16, height: u16, columns: u16, rows: u16) -> Option<CellPixelSize> {
+    if width == 0 || height == 0 || columns == 0 || rows == 0 {
+        return None;
+    }
+    let cell = CellPixelSize {
+        width: width / columns,
+        height: height / rows,
+    };
+    (cell.width > 0 && cell.height > 0).then_some(cell)
+}
+
+impl CellMetrics {
+    /// Metrics for cells of the given pixel size: a dot step is a quarter
+    /// of the cell's height by half its width.
+    ///
+    /// The result is clamped to [`MIN_DOT_ASPECT`]..=[`MAX_DOT_ASPECT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dotmax::utils::cell_metrics::{CellMetrics, CellPixelSize};
+    ///
+    /// // A 9×21 font: dots are 5.25 px apart vertically, 4.5 px horizontally
+    /// let metrics = CellMetrics::from_cell_pixels(CellPixelSize { width: 9, height: 21 });
+    /// assert!((metrics.dot_aspect() - 21.0 / 18.0).abs() < 1e-6);
+    /// ```
+    #[must_use]
+    pub fn from_cell_pixels(size: CellPixelSize) -> Self {
+        let aspect = f32::from(size.height) / (2.0 * f32::from(size.width.max(1)));
+        Self {
+            dot_aspect: aspect.clamp(MIN_DOT_ASPECT, MAX_DOT_ASPECT),
+        }
+    }
+
+    /// Metrics from the cell pixel size the terminal reports, or `None` if
+    /// it doesn't report one.
+    #[must_use]
+    pub fn from_terminal() -> Option<Self> {
+        query_cell_pixel_size().map(Self::from_cell_pixels)
+    }
+}
+
+/// Tracks the terminal's cell pixel size and updates the process-wide
+/// metrics when it changes.
+///
+/// Zooming the font changes the cell size without changing the window, and
+/// the terminal reports it as an ordinary resize. Poll the watcher on every
+/// resize event; when it returns new metrics, the dot aspect has changed and
+/// aspect-corrected content should be drawn again.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{self, Event};
+/// use dotmax::utils::cell_metrics::CellSizeWatcher;
+///
+/// let mut watcher = CellSizeWatcher::new();
+/// loop {
+///     if let Event::Resize(..) = event::read()? {
+///         if let Some(metrics) = watcher.poll() {
+///             println!("dot aspect is now {:.2}, redrawing", metrics.dot_aspect());
+///         }
+///     }
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CellSizeWatcher {
+    last: Option<CellPixelSize>,
+}
+
+impl CellSizeWatcher {
+    /// Starts watching, applying the current cell size with
+    /// [`set_cell_metrics`] if the terminal reports one.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut watcher = Self::default();
+        watcher.poll();
+        watcher
+    }
+
+    /// The cell size seen by the last poll, if the terminal reported one.
+    #[must_use]
+    pub const fn cell_size(&self) -> Option<CellPixelSize> {
+        self.last
+    }
+
+    /// Queries the cell size again. If it changed, stores the new metrics
+    /// with [`set_cell_metrics`] and returns them.
+    ///
+    /// Returns `None` when nothing changed or the terminal stopped reporting
+    /// pixel sizes; the last metrics stay in place in that case.
+    pub fn poll(&mut self) -> Option<CellMetrics> {
+        let metrics = self.observe(query_cell_pixel_size())?;
+        set_cell_metrics(metrics);
+        Some(metrics)
+    }
+
+    /// Records `size`, returning its metrics if it differs from the last.
+    fn observe(&mut self, size: Option<CellPixelSize>) -> Option<CellMetrics> {
+        let size = size?;
+        if self.last == Some(size) {
+            return None;
+        }
+        self.last = Some(size);
+        let metrics = CellMetrics::from_cell_pixels(size);
+        info!(
+            width = size.width,
+            height = size.height,
+            dot_aspect = metrics.dot_aspect,
+            "Cell pixel size changed"
+        );
+        Some(metrics)
+    }
+}
+
+// ============================================================================
+// Process-wide metrics used by aspect-aware drawing
+// ============================================================================
+
+/// `f32` bits of the current dot aspect; `0x3F80_0000` is 1.0
+static DOT_ASPECT_BITS: AtomicU32 = AtomicU32::new(0x3F80_0000);
+
+/// Set the metrics used by aspect-aware drawing for the rest of the process.
+pub fn set_cell_metrics(metrics: CellMetrics) {
+    debug!(dot_aspect = metrics.dot_aspect, "Setting cell metrics");
+    DOT_ASPECT_BITS.store(metrics.dot_aspect.to_bits(), Ordering::Relaxed);
+}
+
+/// The metrics set by [`set_cell_metrics`], or the ideal default.
+#[must_use]
+pub fn cell_metrics() -> CellMetrics {
+    CellMetrics {
+        dot_aspect: f32::from_bits(DOT_ASPECT_BITS.load(Ordering::Relaxed)),
+    }
+}
+
+// ============================================================================
+// Calibration
+// ============================================================================
+
+/// Draw the calibration pattern: one outlined box per entry in
+/// [`CALIBRATION_CANDIDATES`], labelled `1`-`7` underneath.
+///
+/// Each box is drawn with the height that would look square *if* the font's
+/// dot aspect were that candidate, so exactly one of them (or a neighbor pair)
+/// looks square to the user.
+///
+/// # Errors
+///
+/// Returns [`DotmaxError::InvalidDimensions`] if the grid is too small to fit
+/// the pattern (at least 35×6 cells is needed) or exceeds the maximum size.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn render_calibration_pattern(width: usize, height: usize) -> Result<BrailleGrid, DotmaxError> {
+    let count = CALIBRATION_CANDIDATES.len();
+    // Each slot: box plus one cell of padding on either side; one text row of labels
+    if width < count * 5 || height < 6 {
+        return Err(DotmaxError::InvalidDimensions { width, height });
+    }
+    let mut grid = BrailleGrid::new(width, height)?;
+
+    let slot_cells = width / count;
+    let max_box_height = ((height - 2) * 4) as f32;
+    let tallest = CALIBRATION_CANDIDATES[0];
+    // Largest side that keeps every box inside its slot and above the labels
+    let side = ((slot_cells - 2) * 2)
+        .min((max_box_height * tallest) as usize)
+        .max(4) as f32;
+
+    for (i, &aspect) in CALIBRATION_CANDIDATES.iter().enumerate() {
+        let box_width = side as u32;
+        let box_height = (side / aspect).round().max(1.0) as u32;
+        let x = (i * slot_cells + 1) * 2;
+        let y = ((max_box_height - box_height as f32) / 2.0).max(0.0) as i32;
+        draw_rectangle(&mut grid, x as i32, y, box_width, box_height)?;
+
+        let label = char::from_digit((i + 1) as u32, 10).unwrap_or('?');
+        grid.set_char(i * slot_cells + slot_cells / 2, height - 1, label)?;
+    }
+    Ok(grid)
+}
+
+/// Run the interactive calibration in the terminal.
+///
+/// Shows the calibration pattern and waits for the user to press the number
+/// of the box that looks most square. `Esc` or `q` keeps the current metrics.
+/// The result is returned, not stored; pass it to [`set_cell_metrics`] to
+/// apply it.
+///
+/// # Errors
+///
+/// Returns an error if the terminal can't be initialized or is too small for
+/// the pattern.
+pub fn calibrate() -> Result<CellMetrics, DotmaxError> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+
+    let mut renderer = TerminalRenderer::new()?;
+    let (cols, rows) = renderer.get_terminal_size()?;
+    let pattern = render_calibration_pattern(cols as usize, rows as usize)?;
+    renderer.render(&pattern)?;
+    info!("Waiting for calibration choice");
+
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char(c) => {
+                if let Some(metrics) = c
+                    .to_digit(10)
+                    .and_then(|d| (d as usize).checked_sub(1))
+                    .and_then(CellMetrics::from_candidate)
+                {
+                    info!(dot_aspect = metrics.dot_aspect, "Calibration chosen");
+                    return Ok(metrics);
+                }
+                if c == 'q' {
+                    return Ok(cell_metrics());
+                }
+            }
+            KeyCode::Esc => return Ok(cell_metrics()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_validates_range() {
+        assert!(CellMetrics::new(1.1).is_ok());
+        assert!(matches!(
+            CellMetrics::new(0.1),
+            Err(DotmaxError::InvalidParameter { .. })
+        ));
+        assert!(CellMetrics::new(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_square_conversions_round_trip() {
+        let m = CellMetrics::new(1.25).unwrap();
+        assert!((m.square_height(50.0) - 40.0).abs() < 1e-4);
+        assert!((m.square_width(m.square_height(50.0)) - 50.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_from_candidate() {
+        let chosen = CellMetrics::from_candidate(3).unwrap();
+        assert!((chosen.dot_aspect() - CALIBRATION_CANDIDATES[3]).abs() < f32::EPSILON);
+        assert!(CellMetrics::from_candidate(CALIBRATION_CANDIDATES.len()).is_none());
+    }
+
+    #[test]
+    fn test_font_advice_depends_on_direction() {
+        let ideal = CellMetrics::default().font_advice();
+        let tall = CellMetrics::new(1.3).unwrap().font_advice();
+        let wide = CellMetrics::new(0.8).unwrap().font_advice();
+        assert!(ideal[0].contains("close to square"));
+        assert!(tall[0].contains("look tall"));
+        assert!(wide[0].contains("look wide"));
+        assert!(tall.len() > ideal.len());
+    }
+
+    #[test]
+    fn test_calibration_pattern_has_labelled_boxes() {
+        let grid = render_calibration_pattern(70, 12).unwrap();
+        let labels: String = (0..70)
+            .map(|x| grid.get_char(x, 11))
+            .filter(char::is_ascii_digit)
+            .collect();
+        assert_eq!(labels, "1234567");
+        // The box for the smallest aspect is drawn tallest
+        let rows_used = |cell_x| (0..11).filter(|&y| !grid.is_empty(cell_x, y)).count();
+        assert!(rows_used(1) > rows_used(61));
+        assert!(rows_used(61) > 0);
+    }
+
+    #[test]
+    fn test_calibration_pattern_rejects_tiny_grid() {
+        assert!(matches!(
+            render_calibration_pattern(20, 4),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cell_pixel_size_and_aspect() {
+        assert_eq!(
+            cell_pixel_size(800, 480, 80, 24),
+            Some(CellPixelSize {
+                width: 10,
+                height: 20
+            })
+        );
+        assert_eq!(cell_pixel_size(0, 0, 80, 24), None);
+        assert_eq!(cell_pixel_size(800, 480, 0, 24), None);
+        assert_eq!(cell_pixel_size(40, 480, 80, 24), None);
+
+        let square = CellMetrics::from_cell_pixels(CellPixelSize {
+            width: 10,
+            height: 20,
+        });
+        assert!((square.dot_aspect() - 1.0).abs() < f32::EPSILON);
+        let extreme = CellMetrics::from_cell_pixels(CellPixelSize {
+            width: 1,
+            height: 50,
+        });
+        assert!((extreme.dot_aspect() - MAX_DOT_ASPECT).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_watcher_reports_only_changes() {
+        let mut watcher = CellSizeWatcher::default();
+        let normal = CellPixelSize {
+            width: 10,
+            height: 20,
+        };
+        let zoomed = CellPixelSize {
+            width: 12,
+            height: 30,
+        };
+        // Observed without touching the process-wide metrics other tests read
+        assert!(watcher.observe(Some(normal)).is_some());
+        assert!(watcher.observe(Some(normal)).is_none());
+        assert!(watcher.observe(None).is_none());
+        assert_eq!(watcher.cell_size(), Some(normal));
+
+        let metrics = watcher.observe(Some(zoomed)).unwrap();
+        assert!((metrics.dot_aspect() - 1.25).abs() < f32::EPSILON);
+    }
+}
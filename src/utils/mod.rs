@@ -3,4 +3,6 @@
 //! This module provides utility functions and types that support the core
 //! functionality of the dotmax library.
 
+pub mod cell_metrics;
 pub mod terminal_caps;
+pub mod terminal_profile;
@@ -0,0 +1,513 @@
+//! Remembering calibration per terminal.
+//!
+//! Calibrating the dot aspect takes the user's attention, and the answer
+//! depends on the terminal and its font rather than on the program asking.
+//! A [`ProfileCache`] keeps what is known about each terminal the user has
+//! run in (dot aspect, braille support, color capability, and background
+//! color) so it only has to be worked out once.
+//!
+//! Profiles are keyed by [`TerminalKey`]: the terminal program
+//! (`$TERM_PROGRAM`, or `$TERM` where that isn't set) plus the cell size in
+//! pixels when the terminal reports one. The cell size tells fonts and
+//! monitors apart; moving a window to a screen with a different scale, or
+//! changing the font size, gives it a profile of its own.
+//!
+//! [`calibrate_once`] is the usual entry point: it applies the stored dot
+//! aspect for the current terminal, and only runs the interactive
+//! [`calibrate`] when there is none.
+//!
+//! # File Format
+//!
+//! One tab-separated line per terminal, oldest first, after a version header
+//! (tabs shown as spaces here):
+//!
+//! ```text
+//! # dotmax terminal profiles v1
+//! wezterm@10x22         1.08  yes  truecolor  #1e1e2e
+//! xterm-256color@-      -     no   256        -
+//! ```
+//!
+//! The columns are key, dot aspect, braille support (`yes`/`no`), color
+//! capability (`mono`, `16`, `256`, or `truecolor`), and background color,
+//! with `-` for "not known". Lines that don't parse are skipped.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use dotmax::utils::terminal_profile::{calibrate_once, ProfileCache};
+//!
+//! let path = ProfileCache::default_path().expect("no home directory");
+//! let mut cache = ProfileCache::load(path)?;
+//!
+//! // Asks the user only the first time this terminal is seen
+//! let metrics = calibrate_once(&mut cache)?;
+//! println!("dot aspect {:.2}", metrics.dot_aspect());
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::cell_metrics::{
+    calibrate, query_cell_pixel_size, set_cell_metrics, CellMetrics, CellPixelSize,
+};
+use super::terminal_caps::ColorCapability;
+use crate::grid::Color;
+use crate::Result;
+
+/// First line of every profile file.
+const HEADER: &str = "# dotmax terminal profiles v1";
+
+/// Identifies a terminal: its program name and, if reported, its cell size
+/// in pixels.
+///
+/// Displays as `program@WxH`, or `program@-` without a cell size.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TerminalKey {
+    program: String,
+    cell_size: Option<CellPixelSize>,
+}
+
+impl TerminalKey {
+    /// A key for `program` with cells of `cell_size` pixels.
+    ///
+    /// The program name is lowercased, and whitespace and `@` are replaced
+    /// with `_` so the key stays one field of the profile file.
+    #[must_use]
+    pub fn new(program: &str, cell_size: Option<CellPixelSize>) -> Self {
+        let program = program
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_whitespace() || c.is_control() || c == '@' {
+                    '_'
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect::<String>();
+        let program = if program.is_empty() {
+            "unknown".to_string()
+        } else {
+            program
+        };
+        Self { program, cell_size }
+    }
+
+    /// The key for the terminal this process is running in.
+    #[must_use]
+    pub fn current() -> Self {
+        let program = std::env::var("TERM_PROGRAM")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| std::env::var("TERM").ok())
+            .unwrap_or_default();
+        Self::new(&program, query_cell_pixel_size())
+    }
+
+    /// The terminal program name.
+    #[must_use]
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// The cell size in pixels, if the terminal reported one.
+    #[must_use]
+    pub const fn cell_size(&self) -> Option<CellPixelSize> {
+        self.cell_size
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let (program, size) = text.rsplit_once('@')?;
+        let cell_size = match size {
+            "-" => None,
+            size => {
+                let (width, height) = size.split_once('x')?;
+                Some(CellPixelSize {
+                    width: width.parse().ok()?,
+                    height: height.parse().ok()?,
+                })
+            }
+        };
+        let key = Self::new(program, cell_size);
+        (key.program == program).then_some(key)
+    }
+}
+
+impl fmt::Display for TerminalKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cell_size {
+            Some(size) => write!(f, "{}@{}x{}", self.program, size.width, size.height),
+            None => write!(f, "{}@-", self.program),
+        }
+    }
+}
+
+/// What is known about one terminal.
+///
+/// `None` fields haven't been measured; fall back to detection or defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TerminalProfile {
+    /// Calibrated dot aspect
+    pub dot_aspect: Option<CellMetrics>,
+    /// Whether the terminal's font draws braille glyphs properly
+    pub braille: Option<bool>,
+    /// Colors the terminal can show
+    pub color: Option<ColorCapability>,
+    /// The terminal's background color
+    pub background: Option<Color>,
+}
+
+impl TerminalProfile {
+    /// Whether nothing is known.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Stores the dot aspect with [`set_cell_metrics`], if there is one.
+    pub fn apply(&self) {
+        if let Some(metrics) = self.dot_aspect {
+            set_cell_metrics(metrics);
+        }
+    }
+}
+
+/// Per-terminal profiles backed by a profile file.
+///
+/// Changes stay in memory until [`save`](Self::save).
+#[derive(Debug, Clone)]
+pub struct ProfileCache {
+    path: PathBuf,
+    /// Oldest first
+    profiles: Vec<(TerminalKey, TerminalProfile)>,
+}
+
+impl ProfileCache {
+    /// Most terminals remembered; recording a new one past this forgets the
+    /// one touched longest ago.
+    pub const MAX_ENTRIES: usize = 64;
+
+    /// Where profiles are kept by default: `dotmax/terminal-profiles` under
+    /// `$XDG_STATE_HOME` (or `~/.local/state`) on Unix, and under
+    /// `%LOCALAPPDATA%` on Windows. `None` if those aren't set.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        let env_dir = |name| std::env::var_os(name).filter(|dir| !dir.is_empty());
+        let base = if cfg!(windows) {
+            env_dir("LOCALAPPDATA").map(PathBuf::from)
+        } else {
+            env_dir("XDG_STATE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env_dir("HOME").map(|home| Path::new(&home).join(".local/state")))
+        };
+        base.map(|dir| dir.join("dotmax").join("terminal-profiles"))
+    }
+
+    /// Reads the profile file at `path`, or starts empty if it doesn't
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the
+    /// file exists but can't be read.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut profiles: Vec<(TerminalKey, TerminalProfile)> = Vec::new();
+        for line in text.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, profile)) = parse_line(line) {
+                profiles.retain(|(existing, _)| *existing != key);
+                profiles.push((key, profile));
+            } else {
+                tracing::warn!("Skipping malformed terminal profile line: {line:?}");
+            }
+        }
+        let excess = profiles.len().saturating_sub(Self::MAX_ENTRIES);
+        profiles.drain(..excess);
+
+        Ok(Self { path, profiles })
+    }
+
+    /// The profile file this was loaded from and saves to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The profile for `key`, if there is one.
+    #[must_use]
+    pub fn get(&self, key: &TerminalKey) -> Option<&TerminalProfile> {
+        self.profiles
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, profile)| profile)
+    }
+
+    /// Stores `profile` for `key`, replacing any earlier one. An empty
+    /// profile just forgets the terminal.
+    pub fn record(&mut self, key: TerminalKey, profile: TerminalProfile) {
+        self.forget(&key);
+        if profile.is_default() {
+            return;
+        }
+        if self.profiles.len() == Self::MAX_ENTRIES {
+            self.profiles.remove(0);
+        }
+        self.profiles.push((key, profile));
+    }
+
+    /// Drops the profile for `key`.
+    pub fn forget(&mut self, key: &TerminalKey) {
+        self.profiles.retain(|(existing, _)| existing != key);
+    }
+
+    /// How many terminals are remembered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Whether no terminals are remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    /// Writes the profile file, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the
+    /// directory or file can't be written.
+    pub fn save(&self) -> Result<()> {
+        use std::fmt::Write;
+
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut text = format!("{HEADER}\n");
+        for (key, profile) in &self.profiles {
+            let _ = writeln!(
+                text,
+                "{key}\t{}\t{}\t{}\t{}",
+                profile
+                    .dot_aspect
+                    .map_or_else(|| "-".to_string(), |m| m.dot_aspect().to_string()),
+                profile
+                    .braille
+                    .map_or("-", |ok| if ok { "yes" } else { "no" }),
+                profile.color.map_or("-", capability_name),
+                profile.background.map_or_else(
+                    || "-".to_string(),
+                    |c| format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b)
+                ),
+            );
+        }
+        std::fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+/// Applies the stored dot aspect for the current terminal, running the
+/// interactive [`calibrate`] first if there isn't one yet.
+///
+/// A fresh calibration is recorded in `cache` and saved, so the next run in
+/// the same terminal skips it. Either way the metrics are stored with
+/// [`set_cell_metrics`] and returned.
+///
+/// # Errors
+///
+/// Returns an error if calibration fails (see [`calibrate`]) or the cache
+/// can't be saved.
+pub fn calibrate_once(cache: &mut ProfileCache) -> Result<CellMetrics> {
+    let key = TerminalKey::current();
+    let mut profile = cache.get(&key).copied().unwrap_or_default();
+    let metrics = if let Some(metrics) = profile.dot_aspect {
+        metrics
+    } else {
+        let metrics = calibrate()?;
+        profile.dot_aspect = Some(metrics);
+        cache.record(key, profile);
+        cache.save()?;
+        metrics
+    };
+    set_cell_metrics(metrics);
+    Ok(metrics)
+}
+
+fn parse_line(line: &str) -> Option<(TerminalKey, TerminalProfile)> {
+    let mut fields = line.split('\t');
+    let key = TerminalKey::parse(fields.next()?)?;
+    let dot_aspect = match fields.next()? {
+        "-" => None,
+        value => Some(CellMetrics::new(value.parse().ok()?).ok()?),
+    };
+    let braille = match fields.next()? {
+        "-" => None,
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => return None,
+    };
+    let color = match fields.next()? {
+        "-" => None,
+        name => Some(capability_from_name(name)?),
+    };
+    let background = match fields.next()? {
+        "-" => None,
+        hex => Some(parse_hex(hex)?),
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+
+    let profile = TerminalProfile {
+        dot_aspect,
+        braille,
+        color,
+        background,
+    };
+    Some((key, profile))
+}
+
+const fn capability_name(capability: ColorCapability) -> &'static str {
+    match capability {
+        ColorCapability::Monochrome => "mono",
+        ColorCapability::Ansi16 => "16",
+        ColorCapability::Ansi256 => "256",
+        ColorCapability::TrueColor => "truecolor",
+    }
+}
+
+fn capability_from_name(name: &str) -> Option<ColorCapability> {
+    [
+        ColorCapability::Monochrome,
+        ColorCapability::Ansi16,
+        ColorCapability::Ansi256,
+        ColorCapability::TrueColor,
+    ]
+    .into_iter()
+    .find(|&capability| capability_name(capability) == name)
+}
+
+/// Parses `#rrggbb`.
+fn parse_hex(text: &str) -> Option<Color> {
+    let hex = text.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(program: &str, width: u16, height: u16) -> TerminalKey {
+        TerminalKey::new(program, Some(CellPixelSize { width, height }))
+    }
+
+    #[test]
+    fn test_key_display_and_parse() {
+        let wez = key("WezTerm", 10, 22);
+        assert_eq!(wez.to_string(), "wezterm@10x22");
+        assert_eq!(TerminalKey::parse("wezterm@10x22"), Some(wez));
+
+        let odd = TerminalKey::new(" Apple Terminal@2 ", None);
+        assert_eq!(odd.to_string(), "apple_terminal_2@-");
+        assert_eq!(TerminalKey::parse(&odd.to_string()), Some(odd));
+        assert_eq!(TerminalKey::new("", None).program(), "unknown");
+
+        assert_eq!(TerminalKey::parse("wezterm"), None);
+        assert_eq!(TerminalKey::parse("wezterm@10by22"), None);
+        assert_eq!(TerminalKey::parse("WezTerm@-"), None);
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("terminal-profiles");
+
+        let mut cache = ProfileCache::load(&path).unwrap();
+        assert!(cache.is_empty());
+        let full = TerminalProfile {
+            dot_aspect: Some(CellMetrics::new(1.08).unwrap()),
+            braille: Some(true),
+            color: Some(ColorCapability::TrueColor),
+            background: Some(Color::rgb(0x1e, 0x1e, 0x2e)),
+        };
+        let partial = TerminalProfile {
+            braille: Some(false),
+            color: Some(ColorCapability::Ansi256),
+            ..TerminalProfile::default()
+        };
+        let bare = TerminalKey::new("xterm-256color", None);
+        cache.record(key("wezterm", 10, 22), full);
+        cache.record(bare.clone(), partial);
+        cache.save().unwrap();
+
+        let loaded = ProfileCache::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get(&key("wezterm", 10, 22)), Some(&full));
+        assert_eq!(loaded.get(&bare), Some(&partial));
+        // A zoomed font is a different profile
+        assert_eq!(loaded.get(&key("wezterm", 12, 26)), None);
+    }
+
+    #[test]
+    fn test_record_replaces_and_evicts_oldest() {
+        let braille = TerminalProfile {
+            braille: Some(true),
+            ..TerminalProfile::default()
+        };
+        let mut cache = ProfileCache::load("/nonexistent/terminal-profiles").unwrap();
+        for width in 0..ProfileCache::MAX_ENTRIES as u16 {
+            cache.record(key("kitty", width + 1, 20), braille);
+        }
+        // Touching the oldest makes it the newest
+        cache.record(key("kitty", 1, 20), braille);
+        cache.record(key("foot", 8, 16), braille);
+
+        assert_eq!(cache.len(), ProfileCache::MAX_ENTRIES);
+        assert!(cache.get(&key("kitty", 1, 20)).is_some());
+        assert!(cache.get(&key("kitty", 2, 20)).is_none());
+
+        cache.record(key("foot", 8, 16), TerminalProfile::default());
+        assert!(cache.get(&key("foot", 8, 16)).is_none());
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("terminal-profiles");
+        std::fs::write(
+            &path,
+            format!(
+                "{HEADER}\n\
+                 foot@8x16\t1.17\tyes\t256\t#000000\n\
+                 no-size\t-\tyes\t-\t-\n\
+                 foot@8x17\t9.5\t-\t-\t-\n\
+                 foot@8x18\t-\tmaybe\t-\t-\n\
+                 foot@8x19\t-\t-\t-\t#12345\n\
+                 foot@8x20\t-\t-\t-\n"
+            ),
+        )
+        .unwrap();
+
+        let cache = ProfileCache::load(&path).unwrap();
+        assert_eq!(cache.len(), 1);
+        let profile = cache.get(&key("foot", 8, 16)).unwrap();
+        assert_eq!(profile.background, Some(Color::black()));
+        assert_eq!(profile.color, Some(ColorCapability::Ansi256));
+    }
+}
@@ -0,0 +1,430 @@
+//! Remembering where a viewer left off.
+//!
+//! A [`ViewerState`] is a small file of per-media records: the playback
+//! position a viewer stopped at, and the dithering, color mode, and color
+//! scheme that were in use. Records are keyed by a hash of the file's
+//! contents ([`FileKey`]), so they follow a file through renames and are
+//! dropped by edits.
+//!
+//! Nothing is remembered unless an application asks:
+//! [`quick::show_file_resuming`](crate::quick::show_file_resuming) loads a
+//! record before playback and stores one on exit, and custom viewers can
+//! call [`get`](ViewerState::get) and [`record`](ViewerState::record)
+//! themselves.
+//!
+//! # File Format
+//!
+//! One tab-separated line per file, oldest first, after a version header
+//! (tabs shown as spaces here):
+//!
+//! ```text
+//! # dotmax viewer state v1
+//! 9f3c2a71d04be815  42500  bayer  grayscale  heat_map
+//! 04be8159f3c2a71d  0      -      -          -
+//! ```
+//!
+//! The columns are key, position in milliseconds, dithering, color mode,
+//! and scheme name, with `-` for "not set". Only the most recent
+//! [`MAX_ENTRIES`](ViewerState::MAX_ENTRIES) files are kept. Lines that
+//! don't parse are skipped, so a damaged file costs only its bad records.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use dotmax::viewer_state::{FileKey, ViewerEntry, ViewerState};
+//!
+//! let path = ViewerState::default_path().expect("no home directory");
+//! let mut state = ViewerState::load(path)?;
+//!
+//! let key = FileKey::of("clip.mp4")?;
+//! let resume_at = state.get(key).map_or(Duration::ZERO, |entry| entry.position);
+//!
+//! // ... play from `resume_at` ...
+//!
+//! state.record(key, ViewerEntry { position: Duration::from_secs(93), ..Default::default() });
+//! state.save()?;
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::image::{ColorMode, DitheringMethod};
+use crate::Result;
+
+/// First line of every state file.
+const HEADER: &str = "# dotmax viewer state v1";
+
+/// How much of a file [`FileKey::of`] reads. Enough to tell media files
+/// apart without reading a whole video.
+const HASHED_PREFIX_LEN: u64 = 1024 * 1024;
+
+/// Identifies a media file by its contents.
+///
+/// The key is a 64-bit FNV-1a hash of the file's length and its first
+/// megabyte. It is stable across runs, platforms, and Rust versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileKey(u64);
+
+impl FileKey {
+    /// Hashes the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the
+    /// file can't be read.
+    pub fn of(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        let mut prefix = Vec::new();
+        file.take(HASHED_PREFIX_LEN).read_to_end(&mut prefix)?;
+        Ok(Self::from_parts(len, &prefix))
+    }
+
+    fn from_parts(len: u64, prefix: &[u8]) -> Self {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let hash = len
+            .to_le_bytes()
+            .iter()
+            .chain(prefix)
+            .fold(OFFSET_BASIS, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            });
+        Self(hash)
+    }
+}
+
+impl fmt::Display for FileKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// What a viewer remembers about one file.
+///
+/// `None` fields leave the viewer's own default in place.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewerEntry {
+    /// Where playback stopped. Zero for files that played to the end.
+    pub position: Duration,
+    /// The dithering method in use.
+    pub dithering: Option<DitheringMethod>,
+    /// The color mode in use.
+    pub color_mode: Option<ColorMode>,
+    /// The name of the recoloring scheme in use (see
+    /// [`get_scheme`](crate::get_scheme)).
+    pub scheme: Option<String>,
+}
+
+impl ViewerEntry {
+    /// Whether restoring this entry would change anything.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Per-file viewer records backed by a state file.
+///
+/// Changes stay in memory until [`save`](Self::save).
+#[derive(Debug, Clone)]
+pub struct ViewerState {
+    path: PathBuf,
+    /// Oldest first
+    entries: Vec<(FileKey, ViewerEntry)>,
+}
+
+impl ViewerState {
+    /// Most files remembered; recording a new one past this forgets the one
+    /// touched longest ago.
+    pub const MAX_ENTRIES: usize = 256;
+
+    /// Where state is kept by default: `dotmax/viewer-state` under
+    /// `$XDG_STATE_HOME` (or `~/.local/state`) on Unix, and under
+    /// `%LOCALAPPDATA%` on Windows. `None` if those aren't set.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        let env_dir = |name| std::env::var_os(name).filter(|dir| !dir.is_empty());
+        let base = if cfg!(windows) {
+            env_dir("LOCALAPPDATA").map(PathBuf::from)
+        } else {
+            env_dir("XDG_STATE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env_dir("HOME").map(|home| Path::new(&home).join(".local/state")))
+        };
+        base.map(|dir| dir.join("dotmax").join("viewer-state"))
+    }
+
+    /// Reads the state file at `path`, or starts empty if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the
+    /// file exists but can't be read.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut entries: Vec<(FileKey, ViewerEntry)> = Vec::new();
+        for line in text.lines() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, entry)) = parse_line(line) {
+                entries.retain(|(existing, _)| *existing != key);
+                entries.push((key, entry));
+            } else {
+                tracing::warn!("Skipping malformed viewer state line: {line:?}");
+            }
+        }
+        let excess = entries.len().saturating_sub(Self::MAX_ENTRIES);
+        entries.drain(..excess);
+
+        Ok(Self { path, entries })
+    }
+
+    /// The state file this was loaded from and saves to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The record for `key`, if there is one.
+    #[must_use]
+    pub fn get(&self, key: FileKey) -> Option<&ViewerEntry> {
+        self.entries
+            .iter()
+            .find(|(existing, _)| *existing == key)
+            .map(|(_, entry)| entry)
+    }
+
+    /// Stores `entry` for `key`, replacing any earlier record. A default
+    /// entry has nothing to restore, so it just forgets the file.
+    pub fn record(&mut self, key: FileKey, entry: ViewerEntry) {
+        self.forget(key);
+        if entry.is_default() {
+            return;
+        }
+        if self.entries.len() == Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, entry));
+    }
+
+    /// Drops the record for `key`.
+    pub fn forget(&mut self, key: FileKey) {
+        self.entries.retain(|(existing, _)| *existing != key);
+    }
+
+    /// How many files are remembered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no files are remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the state file, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::Terminal`](crate::DotmaxError::Terminal) if the
+    /// directory or file can't be written.
+    pub fn save(&self) -> Result<()> {
+        use std::fmt::Write;
+
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let mut text = format!("{HEADER}\n");
+        for (key, entry) in &self.entries {
+            let _ = writeln!(
+                text,
+                "{key}\t{}\t{}\t{}\t{}",
+                entry.position.as_millis(),
+                entry.dithering.map_or("-", dithering_name),
+                entry.color_mode.map_or("-", color_mode_name),
+                entry.scheme.as_deref().unwrap_or("-"),
+            );
+        }
+        std::fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<(FileKey, ViewerEntry)> {
+    let mut fields = line.split('\t');
+    let key = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let position = Duration::from_millis(fields.next()?.parse().ok()?);
+    let dithering = match fields.next()? {
+        "-" => None,
+        name => Some(dithering_from_name(name)?),
+    };
+    let color_mode = match fields.next()? {
+        "-" => None,
+        name => Some(color_mode_from_name(name)?),
+    };
+    let scheme = match fields.next()? {
+        "-" => None,
+        name => Some(name.to_string()),
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+
+    let entry = ViewerEntry {
+        position,
+        dithering,
+        color_mode,
+        scheme,
+    };
+    Some((FileKey(key), entry))
+}
+
+const fn dithering_name(method: DitheringMethod) -> &'static str {
+    match method {
+        DitheringMethod::None => "none",
+        DitheringMethod::FloydSteinberg => "floyd_steinberg",
+        DitheringMethod::Bayer => "bayer",
+        DitheringMethod::Atkinson => "atkinson",
+    }
+}
+
+fn dithering_from_name(name: &str) -> Option<DitheringMethod> {
+    [
+        DitheringMethod::None,
+        DitheringMethod::FloydSteinberg,
+        DitheringMethod::Bayer,
+        DitheringMethod::Atkinson,
+    ]
+    .into_iter()
+    .find(|&method| dithering_name(method) == name)
+}
+
+const fn color_mode_name(mode: ColorMode) -> &'static str {
+    match mode {
+        ColorMode::Monochrome => "monochrome",
+        ColorMode::Grayscale => "grayscale",
+        ColorMode::TrueColor => "truecolor",
+    }
+}
+
+fn color_mode_from_name(name: &str) -> Option<ColorMode> {
+    [
+        ColorMode::Monochrome,
+        ColorMode::Grayscale,
+        ColorMode::TrueColor,
+    ]
+    .into_iter()
+    .find(|&mode| color_mode_name(mode) == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(position_ms: u64) -> ViewerEntry {
+        ViewerEntry {
+            position: Duration::from_millis(position_ms),
+            ..ViewerEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_file_key_depends_on_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.gif");
+        let b = dir.path().join("b.gif");
+        std::fs::write(&a, b"GIF89a one").unwrap();
+        std::fs::write(&b, b"GIF89a one").unwrap();
+        assert_eq!(FileKey::of(&a).unwrap(), FileKey::of(&b).unwrap());
+
+        std::fs::write(&b, b"GIF89a two").unwrap();
+        assert_ne!(FileKey::of(&a).unwrap(), FileKey::of(&b).unwrap());
+
+        // Pinned so stored keys stay valid across releases
+        assert_eq!(FileKey::from_parts(0, b"").to_string(), "a8c7f832281a39c5");
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("viewer-state");
+
+        let mut state = ViewerState::load(&path).unwrap();
+        assert!(state.is_empty());
+        let full = ViewerEntry {
+            position: Duration::from_millis(42_500),
+            dithering: Some(DitheringMethod::Bayer),
+            color_mode: Some(ColorMode::Grayscale),
+            scheme: Some("heat_map".to_string()),
+        };
+        state.record(FileKey(1), full.clone());
+        state.record(FileKey(2), entry(7));
+        state.save().unwrap();
+
+        let loaded = ViewerState::load(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.get(FileKey(1)), Some(&full));
+        assert_eq!(loaded.get(FileKey(2)), Some(&entry(7)));
+        assert_eq!(loaded.get(FileKey(3)), None);
+    }
+
+    #[test]
+    fn test_record_replaces_and_evicts_oldest() {
+        let mut state = ViewerState::load("/nonexistent/viewer-state").unwrap();
+        for i in 0..ViewerState::MAX_ENTRIES as u64 {
+            state.record(FileKey(i), entry(i + 1));
+        }
+        // Touching the oldest makes it the newest
+        state.record(FileKey(0), entry(500));
+        state.record(FileKey(9999), entry(1));
+
+        assert_eq!(state.len(), ViewerState::MAX_ENTRIES);
+        assert_eq!(state.get(FileKey(0)), Some(&entry(500)));
+        assert_eq!(state.get(FileKey(1)), None);
+
+        state.record(FileKey(0), ViewerEntry::default());
+        assert_eq!(state.get(FileKey(0)), None);
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("viewer-state");
+        std::fs::write(
+            &path,
+            format!(
+                "{HEADER}\n\
+                 00000000000000ff\t1000\tbayer\t-\t-\n\
+                 not-a-key\t1000\t-\t-\t-\n\
+                 0000000000000001\t1000\tsparkles\t-\t-\n\
+                 0000000000000002\t1000\t-\t-\n"
+            ),
+        )
+        .unwrap();
+
+        let state = ViewerState::load(&path).unwrap();
+        assert_eq!(state.len(), 1);
+        assert_eq!(
+            state.get(FileKey(0xff)).unwrap().dithering,
+            Some(DitheringMethod::Bayer)
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Music visualizer rendering from spectrum and waveform data.
+//!
+//! The braille grid started life in the
+//! [crabmusic](https://github.com/newjordan/crabmusic) visualizer, and this
+//! module brings its display modes back as plain rendering: hand a
+//! [`Visualizer`] a slice of numbers each frame and it draws them. There is
+//! no audio capture or FFT here; take spectra and samples from whatever
+//! audio stack the application already uses.
+//!
+//! - [`Mode::Bars`]: spectrum bars rising from the bottom
+//! - [`Mode::Mirrored`]: spectrum bars growing up and down from the middle
+//! - [`Mode::Waveform`]: an oscilloscope trace of raw samples
+//!
+//! Spectrum values are levels from 0.0 to 1.0, lowest frequency first, and
+//! waveform samples run from -1.0 to 1.0. Anything outside is clipped after
+//! [`gain`](Visualizer::gain) is applied. Data of any length fits the area:
+//! neighbouring spectrum values share a bar by keeping the loudest, and each
+//! dot column of a waveform spans the samples that fall in it, so a burst
+//! between columns still shows.
+//!
+//! With a [`ColorScheme`], cells are colored by level: height for bars, and
+//! distance from the center line for the mirrored and waveform modes.
+//!
+//! Requires the `viz` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::viz::{Mode, Visualizer};
+//! use dotmax::{BrailleGrid, ColorScheme};
+//!
+//! // Usually magnitudes from an FFT, scaled to 0..1
+//! let spectrum = [0.9, 0.7, 0.8, 0.5, 0.3, 0.35, 0.2, 0.1];
+//!
+//! let mut grid = BrailleGrid::new(16, 4)?;
+//! let bars = Visualizer::new(Mode::Bars).scheme(ColorScheme::heat_map());
+//! bars.render(&mut grid, &spectrum)?;
+//!
+//! assert_eq!(grid.get_char(0, 0), '⡄'); // 0.9 of 16 dots is 14; the top 2 stay clear
+//! assert!(grid.get_color(0, 3).is_some());
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::color::schemes::ColorScheme;
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+/// How a [`Visualizer`] draws its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Spectrum bars rising from the bottom edge.
+    #[default]
+    Bars,
+    /// Spectrum bars reaching up and down from the middle row, half the
+    /// area's height at full level.
+    Mirrored,
+    /// Waveform samples as a connected trace, 0.0 on the middle row.
+    Waveform,
+}
+
+/// Draws spectrum or waveform data; see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Visualizer {
+    mode: Mode,
+    scheme: Option<ColorScheme>,
+    bars: Option<usize>,
+    gap: usize,
+    gain: f32,
+}
+
+impl Default for Visualizer {
+    fn default() -> Self {
+        Self::new(Mode::default())
+    }
+}
+
+impl Visualizer {
+    /// A visualizer in `mode`: one bar per cell column with a one-dot gap,
+    /// unit gain, and no colors.
+    #[must_use]
+    pub const fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            scheme: None,
+            bars: None,
+            gap: 1,
+            gain: 1.0,
+        }
+    }
+
+    /// Colors cells by level with `scheme`.
+    #[must_use]
+    pub fn scheme(mut self, scheme: ColorScheme) -> Self {
+        self.scheme = Some(scheme);
+        self
+    }
+
+    /// Draws `count` bars across the area instead of one per cell column.
+    /// More bars than dot columns are drawn one dot column each.
+    #[must_use]
+    pub const fn bars(mut self, count: usize) -> Self {
+        self.bars = Some(count);
+        self
+    }
+
+    /// Leaves `dots` empty dot columns after each bar (default 1). Bars too
+    /// narrow to fit a gap are drawn without one.
+    #[must_use]
+    pub const fn gap(mut self, dots: usize) -> Self {
+        self.gap = dots;
+        self
+    }
+
+    /// Multiplies every value by `gain` before clipping (default 1.0).
+    #[must_use]
+    pub const fn gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Clears the grid and draws `data` over all of it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`render_region`](Self::render_region), which can't fail for
+    /// the whole grid.
+    pub fn render(&self, grid: &mut BrailleGrid, data: &[f32]) -> Result<(), DotmaxError> {
+        let (width, height) = grid.dimensions();
+        self.render_region(grid, data, 0, 0, width, height)
+    }
+
+    /// Clears the `width × height` cell region whose top-left cell is
+    /// `(x, y)` and draws `data` into it. Empty data leaves the region
+    /// blank.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::OutOfBounds`] if the region extends past the
+    /// grid.
+    pub fn render_region(
+        &self,
+        grid: &mut BrailleGrid,
+        data: &[f32],
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        grid.clear_region(x, y, width, height)?;
+        if data.is_empty() || width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let area = Area {
+            left: x * 2,
+            top: y * 4,
+            width: width * 2,
+            height: height * 4,
+        };
+        match self.mode {
+            Mode::Bars | Mode::Mirrored => self.draw_bars(grid, data, &area)?,
+            Mode::Waveform => self.draw_waveform(grid, data, &area)?,
+        }
+
+        if let Some(scheme) = &self.scheme {
+            for row in 0..height {
+                let level = self.level(row, height);
+                for column in 0..width {
+                    if !grid.is_empty(x + column, y + row) {
+                        grid.set_cell_color(x + column, y + row, scheme.sample(level))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn draw_bars(
+        &self,
+        grid: &mut BrailleGrid,
+        data: &[f32],
+        area: &Area,
+    ) -> Result<(), DotmaxError> {
+        let count = self.bars.unwrap_or(area.width / 2).clamp(1, area.width);
+        let center = area.height / 2;
+        for (bar, level) in peaks(data, count).into_iter().enumerate() {
+            let level = (level * self.gain).clamp(0.0, 1.0);
+            let (start, mut end) = (bar * area.width / count, (bar + 1) * area.width / count);
+            if end - start > self.gap {
+                end -= self.gap;
+            }
+            let rows = if self.mode == Mode::Mirrored {
+                let reach = (level * center as f32).round() as usize;
+                center - reach..center + reach
+            } else {
+                area.height - (level * area.height as f32).round() as usize..area.height
+            };
+            for dot_y in rows {
+                for dot_x in start..end {
+                    grid.set_dot(area.left + dot_x, area.top + dot_y)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn draw_waveform(
+        &self,
+        grid: &mut BrailleGrid,
+        data: &[f32],
+        area: &Area,
+    ) -> Result<(), DotmaxError> {
+        let bottom = (area.height - 1) as f32;
+        let row = |sample: f32| {
+            let sample = if sample.is_nan() { 0.0 } else { sample };
+            let sample = (sample * self.gain).clamp(-1.0, 1.0);
+            ((1.0 - sample) * bottom / 2.0).round() as usize
+        };
+
+        let mut previous = None;
+        for dot_x in 0..area.width {
+            let (start, end) = span(dot_x, area.width, data.len());
+            let rows = data[start..end].iter().map(|&sample| row(sample));
+            // Reach back to the last sample of the previous column so the
+            // trace stays connected
+            let (low, high) = rows
+                .chain(previous)
+                .fold((usize::MAX, 0), |(low, high), r| (low.min(r), high.max(r)));
+            for dot_y in low..=high {
+                grid.set_dot(area.left + dot_x, area.top + dot_y)?;
+            }
+            previous = Some(row(data[end - 1]));
+        }
+        Ok(())
+    }
+
+    /// The color level of cell row `row` out of `rows`: its top edge's
+    /// height for bars, or its outer edge's distance from the center line.
+    #[allow(clippy::cast_precision_loss)]
+    fn level(&self, row: usize, rows: usize) -> f32 {
+        if self.mode == Mode::Bars {
+            (rows - row) as f32 / rows as f32
+        } else {
+            let center = rows as f32 / 2.0;
+            let (top, bottom) = (row as f32, (row + 1) as f32);
+            (center - top).abs().max((bottom - center).abs()) / center
+        }
+    }
+}
+
+/// A region of the grid in dots.
+struct Area {
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+/// The range of `len` values that falls in slot `index` of `slots`, never
+/// empty; with fewer values than slots, neighbouring slots share one.
+fn span(index: usize, slots: usize, len: usize) -> (usize, usize) {
+    let start = (index * len / slots).min(len - 1);
+    let end = ((index + 1) * len / slots).clamp(start + 1, len);
+    (start, end)
+}
+
+/// `data` reduced or stretched to `count` values, keeping the loudest of
+/// each group. NaN counts as silence.
+fn peaks(data: &[f32], count: usize) -> Vec<f32> {
+    (0..count)
+        .map(|index| {
+            let (start, end) = span(index, count, data.len());
+            data[start..end]
+                .iter()
+                .filter(|value| !value.is_nan())
+                .fold(0.0, |peak: f32, &value| peak.max(value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rows of the grid as `#` for set dots and `.` for clear ones.
+    fn dots(grid: &BrailleGrid) -> Vec<String> {
+        (0..grid.dot_height())
+            .map(|y| {
+                (0..grid.dot_width())
+                    .map(|x| if grid.is_dot_set(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bars_rise_from_bottom() {
+        let mut grid = BrailleGrid::new(2, 1).unwrap();
+        Visualizer::new(Mode::Bars)
+            .render(&mut grid, &[0.5, 1.0])
+            .unwrap();
+        assert_eq!(dots(&grid), ["..#.", "..#.", "#.#.", "#.#."]);
+
+        // Four values into two bars keep each pair's peak; no room for gaps
+        Visualizer::new(Mode::Bars)
+            .bars(4)
+            .render(&mut grid, &[0.25, 0.0, 0.0, 0.75])
+            .unwrap();
+        assert_eq!(dots(&grid), ["....", "...#", "...#", "#..#"]);
+        Visualizer::new(Mode::Bars)
+            .bars(2)
+            .gap(0)
+            .gain(2.0)
+            .render(&mut grid, &[0.25, 0.0, 0.0, 0.75])
+            .unwrap();
+        assert_eq!(dots(&grid), ["..##", "..##", "####", "####"]);
+    }
+
+    #[test]
+    fn test_mirrored_is_symmetric() {
+        let mut grid = BrailleGrid::new(1, 2).unwrap();
+        Visualizer::new(Mode::Mirrored)
+            .gap(0)
+            .render(&mut grid, &[0.5])
+            .unwrap();
+        assert_eq!(
+            dots(&grid),
+            ["..", "..", "##", "##", "##", "##", "..", ".."]
+        );
+    }
+
+    #[test]
+    fn test_waveform_is_connected() {
+        let mut grid = BrailleGrid::new(2, 2).unwrap();
+        Visualizer::new(Mode::Waveform)
+            .render(&mut grid, &[0.0, 0.0, 1.0, -1.0])
+            .unwrap();
+        assert_eq!(
+            dots(&grid),
+            ["..##", "..##", "..##", "..##", "####", "...#", "...#", "...#"]
+        );
+
+        // Silence is a flat line across the middle
+        Visualizer::new(Mode::Waveform)
+            .render(&mut grid, &[0.0; 100])
+            .unwrap();
+        assert_eq!(dots(&grid)[4], "####");
+        assert!(dots(&grid)[3].chars().all(|c| c == '.'));
+    }
+
+    #[test]
+    fn test_colors_follow_level() {
+        let scheme = ColorScheme::grayscale();
+        let mut grid = BrailleGrid::new(1, 4).unwrap();
+        Visualizer::new(Mode::Bars)
+            .scheme(scheme.clone())
+            .render(&mut grid, &[1.0])
+            .unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(scheme.sample(1.0)));
+        assert_eq!(grid.get_color(0, 3), Some(scheme.sample(0.25)));
+
+        Visualizer::new(Mode::Mirrored)
+            .scheme(scheme.clone())
+            .render(&mut grid, &[0.5])
+            .unwrap();
+        assert_eq!(grid.get_color(0, 0), None);
+        assert_eq!(grid.get_color(0, 1), Some(scheme.sample(0.5)));
+        assert_eq!(grid.get_color(0, 2), Some(scheme.sample(0.5)));
+    }
+
+    #[test]
+    fn test_region_and_empty_data() {
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        grid.set_dot(0, 0).unwrap();
+        Visualizer::new(Mode::Bars)
+            .render_region(&mut grid, &[1.0], 2, 0, 2, 2)
+            .unwrap();
+        assert!(grid.is_dot_set(0, 0));
+        assert!(grid.is_dot_set(4, 0) && grid.is_dot_set(6, 7));
+        assert!(!grid.is_dot_set(3, 7));
+
+        Visualizer::default().render(&mut grid, &[]).unwrap();
+        assert!(dots(&grid).iter().all(|row| !row.contains('#')));
+        assert!(Visualizer::default()
+            .render_region(&mut grid, &[1.0], 3, 0, 2, 1)
+            .is_err());
+    }
+}
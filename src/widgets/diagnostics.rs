@@ -0,0 +1,248 @@
+//! Per-frame timing diagnostics for media playback.
+//!
+//! [`PlaybackDiagnostics`] shows where the time for the last frame went
+//! and how far playback has drifted from the wall clock, as a small block
+//! in the top-left corner of the frame:
+//!
+//! ```text
+//! frame     1234
+//! decode   4.1ms
+//! convert  2.0ms
+//! render   1.3ms
+//! queue       --
+//! drift    +12ms
+//! ```
+//!
+//! Drift is how much later (`+`) or earlier (`-`) a frame reached the
+//! screen than its presentation time says it should have, counted from the
+//! last [`resync`](PlaybackDiagnostics::resync). Steadily growing drift
+//! means decoding and drawing can't keep up with the media's frame rate;
+//! the other lines say which step is the slow one. Queue depth is the
+//! number of frames decoded ahead, `--` for players that don't decode
+//! ahead.
+//!
+//! The overlay starts hidden and is toggled at runtime, e.g. with the
+//! [`ToggleDiagnostics`](crate::keymap::KeyAction::ToggleDiagnostics) key
+//! in `quick`'s playback helpers. It keeps recording while hidden, so the
+//! numbers are current the moment it's shown.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use dotmax::widgets::{FrameTiming, PlaybackDiagnostics};
+//! use dotmax::BrailleGrid;
+//!
+//! let start = Instant::now();
+//! let mut diagnostics = PlaybackDiagnostics::new();
+//! diagnostics.resync(Duration::ZERO, start);
+//!
+//! // The frame due at 40ms reached the screen at 52ms
+//! let timing = FrameTiming {
+//!     decode: Duration::from_millis(8),
+//!     ..FrameTiming::default()
+//! };
+//! diagnostics.record(timing, Duration::from_millis(40), start + Duration::from_millis(52));
+//! assert!((diagnostics.drift() - 0.012).abs() < 1e-9);
+//!
+//! diagnostics.toggle();
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! diagnostics.render(&mut grid);
+//! assert_eq!(grid.get_char(0, 1), 'd'); // "decode   8.0ms"
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::{Duration, Instant};
+
+use crate::grid::BrailleGrid;
+
+use super::put_text;
+
+/// Where the time for one frame went.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameTiming {
+    /// Reading and decompressing the frame
+    pub decode: Duration,
+    /// Turning decoded pixels into dots
+    pub convert: Duration,
+    /// Drawing the grid to the terminal
+    pub render: Duration,
+    /// Frames decoded ahead and waiting, for players that decode ahead
+    pub queue_depth: Option<usize>,
+}
+
+/// Runtime-toggleable overlay of frame timings and A/V drift.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackDiagnostics {
+    visible: bool,
+    frames: u64,
+    last: FrameTiming,
+    drift: f64,
+    anchor: Option<(Instant, Duration)>,
+}
+
+impl PlaybackDiagnostics {
+    /// A hidden overlay with nothing recorded.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            visible: false,
+            frames: 0,
+            last: FrameTiming {
+                decode: Duration::ZERO,
+                convert: Duration::ZERO,
+                render: Duration::ZERO,
+                queue_depth: None,
+            },
+            drift: 0.0,
+            anchor: None,
+        }
+    }
+
+    /// Shows the overlay if hidden, hides it if shown.
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Shows or hides the overlay.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Whether the overlay is drawn.
+    #[must_use]
+    pub const fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Ties presentation time `timestamp` to the wall-clock instant `now`.
+    /// Call it when playback starts, resumes from pause, or seeks, so time
+    /// spent paused or skipped doesn't count as drift.
+    pub fn resync(&mut self, timestamp: Duration, now: Instant) {
+        self.anchor = Some((now, timestamp));
+    }
+
+    /// Records a frame with presentation time `timestamp` that reached the
+    /// screen at `now`. The first frame after creation resyncs on itself.
+    pub fn record(&mut self, timing: FrameTiming, timestamp: Duration, now: Instant) {
+        let (anchor_now, anchor_timestamp) = *self.anchor.get_or_insert((now, timestamp));
+        let wall = now.saturating_duration_since(anchor_now).as_secs_f64();
+        let media = timestamp.as_secs_f64() - anchor_timestamp.as_secs_f64();
+        self.drift = wall - media;
+        self.last = timing;
+        self.frames += 1;
+    }
+
+    /// Timings of the last recorded frame.
+    #[must_use]
+    pub const fn last(&self) -> FrameTiming {
+        self.last
+    }
+
+    /// Seconds the last frame was late (positive) or early (negative).
+    #[must_use]
+    pub const fn drift(&self) -> f64 {
+        self.drift
+    }
+
+    /// The overlay's lines, each 14 cells wide.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let ms = |time: Duration| format!("{:.1}ms", time.as_secs_f64() * 1000.0);
+        [
+            ("frame", self.frames.to_string()),
+            ("decode", ms(self.last.decode)),
+            ("convert", ms(self.last.convert)),
+            ("render", ms(self.last.render)),
+            (
+                "queue",
+                self.last
+                    .queue_depth
+                    .map_or_else(|| "--".to_string(), |depth| depth.to_string()),
+            ),
+            ("drift", format!("{:+.0}ms", self.drift * 1000.0)),
+        ]
+        .into_iter()
+        .map(|(label, value)| format!("{label:<7}{value:>7}"))
+        .collect()
+    }
+
+    /// Draws the overlay in the top-left corner of `grid` if visible.
+    pub fn render(&self, grid: &mut BrailleGrid) {
+        if !self.visible {
+            return;
+        }
+        for (y, line) in self.lines().iter().enumerate().take(grid.height()) {
+            put_text(grid, 0, y, line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_drift_follows_the_anchor() {
+        let start = Instant::now();
+        let mut diagnostics = PlaybackDiagnostics::new();
+
+        // First frame anchors itself
+        diagnostics.record(FrameTiming::default(), ms(1000), start);
+        assert_eq!(diagnostics.drift(), 0.0);
+
+        // Shown 10ms before it was due
+        diagnostics.record(FrameTiming::default(), ms(1040), start + ms(30));
+        assert!((diagnostics.drift() + 0.010).abs() < 1e-9);
+
+        // A long pause, then a resync: no drift
+        diagnostics.resync(ms(1040), start + ms(5000));
+        diagnostics.record(FrameTiming::default(), ms(1080), start + ms(5040));
+        assert!(diagnostics.drift().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lines() {
+        let start = Instant::now();
+        let mut diagnostics = PlaybackDiagnostics::new();
+        diagnostics.resync(Duration::ZERO, start);
+        let timing = FrameTiming {
+            decode: Duration::from_micros(4100),
+            convert: ms(2),
+            render: Duration::from_micros(1300),
+            queue_depth: Some(3),
+        };
+        diagnostics.record(timing, ms(40), start + ms(52));
+        assert_eq!(diagnostics.last(), timing);
+        assert_eq!(
+            diagnostics.lines(),
+            [
+                "frame        1",
+                "decode   4.1ms",
+                "convert  2.0ms",
+                "render   1.3ms",
+                "queue        3",
+                "drift    +12ms",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_only_when_visible() {
+        let mut grid = BrailleGrid::new(20, 3).unwrap();
+        let mut diagnostics = PlaybackDiagnostics::new();
+        diagnostics.render(&mut grid);
+        assert_eq!(grid.get_char(0, 0), '⠀');
+
+        diagnostics.toggle();
+        assert!(diagnostics.is_visible());
+        diagnostics.render(&mut grid);
+        let row: String = (0..14).map(|x| grid.get_char(x, 2)).collect();
+        assert_eq!(row, "convert  0.0ms");
+        assert_eq!(grid.get_char(14, 0), '⠀');
+    }
+}
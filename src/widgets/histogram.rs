@@ -0,0 +1,230 @@
+//! Luminance histogram overlay for exposure tuning.
+//!
+//! [`HistogramOverlay`] draws a luminance histogram as braille bars, with a
+//! status line underneath showing the mean level and the threshold automatic
+//! thresholding would pick. When too many pixels sit at pure black or pure
+//! white, the status line adds a red shadow or highlight clipping warning
+//! and the matching end of the histogram turns red. Watching it while
+//! adjusting brightness, contrast, or threshold shows straight away whether a
+//! camera or image is over- or underexposed.
+//!
+//! The region is cleared before drawing, so the overlay stays readable on top
+//! of a rendered frame.
+//!
+//! # Layout
+//!
+//! A `width × height` region holds `height - 1` rows of bars, each cell two
+//! bars wide, and a status row at the bottom:
+//!
+//! ```text
+//! mean 118 thr 131 ▲ highlights 6%
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::widgets::HistogramOverlay;
+//! use dotmax::BrailleGrid;
+//!
+//! // Usually `dotmax::analysis::luma_histogram(&image)`
+//! let mut histogram = [0u32; 256];
+//! histogram[40..200].fill(10);
+//!
+//! let mut grid = BrailleGrid::new(40, 12)?;
+//! HistogramOverlay::new(histogram).render(&mut grid, 0, 6, 32, 6)?;
+//!
+//! assert_eq!(grid.get_char(0, 11), 'm'); // "mean 120 thr 119"
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::fmt::Write;
+
+use crate::analysis::ExposureStats;
+use crate::error::DotmaxError;
+use crate::grid::{BrailleGrid, Color};
+
+use super::put_text;
+
+/// Color for clipping warnings and the clipped ends of the histogram.
+const WARNING: Color = Color::rgb(255, 64, 64);
+
+/// Luminance histogram with clipping warnings.
+#[derive(Debug, Clone)]
+pub struct HistogramOverlay {
+    histogram: [u32; 256],
+    clip_warning: f32,
+}
+
+impl HistogramOverlay {
+    /// An overlay for a 256-bin luminance histogram, such as one from
+    /// [`luma_histogram`](crate::analysis::luma_histogram).
+    #[must_use]
+    pub const fn new(histogram: [u32; 256]) -> Self {
+        Self {
+            histogram,
+            clip_warning: 0.02,
+        }
+    }
+
+    /// Fraction of pixels at black or white that triggers a clipping warning
+    /// (default `0.02`, i.e. 2%).
+    #[must_use]
+    pub const fn clip_warning(mut self, fraction: f32) -> Self {
+        self.clip_warning = fraction;
+        self
+    }
+
+    /// Statistics behind the status line.
+    #[must_use]
+    pub fn stats(&self) -> ExposureStats {
+        ExposureStats::from_histogram(&self.histogram)
+    }
+
+    /// Draws the overlay into the `width × height` cell region whose top-left
+    /// cell is `(x, y)`. Anything outside the grid is clipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if `width` is 0 or `height`
+    /// is less than 2 (one row of bars plus the status row).
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), DotmaxError> {
+        if width == 0 || height < 2 {
+            return Err(DotmaxError::InvalidDimensions { width, height });
+        }
+        let visible_width = width.min(grid.width().saturating_sub(x));
+        let visible_height = height.min(grid.height().saturating_sub(y));
+        if visible_width == 0 || visible_height == 0 {
+            return Ok(());
+        }
+        grid.clear_region(x, y, visible_width, visible_height)?;
+
+        let stats = self.stats();
+        let shadows = stats.shadows_clipped > self.clip_warning;
+        let highlights = stats.highlights_clipped > self.clip_warning;
+
+        // Average count per level in each bar, so bars covering more levels
+        // aren't taller just for that
+        let bars = width * 2;
+        let heights: Vec<f32> = (0..bars)
+            .map(|bar| {
+                let start = bar * 256 / bars;
+                let end = ((bar + 1) * 256 / bars).max(start + 1).min(256);
+                let sum: u64 = self.histogram[start..end]
+                    .iter()
+                    .map(|&c| u64::from(c))
+                    .sum();
+                sum as f32 / (end - start) as f32
+            })
+            .collect();
+        let tallest = heights.iter().copied().fold(0.0, f32::max);
+
+        let dot_rows = (height - 1) * 4;
+        let (left, bottom) = (x * 2, (y + height - 1) * 4);
+        for (bar, &value) in heights.iter().enumerate() {
+            if value <= 0.0 {
+                continue;
+            }
+            let dots = ((value / tallest * dot_rows as f32).round() as usize).clamp(1, dot_rows);
+            for dot in 1..=dots {
+                // Out-of-bounds dots are clipped
+                let _ = grid.set_dot(left + bar, bottom - dot);
+            }
+        }
+        for row in y..y + height - 1 {
+            if shadows {
+                let _ = grid.set_cell_color(x, row, WARNING);
+            }
+            if highlights {
+                let _ = grid.set_cell_color(x + width - 1, row, WARNING);
+            }
+        }
+
+        let mut text = format!("mean {:.0} thr {}", stats.mean, stats.threshold);
+        let plain = text.chars().count();
+        if shadows {
+            let _ = write!(text, " ▼ shadows {:.0}%", stats.shadows_clipped * 100.0);
+        }
+        if highlights {
+            let _ = write!(
+                text,
+                " ▲ highlights {:.0}%",
+                stats.highlights_clipped * 100.0
+            );
+        }
+        let text: String = text.chars().take(width).collect();
+        let bottom_row = y + height - 1;
+        put_text(grid, x, bottom_row, &text);
+        for offset in plain..text.chars().count() {
+            let _ = grid.set_cell_color(x + offset, bottom_row, WARNING);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(grid: &BrailleGrid, y: usize) -> String {
+        (0..grid.width()).map(|x| grid.get_char(x, y)).collect()
+    }
+
+    #[test]
+    fn test_bars_follow_counts() {
+        let mut histogram = [0u32; 256];
+        histogram[10] = 50;
+        histogram[250] = 100;
+        let mut grid = BrailleGrid::new(4, 3).unwrap();
+        HistogramOverlay::new(histogram)
+            .clip_warning(1.0)
+            .render(&mut grid, 0, 0, 4, 3)
+            .unwrap();
+
+        // 8 bars of 32 levels: level 10 in the first, 250 in the last
+        assert!(grid.is_dot_set(0, 7));
+        assert!(!grid.is_dot_set(0, 3));
+        assert!(grid.is_dot_set(7, 0));
+        assert!(!grid.is_dot_set(3, 7));
+    }
+
+    #[test]
+    fn test_clipping_warnings() {
+        let mut histogram = [0u32; 256];
+        histogram[128] = 90;
+        histogram[255] = 10;
+        let mut grid = BrailleGrid::new(40, 3).unwrap();
+        HistogramOverlay::new(histogram)
+            .render(&mut grid, 0, 0, 40, 3)
+            .unwrap();
+
+        assert_eq!(
+            row_text(&grid, 2).trim_end_matches('⠀'),
+            "mean 141 thr 128 ▲ highlights 10%"
+        );
+        assert_eq!(grid.get_color(39, 0), Some(WARNING));
+        assert_eq!(grid.get_color(0, 0), None);
+        assert_eq!(grid.get_color(17, 2), Some(WARNING));
+    }
+
+    #[test]
+    fn test_rejects_regions_without_room_for_bars() {
+        let mut grid = BrailleGrid::new(10, 10).unwrap();
+        let overlay = HistogramOverlay::new([1; 256]);
+        assert!(overlay.render(&mut grid, 0, 0, 10, 1).is_err());
+        assert!(overlay.render(&mut grid, 0, 0, 0, 5).is_err());
+        assert!(overlay.render(&mut grid, 8, 8, 10, 5).is_ok());
+    }
+}
@@ -0,0 +1,412 @@
+//! Legends and scale bars for intensity mappings.
+//!
+//! A legend shows which color or character stands for which value, so a
+//! heatmap or density plot can be read without guessing. Both legend types
+//! draw a bar sampled from 0.0 to 1.0 and label it with the data values at the
+//! minimum, midpoint, and maximum. Labels are written in the grid's character
+//! layer, so they render as plain text next to the braille output.
+//!
+//! # Layout
+//!
+//! Horizontal legends occupy `length` cells across: an optional title row, the
+//! bar row, and a label row underneath. Vertical legends put the maximum at the
+//! top, with labels to the right of a one-cell-wide bar. [`ColorLegend::size`]
+//! and [`DensityLegend::size`] report the footprint for placement. Anything
+//! falling outside the grid is clipped.
+//!
+//! # Examples
+//!
+//! ```
+//! use dotmax::widgets::ColorLegend;
+//! use dotmax::{BrailleGrid, ColorScheme};
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! ColorLegend::new(ColorScheme::heat_map())
+//!     .range(-10.0, 30.0)
+//!     .title("°C")
+//!     .render(&mut grid, 0, 7, 30)?;
+//!
+//! assert_eq!(grid.get_char(0, 7), '°');
+//! assert_eq!(grid.get_char(0, 9), '-'); // "-10" under the left end
+//! assert_eq!(grid.get_char(29, 9), '0'); // "30" ends under the right end
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use crate::color::schemes::ColorScheme;
+use crate::density::DensitySet;
+use crate::error::DotmaxError;
+use crate::grid::BrailleGrid;
+
+use super::put_text;
+
+/// Direction a legend's bar runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Minimum on the left, labels below the bar
+    #[default]
+    Horizontal,
+    /// Maximum at the top, labels to the right of the bar
+    Vertical,
+}
+
+/// Range, title, and layout shared by both legend types.
+#[derive(Debug, Clone)]
+struct Scale {
+    min: f64,
+    max: f64,
+    title: Option<String>,
+    orientation: Orientation,
+    precision: Option<usize>,
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 1.0,
+            title: None,
+            orientation: Orientation::Horizontal,
+            precision: None,
+        }
+    }
+}
+
+impl Scale {
+    /// Labels for min, mid, and max, formatted with a shared precision.
+    fn labels(&self) -> [String; 3] {
+        let mid = (self.min + self.max) / 2.0;
+        let precision = self.precision.unwrap_or_else(|| {
+            let span = (self.max - self.min).abs();
+            if [self.min, mid, self.max]
+                .iter()
+                .all(|v| v.fract().abs() < f64::EPSILON)
+                || span >= 100.0
+            {
+                0
+            } else if span >= 1.0 {
+                1
+            } else {
+                2
+            }
+        });
+        [self.min, mid, self.max].map(|v| format!("{v:.precision$}"))
+    }
+
+    fn title_rows(&self) -> usize {
+        usize::from(self.title.is_some())
+    }
+
+    fn size(&self, length: usize) -> (usize, usize) {
+        let [min, mid, max] = self.labels();
+        let title = self.title.as_ref().map_or(0, |t| t.chars().count());
+        match self.orientation {
+            Orientation::Horizontal => (length.max(title), self.title_rows() + 2),
+            Orientation::Vertical => {
+                let label = [min, mid, max].iter().map(|l| l.chars().count()).max();
+                (
+                    (2 + label.unwrap_or(0)).max(title),
+                    self.title_rows() + length,
+                )
+            }
+        }
+    }
+
+    /// Draw the title, call `draw_cell(grid, cell_x, cell_y, t)` for each bar
+    /// cell, then draw the labels.
+    #[allow(clippy::cast_precision_loss)]
+    fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        length: usize,
+        mut draw_cell: impl FnMut(&mut BrailleGrid, usize, usize, f32),
+    ) -> Result<(), DotmaxError> {
+        if length == 0 {
+            return Err(DotmaxError::InvalidDimensions {
+                width: length,
+                height: 1,
+            });
+        }
+        if let Some(title) = &self.title {
+            put_text(grid, x, y, title);
+        }
+        let top = y + self.title_rows();
+        let last = (length - 1).max(1) as f32;
+        let [min, mid, max] = self.labels();
+
+        match self.orientation {
+            Orientation::Horizontal => {
+                for i in 0..length {
+                    if x + i < grid.width() && top < grid.height() {
+                        draw_cell(grid, x + i, top, i as f32 / last);
+                    }
+                }
+                let row = top + 1;
+                let max_start = (x + length).saturating_sub(max.chars().count()).max(x);
+                let mid_len = mid.chars().count();
+                let mid_start = (x + length / 2).saturating_sub(mid_len / 2);
+                put_text(grid, x, row, &min);
+                // The midpoint label only fits if it leaves a gap on both sides
+                if mid_start > x + min.chars().count() && mid_start + mid_len < max_start {
+                    put_text(grid, mid_start, row, &mid);
+                }
+                // On a bar too short for both end labels, the max label wins
+                put_text(grid, max_start, row, &max);
+            }
+            Orientation::Vertical => {
+                for i in 0..length {
+                    if x < grid.width() && top + i < grid.height() {
+                        draw_cell(grid, x, top + i, 1.0 - i as f32 / last);
+                    }
+                }
+                let col = x + 2;
+                put_text(grid, col, top + length - 1, &min);
+                if length >= 5 {
+                    put_text(grid, col, top + length / 2, &mid);
+                }
+                put_text(grid, col, top, &max);
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! scale_setters {
+    () => {
+        /// Data values represented by the two ends of the bar (default
+        /// `0.0..=1.0`). These only affect the labels.
+        #[must_use]
+        pub const fn range(mut self, min: f64, max: f64) -> Self {
+            self.scale.min = min;
+            self.scale.max = max;
+            self
+        }
+
+        /// Text written above the bar, such as a unit or quantity name.
+        #[must_use]
+        pub fn title(mut self, title: impl Into<String>) -> Self {
+            self.scale.title = Some(title.into());
+            self
+        }
+
+        /// Run the bar top to bottom instead of left to right.
+        #[must_use]
+        pub const fn vertical(mut self) -> Self {
+            self.scale.orientation = Orientation::Vertical;
+            self
+        }
+
+        /// Fixed number of decimal places for labels. By default whole-number
+        /// ranges get none and smaller spans get one or two.
+        #[must_use]
+        pub const fn precision(mut self, decimals: usize) -> Self {
+            self.scale.precision = Some(decimals);
+            self
+        }
+
+        /// Cells covered by [`render`](Self::render) with a bar of `length`
+        /// cells, as `(width, height)`.
+        #[must_use]
+        pub fn size(&self, length: usize) -> (usize, usize) {
+            self.scale.size(length)
+        }
+    };
+}
+
+/// A gradient bar showing a [`ColorScheme`], with value labels.
+///
+/// The bar is drawn as fully set braille cells colored from the scheme.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::widgets::ColorLegend;
+/// use dotmax::{BrailleGrid, ColorScheme};
+///
+/// let mut grid = BrailleGrid::new(80, 24)?;
+/// let legend = ColorLegend::new(ColorScheme::blue_purple()).range(0.0, 1.0).vertical();
+///
+/// // Place it against the right edge
+/// let (width, _) = legend.size(20);
+/// legend.render(&mut grid, 80 - width, 2, 20)?;
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColorLegend {
+    scheme: ColorScheme,
+    scale: Scale,
+}
+
+impl ColorLegend {
+    /// Create a horizontal legend for `scheme` labelled `0` to `1`.
+    #[must_use]
+    pub fn new(scheme: ColorScheme) -> Self {
+        Self {
+            scheme,
+            scale: Scale::default(),
+        }
+    }
+
+    scale_setters!();
+
+    /// Draw the legend with its top-left corner at cell `(x, y)` and a bar
+    /// `length` cells long.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if `length` is 0.
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        length: usize,
+    ) -> Result<(), DotmaxError> {
+        self.scale.render(grid, x, y, length, |grid, cx, cy, t| {
+            for dy in 0..4 {
+                for dx in 0..2 {
+                    let _ = grid.set_dot(cx * 2 + dx, cy * 4 + dy);
+                }
+            }
+            let _ = grid.set_cell_color(cx, cy, self.scheme.sample(t));
+        })
+    }
+}
+
+/// A character ramp showing a [`DensitySet`], with value labels.
+///
+/// # Examples
+///
+/// ```
+/// use dotmax::density::DensitySet;
+/// use dotmax::widgets::DensityLegend;
+/// use dotmax::BrailleGrid;
+///
+/// let mut grid = BrailleGrid::new(20, 3)?;
+/// DensityLegend::new(DensitySet::simple()).render(&mut grid, 0, 0, 10)?;
+///
+/// assert_eq!(grid.get_char(0, 0), ' ');
+/// assert_eq!(grid.get_char(9, 0), '@');
+/// # Ok::<(), dotmax::DotmaxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DensityLegend {
+    density: DensitySet,
+    scale: Scale,
+}
+
+impl DensityLegend {
+    /// Create a horizontal legend for `density` labelled `0` to `1`.
+    #[must_use]
+    pub fn new(density: DensitySet) -> Self {
+        Self {
+            density,
+            scale: Scale::default(),
+        }
+    }
+
+    scale_setters!();
+
+    /// Draw the legend with its top-left corner at cell `(x, y)` and a bar
+    /// `length` cells long.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DotmaxError::InvalidDimensions`] if `length` is 0.
+    pub fn render(
+        &self,
+        grid: &mut BrailleGrid,
+        x: usize,
+        y: usize,
+        length: usize,
+    ) -> Result<(), DotmaxError> {
+        self.scale.render(grid, x, y, length, |grid, cx, cy, t| {
+            let _ = grid.set_char(cx, cy, self.density.map(t));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(grid: &BrailleGrid, y: usize) -> String {
+        (0..grid.width()).map(|x| grid.get_char(x, y)).collect()
+    }
+
+    #[test]
+    fn test_horizontal_labels() {
+        let mut grid = BrailleGrid::new(20, 2).unwrap();
+        ColorLegend::new(ColorScheme::grayscale())
+            .range(0.0, 100.0)
+            .render(&mut grid, 0, 0, 20)
+            .unwrap();
+        assert_eq!(row_text(&grid, 1).replace('⠀', " "), "0        50      100");
+    }
+
+    #[test]
+    fn test_bar_colors_follow_scheme() {
+        let scheme = ColorScheme::heat_map();
+        let mut grid = BrailleGrid::new(10, 2).unwrap();
+        ColorLegend::new(scheme.clone())
+            .render(&mut grid, 0, 0, 10)
+            .unwrap();
+        assert_eq!(grid.get_color(0, 0), Some(scheme.sample(0.0)));
+        assert_eq!(grid.get_color(9, 0), Some(scheme.sample(1.0)));
+        assert_eq!(grid.get_char(4, 0), '⣿');
+    }
+
+    #[test]
+    fn test_vertical_puts_max_on_top() {
+        let mut grid = BrailleGrid::new(10, 7).unwrap();
+        DensityLegend::new(DensitySet::simple())
+            .range(1.0, 9.0)
+            .title("n")
+            .vertical()
+            .render(&mut grid, 0, 0, 6)
+            .unwrap();
+        assert_eq!(grid.get_char(0, 0), 'n');
+        assert_eq!(grid.get_char(0, 1), '@');
+        assert_eq!(grid.get_char(0, 6), ' ');
+        assert_eq!(grid.get_char(2, 1), '9');
+        assert_eq!(grid.get_char(2, 4), '5');
+        assert_eq!(grid.get_char(2, 6), '1');
+    }
+
+    #[test]
+    fn test_mid_label_dropped_when_crowded() {
+        let mut grid = BrailleGrid::new(10, 2).unwrap();
+        DensityLegend::new(DensitySet::blocks())
+            .range(0.0, 0.5)
+            .render(&mut grid, 0, 0, 10)
+            .unwrap();
+        // "0.00" and "0.50" leave no room for "0.25"
+        assert_eq!(row_text(&grid, 1).replace('⠀', " "), "0.00  0.50");
+    }
+
+    #[test]
+    fn test_size_and_clipping() {
+        let legend = DensityLegend::new(DensitySet::simple()).title("a long title");
+        assert_eq!(legend.size(5), (12, 3));
+        assert_eq!(legend.clone().vertical().size(5), (12, 6));
+
+        // Partially off-grid legends are clipped rather than rejected
+        let mut grid = BrailleGrid::new(4, 2).unwrap();
+        assert!(legend.render(&mut grid, 2, 0, 10).is_ok());
+        assert!(matches!(
+            legend.render(&mut grid, 0, 0, 0),
+            Err(DotmaxError::InvalidDimensions { .. })
+        ));
+    }
+
+    #[test]
+    fn test_precision_override() {
+        assert_eq!(Scale::default().labels(), ["0.0", "0.5", "1.0"]);
+        let scale = Scale {
+            precision: Some(2),
+            ..Scale::default()
+        };
+        assert_eq!(scale.labels(), ["0.00", "0.50", "1.00"]);
+    }
+}
@@ -0,0 +1,35 @@
+//! Reusable chart furniture drawn onto a [`BrailleGrid`](crate::BrailleGrid).
+//!
+//! Widgets don't own a grid; each one renders into a region of a grid you
+//! already have, so they compose with images, plots, and primitives on the
+//! same frame.
+//!
+//! - [`ColorLegend`]: a colored gradient bar for a [`ColorScheme`](crate::ColorScheme)
+//! - [`DensityLegend`]: a character ramp for a [`DensitySet`](crate::density::DensitySet)
+//! - [`PlaybackOsd`]: a time, progress, and play/pause bar for media playback
+//! - [`HistogramOverlay`]: a luminance histogram with clipping warnings
+//! - [`StatsHud`]: frame rate, frame times, and bytes per frame for animations
+//! - [`PlaybackDiagnostics`]: per-frame decode, convert, and render times and A/V drift
+
+pub mod diagnostics;
+pub mod histogram;
+pub mod legend;
+pub mod osd;
+pub mod stats_hud;
+
+pub use diagnostics::{FrameTiming, PlaybackDiagnostics};
+pub use histogram::HistogramOverlay;
+pub use legend::{ColorLegend, DensityLegend, Orientation};
+pub use osd::PlaybackOsd;
+pub use stats_hud::StatsHud;
+
+use crate::grid::BrailleGrid;
+
+/// Write `text` into the character layer starting at a cell, clipping at the
+/// grid edges.
+fn put_text(grid: &mut BrailleGrid, x: usize, y: usize, text: &str) {
+    for (offset, ch) in text.chars().enumerate() {
+        // Out-of-bounds cells are clipped
+        let _ = grid.set_char(x + offset, y, ch);
+    }
+}
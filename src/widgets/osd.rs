@@ -0,0 +1,289 @@
+//! On-screen display for media playback.
+//!
+//! [`PlaybackOsd`] draws a one-row status bar across the bottom of a frame:
+//! a play/pause indicator, elapsed and total time, a progress bar, and a
+//! volume readout. It is written into the grid's character layer, so it sits
+//! on top of whatever braille image the frame already holds.
+//!
+//! The bar hides itself a few seconds after the last [`wake`](PlaybackOsd::wake)
+//! and stays up while playback is paused, the usual behavior for a video
+//! player. `quick`'s playback helpers use it; custom players can drive one
+//! from a frame hook or their own loop.
+//!
+//! # Layout
+//!
+//! ```text
+//! ▶ 00:42 / 03:10 ████░░░░░░░░░░░░░░░░ vol --
+//! ```
+//!
+//! The progress bar takes whatever width is left. On narrow grids the volume
+//! readout is dropped first, then the bar. Times switch to `h:mm:ss` at one
+//! hour. Volume is a placeholder until audio playback exists: it shows `--`
+//! unless a level is set with [`PlaybackOsd::set_volume`].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use dotmax::widgets::PlaybackOsd;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! let mut osd = PlaybackOsd::new();
+//! osd.set_position(Duration::from_secs(30), Some(Duration::from_secs(120)));
+//! osd.wake();
+//! osd.render(&mut grid);
+//!
+//! assert_eq!(grid.get_char(0, 9), '▶');
+//! assert_eq!(grid.get_char(2, 9), '0'); // "00:30 / 02:00"
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::time::{Duration, Instant};
+
+use crate::grid::BrailleGrid;
+
+use super::put_text;
+
+/// Narrowest progress bar worth drawing, in cells.
+const MIN_BAR_WIDTH: usize = 4;
+
+/// Playback status bar with auto-hide.
+#[derive(Debug, Clone)]
+pub struct PlaybackOsd {
+    elapsed: Duration,
+    total: Option<Duration>,
+    progress: Option<f32>,
+    paused: bool,
+    volume: Option<u8>,
+    hide_after: Duration,
+    woken_at: Option<Instant>,
+}
+
+impl Default for PlaybackOsd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlaybackOsd {
+    /// A hidden OSD at 00:00 that auto-hides 3 seconds after each wake.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            total: None,
+            progress: None,
+            paused: false,
+            volume: None,
+            hide_after: Duration::from_secs(3),
+            woken_at: None,
+        }
+    }
+
+    /// How long the OSD stays up after a wake (default 3 seconds).
+    /// `Duration::ZERO` keeps it visible permanently.
+    #[must_use]
+    pub const fn hide_after(mut self, delay: Duration) -> Self {
+        self.hide_after = delay;
+        self
+    }
+
+    /// Sets the playback position. With a known `total`, the progress bar
+    /// follows `elapsed / total`; without one the bar stays empty.
+    pub fn set_position(&mut self, elapsed: Duration, total: Option<Duration>) {
+        self.elapsed = elapsed;
+        self.total = total;
+        self.progress = total
+            .filter(|t| !t.is_zero())
+            .map(|t| (elapsed.as_secs_f32() / t.as_secs_f32()).min(1.0));
+    }
+
+    /// Sets the position from a frame hook's [`FrameInfo`](crate::media::FrameInfo).
+    ///
+    /// When the media has no known duration (typical for GIF and APNG), the
+    /// bar falls back to the frame count.
+    #[cfg(feature = "image")]
+    pub fn update(&mut self, info: &crate::media::FrameInfo) {
+        self.set_position(info.timestamp, info.duration);
+        if self.progress.is_none() {
+            self.progress = info.progress();
+        }
+    }
+
+    /// Sets the play/pause indicator and wakes the OSD.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.wake();
+    }
+
+    /// Whether the indicator shows paused.
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Sets the volume readout as a percentage, or `None` for `--`.
+    pub fn set_volume(&mut self, volume: Option<u8>) {
+        self.volume = volume.map(|v| v.min(100));
+    }
+
+    /// Shows the OSD, restarting the auto-hide timer.
+    pub fn wake(&mut self) {
+        self.wake_at(Instant::now());
+    }
+
+    /// [`wake`](Self::wake) with an explicit clock reading.
+    pub fn wake_at(&mut self, now: Instant) {
+        self.woken_at = Some(now);
+    }
+
+    /// Whether the OSD should be drawn now.
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.is_visible_at(Instant::now())
+    }
+
+    /// Whether the OSD should be drawn at `now`. Paused playback keeps it up.
+    #[must_use]
+    pub fn is_visible_at(&self, now: Instant) -> bool {
+        self.paused
+            || self.woken_at.is_some_and(|woken| {
+                self.hide_after.is_zero() || now.saturating_duration_since(woken) < self.hide_after
+            })
+    }
+
+    /// The status line laid out for `width` cells, padded with spaces.
+    #[must_use]
+    pub fn text(&self, width: usize) -> String {
+        let icon = if self.paused { '‖' } else { '▶' };
+        let mut time = format_time(self.elapsed);
+        if let Some(total) = self.total {
+            time = format!("{time} / {}", format_time(total));
+        }
+        let volume = self
+            .volume
+            .map_or_else(|| "vol --".to_string(), |v| format!("vol {v}%"));
+
+        let head = format!("{icon} {time}");
+        let head_len = head.chars().count();
+        let tail_len = volume.chars().count() + 1;
+
+        let mut line = head;
+        if head_len + 1 + MIN_BAR_WIDTH + tail_len <= width {
+            line.push(' ');
+            line.push_str(&self.bar(width - head_len - 1 - tail_len));
+            line.push(' ');
+            line.push_str(&volume);
+        } else if head_len + 1 + MIN_BAR_WIDTH <= width {
+            line.push(' ');
+            line.push_str(&self.bar(width - head_len - 1));
+        }
+
+        let mut line: String = line.chars().take(width).collect();
+        let len = line.chars().count();
+        line.extend(std::iter::repeat(' ').take(width - len));
+        line
+    }
+
+    /// Draws the status line across the bottom row of `grid` if visible.
+    pub fn render(&self, grid: &mut BrailleGrid) {
+        self.render_at(grid, Instant::now());
+    }
+
+    /// [`render`](Self::render) with an explicit clock reading.
+    pub fn render_at(&self, grid: &mut BrailleGrid, now: Instant) {
+        if !self.is_visible_at(now) || grid.height() == 0 {
+            return;
+        }
+        let y = grid.height() - 1;
+        put_text(grid, 0, y, &self.text(grid.width()));
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn bar(&self, width: usize) -> String {
+        let filled = self
+            .progress
+            .map_or(0, |p| (p.clamp(0.0, 1.0) * width as f32).round() as usize);
+        let mut bar = "█".repeat(filled);
+        bar.push_str(&"░".repeat(width - filled));
+        bar
+    }
+}
+
+/// `mm:ss` below an hour, `h:mm:ss` from there on.
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(grid: &BrailleGrid, y: usize) -> String {
+        (0..grid.width()).map(|x| grid.get_char(x, y)).collect()
+    }
+
+    #[test]
+    fn test_full_layout() {
+        let mut osd = PlaybackOsd::new();
+        osd.set_position(Duration::from_secs(30), Some(Duration::from_secs(120)));
+        assert_eq!(osd.text(32), "▶ 00:30 / 02:00 ██░░░░░░░ vol --");
+    }
+
+    #[test]
+    fn test_narrow_drops_volume_then_bar() {
+        let mut osd = PlaybackOsd::new();
+        osd.set_position(Duration::from_secs(5), Some(Duration::from_secs(10)));
+        osd.set_paused(true);
+        assert_eq!(osd.text(20), "‖ 00:05 / 00:10 ██░░");
+        assert_eq!(osd.text(17), "‖ 00:05 / 00:10  ");
+        assert_eq!(osd.text(5), "‖ 00:");
+    }
+
+    #[test]
+    fn test_long_times_and_volume() {
+        let mut osd = PlaybackOsd::new();
+        osd.set_position(Duration::from_secs(3725), None);
+        osd.set_volume(Some(80));
+        assert_eq!(osd.text(22), "▶ 1:02:05 ░░░░ vol 80%");
+    }
+
+    #[test]
+    fn test_auto_hide_and_pause() {
+        let start = Instant::now();
+        let mut osd = PlaybackOsd::new().hide_after(Duration::from_secs(2));
+        assert!(!osd.is_visible_at(start));
+
+        osd.wake_at(start);
+        assert!(osd.is_visible_at(start + Duration::from_secs(1)));
+        assert!(!osd.is_visible_at(start + Duration::from_secs(2)));
+
+        osd.set_paused(true);
+        assert!(osd.is_visible_at(start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_render_bottom_row_only_when_visible() {
+        let start = Instant::now();
+        let mut grid = BrailleGrid::new(12, 3).unwrap();
+        let mut osd = PlaybackOsd::new();
+
+        osd.render_at(&mut grid, start);
+        assert_eq!(grid.get_char(0, 2), '⠀');
+
+        osd.wake_at(start);
+        osd.render_at(&mut grid, start);
+        assert_eq!(row_text(&grid, 2), "▶ 00:00 ░░░░");
+        assert_eq!(grid.get_char(0, 1), '⠀');
+    }
+}
@@ -0,0 +1,255 @@
+//! Frame statistics overlay for animations.
+//!
+//! [`StatsHud`] keeps the timings of the last couple of seconds of frames
+//! and draws them as a small block in the top-right corner of a grid: the
+//! achieved frame rate, 50th/95th/99th percentile frame times, how many
+//! frames overran their budget, and the average bytes sent to the terminal
+//! per frame. It is written into the character layer with
+//! [`set_char`](BrailleGrid::set_char), so it sits on top of the frame.
+//!
+//! [`AnimationLoop`](crate::animation::AnimationLoop) draws one when built
+//! with [`show_stats(true)`](crate::animation::AnimationLoopBuilder::show_stats);
+//! custom loops can feed one through [`record`](StatsHud::record).
+//!
+//! # Layout
+//!
+//! ```text
+//! fps      59.8
+//! p50     2.1ms
+//! p95     3.4ms
+//! p99    12.0ms
+//! dropped     3
+//! B/frame  1.2k
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use dotmax::widgets::StatsHud;
+//! use dotmax::BrailleGrid;
+//!
+//! let mut hud = StatsHud::new(60);
+//! for _ in 0..30 {
+//!     hud.record(Duration::from_millis(2), Duration::from_millis(20), 900);
+//! }
+//! assert_eq!(hud.dropped(), 0);
+//!
+//! let mut grid = BrailleGrid::new(40, 10)?;
+//! hud.render(&mut grid);
+//! assert_eq!(grid.get_char(27, 0), 'f'); // "fps      50.0"
+//! # Ok::<(), dotmax::DotmaxError>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::grid::BrailleGrid;
+
+use super::put_text;
+
+/// Frames kept for the rate, percentiles, and byte average.
+const WINDOW: usize = 120;
+
+/// Width of every HUD line, in cells.
+const LINE_WIDTH: usize = 13;
+
+/// One recorded frame.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    frame_time: Duration,
+    interval: Duration,
+    bytes: u64,
+}
+
+/// Rolling frame statistics with a corner overlay.
+#[derive(Debug, Clone)]
+pub struct StatsHud {
+    budget: Duration,
+    samples: VecDeque<Sample>,
+    dropped: u64,
+}
+
+impl StatsHud {
+    /// An empty HUD for an animation aiming at `target_fps` (at least 1).
+    /// Frames taking longer than `1 / target_fps` count as dropped.
+    #[must_use]
+    pub fn new(target_fps: u32) -> Self {
+        Self {
+            budget: Duration::from_secs_f64(1.0 / f64::from(target_fps.max(1))),
+            samples: VecDeque::with_capacity(WINDOW),
+            dropped: 0,
+        }
+    }
+
+    /// Records a frame that took `frame_time` to draw and render, started
+    /// `interval` after the one before it, and wrote `bytes` to the
+    /// terminal.
+    pub fn record(&mut self, frame_time: Duration, interval: Duration, bytes: u64) {
+        if frame_time > self.budget {
+            self.dropped += 1;
+        }
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            frame_time,
+            interval,
+            bytes,
+        });
+    }
+
+    /// Frames per second over the recent window, or 0 before any frames.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fps(&self) -> f64 {
+        let total: Duration = self.samples.iter().map(|s| s.interval).sum();
+        if total.is_zero() {
+            return 0.0;
+        }
+        self.samples.len() as f64 / total.as_secs_f64()
+    }
+
+    /// The frame time below which `percent` percent of recent frames fall
+    /// (nearest rank), or zero before any frames.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn percentile(&self, percent: f64) -> Duration {
+        let mut times: Vec<Duration> = self.samples.iter().map(|s| s.frame_time).collect();
+        if times.is_empty() {
+            return Duration::ZERO;
+        }
+        times.sort_unstable();
+        let rank = (percent.clamp(0.0, 100.0) / 100.0 * times.len() as f64).ceil() as usize;
+        times[rank.max(1) - 1]
+    }
+
+    /// Frames that overran their budget since the HUD was created.
+    #[must_use]
+    pub const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Average bytes written per frame over the recent window.
+    #[must_use]
+    pub fn bytes_per_frame(&self) -> u64 {
+        let total: u64 = self.samples.iter().map(|s| s.bytes).sum();
+        total / (self.samples.len().max(1) as u64)
+    }
+
+    /// The HUD's lines, each [`LINE_WIDTH`] cells wide.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let ms = |time: Duration| format!("{:.1}ms", time.as_secs_f64() * 1000.0);
+        [
+            ("fps", format!("{:.1}", self.fps())),
+            ("p50", ms(self.percentile(50.0))),
+            ("p95", ms(self.percentile(95.0))),
+            ("p99", ms(self.percentile(99.0))),
+            ("dropped", self.dropped.to_string()),
+            ("B/frame", format_bytes(self.bytes_per_frame())),
+        ]
+        .into_iter()
+        .map(|(label, value)| format!("{label:<7}{value:>6}"))
+        .collect()
+    }
+
+    /// Draws the HUD in the top-right corner of `grid`, clipped to fit.
+    pub fn render(&self, grid: &mut BrailleGrid) {
+        let x = grid.width().saturating_sub(LINE_WIDTH);
+        for (y, line) in self.lines().iter().enumerate().take(grid.height()) {
+            put_text(grid, x, y, line);
+        }
+    }
+}
+
+/// `812`, `1.2k`, `3.4M`: short enough for the HUD column.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1000 {
+        bytes.to_string()
+    } else if bytes < 1_000_000 {
+        format!("{:.1}k", bytes as f64 / 1000.0)
+    } else {
+        format!("{:.1}M", bytes as f64 / 1_000_000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_percentiles_and_drops() {
+        let mut hud = StatsHud::new(100); // 10ms budget
+        for millis in 1..=100 {
+            hud.record(ms(millis), ms(10), 0);
+        }
+        assert_eq!(hud.percentile(50.0), ms(50));
+        assert_eq!(hud.percentile(95.0), ms(95));
+        assert_eq!(hud.percentile(99.0), ms(99));
+        assert_eq!(hud.percentile(0.0), ms(1));
+        assert_eq!(hud.dropped(), 90);
+        assert!((hud.fps() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_window_rolls_but_drops_accumulate() {
+        let mut hud = StatsHud::new(60);
+        for _ in 0..WINDOW {
+            hud.record(ms(100), ms(100), 5000);
+        }
+        for _ in 0..WINDOW {
+            hud.record(ms(1), ms(20), 100);
+        }
+        assert_eq!(hud.percentile(99.0), ms(1));
+        assert_eq!(hud.bytes_per_frame(), 100);
+        assert_eq!(hud.dropped(), WINDOW as u64);
+    }
+
+    #[test]
+    fn test_empty_hud() {
+        let hud = StatsHud::new(0);
+        assert_eq!(hud.fps(), 0.0);
+        assert_eq!(hud.percentile(95.0), Duration::ZERO);
+        assert_eq!(hud.bytes_per_frame(), 0);
+    }
+
+    #[test]
+    fn test_lines_and_render() {
+        let mut hud = StatsHud::new(30);
+        hud.record(Duration::from_micros(2500), ms(40), 1234);
+        assert_eq!(
+            hud.lines(),
+            [
+                "fps      25.0",
+                "p50     2.5ms",
+                "p95     2.5ms",
+                "p99     2.5ms",
+                "dropped     0",
+                "B/frame  1.2k",
+            ]
+        );
+
+        let mut grid = BrailleGrid::new(20, 4).unwrap();
+        hud.render(&mut grid);
+        let row: String = (7..20).map(|x| grid.get_char(x, 3)).collect();
+        assert_eq!(row, "p99     2.5ms");
+        assert_eq!(grid.get_char(6, 0), '⠀');
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(812), "812");
+        assert_eq!(format_bytes(1234), "1.2k");
+        assert_eq!(format_bytes(3_400_000), "3.4M");
+    }
+}
@@ -0,0 +1,105 @@
+//! Tests for the determinism guarantees in the crate docs
+//!
+//! Each test checks one documented ordering or tie-breaking rule, or that
+//! repeating an operation gives identical output, so golden-file tests
+//! downstream stay stable across releases.
+
+use dotmax::analysis::{connected_components, Connectivity};
+use dotmax::animation::dissolve;
+use dotmax::plot::{LineChart, Series};
+use dotmax::primitives::draw_rectangle_filled;
+use dotmax::{BrailleGrid, Color, DirtyRect};
+
+/// Every cell's character and color, row by row
+fn snapshot(grid: &BrailleGrid) -> Vec<(char, Option<Color>)> {
+    let (width, height) = grid.dimensions();
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| (grid.get_char(x, y), grid.get_color(x, y)))
+        .collect()
+}
+
+#[test]
+fn test_regions_in_row_major_order() {
+    let mut grid = BrailleGrid::new(20, 5).unwrap();
+    // Drawn bottom-right first; order must follow position, not drawing
+    draw_rectangle_filled(&mut grid, 30, 12, 4, 4).unwrap();
+    draw_rectangle_filled(&mut grid, 2, 10, 3, 3).unwrap();
+    grid.set_dot(20, 1).unwrap();
+
+    let regions = connected_components(&grid, Connectivity::Eight);
+    let firsts: Vec<_> = regions.iter().map(|region| region.dots[0]).collect();
+    assert_eq!(firsts, [(20, 1), (2, 10), (30, 12)]);
+    for region in &regions {
+        let mut sorted = region.dots.clone();
+        sorted.sort_by_key(|&(x, y)| (y, x));
+        assert_eq!(region.dots, sorted);
+    }
+}
+
+#[test]
+fn test_dirty_rects_top_to_bottom() {
+    let mut grid = BrailleGrid::new(30, 10).unwrap();
+    grid.set_dirty_tracking(true);
+    grid.take_dirty_rects();
+
+    grid.set_dot(40, 32).unwrap(); // cell (20, 8)
+    grid.set_dot(2, 4).unwrap(); // cell (1, 1)
+    grid.set_dot(10, 20).unwrap(); // cell (5, 5)
+    assert_eq!(
+        grid.take_dirty_rects().unwrap(),
+        [
+            DirtyRect {
+                x: 1,
+                y: 1,
+                width: 1,
+                height: 1
+            },
+            DirtyRect {
+                x: 5,
+                y: 5,
+                width: 1,
+                height: 1
+            },
+            DirtyRect {
+                x: 20,
+                y: 8,
+                width: 1,
+                height: 1
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_crowded_chart_labels_repeat_exactly() {
+    let values: Vec<f64> = (0..200).map(|i| f64::from(i).sin() * 1234.5).collect();
+    let render = || {
+        let mut grid = BrailleGrid::new(18, 6).unwrap();
+        LineChart::new()
+            .series(Series::from_values(&values))
+            .render(&mut grid, 0, 0, 18, 6)
+            .unwrap();
+        grid
+    };
+
+    let first = render();
+    for _ in 0..5 {
+        let again = render();
+        assert_eq!(again.get_raw_patterns(), first.get_raw_patterns());
+        assert_eq!(snapshot(&again), snapshot(&first));
+    }
+}
+
+#[test]
+fn test_dissolve_is_reproducible() {
+    let from = BrailleGrid::new(40, 10).unwrap();
+    let mut to = BrailleGrid::new(40, 10).unwrap();
+    draw_rectangle_filled(&mut to, 0, 0, 80, 40).unwrap();
+
+    for progress in [0.1, 0.5, 0.9] {
+        let frame = dissolve(&from, &to, progress).unwrap();
+        let again = dissolve(&from, &to, progress).unwrap();
+        assert_eq!(frame.get_raw_patterns(), again.get_raw_patterns());
+    }
+}
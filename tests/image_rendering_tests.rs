@@ -303,6 +303,44 @@ mod image_pipeline_tests {
         assert!(grid.width() > 0);
         assert!(grid.height() > 0);
     }
+
+    #[test]
+    fn test_renderer_despeckle_removes_isolated_dots() {
+        use dotmax::image::ImageRenderer;
+
+        // Black canvas with a solid white block and three lone white pixels
+        let white = image::Rgba([255, 255, 255, 255]);
+        let mut img = image::RgbaImage::from_pixel(40, 40, image::Rgba([0, 0, 0, 255]));
+        for y in 8..24 {
+            for x in 8..24 {
+                img.put_pixel(x, y, white);
+            }
+        }
+        for &(x, y) in &[(32, 4), (35, 30), (4, 36)] {
+            img.put_pixel(x, y, white);
+        }
+
+        let render = |despeckle: Option<u8>| {
+            let mut renderer = ImageRenderer::new()
+                .load_from_rgba(img.clone())
+                .resize(20, 10, false)
+                .expect("resize")
+                .dithering(DitheringMethod::None)
+                .threshold(128);
+            if let Some(n) = despeckle {
+                renderer = renderer.despeckle(n);
+            }
+            renderer.render().expect("render")
+        };
+        let count = |grid: &dotmax::BrailleGrid| -> u32 {
+            grid.get_raw_patterns().iter().map(|p| p.count_ones()).sum()
+        };
+
+        let plain = render(None);
+        let cleaned = render(Some(1));
+        assert_eq!(count(&plain), 16 * 16 + 3);
+        assert_eq!(count(&cleaned), 16 * 16);
+    }
 }
 
 /// Integration tests for color mode rendering pipeline
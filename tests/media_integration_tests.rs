@@ -527,6 +527,7 @@ mod cross_format_tests {
         let test_cases = [
             ("tests/fixtures/media/animated.gif", MediaFormat::AnimatedGif),
             ("tests/fixtures/media/animated.png", MediaFormat::AnimatedPng),
+            ("tests/fixtures/media/animated.webp", MediaFormat::AnimatedWebp),
         ];
 
         for (path, expected) in test_cases {
@@ -0,0 +1,41 @@
+//! Long-running soak tests, opt-in with `cargo test -- --ignored`
+//!
+//! `DOTMAX_SOAK_SECONDS` sets how long each runs (default one hour).
+
+use std::time::Duration;
+
+use dotmax::animation::AnimationLoop;
+use dotmax::primitives::{draw_circle, draw_line};
+use dotmax::testing::{soak, SoakBudget};
+use dotmax::Color;
+
+fn soak_duration() -> Duration {
+    let seconds = std::env::var("DOTMAX_SOAK_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(60 * 60);
+    Duration::from_secs(seconds)
+}
+
+#[test]
+#[ignore = "runs for an hour by default"]
+fn test_colored_animation_soak() {
+    let mut animation = AnimationLoop::new(120, 40).fps(30).on_frame(|frame, grid| {
+        // The same amount of drawing every frame, so only leaks show up as drift
+        let y = (frame % 160) as i32;
+        draw_line(grid, 0, y, 239, 159 - y)?;
+        draw_circle(grid, (frame % 200) as i32 + 20, 80, 20)?;
+        #[allow(clippy::cast_possible_truncation)]
+        let shade = (frame % 256) as u8;
+        grid.set_cell_color(
+            (frame % 120) as usize,
+            20,
+            Color::rgb(shade, 0, 255 - shade),
+        )?;
+        Ok(true)
+    });
+
+    let report = soak(&mut animation, soak_duration()).unwrap();
+    println!("{report}");
+    report.assert_within(&SoakBudget::default());
+}